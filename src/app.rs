@@ -2,6 +2,7 @@ use std::{
     cmp::{max, min},
     collections::HashMap,
     path::PathBuf,
+    sync::mpsc,
     time::Instant,
 };
 
@@ -11,49 +12,92 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use typed_builder::*;
 
 use data_farmer::*;
-use data_harvester::{processes, temperature};
+use data_harvester::{disks, processes, temperature};
+pub use filter::Filter;
 use layout_manager::*;
+use process_actions::ProcessAction;
+use remote_hosts::get_host_data;
 pub use states::*;
 
 use crate::{
-    canvas, constants,
+    canvas::{self, snapshot::SnapshotFormat, ColourScheme},
+    constants,
+    export::{
+        self, history::HistoryFormat, influxdb::InfluxDbOptions, metrics_log::MetricsLogOptions,
+    },
+    options::layout_options::*,
     options::Config,
     options::ConfigFlags,
+    options::IgnoreList,
+    options::LayoutPageConfig,
+    options::ProcessColumnConfig,
+    options::SortConfig,
     options::WidgetIdEnabled,
+    recording::ReplayControl,
+    remote::server::ServerTlsOptions,
     units::data_units::DataUnit,
     utils::error::{BottomError, Result},
     Pid,
 };
 
+pub mod alert_commands;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod filter;
 pub mod layout_manager;
-mod process_killer;
+pub mod metric_alerts;
+pub mod notifications;
+pub mod process_actions;
+pub mod process_alerts;
 pub mod query;
+pub mod remote_hosts;
 pub mod states;
 
 const MAX_SEARCH_LENGTH: usize = 200;
 
+/// How many seconds the keys that seek through a replay move by.
+const REPLAY_SEEK_SECONDS: i64 = 10;
+
 #[derive(Debug, Clone)]
 pub enum AxisScaling {
     Log,
     Linear,
 }
 
+/// The range of a y-axis, which either fits the data or goes up to a set maximum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisRange {
+    Auto,
+    Fixed(f64),
+}
+
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
 #[derive(Debug)]
 pub struct AppConfigFields {
     pub update_rate_in_milliseconds: u64,
     pub temperature_type: temperature::TemperatureType,
-    pub use_dot: bool,
+    /// How the graphs of widgets that do not set their own style are drawn.
+    pub graph_style: GraphStyle,
+    /// Whether the graphs of widgets that do not set it themselves are filled.
+    pub is_filled_graph: bool,
     pub left_legend: bool,
     pub show_average_cpu: bool,
     pub use_current_cpu_total: bool,
+    pub cpu_frequency_graph: bool,
+    pub show_process_fds: bool,
+    pub show_process_memory_breakdown: bool,
+    pub show_process_latency: bool,
+    pub show_process_security: bool,
+    pub show_process_io_priority: bool,
     pub use_basic_mode: bool,
+    /// The most rows the CPU bars may take in basic mode, if limited; more columns are used instead.
+    pub basic_cpu_rows: Option<u16>,
     pub default_time_value: u64,
     pub time_interval: u64,
     pub hide_time: bool,
+    /// Whether to draw gridlines and labels at round times along the x-axis of graphs.
+    pub show_time_gridlines: bool,
     pub autohide_time: bool,
     pub use_old_network_legend: bool,
     pub table_gap: u16,
@@ -61,10 +105,64 @@ pub struct AppConfigFields {
     pub no_write: bool,
     pub show_table_scroll_position: bool,
     pub is_advanced_kill: bool,
+    pub use_disk_smart: bool,
+    pub use_disk_inodes: bool,
+    pub use_disk_latency: bool,
+    pub use_ipmi: bool,
+    pub kubelet_url: Option<String>,
+    pub kubelet_insecure: bool,
+    pub privilege_helper: Option<String>,
+    pub network_highlight_errors: bool,
+    pub use_network_wireless: bool,
+    pub use_network_connections: bool,
+    pub nut_server: Option<String>,
+    /// The address to serve the harvested metrics to Prometheus at, if any.
+    pub prometheus_address: Option<String>,
+    /// Where and how often to push the harvested metrics in the InfluxDB line protocol, if at all.
+    pub influxdb: Option<InfluxDbOptions>,
+    /// Where and how to log the harvested metrics on every refresh, if at all.
+    pub metrics_log: Option<MetricsLogOptions>,
+    /// The address to serve the harvested data to remote clients at, if any.
+    pub serve_address: Option<String>,
+    /// The certificate and key to serve remote clients over TLS with, if any.
+    pub serve_tls: Option<ServerTlsOptions>,
+    /// The address of the remote server to show the data of, rather than the local host's, if any.
+    pub connect_address: Option<String>,
+    /// The token that remote clients have to send to the server to be accepted.
+    pub remote_token: Option<String>,
+    /// The command that `--ssh` runs on the remote host to serve its data over stdin and stdout.
+    pub ssh_command: String,
+    /// The file to record every harvest to, if any.
+    pub record_path: Option<PathBuf>,
+    /// The recording to replay in place of the data of the local host, if any.
+    pub replay_path: Option<PathBuf>,
+    /// The format that histories are exported in.
+    pub history_export_format: HistoryFormat,
+    /// The directory that histories are exported to.
+    pub history_export_dir: PathBuf,
+    /// The format that snapshots of the screen are saved in.
+    pub snapshot_format: SnapshotFormat,
+    /// The directory that snapshots of the screen are saved to.
+    pub snapshot_dir: PathBuf,
+    pub temp_names: HashMap<String, String>,
+    /// The column that disk tables start out sorted by, and whether it is descending.  Unsorted if
+    /// not set.
+    pub disk_sorting: Option<(disks::DiskSorting, bool)>,
+    /// The column to sort the temperature table by, and whether it is descending.  Unsorted if not
+    /// set.
+    pub temp_sorting: Option<(temperature::TempSorting, bool)>,
+    /// The refresh rates in milliseconds of the widgets that are not refreshed at
+    /// `update_rate_in_milliseconds`.
+    pub refresh_rates: HashMap<BottomWidgetType, u64>,
     // TODO: Remove these, move network details state-side.
     pub network_unit_type: DataUnit,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
+    /// The y-axis range of network graphs with a linear scale, where a fixed maximum is in bits
+    /// per second.
+    pub network_y_range: AxisRange,
+    /// The y-axis range of memory graphs, where a fixed maximum is a percentage.
+    pub memory_y_range: AxisRange,
 }
 
 /// For filtering out information
@@ -74,12 +172,59 @@ pub struct DataFilters {
     pub mount_filter: Option<Filter>,
     pub temp_filter: Option<Filter>,
     pub net_filter: Option<Filter>,
+    pub interface_filter: Option<Filter>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Filter {
-    pub is_list_ignored: bool,
-    pub list: Vec<regex::Regex>,
+/// The data filters that can be edited while running, in the filters dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFilterKind {
+    Mount,
+    Temp,
+    Net,
+}
+
+impl DataFilterKind {
+    /// The name of the filter in the config file.
+    pub fn get_config_name(&self) -> &'static str {
+        match self {
+            DataFilterKind::Mount => "mount_filter",
+            DataFilterKind::Temp => "temp_filter",
+            DataFilterKind::Net => "net_filter",
+        }
+    }
+
+    /// What the filter hides, for the title of the filters dialog.
+    pub fn get_entry_name(&self) -> &'static str {
+        match self {
+            DataFilterKind::Mount => "Mounts",
+            DataFilterKind::Temp => "Sensors",
+            DataFilterKind::Net => "Interfaces",
+        }
+    }
+
+    pub fn get_filter<'a>(&self, filters: &'a DataFilters) -> &'a Option<Filter> {
+        match self {
+            DataFilterKind::Mount => &filters.mount_filter,
+            DataFilterKind::Temp => &filters.temp_filter,
+            DataFilterKind::Net => &filters.net_filter,
+        }
+    }
+
+    fn get_filter_mut<'a>(&self, filters: &'a mut DataFilters) -> &'a mut Option<Filter> {
+        match self {
+            DataFilterKind::Mount => &mut filters.mount_filter,
+            DataFilterKind::Temp => &mut filters.temp_filter,
+            DataFilterKind::Net => &mut filters.net_filter,
+        }
+    }
+
+    fn get_ignore_list_mut<'a>(&self, config: &'a mut Config) -> &'a mut Option<IgnoreList> {
+        match self {
+            DataFilterKind::Mount => &mut config.mount_filter,
+            DataFilterKind::Temp => &mut config.temp_filter,
+            DataFilterKind::Net => &mut config.net_filter,
+        }
+    }
 }
 
 #[derive(TypedBuilder)]
@@ -96,9 +241,20 @@ pub struct App {
     #[builder(default, setter(skip))]
     to_delete_process_list: Option<(String, Vec<Pid>)>,
 
+    /// Receives the number of processes killed, or the error, from a kill retried with the
+    /// privilege helper.
+    #[builder(default, setter(skip))]
+    privileged_kill_receiver: Option<mpsc::Receiver<Result<usize>>>,
+
+    #[builder(default, setter(skip))]
+    toast: Option<AppToast>,
+
     #[builder(default = false, setter(skip))]
     pub is_frozen: bool,
 
+    #[builder(default, setter(skip))]
+    history_drag: Option<HistoryDrag>,
+
     #[builder(default = Instant::now(), setter(skip))]
     last_key_press: Instant,
 
@@ -114,6 +270,48 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
+    #[builder(default, setter(skip))]
+    pub thread_dialog_state: AppThreadDialogState,
+
+    #[builder(default, setter(skip))]
+    pub process_details_dialog_state: AppProcessDetailsDialogState,
+
+    #[builder(default, setter(skip))]
+    pub open_files_dialog_state: AppOpenFilesDialogState,
+
+    #[builder(default, setter(skip))]
+    pub renice_dialog_state: AppReniceDialogState,
+
+    #[builder(default, setter(skip))]
+    pub io_priority_dialog_state: AppIoPriorityDialogState,
+
+    #[builder(default, setter(skip))]
+    pub affinity_dialog_state: AppAffinityDialogState,
+
+    #[builder(default, setter(skip))]
+    pub columns_dialog_state: AppColumnsDialogState,
+
+    #[builder(default, setter(skip))]
+    pub filters_dialog_state: AppFiltersDialogState,
+
+    /// Whether the data filters have been edited since the collection thread was last told.
+    #[builder(default = false, setter(skip))]
+    pub is_filters_outdated: bool,
+
+    /// How a key has asked to move through the recording being replayed, which the replay thread
+    /// has not been told of yet.
+    #[builder(default, setter(skip))]
+    pub replay_control: Option<ReplayControl>,
+
+    /// Whether a snapshot of the screen should be saved once the next frame is drawn.
+    #[builder(default = false, setter(skip))]
+    pub is_snapshot_requested: bool,
+
+    #[builder(default, setter(skip))]
+    pub layout_edit_state: AppLayoutEditState,
+
+    pub colour_scheme_state: AppColourSchemeState,
+
     #[builder(default = false, setter(skip))]
     pub is_expanded: bool,
 
@@ -143,21 +341,45 @@ pub struct App {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: BatteryState,
+    pub conn_state: ConnState,
+    pub users_state: UsersState,
+    pub activity_state: ActivityState,
+    pub pods_state: PodsState,
+    pub vm_state: VmState,
+    pub numa_state: NumaState,
+    pub units_state: UnitsState,
+    pub kernel_log_state: KernelLogState,
+    pub alerts_state: AlertsState,
+    pub power_state: PowerState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
     pub current_widget: BottomWidget,
     pub used_widgets: UsedWidgets,
+    pub process_alerts: process_alerts::ProcessAlerts,
+    pub metric_alerts: metric_alerts::MetricAlerts,
+    /// The remote hosts that CPU and memory widgets can be assigned to.
+    pub remote_hosts: Vec<remote_hosts::RemoteHost>,
     pub filters: DataFilters,
     pub config: Config,
     pub config_path: Option<PathBuf>,
+    /// The queries previously searched for in the process widgets.
+    pub search_history: SearchHistory,
+    /// The process searches saved in the config, which can be cycled through.
+    pub process_filters: Vec<SavedProcessFilter>,
+    pub global_filter: GlobalFilterState,
+    /// The named layouts that can be switched between.  Empty in basic mode.
+    pub layout_pages: Vec<LayoutPage>,
+    #[builder(default, setter(skip))]
+    pub current_layout_page: usize,
+    pub proc_widget_defaults: ProcWidgetDefaults,
 }
 
 #[cfg(target_os = "windows")]
 const MAX_SIGNAL: usize = 1;
 #[cfg(target_os = "linux")]
 const MAX_SIGNAL: usize = 64;
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
 const MAX_SIGNAL: usize = 31;
 
 impl App {
@@ -168,6 +390,13 @@ impl App {
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
         self.delete_dialog_state.is_showing_dd = false;
+        self.close_thread_dialog();
+        self.close_process_details_dialog();
+        self.close_open_files_dialog();
+        self.close_renice_dialog();
+        self.close_io_priority_dialog();
+        self.close_affinity_dialog();
+        self.close_columns_dialog();
 
         // Close all searches and reset it
         self.proc_state
@@ -192,18 +421,1585 @@ impl App {
 
         // Reset data
         self.data_collection.reset();
+        for host in &mut self.remote_hosts {
+            host.data_collection.reset();
+        }
     }
 
     pub fn should_get_widget_bounds(&self) -> bool {
         self.is_force_redraw || self.is_determining_widget_boundary
     }
 
-    fn close_dd(&mut self) {
-        self.delete_dialog_state.is_showing_dd = false;
-        self.delete_dialog_state.selected_signal = KillSignal::default();
-        self.delete_dialog_state.scroll_pos = 0;
-        self.to_delete_process_list = None;
-        self.dd_err = None;
+    fn close_dd(&mut self) {
+        self.delete_dialog_state.is_showing_dd = false;
+        self.delete_dialog_state.selected_signal = KillSignal::default();
+        self.delete_dialog_state.scroll_pos = 0;
+        self.delete_dialog_state.is_searching_signals = false;
+        self.delete_dialog_state.signal_search_query.clear();
+        self.delete_dialog_state.is_killing_tree = false;
+        self.delete_dialog_state.is_killing_tagged = false;
+        self.delete_dialog_state.failed_action = ProcessAction::default();
+        self.delete_dialog_state.privileged_retry = None;
+        self.to_delete_process_list = None;
+        self.dd_err = None;
+    }
+
+    /// Shows a toast with `text`, replacing any current toast.
+    /// Returns whether processes can be acted on, showing why not if they cannot.  They cannot be
+    /// while showing the data of a remote host, as actions would apply to the local host instead.
+    fn can_act_on_processes(&mut self) -> bool {
+        if self.app_config_fields.connect_address.is_some() {
+            self.show_toast(
+                "Processes cannot be acted on while showing a remote host.".to_string(),
+                true,
+            );
+            false
+        } else if self.app_config_fields.replay_path.is_some() {
+            self.show_toast(
+                "Processes cannot be acted on while replaying a recording.".to_string(),
+                true,
+            );
+            false
+        } else {
+            true
+        }
+    }
+
+    pub fn show_toast(&mut self, text: String, is_error: bool) {
+        self.toast = Some(AppToast {
+            text,
+            is_error,
+            created_instant: Instant::now(),
+        });
+    }
+
+    /// Returns the current toast, if there is one and it has not timed out yet.
+    pub fn get_toast(&self) -> Option<&AppToast> {
+        self.toast.as_ref().filter(|toast| {
+            toast.created_instant.elapsed().as_millis()
+                < constants::TOAST_TIMEOUT_MILLISECONDS.into()
+        })
+    }
+
+    /// Retries the kill that failed because of missing permissions with the privilege helper.
+    /// The helper may wait for the user to authenticate, so it is run on a separate thread, and the
+    /// result is reported in a toast once it is done.
+    #[cfg(target_family = "unix")]
+    fn retry_kill_with_privilege_helper(&mut self) {
+        if let (Some((pids, signal)), Some(helper)) = (
+            self.delete_dialog_state.privileged_retry.take(),
+            self.app_config_fields.privilege_helper.clone(),
+        ) {
+            let (sender, receiver) = mpsc::channel();
+            self.show_toast(format!("Retrying with {}...", helper), false);
+            std::thread::spawn(move || {
+                let result = process_actions::kill_processes_with_helper(&helper, &pids, signal)
+                    .map(|_| pids.len());
+                sender.send(result).ok();
+            });
+            self.privileged_kill_receiver = Some(receiver);
+        }
+    }
+
+    /// Checks whether a kill retried with the privilege helper has finished, and if so, reports
+    /// the result in a toast.
+    pub fn poll_privileged_kill(&mut self) {
+        if let Some(receiver) = &self.privileged_kill_receiver {
+            match receiver.try_recv() {
+                Ok(Ok(num_killed)) => {
+                    self.privileged_kill_receiver = None;
+                    self.show_toast(
+                        if num_killed == 1 {
+                            "Killed 1 process.".to_string()
+                        } else {
+                            format!("Killed {} processes.", num_killed)
+                        },
+                        false,
+                    );
+                }
+                Ok(Err(err)) => {
+                    self.privileged_kill_receiver = None;
+                    self.show_toast(format!("Failed to kill process: {}", err), true);
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.privileged_kill_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+    }
+
+    /// Opens a dialog listing the threads of the currently selected process.
+    pub fn open_thread_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get(&self.current_widget.widget_id)
+        {
+            // Threads only make sense for a single process.
+            if proc_widget_state.is_grouped {
+                return;
+            }
+
+            if let Some(process) = self
+                .canvas_data
+                .finalized_process_data_map
+                .get(&self.current_widget.widget_id)
+                .and_then(|process_list| {
+                    process_list.get(proc_widget_state.scroll_state.current_scroll_position)
+                })
+            {
+                self.thread_dialog_state.is_showing_threads = true;
+                self.thread_dialog_state.process = Some((process.pid, process.name.clone()));
+                self.thread_dialog_state.scroll_state = AppScrollWidgetState::default();
+                self.thread_dialog_state.is_pid_outdated = true;
+                self.data_collection.thread_harvest.clear();
+                self.canvas_data.thread_data.clear();
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    /// Opens a dialog to set an exact nice value for the currently selected process(es).
+    pub fn open_renice_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let current_nice = process
+                .1
+                .first()
+                .and_then(|pid| process_actions::get_process_priority(*pid).ok());
+
+            self.renice_dialog_state.is_showing_renice = true;
+            self.renice_dialog_state.nice_input = current_nice
+                .map(|nice| nice.to_string())
+                .unwrap_or_default();
+            self.renice_dialog_state.current_nice = current_nice;
+            self.renice_dialog_state.process = Some(process);
+            self.renice_dialog_state.error = None;
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_renice_dialog(&mut self) {
+        self.renice_dialog_state = AppReniceDialogState::default();
+    }
+
+    /// Opens a dialog to change the global filter, starting with its current query.
+    pub fn open_global_filter_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        self.global_filter.is_editing = true;
+        self.global_filter.input = self.global_filter.query_text.clone();
+        self.global_filter.error = None;
+        self.is_force_redraw = true;
+    }
+
+    fn close_global_filter_dialog(&mut self) {
+        self.global_filter.is_editing = false;
+        self.global_filter.input.clear();
+        self.global_filter.error = None;
+    }
+
+    /// Applies the query typed into the global filter dialog, closing it on success or showing
+    /// why the query is invalid otherwise.
+    fn apply_global_filter_dialog(&mut self) {
+        let input = self.global_filter.input.clone();
+        if let Err(err) = self.global_filter.set_query(&input) {
+            self.global_filter.error = Some(err.to_string());
+            return;
+        }
+        self.close_global_filter_dialog();
+
+        // Processes have to be collected for the filter to apply, even without process widgets.
+        if self.global_filter.query.is_some() && !self.used_widgets.use_proc {
+            self.used_widgets.use_proc = true;
+            self.layout_edit_state.is_used_widgets_outdated = true;
+        }
+        self.proc_state.force_update_all = true;
+    }
+
+    fn on_renice_char(&mut self, caught_char: char) {
+        let nice_input = &mut self.renice_dialog_state.nice_input;
+        match caught_char {
+            '-' if nice_input.is_empty() => nice_input.push('-'),
+            '0'..='9' if nice_input.trim_start_matches('-').len() < 2 => {
+                nice_input.push(caught_char)
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies the nice value typed into the renice dialog, closing it on success or showing the
+    /// error in it otherwise.
+    fn apply_renice_dialog(&mut self) {
+        let nice = match self.renice_dialog_state.nice_input.parse::<i32>() {
+            Ok(nice) if process_actions::NICE_RANGE.contains(&nice) => nice,
+            _ => {
+                self.renice_dialog_state.error = Some(format!(
+                    "The nice value must be between {} and {}.",
+                    process_actions::NICE_RANGE.start(),
+                    process_actions::NICE_RANGE.end()
+                ));
+                return;
+            }
+        };
+
+        if let Some((_name, pids)) = &self.renice_dialog_state.process {
+            let result = pids
+                .iter()
+                .try_for_each(|pid| process_actions::set_process_priority(*pid, nice));
+
+            match result {
+                Ok(()) => self.close_renice_dialog(),
+                Err(err) => self.renice_dialog_state.error = Some(err.to_string()),
+            }
+        }
+    }
+
+    /// Changes the nice value of the selected process(es) by `delta`, showing an error dialog on
+    /// failure.  A negative `delta` raises the priority.
+    pub fn change_selected_process_priority(&mut self, delta: i32) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some((_name, pids)) = self.get_selected_process() {
+            let result = pids.into_iter().try_for_each(|pid| {
+                let nice = process_actions::get_process_priority(pid)?;
+                let new_nice = (nice + delta).clamp(
+                    *process_actions::NICE_RANGE.start(),
+                    *process_actions::NICE_RANGE.end(),
+                );
+                process_actions::set_process_priority(pid, new_nice)
+            });
+
+            if let Err(err) = result {
+                self.dd_err = Some(err.to_string());
+                self.delete_dialog_state.failed_action = ProcessAction::Renice;
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_determining_widget_boundary = true;
+            }
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Opens a dialog to set the I/O scheduling class and priority of the currently selected
+    /// process(es).
+    pub fn open_io_priority_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let priority = match process.1.first() {
+                Some(pid) => process_actions::get_process_io_priority(*pid),
+                None => Ok(processes::IoPriority::default()),
+            };
+
+            self.io_priority_dialog_state = AppIoPriorityDialogState::default();
+            self.io_priority_dialog_state.is_showing_io_priority = true;
+            match priority {
+                Ok(priority) => self.io_priority_dialog_state.priority = priority,
+                Err(err) => self.io_priority_dialog_state.error = Some(err.to_string()),
+            }
+            self.io_priority_dialog_state.process = Some(process);
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_io_priority_dialog(&mut self) {
+        self.io_priority_dialog_state = AppIoPriorityDialogState::default();
+    }
+
+    /// Moves to the next or previous I/O scheduling class in the I/O priority dialog.
+    fn change_io_priority_class(&mut self, num_to_change_by: i64) {
+        let priority = &mut self.io_priority_dialog_state.priority;
+        let classes = &processes::IoPriorityClass::ALL;
+        if let Some(current_posn) = classes.iter().position(|class| *class == priority.class) {
+            let new_posn = (current_posn as i64 + num_to_change_by)
+                .clamp(0, classes.len() as i64 - 1) as usize;
+            priority.class = classes[new_posn];
+        }
+    }
+
+    /// Changes the level in the I/O priority dialog, if the selected class has levels.  A
+    /// negative `num_to_change_by` raises the priority.
+    fn change_io_priority_level(&mut self, num_to_change_by: i64) {
+        let priority = &mut self.io_priority_dialog_state.priority;
+        if priority.class.has_levels() {
+            priority.level = (i64::from(priority.level) + num_to_change_by)
+                .clamp(0, processes::IoPriority::MAX_LEVEL.into())
+                as u8;
+        }
+    }
+
+    fn on_io_priority_char(&mut self, caught_char: char) {
+        match caught_char {
+            'h' => self.change_io_priority_class(-1),
+            'l' => self.change_io_priority_class(1),
+            'k' => self.change_io_priority_level(-1),
+            'j' => self.change_io_priority_level(1),
+            _ => {}
+        }
+    }
+
+    /// Applies the I/O priority chosen in the I/O priority dialog, closing it on success or
+    /// showing the error in it otherwise.
+    fn apply_io_priority_dialog(&mut self) {
+        let priority = self.io_priority_dialog_state.priority;
+        if let Some((_name, pids)) = &self.io_priority_dialog_state.process {
+            let result = pids
+                .iter()
+                .try_for_each(|pid| process_actions::set_process_io_priority(*pid, priority));
+
+            match result {
+                Ok(()) => self.close_io_priority_dialog(),
+                Err(err) => self.io_priority_dialog_state.error = Some(err.to_string()),
+            }
+        }
+    }
+
+    /// Opens a dialog to choose which CPUs the currently selected process(es) may run on.
+    pub fn open_affinity_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let cpus = match process.1.first() {
+                Some(pid) => process_actions::get_process_affinity(*pid),
+                None => Ok(Vec::new()),
+            };
+
+            self.affinity_dialog_state = AppAffinityDialogState::default();
+            self.affinity_dialog_state.is_showing_affinity = true;
+            match cpus {
+                Ok(cpus) => self.affinity_dialog_state.cpus = cpus,
+                Err(err) => self.affinity_dialog_state.error = Some(err.to_string()),
+            }
+            self.affinity_dialog_state.process = Some(process);
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_affinity_dialog(&mut self) {
+        self.affinity_dialog_state = AppAffinityDialogState::default();
+    }
+
+    fn change_affinity_position(&mut self, num_to_change_by: i64) {
+        let scroll_state = &mut self.affinity_dialog_state.scroll_state;
+        let num_cpus = self.affinity_dialog_state.cpus.len();
+        let current_posn = scroll_state.current_scroll_position;
+
+        if current_posn as i64 + num_to_change_by >= 0
+            && current_posn as i64 + num_to_change_by < num_cpus as i64
+        {
+            scroll_state.current_scroll_position =
+                (current_posn as i64 + num_to_change_by) as usize;
+        }
+
+        if num_to_change_by < 0 {
+            scroll_state.scroll_direction = ScrollDirection::Up;
+        } else {
+            scroll_state.scroll_direction = ScrollDirection::Down;
+        }
+    }
+
+    fn on_affinity_char(&mut self, caught_char: char) {
+        match caught_char {
+            'j' => self.change_affinity_position(1),
+            'k' => self.change_affinity_position(-1),
+            ' ' => {
+                let affinity_state = &mut self.affinity_dialog_state;
+                if let Some(is_allowed) = affinity_state
+                    .cpus
+                    .get_mut(affinity_state.scroll_state.current_scroll_position)
+                {
+                    *is_allowed = !*is_allowed;
+                }
+            }
+            'a' => {
+                // Check every CPU, unless they are all checked already.
+                let cpus = &mut self.affinity_dialog_state.cpus;
+                let new_value = !cpus.iter().all(|is_allowed| *is_allowed);
+                cpus.iter_mut()
+                    .for_each(|is_allowed| *is_allowed = new_value);
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies the CPUs checked in the affinity dialog, closing it on success or showing the
+    /// error in it otherwise.
+    fn apply_affinity_dialog(&mut self) {
+        let cpus = &self.affinity_dialog_state.cpus;
+        if !cpus.iter().any(|is_allowed| *is_allowed) {
+            self.affinity_dialog_state.error =
+                Some("At least one CPU must be selected.".to_string());
+            return;
+        }
+
+        if let Some((_name, pids)) = &self.affinity_dialog_state.process {
+            let result = pids
+                .iter()
+                .try_for_each(|pid| process_actions::set_process_affinity(*pid, cpus));
+
+            match result {
+                Ok(()) => self.close_affinity_dialog(),
+                Err(err) => self.affinity_dialog_state.error = Some(err.to_string()),
+            }
+        }
+    }
+
+    /// Opens a dialog to choose the columns of the current process widget.
+    pub fn open_columns_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.columns_dialog_state = AppColumnsDialogState::default();
+            self.columns_dialog_state.is_showing_columns = true;
+            self.columns_dialog_state.widget_id = self.current_widget.widget_id;
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_columns_dialog(&mut self) {
+        self.columns_dialog_state = AppColumnsDialogState::default();
+    }
+
+    /// Returns the column selected in the columns dialog.
+    fn get_columns_dialog_column(&self) -> Option<processes::ProcessSorting> {
+        self.proc_state
+            .widget_states
+            .get(&self.columns_dialog_state.widget_id)
+            .and_then(|proc_widget_state| {
+                proc_widget_state
+                    .columns
+                    .get_choosable_columns()
+                    .get(
+                        self.columns_dialog_state
+                            .scroll_state
+                            .current_scroll_position,
+                    )
+                    .cloned()
+            })
+    }
+
+    fn change_columns_position(&mut self, num_to_change_by: i64) {
+        let num_columns = match self
+            .proc_state
+            .widget_states
+            .get(&self.columns_dialog_state.widget_id)
+        {
+            Some(proc_widget_state) => proc_widget_state.columns.get_choosable_columns().len(),
+            None => 0,
+        };
+        let scroll_state = &mut self.columns_dialog_state.scroll_state;
+        let current_posn = scroll_state.current_scroll_position;
+
+        if current_posn as i64 + num_to_change_by >= 0
+            && current_posn as i64 + num_to_change_by < num_columns as i64
+        {
+            scroll_state.current_scroll_position =
+                (current_posn as i64 + num_to_change_by) as usize;
+        }
+
+        if num_to_change_by < 0 {
+            scroll_state.scroll_direction = ScrollDirection::Up;
+        } else {
+            scroll_state.scroll_direction = ScrollDirection::Down;
+        }
+    }
+
+    /// Applies `change` to the column selected in the columns dialog, then redraws the process
+    /// widget with the new columns.
+    fn change_columns_dialog_column(
+        &mut self, change: impl FnOnce(&mut ProcColumn, &processes::ProcessSorting),
+    ) {
+        let widget_id = self.columns_dialog_state.widget_id;
+        if let Some(column) = self.get_columns_dialog_column() {
+            if let Some(proc_widget_state) = self.proc_state.widget_states.get_mut(&widget_id) {
+                change(&mut proc_widget_state.columns, &column);
+                proc_widget_state
+                    .columns
+                    .set_to_sorted_index_from_type(&proc_widget_state.process_sorting_type);
+                proc_widget_state.requires_redraw = true;
+                self.proc_state.force_update = Some(widget_id);
+            }
+        }
+    }
+
+    fn on_columns_char(&mut self, caught_char: char) {
+        match caught_char {
+            'j' => self.change_columns_position(1),
+            'k' => self.change_columns_position(-1),
+            ' ' => self.change_columns_dialog_column(|columns, column| {
+                columns.toggle_hidden(column);
+            }),
+            '[' => {
+                self.change_columns_dialog_column(|columns, column| {
+                    columns.move_column(column, -1)
+                });
+                self.change_columns_position(-1);
+            }
+            ']' => {
+                self.change_columns_dialog_column(|columns, column| columns.move_column(column, 1));
+                self.change_columns_position(1);
+            }
+            '+' => self.change_columns_dialog_column(|columns, column| {
+                columns.change_column_width(column, 1)
+            }),
+            '-' => self.change_columns_dialog_column(|columns, column| {
+                columns.change_column_width(column, -1)
+            }),
+            '=' => self.change_columns_dialog_column(|columns, column| {
+                columns.column_widths.remove(column);
+            }),
+            'w' => self.save_process_columns(),
+            _ => {}
+        }
+    }
+
+    /// Saves the columns of the process widget in the columns dialog to the config file, showing
+    /// the result in the dialog.
+    fn save_process_columns(&mut self) {
+        let columns = match self
+            .proc_state
+            .widget_states
+            .get(&self.columns_dialog_state.widget_id)
+        {
+            Some(proc_widget_state) => proc_widget_state.columns.get_columns(),
+            None => return,
+        };
+
+        let save_result = self.write_config(|config| {
+            config.process_column = Some(get_process_column_configs(&columns));
+        });
+
+        self.columns_dialog_state.save_result = Some(save_result);
+    }
+
+    /// Opens a dialog to hide or show the mounts, sensors, or interfaces of the current disk,
+    /// temperature, or network widget, by editing the filter for them.
+    pub fn open_filters_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        let kind = match self.current_widget.widget_type {
+            BottomWidgetType::Disk => DataFilterKind::Mount,
+            BottomWidgetType::Temp => DataFilterKind::Temp,
+            BottomWidgetType::Net | BottomWidgetType::BasicNet => DataFilterKind::Net,
+            _ => return,
+        };
+
+        // Hidden entries are no longer collected, so those named in the filter are listed too.
+        let mut entries: Vec<String> = match kind {
+            DataFilterKind::Mount => self
+                .data_collection
+                .disk_harvest
+                .iter()
+                .map(|disk| disk.mount_point.clone())
+                .collect(),
+            DataFilterKind::Temp => self
+                .data_collection
+                .temp_harvest
+                .iter()
+                .map(|sensor| sensor.name.clone())
+                .collect(),
+            DataFilterKind::Net => self
+                .data_collection
+                .network_harvest
+                .interfaces
+                .iter()
+                .map(|interface| interface.name.clone())
+                .collect(),
+        };
+        if let Some(ignore_list) = kind.get_ignore_list_mut(&mut self.config) {
+            entries.extend(
+                ignore_list
+                    .list
+                    .iter()
+                    .filter_map(|list_entry| get_filters_dialog_name(ignore_list, list_entry)),
+            );
+        }
+        entries.sort();
+        entries.dedup();
+
+        self.filters_dialog_state = AppFiltersDialogState {
+            is_showing_filters: true,
+            kind: Some(kind),
+            entries,
+            ..AppFiltersDialogState::default()
+        };
+        self.is_force_redraw = true;
+    }
+
+    fn close_filters_dialog(&mut self) {
+        self.filters_dialog_state = AppFiltersDialogState::default();
+    }
+
+    fn change_filters_position(&mut self, num_to_change_by: i64) {
+        let num_entries = self.filters_dialog_state.entries.len();
+        let scroll_state = &mut self.filters_dialog_state.scroll_state;
+        let current_posn = scroll_state.current_scroll_position;
+
+        if current_posn as i64 + num_to_change_by >= 0
+            && current_posn as i64 + num_to_change_by < num_entries as i64
+        {
+            scroll_state.current_scroll_position =
+                (current_posn as i64 + num_to_change_by) as usize;
+        }
+
+        if num_to_change_by < 0 {
+            scroll_state.scroll_direction = ScrollDirection::Up;
+        } else {
+            scroll_state.scroll_direction = ScrollDirection::Down;
+        }
+    }
+
+    /// Hides the entry selected in the filters dialog if it is shown, or shows it if it is hidden,
+    /// by adding it to or removing it from the list of the filter.  The collection thread is then
+    /// told about the new filter, which is only saved to the config file with `w`.
+    fn toggle_filters_dialog_entry(&mut self) {
+        let kind = match self.filters_dialog_state.kind {
+            Some(kind) => kind,
+            None => return,
+        };
+        let name = match self.filters_dialog_state.entries.get(
+            self.filters_dialog_state
+                .scroll_state
+                .current_scroll_position,
+        ) {
+            Some(name) => name.clone(),
+            None => return,
+        };
+        let is_shown = kind
+            .get_filter(&self.filters)
+            .as_ref()
+            .map_or(true, |filter| filter.is_shown(&name));
+
+        // A new filter matches entries exactly, so that hiding one does not hide others with it.
+        let ignore_list = kind
+            .get_ignore_list_mut(&mut self.config)
+            .get_or_insert_with(|| IgnoreList {
+                is_list_ignored: true,
+                case_sensitive: true,
+                whole_word: true,
+                ..IgnoreList::default()
+            });
+        let list_entry = get_filters_dialog_list_entry(ignore_list, &name);
+        let previous_list = ignore_list.list.clone();
+
+        if is_shown == ignore_list.is_list_ignored {
+            ignore_list.list.push(list_entry);
+        } else {
+            ignore_list.list.retain(|entry| *entry != list_entry);
+        }
+
+        // The entry may still be matched by another pattern, or by the allow or deny lists.
+        let message =
+            match crate::options::get_ignore_list(kind.get_ignore_list_mut(&mut self.config)) {
+                Ok(Some(filter)) if filter.is_shown(&name) != is_shown => {
+                    *kind.get_filter_mut(&mut self.filters) = Some(filter);
+                    self.is_filters_outdated = true;
+                    Ok(format!(
+                        "{} {}.",
+                        if is_shown { "Hid" } else { "Showed" },
+                        name
+                    ))
+                }
+                Ok(_) => Err(format!(
+                "{} is matched by a pattern in {}, which can only be changed in the config file.",
+                name,
+                kind.get_config_name()
+            )),
+                Err(err) => Err(err.to_string()),
+            };
+        if message.is_err() {
+            if let Some(ignore_list) = kind.get_ignore_list_mut(&mut self.config) {
+                ignore_list.list = previous_list;
+            }
+        }
+        self.filters_dialog_state.message = Some(message);
+    }
+
+    fn on_filters_char(&mut self, caught_char: char) {
+        match caught_char {
+            'j' => self.change_filters_position(1),
+            'k' => self.change_filters_position(-1),
+            ' ' => self.toggle_filters_dialog_entry(),
+            'w' => {
+                // The config was already updated along with the filter.
+                let save_result = self.write_config(|_config| {});
+                self.filters_dialog_state.message = Some(save_result);
+            }
+            _ => {}
+        }
+    }
+
+    /// Updates the config with `update` and writes it to the config file, returning what to tell
+    /// the user about how that went.  The config is left as is if it cannot be written.
+    fn write_config(
+        &mut self, update: impl FnOnce(&mut Config),
+    ) -> std::result::Result<String, String> {
+        if self.app_config_fields.no_write {
+            Err("Not saved, as writing to the config file is disabled.".to_string())
+        } else if let Some(config_path) = &self.config_path {
+            update(&mut self.config);
+
+            self.config
+                .get_config_as_bytes()
+                .map_err(|err| err.to_string())
+                .and_then(|config_bytes| {
+                    std::fs::write(config_path, config_bytes).map_err(|err| err.to_string())
+                })
+                .map(|()| format!("Saved to {}.", config_path.display()))
+        } else {
+            Err("Not saved, as there is no config file.".to_string())
+        }
+    }
+
+    /// Saves the layout pages to the config file, along with the sorting, columns, and toggles
+    /// of the selected process widget (or the first one if none is selected) and the search
+    /// settings of every process widget and the current color scheme, so that they are used again
+    /// on the next start.
+    pub fn save_settings(&mut self) {
+        self.reset_multi_tap_keys();
+
+        // Basic mode has no layout of its own to save.
+        let layout_pages = self.layout_pages.clone();
+
+        let selected_proc_widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::Proc | BottomWidgetType::ProcSearch | BottomWidgetType::ProcSort => {
+                Some(
+                    self.current_widget.widget_id
+                        - self.current_widget.widget_type.get_parent_offset(),
+                )
+            }
+            _ => None,
+        };
+        let proc_widget_state = selected_proc_widget_id
+            .and_then(|widget_id| self.proc_state.widget_states.get(&widget_id))
+            .or_else(|| {
+                self.proc_state
+                    .widget_states
+                    .keys()
+                    .min()
+                    .and_then(|widget_id| self.proc_state.widget_states.get(widget_id))
+            });
+        let process_columns = proc_widget_state.map(|proc_widget_state| {
+            get_process_column_configs(&proc_widget_state.columns.get_columns())
+        });
+        let process_sort = proc_widget_state.map(|proc_widget_state| SortConfig {
+            column: proc_widget_state.process_sorting_type.to_string(),
+            descending: Some(proc_widget_state.is_process_sort_descending),
+        });
+        let process_toggles = proc_widget_state.map(|proc_widget_state| {
+            (
+                proc_widget_state.is_tree_mode,
+                proc_widget_state.is_grouped,
+                proc_widget_state.is_using_command,
+                proc_widget_state
+                    .columns
+                    .is_enabled(&processes::ProcessSorting::Mem),
+                proc_widget_state.is_hiding_kernel_threads,
+            )
+        });
+
+        let mut case_sensitive_map = HashMap::new();
+        let mut whole_word_map = HashMap::new();
+        let mut regex_map = HashMap::new();
+        let mut fuzzy_map = HashMap::new();
+        for (widget_id, proc_widget_state) in &self.proc_state.widget_states {
+            let search_state = &proc_widget_state.process_search_state;
+            case_sensitive_map.insert(*widget_id, !search_state.is_ignoring_case);
+            whole_word_map.insert(*widget_id, search_state.is_searching_whole_word);
+            regex_map.insert(*widget_id, search_state.is_searching_with_regex);
+            fuzzy_map.insert(*widget_id, search_state.is_searching_with_fuzzy);
+        }
+
+        // The colours in `[colors]` are used whenever they are set, so there is nothing to pick.
+        let colour_scheme_name = self
+            .colour_scheme_state
+            .get_current()
+            .filter(|colour_scheme| **colour_scheme != ColourScheme::Custom)
+            .map(|colour_scheme| colour_scheme.get_name().to_string());
+
+        let save_result = self.write_config(|config| {
+            if let Some((first_page, other_pages)) = layout_pages.split_first() {
+                config.row = Some(first_page.rows.clone());
+                if !other_pages.is_empty() {
+                    config.layout = Some(
+                        other_pages
+                            .iter()
+                            .map(|layout_page| LayoutPageConfig {
+                                name: layout_page.name.clone(),
+                                row: layout_page.rows.clone(),
+                            })
+                            .collect(),
+                    );
+                }
+            }
+            if process_columns.is_some() {
+                config.process_column = process_columns;
+            }
+            if process_sort.is_some() {
+                config
+                    .default_sort
+                    .get_or_insert_with(Default::default)
+                    .proc = process_sort;
+            }
+
+            let flags = config.flags.get_or_insert_with(ConfigFlags::default);
+            if colour_scheme_name.is_some() {
+                flags.color = colour_scheme_name;
+            }
+            if let Some((is_tree_mode, is_grouped, is_using_command, mem_as_value, hide_kernel)) =
+                process_toggles
+            {
+                flags.tree = Some(is_tree_mode);
+                flags.group_processes = Some(is_grouped);
+                flags.process_command = Some(is_using_command);
+                flags.mem_as_value = Some(mem_as_value);
+                flags.hide_kernel_threads = Some(hide_kernel);
+            }
+
+            if case_sensitive_map.is_empty() {
+                return;
+            }
+
+            // The search settings of each widget are only used if they are not set for all of
+            // them.
+            flags.case_sensitive = None;
+            flags.whole_word = None;
+            flags.regex = None;
+            flags.fuzzy = None;
+            flags.search_case_enabled_widgets =
+                Some(WidgetIdEnabled::create_from_hashmap(&case_sensitive_map));
+            flags.search_case_enabled_widgets_map = Some(case_sensitive_map);
+            flags.search_whole_word_enabled_widgets =
+                Some(WidgetIdEnabled::create_from_hashmap(&whole_word_map));
+            flags.search_whole_word_enabled_widgets_map = Some(whole_word_map);
+            flags.search_regex_enabled_widgets =
+                Some(WidgetIdEnabled::create_from_hashmap(&regex_map));
+            flags.search_regex_enabled_widgets_map = Some(regex_map);
+            flags.search_fuzzy_enabled_widgets =
+                Some(WidgetIdEnabled::create_from_hashmap(&fuzzy_map));
+            flags.search_fuzzy_enabled_widgets_map = Some(fuzzy_map);
+        });
+
+        match save_result {
+            Ok(text) => self.show_toast(text, false),
+            Err(text) => self.show_toast(text, true),
+        }
+    }
+
+    /// Switches to the next colour scheme, which is applied the next time the canvas is drawn.
+    pub fn cycle_colour_scheme(&mut self) {
+        self.reset_multi_tap_keys();
+
+        let colour_scheme_state = &mut self.colour_scheme_state;
+        if colour_scheme_state.colour_schemes.len() <= 1 {
+            self.show_toast("There are no other color schemes.".to_string(), true);
+            return;
+        }
+        colour_scheme_state.current_index =
+            (colour_scheme_state.current_index + 1) % colour_scheme_state.colour_schemes.len();
+        colour_scheme_state.is_outdated = true;
+
+        if let Some(colour_scheme) = self.colour_scheme_state.get_current() {
+            let text = format!("Color scheme: {}", colour_scheme.get_name());
+            self.show_toast(text, false);
+        }
+    }
+
+    /// Saves a snapshot of the screen once the next frame is drawn, which is what the snapshot
+    /// shows.
+    pub fn request_snapshot(&mut self) {
+        self.reset_multi_tap_keys();
+        self.is_snapshot_requested = true;
+    }
+
+    /// Starts or stops editing the layout, which cannot be done in basic mode.
+    pub fn toggle_layout_edit_mode(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if self.layout_edit_state.is_editing {
+            self.layout_edit_state.is_editing = false;
+        } else if self.layout_pages.is_empty() {
+            self.show_toast(
+                "The layout cannot be edited in basic mode.".to_string(),
+                true,
+            );
+        } else {
+            self.is_expanded = false;
+            self.layout_edit_state.is_editing = true;
+        }
+        self.is_force_redraw = true;
+    }
+
+    /// Switches to the layout page `change` pages after the current one, wrapping around.  Each
+    /// page keeps the states of its widgets, and the widget that was selected on it.
+    pub fn change_layout_page(&mut self, change: i32) {
+        self.reset_multi_tap_keys();
+
+        let page_count = self.layout_pages.len();
+        if page_count <= 1 {
+            self.show_toast("There are no other layout pages.".to_string(), true);
+            return;
+        }
+
+        if let Some(current_page) = self.layout_pages.get_mut(self.current_layout_page) {
+            current_page.current_widget_id = self.current_widget.widget_id;
+        }
+        self.current_layout_page =
+            (self.current_layout_page as i32 + change).rem_euclid(page_count as i32) as usize;
+
+        let new_page = &self.layout_pages[self.current_layout_page];
+        self.widget_map = new_page
+            .layout
+            .widgets()
+            .map(|widget| (widget.widget_id, widget.clone()))
+            .collect();
+        let new_widget = self
+            .widget_map
+            .get(&new_page.current_widget_id)
+            .or_else(|| {
+                self.widget_map
+                    .keys()
+                    .min()
+                    .and_then(|widget_id| self.widget_map.get(widget_id))
+            });
+        if let Some(new_widget) = new_widget {
+            self.current_widget = new_widget.clone();
+        }
+        self.layout_edit_state.new_layout = Some(new_page.layout.clone());
+        let toast_text = format!(
+            "{} ({}/{})",
+            new_page.name,
+            self.current_layout_page + 1,
+            page_count
+        );
+
+        self.is_expanded = false;
+        self.show_toast(toast_text, false);
+        self.proc_state.force_update_all = true;
+        self.is_force_redraw = true;
+    }
+
+    fn on_layout_edit_char(&mut self, caught_char: char) {
+        match caught_char {
+            'h' => self.move_widget_selection(&WidgetDirection::Left),
+            'l' => self.move_widget_selection(&WidgetDirection::Right),
+            'k' => self.move_widget_selection(&WidgetDirection::Up),
+            'j' => self.move_widget_selection(&WidgetDirection::Down),
+            'H' => self.swap_layout_widget(&WidgetDirection::Left),
+            'L' => self.swap_layout_widget(&WidgetDirection::Right),
+            'K' => self.swap_layout_widget(&WidgetDirection::Up),
+            'J' => self.swap_layout_widget(&WidgetDirection::Down),
+            '+' => self.resize_layout_widget(1),
+            '-' => self.resize_layout_widget(-1),
+            ']' => self.resize_layout_row(1),
+            '[' => self.resize_layout_row(-1),
+            't' => self.change_layout_widget_type(1),
+            'T' => self.change_layout_widget_type(-1),
+            'a' => self.add_layout_widget(),
+            'd' => self.remove_layout_widget(),
+            'M' => self.toggle_layout_edit_mode(),
+            '<' => self.change_layout_page(-1),
+            '>' => self.change_layout_page(1),
+            '?' => {
+                self.help_dialog_state.is_showing_help = true;
+                self.is_force_redraw = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the position of `widget` among the widgets of the layout rows.
+    fn get_layout_position(&self, widget: &BottomWidget) -> Option<usize> {
+        let widget_id = widget.widget_id - widget.widget_type.get_parent_offset();
+        get_layout_widget_ids(self.widget_map.values())
+            .iter()
+            .position(|layout_widget_id| *layout_widget_id == widget_id)
+    }
+
+    /// Returns the position of the closest widget in `direction` of the selected one, skipping
+    /// over any other parts of the selected widget, like the legend of a CPU widget.
+    fn get_layout_neighbour_position(&self, direction: &WidgetDirection) -> Option<usize> {
+        let position = self.get_layout_position(&self.current_widget)?;
+        let mut widget = self.widget_map.get(&self.current_widget.widget_id)?;
+        for _ in 0..self.widget_map.len() {
+            let neighbour_id = match direction {
+                WidgetDirection::Left => widget.left_neighbour,
+                WidgetDirection::Right => widget.right_neighbour,
+                WidgetDirection::Up => widget.up_neighbour,
+                WidgetDirection::Down => widget.down_neighbour,
+            }?;
+            widget = self.widget_map.get(&neighbour_id)?;
+            match self.get_layout_position(widget) {
+                Some(neighbour_position) if neighbour_position != position => {
+                    return Some(neighbour_position);
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    fn swap_layout_widget(&mut self, direction: &WidgetDirection) {
+        if let Some(neighbour_position) = self.get_layout_neighbour_position(direction) {
+            self.edit_layout(|layout_rows, position| {
+                let paths = get_final_widget_paths(layout_rows);
+                // The widgets trade places, but the sizes of the places stay the same.
+                let widget = get_final_widget_mut(layout_rows, paths[position])?.clone();
+                let neighbour = get_final_widget_mut(layout_rows, paths[neighbour_position])?;
+                let neighbour_type =
+                    std::mem::replace(&mut neighbour.widget_type, widget.widget_type);
+                let neighbour_default = std::mem::replace(&mut neighbour.default, widget.default);
+                let neighbour_graph_style =
+                    std::mem::replace(&mut neighbour.graph_style, widget.graph_style);
+                let neighbour_filled = std::mem::replace(&mut neighbour.filled, widget.filled);
+                let widget = get_final_widget_mut(layout_rows, paths[position])?;
+                widget.widget_type = neighbour_type;
+                widget.default = neighbour_default;
+                widget.graph_style = neighbour_graph_style;
+                widget.filled = neighbour_filled;
+
+                let mut origins: Vec<_> = (0..paths.len()).map(Some).collect();
+                origins.swap(position, neighbour_position);
+                Some((origins, neighbour_position))
+            });
+        }
+    }
+
+    fn resize_layout_widget(&mut self, change: i32) {
+        self.edit_layout(|layout_rows, position| {
+            let paths = get_final_widget_paths(layout_rows);
+            let widget = get_final_widget_mut(layout_rows, paths[position])?;
+            widget.ratio = Some(get_changed_ratio(widget.ratio, change));
+
+            Some(((0..paths.len()).map(Some).collect(), position))
+        });
+    }
+
+    fn resize_layout_row(&mut self, change: i32) {
+        self.edit_layout(|layout_rows, position| {
+            let paths = get_final_widget_paths(layout_rows);
+            let (row_index, _child_index, _col_index) = paths[position];
+            let row = layout_rows.get_mut(row_index)?;
+            row.ratio = Some(get_changed_ratio(row.ratio, change));
+
+            Some(((0..paths.len()).map(Some).collect(), position))
+        });
+    }
+
+    fn change_layout_widget_type(&mut self, change: isize) {
+        let layout_types = BottomWidgetType::get_layout_types();
+        self.edit_layout(|layout_rows, position| {
+            let paths = get_final_widget_paths(layout_rows);
+            let widget = get_final_widget_mut(layout_rows, paths[position])?;
+            let type_index = widget
+                .widget_type
+                .parse::<BottomWidgetType>()
+                .ok()
+                .and_then(|widget_type| {
+                    layout_types
+                        .iter()
+                        .position(|layout_type| *layout_type == widget_type)
+                });
+            let new_type_index = match type_index {
+                Some(type_index) => {
+                    (type_index as isize + change).rem_euclid(layout_types.len() as isize) as usize
+                }
+                None => 0,
+            };
+            widget.widget_type = layout_types[new_type_index].get_config_name().to_string();
+            if !layout_types[new_type_index].can_have_host() {
+                widget.host = None;
+            }
+
+            // The widget starts over with a new state.
+            let mut origins: Vec<_> = (0..paths.len()).map(Some).collect();
+            origins[position] = None;
+            Some((origins, position))
+        });
+    }
+
+    /// Adds a widget of the same type as the selected one right after it.
+    fn add_layout_widget(&mut self) {
+        self.edit_layout(|layout_rows, position| {
+            let paths = get_final_widget_paths(layout_rows);
+            let widget = FinalWidget {
+                default: None,
+                ..get_final_widget_mut(layout_rows, paths[position])?.clone()
+            };
+            insert_final_widget_after(layout_rows, paths[position], widget);
+
+            let origins = (0..=position)
+                .map(Some)
+                .chain(std::iter::once(None))
+                .chain((position + 1..paths.len()).map(Some))
+                .collect();
+            Some((origins, position + 1))
+        });
+    }
+
+    fn remove_layout_widget(&mut self) {
+        if get_layout_widget_ids(self.widget_map.values()).len() <= 1 {
+            self.show_toast("The last widget cannot be removed.".to_string(), true);
+            return;
+        }
+
+        self.edit_layout(|layout_rows, position| {
+            let paths = get_final_widget_paths(layout_rows);
+            remove_final_widget(layout_rows, paths[position]);
+
+            let origins = (0..paths.len())
+                .filter(|origin| *origin != position)
+                .map(Some)
+                .collect();
+            Some((origins, position.min(paths.len() - 2)))
+        });
+    }
+
+    /// Edits the layout rows with `edit`, which is given them and the position of the selected
+    /// widget, and returns the position that each widget of the edited rows had before (if any)
+    /// and the position of the widget to select.  The states of the widgets move with them.
+    fn edit_layout(
+        &mut self, edit: impl FnOnce(&mut Vec<Row>, usize) -> Option<(Vec<Option<usize>>, usize)>,
+    ) {
+        let position = match self.get_layout_position(&self.current_widget) {
+            Some(position) => position,
+            None => return,
+        };
+        let mut layout_rows = match self.layout_pages.get(self.current_layout_page) {
+            Some(layout_page) => layout_page.rows.clone(),
+            None => return,
+        };
+        let (origins, new_position) = match edit(&mut layout_rows, position) {
+            Some(edit_result) => edit_result,
+            None => return,
+        };
+
+        let new_layout = match crate::options::convert_rows_to_layout(
+            &layout_rows,
+            self.app_config_fields.left_legend,
+            &None,
+            &mut 0,
+            &mut 1,
+            self.current_layout_page as u64 * constants::LAYOUT_PAGE_WIDGET_IDS,
+        ) {
+            Ok(new_layout) => new_layout,
+            Err(err) => {
+                self.show_toast(format!("Failed to edit the layout: {}", err), true);
+                return;
+            }
+        };
+        let new_widget_map: HashMap<u64, BottomWidget> = new_layout
+            .widgets()
+            .map(|widget| (widget.widget_id, widget.clone()))
+            .collect();
+
+        let old_ids = get_layout_widget_ids(self.widget_map.values());
+        let new_ids = get_layout_widget_ids(new_widget_map.values());
+        let mut id_map: HashMap<u64, u64> = origins
+            .iter()
+            .zip(&new_ids)
+            .filter_map(|(origin, new_id)| origin.map(|origin| (old_ids[origin], *new_id)))
+            .collect();
+        // The widgets of the other pages are left as they are.
+        id_map.extend(
+            self.layout_pages
+                .iter()
+                .enumerate()
+                .filter(|(page_index, _layout_page)| *page_index != self.current_layout_page)
+                .flat_map(|(_page_index, layout_page)| layout_page.layout.widgets())
+                .map(|widget| (widget.widget_id, widget.widget_id)),
+        );
+        self.remap_widget_states(&id_map);
+        for (origin, new_id) in origins.iter().zip(&new_ids) {
+            if let (None, Some(widget)) = (origin, new_widget_map.get(new_id)) {
+                self.init_widget_state(widget);
+            }
+        }
+
+        if let Some(new_widget) = new_ids
+            .get(new_position)
+            .and_then(|new_id| new_widget_map.get(new_id))
+        {
+            self.current_widget = new_widget.clone();
+        }
+        self.widget_map = new_widget_map;
+        if let Some(layout_page) = self.layout_pages.get_mut(self.current_layout_page) {
+            layout_page.rows = layout_rows;
+            layout_page.layout = new_layout.clone();
+        }
+
+        // The widgets of every page keep being updated, not just those shown.
+        let used_widget_types = self
+            .layout_pages
+            .iter()
+            .flat_map(|layout_page| layout_page.layout.widgets())
+            .map(|widget| widget.widget_type.clone())
+            .collect();
+        let used_widgets = UsedWidgets::from_widget_types(
+            &used_widget_types,
+            self.process_alerts.has_rules() || self.global_filter.query.is_some(),
+        )
+        .union(&self.metric_alerts.get_used_widgets());
+        if used_widgets != self.used_widgets {
+            self.used_widgets = used_widgets;
+            self.layout_edit_state.is_used_widgets_outdated = true;
+        }
+
+        self.layout_edit_state.new_layout = Some(new_layout);
+        self.proc_state.force_update_all = true;
+        self.is_force_redraw = true;
+    }
+
+    /// Moves the widget states, and the data kept for each widget, to the new IDs of their widgets
+    /// in `id_map`.  Those of widgets that are not in it are dropped.
+    fn remap_widget_states(&mut self, id_map: &HashMap<u64, u64>) {
+        remap_widget_ids(&mut self.cpu_state.widget_states, id_map);
+        remap_widget_ids(&mut self.mem_state.widget_states, id_map);
+        remap_widget_ids(&mut self.net_state.widget_states, id_map);
+        remap_widget_ids(&mut self.proc_state.widget_states, id_map);
+        remap_widget_ids(&mut self.temp_state.widget_states, id_map);
+        remap_widget_ids(&mut self.disk_state.widget_states, id_map);
+        remap_widget_ids(&mut self.battery_state.widget_states, id_map);
+        remap_widget_ids(&mut self.conn_state.widget_states, id_map);
+        remap_widget_ids(&mut self.users_state.widget_states, id_map);
+        remap_widget_ids(&mut self.activity_state.widget_states, id_map);
+        remap_widget_ids(&mut self.pods_state.widget_states, id_map);
+        remap_widget_ids(&mut self.vm_state.widget_states, id_map);
+        remap_widget_ids(&mut self.numa_state.widget_states, id_map);
+        remap_widget_ids(&mut self.units_state.widget_states, id_map);
+        remap_widget_ids(&mut self.kernel_log_state.widget_states, id_map);
+        remap_widget_ids(&mut self.alerts_state.widget_states, id_map);
+        remap_widget_ids(&mut self.power_state.widget_states, id_map);
+
+        remap_widget_ids(&mut self.canvas_data.finalized_process_data_map, id_map);
+        remap_widget_ids(&mut self.canvas_data.stringified_process_data_map, id_map);
+        remap_widget_ids(&mut self.canvas_data.connection_data, id_map);
+        remap_widget_ids(&mut self.canvas_data.disk_data, id_map);
+    }
+
+    /// Gives a new widget the state that it would have started with.
+    fn init_widget_state(&mut self, widget: &BottomWidget) {
+        let widget_id = widget.widget_id;
+        let default_time_value = self.app_config_fields.default_time_value;
+        let graph_style = widget
+            .graph_style
+            .unwrap_or(self.app_config_fields.graph_style);
+        let is_filled = widget
+            .is_filled
+            .unwrap_or(self.app_config_fields.is_filled_graph);
+        let autohide_timer = if self.app_config_fields.autohide_time {
+            Some(Instant::now())
+        } else {
+            None
+        };
+        let host = widget
+            .host
+            .as_deref()
+            .and_then(|name| remote_hosts::get_host_index(&self.remote_hosts, name));
+
+        match widget.widget_type {
+            BottomWidgetType::Cpu => {
+                self.cpu_state.widget_states.insert(
+                    widget_id,
+                    CpuWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        graph_style,
+                        is_filled,
+                        host,
+                    ),
+                );
+            }
+            BottomWidgetType::Mem => {
+                self.mem_state.widget_states.insert(
+                    widget_id,
+                    MemWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        graph_style,
+                        is_filled,
+                        host,
+                    ),
+                );
+            }
+            BottomWidgetType::Net => {
+                self.net_state.widget_states.insert(
+                    widget_id,
+                    NetWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        graph_style,
+                        is_filled,
+                        self.app_config_fields.network_scale_type.clone(),
+                    ),
+                );
+            }
+            BottomWidgetType::Proc => {
+                self.proc_state
+                    .widget_states
+                    .insert(widget_id, self.proc_widget_defaults.init_state());
+            }
+            BottomWidgetType::Disk => {
+                self.disk_state.widget_states.insert(
+                    widget_id,
+                    DiskWidgetState::init(self.app_config_fields.disk_sorting),
+                );
+            }
+            BottomWidgetType::Temp => {
+                self.temp_state
+                    .widget_states
+                    .insert(widget_id, TempWidgetState::init());
+            }
+            BottomWidgetType::Battery => {
+                self.battery_state
+                    .widget_states
+                    .insert(widget_id, BatteryWidgetState::default());
+            }
+            BottomWidgetType::Conn => {
+                self.conn_state
+                    .widget_states
+                    .insert(widget_id, ConnWidgetState::init());
+            }
+            BottomWidgetType::Users => {
+                self.users_state
+                    .widget_states
+                    .insert(widget_id, UsersWidgetState::init());
+            }
+            BottomWidgetType::Activity => {
+                self.activity_state
+                    .widget_states
+                    .insert(widget_id, ActivityWidgetState::init());
+            }
+            BottomWidgetType::Pods => {
+                self.pods_state
+                    .widget_states
+                    .insert(widget_id, PodsWidgetState::init());
+            }
+            BottomWidgetType::Vm => {
+                self.vm_state
+                    .widget_states
+                    .insert(widget_id, VmWidgetState::init());
+            }
+            BottomWidgetType::Numa => {
+                self.numa_state
+                    .widget_states
+                    .insert(widget_id, NumaWidgetState::init());
+            }
+            BottomWidgetType::Units => {
+                self.units_state
+                    .widget_states
+                    .insert(widget_id, UnitsWidgetState::init());
+            }
+            BottomWidgetType::KernelLog => {
+                self.kernel_log_state
+                    .widget_states
+                    .insert(widget_id, KernelLogWidgetState::init());
+            }
+            BottomWidgetType::Alerts => {
+                self.alerts_state
+                    .widget_states
+                    .insert(widget_id, AlertsWidgetState::init());
+            }
+            BottomWidgetType::Power => {
+                self.power_state.widget_states.insert(
+                    widget_id,
+                    PowerWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        graph_style,
+                        is_filled,
+                    ),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens a dialog with the details of the currently selected process.
+    pub fn open_process_details_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get(&self.current_widget.widget_id)
+        {
+            // Details only make sense for a single process.
+            if proc_widget_state.is_grouped {
+                return;
+            }
+
+            if let Some(pid) = self
+                .canvas_data
+                .finalized_process_data_map
+                .get(&self.current_widget.widget_id)
+                .and_then(|process_list| {
+                    process_list.get(proc_widget_state.scroll_state.current_scroll_position)
+                })
+                .map(|process| process.pid)
+            {
+                self.process_details_dialog_state = AppProcessDetailsDialogState::default();
+                self.process_details_dialog_state.is_showing_details = true;
+                self.process_details_dialog_state.pid = Some(pid);
+                self.refresh_process_details();
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    /// Collects the details of the process in the details dialog again.  This only reads
+    /// information about that single process, so it is cheap enough to do on every update.
+    pub fn refresh_process_details(&mut self) {
+        if let Some(pid) = self.process_details_dialog_state.pid {
+            match processes::get_process_details(pid) {
+                Ok(details) => {
+                    self.process_details_dialog_state.details = Some(details);
+                    self.process_details_dialog_state.error = None;
+                }
+                Err(err) => self.process_details_dialog_state.error = Some(err.to_string()),
+            }
+        }
+    }
+
+    fn close_process_details_dialog(&mut self) {
+        self.process_details_dialog_state = AppProcessDetailsDialogState::default();
+    }
+
+    fn process_details_scroll_up(&mut self) {
+        let scroll_state = &mut self.process_details_dialog_state.scroll_state;
+        scroll_state.current_scroll_index = scroll_state.current_scroll_index.saturating_sub(1);
+    }
+
+    fn process_details_scroll_down(&mut self) {
+        let scroll_state = &mut self.process_details_dialog_state.scroll_state;
+        if scroll_state.current_scroll_index < scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index += 1;
+        }
+    }
+
+    /// Opens a dialog listing the open files of the currently selected process.  The open files are
+    /// only collected now, rather than on every update.
+    pub fn open_open_files_dialog(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get(&self.current_widget.widget_id)
+        {
+            // Open files only make sense for a single process.
+            if proc_widget_state.is_grouped {
+                return;
+            }
+
+            if let Some(process) = self
+                .canvas_data
+                .finalized_process_data_map
+                .get(&self.current_widget.widget_id)
+                .and_then(|process_list| {
+                    process_list.get(proc_widget_state.scroll_state.current_scroll_position)
+                })
+            {
+                self.open_files_dialog_state = AppOpenFilesDialogState::default();
+                self.open_files_dialog_state.is_showing_open_files = true;
+                match processes::get_open_files(process.pid) {
+                    Ok(open_files) => {
+                        self.open_files_dialog_state.open_files =
+                            crate::data_conversion::convert_open_file_rows(&open_files)
+                    }
+                    Err(err) => self.open_files_dialog_state.error = Some(err.to_string()),
+                }
+                self.open_files_dialog_state.process = Some((process.pid, process.name.clone()));
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    fn close_open_files_dialog(&mut self) {
+        self.open_files_dialog_state = AppOpenFilesDialogState::default();
+    }
+
+    fn change_open_files_position(&mut self, num_to_change_by: i64) {
+        let scroll_state = &mut self.open_files_dialog_state.scroll_state;
+        let num_open_files = self.open_files_dialog_state.open_files.len();
+        let current_posn = scroll_state.current_scroll_position;
+
+        if current_posn as i64 + num_to_change_by < 0 {
+            scroll_state.current_scroll_position = 0;
+        } else if current_posn as i64 + num_to_change_by >= num_open_files as i64 {
+            scroll_state.current_scroll_position = num_open_files.saturating_sub(1);
+        } else {
+            scroll_state.current_scroll_position =
+                (current_posn as i64 + num_to_change_by) as usize;
+        }
+
+        if num_to_change_by < 0 {
+            scroll_state.scroll_direction = ScrollDirection::Up;
+        } else {
+            scroll_state.scroll_direction = ScrollDirection::Down;
+        }
+    }
+
+    fn close_thread_dialog(&mut self) {
+        if self.thread_dialog_state.process.is_some() {
+            self.thread_dialog_state.is_showing_threads = false;
+            self.thread_dialog_state.process = None;
+            self.thread_dialog_state.is_pid_outdated = true;
+        }
+    }
+
+    fn change_thread_position(&mut self, num_to_change_by: i64) {
+        let scroll_state = &mut self.thread_dialog_state.scroll_state;
+        let num_threads = self.canvas_data.thread_data.len();
+        let current_posn = scroll_state.current_scroll_position;
+
+        if current_posn as i64 + num_to_change_by < 0 {
+            scroll_state.current_scroll_position = 0;
+        } else if current_posn as i64 + num_to_change_by >= num_threads as i64 {
+            scroll_state.current_scroll_position = num_threads.saturating_sub(1);
+        } else {
+            scroll_state.current_scroll_position =
+                (current_posn as i64 + num_to_change_by) as usize;
+        }
+
+        if num_to_change_by < 0 {
+            scroll_state.scroll_direction = ScrollDirection::Up;
+        } else {
+            scroll_state.scroll_direction = ScrollDirection::Down;
+        }
     }
 
     pub fn on_esc(&mut self) {
@@ -212,6 +2008,27 @@ impl App {
             if self.help_dialog_state.is_showing_help {
                 self.help_dialog_state.is_showing_help = false;
                 self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            } else if self.thread_dialog_state.is_showing_threads {
+                self.close_thread_dialog();
+            } else if self.process_details_dialog_state.is_showing_details {
+                self.close_process_details_dialog();
+            } else if self.open_files_dialog_state.is_showing_open_files {
+                self.close_open_files_dialog();
+            } else if self.renice_dialog_state.is_showing_renice {
+                self.close_renice_dialog();
+            } else if self.global_filter.is_editing {
+                self.close_global_filter_dialog();
+            } else if self.io_priority_dialog_state.is_showing_io_priority {
+                self.close_io_priority_dialog();
+            } else if self.affinity_dialog_state.is_showing_affinity {
+                self.close_affinity_dialog();
+            } else if self.columns_dialog_state.is_showing_columns {
+                self.close_columns_dialog();
+            } else if self.filters_dialog_state.is_showing_filters {
+                self.close_filters_dialog();
+            } else if self.delete_dialog_state.is_searching_signals {
+                self.delete_dialog_state.is_searching_signals = false;
+                self.delete_dialog_state.signal_search_query.clear();
             } else {
                 self.close_dd();
             }
@@ -219,6 +2036,8 @@ impl App {
             self.is_force_redraw = true;
         } else if self.is_config_open {
             self.close_config_screen();
+        } else if self.layout_edit_state.is_editing {
+            self.toggle_layout_edit_mode();
         } else {
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
@@ -228,6 +2047,10 @@ impl App {
                     {
                         if current_proc_state.is_search_enabled() || current_proc_state.is_sort_open
                         {
+                            if current_proc_state.is_search_enabled() {
+                                self.search_history
+                                    .record(current_proc_state.get_current_search_query());
+                            }
                             current_proc_state
                                 .process_search_state
                                 .search_state
@@ -244,6 +2067,8 @@ impl App {
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
                     {
                         if current_proc_state.is_search_enabled() {
+                            self.search_history
+                                .record(current_proc_state.get_current_search_query());
                             current_proc_state
                                 .process_search_state
                                 .search_state
@@ -254,6 +2079,32 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Conn => {
+                    if let Some(conn_widget_state) = self
+                        .conn_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if conn_widget_state.is_searching {
+                            conn_widget_state.is_searching = false;
+                            conn_widget_state.search_query.clear();
+                            self.conn_state.force_update = Some(self.current_widget.widget_id);
+                            return;
+                        }
+                    }
+                }
+                BottomWidgetType::Disk => {
+                    if let Some(disk_widget_state) = self
+                        .disk_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if disk_widget_state.is_searching {
+                            disk_widget_state.is_searching = false;
+                            disk_widget_state.search_query.clear();
+                            self.disk_state.force_update = Some(self.current_widget.widget_id);
+                            return;
+                        }
+                    }
+                }
                 BottomWidgetType::ProcSort => {
                     if let Some(current_proc_state) = self
                         .proc_state
@@ -286,13 +2137,47 @@ impl App {
         )
     }
 
+    /// Returns whether characters are currently being typed into a connections widget's search.
+    pub fn is_in_conn_search(&self) -> bool {
+        if let BottomWidgetType::Conn = self.current_widget.widget_type {
+            self.conn_state
+                .get_widget_state(self.current_widget.widget_id)
+                .map(|conn_widget_state| conn_widget_state.is_searching)
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Returns whether characters are currently being typed into a disk widget's search.
+    pub fn is_in_disk_search(&self) -> bool {
+        if let BottomWidgetType::Disk = self.current_widget.widget_type {
+            self.disk_state
+                .get_widget_state(self.current_widget.widget_id)
+                .map(|disk_widget_state| disk_widget_state.is_searching)
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
     fn reset_multi_tap_keys(&mut self) {
         self.awaiting_second_char = false;
         self.second_char = None;
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.thread_dialog_state.is_showing_threads
+            || self.process_details_dialog_state.is_showing_details
+            || self.open_files_dialog_state.is_showing_open_files
+            || self.renice_dialog_state.is_showing_renice
+            || self.global_filter.is_editing
+            || self.io_priority_dialog_state.is_showing_io_priority
+            || self.affinity_dialog_state.is_showing_affinity
+            || self.columns_dialog_state.is_showing_columns
+            || self.filters_dialog_state.is_showing_filters
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -318,60 +2203,105 @@ impl App {
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        // Do NOT allow when in tree mode!
-                        if !proc_widget_state.is_tree_mode {
-                            // Toggles process widget grouping state
-                            proc_widget_state.is_grouped = !(proc_widget_state.is_grouped);
-
-                            // Forcefully switch off column if we were on it...
-                            if (proc_widget_state.is_grouped
-                                && (proc_widget_state.process_sorting_type
-                                    == processes::ProcessSorting::Pid
-                                    || proc_widget_state.process_sorting_type
-                                        == processes::ProcessSorting::User
-                                    || proc_widget_state.process_sorting_type
-                                        == processes::ProcessSorting::State))
-                                || (!proc_widget_state.is_grouped
-                                    && proc_widget_state.process_sorting_type
-                                        == processes::ProcessSorting::Count)
-                            {
-                                proc_widget_state.process_sorting_type =
-                                    processes::ProcessSorting::CpuPercent; // Go back to default, negate PID for group
-                                proc_widget_state.is_process_sort_descending = true;
-                            }
-
-                            proc_widget_state.columns.set_to_sorted_index_from_type(
-                                &proc_widget_state.process_sorting_type,
-                            );
-
-                            proc_widget_state.columns.try_set(
-                                &processes::ProcessSorting::State,
-                                !(proc_widget_state.is_grouped),
-                            );
-
-                            #[cfg(target_family = "unix")]
-                            proc_widget_state.columns.try_set(
-                                &processes::ProcessSorting::User,
-                                !(proc_widget_state.is_grouped),
-                            );
-
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Count);
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Pid);
-
-                            proc_widget_state.requires_redraw = true;
+                        if proc_widget_state.is_grouped && proc_widget_state.is_grouped_by_user {
+                            // Switch from grouping by user to grouping by name.
+                            proc_widget_state.is_grouped_by_user = false;
                             self.proc_state.force_update = Some(self.current_widget.widget_id);
+                            return;
                         }
                     }
+
+                    self.toggle_process_grouping();
                 }
                 _ => {}
             }
         }
     }
 
+    fn toggle_process_grouping(&mut self) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            // Do NOT allow when in tree mode!
+            if !proc_widget_state.is_tree_mode {
+                // Toggles process widget grouping state
+                proc_widget_state.is_grouped = !(proc_widget_state.is_grouped);
+
+                // Forcefully switch off column if we were on it...
+                if (proc_widget_state.is_grouped
+                    && (proc_widget_state.process_sorting_type == processes::ProcessSorting::Pid
+                        || proc_widget_state.process_sorting_type
+                            == processes::ProcessSorting::User
+                        || proc_widget_state.process_sorting_type
+                            == processes::ProcessSorting::Group
+                        || proc_widget_state.process_sorting_type
+                            == processes::ProcessSorting::State))
+                    || (!proc_widget_state.is_grouped
+                        && proc_widget_state.process_sorting_type
+                            == processes::ProcessSorting::Count)
+                {
+                    proc_widget_state.process_sorting_type = processes::ProcessSorting::CpuPercent; // Go back to default, negate PID for group
+                    proc_widget_state.is_process_sort_descending = true;
+                }
+
+                proc_widget_state
+                    .columns
+                    .set_to_sorted_index_from_type(&proc_widget_state.process_sorting_type);
+
+                proc_widget_state.columns.try_set(
+                    &processes::ProcessSorting::State,
+                    !(proc_widget_state.is_grouped),
+                );
+
+                #[cfg(target_family = "unix")]
+                proc_widget_state.columns.try_set(
+                    &processes::ProcessSorting::User,
+                    !(proc_widget_state.is_grouped),
+                );
+
+                #[cfg(target_family = "unix")]
+                proc_widget_state.columns.try_set(
+                    &processes::ProcessSorting::Group,
+                    !(proc_widget_state.is_grouped),
+                );
+
+                proc_widget_state
+                    .columns
+                    .toggle(&processes::ProcessSorting::Count);
+                proc_widget_state
+                    .columns
+                    .toggle(&processes::ProcessSorting::Pid);
+
+                proc_widget_state.requires_redraw = true;
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+            }
+        }
+    }
+
+    /// Toggles grouping processes by the user running them, or switches to it if they are grouped
+    /// by name.
+    pub fn toggle_grouping_by_user(&mut self) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            if proc_widget_state.is_tree_mode {
+                return;
+            }
+
+            if proc_widget_state.is_grouped && !proc_widget_state.is_grouped_by_user {
+                proc_widget_state.is_grouped_by_user = true;
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+                return;
+            }
+
+            proc_widget_state.is_grouped_by_user = !proc_widget_state.is_grouped;
+        }
+
+        self.toggle_process_grouping();
+    }
+
     /// I don't like this, but removing it causes a bunch of breakage.
     /// Use ``proc_widget_state.is_grouped`` if possible!
     pub fn is_grouped(&self, widget_id: u64) -> bool {
@@ -402,6 +2332,24 @@ impl App {
                         self.is_force_redraw = true;
                     }
                 }
+                BottomWidgetType::Conn => {
+                    if let Some(conn_widget_state) = self
+                        .conn_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        conn_widget_state.is_searching = true;
+                        self.is_force_redraw = true;
+                    }
+                }
+                BottomWidgetType::Disk => {
+                    if let Some(disk_widget_state) = self
+                        .disk_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        disk_widget_state.is_searching = true;
+                        self.is_force_redraw = true;
+                    }
+                }
                 _ => {}
             }
         }
@@ -438,6 +2386,24 @@ impl App {
 
                 self.is_force_redraw = true;
             }
+            BottomWidgetType::Conn => {
+                if let Some(conn_widget_state) = self
+                    .conn_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    conn_widget_state.cycle_sorting_type();
+                    self.conn_state.force_update = Some(self.current_widget.widget_id);
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    disk_widget_state.cycle_sorting_type(self.app_config_fields.use_disk_inodes);
+                    self.disk_state.force_update = Some(self.current_widget.widget_id);
+                }
+            }
             _ => {}
         }
     }
@@ -459,6 +2425,26 @@ impl App {
                     self.proc_state.force_update = Some(widget_id);
                 }
             }
+            BottomWidgetType::Conn => {
+                if let Some(conn_widget_state) = self
+                    .conn_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    conn_widget_state.is_sort_descending = !conn_widget_state.is_sort_descending;
+                    self.conn_state.force_update = Some(self.current_widget.widget_id);
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if let Some((_sorting, is_descending)) = &mut disk_widget_state.sorting {
+                        *is_descending = !*is_descending;
+                        self.disk_state.force_update = Some(self.current_widget.widget_id);
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -675,6 +2661,113 @@ impl App {
         }
     }
 
+    pub fn toggle_search_fuzzy(&mut self) {
+        let is_in_search_widget = self.is_in_search_widget();
+        let mut is_searching_with_fuzzy: Option<bool> = None;
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            if is_in_search_widget && proc_widget_state.is_search_enabled() {
+                proc_widget_state.process_search_state.search_toggle_fuzzy();
+                proc_widget_state.update_query();
+                self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+
+                is_searching_with_fuzzy = Some(
+                    proc_widget_state
+                        .process_search_state
+                        .is_searching_with_fuzzy,
+                );
+            }
+        }
+
+        // Also toggle it in the config file if we actually changed it.
+        if let Some(is_searching_with_fuzzy) = is_searching_with_fuzzy {
+            if let Some(flags) = &mut self.config.flags {
+                if let Some(map) = &mut flags.search_fuzzy_enabled_widgets_map {
+                    // Just update the map.
+                    let mapping = map.entry(self.current_widget.widget_id - 1).or_default();
+                    *mapping = is_searching_with_fuzzy;
+
+                    flags.search_fuzzy_enabled_widgets =
+                        Some(WidgetIdEnabled::create_from_hashmap(map));
+                } else {
+                    // Map doesn't exist yet... initialize ourselves.
+                    let mut map = HashMap::default();
+                    map.insert(self.current_widget.widget_id - 1, is_searching_with_fuzzy);
+                    flags.search_fuzzy_enabled_widgets =
+                        Some(WidgetIdEnabled::create_from_hashmap(&map));
+                    flags.search_fuzzy_enabled_widgets_map = Some(map);
+                }
+            } else {
+                // Must initialize it ourselves...
+                let mut map = HashMap::default();
+                map.insert(self.current_widget.widget_id - 1, is_searching_with_fuzzy);
+
+                self.config.flags = Some(
+                    ConfigFlags::builder()
+                        .search_fuzzy_enabled_widgets(WidgetIdEnabled::create_from_hashmap(&map))
+                        .search_fuzzy_enabled_widgets_map(map)
+                        .build(),
+                );
+            }
+        }
+    }
+
+    /// Toggles hiding kernel threads in the current process widget.
+    /// Hides the mount point of the selected disk from the disk widget, unless it is showing no
+    /// disks.
+    pub fn toggle_hiding_selected_mount(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        if let Some(disk_widget_state) = self.disk_state.get_mut_widget_state(widget_id) {
+            let selected_mount = self
+                .canvas_data
+                .disk_data
+                .get(&widget_id)
+                .and_then(|disk_data| {
+                    disk_data.get(disk_widget_state.scroll_state.current_scroll_position)
+                })
+                .and_then(|row| row.get(1))
+                .filter(|mount| !mount.is_empty());
+
+            if let Some(selected_mount) = selected_mount {
+                disk_widget_state
+                    .hidden_mounts
+                    .insert(selected_mount.clone());
+                self.disk_state.force_update = Some(widget_id);
+                self.show_toast(
+                    format!("Hid {}, press U to show it again", selected_mount),
+                    false,
+                );
+            }
+        }
+    }
+
+    /// Shows all the mount points that were hidden from the disk widget again.
+    pub fn unhide_all_mounts(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        if let Some(disk_widget_state) = self.disk_state.get_mut_widget_state(widget_id) {
+            if !disk_widget_state.hidden_mounts.is_empty() {
+                disk_widget_state.hidden_mounts.clear();
+                self.disk_state.force_update = Some(widget_id);
+            }
+        }
+    }
+
+    pub fn toggle_kernel_threads(&mut self) {
+        if let BottomWidgetType::Proc | BottomWidgetType::ProcSort = self.current_widget.widget_type
+        {
+            let widget_id =
+                self.current_widget.widget_id - self.current_widget.widget_type.get_parent_offset();
+            if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+                proc_widget_state.is_hiding_kernel_threads =
+                    !proc_widget_state.is_hiding_kernel_threads;
+                self.proc_state.force_update = Some(widget_id);
+            }
+        }
+    }
+
     pub fn toggle_tree_mode(&mut self) {
         if let Some(proc_widget_state) = self
             .proc_state
@@ -687,6 +2780,7 @@ impl App {
             if proc_widget_state.is_tree_mode {
                 // Disable grouping if so!
                 proc_widget_state.is_grouped = false;
+                proc_widget_state.is_grouped_by_user = false;
 
                 proc_widget_state
                     .columns
@@ -697,6 +2791,11 @@ impl App {
                     .columns
                     .try_enable(&processes::ProcessSorting::User);
 
+                #[cfg(target_family = "unix")]
+                proc_widget_state
+                    .columns
+                    .try_enable(&processes::ProcessSorting::Group);
+
                 proc_widget_state
                     .columns
                     .try_disable(&processes::ProcessSorting::Count);
@@ -717,8 +2816,26 @@ impl App {
 
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.renice_dialog_state.is_showing_renice {
+            self.apply_renice_dialog();
+            self.is_force_redraw = true;
+        } else if self.global_filter.is_editing {
+            self.apply_global_filter_dialog();
+            self.is_force_redraw = true;
+        } else if self.io_priority_dialog_state.is_showing_io_priority {
+            self.apply_io_priority_dialog();
+            self.is_force_redraw = true;
+        } else if self.affinity_dialog_state.is_showing_affinity {
+            self.apply_affinity_dialog();
+            self.is_force_redraw = true;
+        } else if self.columns_dialog_state.is_showing_columns {
+            self.on_columns_char(' ');
+        } else if self.filters_dialog_state.is_showing_filters {
+            self.toggle_filters_dialog_entry();
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
+                #[cfg(target_family = "unix")]
+                self.retry_kill_with_privilege_helper();
                 self.close_dd();
             } else if self.delete_dialog_state.selected_signal != KillSignal::Cancel {
                 // If within dd...
@@ -727,22 +2844,57 @@ impl App {
                     let dd_result = self.kill_highlighted_process();
                     self.delete_dialog_state.scroll_pos = 0;
                     self.delete_dialog_state.selected_signal = KillSignal::default();
+                    self.delete_dialog_state.is_searching_signals = false;
+                    self.delete_dialog_state.signal_search_query.clear();
+                    self.delete_dialog_state.is_killing_tree = false;
 
                     // Check if there was an issue... if so, inform the user.
                     if let Err(dd_err) = dd_result {
                         self.dd_err = Some(dd_err.to_string());
+                        self.delete_dialog_state.failed_action = ProcessAction::Kill;
                     } else {
+                        if self.delete_dialog_state.is_killing_tagged {
+                            // The tagged processes are gone now, so there is nothing left to tag.
+                            self.untag_all_processes();
+                        }
+                        self.delete_dialog_state.is_killing_tagged = false;
                         self.delete_dialog_state.is_showing_dd = false;
                     }
                 }
             } else {
-                self.delete_dialog_state.scroll_pos = 0;
-                self.delete_dialog_state.selected_signal = KillSignal::default();
-                self.delete_dialog_state.is_showing_dd = false;
+                self.close_dd();
             }
             self.is_force_redraw = true;
+        } else if self.layout_edit_state.is_editing && !self.is_in_dialog() {
+            self.toggle_layout_edit_mode();
+        } else if self.is_in_conn_search() {
+            if let Some(conn_widget_state) = self
+                .conn_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                conn_widget_state.is_searching = false;
+                self.is_force_redraw = true;
+            }
+        } else if self.is_in_disk_search() {
+            if let Some(disk_widget_state) = self
+                .disk_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                disk_widget_state.is_searching = false;
+                self.is_force_redraw = true;
+            }
         } else if !self.is_in_dialog() {
-            if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
+            if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                self.open_process_details_dialog();
+            } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+                if let Some(proc_widget_state) = self
+                    .proc_state
+                    .get_widget_state(self.current_widget.widget_id - 1)
+                {
+                    self.search_history
+                        .record(proc_widget_state.get_current_search_query());
+                }
+            } else if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
                 if let Some(proc_widget_state) = self
                     .proc_state
                     .widget_states
@@ -811,6 +2963,45 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
+        if self.renice_dialog_state.is_showing_renice {
+            self.renice_dialog_state.nice_input.pop();
+            return;
+        }
+
+        if self.global_filter.is_editing {
+            self.global_filter.input.pop();
+            return;
+        }
+
+        #[cfg(target_family = "unix")]
+        if self.delete_dialog_state.is_searching_signals {
+            self.delete_dialog_state.signal_search_query.pop();
+            self.change_signal_position(0);
+            return;
+        }
+
+        if self.is_in_conn_search() {
+            if let Some(conn_widget_state) = self
+                .conn_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                conn_widget_state.search_query.pop();
+                self.conn_state.force_update = Some(self.current_widget.widget_id);
+            }
+            return;
+        }
+
+        if self.is_in_disk_search() {
+            if let Some(disk_widget_state) = self
+                .disk_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                disk_widget_state.search_query.pop();
+                self.disk_state.force_update = Some(self.current_widget.widget_id);
+            }
+            return;
+        }
+
         if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
@@ -876,7 +3067,9 @@ impl App {
 
     #[cfg(target_family = "unix")]
     pub fn on_number(&mut self, number_char: char) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.delete_dialog_state.is_searching_signals {
+            self.on_signal_search_char(number_char);
+        } else if self.delete_dialog_state.is_showing_dd {
             if self
                 .delete_dialog_state
                 .last_number_press
@@ -894,7 +3087,7 @@ impl App {
             if kbd_signal > 64 || kbd_signal == 32 || kbd_signal == 33 {
                 kbd_signal %= 10;
             }
-            #[cfg(target_os = "macos")]
+            #[cfg(any(target_os = "macos", target_os = "freebsd"))]
             if kbd_signal > 31 {
                 kbd_signal %= 10;
             }
@@ -908,12 +3101,78 @@ impl App {
         }
     }
 
+    /// Returns whether key presses are being typed into the kill dialog's signal search.
+    pub fn is_in_signal_search(&self) -> bool {
+        self.delete_dialog_state.is_showing_dd && self.delete_dialog_state.is_searching_signals
+    }
+
+    #[cfg(target_family = "unix")]
+    fn on_signal_search_char(&mut self, caught_char: char) {
+        if UnicodeWidthStr::width(self.delete_dialog_state.signal_search_query.as_str())
+            <= MAX_SEARCH_LENGTH
+        {
+            self.delete_dialog_state
+                .signal_search_query
+                .push(caught_char);
+        }
+
+        // Jump to the first match, rather than staying on a signal that might be filtered out.
+        self.delete_dialog_state.selected_signal = KillSignal::Cancel;
+        self.change_signal_position(1);
+    }
+
+    /// Moves the selection in the signal picker by `delta` entries, only counting the signals that
+    /// match the current signal search.
+    #[cfg(target_family = "unix")]
+    fn change_signal_position(&mut self, delta: i64) {
+        let signals =
+            process_actions::get_matching_signals(&self.delete_dialog_state.signal_search_query);
+        let current_signal = match self.delete_dialog_state.selected_signal {
+            KillSignal::Cancel => 0,
+            KillSignal::Kill(signal) => signal,
+        };
+        let current_index = signals
+            .iter()
+            .position(|(signal, _name)| *signal == current_signal)
+            .unwrap_or(0);
+
+        let new_index = if delta < 0 {
+            current_index.saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            min(
+                current_index.saturating_add(delta as usize),
+                signals.len() - 1,
+            )
+        };
+
+        self.delete_dialog_state.selected_signal = match signals[new_index].0 {
+            0 => KillSignal::Cancel,
+            signal => KillSignal::Kill(signal),
+        };
+    }
+
     pub fn on_up_key(&mut self) {
         if self.is_config_open {
+        } else if self.layout_edit_state.is_editing && !self.is_in_dialog() {
+            self.move_widget_selection(&WidgetDirection::Up);
         } else if !self.is_in_dialog() {
             self.decrement_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.thread_dialog_state.is_showing_threads {
+            self.change_thread_position(-1);
+        } else if self.process_details_dialog_state.is_showing_details {
+            self.process_details_scroll_up();
+        } else if self.open_files_dialog_state.is_showing_open_files {
+            self.change_open_files_position(-1);
+        } else if self.io_priority_dialog_state.is_showing_io_priority {
+            self.change_io_priority_level(-1);
+        } else if self.affinity_dialog_state.is_showing_affinity {
+            self.change_affinity_position(-1);
+        } else if self.columns_dialog_state.is_showing_columns {
+            self.change_columns_position(-1);
+        } else if self.filters_dialog_state.is_showing_filters {
+            self.change_filters_position(-1);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_right_key();
@@ -932,10 +3191,26 @@ impl App {
 
     pub fn on_down_key(&mut self) {
         if self.is_config_open {
+        } else if self.layout_edit_state.is_editing && !self.is_in_dialog() {
+            self.move_widget_selection(&WidgetDirection::Down);
         } else if !self.is_in_dialog() {
             self.increment_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.thread_dialog_state.is_showing_threads {
+            self.change_thread_position(1);
+        } else if self.process_details_dialog_state.is_showing_details {
+            self.process_details_scroll_down();
+        } else if self.open_files_dialog_state.is_showing_open_files {
+            self.change_open_files_position(1);
+        } else if self.io_priority_dialog_state.is_showing_io_priority {
+            self.change_io_priority_level(1);
+        } else if self.affinity_dialog_state.is_showing_affinity {
+            self.change_affinity_position(1);
+        } else if self.columns_dialog_state.is_showing_columns {
+            self.change_columns_position(1);
+        } else if self.filters_dialog_state.is_showing_filters {
+            self.change_filters_position(1);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_left_key();
@@ -954,6 +3229,8 @@ impl App {
 
     pub fn on_left_key(&mut self) {
         if self.is_config_open {
+        } else if self.layout_edit_state.is_editing && !self.is_in_dialog() {
+            self.move_widget_selection(&WidgetDirection::Left);
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
                 BottomWidgetType::ProcSearch => {
@@ -996,23 +3273,16 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Proc => self.collapse_process_branch(),
                 _ => {}
             }
+        } else if self.io_priority_dialog_state.is_showing_io_priority {
+            self.change_io_priority_class(-1);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
                 if self.app_config_fields.is_advanced_kill {
-                    match self.delete_dialog_state.selected_signal {
-                        KillSignal::Kill(prev_signal) => {
-                            self.delete_dialog_state.selected_signal = match prev_signal - 1 {
-                                0 => KillSignal::Cancel,
-                                // 32+33 are skipped
-                                33 => KillSignal::Kill(31),
-                                signal => KillSignal::Kill(signal),
-                            };
-                        }
-                        KillSignal::Cancel => {}
-                    };
+                    self.change_signal_position(-1);
                 } else {
                     self.delete_dialog_state.selected_signal = KillSignal::default();
                 }
@@ -1026,6 +3296,8 @@ impl App {
 
     pub fn on_right_key(&mut self) {
         if self.is_config_open {
+        } else if self.layout_edit_state.is_editing && !self.is_in_dialog() {
+            self.move_widget_selection(&WidgetDirection::Right);
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
                 BottomWidgetType::ProcSearch => {
@@ -1072,23 +3344,16 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Proc => self.expand_process_branch(),
                 _ => {}
             }
+        } else if self.io_priority_dialog_state.is_showing_io_priority {
+            self.change_io_priority_class(1);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
                 if self.app_config_fields.is_advanced_kill {
-                    let new_signal = match self.delete_dialog_state.selected_signal {
-                        KillSignal::Cancel => 1,
-                        // 32+33 are skipped
-                        #[cfg(target_os = "linux")]
-                        KillSignal::Kill(31) => 34,
-                        #[cfg(target_os = "macos")]
-                        KillSignal::Kill(31) => 31,
-                        KillSignal::Kill(64) => 64,
-                        KillSignal::Kill(signal) => signal + 1,
-                    };
-                    self.delete_dialog_state.selected_signal = KillSignal::Kill(new_signal);
+                    self.change_signal_position(1);
                 } else {
                     self.delete_dialog_state.selected_signal = KillSignal::Cancel;
                 }
@@ -1102,6 +3367,12 @@ impl App {
 
     pub fn on_page_up(&mut self) {
         if self.delete_dialog_state.is_showing_dd {
+            #[cfg(target_family = "unix")]
+            if self.app_config_fields.is_advanced_kill {
+                self.change_signal_position(-8);
+                return;
+            }
+
             let mut new_signal = match self.delete_dialog_state.selected_signal {
                 KillSignal::Cancel => 0,
                 KillSignal::Kill(signal) => max(signal, 8) - 8,
@@ -1132,6 +3403,12 @@ impl App {
 
     pub fn on_page_down(&mut self) {
         if self.delete_dialog_state.is_showing_dd {
+            #[cfg(target_family = "unix")]
+            if self.app_config_fields.is_advanced_kill {
+                self.change_signal_position(8);
+                return;
+            }
+
             let mut new_signal = match self.delete_dialog_state.selected_signal {
                 KillSignal::Cancel => 8,
                 KillSignal::Kill(signal) => min(signal + 8, MAX_SIGNAL),
@@ -1239,9 +3516,31 @@ impl App {
             }
         } else if self.is_config_open {
         }
-    }
+    }
+
+    pub fn clear_search(&mut self) {
+        if self.is_in_conn_search() {
+            if let Some(conn_widget_state) = self
+                .conn_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                conn_widget_state.search_query.clear();
+                self.conn_state.force_update = Some(self.current_widget.widget_id);
+            }
+            return;
+        }
+
+        if self.is_in_disk_search() {
+            if let Some(disk_widget_state) = self
+                .disk_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                disk_widget_state.search_query.clear();
+                self.disk_state.force_update = Some(self.current_widget.widget_id);
+            }
+            return;
+        }
 
-    pub fn clear_search(&mut self) {
         if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -1330,43 +3629,180 @@ impl App {
         }
     }
 
-    pub fn start_killing_process(&mut self) {
+    /// Returns the name and PIDs of the currently selected process, or of every process in the
+    /// selected group if grouping.
+    /// Returns the processes that actions apply to: the tagged processes if there are any, and
+    /// otherwise the highlighted process(es).
+    fn get_selected_process(&self) -> Option<(String, Vec<Pid>)> {
+        self.get_tagged_processes()
+            .or_else(|| self.get_highlighted_process())
+    }
+
+    /// Returns the tagged processes of the current process widget that still exist, along with
+    /// their distinct names joined together.
+    fn get_tagged_processes(&self) -> Option<(String, Vec<Pid>)> {
+        let proc_widget_state = self
+            .proc_state
+            .widget_states
+            .get(&self.current_widget.widget_id)?;
+        if proc_widget_state.tagged_pids.is_empty() {
+            return None;
+        }
+
+        let mut tagged_processes = self
+            .data_collection
+            .process_harvest
+            .iter()
+            .filter(|process| proc_widget_state.tagged_pids.contains(&process.pid))
+            .map(|process| (process.pid, process.name.as_str()))
+            .collect::<Vec<_>>();
+        if tagged_processes.is_empty() {
+            return None;
+        }
+        tagged_processes.sort_unstable();
+
+        let mut names = tagged_processes
+            .iter()
+            .map(|(_pid, name)| *name)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+
+        Some((
+            names.join(", "),
+            tagged_processes
+                .into_iter()
+                .map(|(pid, _name)| pid)
+                .collect(),
+        ))
+    }
+
+    fn get_highlighted_process(&self) -> Option<(String, Vec<Pid>)> {
+        let proc_widget_state = self
+            .proc_state
+            .widget_states
+            .get(&self.current_widget.widget_id)?;
+        let corresponding_filtered_process_list = self
+            .canvas_data
+            .finalized_process_data_map
+            .get(&self.current_widget.widget_id)?;
+        let process = corresponding_filtered_process_list
+            .get(proc_widget_state.scroll_state.current_scroll_position)?;
+
+        if self.is_grouped(self.current_widget.widget_id) {
+            Some((process.name.to_string(), process.group_pids.clone()))
+        } else {
+            Some((process.name.clone(), vec![process.pid]))
+        }
+    }
+
+    /// Tags the highlighted process(es) for batch actions, or untags them if they are all tagged
+    /// already, and then moves down to the next row.
+    pub fn toggle_tag_highlighted_process(&mut self) {
         self.reset_multi_tap_keys();
 
+        if let Some((_name, pids)) = self.get_highlighted_process() {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                let tagged_pids = &mut proc_widget_state.tagged_pids;
+                if pids.iter().all(|pid| tagged_pids.contains(pid)) {
+                    for pid in &pids {
+                        tagged_pids.remove(pid);
+                    }
+                } else {
+                    tagged_pids.extend(pids);
+                }
+            }
+            self.change_process_position(1);
+        }
+    }
+
+    /// Cycles the current process widget between not following the selected process, following
+    /// it, and following it while pinning it to the top.
+    pub fn toggle_follow_process(&mut self) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            proc_widget_state.follow_mode = proc_widget_state.follow_mode.next();
+            self.proc_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Untags every process in the current process widget.
+    pub fn untag_all_processes(&mut self) {
         if let Some(proc_widget_state) = self
             .proc_state
             .widget_states
-            .get(&self.current_widget.widget_id)
+            .get_mut(&self.current_widget.widget_id)
         {
-            if let Some(corresponding_filtered_process_list) = self
-                .canvas_data
-                .finalized_process_data_map
-                .get(&self.current_widget.widget_id)
-            {
-                if proc_widget_state.scroll_state.current_scroll_position
-                    < corresponding_filtered_process_list.len()
-                {
-                    let current_process: (String, Vec<Pid>);
-                    if self.is_grouped(self.current_widget.widget_id) {
-                        if let Some(process) = &corresponding_filtered_process_list
-                            .get(proc_widget_state.scroll_state.current_scroll_position)
-                        {
-                            current_process = (process.name.to_string(), process.group_pids.clone())
-                        } else {
-                            return;
-                        }
-                    } else {
-                        let process = corresponding_filtered_process_list
-                            [proc_widget_state.scroll_state.current_scroll_position]
-                            .clone();
-                        current_process = (process.name.clone(), vec![process.pid])
-                    };
+            proc_widget_state.tagged_pids.clear();
+        }
+    }
 
-                    self.to_delete_process_list = Some(current_process);
-                    self.delete_dialog_state.is_showing_dd = true;
-                    self.is_determining_widget_boundary = true;
-                }
+    pub fn start_killing_process(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some(current_process) = self.get_selected_process() {
+            self.delete_dialog_state.is_killing_tagged = self.get_tagged_processes().is_some();
+            self.to_delete_process_list = Some(current_process);
+            self.delete_dialog_state.is_showing_dd = true;
+            self.is_determining_widget_boundary = true;
+        }
+    }
+
+    /// Suspends the selected process(es) with `SIGSTOP`, showing an error dialog on failure.
+    pub fn suspend_selected_process(&mut self) {
+        self.apply_to_selected_process(
+            ProcessAction::Suspend,
+            process_actions::suspend_process_given_pid,
+        );
+    }
+
+    /// Resumes the selected process(es) with `SIGCONT`, showing an error dialog on failure.
+    pub fn resume_selected_process(&mut self) {
+        self.apply_to_selected_process(
+            ProcessAction::Resume,
+            process_actions::resume_process_given_pid,
+        );
+    }
+
+    fn apply_to_selected_process(&mut self, action: ProcessAction, apply: fn(Pid) -> Result<()>) {
+        self.reset_multi_tap_keys();
+
+        if !self.can_act_on_processes() {
+            return;
+        }
+
+        if let Some((_name, pids)) = self.get_selected_process() {
+            let result = pids.into_iter().try_for_each(apply);
+
+            if let Err(err) = result {
+                self.dd_err = Some(err.to_string());
+                self.delete_dialog_state.failed_action = action;
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_determining_widget_boundary = true;
             }
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Like [`App::start_killing_process`], but also kills every descendant of the selected
+    /// process(es), based on the parent PIDs of the last harvest.
+    pub fn start_killing_process_tree(&mut self) {
+        self.start_killing_process();
+
+        if let Some((_name, pids)) = &mut self.to_delete_process_list {
+            *pids =
+                process_actions::get_process_tree_pids(&self.data_collection.process_harvest, pids);
+            self.delete_dialog_state.is_killing_tree = true;
         }
     }
 
@@ -1388,6 +3824,11 @@ impl App {
             }
             self.last_key_press = current_key_press_inst;
 
+            if self.layout_edit_state.is_editing {
+                self.on_layout_edit_char(caught_char);
+                return;
+            }
+
             if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
                 let is_in_search_widget = self.is_in_search_widget();
                 if let Some(proc_widget_state) = self
@@ -1442,14 +3883,44 @@ impl App {
                         return;
                     }
                 }
+            } else if self.is_in_conn_search() {
+                if let Some(conn_widget_state) = self
+                    .conn_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if UnicodeWidthStr::width(conn_widget_state.search_query.as_str())
+                        <= MAX_SEARCH_LENGTH
+                    {
+                        conn_widget_state.search_query.push(caught_char);
+                        self.conn_state.force_update = Some(self.current_widget.widget_id);
+                    }
+                }
+                return;
+            } else if self.is_in_disk_search() {
+                if let Some(disk_widget_state) = self
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if UnicodeWidthStr::width(disk_widget_state.search_query.as_str())
+                        <= MAX_SEARCH_LENGTH
+                    {
+                        disk_widget_state.search_query.push(caught_char);
+                        self.disk_state.force_update = Some(self.current_widget.widget_id);
+                    }
+                }
+                return;
             }
             self.handle_char(caught_char);
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
-                '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
+                '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
                     let potential_index = caught_char.to_digit(10);
                     if let Some(potential_index) = potential_index {
-                        let potential_index = potential_index as usize;
+                        // 0 comes after 9, like on the keyboard.
+                        let potential_index = match potential_index {
+                            0 => 10,
+                            potential_index => potential_index as usize,
+                        };
                         if (potential_index) < self.help_dialog_state.index_shortcuts.len() {
                             self.help_scroll_to_or_max(
                                 self.help_dialog_state.index_shortcuts[potential_index],
@@ -1460,8 +3931,45 @@ impl App {
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                 _ => {}
             }
+        } else if self.thread_dialog_state.is_showing_threads {
+            if let 'j' | 'k' | 'g' | 'G' = caught_char {
+                self.handle_char(caught_char)
+            }
+        } else if self.process_details_dialog_state.is_showing_details {
+            match caught_char {
+                'j' => self.process_details_scroll_down(),
+                'k' => self.process_details_scroll_up(),
+                _ => {}
+            }
+        } else if self.open_files_dialog_state.is_showing_open_files {
+            match caught_char {
+                'j' => self.change_open_files_position(1),
+                'k' => self.change_open_files_position(-1),
+                _ => {}
+            }
+        } else if self.renice_dialog_state.is_showing_renice {
+            self.on_renice_char(caught_char);
+        } else if self.global_filter.is_editing {
+            if UnicodeWidthStr::width(self.global_filter.input.as_str()) <= MAX_SEARCH_LENGTH {
+                self.global_filter.input.push(caught_char);
+            }
+        } else if self.io_priority_dialog_state.is_showing_io_priority {
+            self.on_io_priority_char(caught_char);
+        } else if self.affinity_dialog_state.is_showing_affinity {
+            self.on_affinity_char(caught_char);
+        } else if self.columns_dialog_state.is_showing_columns {
+            self.on_columns_char(caught_char);
+        } else if self.filters_dialog_state.is_showing_filters {
+            self.on_filters_char(caught_char);
+        } else if self.delete_dialog_state.is_searching_signals {
+            #[cfg(target_family = "unix")]
+            self.on_signal_search_char(caught_char);
         } else if self.delete_dialog_state.is_showing_dd {
             match caught_char {
+                #[cfg(target_family = "unix")]
+                '/' if self.app_config_fields.is_advanced_kill => {
+                    self.delete_dialog_state.is_searching_signals = true;
+                }
                 'h' => self.on_left_key(),
                 'j' => self.on_down_key(),
                 'k' => self.on_up_key(),
@@ -1494,6 +4002,27 @@ impl App {
     }
 
     fn handle_char(&mut self, caught_char: char) {
+        if self.app_config_fields.replay_path.is_some() {
+            // Space tags processes in the process widget, so the replay is only paused with it
+            // elsewhere.
+            let replay_control = match caught_char {
+                ' ' if self.current_widget.widget_type != BottomWidgetType::Proc => {
+                    Some(ReplayControl::TogglePause)
+                }
+                'Z' => Some(ReplayControl::Step),
+                '(' => Some(ReplayControl::Seek(-REPLAY_SEEK_SECONDS)),
+                ')' => Some(ReplayControl::Seek(REPLAY_SEEK_SECONDS)),
+                '0'..='9' => caught_char
+                    .to_digit(10)
+                    .map(|tenth| ReplayControl::SeekToTenth(tenth as u8)),
+                _ => None,
+            };
+            if replay_control.is_some() {
+                self.replay_control = replay_control;
+                return;
+            }
+        }
+
         match caught_char {
             '/' => {
                 self.on_slash();
@@ -1540,6 +4069,13 @@ impl App {
                 self.is_frozen = !self.is_frozen;
                 if self.is_frozen {
                     self.data_collection.set_frozen_time();
+                    for host in &mut self.remote_hosts {
+                        host.data_collection.set_frozen_time();
+                    }
+                }
+                // Graphs that were scrolled back are placed relative to a different time now.
+                if self.data_collection.history_instant.is_some() {
+                    self.force_update_graphs();
                 }
             }
             'C' => {
@@ -1652,13 +4188,115 @@ impl App {
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
             'J' | 'S' => self.move_widget_selection(&WidgetDirection::Down),
             't' => self.toggle_tree_mode(),
+            'X' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.start_killing_process_tree();
+                }
+            }
+            'T' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.open_thread_dialog();
+                }
+            }
+            'E' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.open_columns_dialog();
+                }
+            }
+            'M' => self.toggle_layout_edit_mode(),
+            '<' => self.change_layout_page(-1),
+            '>' => self.change_layout_page(1),
+            'F' => match self.current_widget.widget_type {
+                BottomWidgetType::Proc => self.open_open_files_dialog(),
+                _ => self.open_filters_dialog(),
+            },
+            'U' => match self.current_widget.widget_type {
+                BottomWidgetType::Proc => self.untag_all_processes(),
+                BottomWidgetType::Disk => self.unhide_all_mounts(),
+                _ => {}
+            },
+            #[cfg(target_family = "unix")]
+            'u' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.toggle_grouping_by_user();
+                }
+            }
+            'Y' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.toggle_follow_process();
+                }
+            }
+            'z' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.suspend_selected_process();
+                }
+            }
+            '[' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.change_selected_process_priority(-1);
+                }
+            }
+            ']' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.change_selected_process_priority(1);
+                }
+            }
+            '\\' => self.open_global_filter_dialog(),
+            '{' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.cycle_process_filter(-1);
+                }
+            }
+            '}' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.cycle_process_filter(1);
+                }
+            }
+            'N' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.open_renice_dialog();
+                }
+            }
+            'o' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.open_io_priority_dialog();
+                }
+            }
+            'a' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.open_affinity_dialog();
+                }
+            }
+            'r' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.resume_selected_process();
+                }
+            }
             '+' => self.on_plus(),
             '-' => self.on_minus(),
             '=' => self.reset_zoom(),
+            'v' => self.cycle_graph_style(),
             'e' => self.toggle_expand_widget(),
             's' => self.toggle_sort(),
             'I' => self.invert_sort(),
+            'x' => match self.current_widget.widget_type {
+                BottomWidgetType::Disk => self.toggle_hiding_selected_mount(),
+                BottomWidgetType::Proc | BottomWidgetType::ProcSort => self.toggle_kernel_threads(),
+                _ => {}
+            },
             '%' => self.toggle_percentages(),
+            'i' => self.cycle_network_interface(),
+            'b' => match self.current_widget.widget_type {
+                BottomWidgetType::Mem => self.toggle_stacked_memory(),
+                BottomWidgetType::Net => self.toggle_per_interface_network(),
+                _ => {}
+            },
+            'B' => self.toggle_split_network(),
+            'y' => self.toggle_network_scale(),
+            'O' => self.cycle_cpu_grouping(),
+            ',' => self.scroll_history_back(),
+            '.' => self.scroll_history_forward(),
+            'w' => self.export_widget_history(),
             ' ' => self.on_space(),
             _ => {}
         }
@@ -1670,7 +4308,303 @@ impl App {
         }
     }
 
-    pub fn on_space(&mut self) {}
+    /// Searches the selected process widget for the saved filter `change` filters after the one
+    /// it is searching for, going through no filter at all after the last one.
+    pub fn cycle_process_filter(&mut self, change: i64) {
+        if self.process_filters.is_empty() {
+            self.show_toast(
+                "There are no saved process filters in the config.".to_string(),
+                true,
+            );
+            return;
+        }
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            let process_filters = &self.process_filters;
+            let current_position = process_filters
+                .iter()
+                .position(|process_filter| {
+                    &process_filter.query == proc_widget_state.get_current_search_query()
+                })
+                .unwrap_or(process_filters.len());
+            let new_position = (current_position as i64 + change)
+                .rem_euclid(process_filters.len() as i64 + 1)
+                as usize;
+
+            let text = match process_filters.get(new_position) {
+                Some(process_filter) => {
+                    proc_widget_state.set_search_query(process_filter.query.clone());
+                    proc_widget_state
+                        .process_search_state
+                        .search_state
+                        .is_enabled = true;
+                    format!("Filter: {}", process_filter.name)
+                }
+                None => {
+                    proc_widget_state.clear_search();
+                    "Filter: none".to_string()
+                }
+            };
+
+            self.proc_state.force_update = Some(self.current_widget.widget_id);
+            self.is_force_redraw = true;
+            self.show_toast(text, false);
+        }
+    }
+
+    pub fn on_space(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.toggle_tag_highlighted_process();
+        }
+    }
+
+    /// Cycles the network widgets through showing all interfaces and then each individual interface.
+    pub fn cycle_network_interface(&mut self) {
+        if let BottomWidgetType::Net | BottomWidgetType::BasicNet = self.current_widget.widget_type
+        {
+            let interfaces = &self.data_collection.network_harvest.interfaces;
+            let next_index = match &self.net_state.selected_interface {
+                Some(selected_interface) => interfaces
+                    .iter()
+                    .position(|interface| &interface.name == selected_interface)
+                    .map(|index| index + 1),
+                None => Some(0),
+            };
+
+            self.net_state.selected_interface = next_index
+                .and_then(|index| interfaces.get(index))
+                .map(|interface| interface.name.clone());
+            self.net_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Switches the selected graph to the next graph style.
+    pub fn cycle_graph_style(&mut self) {
+        let widget_type = &self.current_widget.widget_type;
+        let widget_id = self.current_widget.widget_id - widget_type.get_parent_offset();
+        let graph_style = match widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => self
+                .cpu_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.graph_style),
+            BottomWidgetType::Mem => self
+                .mem_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.graph_style),
+            BottomWidgetType::Net => self
+                .net_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.graph_style),
+            BottomWidgetType::Power => self
+                .power_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.graph_style),
+            _ => None,
+        };
+
+        if let Some(graph_style) = graph_style {
+            *graph_style = graph_style.next();
+            let graph_style_name = graph_style.get_name();
+
+            // Keep it in the layout as well, so that saving the layout keeps the style.
+            if let (Some(position), Some(layout_page)) = (
+                self.get_layout_position(&self.current_widget),
+                self.layout_pages.get_mut(self.current_layout_page),
+            ) {
+                let paths = get_final_widget_paths(&layout_page.rows);
+                if let Some(widget) = paths
+                    .get(position)
+                    .and_then(|path| get_final_widget_mut(&mut layout_page.rows, *path))
+                {
+                    widget.graph_style = Some(graph_style_name.to_string());
+                }
+            }
+
+            self.show_toast(format!("Graph style: {}", graph_style_name), false);
+        }
+    }
+
+    /// Toggles between drawing the total usage and one line per interface in the network graph.
+    pub fn toggle_per_interface_network(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            self.net_state.is_per_interface = !self.net_state.is_per_interface;
+            self.net_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Switches between graphing RX and TX together and separately.
+    pub fn toggle_split_network(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            self.net_state.is_split = !self.net_state.is_split;
+            self.net_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Switches the memory graph between separate lines and stacked bands.
+    pub fn toggle_stacked_memory(&mut self) {
+        if let BottomWidgetType::Mem = self.current_widget.widget_type {
+            self.mem_state.is_stacked = !self.mem_state.is_stacked;
+            self.mem_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Cycles how the cores in the CPU legend are grouped.
+    pub fn cycle_cpu_grouping(&mut self) {
+        if let BottomWidgetType::Cpu | BottomWidgetType::CpuLegend = self.current_widget.widget_type
+        {
+            self.cpu_state.grouping = self.cpu_state.grouping.next();
+
+            // The legend is in a different order now, so go back to "All".
+            for cpu_widget_state in self.cpu_state.widget_states.values_mut() {
+                cpu_widget_state.scroll_state.current_scroll_position = 0;
+                cpu_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            }
+            self.cpu_state.force_update = Some(
+                self.current_widget.widget_id - self.current_widget.widget_type.get_parent_offset(),
+            );
+            self.show_toast(
+                format!("CPU grouping: {}", self.cpu_state.grouping.get_name()),
+                false,
+            );
+        }
+    }
+
+    /// Switches the selected network graph between a linear and a logarithmic y-axis.
+    pub fn toggle_network_scale(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            if let Some(net_widget_state) = self
+                .net_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                net_widget_state.scale_type = match net_widget_state.scale_type {
+                    AxisScaling::Log => AxisScaling::Linear,
+                    AxisScaling::Linear => AxisScaling::Log,
+                };
+                // The last automatic maximum was for the other scale.
+                net_widget_state.auto_max_entry = None;
+                self.net_state.force_update = Some(self.current_widget.widget_id);
+
+                let scale_name = match net_widget_state.scale_type {
+                    AxisScaling::Log => "log",
+                    AxisScaling::Linear => "linear",
+                };
+                self.show_toast(format!("Network scale: {}", scale_name), false);
+            }
+        }
+    }
+
+    /// Writes the history of the selected graph, up to where it ends, to a file in the format and
+    /// directory set in the config file.
+    pub fn export_widget_history(&mut self) {
+        let widget_type = &self.current_widget.widget_type;
+        let widget_id = self.current_widget.widget_id - widget_type.get_parent_offset();
+        let host = match widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => self
+                .cpu_state
+                .widget_states
+                .get(&widget_id)
+                .and_then(|widget_state| widget_state.host),
+            BottomWidgetType::Mem => self
+                .mem_state
+                .widget_states
+                .get(&widget_id)
+                .and_then(|widget_state| widget_state.host),
+            _ => None,
+        };
+        let (host_name, data_collection) = match host.and_then(|host| self.remote_hosts.get(host)) {
+            Some(host) => (Some(host.name.as_str()), &host.data_collection),
+            None => (None, &self.data_collection),
+        };
+
+        let history = export::history::get_history(
+            data_collection,
+            widget_type,
+            data_collection.get_graph_end_instant(self.is_frozen),
+        );
+        let toast = match history {
+            Some(history) => match export::history::write_history(
+                &history,
+                self.app_config_fields.history_export_format,
+                &self.app_config_fields.history_export_dir,
+                host_name,
+            ) {
+                Ok(path) => Ok(format!("Exported the history to {}", path.display())),
+                Err(err) => Err(format!("Failed to export the history: {}", err)),
+            },
+            None => Err("The selected widget has no history to export.".to_string()),
+        };
+        match toast {
+            Ok(text) => self.show_toast(text, false),
+            Err(text) => self.show_toast(text, true),
+        }
+    }
+
+    fn is_on_graph(&self) -> bool {
+        matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Cpu
+                | BottomWidgetType::CpuLegend
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net
+                | BottomWidgetType::Power
+        )
+    }
+
+    fn force_update_graphs(&mut self) {
+        let widget_id = Some(self.current_widget.widget_id);
+        self.cpu_state.force_update = widget_id;
+        self.mem_state.force_update = widget_id;
+        self.net_state.force_update = widget_id;
+        self.power_state.force_update = widget_id;
+    }
+
+    /// Scrolls all graphs to end `offset` milliseconds before the latest data.
+    fn set_history_offset(&mut self, offset: u64) {
+        let previous_history_instant = self.data_collection.history_instant;
+        self.data_collection
+            .set_history_offset(self.is_frozen, offset);
+        for host in &mut self.remote_hosts {
+            host.data_collection
+                .set_history_offset(self.is_frozen, offset);
+        }
+        if self.data_collection.history_instant != previous_history_instant {
+            self.force_update_graphs();
+        }
+    }
+
+    /// Scrolls the graphs back in time, to older data that is still kept.
+    pub fn scroll_history_back(&mut self) {
+        if self.is_on_graph() {
+            let offset = self.data_collection.get_history_offset(self.is_frozen);
+            self.set_history_offset(offset + self.app_config_fields.time_interval);
+        }
+    }
+
+    /// Scrolls the graphs forward in time, back to following the latest data at the end.
+    pub fn scroll_history_forward(&mut self) {
+        if self.is_on_graph() {
+            let offset = self.data_collection.get_history_offset(self.is_frozen);
+            self.set_history_offset(offset.saturating_sub(self.app_config_fields.time_interval));
+        }
+    }
+
+    /// Dragging a graph to the right scrolls back in time, as if pulling the older data into view.
+    pub fn on_left_mouse_drag(&mut self, x: u16) {
+        if let Some(history_drag) = &self.history_drag {
+            let columns = f64::from(x) - f64::from(history_drag.start_x);
+            let offset =
+                history_drag.start_offset as f64 + columns * history_drag.millis_per_column;
+            self.set_history_offset(offset.max(0.0) as u64);
+        }
+    }
+
+    pub fn on_left_mouse_release(&mut self) {
+        self.history_drag = None;
+    }
 
     pub fn open_config_screen(&mut self) {
         self.is_config_open = true;
@@ -1709,18 +4643,30 @@ impl App {
         if let BottomWidgetType::Proc = self.current_widget.widget_type {
             if let Some(current_selected_processes) = &self.to_delete_process_list {
                 #[cfg(target_family = "unix")]
-                let signal = match self.delete_dialog_state.selected_signal {
-                    KillSignal::Kill(sig) => sig,
-                    KillSignal::Cancel => 15, // should never happen, so just TERM
-                };
-                for pid in &current_selected_processes.1 {
-                    #[cfg(target_family = "unix")]
-                    {
-                        process_killer::kill_process_given_pid(*pid, signal)?;
+                {
+                    let signal = match self.delete_dialog_state.selected_signal {
+                        KillSignal::Kill(sig) => sig,
+                        KillSignal::Cancel => 15, // should never happen, so just TERM
+                    };
+                    for (itx, pid) in current_selected_processes.1.iter().enumerate() {
+                        if let Err(err) = process_actions::kill_process_given_pid(*pid, signal) {
+                            // Offer to retry the rest as root instead.
+                            if let BottomError::PermissionError(_) = err {
+                                if self.app_config_fields.privilege_helper.is_some() {
+                                    self.delete_dialog_state.privileged_retry = Some((
+                                        current_selected_processes.1[itx..].to_vec(),
+                                        signal,
+                                    ));
+                                }
+                            }
+                            return Err(err);
+                        }
                     }
-                    #[cfg(target_os = "windows")]
-                    {
-                        process_killer::kill_process_given_pid(*pid)?;
+                }
+                #[cfg(target_os = "windows")]
+                {
+                    for pid in &current_selected_processes.1 {
+                        process_actions::kill_process_given_pid(*pid)?;
                     }
                 }
             }
@@ -1748,12 +4694,14 @@ impl App {
     }
 
     fn expand_widget(&mut self) {
-        // TODO: [BASIC] Expansion in basic mode.
-        if !self.ignore_normal_keybinds() && !self.app_config_fields.use_basic_mode {
-            // Pop-out mode.  We ignore if in process search.
+        if !self.ignore_normal_keybinds() {
+            // Pop-out mode.  We ignore if in process search, as well as anything with nothing of
+            // its own to show.
 
             match self.current_widget.widget_type {
-                BottomWidgetType::ProcSearch => {}
+                BottomWidgetType::ProcSearch
+                | BottomWidgetType::Empty
+                | BottomWidgetType::BasicTables => {}
                 _ => {
                     self.is_expanded = true;
                     self.is_force_redraw = true;
@@ -2241,6 +5189,78 @@ impl App {
                         temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
                     }
                 }
+                BottomWidgetType::Users => {
+                    if let Some(users_widget_state) = self
+                        .users_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        users_widget_state.scroll_state.current_scroll_position = 0;
+                        users_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Activity => {
+                    if let Some(activity_widget_state) = self
+                        .activity_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        activity_widget_state.scroll_state.current_scroll_position = 0;
+                        activity_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Pods => {
+                    if let Some(pods_widget_state) = self
+                        .pods_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        pods_widget_state.scroll_state.current_scroll_position = 0;
+                        pods_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Vm => {
+                    if let Some(vm_widget_state) = self
+                        .vm_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        vm_widget_state.scroll_state.current_scroll_position = 0;
+                        vm_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Numa => {
+                    if let Some(numa_widget_state) = self
+                        .numa_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        numa_widget_state.scroll_state.current_scroll_position = 0;
+                        numa_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Units => {
+                    if let Some(units_widget_state) = self
+                        .units_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        units_widget_state.scroll_state.current_scroll_position = 0;
+                        units_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::KernelLog => {
+                    if let Some(kernel_log_widget_state) = self
+                        .kernel_log_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        kernel_log_widget_state.scroll_state.current_scroll_position = 0;
+                        kernel_log_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Alerts => {
+                    if let Some(alerts_widget_state) = self
+                        .alerts_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        alerts_widget_state.scroll_state.current_scroll_position = 0;
+                        alerts_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
                 BottomWidgetType::Disk => {
                     if let Some(disk_widget_state) = self
                         .disk_state
@@ -2250,6 +5270,15 @@ impl App {
                         disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
                     }
                 }
+                BottomWidgetType::Conn => {
+                    if let Some(conn_widget_state) = self
+                        .conn_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        conn_widget_state.scroll_state.current_scroll_position = 0;
+                        conn_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
                 BottomWidgetType::CpuLegend => {
                     if let Some(cpu_widget_state) = self
                         .cpu_state
@@ -2266,6 +5295,11 @@ impl App {
         } else if self.is_config_open {
         } else if self.help_dialog_state.is_showing_help {
             self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        } else if self.thread_dialog_state.is_showing_threads {
+            self.thread_dialog_state
+                .scroll_state
+                .current_scroll_position = 0;
+            self.thread_dialog_state.scroll_state.scroll_direction = ScrollDirection::Up;
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Cancel;
         }
@@ -2315,24 +5349,154 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Users => {
+                    if let Some(users_widget_state) = self
+                        .users_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.session_data.is_empty() {
+                            users_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.session_data.len() - 1;
+                            users_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Activity => {
+                    if let Some(activity_widget_state) = self
+                        .activity_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.activity_data.is_empty() {
+                            activity_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.activity_data.len() - 1;
+                            activity_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Pods => {
+                    if let Some(pods_widget_state) = self
+                        .pods_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.pod_data.is_empty() {
+                            pods_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.pod_data.len() - 1;
+                            pods_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Vm => {
+                    if let Some(vm_widget_state) = self
+                        .vm_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.vm_data.is_empty() {
+                            vm_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.vm_data.len() - 1;
+                            vm_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Numa => {
+                    if let Some(numa_widget_state) = self
+                        .numa_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.numa_data.is_empty() {
+                            numa_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.numa_data.len() - 1;
+                            numa_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Units => {
+                    if let Some(units_widget_state) = self
+                        .units_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.unit_data.is_empty() {
+                            units_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.unit_data.len() - 1;
+                            units_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::KernelLog => {
+                    if let Some(kernel_log_widget_state) = self
+                        .kernel_log_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.kernel_log_data.is_empty() {
+                            kernel_log_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.kernel_log_data.len() - 1;
+                            kernel_log_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Alerts => {
+                    if let Some(alerts_widget_state) = self
+                        .alerts_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.alert_data.is_empty() {
+                            alerts_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.alert_data.len() - 1;
+                            alerts_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
                 BottomWidgetType::Disk => {
                     if let Some(disk_widget_state) = self
                         .disk_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if !self.canvas_data.disk_data.is_empty() {
-                            disk_widget_state.scroll_state.current_scroll_position =
-                                self.canvas_data.disk_data.len() - 1;
+                        let num_disks = self
+                            .canvas_data
+                            .disk_data
+                            .get(&self.current_widget.widget_id)
+                            .map_or(0, |disk_data| disk_data.len());
+                        if num_disks > 0 {
+                            disk_widget_state.scroll_state.current_scroll_position = num_disks - 1;
                             disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
                         }
                     }
                 }
+                BottomWidgetType::Conn => {
+                    if let Some(conn_widget_state) = self
+                        .conn_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if let Some(connection_data) = self
+                            .canvas_data
+                            .connection_data
+                            .get(&self.current_widget.widget_id)
+                        {
+                            if !connection_data.is_empty() {
+                                conn_widget_state.scroll_state.current_scroll_position =
+                                    connection_data.len() - 1;
+                                conn_widget_state.scroll_state.scroll_direction =
+                                    ScrollDirection::Down;
+                            }
+                        }
+                    }
+                }
                 BottomWidgetType::CpuLegend => {
                     if let Some(cpu_widget_state) = self
                         .cpu_state
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
                     {
-                        let cap = self.canvas_data.cpu_data.len();
+                        let (_host_name, _data_collection, canvas_data) = get_host_data(
+                            &self.data_collection,
+                            &mut self.canvas_data,
+                            &mut self.remote_hosts,
+                            cpu_widget_state.host,
+                        );
+                        let cap = canvas_data.cpu_legend_entries.len();
                         if cap > 0 {
                             cpu_widget_state.scroll_state.current_scroll_position = cap - 1;
                             cpu_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
@@ -2349,11 +5513,44 @@ impl App {
                 .scroll_state
                 .max_scroll_index
                 .saturating_sub(1);
+        } else if self.thread_dialog_state.is_showing_threads {
+            self.thread_dialog_state
+                .scroll_state
+                .current_scroll_position = self.canvas_data.thread_data.len().saturating_sub(1);
+            self.thread_dialog_state.scroll_state.scroll_direction = ScrollDirection::Down;
         } else if self.delete_dialog_state.is_showing_dd {
+            #[cfg(target_family = "unix")]
+            if self.app_config_fields.is_advanced_kill {
+                self.change_signal_position(i64::MAX);
+                return;
+            }
+
             self.delete_dialog_state.selected_signal = KillSignal::Kill(MAX_SIGNAL);
         }
     }
 
+    /// Replaces the process search query with an older query from the search history if `amount`
+    /// is negative, or a newer one if it is positive.
+    fn change_search_history_position(&mut self, amount: i64) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            let query = if amount < 0 {
+                self.search_history
+                    .select_previous(proc_widget_state.get_current_search_query())
+            } else {
+                self.search_history.select_next()
+            };
+
+            if let Some(query) = query {
+                proc_widget_state.set_search_query(query.to_string());
+                self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+            }
+        }
+    }
+
     pub fn decrement_position_count(&mut self) {
         self.change_position_count(-1);
     }
@@ -2369,8 +5566,18 @@ impl App {
                     self.change_process_position(amount);
                 }
                 BottomWidgetType::ProcSort => self.change_process_sort_position(amount),
+                BottomWidgetType::ProcSearch => self.change_search_history_position(amount),
                 BottomWidgetType::Temp => self.change_temp_position(amount),
+                BottomWidgetType::Users => self.change_users_position(amount),
+                BottomWidgetType::Activity => self.change_activity_position(amount),
+                BottomWidgetType::Pods => self.change_pods_position(amount),
+                BottomWidgetType::Vm => self.change_vm_position(amount),
+                BottomWidgetType::Numa => self.change_numa_position(amount),
+                BottomWidgetType::Units => self.change_units_position(amount),
+                BottomWidgetType::KernelLog => self.change_kernel_log_position(amount),
+                BottomWidgetType::Alerts => self.change_alerts_position(amount),
                 BottomWidgetType::Disk => self.increment_disk_position(amount),
+                BottomWidgetType::Conn => self.change_conn_position(amount),
                 BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
                 _ => {}
             }
@@ -2410,7 +5617,13 @@ impl App {
         {
             let current_posn = cpu_widget_state.scroll_state.current_scroll_position;
 
-            let cap = self.canvas_data.cpu_data.len();
+            let (_host_name, _data_collection, canvas_data) = get_host_data(
+                &self.data_collection,
+                &mut self.canvas_data,
+                &mut self.remote_hosts,
+                cpu_widget_state.host,
+            );
+            let cap = canvas_data.cpu_legend_entries.len();
             if current_posn as i64 + num_to_change_by < 0 {
                 cpu_widget_state.scroll_state.current_scroll_position = 0;
             } else if current_posn as i64 + num_to_change_by >= cap as i64 {
@@ -2454,41 +5667,265 @@ impl App {
             }
 
             if num_to_change_by < 0 {
-                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+
+            return Some(proc_widget_state.scroll_state.current_scroll_position);
+        }
+
+        None
+    }
+
+    fn change_temp_position(&mut self, num_to_change_by: i64) {
+        if let Some(temp_widget_state) = self
+            .temp_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = temp_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                temp_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.temp_sensor_data.len() as i64
+            {
+                temp_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.temp_sensor_data.len().saturating_sub(1);
+            } else {
+                temp_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_users_position(&mut self, num_to_change_by: i64) {
+        if let Some(users_widget_state) = self
+            .users_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = users_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                users_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.session_data.len() as i64
+            {
+                users_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.session_data.len().saturating_sub(1);
+            } else {
+                users_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                users_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                users_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_activity_position(&mut self, num_to_change_by: i64) {
+        if let Some(activity_widget_state) = self
+            .activity_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = activity_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                activity_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.activity_data.len() as i64
+            {
+                activity_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.activity_data.len().saturating_sub(1);
+            } else {
+                activity_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                activity_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                activity_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_pods_position(&mut self, num_to_change_by: i64) {
+        if let Some(pods_widget_state) = self
+            .pods_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = pods_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                pods_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.pod_data.len() as i64
+            {
+                pods_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.pod_data.len().saturating_sub(1);
+            } else {
+                pods_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                pods_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                pods_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_vm_position(&mut self, num_to_change_by: i64) {
+        if let Some(vm_widget_state) = self
+            .vm_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = vm_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                vm_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.vm_data.len() as i64
+            {
+                vm_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.vm_data.len().saturating_sub(1);
+            } else {
+                vm_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                vm_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                vm_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_numa_position(&mut self, num_to_change_by: i64) {
+        if let Some(numa_widget_state) = self
+            .numa_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = numa_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                numa_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.numa_data.len() as i64
+            {
+                numa_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.numa_data.len().saturating_sub(1);
+            } else {
+                numa_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                numa_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                numa_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_units_position(&mut self, num_to_change_by: i64) {
+        if let Some(units_widget_state) = self
+            .units_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = units_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                units_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.unit_data.len() as i64
+            {
+                units_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.unit_data.len().saturating_sub(1);
+            } else {
+                units_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                units_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                units_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn change_kernel_log_position(&mut self, num_to_change_by: i64) {
+        if let Some(kernel_log_widget_state) = self
+            .kernel_log_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = kernel_log_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                kernel_log_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by
+                >= self.canvas_data.kernel_log_data.len() as i64
+            {
+                kernel_log_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.kernel_log_data.len().saturating_sub(1);
+            } else {
+                kernel_log_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                kernel_log_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                kernel_log_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
             }
-
-            return Some(proc_widget_state.scroll_state.current_scroll_position);
         }
-
-        None
     }
 
-    fn change_temp_position(&mut self, num_to_change_by: i64) {
-        if let Some(temp_widget_state) = self
-            .temp_state
+    fn change_alerts_position(&mut self, num_to_change_by: i64) {
+        if let Some(alerts_widget_state) = self
+            .alerts_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
         {
-            let current_posn = temp_widget_state.scroll_state.current_scroll_position;
+            let current_posn = alerts_widget_state.scroll_state.current_scroll_position;
 
             if current_posn as i64 + num_to_change_by < 0 {
-                temp_widget_state.scroll_state.current_scroll_position = 0;
+                alerts_widget_state.scroll_state.current_scroll_position = 0;
             } else if current_posn as i64 + num_to_change_by
-                >= self.canvas_data.temp_sensor_data.len() as i64
+                >= self.canvas_data.alert_data.len() as i64
             {
-                temp_widget_state.scroll_state.current_scroll_position =
-                    self.canvas_data.temp_sensor_data.len().saturating_sub(1);
+                alerts_widget_state.scroll_state.current_scroll_position =
+                    self.canvas_data.alert_data.len().saturating_sub(1);
             } else {
-                temp_widget_state.scroll_state.current_scroll_position =
+                alerts_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
             }
 
             if num_to_change_by < 0 {
-                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                alerts_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                alerts_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
             }
         }
     }
@@ -2501,8 +5938,13 @@ impl App {
         {
             let current_posn = disk_widget_state.scroll_state.current_scroll_position;
 
+            let num_disks = self
+                .canvas_data
+                .disk_data
+                .get(&self.current_widget.widget_id)
+                .map_or(0, |disk_data| disk_data.len());
             if current_posn as i64 + num_to_change_by >= 0
-                && current_posn as i64 + num_to_change_by < self.canvas_data.disk_data.len() as i64
+                && current_posn as i64 + num_to_change_by < num_disks as i64
             {
                 disk_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
@@ -2516,6 +5958,38 @@ impl App {
         }
     }
 
+    fn change_conn_position(&mut self, num_to_change_by: i64) {
+        if let Some(conn_widget_state) = self
+            .conn_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let num_connections = self
+                .canvas_data
+                .connection_data
+                .get(&self.current_widget.widget_id)
+                .map(|connection_data| connection_data.len())
+                .unwrap_or(0);
+            let current_posn = conn_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by < 0 {
+                conn_widget_state.scroll_state.current_scroll_position = 0;
+            } else if current_posn as i64 + num_to_change_by >= num_connections as i64 {
+                conn_widget_state.scroll_state.current_scroll_position =
+                    num_connections.saturating_sub(1);
+            } else {
+                conn_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                conn_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                conn_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
     fn help_scroll_up(&mut self) {
         if self.help_dialog_state.scroll_state.current_scroll_index > 0 {
             self.help_dialog_state.scroll_state.current_scroll_index -= 1;
@@ -2539,7 +6013,25 @@ impl App {
         }
     }
 
-    pub fn handle_scroll_up(&mut self) {
+    /// Returns the graph widget at `x` and `y`, if there is one that is drawn there.
+    fn get_hovered_graph(&self, x: u16, y: u16) -> Option<(BottomWidgetType, u64)> {
+        self.widget_map
+            .iter()
+            .find(|(_widget_id, widget)| {
+                if let (Some((tlc_x, tlc_y)), Some((brc_x, brc_y))) =
+                    (widget.top_left_corner, widget.bottom_right_corner)
+                {
+                    widget.widget_type.is_widget_graph()
+                        && (x >= tlc_x && y >= tlc_y)
+                        && (x < brc_x && y < brc_y)
+                } else {
+                    false
+                }
+            })
+            .map(|(widget_id, widget)| (widget.widget_type.clone(), *widget_id))
+    }
+
+    pub fn handle_scroll_up(&mut self, x: u16, y: u16) {
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -2549,6 +6041,19 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.thread_dialog_state.is_showing_threads {
+            self.change_thread_position(-1);
+        } else if self.process_details_dialog_state.is_showing_details {
+            self.process_details_scroll_up();
+        } else if self.open_files_dialog_state.is_showing_open_files {
+            self.change_open_files_position(-1);
+        } else if self.columns_dialog_state.is_showing_columns {
+            self.change_columns_position(-1);
+        } else if self.filters_dialog_state.is_showing_filters {
+            self.change_filters_position(-1);
+        } else if let Some((widget_type, widget_id)) = self.get_hovered_graph(x, y) {
+            // The graph under the mouse is zoomed, even if another widget is selected.
+            self.zoom_widget_in(&widget_type, widget_id);
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_in();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2556,7 +6061,7 @@ impl App {
         }
     }
 
-    pub fn handle_scroll_down(&mut self) {
+    pub fn handle_scroll_down(&mut self, x: u16, y: u16) {
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -2566,6 +6071,18 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.thread_dialog_state.is_showing_threads {
+            self.change_thread_position(1);
+        } else if self.process_details_dialog_state.is_showing_details {
+            self.process_details_scroll_down();
+        } else if self.open_files_dialog_state.is_showing_open_files {
+            self.change_open_files_position(1);
+        } else if self.columns_dialog_state.is_showing_columns {
+            self.change_columns_position(1);
+        } else if self.filters_dialog_state.is_showing_filters {
+            self.change_filters_position(1);
+        } else if let Some((widget_type, widget_id)) = self.get_hovered_graph(x, y) {
+            self.zoom_widget_out(&widget_type, widget_id);
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_out();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2620,19 +6137,94 @@ impl App {
         }
     }
 
+    /// Collapses the selected branch in tree mode, or selects its parent if it is already collapsed
+    /// or has no children.
+    fn collapse_process_branch(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        if let Some(proc_widget_state) = self.proc_state.widget_states.get_mut(&widget_id) {
+            if !proc_widget_state.is_tree_mode {
+                return;
+            }
+
+            if let Some(displayed_process_list) =
+                self.canvas_data.finalized_process_data_map.get(&widget_id)
+            {
+                let current_posn = proc_widget_state.scroll_state.current_scroll_position;
+                if let Some(corresponding_process) = displayed_process_list.get(current_posn) {
+                    let corresponding_pid = corresponding_process.pid;
+
+                    // Children are listed right after their parent unless they are hidden.
+                    let has_shown_children = displayed_process_list
+                        .get(current_posn + 1)
+                        .map(|next_process| next_process.ppid == Some(corresponding_pid))
+                        .unwrap_or(false);
+
+                    if has_shown_children {
+                        if let Some(process_data) = self
+                            .canvas_data
+                            .single_process_data
+                            .get_mut(&corresponding_pid)
+                        {
+                            process_data.is_collapsed_entry = true;
+                            self.proc_state.force_update = Some(widget_id);
+                        }
+                    } else if let Some(parent_posn) = displayed_process_list[..current_posn]
+                        .iter()
+                        .rposition(|process| Some(process.pid) == corresponding_process.ppid)
+                    {
+                        proc_widget_state.scroll_state.current_scroll_position = parent_posn;
+                        proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expands the selected branch in tree mode if it is collapsed.
+    fn expand_process_branch(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        if let Some(proc_widget_state) = self.proc_state.widget_states.get(&widget_id) {
+            if !proc_widget_state.is_tree_mode {
+                return;
+            }
+
+            let current_posn = proc_widget_state.scroll_state.current_scroll_position;
+            if let Some(corresponding_process) = self
+                .canvas_data
+                .finalized_process_data_map
+                .get(&widget_id)
+                .and_then(|displayed_process_list| displayed_process_list.get(current_posn))
+            {
+                if let Some(process_data) = self
+                    .canvas_data
+                    .single_process_data
+                    .get_mut(&corresponding_process.pid)
+                {
+                    if process_data.is_collapsed_entry {
+                        process_data.is_collapsed_entry = false;
+                        self.proc_state.force_update = Some(widget_id);
+                    }
+                }
+            }
+        }
+    }
+
     fn zoom_out(&mut self) {
-        match self.current_widget.widget_type {
+        self.zoom_widget_out(
+            &self.current_widget.widget_type.clone(),
+            self.current_widget.widget_id,
+        );
+    }
+
+    fn zoom_widget_out(&mut self, widget_type: &BottomWidgetType, widget_id: u64) {
+        match widget_type {
             BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .cpu_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
+                if let Some(cpu_widget_state) = self.cpu_state.widget_states.get_mut(&widget_id) {
                     let new_time = cpu_widget_state.current_display_time
                         + self.app_config_fields.time_interval;
                     if new_time <= constants::STALE_MAX_MILLISECONDS {
                         cpu_widget_state.current_display_time = new_time;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        self.cpu_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2640,7 +6232,7 @@ impl App {
                         != constants::STALE_MAX_MILLISECONDS
                     {
                         cpu_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        self.cpu_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2648,16 +6240,12 @@ impl App {
                 }
             }
             BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .mem_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
+                if let Some(mem_widget_state) = self.mem_state.widget_states.get_mut(&widget_id) {
                     let new_time = mem_widget_state.current_display_time
                         + self.app_config_fields.time_interval;
                     if new_time <= constants::STALE_MAX_MILLISECONDS {
                         mem_widget_state.current_display_time = new_time;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        self.mem_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2665,7 +6253,7 @@ impl App {
                         != constants::STALE_MAX_MILLISECONDS
                     {
                         mem_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        self.mem_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2673,16 +6261,12 @@ impl App {
                 }
             }
             BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .net_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
+                if let Some(net_widget_state) = self.net_state.widget_states.get_mut(&widget_id) {
                     let new_time = net_widget_state.current_display_time
                         + self.app_config_fields.time_interval;
                     if new_time <= constants::STALE_MAX_MILLISECONDS {
                         net_widget_state.current_display_time = new_time;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        self.net_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             net_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2690,30 +6274,55 @@ impl App {
                         != constants::STALE_MAX_MILLISECONDS
                     {
                         net_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        self.net_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             net_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
             }
+            BottomWidgetType::Power => {
+                if let Some(power_widget_state) = self.power_state.widget_states.get_mut(&widget_id)
+                {
+                    let new_time = power_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= constants::STALE_MAX_MILLISECONDS {
+                        power_widget_state.current_display_time = new_time;
+                        self.power_state.force_update = Some(widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if power_widget_state.current_display_time
+                        != constants::STALE_MAX_MILLISECONDS
+                    {
+                        power_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
+                        self.power_state.force_update = Some(widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             _ => {}
         }
     }
 
     fn zoom_in(&mut self) {
-        match self.current_widget.widget_type {
+        self.zoom_widget_in(
+            &self.current_widget.widget_type.clone(),
+            self.current_widget.widget_id,
+        );
+    }
+
+    fn zoom_widget_in(&mut self, widget_type: &BottomWidgetType, widget_id: u64) {
+        match widget_type {
             BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .cpu_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
+                if let Some(cpu_widget_state) = self.cpu_state.widget_states.get_mut(&widget_id) {
                     let new_time = cpu_widget_state.current_display_time
                         - self.app_config_fields.time_interval;
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
                         cpu_widget_state.current_display_time = new_time;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        self.cpu_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2721,7 +6330,7 @@ impl App {
                         != constants::STALE_MIN_MILLISECONDS
                     {
                         cpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        self.cpu_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2729,16 +6338,12 @@ impl App {
                 }
             }
             BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .mem_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
+                if let Some(mem_widget_state) = self.mem_state.widget_states.get_mut(&widget_id) {
                     let new_time = mem_widget_state.current_display_time
                         - self.app_config_fields.time_interval;
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
                         mem_widget_state.current_display_time = new_time;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        self.mem_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2746,7 +6351,7 @@ impl App {
                         != constants::STALE_MIN_MILLISECONDS
                     {
                         mem_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        self.mem_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2754,16 +6359,12 @@ impl App {
                 }
             }
             BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .net_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
+                if let Some(net_widget_state) = self.net_state.widget_states.get_mut(&widget_id) {
                     let new_time = net_widget_state.current_display_time
                         - self.app_config_fields.time_interval;
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
                         net_widget_state.current_display_time = new_time;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        self.net_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             net_widget_state.autohide_timer = Some(Instant::now());
                         }
@@ -2771,13 +6372,35 @@ impl App {
                         != constants::STALE_MIN_MILLISECONDS
                     {
                         net_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        self.net_state.force_update = Some(widget_id);
                         if self.app_config_fields.autohide_time {
                             net_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
             }
+            BottomWidgetType::Power => {
+                if let Some(power_widget_state) = self.power_state.widget_states.get_mut(&widget_id)
+                {
+                    let new_time = power_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        power_widget_state.current_display_time = new_time;
+                        self.power_state.force_update = Some(widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if power_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        power_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
+                        self.power_state.force_update = Some(widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -2824,15 +6447,64 @@ impl App {
         }
     }
 
+    fn reset_power_zoom(&mut self) {
+        if let Some(power_widget_state) = self
+            .power_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            power_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.power_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                power_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
     fn reset_zoom(&mut self) {
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => self.reset_cpu_zoom(),
             BottomWidgetType::Mem => self.reset_mem_zoom(),
             BottomWidgetType::Net => self.reset_net_zoom(),
+            BottomWidgetType::Power => self.reset_power_zoom(),
             _ => {}
         }
     }
 
+    /// Returns where a drag of the current widget from column `x` starts, if it is a graph.
+    fn get_history_drag(&self, x: u16) -> Option<HistoryDrag> {
+        let widget_id = self.current_widget.widget_id;
+        let current_display_time = match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => self
+                .cpu_state
+                .get_widget_state(widget_id)
+                .map(|widget_state| widget_state.current_display_time),
+            BottomWidgetType::Mem => self
+                .mem_state
+                .get_widget_state(widget_id)
+                .map(|widget_state| widget_state.current_display_time),
+            BottomWidgetType::Net => self
+                .net_state
+                .get_widget_state(widget_id)
+                .map(|widget_state| widget_state.current_display_time),
+            BottomWidgetType::Power => self
+                .power_state
+                .get_widget_state(widget_id)
+                .map(|widget_state| widget_state.current_display_time),
+            _ => None,
+        }?;
+
+        let (tlc_x, _tlc_y) = self.current_widget.top_left_corner?;
+        let (brc_x, _brc_y) = self.current_widget.bottom_right_corner?;
+        let width = brc_x.saturating_sub(tlc_x).max(1);
+
+        Some(HistoryDrag {
+            start_x: x,
+            start_offset: self.data_collection.get_history_offset(self.is_frozen),
+            millis_per_column: current_display_time as f64 / f64::from(width),
+        })
+    }
+
     /// Moves the mouse to the widget that was clicked on, then propagates the click down to be
     /// handled by the widget specifically.
     pub fn on_left_mouse_up(&mut self, x: u16, y: u16) {
@@ -2917,12 +6589,8 @@ impl App {
                 Some((_, _, _, _, 0)) => {
                     self.delete_dialog_state.selected_signal = KillSignal::Cancel
                 }
-                Some((_, _, _, _, idx)) => {
-                    if *idx > 31 {
-                        self.delete_dialog_state.selected_signal = KillSignal::Kill(*idx + 2)
-                    } else {
-                        self.delete_dialog_state.selected_signal = KillSignal::Kill(*idx)
-                    }
+                Some((_, _, _, _, signal)) => {
+                    self.delete_dialog_state.selected_signal = KillSignal::Kill(*signal)
                 }
                 _ => {}
             }
@@ -2967,6 +6635,32 @@ impl App {
             return;
         }
 
+        self.history_drag = self.get_history_drag(x);
+
+        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+            let clicked_option = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id - 1)
+                .and_then(|proc_widget_state| {
+                    proc_widget_state
+                        .process_search_state
+                        .option_locs
+                        .iter()
+                        .position(|(x_start, x_end, option_y)| {
+                            x >= *x_start && x < *x_end && y == *option_y
+                        })
+                });
+
+            match clicked_option {
+                Some(0) => self.toggle_ignore_case(),
+                Some(1) => self.toggle_search_whole_word(),
+                Some(2) => self.toggle_search_regex(),
+                Some(3) => self.toggle_search_fuzzy(),
+                _ => {}
+            }
+            return;
+        }
+
         // Now handle click propagation down to widget.
         if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
             &self.current_widget.top_left_corner,
@@ -2981,7 +6675,16 @@ impl App {
                     | BottomWidgetType::ProcSort
                     | BottomWidgetType::CpuLegend
                     | BottomWidgetType::Temp
-                    | BottomWidgetType::Disk => {
+                    | BottomWidgetType::Disk
+                    | BottomWidgetType::Conn
+                    | BottomWidgetType::Users
+                    | BottomWidgetType::Activity
+                    | BottomWidgetType::Pods
+                    | BottomWidgetType::Vm
+                    | BottomWidgetType::Numa
+                    | BottomWidgetType::Units
+                    | BottomWidgetType::KernelLog
+                    | BottomWidgetType::Alerts => {
                         // Get our index...
                         let clicked_entry = y - *tlc_y;
                         // + 1 so we start at 0.
@@ -3068,6 +6771,122 @@ impl App {
                                         }
                                     }
                                 }
+                                BottomWidgetType::Users => {
+                                    if let Some(users_widget_state) = self
+                                        .users_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            users_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_users_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Activity => {
+                                    if let Some(activity_widget_state) = self
+                                        .activity_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) = activity_widget_state
+                                            .scroll_state
+                                            .table_state
+                                            .selected()
+                                        {
+                                            self.change_activity_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Pods => {
+                                    if let Some(pods_widget_state) = self
+                                        .pods_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            pods_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_pods_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Vm => {
+                                    if let Some(vm_widget_state) = self
+                                        .vm_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            vm_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_vm_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Numa => {
+                                    if let Some(numa_widget_state) = self
+                                        .numa_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            numa_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_numa_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Units => {
+                                    if let Some(units_widget_state) = self
+                                        .units_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            units_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_units_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::KernelLog => {
+                                    if let Some(kernel_log_widget_state) = self
+                                        .kernel_log_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) = kernel_log_widget_state
+                                            .scroll_state
+                                            .table_state
+                                            .selected()
+                                        {
+                                            self.change_kernel_log_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Alerts => {
+                                    if let Some(alerts_widget_state) = self
+                                        .alerts_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            alerts_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_alerts_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
                                 BottomWidgetType::Disk => {
                                     if let Some(disk_widget_state) = self
                                         .disk_state
@@ -3082,6 +6901,20 @@ impl App {
                                         }
                                     }
                                 }
+                                BottomWidgetType::Conn => {
+                                    if let Some(conn_widget_state) = self
+                                        .conn_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            conn_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.change_conn_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
                                 _ => {}
                             }
                         } else {
@@ -3169,3 +7002,59 @@ impl App {
         }
     }
 }
+
+/// Returns the IDs of the widgets that correspond to the widgets of the layout rows, in the same
+/// order, leaving out the other parts of them like the legends of CPU widgets.
+fn get_layout_widget_ids<'a>(widgets: impl Iterator<Item = &'a BottomWidget>) -> Vec<u64> {
+    let mut widget_ids: Vec<u64> = widgets
+        .filter(|widget| widget.widget_type.get_parent_offset() == 0)
+        .map(|widget| widget.widget_id)
+        .collect();
+    widget_ids.sort_unstable();
+
+    widget_ids
+}
+
+fn get_changed_ratio(ratio: Option<u32>, change: i32) -> u32 {
+    (ratio.unwrap_or(1) as i32 + change).max(1) as u32
+}
+
+fn remap_widget_ids<T>(widget_values: &mut HashMap<u64, T>, id_map: &HashMap<u64, u64>) {
+    *widget_values = widget_values
+        .drain()
+        .filter_map(|(widget_id, value)| id_map.get(&widget_id).map(|new_id| (*new_id, value)))
+        .collect();
+}
+
+fn get_process_column_configs(
+    columns: &[(processes::ProcessSorting, Option<u16>)],
+) -> Vec<ProcessColumnConfig> {
+    columns
+        .iter()
+        .map(|(column, width)| ProcessColumnConfig {
+            name: column.to_string(),
+            width: *width,
+        })
+        .collect()
+}
+
+/// Returns the entry of a filter's list that matches exactly `name`, for the filters dialog.
+fn get_filters_dialog_list_entry(ignore_list: &IgnoreList, name: &str) -> String {
+    if ignore_list.regex || ignore_list.glob || !ignore_list.whole_word {
+        format!("regex:{}", regex::escape(name))
+    } else {
+        name.to_string()
+    }
+}
+
+/// Returns the name that an entry of a filter's list matches exactly, if it only matches one.
+fn get_filters_dialog_name(ignore_list: &IgnoreList, list_entry: &str) -> Option<String> {
+    if let Some(pattern) = list_entry.strip_prefix("regex:") {
+        let name = pattern.replace('\\', "");
+        (regex::escape(&name) == pattern).then_some(name)
+    } else if ignore_list.regex || ignore_list.glob || list_entry.starts_with("glob:") {
+        None
+    } else {
+        Some(list_entry.to_string())
+    }
+}
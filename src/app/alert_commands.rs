@@ -0,0 +1,173 @@
+//! Running the commands of alert rules when they start and stop alerting, so that alerts can be
+//! sent on to pagers, webhooks, and the like without bottom having to support each of them.  The
+//! details of the alert are passed to the command in environment variables.
+
+use std::{
+    io,
+    process::{Command, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::app::metric_alerts::{AlertTransition, MetricAlerts};
+
+/// Returns the command that runs `command_line` with the shell of the OS.
+fn get_shell_command(command_line: &str) -> Command {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            let mut command = Command::new("cmd");
+            command.args(["/C", command_line]);
+        } else {
+            let mut command = Command::new("sh");
+            command.args(["-c", command_line]);
+        }
+    }
+    command
+}
+
+/// Returns the command to run for `transition`, with the details of the alert set in its
+/// environment, or `None` if its rule has no command.
+fn get_alert_command(
+    metric_alerts: &MetricAlerts, transition: &AlertTransition, time: u64,
+) -> Option<Command> {
+    let rule = &metric_alerts.get_rules()[transition.rule_index];
+    let mut command = get_shell_command(rule.command.as_deref()?);
+    let state = if transition.is_firing {
+        "firing"
+    } else {
+        "resolved"
+    };
+
+    command
+        .env("BTM_ALERT_NAME", &rule.name)
+        .env("BTM_ALERT_STATE", state)
+        .env("BTM_ALERT_SEVERITY", rule.severity.get_name())
+        .env("BTM_ALERT_METRIC", rule.metric.get_key())
+        .env("BTM_ALERT_COMPARISON", rule.comparison.get_symbol())
+        .env("BTM_ALERT_THRESHOLD", rule.threshold.to_string())
+        .env("BTM_ALERT_DURATION", rule.duration.as_secs().to_string())
+        .env(
+            "BTM_ALERT_RULE",
+            metric_alerts.describe_rule(transition.rule_index),
+        )
+        .env("BTM_ALERT_TIME", time.to_string());
+    if let Some(value) = transition.value {
+        command.env("BTM_ALERT_VALUE", value.to_string()).env(
+            "BTM_ALERT_VALUE_DESCRIPTION",
+            metric_alerts.describe_value(transition.rule_index, value),
+        );
+    }
+
+    // Anything the command prints would be drawn over the TUI.
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    Some(command)
+}
+
+/// Runs the command of the rule of `transition`, if it has one, without waiting for it to finish.
+pub fn run_alert_command(
+    metric_alerts: &MetricAlerts, transition: &AlertTransition,
+) -> io::Result<()> {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    if let Some(mut command) = get_alert_command(metric_alerts, transition, time) {
+        let mut child = command.spawn().map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!(
+                    "unable to run {}, {}",
+                    command.get_program().to_string_lossy(),
+                    err
+                ),
+            )
+        })?;
+
+        // The command is waited on elsewhere, so that it is not left behind once it exits.
+        std::thread::spawn(move || child.wait());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsStr, time::Duration};
+
+    use super::*;
+    use crate::app::{
+        data_harvester::temperature::TemperatureType,
+        metric_alerts::{AlertComparison, AlertMetric, AlertSeverity, MetricAlertRule},
+    };
+
+    fn get_env<'a>(command: &'a Command, key: &str) -> Option<&'a OsStr> {
+        command
+            .get_envs()
+            .find(|(env_key, _value)| *env_key == key)
+            .and_then(|(_key, value)| value)
+    }
+
+    #[test]
+    fn test_alert_command() {
+        let rule = MetricAlertRule {
+            name: "High CPU".to_string(),
+            metric: AlertMetric::Cpu,
+            comparison: AlertComparison::Above,
+            threshold: 90.0,
+            duration: Duration::from_secs(30),
+            severity: AlertSeverity::Critical,
+            notify: false,
+            command: Some("curl -d \"$BTM_ALERT_NAME\" https://example.com".to_string()),
+        };
+        let metric_alerts = MetricAlerts::new(vec![rule.clone(), rule], TemperatureType::Celsius);
+
+        let command = get_alert_command(
+            &metric_alerts,
+            &AlertTransition {
+                rule_index: 0,
+                value: Some(95.5),
+                is_firing: true,
+            },
+            1700000000,
+        )
+        .unwrap();
+        if cfg!(not(target_os = "windows")) {
+            assert_eq!(command.get_program(), "sh");
+            assert_eq!(
+                command.get_args().collect::<Vec<_>>(),
+                vec!["-c", "curl -d \"$BTM_ALERT_NAME\" https://example.com"]
+            );
+        }
+        assert_eq!(get_env(&command, "BTM_ALERT_NAME").unwrap(), "High CPU");
+        assert_eq!(get_env(&command, "BTM_ALERT_STATE").unwrap(), "firing");
+        assert_eq!(get_env(&command, "BTM_ALERT_SEVERITY").unwrap(), "critical");
+        assert_eq!(get_env(&command, "BTM_ALERT_METRIC").unwrap(), "cpu");
+        assert_eq!(get_env(&command, "BTM_ALERT_COMPARISON").unwrap(), ">");
+        assert_eq!(get_env(&command, "BTM_ALERT_THRESHOLD").unwrap(), "90");
+        assert_eq!(get_env(&command, "BTM_ALERT_DURATION").unwrap(), "30");
+        assert_eq!(
+            get_env(&command, "BTM_ALERT_RULE").unwrap(),
+            "CPU > 90.0% for 30s"
+        );
+        assert_eq!(get_env(&command, "BTM_ALERT_VALUE").unwrap(), "95.5");
+        assert_eq!(
+            get_env(&command, "BTM_ALERT_VALUE_DESCRIPTION").unwrap(),
+            "CPU 95.5%"
+        );
+        assert_eq!(get_env(&command, "BTM_ALERT_TIME").unwrap(), "1700000000");
+
+        let command = get_alert_command(
+            &metric_alerts,
+            &AlertTransition {
+                rule_index: 1,
+                value: None,
+                is_firing: false,
+            },
+            1700000000,
+        )
+        .unwrap();
+        assert_eq!(get_env(&command, "BTM_ALERT_STATE").unwrap(), "resolved");
+        assert!(get_env(&command, "BTM_ALERT_VALUE").is_none());
+    }
+}
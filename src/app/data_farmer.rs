@@ -14,28 +14,57 @@
 /// more points as this is used!
 use once_cell::sync::Lazy;
 
-use std::{time::Instant, vec::Vec};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+    vec::Vec,
+};
 
 #[cfg(feature = "battery")]
 use crate::data_harvester::batteries;
 
 use crate::{
-    data_harvester::{cpu, disks, memory, network, processes, temperature, Data},
+    data_harvester::{
+        activity, connections, cpu, disks, kernel_log, memory, network, numa, pods, power,
+        processes, sessions, temperature, units, vms, Data,
+    },
     utils::gen_util::{get_decimal_bytes, GIGA_LIMIT},
+    Pid,
 };
 use regex::Regex;
 
 pub type TimeOffset = f64;
 pub type Value = f64;
 
-#[derive(Debug, Default)]
+/// How many kernel log messages to keep around for the kernel log widget.
+const KERNEL_LOG_MAX_ENTRIES: usize = 1000;
+
+/// How many readings of each temperature sensor to keep around for the temperature table's
+/// sparklines.
+pub const TEMP_HISTORY_LENGTH: usize = 12;
+
+/// How many CPU usage readings of each process to keep around for the process table's sparklines.
+pub const PROCESS_CPU_HISTORY_LENGTH: usize = 10;
+
+#[derive(Clone, Debug, Default)]
 pub struct TimedData {
     pub rx_data: Value,
     pub tx_data: Value,
+    /// RX/TX of each interface, keyed by the interface name.
+    pub interface_data: HashMap<String, (Value, Value)>,
     pub cpu_data: Vec<Value>,
+    /// The average CPU frequency as a percentage of the average maximum frequency.
+    pub cpu_frequency_data: Option<Value>,
     pub load_avg_data: [f32; 3],
     pub mem_data: Option<Value>,
     pub swap_data: Option<Value>,
+    /// The page cache and buffers as a percentage of the total RAM.
+    pub cache_data: Option<Value>,
+    /// Power draw in watts of each power domain, keyed by the domain name.
+    pub power_data: HashMap<String, Value>,
+    /// Charge percentage of each battery, in the same order as the battery harvest.
+    #[cfg(feature = "battery")]
+    pub battery_data: Vec<Value>,
 }
 
 /// AppCollection represents the pooled data stored within the main app
@@ -50,19 +79,48 @@ pub struct TimedData {
 #[derive(Debug)]
 pub struct DataCollection {
     pub current_instant: Instant,
+    /// When disks were last harvested, which disk I/O rates are calculated from.
+    pub disk_instant: Instant,
     pub frozen_instant: Option<Instant>,
+    /// The time of the newest data that graphs show, if they have been scrolled back from the
+    /// latest data.  It stays put as new data comes in, so that what is being looked at does not
+    /// move away.
+    pub history_instant: Option<Instant>,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
     pub network_harvest: network::NetworkHarvest,
     pub memory_harvest: memory::MemHarvest,
     pub swap_harvest: memory::MemHarvest,
+    pub huge_pages_harvest: Option<memory::HugePagesHarvest>,
+    pub shared_memory_harvest: Option<memory::SharedMemHarvest>,
     pub cpu_harvest: cpu::CpuHarvest,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
     pub process_harvest: Vec<processes::ProcessHarvest>,
+    /// The most recent CPU usage readings of each process, oldest first, keyed by the PID and the
+    /// start time so that a reused PID starts over.
+    pub process_cpu_history: HashMap<(Pid, Option<u64>), VecDeque<f64>>,
+    pub thread_harvest: Vec<processes::ThreadHarvest>,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IoHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
+    pub io_times_prev: Vec<Option<disks::IoTimes>>,
+    pub io_latency_labels: Vec<(String, String)>, // The average wait time and utilization
+    /// Whether any disks were mounted, unmounted, or plugged in since the last harvest.
+    pub are_disks_changed: bool,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    /// The most recent readings of each sensor in `temp_harvest`, oldest first.
+    pub temp_history: Vec<VecDeque<f32>>,
+    pub throttle_harvest: Option<temperature::ThrottleStatus>,
+    pub connection_harvest: Vec<connections::ConnectionHarvest>,
+    pub power_harvest: Vec<power::PowerHarvest>,
+    pub session_harvest: Vec<sessions::SessionHarvest>,
+    pub numa_harvest: Vec<numa::NumaNodeHarvest>,
+    pub vm_harvest: Vec<vms::VmHarvest>,
+    pub activity_harvest: Vec<activity::ActivityHarvest>,
+    pub pod_harvest: Vec<pods::PodHarvest>,
+    pub unit_harvest: Vec<units::UnitHarvest>,
+    /// The most recent kernel log messages, oldest first.
+    pub kernel_log_harvest: VecDeque<kernel_log::KernelLogHarvest>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryHarvest>,
 }
@@ -71,19 +129,39 @@ impl Default for DataCollection {
     fn default() -> Self {
         DataCollection {
             current_instant: Instant::now(),
+            disk_instant: Instant::now(),
             frozen_instant: None,
+            history_instant: None,
             timed_data_vec: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
             memory_harvest: memory::MemHarvest::default(),
             swap_harvest: memory::MemHarvest::default(),
+            huge_pages_harvest: None,
+            shared_memory_harvest: None,
             cpu_harvest: cpu::CpuHarvest::default(),
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
             process_harvest: Vec::default(),
+            process_cpu_history: HashMap::default(),
+            thread_harvest: Vec::default(),
             disk_harvest: Vec::default(),
             io_harvest: disks::IoHarvest::default(),
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
+            io_times_prev: Vec::default(),
+            io_latency_labels: Vec::default(),
+            are_disks_changed: false,
             temp_harvest: Vec::default(),
+            temp_history: Vec::default(),
+            throttle_harvest: None,
+            connection_harvest: Vec::default(),
+            power_harvest: Vec::default(),
+            session_harvest: Vec::default(),
+            numa_harvest: Vec::default(),
+            vm_harvest: Vec::default(),
+            activity_harvest: Vec::default(),
+            pod_harvest: Vec::default(),
+            unit_harvest: Vec::default(),
+            kernel_log_harvest: VecDeque::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
         }
@@ -93,15 +171,34 @@ impl Default for DataCollection {
 impl DataCollection {
     pub fn reset(&mut self) {
         self.timed_data_vec = Vec::default();
+        self.history_instant = None;
         self.network_harvest = network::NetworkHarvest::default();
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
+        self.huge_pages_harvest = None;
+        self.shared_memory_harvest = None;
         self.cpu_harvest = cpu::CpuHarvest::default();
         self.process_harvest = Vec::default();
+        self.process_cpu_history = HashMap::default();
+        self.thread_harvest = Vec::default();
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IoHarvest::default();
         self.io_labels_and_prev = Vec::default();
+        self.io_times_prev = Vec::default();
+        self.io_latency_labels = Vec::default();
+        self.are_disks_changed = false;
         self.temp_harvest = Vec::default();
+        self.temp_history = Vec::default();
+        self.throttle_harvest = None;
+        self.connection_harvest = Vec::default();
+        self.power_harvest = Vec::default();
+        self.session_harvest = Vec::default();
+        self.numa_harvest = Vec::default();
+        self.vm_harvest = Vec::default();
+        self.activity_harvest = Vec::default();
+        self.pod_harvest = Vec::default();
+        self.unit_harvest = Vec::default();
+        self.kernel_log_harvest = VecDeque::default();
         #[cfg(feature = "battery")]
         {
             self.battery_harvest = Vec::default();
@@ -112,8 +209,59 @@ impl DataCollection {
         self.frozen_instant = Some(self.current_instant);
     }
 
+    /// Returns the time that the points of graphs are placed relative to, which is when the data
+    /// was frozen if it is.
+    pub fn get_graph_instant(&self, is_frozen: bool) -> Instant {
+        if is_frozen {
+            if let Some(frozen_instant) = self.frozen_instant {
+                return frozen_instant;
+            }
+        }
+
+        self.current_instant
+    }
+
+    /// Returns the time of the newest data that graphs show.
+    pub fn get_graph_end_instant(&self, is_frozen: bool) -> Instant {
+        self.history_instant
+            .unwrap_or_else(|| self.get_graph_instant(is_frozen))
+    }
+
+    /// Returns how far graphs have been scrolled back from the latest data, in milliseconds.
+    pub fn get_history_offset(&self, is_frozen: bool) -> u64 {
+        self.get_graph_instant(is_frozen)
+            .saturating_duration_since(self.get_graph_end_instant(is_frozen))
+            .as_millis() as u64
+    }
+
+    /// Scrolls graphs to end `offset` milliseconds before the latest data, or rather at the
+    /// newest data that is at least that old.  An offset of 0 goes back to the latest data.
+    pub fn set_history_offset(&mut self, is_frozen: bool, offset: u64) {
+        let graph_instant = self.get_graph_instant(is_frozen);
+        let target_instant = graph_instant
+            .checked_sub(Duration::from_millis(offset))
+            .unwrap_or(graph_instant);
+
+        self.history_instant = if offset == 0 {
+            None
+        } else {
+            // Graphs can only end at a time that there is data for, so that the newest point is
+            // right at their edge.  If the offset is past the oldest data, it is used instead.
+            let index = self
+                .timed_data_vec
+                .partition_point(|(instant, _timed_data)| *instant <= target_instant);
+            self.timed_data_vec
+                .get(index.saturating_sub(1))
+                .map(|(instant, _timed_data)| *instant)
+                .filter(|instant| *instant < graph_instant)
+        };
+    }
+
+    /// Drops the data that is older than `max_time_millis` relative to the latest data, rather
+    /// than to now, so that replays, whose data is placed on a timeline of its own, are cleaned
+    /// alike.
     pub fn clean_data(&mut self, max_time_millis: u64) {
-        let current_time = Instant::now();
+        let current_time = self.current_instant;
 
         let remove_index = match self
             .timed_data_vec
@@ -129,6 +277,17 @@ impl DataCollection {
         };
 
         self.timed_data_vec.drain(0..remove_index);
+
+        // Anything scrolled back to data that is now gone moves up to the oldest that is left.
+        if let Some(history_instant) = self.history_instant {
+            if let Some((oldest_instant, _timed_data)) = self.timed_data_vec.first() {
+                if history_instant < *oldest_instant {
+                    self.history_instant = Some(*oldest_instant);
+                }
+            } else {
+                self.history_instant = None;
+            }
+        }
     }
 
     pub fn eat_data(&mut self, harvested_data: Box<Data>) {
@@ -136,30 +295,60 @@ impl DataCollection {
         // trace!("Harvested time: {:?}", harvested_time);
         // trace!("New current instant: {:?}", self.current_instant);
         let mut new_entry = TimedData::default();
+        // Harvesters with their own refresh rates are not in every update, so their graphs keep
+        // their last values until they are refreshed again.
+        let last_entry = self
+            .timed_data_vec
+            .last()
+            .map(|(_time, last_entry)| last_entry.clone())
+            .unwrap_or_default();
 
         // Network
         if let Some(network) = harvested_data.network {
             self.eat_network(network, &mut new_entry);
+        } else {
+            new_entry.rx_data = last_entry.rx_data;
+            new_entry.tx_data = last_entry.tx_data;
+            new_entry.interface_data = last_entry.interface_data;
         }
 
         // Memory and Swap
         if let (Some(memory), Some(swap)) = (harvested_data.memory, harvested_data.swap) {
             self.eat_memory_and_swap(memory, swap, &mut new_entry);
+        } else {
+            new_entry.mem_data = last_entry.mem_data;
+            new_entry.swap_data = last_entry.swap_data;
+            new_entry.cache_data = last_entry.cache_data;
+        }
+
+        // Huge pages
+        if let Some(huge_pages) = harvested_data.huge_pages {
+            self.huge_pages_harvest = Some(huge_pages);
+        }
+
+        // Shared memory
+        if let Some(shared_memory) = harvested_data.shared_memory {
+            self.shared_memory_harvest = Some(shared_memory);
         }
 
         // CPU
         if let Some(cpu) = harvested_data.cpu {
             self.eat_cpu(cpu, &mut new_entry);
+        } else {
+            new_entry.cpu_data = last_entry.cpu_data;
+            new_entry.cpu_frequency_data = last_entry.cpu_frequency_data;
         }
 
         // Load Average
         if let Some(load_avg) = harvested_data.load_avg {
             self.eat_load_avg(load_avg, &mut new_entry);
+        } else {
+            new_entry.load_avg_data = last_entry.load_avg_data;
         }
 
         // Temp
         if let Some(temperature_sensors) = harvested_data.temperature_sensors {
-            self.eat_temp(temperature_sensors);
+            self.eat_temp(temperature_sensors, harvested_data.throttle_status);
         }
 
         // Disks
@@ -174,11 +363,65 @@ impl DataCollection {
             self.eat_proc(list_of_processes);
         }
 
+        // Threads of the process being inspected, if any
+        if let Some(threads) = harvested_data.threads {
+            self.eat_threads(threads);
+        }
+
+        // Connections
+        if let Some(connections) = harvested_data.connections {
+            self.eat_connections(connections);
+        }
+
+        // Sessions
+        if let Some(sessions) = harvested_data.sessions {
+            self.eat_sessions(sessions);
+        }
+
+        // NUMA
+        if let Some(numa) = harvested_data.numa {
+            self.eat_numa(numa);
+        }
+
+        // VMs
+        if let Some(vms) = harvested_data.vms {
+            self.eat_vms(vms);
+        }
+
+        // Activity
+        if let Some(activity) = harvested_data.activity {
+            self.eat_activity(activity);
+        }
+
+        // Pods
+        if let Some(pods) = harvested_data.pods {
+            self.eat_pods(pods);
+        }
+
+        // Units
+        if let Some(units) = harvested_data.units {
+            self.eat_units(units);
+        }
+
+        // Kernel log
+        if let Some(kernel_log) = harvested_data.kernel_log {
+            self.eat_kernel_log(kernel_log);
+        }
+
+        // Power
+        if let Some(power) = harvested_data.power {
+            self.eat_power(power, &mut new_entry);
+        } else {
+            new_entry.power_data = last_entry.power_data;
+        }
+
         #[cfg(feature = "battery")]
         {
             // Battery
             if let Some(list_of_batteries) = harvested_data.list_of_batteries {
-                self.eat_battery(list_of_batteries);
+                self.eat_battery(list_of_batteries, &mut new_entry);
+            } else {
+                new_entry.battery_data = last_entry.battery_data;
             }
         }
 
@@ -192,6 +435,10 @@ impl DataCollection {
     ) {
         // Memory
         new_entry.mem_data = memory.use_percent;
+        new_entry.cache_data = memory
+            .cache_in_kib
+            .filter(|_cache_in_kib| memory.mem_total_in_kib > 0)
+            .map(|cache_in_kib| cache_in_kib as f64 / memory.mem_total_in_kib as f64 * 100.0);
 
         // Swap
         new_entry.swap_data = swap.use_percent;
@@ -212,6 +459,18 @@ impl DataCollection {
             new_entry.tx_data = network.tx as f64;
         }
 
+        // Per interface
+        new_entry.interface_data = network
+            .interfaces
+            .iter()
+            .map(|interface| {
+                (
+                    interface.name.clone(),
+                    (interface.rx as f64, interface.tx as f64),
+                )
+            })
+            .collect();
+
         // In addition copy over latest data for easy reference
         self.network_harvest = network;
     }
@@ -223,6 +482,23 @@ impl DataCollection {
         cpu.iter()
             .for_each(|cpu| new_entry.cpu_data.push(cpu.cpu_usage));
 
+        let (total_frequency, total_max_frequency) = cpu
+            .iter()
+            .filter(|cpu| cpu.cpu_count.is_some())
+            .filter_map(|cpu| match (cpu.frequency_mhz, cpu.max_frequency_mhz) {
+                (Some(frequency), Some(max_frequency)) => Some((frequency, max_frequency)),
+                _ => None,
+            })
+            .fold(
+                (0.0, 0.0),
+                |(total, total_max), (frequency, max_frequency)| {
+                    (total + frequency, total_max + max_frequency)
+                },
+            );
+        if total_max_frequency > 0.0 {
+            new_entry.cpu_frequency_data = Some(total_frequency / total_max_frequency * 100.0);
+        }
+
         self.cpu_harvest = cpu.to_vec();
     }
 
@@ -232,9 +508,35 @@ impl DataCollection {
         self.load_avg_harvest = load_avg;
     }
 
-    fn eat_temp(&mut self, temperature_sensors: Vec<temperature::TempHarvest>) {
-        // TODO: [PO] To implement
-        self.temp_harvest = temperature_sensors.to_vec();
+    fn eat_temp(
+        &mut self, temperature_sensors: Vec<temperature::TempHarvest>,
+        throttle_status: Option<temperature::ThrottleStatus>,
+    ) {
+        // Carry over the history of each sensor that is still around, matching them by name.
+        let mut previous_history = self
+            .temp_harvest
+            .drain(..)
+            .map(|sensor| sensor.name)
+            .zip(self.temp_history.drain(..))
+            .collect::<Vec<_>>();
+        self.temp_history = temperature_sensors
+            .iter()
+            .map(|sensor| {
+                let mut history = previous_history
+                    .iter()
+                    .position(|(name, _history)| *name == sensor.name)
+                    .map(|index| previous_history.remove(index).1)
+                    .unwrap_or_default();
+                history.push_back(sensor.temperature);
+                if history.len() > TEMP_HISTORY_LENGTH {
+                    history.pop_front();
+                }
+                history
+            })
+            .collect();
+
+        self.temp_harvest = temperature_sensors;
+        self.throttle_harvest = throttle_status;
     }
 
     fn eat_disks(
@@ -243,30 +545,30 @@ impl DataCollection {
         // TODO: [PO] To implement
 
         let time_since_last_harvest = harvested_time
-            .duration_since(self.current_instant)
+            .duration_since(self.disk_instant)
             .as_secs_f64();
+        self.disk_instant = harvested_time;
 
-        for (itx, device) in disks.iter().enumerate() {
-            if let Some(trim) = device.name.split('/').last() {
-                let io_device = if cfg!(target_os = "macos") {
-                    // Must trim one level further!
-                    static DISK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"disk\d+").unwrap());
-                    if let Some(disk_trim) = DISK_REGEX.find(trim) {
-                        io.get(disk_trim.as_str())
-                    } else {
-                        None
-                    }
-                } else {
-                    io.get(trim)
-                };
+        self.are_disks_changed = !is_same_disk_list(&self.disk_harvest, &disks);
+        if self.are_disks_changed {
+            self.remap_disk_state(&disks, &io);
+        }
 
-                if let Some(io_device) = io_device {
+        for (itx, device) in disks.iter().enumerate() {
+            if device.name.split('/').last().is_some() {
+                if let Some(io_device) = get_io_device(&io, &device.name) {
                     let (io_r_pt, io_w_pt) = if let Some(io) = io_device {
                         (io.read_bytes, io.write_bytes)
                     } else {
                         (0, 0)
                     };
 
+                    self.eat_io_times(
+                        itx,
+                        io_device.as_ref().and_then(|io| io.times.clone()),
+                        time_since_last_harvest,
+                    );
+
                     if self.io_labels.len() <= itx {
                         self.io_labels.push((String::default(), String::default()));
                     }
@@ -311,6 +613,8 @@ impl DataCollection {
                     if let Some(io_labels) = self.io_labels.get_mut(itx) {
                         *io_labels = ("N/A".to_string(), "N/A".to_string());
                     }
+
+                    self.eat_io_times(itx, None, time_since_last_harvest);
                 }
             }
         }
@@ -319,12 +623,198 @@ impl DataCollection {
         self.io_harvest = io;
     }
 
+    /// The per-disk I/O state is stored by index, so if disks were added or removed, move each
+    /// disk's state to its new index so that rates aren't calculated against another disk.
+    fn remap_disk_state(&mut self, new_disks: &[disks::DiskHarvest], io: &disks::IoHarvest) {
+        let mut io_labels_and_prev = Vec::with_capacity(new_disks.len());
+        let mut io_labels = Vec::with_capacity(new_disks.len());
+        let mut io_times_prev = Vec::with_capacity(new_disks.len());
+        let mut io_latency_labels = Vec::with_capacity(new_disks.len());
+
+        for new_disk in new_disks {
+            let old_itx = self.disk_harvest.iter().position(|old_disk| {
+                old_disk.name == new_disk.name && old_disk.mount_point == new_disk.mount_point
+            });
+
+            match old_itx {
+                Some(old_itx)
+                    if old_itx < self.io_labels_and_prev.len()
+                        && old_itx < self.io_labels.len() =>
+                {
+                    io_labels_and_prev.push(self.io_labels_and_prev[old_itx]);
+                    io_labels.push(self.io_labels[old_itx].clone());
+                    io_times_prev.push(
+                        self.io_times_prev
+                            .get(old_itx)
+                            .and_then(|times| times.clone()),
+                    );
+                    io_latency_labels.push(
+                        self.io_latency_labels
+                            .get(old_itx)
+                            .cloned()
+                            .unwrap_or_else(|| ("N/A".to_string(), "N/A".to_string())),
+                    );
+                }
+                _ => {
+                    // A new disk has nothing to compare against yet, so start from its current
+                    // counters, like on the first harvest.
+                    let current = match get_io_device(io, &new_disk.name) {
+                        Some(Some(io)) => (io.read_bytes, io.write_bytes),
+                        _ => (0, 0),
+                    };
+                    io_labels_and_prev.push(((0, 0), current));
+                    io_labels.push(("N/A".to_string(), "N/A".to_string()));
+                    io_times_prev.push(None);
+                    io_latency_labels.push(("N/A".to_string(), "N/A".to_string()));
+                }
+            }
+        }
+
+        self.io_labels_and_prev = io_labels_and_prev;
+        self.io_labels = io_labels;
+        self.io_times_prev = io_times_prev;
+        self.io_latency_labels = io_latency_labels;
+    }
+
+    /// Updates the average wait time and utilization labels of the disk at `itx` from the change in
+    /// its timing counters since the last harvest.
+    fn eat_io_times(
+        &mut self, itx: usize, times: Option<disks::IoTimes>, time_since_last_harvest: f64,
+    ) {
+        if self.io_latency_labels.len() <= itx {
+            self.io_latency_labels
+                .push(("N/A".to_string(), "N/A".to_string()));
+        }
+        if self.io_times_prev.len() <= itx {
+            self.io_times_prev.push(times.clone());
+        }
+
+        if let (Some(curr), Some(labels)) = (&times, self.io_latency_labels.get_mut(itx)) {
+            let prev = self.io_times_prev[itx].as_ref().unwrap_or(curr);
+            let completed_ops = curr.completed_ops.saturating_sub(prev.completed_ops);
+            let wait_time_ms = curr.wait_time_ms.saturating_sub(prev.wait_time_ms);
+            let busy_time_ms = curr.busy_time_ms.saturating_sub(prev.busy_time_ms);
+
+            let await_ms = if completed_ops == 0 {
+                0.0
+            } else {
+                wait_time_ms as f64 / completed_ops as f64
+            };
+            let util_percent = if time_since_last_harvest > 0.0 {
+                (busy_time_ms as f64 / (time_since_last_harvest * 1000.0) * 100.0).min(100.0)
+            } else {
+                0.0
+            };
+
+            *labels = (
+                format!("{:.1}ms", await_ms),
+                format!("{:.0}%", util_percent),
+            );
+        } else if let Some(labels) = self.io_latency_labels.get_mut(itx) {
+            *labels = ("N/A".to_string(), "N/A".to_string());
+        }
+
+        self.io_times_prev[itx] = times;
+    }
+
     fn eat_proc(&mut self, list_of_processes: Vec<processes::ProcessHarvest>) {
+        // Processes that have exited are dropped from the history.
+        let mut previous_history = std::mem::take(&mut self.process_cpu_history);
+        self.process_cpu_history = list_of_processes
+            .iter()
+            .map(|process| {
+                let key = (process.pid, process.start_time);
+                let mut history = previous_history.remove(&key).unwrap_or_default();
+                history.push_back(process.cpu_usage_percent);
+                if history.len() > PROCESS_CPU_HISTORY_LENGTH {
+                    history.pop_front();
+                }
+                (key, history)
+            })
+            .collect();
+
         self.process_harvest = list_of_processes;
     }
 
+    fn eat_threads(&mut self, threads: Vec<processes::ThreadHarvest>) {
+        self.thread_harvest = threads;
+    }
+
+    fn eat_connections(&mut self, connections: Vec<connections::ConnectionHarvest>) {
+        self.connection_harvest = connections;
+    }
+
+    fn eat_sessions(&mut self, sessions: Vec<sessions::SessionHarvest>) {
+        self.session_harvest = sessions;
+    }
+
+    fn eat_numa(&mut self, numa: Vec<numa::NumaNodeHarvest>) {
+        self.numa_harvest = numa;
+    }
+
+    fn eat_vms(&mut self, vms: Vec<vms::VmHarvest>) {
+        self.vm_harvest = vms;
+    }
+
+    fn eat_activity(&mut self, activity: Vec<activity::ActivityHarvest>) {
+        self.activity_harvest = activity;
+    }
+
+    fn eat_pods(&mut self, pods: Vec<pods::PodHarvest>) {
+        self.pod_harvest = pods;
+    }
+
+    fn eat_units(&mut self, units: Vec<units::UnitHarvest>) {
+        self.unit_harvest = units;
+    }
+
+    fn eat_kernel_log(&mut self, kernel_log: Vec<kernel_log::KernelLogHarvest>) {
+        self.kernel_log_harvest.extend(kernel_log);
+        while self.kernel_log_harvest.len() > KERNEL_LOG_MAX_ENTRIES {
+            self.kernel_log_harvest.pop_front();
+        }
+    }
+
+    fn eat_power(&mut self, power: Vec<power::PowerHarvest>, new_entry: &mut TimedData) {
+        new_entry.power_data = power
+            .iter()
+            .map(|domain| (domain.name.clone(), domain.watts))
+            .collect();
+
+        self.power_harvest = power;
+    }
+
     #[cfg(feature = "battery")]
-    fn eat_battery(&mut self, list_of_batteries: Vec<batteries::BatteryHarvest>) {
+    fn eat_battery(
+        &mut self, list_of_batteries: Vec<batteries::BatteryHarvest>, new_entry: &mut TimedData,
+    ) {
+        new_entry.battery_data = list_of_batteries
+            .iter()
+            .map(|battery| battery.charge_percent)
+            .collect();
+
         self.battery_harvest = list_of_batteries;
     }
 }
+
+/// Returns whether two disk lists have the same disks in the same order.
+fn is_same_disk_list(old_disks: &[disks::DiskHarvest], new_disks: &[disks::DiskHarvest]) -> bool {
+    old_disks.len() == new_disks.len()
+        && old_disks.iter().zip(new_disks).all(|(old_disk, new_disk)| {
+            old_disk.name == new_disk.name && old_disk.mount_point == new_disk.mount_point
+        })
+}
+
+/// Returns the I/O counters of the device with the given name, if any.
+fn get_io_device<'a>(io: &'a disks::IoHarvest, name: &str) -> Option<&'a Option<disks::IoData>> {
+    let trim = name.split('/').last()?;
+    if cfg!(target_os = "macos") {
+        // Must trim one level further!
+        static DISK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"disk\d+").unwrap());
+        DISK_REGEX
+            .find(trim)
+            .and_then(|disk_trim| io.get(disk_trim.as_str()))
+    } else {
+        io.get(trim)
+    }
+}
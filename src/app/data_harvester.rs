@@ -1,9 +1,13 @@
 //! This is the main file to house data collection functions.
 
-use std::time::Instant;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 #[cfg(target_os = "linux")]
 use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
 
 #[cfg(not(target_os = "linux"))]
 use sysinfo::{System, SystemExt};
@@ -11,33 +15,58 @@ use sysinfo::{System, SystemExt};
 #[cfg(feature = "battery")]
 use battery::{Battery, Manager};
 
-use crate::app::layout_manager::UsedWidgets;
+use crate::app::layout_manager::{BottomWidgetType, UsedWidgets};
 
 use futures::join;
 
-use super::DataFilters;
+use super::{AppConfigFields, DataFilters};
 
+pub mod activity;
 #[cfg(feature = "battery")]
 pub mod batteries;
+pub mod connections;
 pub mod cpu;
 pub mod disks;
+pub mod kernel_log;
 pub mod memory;
 pub mod network;
+pub mod numa;
+pub mod pods;
+pub mod power;
 pub mod processes;
+pub mod sessions;
 pub mod temperature;
+pub mod units;
+pub mod vms;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Data {
+    /// When the data was harvested.  This is not sent to remote clients, which use the time that
+    /// the data arrived at instead.
+    #[serde(skip, default = "Instant::now")]
     pub last_collection_time: Instant,
     pub cpu: Option<cpu::CpuHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
     pub memory: Option<memory::MemHarvest>,
     pub swap: Option<memory::MemHarvest>,
+    pub huge_pages: Option<memory::HugePagesHarvest>,
+    pub shared_memory: Option<memory::SharedMemHarvest>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
+    pub throttle_status: Option<temperature::ThrottleStatus>,
     pub network: Option<network::NetworkHarvest>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
+    pub threads: Option<Vec<processes::ThreadHarvest>>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
+    pub connections: Option<Vec<connections::ConnectionHarvest>>,
+    pub power: Option<Vec<power::PowerHarvest>>,
+    pub sessions: Option<Vec<sessions::SessionHarvest>>,
+    pub numa: Option<Vec<numa::NumaNodeHarvest>>,
+    pub vms: Option<Vec<vms::VmHarvest>>,
+    pub activity: Option<Vec<activity::ActivityHarvest>>,
+    pub pods: Option<Vec<pods::PodHarvest>>,
+    pub units: Option<Vec<units::UnitHarvest>>,
+    pub kernel_log: Option<Vec<kernel_log::KernelLogHarvest>>,
     #[cfg(feature = "battery")]
     pub list_of_batteries: Option<Vec<batteries::BatteryHarvest>>,
 }
@@ -50,11 +79,24 @@ impl Default for Data {
             load_avg: None,
             memory: None,
             swap: None,
+            huge_pages: None,
+            shared_memory: None,
             temperature_sensors: None,
+            throttle_status: None,
             list_of_processes: None,
+            threads: None,
             disks: None,
             io: None,
             network: None,
+            connections: None,
+            power: None,
+            sessions: None,
+            numa: None,
+            vms: None,
+            activity: None,
+            pods: None,
+            units: None,
+            kernel_log: None,
             #[cfg(feature = "battery")]
             list_of_batteries: None,
         }
@@ -65,12 +107,25 @@ impl Data {
     pub fn cleanup(&mut self) {
         self.io = None;
         self.temperature_sensors = None;
+        self.throttle_status = None;
         self.list_of_processes = None;
+        self.threads = None;
         self.disks = None;
         self.memory = None;
         self.swap = None;
+        self.huge_pages = None;
+        self.shared_memory = None;
         self.cpu = None;
         self.load_avg = None;
+        self.connections = None;
+        self.power = None;
+        self.sessions = None;
+        self.numa = None;
+        self.vms = None;
+        self.activity = None;
+        self.pods = None;
+        self.units = None;
+        // The kernel log isn't cleared, as each message is only harvested once.
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -78,27 +133,129 @@ impl Data {
     }
 }
 
+/// Tracks when each harvester was last run and is next due, so that harvesters can be refreshed
+/// at their own rates.  Harvesters are named after the widgets that they harvest for.
+#[derive(Debug, Default)]
+pub struct HarvestSchedule {
+    default_rate: Duration,
+    rates: HashMap<BottomWidgetType, Duration>,
+    last_harvest_times: HashMap<BottomWidgetType, Instant>,
+    due_times: HashMap<BottomWidgetType, Instant>,
+}
+
+impl HarvestSchedule {
+    /// Sets the refresh rate of each harvester in milliseconds, with any not given being refreshed
+    /// at `default_rate_in_milliseconds`.
+    pub fn set_rates(
+        &mut self, default_rate_in_milliseconds: u64,
+        rates_in_milliseconds: &HashMap<BottomWidgetType, u64>,
+    ) {
+        self.default_rate = Duration::from_millis(default_rate_in_milliseconds);
+        self.rates = rates_in_milliseconds
+            .iter()
+            .map(|(widget_type, rate)| (widget_type.clone(), Duration::from_millis(*rate)))
+            .collect();
+        // Reschedule everything, as the old due times may be far off now.
+        self.due_times.clear();
+    }
+
+    fn get_rate(&self, widget_type: &BottomWidgetType) -> Duration {
+        self.rates
+            .get(widget_type)
+            .copied()
+            .unwrap_or(self.default_rate)
+    }
+
+    /// Returns whether the harvester is due at `now`, and if so, schedules its next harvest.
+    fn take_if_due(&mut self, widget_type: BottomWidgetType, now: Instant) -> bool {
+        let is_due = match self.due_times.get(&widget_type) {
+            Some(due_time) => now >= *due_time,
+            None => true,
+        };
+
+        if is_due {
+            self.due_times
+                .insert(widget_type.clone(), now + self.get_rate(&widget_type));
+            self.last_harvest_times.insert(widget_type, now);
+        }
+
+        is_due
+    }
+
+    pub fn get_last_harvest_time(&self, widget_type: &BottomWidgetType) -> Option<Instant> {
+        self.last_harvest_times.get(widget_type).copied()
+    }
+
+    /// Returns how long it is from `now` until the next of `widget_types` is due.
+    fn get_time_until_next(&self, widget_types: &[BottomWidgetType], now: Instant) -> Duration {
+        widget_types
+            .iter()
+            .map(|widget_type| match self.due_times.get(widget_type) {
+                Some(due_time) => due_time.saturating_duration_since(now),
+                None => Duration::ZERO,
+            })
+            .min()
+            .unwrap_or(self.default_rate)
+    }
+}
+
 #[derive(Debug)]
 pub struct DataCollector {
     pub data: Data,
     #[cfg(not(target_os = "linux"))]
     sys: System,
+    #[cfg(not(target_os = "freebsd"))]
     previous_cpu_times: Vec<(cpu::PastCpuWork, cpu::PastCpuTotal)>,
+    #[cfg(not(target_os = "freebsd"))]
     previous_average_cpu_time: Option<(cpu::PastCpuWork, cpu::PastCpuTotal)>,
     #[cfg(target_os = "linux")]
     pid_mapping: FxHashMap<crate::Pid, processes::PrevProcDetails>,
     #[cfg(target_os = "linux")]
+    thread_tracker: processes::ThreadTracker,
+    #[cfg(target_os = "linux")]
     prev_idle: f64,
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
+    #[cfg(target_os = "windows")]
+    counter_tracker: Option<processes::CounterTracker>,
     mem_total_kb: u64,
     temperature_type: temperature::TemperatureType,
     use_current_cpu_total: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    collect_process_fds: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    collect_process_memory_breakdown: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    collect_process_latency: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    collect_process_security: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    collect_process_io_priority: bool,
     last_collection_time: Instant,
     total_rx: u64,
     total_tx: u64,
+    prev_interface_totals: network::PrevInterfaceTotals,
     show_average_cpu: bool,
+    use_disk_smart: bool,
+    smart_cache: disks::SmartCache,
+    use_ipmi: bool,
+    ipmi_cache: temperature::ipmi::IpmiCache,
+    use_network_wireless: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    use_network_connections: bool,
+    #[cfg(feature = "battery")]
+    nut_server: Option<String>,
+    wireless_cache: network::WirelessCache,
+    power_tracker: power::PowerTracker,
+    unit_tracker: units::UnitTracker,
+    vm_tracker: vms::VmTracker,
+    activity_tracker: activity::ActivityTracker,
+    pod_tracker: pods::PodTracker,
+    kubelet_url: Option<String>,
+    kubelet_insecure: bool,
+    kernel_log_tracker: kernel_log::KernelLogTracker,
     widgets_to_harvest: UsedWidgets,
+    schedule: HarvestSchedule,
     #[cfg(feature = "battery")]
     battery_manager: Option<Manager>,
     #[cfg(feature = "battery")]
@@ -112,22 +269,52 @@ impl DataCollector {
             data: Data::default(),
             #[cfg(not(target_os = "linux"))]
             sys: System::new_with_specifics(sysinfo::RefreshKind::new()),
+            #[cfg(not(target_os = "freebsd"))]
             previous_cpu_times: vec![],
+            #[cfg(not(target_os = "freebsd"))]
             previous_average_cpu_time: None,
             #[cfg(target_os = "linux")]
             pid_mapping: FxHashMap::default(),
             #[cfg(target_os = "linux")]
+            thread_tracker: processes::ThreadTracker::default(),
+            #[cfg(target_os = "linux")]
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
+            #[cfg(target_os = "windows")]
+            counter_tracker: None,
             mem_total_kb: 0,
             temperature_type: temperature::TemperatureType::Celsius,
             use_current_cpu_total: false,
+            collect_process_fds: false,
+            collect_process_memory_breakdown: false,
+            collect_process_latency: false,
+            collect_process_security: false,
+            collect_process_io_priority: false,
             last_collection_time: Instant::now(),
             total_rx: 0,
             total_tx: 0,
+            prev_interface_totals: network::PrevInterfaceTotals::default(),
             show_average_cpu: false,
+            use_disk_smart: false,
+            smart_cache: disks::SmartCache::default(),
+            use_ipmi: false,
+            ipmi_cache: temperature::ipmi::IpmiCache::default(),
+            use_network_wireless: false,
+            use_network_connections: false,
+            #[cfg(feature = "battery")]
+            nut_server: None,
+            wireless_cache: network::WirelessCache::default(),
+            power_tracker: power::PowerTracker::default(),
+            unit_tracker: units::UnitTracker::default(),
+            vm_tracker: vms::VmTracker::default(),
+            activity_tracker: activity::ActivityTracker::default(),
+            pod_tracker: pods::PodTracker::default(),
+            kubelet_url: None,
+            kubelet_insecure: false,
+            kernel_log_tracker: kernel_log::KernelLogTracker::default(),
             widgets_to_harvest: UsedWidgets::default(),
+            schedule: HarvestSchedule::default(),
             #[cfg(feature = "battery")]
             battery_manager: None,
             #[cfg(feature = "battery")]
@@ -153,11 +340,20 @@ impl DataCollector {
             }
 
             // Refresh network list once...
-            if cfg!(target_os = "windows") && self.widgets_to_harvest.use_net {
+            if cfg!(any(target_os = "windows", target_os = "freebsd"))
+                && self.widgets_to_harvest.use_net
+            {
                 self.sys.refresh_networks_list();
             }
         }
 
+        #[cfg(target_os = "windows")]
+        {
+            if self.widgets_to_harvest.use_proc {
+                self.counter_tracker = processes::CounterTracker::new();
+            }
+        }
+
         #[cfg(feature = "battery")]
         {
             if self.widgets_to_harvest.use_battery {
@@ -178,6 +374,8 @@ impl DataCollector {
         std::thread::sleep(std::time::Duration::from_millis(250));
 
         self.data.cleanup();
+        // Harvest everything again right away, since the data from above was just thrown out.
+        self.schedule.due_times.clear();
 
         // trace!("Enabled widgets to harvest: {:#?}", self.widgets_to_harvest);
     }
@@ -195,6 +393,105 @@ impl DataCollector {
         self.widgets_to_harvest = used_widgets;
     }
 
+    pub fn set_refresh_rates(
+        &mut self, default_rate_in_milliseconds: u64,
+        rates_in_milliseconds: &HashMap<BottomWidgetType, u64>,
+    ) {
+        self.schedule
+            .set_rates(default_rate_in_milliseconds, rates_in_milliseconds);
+    }
+
+    /// Returns the types of the used widgets that have harvesters.
+    fn get_used_widget_types(&self) -> Vec<BottomWidgetType> {
+        let used_widgets = &self.widgets_to_harvest;
+        vec![
+            (BottomWidgetType::Cpu, used_widgets.use_cpu),
+            (BottomWidgetType::Mem, used_widgets.use_mem),
+            (BottomWidgetType::Net, used_widgets.use_net),
+            (BottomWidgetType::Proc, used_widgets.use_proc),
+            (BottomWidgetType::Disk, used_widgets.use_disk),
+            (BottomWidgetType::Temp, used_widgets.use_temp),
+            (BottomWidgetType::Battery, used_widgets.use_battery),
+            (BottomWidgetType::Conn, used_widgets.use_conn),
+            (BottomWidgetType::Power, used_widgets.use_power),
+            (BottomWidgetType::Users, used_widgets.use_users),
+            (BottomWidgetType::Activity, used_widgets.use_activity),
+            (BottomWidgetType::Pods, used_widgets.use_pods),
+            (BottomWidgetType::Vm, used_widgets.use_vm),
+            (BottomWidgetType::Numa, used_widgets.use_numa),
+            (BottomWidgetType::Units, used_widgets.use_units),
+            (BottomWidgetType::KernelLog, used_widgets.use_kernel_log),
+        ]
+        .into_iter()
+        .filter(|(_widget_type, is_used)| *is_used)
+        .map(|(widget_type, _is_used)| widget_type)
+        .collect()
+    }
+
+    /// Returns the used widgets whose harvesters are due at `now`, scheduling their next harvests.
+    fn take_due_widgets(&mut self, now: Instant) -> UsedWidgets {
+        let used_widgets = self.widgets_to_harvest.clone();
+        let schedule = &mut self.schedule;
+        let mut is_due = |widget_type: BottomWidgetType, is_used: bool| {
+            is_used && schedule.take_if_due(widget_type, now)
+        };
+
+        UsedWidgets {
+            use_cpu: is_due(BottomWidgetType::Cpu, used_widgets.use_cpu),
+            use_mem: is_due(BottomWidgetType::Mem, used_widgets.use_mem),
+            use_net: is_due(BottomWidgetType::Net, used_widgets.use_net),
+            use_proc: is_due(BottomWidgetType::Proc, used_widgets.use_proc),
+            use_disk: is_due(BottomWidgetType::Disk, used_widgets.use_disk),
+            use_temp: is_due(BottomWidgetType::Temp, used_widgets.use_temp),
+            use_battery: is_due(BottomWidgetType::Battery, used_widgets.use_battery),
+            use_conn: is_due(BottomWidgetType::Conn, used_widgets.use_conn),
+            use_power: is_due(BottomWidgetType::Power, used_widgets.use_power),
+            use_users: is_due(BottomWidgetType::Users, used_widgets.use_users),
+            use_activity: is_due(BottomWidgetType::Activity, used_widgets.use_activity),
+            use_pods: is_due(BottomWidgetType::Pods, used_widgets.use_pods),
+            use_vm: is_due(BottomWidgetType::Vm, used_widgets.use_vm),
+            use_numa: is_due(BottomWidgetType::Numa, used_widgets.use_numa),
+            use_units: is_due(BottomWidgetType::Units, used_widgets.use_units),
+            use_kernel_log: is_due(BottomWidgetType::KernelLog, used_widgets.use_kernel_log),
+            use_alerts: used_widgets.use_alerts,
+        }
+    }
+
+    /// Returns whether any used widget's harvester is due.
+    pub fn is_update_due(&self) -> bool {
+        self.get_time_until_next_update().is_zero()
+    }
+
+    /// Returns how long it is until the next used widget's harvester is due.
+    pub fn get_time_until_next_update(&self) -> Duration {
+        self.schedule
+            .get_time_until_next(&self.get_used_widget_types(), Instant::now())
+    }
+
+    /// Sets everything about harvesting that comes from the config file or the command line.
+    pub fn set_config_fields(&mut self, app_config_fields: &AppConfigFields) {
+        self.set_refresh_rates(
+            app_config_fields.update_rate_in_milliseconds,
+            &app_config_fields.refresh_rates,
+        );
+        self.set_temperature_type(app_config_fields.temperature_type.clone());
+        self.set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
+        self.set_collect_process_fds(app_config_fields.show_process_fds);
+        self.set_collect_process_memory_breakdown(app_config_fields.show_process_memory_breakdown);
+        self.set_collect_process_latency(app_config_fields.show_process_latency);
+        self.set_collect_process_security(app_config_fields.show_process_security);
+        self.set_collect_process_io_priority(app_config_fields.show_process_io_priority);
+        self.set_show_average_cpu(app_config_fields.show_average_cpu);
+        self.set_use_disk_smart(app_config_fields.use_disk_smart);
+        self.set_use_ipmi(app_config_fields.use_ipmi);
+        self.set_kubelet_url(app_config_fields.kubelet_url.clone());
+        self.set_kubelet_insecure(app_config_fields.kubelet_insecure);
+        self.set_use_network_wireless(app_config_fields.use_network_wireless);
+        self.set_use_network_connections(app_config_fields.use_network_connections);
+        #[cfg(feature = "battery")]
+        self.set_nut_server(app_config_fields.nut_server.clone());
+    }
+
     pub fn set_temperature_type(&mut self, temperature_type: temperature::TemperatureType) {
         self.temperature_type = temperature_type;
     }
@@ -203,37 +500,131 @@ impl DataCollector {
         self.use_current_cpu_total = use_current_cpu_total;
     }
 
+    pub fn set_collect_process_fds(&mut self, collect_process_fds: bool) {
+        self.collect_process_fds = collect_process_fds;
+    }
+
+    pub fn set_collect_process_memory_breakdown(&mut self, collect_process_memory_breakdown: bool) {
+        self.collect_process_memory_breakdown = collect_process_memory_breakdown;
+    }
+
+    pub fn set_collect_process_latency(&mut self, collect_process_latency: bool) {
+        self.collect_process_latency = collect_process_latency;
+    }
+
+    pub fn set_collect_process_security(&mut self, collect_process_security: bool) {
+        self.collect_process_security = collect_process_security;
+    }
+
+    pub fn set_collect_process_io_priority(&mut self, collect_process_io_priority: bool) {
+        self.collect_process_io_priority = collect_process_io_priority;
+    }
+
+    /// Sets the filters for which disks, mounts, sensors, and interfaces are collected.
+    pub fn set_filters(&mut self, filters: DataFilters) {
+        self.filters = filters;
+    }
+
+    /// Sets which process to also collect the threads of.  This is currently only supported on Linux.
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    pub fn set_thread_pid(&mut self, pid: Option<crate::Pid>) {
+        #[cfg(target_os = "linux")]
+        self.thread_tracker.set_pid(pid);
+    }
+
     pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
         self.show_average_cpu = show_average_cpu;
     }
 
+    pub fn set_use_disk_smart(&mut self, use_disk_smart: bool) {
+        self.use_disk_smart = use_disk_smart;
+    }
+
+    pub fn set_use_ipmi(&mut self, use_ipmi: bool) {
+        self.use_ipmi = use_ipmi;
+    }
+
+    pub fn set_kubelet_url(&mut self, kubelet_url: Option<String>) {
+        self.kubelet_url = kubelet_url;
+    }
+
+    pub fn set_kubelet_insecure(&mut self, kubelet_insecure: bool) {
+        self.kubelet_insecure = kubelet_insecure;
+    }
+
+    pub fn set_use_network_wireless(&mut self, use_network_wireless: bool) {
+        self.use_network_wireless = use_network_wireless;
+    }
+
+    pub fn set_use_network_connections(&mut self, use_network_connections: bool) {
+        self.use_network_connections = use_network_connections;
+    }
+
+    /// Sets the address of a NUT daemon to also query UPSes from.
+    #[cfg(feature = "battery")]
+    pub fn set_nut_server(&mut self, nut_server: Option<String>) {
+        self.nut_server = nut_server;
+    }
+
     pub async fn update_data(&mut self) {
+        let current_instant = std::time::Instant::now();
+
+        // Rates are calculated from when each harvester last ran, which may not be the last update.
+        let last_proc_time = self
+            .schedule
+            .get_last_harvest_time(&BottomWidgetType::Proc)
+            .unwrap_or(self.last_collection_time);
+        let last_net_time = self
+            .schedule
+            .get_last_harvest_time(&BottomWidgetType::Net)
+            .unwrap_or(self.last_collection_time);
+        let harvest = self.take_due_widgets(current_instant);
+
         #[cfg(not(target_os = "linux"))]
         {
-            if self.widgets_to_harvest.use_proc {
+            // CPU usage is calculated relative to the previous refresh, so only refresh it once.
+            if harvest.use_proc || (cfg!(target_os = "freebsd") && harvest.use_cpu) {
                 self.sys.refresh_cpu();
+            }
+            if harvest.use_proc {
                 self.sys.refresh_processes();
             }
-            if self.widgets_to_harvest.use_temp {
+            if harvest.use_temp {
                 self.sys.refresh_components();
             }
 
-            if cfg!(target_os = "windows") && self.widgets_to_harvest.use_net {
+            if cfg!(any(target_os = "windows", target_os = "freebsd")) && harvest.use_net {
                 self.sys.refresh_networks();
             }
-        }
 
-        let current_instant = std::time::Instant::now();
+            // FreeBSD also uses sysinfo for memory and disks.
+            if cfg!(target_os = "freebsd") {
+                if harvest.use_mem {
+                    self.sys.refresh_memory();
+                }
+                if harvest.use_disk {
+                    self.sys.refresh_disks_list();
+                }
+            }
+        }
 
         // CPU
-        if self.widgets_to_harvest.use_cpu {
-            if let Ok(cpu_data) = cpu::get_cpu_data_list(
-                self.show_average_cpu,
-                &mut self.previous_cpu_times,
-                &mut self.previous_average_cpu_time,
-            )
-            .await
-            {
+        if harvest.use_cpu {
+            if let Ok(cpu_data) = {
+                #[cfg(target_os = "freebsd")]
+                {
+                    cpu::get_cpu_data_list(&self.sys, self.show_average_cpu).await
+                }
+                #[cfg(not(target_os = "freebsd"))]
+                {
+                    cpu::get_cpu_data_list(
+                        self.show_average_cpu,
+                        &mut self.previous_cpu_times,
+                        &mut self.previous_average_cpu_time,
+                    )
+                    .await
+                }
+            } {
                 self.data.cpu = Some(cpu_data);
             }
 
@@ -248,16 +639,23 @@ impl DataCollector {
 
         // Batteries
         #[cfg(feature = "battery")]
-        {
+        if harvest.use_battery {
             if let Some(battery_manager) = &self.battery_manager {
                 if let Some(battery_list) = &mut self.battery_list {
                     self.data.list_of_batteries =
                         Some(batteries::refresh_batteries(battery_manager, battery_list));
                 }
             }
+
+            if let Some(nut_server) = &self.nut_server {
+                self.data
+                    .list_of_batteries
+                    .get_or_insert_with(Vec::new)
+                    .extend(batteries::nut::get_ups_data(nut_server));
+            }
         }
 
-        if self.widgets_to_harvest.use_proc {
+        if harvest.use_proc {
             if let Ok(process_list) = {
                 #[cfg(target_os = "linux")]
                 {
@@ -266,10 +664,14 @@ impl DataCollector {
                         &mut self.prev_non_idle,
                         &mut self.pid_mapping,
                         self.use_current_cpu_total,
-                        current_instant
-                            .duration_since(self.last_collection_time)
-                            .as_secs(),
+                        current_instant.duration_since(last_proc_time).as_secs(),
                         self.mem_total_kb,
+                        self.collect_process_fds,
+                        self.collect_process_memory_breakdown,
+                        self.collect_process_latency,
+                        self.collect_process_security,
+                        self.collect_process_io_priority,
+                        &mut self.thread_tracker,
                     )
                 }
                 #[cfg(not(target_os = "linux"))]
@@ -281,49 +683,138 @@ impl DataCollector {
                     )
                 }
             } {
+                #[cfg(target_os = "windows")]
+                let process_list = {
+                    let mut process_list = process_list;
+                    if let Some(counter_tracker) = &mut self.counter_tracker {
+                        let sample = counter_tracker.get_sample();
+                        for process in &mut process_list {
+                            if let Some(gpu_usage) = &sample.gpu_usage {
+                                process.gpu_usage_percent =
+                                    Some(gpu_usage.get(&process.pid).copied().unwrap_or(0.0));
+                            }
+                            if let Some((read_bytes_per_sec, write_bytes_per_sec)) =
+                                sample.io_usage.get(&process.pid)
+                            {
+                                process.read_bytes_per_sec = *read_bytes_per_sec;
+                                process.write_bytes_per_sec = *write_bytes_per_sec;
+                            }
+                        }
+                    }
+                    process_list
+                };
+
                 self.data.list_of_processes = Some(process_list);
             }
+
+            #[cfg(target_os = "linux")]
+            {
+                self.data.threads = self.thread_tracker.take_threads();
+            }
+        }
+
+        if let Ok(connections) = connections::get_connection_data(harvest.use_conn) {
+            self.data.connections = connections;
+        }
+
+        self.data.sessions = sessions::get_session_data(harvest.use_users);
+        self.data.numa = numa::get_numa_data(harvest.use_numa);
+
+        self.data.power = self
+            .power_tracker
+            .get_power_data(harvest.use_power, current_instant);
+        self.data.units = self
+            .unit_tracker
+            .get_unit_data(harvest.use_units, current_instant);
+        self.data.vms = self.vm_tracker.get_vm_data(harvest.use_vm, current_instant);
+        self.data.activity = self
+            .activity_tracker
+            .get_activity_data(harvest.use_activity, current_instant);
+        self.data.pods = self.pod_tracker.get_pod_data(
+            harvest.use_pods,
+            self.kubelet_url
+                .as_deref()
+                .unwrap_or(pods::DEFAULT_KUBELET_URL),
+            self.kubelet_insecure,
+            current_instant,
+        );
+        // Messages are only harvested once, so add to any that haven't been sent yet.
+        if let Some(mut kernel_log) = self
+            .kernel_log_tracker
+            .get_kernel_log_data(harvest.use_kernel_log)
+        {
+            self.data
+                .kernel_log
+                .get_or_insert_with(Vec::new)
+                .append(&mut kernel_log);
         }
 
         let network_data_fut = {
-            #[cfg(target_os = "windows")]
+            #[cfg(any(target_os = "windows", target_os = "freebsd"))]
             {
                 network::get_network_data(
                     &self.sys,
-                    self.last_collection_time,
+                    last_net_time,
                     &mut self.total_rx,
                     &mut self.total_tx,
+                    &mut self.prev_interface_totals,
                     current_instant,
-                    self.widgets_to_harvest.use_net,
+                    harvest.use_net,
                     &self.filters.net_filter,
+                    &self.filters.interface_filter,
                 )
             }
-            #[cfg(not(target_os = "windows"))]
+            #[cfg(not(any(target_os = "windows", target_os = "freebsd")))]
             {
                 network::get_network_data(
-                    self.last_collection_time,
+                    last_net_time,
                     &mut self.total_rx,
                     &mut self.total_tx,
+                    &mut self.prev_interface_totals,
                     current_instant,
-                    self.widgets_to_harvest.use_net,
+                    harvest.use_net,
                     &self.filters.net_filter,
+                    &self.filters.interface_filter,
                 )
             }
         };
-        let mem_data_fut = memory::get_mem_data(self.widgets_to_harvest.use_mem);
-        let disk_data_fut = disks::get_disk_usage(
-            self.widgets_to_harvest.use_disk,
-            &self.filters.disk_filter,
-            &self.filters.mount_filter,
-        );
-        let disk_io_usage_fut = disks::get_io_usage(self.widgets_to_harvest.use_disk);
+        let mem_data_fut = {
+            #[cfg(target_os = "freebsd")]
+            {
+                memory::get_mem_data(&self.sys, harvest.use_mem)
+            }
+            #[cfg(not(target_os = "freebsd"))]
+            {
+                memory::get_mem_data(harvest.use_mem)
+            }
+        };
+        let disk_data_fut = {
+            #[cfg(target_os = "freebsd")]
+            {
+                disks::get_disk_usage(
+                    &self.sys,
+                    harvest.use_disk,
+                    &self.filters.disk_filter,
+                    &self.filters.mount_filter,
+                )
+            }
+            #[cfg(not(target_os = "freebsd"))]
+            {
+                disks::get_disk_usage(
+                    harvest.use_disk,
+                    &self.filters.disk_filter,
+                    &self.filters.mount_filter,
+                )
+            }
+        };
+        let disk_io_usage_fut = disks::get_io_usage(harvest.use_disk);
         let temp_data_fut = {
             #[cfg(not(target_os = "linux"))]
             {
                 temperature::get_temperature_data(
                     &self.sys,
                     &self.temperature_type,
-                    self.widgets_to_harvest.use_temp,
+                    harvest.use_temp,
                     &self.filters.temp_filter,
                 )
             }
@@ -332,7 +823,7 @@ impl DataCollector {
             {
                 temperature::get_temperature_data(
                     &self.temperature_type,
-                    self.widgets_to_harvest.use_temp,
+                    harvest.use_temp,
                     &self.filters.temp_filter,
                 )
             }
@@ -346,10 +837,17 @@ impl DataCollector {
             temp_data_fut
         );
 
-        if let Ok(net_data) = net_data {
-            if let Some(net_data) = &net_data {
+        if let Ok(mut net_data) = net_data {
+            if let Some(net_data) = &mut net_data {
                 self.total_rx = net_data.total_rx;
                 self.total_tx = net_data.total_tx;
+
+                if self.use_network_wireless {
+                    net_data.wireless = self.wireless_cache.get_wireless_data(current_instant);
+                }
+                if self.use_network_connections {
+                    net_data.tcp_counts = connections::get_tcp_state_counts();
+                }
             }
             self.data.network = net_data;
         }
@@ -362,7 +860,18 @@ impl DataCollector {
             self.data.swap = swap;
         }
 
-        if let Ok(disks) = disk_res {
+        #[cfg(target_os = "linux")]
+        {
+            self.data.huge_pages = memory::get_huge_page_data(harvest.use_mem);
+            self.data.shared_memory = memory::get_shared_memory_data(harvest.use_mem);
+        }
+
+        if let Ok(mut disks) = disk_res {
+            if self.use_disk_smart {
+                if let Some(disks) = &mut disks {
+                    self.smart_cache.update_disks(disks, current_instant);
+                }
+            }
             self.data.disks = disks;
         }
 
@@ -370,10 +879,25 @@ impl DataCollector {
             self.data.io = io;
         }
 
-        if let Ok(temp) = temp_res {
+        if let Ok(mut temp) = temp_res {
+            if self.use_ipmi {
+                if let Some(temp) = &mut temp {
+                    self.ipmi_cache.add_ipmi_data(
+                        temp,
+                        &self.temperature_type,
+                        &self.filters.temp_filter,
+                        current_instant,
+                    );
+                }
+            }
             self.data.temperature_sensors = temp;
         }
 
+        #[cfg(target_os = "linux")]
+        if harvest.use_temp {
+            self.data.throttle_status = temperature::get_throttle_status();
+        }
+
         // Update time
         self.data.last_collection_time = current_instant;
         self.last_collection_time = current_instant;
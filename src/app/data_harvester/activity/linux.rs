@@ -0,0 +1,249 @@
+//! Linux-specific functions regarding kernel activity.
+
+use std::time::Instant;
+
+use fxhash::FxHashMap;
+
+use super::{ActivityHarvest, ActivityKind};
+
+/// The softirq types in the order they're listed in `/proc/stat`.
+const SOFTIRQ_NAMES: [&str; 10] = [
+    "HI", "TIMER", "NET_TX", "NET_RX", "BLOCK", "IRQ_POLL", "TASKLET", "SCHED", "HRTIMER", "RCU",
+];
+
+/// Tracks the previous value of each counter, as rates are derived from the change in counters
+/// between two harvests.
+#[derive(Debug, Default)]
+pub struct ActivityTracker {
+    prev_counters: FxHashMap<String, u64>,
+    prev_instant: Option<Instant>,
+}
+
+impl ActivityTracker {
+    pub fn get_activity_data(
+        &mut self, actually_get: bool, current_instant: Instant,
+    ) -> Option<Vec<ActivityHarvest>> {
+        if !actually_get {
+            return None;
+        }
+
+        let stat = std::fs::read_to_string("/proc/stat").ok()?;
+        let interrupts = std::fs::read_to_string("/proc/interrupts").unwrap_or_default();
+
+        let mut counters = parse_stat(&stat);
+        let mut irqs = parse_interrupts(&interrupts);
+        // Lines that have never fired are just noise.
+        irqs.retain(|(_, _, count)| *count > 0);
+        counters.append(&mut irqs);
+
+        let elapsed = self
+            .prev_instant
+            .map(|prev_instant| current_instant.duration_since(prev_instant).as_secs_f64())
+            .filter(|elapsed| *elapsed > 0.0);
+
+        let mut activity_harvest: Vec<ActivityHarvest> = counters
+            .iter()
+            .map(|(name, kind, count)| ActivityHarvest {
+                name: name.clone(),
+                kind: kind.clone(),
+                rate_per_sec: elapsed.and_then(|elapsed| {
+                    self.prev_counters
+                        .get(name)
+                        .map(|prev_count| count.saturating_sub(*prev_count) as f64 / elapsed)
+                }),
+                value: *count,
+            })
+            .collect();
+
+        // Keep the summary and softirq rows in a fixed order, but list the busiest IRQs first.
+        activity_harvest.sort_by(|a, b| match (&a.kind, &b.kind) {
+            (ActivityKind::Irq, ActivityKind::Irq) => b
+                .rate_per_sec
+                .unwrap_or(0.0)
+                .partial_cmp(&a.rate_per_sec.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.value.cmp(&a.value)),
+            (ActivityKind::Irq, _) => std::cmp::Ordering::Greater,
+            (_, ActivityKind::Irq) => std::cmp::Ordering::Less,
+            _ => std::cmp::Ordering::Equal,
+        });
+
+        if let Some(entropy) = std::fs::read_to_string("/proc/sys/kernel/random/entropy_avail")
+            .ok()
+            .and_then(|entropy| entropy.trim().parse().ok())
+        {
+            // This goes right after the summary counters.
+            let index = activity_harvest
+                .iter()
+                .take_while(|activity| activity.kind == ActivityKind::Summary)
+                .count();
+            activity_harvest.insert(
+                index,
+                ActivityHarvest {
+                    name: "Entropy available".to_string(),
+                    kind: ActivityKind::Gauge,
+                    rate_per_sec: None,
+                    value: entropy,
+                },
+            );
+        }
+
+        self.prev_counters = counters
+            .into_iter()
+            .map(|(name, _, count)| (name, count))
+            .collect();
+        self.prev_instant = Some(current_instant);
+
+        Some(activity_harvest)
+    }
+}
+
+/// Parses the interrupt, context switch, and softirq counters out of `/proc/stat`.
+fn parse_stat(stat: &str) -> Vec<(String, ActivityKind, u64)> {
+    let mut interrupts = None;
+    let mut context_switches = None;
+    let mut softirqs = None;
+
+    for line in stat.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            // Only the first number is needed, as that's the total.
+            Some("intr") => interrupts = fields.next().and_then(|total| total.parse().ok()),
+            Some("ctxt") => context_switches = fields.next().and_then(|total| total.parse().ok()),
+            Some("softirq") => {
+                softirqs = Some(
+                    fields
+                        .filter_map(|count| count.parse::<u64>().ok())
+                        .collect::<Vec<_>>(),
+                )
+            }
+            _ => {}
+        }
+    }
+
+    let mut counters = Vec::new();
+    if let Some(interrupts) = interrupts {
+        counters.push(("Interrupts".to_string(), ActivityKind::Summary, interrupts));
+    }
+    if let Some(context_switches) = context_switches {
+        counters.push((
+            "Context switches".to_string(),
+            ActivityKind::Summary,
+            context_switches,
+        ));
+    }
+    if let Some(softirqs) = softirqs {
+        if let Some((total, breakdown)) = softirqs.split_first() {
+            counters.push(("Softirqs".to_string(), ActivityKind::Summary, *total));
+            for (name, count) in SOFTIRQ_NAMES.iter().zip(breakdown) {
+                counters.push((format!("Softirq {}", name), ActivityKind::Softirq, *count));
+            }
+        }
+    }
+
+    counters
+}
+
+/// Parses `/proc/interrupts`, summing each line's counts over all CPUs.  Numbered IRQs are named
+/// by their devices, and the rest (like `LOC`) by their descriptions.
+fn parse_interrupts(interrupts: &str) -> Vec<(String, ActivityKind, u64)> {
+    let mut lines = interrupts.lines();
+    let num_cpus = match lines.next() {
+        Some(header) => header.split_whitespace().count(),
+        None => return Vec::new(),
+    };
+
+    lines
+        .filter_map(|line| {
+            let (id, rest) = line.split_once(':')?;
+            let id = id.trim();
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+
+            // Some lines (like ERR) have a single count rather than one per CPU.
+            let num_counts = fields
+                .iter()
+                .take(num_cpus)
+                .take_while(|field| field.parse::<u64>().is_ok())
+                .count();
+            let count = fields[..num_counts]
+                .iter()
+                .filter_map(|field| field.parse::<u64>().ok())
+                .sum();
+            let description = &fields[num_counts..];
+
+            let name = if id.parse::<u64>().is_ok() {
+                // Skip the chip name and the hardware IRQ number and trigger type.
+                match description.get(2..) {
+                    Some(devices) if !devices.is_empty() => {
+                        format!("IRQ {} ({})", id, devices.join(" "))
+                    }
+                    _ => format!("IRQ {}", id),
+                }
+            } else if description.is_empty() {
+                id.to_string()
+            } else {
+                format!("{} ({})", id, description.join(" "))
+            };
+
+            Some((name, ActivityKind::Irq, count))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        let stat = "cpu  2255 34 2290 22625563 6290 127 456 0 0 0\n\
+                    intr 1290202 0 9 0 0\n\
+                    ctxt 2520931\n\
+                    btime 1665000000\n\
+                    softirq 618429 1 235203 3 32444 0 0 93 0 313 350372\n";
+
+        let counters = parse_stat(stat);
+        assert_eq!(
+            counters[..3],
+            [
+                ("Interrupts".to_string(), ActivityKind::Summary, 1290202),
+                (
+                    "Context switches".to_string(),
+                    ActivityKind::Summary,
+                    2520931
+                ),
+                ("Softirqs".to_string(), ActivityKind::Summary, 618429),
+            ]
+        );
+        assert_eq!(counters.len(), 3 + SOFTIRQ_NAMES.len());
+        assert_eq!(
+            counters[6],
+            ("Softirq NET_RX".to_string(), ActivityKind::Softirq, 32444)
+        );
+    }
+
+    #[test]
+    fn test_parse_interrupts() {
+        let interrupts = "            CPU0       CPU1\n  \
+                          0:         36          0   IO-APIC    2-edge      timer\n  \
+                          1:          5          4   IO-APIC    1-edge      i8042, serio\n  \
+                          9:          0          0   IO-APIC    9-fasteoi\n\
+                          LOC:     123456     654321   Local timer interrupts\n\
+                          ERR:          3\n";
+
+        assert_eq!(
+            parse_interrupts(interrupts),
+            vec![
+                ("IRQ 0 (timer)".to_string(), ActivityKind::Irq, 36),
+                ("IRQ 1 (i8042, serio)".to_string(), ActivityKind::Irq, 9),
+                ("IRQ 9".to_string(), ActivityKind::Irq, 0),
+                (
+                    "LOC (Local timer interrupts)".to_string(),
+                    ActivityKind::Irq,
+                    777777
+                ),
+                ("ERR".to_string(), ActivityKind::Irq, 3),
+            ]
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Data collection for kernel activity, like interrupt and context switch rates.
+//!
+//! For Linux, this is handled by reading `/proc/stat` and `/proc/interrupts`.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct ActivityTracker;
+
+        impl ActivityTracker {
+            pub fn get_activity_data(
+                &mut self, _actually_get: bool, _current_instant: std::time::Instant,
+            ) -> Option<Vec<ActivityHarvest>> {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActivityKind {
+    /// A system-wide counter, like the total number of interrupts.
+    Summary,
+    /// A type of softirq, like `NET_RX`.
+    Softirq,
+    /// A hardware interrupt line or architecture-specific interrupt, like `LOC`.
+    Irq,
+    /// A gauge rather than a counter, like the available entropy.
+    Gauge,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActivityHarvest {
+    pub name: String,
+    pub kind: ActivityKind,
+    /// The rate per second, which is only known for counters after the second harvest.
+    pub rate_per_sec: Option<f64>,
+    /// The total count since boot for counters, or the current value for gauges.
+    pub value: u64,
+}
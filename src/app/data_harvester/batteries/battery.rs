@@ -10,17 +10,23 @@
 //! For more information, see https://github.com/svartalf/rust-battery
 
 use battery::{
-    units::{power::watt, ratio::percent, time::second},
+    units::{electric_potential::volt, power::watt, ratio::percent, time::second},
     Battery, Manager,
 };
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatteryHarvest {
+    /// The name to display, if this isn't a plain battery.
+    pub name: Option<String>,
     pub charge_percent: f64,
     pub secs_until_full: Option<i64>,
     pub secs_until_empty: Option<i64>,
     pub power_consumption_rate_watts: f64,
-    pub health_percent: f64,
+    pub voltage_volts: f64,
+    pub health_percent: Option<f64>,
+    pub cycle_count: Option<u32>,
+    pub load_percent: Option<f64>,
 }
 
 pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<BatteryHarvest> {
@@ -29,6 +35,7 @@ pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<Ba
         .filter_map(|battery| {
             if manager.refresh(battery).is_ok() {
                 Some(BatteryHarvest {
+                    name: None,
                     secs_until_full: {
                         let optional_time = battery.time_to_full();
                         optional_time.map(|time| f64::from(time.get::<second>()) as i64)
@@ -39,7 +46,10 @@ pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<Ba
                     },
                     charge_percent: f64::from(battery.state_of_charge().get::<percent>()),
                     power_consumption_rate_watts: f64::from(battery.energy_rate().get::<watt>()),
-                    health_percent: f64::from(battery.state_of_health().get::<percent>()),
+                    voltage_volts: f64::from(battery.voltage().get::<volt>()),
+                    health_percent: Some(f64::from(battery.state_of_health().get::<percent>())),
+                    cycle_count: battery.cycle_count(),
+                    load_percent: None,
                 })
             } else {
                 None
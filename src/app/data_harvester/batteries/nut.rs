@@ -0,0 +1,186 @@
+//! Queries a Network UPS Tools (NUT) daemon for the state of its UPSes.
+//!
+//! This speaks the plain-text protocol of `upsd` directly, see
+//! https://networkupstools.org/docs/developer-guide.chunked/ar01s09.html.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use fxhash::FxHashMap;
+
+use super::BatteryHarvest;
+
+const DEFAULT_NUT_PORT: u16 = 3493;
+
+/// How long to wait on the daemon before giving up for this harvest.
+const NUT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Returns the state of every UPS known to the NUT daemon at `server`, which is a host with an
+/// optional port.  Returns an empty list if the daemon can't be reached.
+pub fn get_ups_data(server: &str) -> Vec<BatteryHarvest> {
+    query_ups_data(server).unwrap_or_default()
+}
+
+fn query_ups_data(server: &str) -> std::io::Result<Vec<BatteryHarvest>> {
+    let address = if server.contains(':') {
+        server.to_string()
+    } else {
+        format!("{}:{}", server, DEFAULT_NUT_PORT)
+    };
+    let socket_address = address.to_socket_addrs()?.next().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "could not resolve NUT server")
+    })?;
+
+    let stream = TcpStream::connect_timeout(&socket_address, NUT_TIMEOUT)?;
+    stream.set_read_timeout(Some(NUT_TIMEOUT))?;
+    stream.set_write_timeout(Some(NUT_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let ups_names = query_list(&mut writer, &mut reader, "LIST UPS")?
+        .iter()
+        .filter_map(|line| line.strip_prefix("UPS "))
+        .filter_map(|rest| rest.split_whitespace().next())
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+
+    let mut ups_data = Vec::with_capacity(ups_names.len());
+    for ups_name in ups_names {
+        let variables = parse_variables(
+            &ups_name,
+            &query_list(&mut writer, &mut reader, &format!("LIST VAR {}", ups_name))?,
+        );
+        ups_data.push(to_battery_harvest(ups_name, &variables));
+    }
+
+    let _ = writer.write_all(b"LOGOUT\n");
+
+    Ok(ups_data)
+}
+
+/// Sends a `LIST` command and returns the lines between its `BEGIN` and `END` markers.
+fn query_list(
+    writer: &mut TcpStream, reader: &mut BufReader<TcpStream>, command: &str,
+) -> std::io::Result<Vec<String>> {
+    writer.write_all(format!("{}\n", command).as_bytes())?;
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        let trimmed = line.trim_end();
+        if trimmed.starts_with("ERR ") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                trimmed.to_string(),
+            ));
+        } else if trimmed.starts_with("BEGIN ") {
+            continue;
+        } else if trimmed.starts_with("END ") {
+            break;
+        }
+        lines.push(trimmed.to_string());
+    }
+
+    Ok(lines)
+}
+
+/// Parses lines of the form `VAR <ups> <variable> "<value>"`.
+fn parse_variables(ups_name: &str, lines: &[String]) -> FxHashMap<String, String> {
+    let prefix = format!("VAR {} ", ups_name);
+
+    lines
+        .iter()
+        .filter_map(|line| line.strip_prefix(&prefix))
+        .filter_map(|rest| rest.split_once(' '))
+        .map(|(variable, value)| {
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            (variable.to_string(), value.replace("\\\"", "\""))
+        })
+        .collect()
+}
+
+fn to_battery_harvest(ups_name: String, variables: &FxHashMap<String, String>) -> BatteryHarvest {
+    let get_f64 = |variable: &str| {
+        variables
+            .get(variable)
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+
+    // The status is a list of flags, i.e. "OL CHRG" or "OB DISCHRG".
+    let is_on_battery = variables
+        .get("ups.status")
+        .map(|status| status.split_whitespace().any(|flag| flag == "OB"))
+        .unwrap_or(false);
+    let runtime_secs = get_f64("battery.runtime").map(|runtime| runtime as i64);
+
+    BatteryHarvest {
+        name: Some(format!("UPS {}", ups_name)),
+        charge_percent: get_f64("battery.charge").unwrap_or(0.0),
+        secs_until_full: None,
+        secs_until_empty: if is_on_battery { runtime_secs } else { None },
+        power_consumption_rate_watts: get_f64("ups.realpower").unwrap_or_else(|| {
+            // Fall back to estimating it from the load and the nominal power.
+            match (get_f64("ups.load"), get_f64("ups.realpower.nominal")) {
+                (Some(load), Some(nominal)) => load / 100.0 * nominal,
+                _ => 0.0,
+            }
+        }),
+        voltage_volts: get_f64("battery.voltage").unwrap_or(0.0),
+        health_percent: None,
+        cycle_count: None,
+        load_percent: get_f64("ups.load"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_variables() {
+        let lines = vec![
+            r#"VAR myups battery.charge "87""#.to_string(),
+            r#"VAR myups ups.status "OB DISCHRG""#.to_string(),
+            r#"VAR myups device.model "Smart-UPS \"1500\"""#.to_string(),
+            r#"VAR otherups battery.charge "12""#.to_string(),
+        ];
+        let variables = parse_variables("myups", &lines);
+
+        assert_eq!(variables.len(), 3);
+        assert_eq!(variables["battery.charge"], "87");
+        assert_eq!(variables["ups.status"], "OB DISCHRG");
+        assert_eq!(variables["device.model"], "Smart-UPS \"1500\"");
+    }
+
+    #[test]
+    fn test_to_battery_harvest() {
+        let mut variables = FxHashMap::default();
+        variables.insert("battery.charge".to_string(), "87".to_string());
+        variables.insert("battery.runtime".to_string(), "1200".to_string());
+        variables.insert("ups.status".to_string(), "OB DISCHRG".to_string());
+        variables.insert("ups.load".to_string(), "25".to_string());
+        variables.insert("ups.realpower.nominal".to_string(), "900".to_string());
+
+        let harvest = to_battery_harvest("myups".to_string(), &variables);
+        assert_eq!(harvest.name.as_deref(), Some("UPS myups"));
+        assert_eq!(harvest.charge_percent, 87.0);
+        assert_eq!(harvest.secs_until_empty, Some(1200));
+        assert_eq!(harvest.power_consumption_rate_watts, 225.0);
+        assert_eq!(harvest.load_percent, Some(25.0));
+
+        variables.insert("ups.status".to_string(), "OL CHRG".to_string());
+        let harvest = to_battery_harvest("myups".to_string(), &variables);
+        assert_eq!(harvest.secs_until_empty, None);
+    }
+}
@@ -0,0 +1,182 @@
+//! Linux-specific functions regarding open sockets.
+
+use std::net::SocketAddr;
+
+use fxhash::FxHashMap;
+use procfs::net::{TcpState, UdpState};
+
+use super::{ConnectionHarvest, ConnectionProtocol, TcpStateCounts};
+use crate::Pid;
+
+fn tcp_state_name(state: &TcpState) -> &'static str {
+    match state {
+        TcpState::Established => "ESTAB",
+        TcpState::SynSent => "SYN-SENT",
+        TcpState::SynRecv => "SYN-RECV",
+        TcpState::FinWait1 => "FIN-WAIT-1",
+        TcpState::FinWait2 => "FIN-WAIT-2",
+        TcpState::TimeWait => "TIME-WAIT",
+        TcpState::Close => "UNCONN",
+        TcpState::CloseWait => "CLOSE-WAIT",
+        TcpState::LastAck => "LAST-ACK",
+        TcpState::Listen => "LISTEN",
+        TcpState::Closing => "CLOSING",
+        TcpState::NewSynRecv => "NEW-SYN-RECV",
+    }
+}
+
+fn udp_state_name(state: &UdpState) -> &'static str {
+    match state {
+        UdpState::Established => "ESTAB",
+        UdpState::Close => "UNCONN",
+    }
+}
+
+/// Maps socket inodes to the PID and name of the process that owns them.  Sockets that belong
+/// to processes we can't inspect (usually due to permissions) are left out.
+fn get_socket_owners() -> FxHashMap<u64, (Pid, String)> {
+    let mut socket_owners = FxHashMap::default();
+
+    if let Ok(processes) = procfs::process::all_processes() {
+        for process in processes {
+            if let Ok(fds) = process.fd() {
+                for fd in fds {
+                    if let procfs::process::FDTarget::Socket(inode) = fd.target {
+                        socket_owners.insert(inode, (process.pid, process.stat.comm.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    socket_owners
+}
+
+pub fn get_connection_data(
+    actually_get: bool,
+) -> crate::utils::error::Result<Option<Vec<ConnectionHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let socket_owners = get_socket_owners();
+    let get_owner = |inode: u64| match socket_owners.get(&inode) {
+        Some((pid, name)) => (Some(*pid), Some(name.clone())),
+        None => (None, None),
+    };
+
+    let mut connections = Vec::new();
+
+    // The IPv6 tables don't exist if IPv6 is disabled, so don't fail on them.
+    let tcp_entries = procfs::net::tcp()?
+        .into_iter()
+        .chain(procfs::net::tcp6().unwrap_or_default());
+    for entry in tcp_entries {
+        let (pid, process_name) = get_owner(entry.inode);
+        connections.push(ConnectionHarvest {
+            protocol: ConnectionProtocol::Tcp,
+            local_address: entry.local_address,
+            remote_address: entry.remote_address,
+            state: tcp_state_name(&entry.state).to_string(),
+            pid,
+            process_name,
+        });
+    }
+
+    let udp_entries = procfs::net::udp()?
+        .into_iter()
+        .chain(procfs::net::udp6().unwrap_or_default());
+    for entry in udp_entries {
+        let (pid, process_name) = get_owner(entry.inode);
+        connections.push(ConnectionHarvest {
+            protocol: ConnectionProtocol::Udp,
+            local_address: entry.local_address,
+            remote_address: entry.remote_address,
+            state: udp_state_name(&entry.state).to_string(),
+            pid,
+            process_name,
+        });
+    }
+
+    Ok(Some(connections))
+}
+
+/// Describes every TCP, UDP, and Unix socket by inode, such as "TCP 127.0.0.1:22 -> 10.0.0.2:5123
+/// (ESTAB)", so that the open files of a process can name their sockets.
+pub fn get_socket_descriptions() -> FxHashMap<u64, String> {
+    let describe = |protocol: &str, local: SocketAddr, remote: SocketAddr, state: &str| {
+        // Listening and unconnected sockets have no remote address worth showing.
+        if remote.port() == 0 {
+            format!("{} {} ({})", protocol, local, state)
+        } else {
+            format!("{} {} -> {} ({})", protocol, local, remote, state)
+        }
+    };
+
+    let mut descriptions = FxHashMap::default();
+
+    let tcp_entries = procfs::net::tcp()
+        .unwrap_or_default()
+        .into_iter()
+        .chain(procfs::net::tcp6().unwrap_or_default());
+    for entry in tcp_entries {
+        descriptions.insert(
+            entry.inode,
+            describe(
+                "TCP",
+                entry.local_address,
+                entry.remote_address,
+                tcp_state_name(&entry.state),
+            ),
+        );
+    }
+
+    let udp_entries = procfs::net::udp()
+        .unwrap_or_default()
+        .into_iter()
+        .chain(procfs::net::udp6().unwrap_or_default());
+    for entry in udp_entries {
+        descriptions.insert(
+            entry.inode,
+            describe(
+                "UDP",
+                entry.local_address,
+                entry.remote_address,
+                udp_state_name(&entry.state),
+            ),
+        );
+    }
+
+    for entry in procfs::net::unix().unwrap_or_default() {
+        descriptions.insert(
+            entry.inode,
+            match entry.path {
+                Some(path) => format!("UNIX {}", path.display()),
+                None => "UNIX (unnamed)".to_string(),
+            },
+        );
+    }
+
+    descriptions
+}
+
+/// Counts TCP sockets by state.  Unlike [`get_connection_data`], this skips looking up the owner of
+/// each socket, so it's cheap enough to do on every refresh.
+pub fn get_tcp_state_counts() -> Option<TcpStateCounts> {
+    let tcp_entries = procfs::net::tcp()
+        .ok()?
+        .into_iter()
+        .chain(procfs::net::tcp6().unwrap_or_default());
+
+    let mut counts = TcpStateCounts::default();
+    for entry in tcp_entries {
+        match entry.state {
+            TcpState::Established => counts.established += 1,
+            TcpState::TimeWait => counts.time_wait += 1,
+            TcpState::Listen => counts.listen += 1,
+            _ => {}
+        }
+    }
+
+    Some(counts)
+}
@@ -0,0 +1,91 @@
+//! Data collection for open sockets.
+//!
+//! For Linux, this is handled by reading `/proc/net` via procfs.
+//! Other platforms are currently not supported.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        pub fn get_connection_data(
+            _actually_get: bool,
+        ) -> crate::utils::error::Result<Option<Vec<ConnectionHarvest>>> {
+            Ok(None)
+        }
+
+        pub fn get_tcp_state_counts() -> Option<TcpStateCounts> {
+            None
+        }
+    }
+}
+
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Pid;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum ConnectionProtocol {
+    Tcp,
+    Udp,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ConnectionSorting {
+    Protocol,
+    LocalAddress,
+    RemoteAddress,
+    State,
+    Pid,
+    ProcessName,
+}
+
+impl ConnectionSorting {
+    /// The order in which the sort column is cycled through, which matches the column order.
+    pub const ORDER: [ConnectionSorting; 6] = [
+        ConnectionSorting::Protocol,
+        ConnectionSorting::LocalAddress,
+        ConnectionSorting::RemoteAddress,
+        ConnectionSorting::State,
+        ConnectionSorting::Pid,
+        ConnectionSorting::ProcessName,
+    ];
+}
+
+impl Default for ConnectionSorting {
+    fn default() -> Self {
+        ConnectionSorting::LocalAddress
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConnectionHarvest {
+    pub protocol: ConnectionProtocol,
+    pub local_address: SocketAddr,
+    pub remote_address: SocketAddr,
+    pub state: String,
+    pub pid: Option<Pid>,
+    pub process_name: Option<String>,
+}
+
+impl ConnectionHarvest {
+    /// Returns the protocol name as `ss` would display it, i.e. `tcp` or `udp6`.
+    pub fn protocol_name(&self) -> &'static str {
+        match (self.protocol, self.local_address.is_ipv6()) {
+            (ConnectionProtocol::Tcp, false) => "tcp",
+            (ConnectionProtocol::Tcp, true) => "tcp6",
+            (ConnectionProtocol::Udp, false) => "udp",
+            (ConnectionProtocol::Udp, true) => "udp6",
+        }
+    }
+}
+
+/// The number of TCP sockets in the states most useful for spotting connection storms.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TcpStateCounts {
+    pub established: u64,
+    pub time_wait: u64,
+    pub listen: u64,
+}
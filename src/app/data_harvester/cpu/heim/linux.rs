@@ -1,6 +1,16 @@
 //! Linux-specific functions regarding CPU usage.
 
+use std::{fs, path::Path};
+
 use heim::cpu::os::linux::CpuTimeExt;
+use once_cell::sync::Lazy;
+
+use crate::app::data_harvester::cpu::{CoreType, CpuTopology};
+
+/// The topology of each core, indexed by the core number.  It is only read once, as it does not
+/// change while running.
+static CPU_TOPOLOGY: Lazy<Vec<Option<CpuTopology>>> = Lazy::new(read_cpu_topology);
+
 pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
     let working_time: f64 = (cpu_time.user()
         + cpu_time.nice()
@@ -14,3 +24,116 @@ pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
         working_time + (cpu_time.idle() + cpu_time.io_wait()).get::<heim::units::time::second>(),
     )
 }
+
+/// Returns the current frequency of the given core in MHz, as reported by cpufreq.
+pub fn get_cpu_frequency(cpu: usize) -> Option<f64> {
+    read_cpufreq_khz(cpu, "scaling_cur_freq").map(|khz| khz / 1000.0)
+}
+
+/// Returns the maximum frequency of the given core in MHz, as reported by cpufreq.
+pub fn get_cpu_max_frequency(cpu: usize) -> Option<f64> {
+    read_cpufreq_khz(cpu, "cpuinfo_max_freq").map(|khz| khz / 1000.0)
+}
+
+fn read_cpufreq_khz(cpu: usize, file: &str) -> Option<f64> {
+    std::fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{}/cpufreq/{}",
+        cpu, file
+    ))
+    .ok()
+    .and_then(|contents| contents.trim().parse::<f64>().ok())
+}
+
+/// Returns where the given core is in the machine, as reported by sysfs.
+pub fn get_cpu_topology(cpu: usize) -> Option<CpuTopology> {
+    CPU_TOPOLOGY.get(cpu).copied().flatten()
+}
+
+fn read_cpu_topology() -> Vec<Option<CpuTopology>> {
+    // Hybrid Intel chips list their performance and efficiency cores under separate PMUs.
+    let performance_cpus = read_cpu_list("/sys/devices/cpu_core/cpus");
+    let efficiency_cpus = read_cpu_list("/sys/devices/cpu_atom/cpus");
+
+    let mut topology = Vec::new();
+    if let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") {
+        for entry in entries.flatten() {
+            let cpu = match entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .and_then(|number| number.parse::<usize>().ok())
+            {
+                Some(cpu) => cpu,
+                None => continue,
+            };
+
+            let path = entry.path().join("topology");
+            let read_id = |file: &str| {
+                fs::read_to_string(path.join(file))
+                    .ok()
+                    .and_then(|contents| contents.trim().parse::<usize>().ok())
+            };
+
+            if topology.len() <= cpu {
+                topology.resize(cpu + 1, None);
+            }
+            topology[cpu] = match (read_id("physical_package_id"), read_id("core_id")) {
+                (Some(package_id), Some(core_id)) => Some(CpuTopology {
+                    package_id,
+                    cluster_id: read_id("cluster_id"),
+                    core_id,
+                    core_type: if performance_cpus.contains(&cpu) {
+                        Some(CoreType::Performance)
+                    } else if efficiency_cpus.contains(&cpu) {
+                        Some(CoreType::Efficiency)
+                    } else {
+                        None
+                    },
+                }),
+                _ => None,
+            };
+        }
+    }
+
+    topology
+}
+
+fn read_cpu_list(path: impl AsRef<Path>) -> Vec<usize> {
+    fs::read_to_string(path)
+        .map(|contents| parse_cpu_list(&contents))
+        .unwrap_or_default()
+}
+
+/// Parses a list of cores like `0-3,8,10-11`.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some(start.parse::<usize>().ok()?..=end.parse::<usize>().ok()?),
+            None => range.parse::<usize>().ok().map(|cpu| cpu..=cpu),
+        })
+        .flatten()
+        .collect()
+}
+
+/// Cores aren't split into named clusters on Linux, so every core uses the same prefix.
+pub fn get_cpu_prefix(_cpu: usize) -> &'static str {
+    "CPU"
+}
+
+/// GPU utilization is currently not supported on this platform.
+pub fn get_gpu_usage() -> Option<f64> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! macOS-specific functions regarding CPU usage, such as telling apart the core clusters of Apple
+//! Silicon chips and reading GPU utilization.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::app::data_harvester::cpu::{CoreType, CpuTopology};
+
+/// The number of efficiency cores, if the chip has more than one performance level.
+static EFFICIENCY_CORES: Lazy<Option<usize>> = Lazy::new(|| {
+    if sysctl_u32("hw.nperflevels")? < 2 {
+        return None;
+    }
+
+    // `perflevel0` is the fastest cluster, so `perflevel1` is the efficiency cluster.
+    sysctl_u32("hw.perflevel1.logicalcpu").map(|count| count as usize)
+});
+
+/// Spawning `ioreg` is relatively expensive, so the GPU utilization is only read this often.
+const GPU_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The last GPU utilization that was read, and when.
+static LAST_GPU_USAGE: Lazy<Mutex<Option<(Option<f64>, Instant)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Returns the legend prefix of the given core. On Apple Silicon, efficiency cores are numbered
+/// first and are prefixed with `E-CPU`, while performance cores are prefixed with `P-CPU`.
+pub fn get_cpu_prefix(cpu: usize) -> &'static str {
+    match *EFFICIENCY_CORES {
+        Some(efficiency_cores) if cpu < efficiency_cores => "E-CPU",
+        Some(_) => "P-CPU",
+        None => "CPU",
+    }
+}
+
+/// Returns where the given core is.  Apple Silicon chips have a single package, with the
+/// efficiency and performance cores in a cluster each, and no hardware threads.
+pub fn get_cpu_topology(cpu: usize) -> Option<CpuTopology> {
+    let efficiency_cores = (*EFFICIENCY_CORES)?;
+    let is_efficiency_core = cpu < efficiency_cores;
+
+    Some(CpuTopology {
+        package_id: 0,
+        cluster_id: Some(if is_efficiency_core { 0 } else { 1 }),
+        core_id: cpu,
+        core_type: Some(if is_efficiency_core {
+            CoreType::Efficiency
+        } else {
+            CoreType::Performance
+        }),
+    })
+}
+
+/// Returns the GPU utilization as a percentage, as reported by the GPU driver through the
+/// IORegistry.  It is read again at most every [`GPU_POLL_INTERVAL`].
+pub fn get_gpu_usage() -> Option<f64> {
+    let mut last_gpu_usage = LAST_GPU_USAGE.lock().ok()?;
+    match *last_gpu_usage {
+        Some((gpu_usage, last_poll)) if last_poll.elapsed() < GPU_POLL_INTERVAL => gpu_usage,
+        _ => {
+            let gpu_usage = read_gpu_usage();
+            *last_gpu_usage = Some((gpu_usage, Instant::now()));
+            gpu_usage
+        }
+    }
+}
+
+fn read_gpu_usage() -> Option<f64> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-r", "-d", "1", "-w", "0", "-c", "IOAccelerator"])
+        .output()
+        .ok()?;
+
+    parse_gpu_usage(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the `Device Utilization %` entry of the `PerformanceStatistics` dictionary in the
+/// output of `ioreg`.
+fn parse_gpu_usage(ioreg: &str) -> Option<f64> {
+    const KEY: &str = "\"Device Utilization %\"=";
+
+    let start = ioreg.find(KEY)? + KEY.len();
+    ioreg[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+fn sysctl_u32(name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    let mut value: u32 = 0;
+    let mut size = std::mem::size_of::<u32>();
+
+    let result = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            &mut value as *mut u32 as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+
+    if result == 0 {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpu_usage() {
+        let ioreg = r#"+-o AGXAcceleratorG13X  <class AGXAcceleratorG13X, id 0x1000004a0, registered, matched, active, busy 0 (0 ms), retain 110>
+    {
+      "PerformanceStatistics" = {"In use system memory"=123043840,"Tiler Utilization %"=4,"Device Utilization %"=17,"Renderer Utilization %"=15}
+    }"#;
+
+        assert_eq!(parse_gpu_usage(ioreg), Some(17.0));
+        assert_eq!(parse_gpu_usage("{}"), None);
+    }
+}
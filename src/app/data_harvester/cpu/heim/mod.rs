@@ -1,5 +1,7 @@
 //! CPU stats through heim.
 //! Supports macOS, Linux, and Windows.
+//!
+//! On macOS, the core clusters of Apple Silicon and the GPU utilization are read separately.
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
@@ -11,6 +13,13 @@ cfg_if::cfg_if! {
     }
 }
 
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "macos")] {
+        pub mod macos;
+        pub use macos::*;
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(target_family = "unix")] {
         pub mod unix;
@@ -18,18 +27,7 @@ cfg_if::cfg_if! {
     }
 }
 
-#[derive(Default, Debug, Clone)]
-pub struct CpuData {
-    pub cpu_prefix: String,
-    pub cpu_count: Option<usize>,
-    pub cpu_usage: f64,
-}
-
-pub type CpuHarvest = Vec<CpuData>;
-
-pub type PastCpuWork = f64;
-pub type PastCpuTotal = f64;
-
+use super::{CpuData, CpuHarvest, PastCpuTotal, PastCpuWork};
 use futures::StreamExt;
 use std::collections::VecDeque;
 
@@ -73,19 +71,25 @@ pub async fn get_cpu_data_list(
                 let present_times = convert_cpu_times(&present);
                 new_cpu_times.push(present_times);
                 cpu_deque.push_back(CpuData {
-                    cpu_prefix: "CPU".to_string(),
+                    cpu_prefix: get_cpu_prefix(itx).to_string(),
                     cpu_count: Some(itx),
                     cpu_usage: calculate_cpu_usage_percentage(
                         convert_cpu_times(&past),
                         present_times,
                     ),
+                    frequency_mhz: get_cpu_frequency(itx),
+                    max_frequency_mhz: get_cpu_max_frequency(itx),
+                    topology: get_cpu_topology(itx),
                 });
             } else {
                 new_cpu_times.push((0.0, 0.0));
                 cpu_deque.push_back(CpuData {
-                    cpu_prefix: "CPU".to_string(),
+                    cpu_prefix: get_cpu_prefix(itx).to_string(),
                     cpu_count: Some(itx),
                     cpu_usage: 0.0,
+                    frequency_mhz: get_cpu_frequency(itx),
+                    max_frequency_mhz: get_cpu_max_frequency(itx),
+                    topology: get_cpu_topology(itx),
                 });
             }
         }
@@ -107,21 +111,27 @@ pub async fn get_cpu_data_list(
                         (
                             present_times,
                             CpuData {
-                                cpu_prefix: "CPU".to_string(),
+                                cpu_prefix: get_cpu_prefix(itx).to_string(),
                                 cpu_count: Some(itx),
                                 cpu_usage: calculate_cpu_usage_percentage(
                                     (*past_cpu_work, *past_cpu_total),
                                     present_times,
                                 ),
+                                frequency_mhz: get_cpu_frequency(itx),
+                                max_frequency_mhz: get_cpu_max_frequency(itx),
+                                topology: get_cpu_topology(itx),
                             },
                         )
                     } else {
                         (
                             (*past_cpu_work, *past_cpu_total),
                             CpuData {
-                                cpu_prefix: "CPU".to_string(),
+                                cpu_prefix: get_cpu_prefix(itx).to_string(),
                                 cpu_count: Some(itx),
                                 cpu_usage: 0.0,
+                                frequency_mhz: get_cpu_frequency(itx),
+                                max_frequency_mhz: get_cpu_max_frequency(itx),
+                                topology: get_cpu_topology(itx),
                             },
                         )
                     }
@@ -157,13 +167,49 @@ pub async fn get_cpu_data_list(
         };
 
         *previous_average_cpu_time = Some(new_average_cpu_time);
+        let average_frequency = |frequencies: Vec<f64>| {
+            if frequencies.is_empty() {
+                None
+            } else {
+                Some(frequencies.iter().sum::<f64>() / frequencies.len() as f64)
+            }
+        };
+        let frequency_mhz = average_frequency(
+            cpu_deque
+                .iter()
+                .filter_map(|cpu| cpu.frequency_mhz)
+                .collect(),
+        );
+        let max_frequency_mhz = average_frequency(
+            cpu_deque
+                .iter()
+                .filter_map(|cpu| cpu.max_frequency_mhz)
+                .collect(),
+        );
+
         cpu_deque.push_front(CpuData {
             cpu_prefix: "AVG".to_string(),
             cpu_count: None,
             cpu_usage,
+            frequency_mhz,
+            max_frequency_mhz,
+            topology: None,
         })
     }
 
+    // The GPU is listed alongside the cores where its utilization is available, as it shares the
+    // same percentage scale.
+    if let Some(gpu_usage) = get_gpu_usage() {
+        cpu_deque.push_back(CpuData {
+            cpu_prefix: "GPU".to_string(),
+            cpu_count: None,
+            cpu_usage: gpu_usage,
+            frequency_mhz: None,
+            max_frequency_mhz: None,
+            topology: None,
+        });
+    }
+
     // Ok(Vec::from(cpu_deque.drain(0..3).collect::<Vec<_>>())) // For artificially limiting the CPU results
 
     Ok(Vec::from(cpu_deque))
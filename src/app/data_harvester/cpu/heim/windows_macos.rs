@@ -8,3 +8,31 @@ pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
         working_time + cpu_time.idle().get::<heim::units::time::second>(),
     )
 }
+
+/// Per-core frequencies are currently not supported on this platform.
+pub fn get_cpu_frequency(_cpu: usize) -> Option<f64> {
+    None
+}
+
+/// Per-core frequencies are currently not supported on this platform.
+pub fn get_cpu_max_frequency(_cpu: usize) -> Option<f64> {
+    None
+}
+
+/// Cores aren't split into named clusters on Windows, so every core uses the same prefix.
+#[cfg(target_os = "windows")]
+pub fn get_cpu_prefix(_cpu: usize) -> &'static str {
+    "CPU"
+}
+
+/// Core topology is currently not supported on this platform.
+#[cfg(target_os = "windows")]
+pub fn get_cpu_topology(_cpu: usize) -> Option<crate::app::data_harvester::cpu::CpuTopology> {
+    None
+}
+
+/// GPU utilization is currently not supported on this platform.
+#[cfg(target_os = "windows")]
+pub fn get_gpu_usage() -> Option<f64> {
+    None
+}
@@ -1,14 +1,59 @@
 //! Data collection for CPU usage and load average.
 //!
-//! For CPU usage, Linux, macOS, and Windows are handled by Heim.
+//! For CPU usage, Linux, macOS, and Windows are handled by Heim, and FreeBSD is handled by
+//! sysinfo.
 //!
-//! For load average, macOS and Linux are supported through Heim.
+//! For load average, macOS and Linux are supported through Heim, and FreeBSD through
+//! `getloadavg`.
+
+use serde::{Deserialize, Serialize};
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
         pub mod heim;
         pub use self::heim::*;
+    } else if #[cfg(target_os = "freebsd")] {
+        pub mod sysinfo;
+        pub use self::sysinfo::*;
     }
 }
 
 pub type LoadAvgHarvest = [f32; 3];
+
+/// Whether a core is one of the faster or the more efficient ones, on chips that have both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CoreType {
+    Performance,
+    Efficiency,
+}
+
+/// Where a core sits in the machine, for grouping the cores in the CPU widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuTopology {
+    /// The physical package, or socket, that the core is on.
+    pub package_id: usize,
+    /// The cluster of cores that the core is in, if the platform reports one.
+    pub cluster_id: Option<usize>,
+    /// The physical core, which is shared by hardware threads of the same package.
+    pub core_id: usize,
+    /// The type of the core, if the chip has more than one.
+    pub core_type: Option<CoreType>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CpuData {
+    pub cpu_prefix: String,
+    pub cpu_count: Option<usize>,
+    pub cpu_usage: f64,
+    /// The current frequency in MHz, if it could be determined.
+    pub frequency_mhz: Option<f64>,
+    /// The maximum frequency in MHz, if it could be determined.
+    pub max_frequency_mhz: Option<f64>,
+    /// Where the core is in the machine, if it could be determined.
+    pub topology: Option<CpuTopology>,
+}
+
+pub type CpuHarvest = Vec<CpuData>;
+
+pub type PastCpuWork = f64;
+pub type PastCpuTotal = f64;
@@ -0,0 +1,53 @@
+//! CPU stats through sysinfo.
+//! Supports FreeBSD.
+
+use std::collections::VecDeque;
+
+use sysinfo::{ProcessorExt, System, SystemExt};
+
+use super::{CpuData, CpuHarvest, LoadAvgHarvest};
+use crate::utils::error::BottomError;
+
+pub async fn get_cpu_data_list(
+    sys: &System, show_average_cpu: bool,
+) -> crate::error::Result<CpuHarvest> {
+    let mut cpu_deque: VecDeque<CpuData> = sys
+        .processors()
+        .iter()
+        .enumerate()
+        .map(|(itx, processor)| CpuData {
+            cpu_prefix: "CPU".to_string(),
+            cpu_count: Some(itx),
+            cpu_usage: processor.cpu_usage() as f64,
+            frequency_mhz: Some(processor.frequency() as f64).filter(|frequency| *frequency > 0.0),
+            max_frequency_mhz: None,
+            topology: None,
+        })
+        .collect();
+
+    if show_average_cpu {
+        cpu_deque.push_front(CpuData {
+            cpu_prefix: "AVG".to_string(),
+            cpu_count: None,
+            cpu_usage: sys.global_processor_info().cpu_usage() as f64,
+            frequency_mhz: None,
+            max_frequency_mhz: None,
+            topology: None,
+        })
+    }
+
+    Ok(Vec::from(cpu_deque))
+}
+
+pub async fn get_load_avg() -> crate::error::Result<LoadAvgHarvest> {
+    let mut load_avg = [0.0_f64; 3];
+
+    // SAFETY: `load_avg` has room for the three requested samples.
+    if unsafe { libc::getloadavg(load_avg.as_mut_ptr(), 3) } != 3 {
+        return Err(BottomError::GenericError(
+            "Could not get the load average".to_string(),
+        ));
+    }
+
+    Ok([load_avg[0] as f32, load_avg[1] as f32, load_avg[2] as f32])
+}
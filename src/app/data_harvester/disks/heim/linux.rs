@@ -1,7 +1,11 @@
 //! Linux-specific things for Heim disk data collection.
 
+use std::collections::HashMap;
+
 use heim::disk::Partition;
 
+use crate::app::data_harvester::disks::IoTimes;
+
 pub fn get_device_name(partition: &Partition) -> String {
     if let Some(device) = partition.device() {
         // See if this disk is actually mounted elsewhere on Linux...
@@ -32,3 +36,48 @@ pub fn get_device_name(partition: &Partition) -> String {
         "Name Unavailable".to_string()
     }
 }
+
+/// Returns the timing counters of each device in `/proc/diskstats`, keyed by device name.
+pub fn get_io_times() -> HashMap<String, IoTimes> {
+    std::fs::read_to_string("/proc/diskstats")
+        .map(|diskstats| diskstats.lines().filter_map(parse_diskstats_line).collect())
+        .unwrap_or_default()
+}
+
+/// Parses a line of `/proc/diskstats`. See <https://www.kernel.org/doc/Documentation/iostats.txt>
+/// for the meaning of each field.
+fn parse_diskstats_line(line: &str) -> Option<(String, IoTimes)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let field = |index: usize| -> Option<u64> { fields.get(index)?.parse().ok() };
+
+    let name = fields.get(2)?.to_string();
+    let (reads, read_time_ms) = (field(3)?, field(6)?);
+    let (writes, write_time_ms) = (field(7)?, field(10)?);
+    let busy_time_ms = field(12)?;
+
+    Some((
+        name,
+        IoTimes {
+            completed_ops: reads + writes,
+            wait_time_ms: read_time_ms + write_time_ms,
+            busy_time_ms,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diskstats_line() {
+        let line = "   8       0 sda 1200 30 90000 400 800 20 50000 600 0 700 1000 0 0 0 0";
+        let (name, times) = parse_diskstats_line(line).unwrap();
+        assert_eq!(name, "sda");
+        assert_eq!(times.completed_ops, 2000);
+        assert_eq!(times.wait_time_ms, 1000);
+        assert_eq!(times.busy_time_ms, 700);
+
+        assert!(parse_diskstats_line("   8       0 sda 1200").is_none());
+    }
+}
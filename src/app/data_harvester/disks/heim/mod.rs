@@ -1,5 +1,7 @@
 use crate::app::Filter;
 
+use super::{get_inode_usage, should_keep_disk, DiskHarvest, IoData, IoHarvest, TMPFS_NAME};
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         pub mod linux;
@@ -10,23 +12,6 @@ cfg_if::cfg_if! {
     }
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct DiskHarvest {
-    pub name: String,
-    pub mount_point: String,
-    pub free_space: Option<u64>,
-    pub used_space: Option<u64>,
-    pub total_space: Option<u64>,
-}
-
-#[derive(Clone, Debug)]
-pub struct IoData {
-    pub read_bytes: u64,
-    pub write_bytes: u64,
-}
-
-pub type IoHarvest = std::collections::HashMap<String, Option<IoData>>;
-
 pub async fn get_io_usage(actually_get: bool) -> crate::utils::error::Result<Option<IoHarvest>> {
     if !actually_get {
         return Ok(None);
@@ -37,6 +22,10 @@ pub async fn get_io_usage(actually_get: bool) -> crate::utils::error::Result<Opt
     let mut io_hash: std::collections::HashMap<String, Option<IoData>> =
         std::collections::HashMap::new();
 
+    // heim doesn't expose the timing counters, so those are read separately.
+    #[cfg(target_os = "linux")]
+    let mut io_times = get_io_times();
+
     let counter_stream = heim::disk::io_counters().await?;
     futures::pin_mut!(counter_stream);
 
@@ -49,6 +38,10 @@ pub async fn get_io_usage(actually_get: bool) -> crate::utils::error::Result<Opt
                 Some(IoData {
                     read_bytes: io.read_bytes().get::<heim::units::information::byte>(),
                     write_bytes: io.write_bytes().get::<heim::units::information::byte>(),
+                    #[cfg(target_os = "linux")]
+                    times: io_times.remove(mount_point),
+                    #[cfg(not(target_os = "linux"))]
+                    times: None,
                 }),
             );
         }
@@ -67,83 +60,81 @@ pub async fn get_disk_usage(
     use futures::StreamExt;
 
     let mut vec_disks: Vec<DiskHarvest> = Vec::new();
+    let mut partitions: Vec<(heim::disk::Partition, bool)> = Vec::new();
+
     let partitions_stream = heim::disk::partitions_physical().await?;
     futures::pin_mut!(partitions_stream);
-
     while let Some(part) = partitions_stream.next().await {
         if let Ok(partition) = part {
-            let name = get_device_name(&partition);
-
-            let mount_point = (partition
-                .mount_point()
-                .to_str()
-                .unwrap_or("Name Unavailable"))
-            .to_string();
-
-            // Precedence ordering in the case where name and mount filters disagree, "allow" takes precedence over "deny".
-            //
-            // For implementation, we do this as follows:
-            // 1. Is the entry allowed through any filter? That is, does it match an entry in a filter where `is_list_ignored` is `false`? If so, we always keep this entry.
-            // 2. Is the entry denied through any filter? That is, does it match an entry in a filter where `is_list_ignored` is `true`? If so, we always deny this entry.
-            // 3. Anything else is allowed.
-
-            let filter_check_map = [(disk_filter, &name), (mount_filter, &mount_point)];
-
-            // This represents case 1.  That is, if there is a match in an allowing list - if there is, then
-            // immediately allow it!
-            let matches_allow_list = filter_check_map.iter().any(|(filter, text)| {
-                if let Some(filter) = filter {
-                    if !filter.is_list_ignored {
-                        for r in &filter.list {
-                            if r.is_match(text) {
-                                return true;
-                            }
-                        }
-                    }
+            partitions.push((partition, false));
+        }
+    }
+
+    // tmpfs mounts aren't physical, but they take up memory, so they're listed (and tagged) too.
+    #[cfg(target_os = "linux")]
+    {
+        let all_partitions_stream = heim::disk::partitions().await?;
+        futures::pin_mut!(all_partitions_stream);
+        while let Some(part) = all_partitions_stream.next().await {
+            if let Ok(partition) = part {
+                // The same tmpfs can be mounted more than once at the same place, so skip repeats.
+                let is_repeat = partitions
+                    .iter()
+                    .any(|(existing, _)| existing.mount_point() == partition.mount_point());
+                if partition.file_system().as_str() == "tmpfs" && !is_repeat {
+                    partitions.push((partition, true));
                 }
-                false
-            });
+            }
+        }
+    }
 
-            let to_keep = if matches_allow_list {
-                true
-            } else {
-                // If it doesn't match an allow list, then check if it is denied.
-                // That is, if it matches in a reject filter, then reject.  Otherwise, we always keep it.
-                !filter_check_map.iter().any(|(filter, text)| {
-                    if let Some(filter) = filter {
-                        if filter.is_list_ignored {
-                            for r in &filter.list {
-                                if r.is_match(text) {
-                                    return true;
-                                }
-                            }
-                        }
-                    }
-                    false
-                })
+    for (partition, is_tmpfs) in partitions {
+        let name = if is_tmpfs {
+            // The "device" of a tmpfs mount is arbitrary, usually just "tmpfs".
+            TMPFS_NAME.to_string()
+        } else {
+            get_device_name(&partition)
+        };
+
+        let mount_point = (partition
+            .mount_point()
+            .to_str()
+            .unwrap_or("Name Unavailable"))
+        .to_string();
+
+        if should_keep_disk(disk_filter, mount_filter, &name, &mount_point) {
+            // The usage line can fail in some cases (for example, if you use Void Linux + LUKS,
+            // see https://github.com/ClementTsang/bottom/issues/419 for details).  As such, check
+            // it like this instead.
+            let (used_inodes, total_inodes) = match get_inode_usage(&mount_point) {
+                Some((used_inodes, total_inodes)) => (Some(used_inodes), Some(total_inodes)),
+                None => (None, None),
             };
 
-            if to_keep {
-                // The usage line can fail in some cases (for example, if you use Void Linux + LUKS,
-                // see https://github.com/ClementTsang/bottom/issues/419 for details).  As such, check
-                // it like this instead.
-                if let Ok(usage) = heim::disk::usage(partition.mount_point()).await {
-                    vec_disks.push(DiskHarvest {
-                        free_space: Some(usage.free().get::<heim::units::information::byte>()),
-                        used_space: Some(usage.used().get::<heim::units::information::byte>()),
-                        total_space: Some(usage.total().get::<heim::units::information::byte>()),
-                        mount_point,
-                        name,
-                    });
-                } else {
-                    vec_disks.push(DiskHarvest {
-                        free_space: None,
-                        used_space: None,
-                        total_space: None,
-                        mount_point,
-                        name,
-                    });
-                }
+            if let Ok(usage) = heim::disk::usage(partition.mount_point()).await {
+                vec_disks.push(DiskHarvest {
+                    free_space: Some(usage.free().get::<heim::units::information::byte>()),
+                    used_space: Some(usage.used().get::<heim::units::information::byte>()),
+                    total_space: Some(usage.total().get::<heim::units::information::byte>()),
+                    used_inodes,
+                    total_inodes,
+                    smart: None,
+                    is_tmpfs,
+                    mount_point,
+                    name,
+                });
+            } else {
+                vec_disks.push(DiskHarvest {
+                    free_space: None,
+                    used_space: None,
+                    total_space: None,
+                    used_inodes,
+                    total_inodes,
+                    smart: None,
+                    is_tmpfs,
+                    mount_point,
+                    name,
+                });
             }
         }
     }
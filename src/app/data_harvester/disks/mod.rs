@@ -1,10 +1,162 @@
 //! Data collection for disks (IO, usage, space, etc.).
 //!
 //! For Linux, macOS, and Windows, this is handled by heim.
+//! For FreeBSD, this is handled by sysinfo.
+
+pub mod smart;
+pub use smart::{SmartCache, SmartData};
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
         pub mod heim;
         pub use self::heim::*;
+    } else if #[cfg(target_os = "freebsd")] {
+        pub mod sysinfo;
+        pub use self::sysinfo::*;
+    }
+}
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::Filter;
+
+/// The name given to tmpfs mounts, which is what disk filters will match against.
+pub const TMPFS_NAME: &str = "[tmpfs]";
+
+/// The columns that the disk table can be sorted by.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DiskSorting {
+    Disk,
+    Mount,
+    UsedPercent,
+    InodePercent,
+    Free,
+    Total,
+    ReadPerSecond,
+    WritePerSecond,
+}
+
+impl DiskSorting {
+    /// The order in which the sort column is cycled through, which matches the column order.
+    pub const ORDER: [DiskSorting; 8] = [
+        DiskSorting::Disk,
+        DiskSorting::Mount,
+        DiskSorting::UsedPercent,
+        DiskSorting::InodePercent,
+        DiskSorting::Free,
+        DiskSorting::Total,
+        DiskSorting::ReadPerSecond,
+        DiskSorting::WritePerSecond,
+    ];
+
+    /// Whether sorting by this column starts out descending, which is the case for anything but
+    /// the disk and mount names.
+    pub fn is_descending_by_default(&self) -> bool {
+        !matches!(self, DiskSorting::Disk | DiskSorting::Mount)
+    }
+}
+
+impl std::str::FromStr for DiskSorting {
+    type Err = String;
+
+    /// Parses a column by its header (such as `Used` or `R/s`), ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let column = match s.to_lowercase().as_str() {
+            "disk" => DiskSorting::Disk,
+            "mount" => DiskSorting::Mount,
+            "used" => DiskSorting::UsedPercent,
+            "inode%" => DiskSorting::InodePercent,
+            "free" => DiskSorting::Free,
+            "total" => DiskSorting::Total,
+            "r/s" => DiskSorting::ReadPerSecond,
+            "w/s" => DiskSorting::WritePerSecond,
+            _ => return Err(format!("\"{}\" is not a sortable disk column.", s)),
+        };
+
+        Ok(column)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskHarvest {
+    pub name: String,
+    pub mount_point: String,
+    pub free_space: Option<u64>,
+    pub used_space: Option<u64>,
+    pub total_space: Option<u64>,
+    pub used_inodes: Option<u64>,
+    pub total_inodes: Option<u64>,
+    pub smart: Option<SmartData>,
+    /// Whether this is a tmpfs mount, which is backed by memory rather than a disk.
+    pub is_tmpfs: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IoData {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    /// Timing counters for calculating latency and utilization, where supported.
+    pub times: Option<IoTimes>,
+}
+
+/// Cumulative I/O counters from which a device's average wait time and utilization are calculated.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct IoTimes {
+    /// The number of completed reads and writes.
+    pub completed_ops: u64,
+    /// The total milliseconds spent on completed reads and writes, including time spent queued.
+    pub wait_time_ms: u64,
+    /// The total milliseconds during which the device had I/O in flight.
+    pub busy_time_ms: u64,
+}
+
+pub type IoHarvest = std::collections::HashMap<String, Option<IoData>>;
+
+/// Returns the number of used and total inodes of the filesystem mounted at the given mount point.
+#[cfg(target_family = "unix")]
+fn get_inode_usage(mount_point: &str) -> Option<(u64, u64)> {
+    let path = std::ffi::CString::new(mount_point).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+
+    // SAFETY: `path` is a valid null-terminated string and `stats` is only read if statvfs succeeds.
+    let stats = unsafe {
+        if libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+
+    #[allow(clippy::useless_conversion)]
+    let (total_inodes, free_inodes) = (u64::from(stats.f_files), u64::from(stats.f_ffree));
+
+    // Some filesystems (i.e. btrfs, vfat) don't have a fixed number of inodes, and report zero.
+    if total_inodes == 0 {
+        None
+    } else {
+        Some((total_inodes.saturating_sub(free_inodes), total_inodes))
     }
 }
+
+#[cfg(not(target_family = "unix"))]
+fn get_inode_usage(_mount_point: &str) -> Option<(u64, u64)> {
+    None
+}
+
+/// Returns whether a disk should be kept given its name and mount point.
+fn should_keep_disk(
+    disk_filter: &Option<Filter>, mount_filter: &Option<Filter>, name: &str, mount_point: &str,
+) -> bool {
+    // Precedence ordering in the case where name and mount filters disagree, "allow" takes precedence over "deny".
+    //
+    // For implementation, we do this as follows:
+    // 1. Is the entry allowed through any filter? If so, we always keep this entry.
+    // 2. Is the entry denied through any filter? If so, we always deny this entry.
+    // 3. Anything else is allowed.
+
+    let checks = [(disk_filter, name), (mount_filter, mount_point)]
+        .iter()
+        .filter_map(|(filter, text)| filter.as_ref().and_then(|filter| filter.check(text)))
+        .collect::<Vec<_>>();
+
+    checks.contains(&true) || !checks.contains(&false)
+}
@@ -0,0 +1,292 @@
+//! SMART data collection for disks.
+//!
+//! Drive temperatures are read from sysfs (via the `drivetemp` or `nvme` hwmon drivers) where possible.
+//! Health, reallocated sectors, and wear level are read by parsing the output of `smartctl`, as there
+//! is no way to get them without root and a vendor-specific ioctl otherwise.  Devices are polled
+//! from a thread of their own, so that slow drives do not hold up harvesting, and drives that are
+//! in standby are left asleep.  This is currently only supported on Linux.
+
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::DiskHarvest;
+
+/// SMART is slow to query (and the values rarely change), so we only poll it this often.
+pub const SMART_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SmartData {
+    /// Temperature in degrees Celsius.
+    pub temperature: Option<f32>,
+    pub is_healthy: Option<bool>,
+    pub reallocated_sectors: Option<u64>,
+    /// How much of the drive's rated endurance has been used, as a percentage.
+    pub wear_level: Option<u8>,
+}
+
+/// Caches SMART data per physical device, since it is only polled every [`SMART_POLL_INTERVAL`].
+#[derive(Debug, Default)]
+pub struct SmartCache {
+    /// Sends the devices to poll to the thread that polls them, once it has been started.
+    poll_sender: Option<mpsc::SyncSender<Vec<String>>>,
+    last_poll: Option<Instant>,
+    /// The SMART data of each device, as of the last poll that finished.
+    devices: Arc<Mutex<FxHashMap<String, Option<SmartData>>>>,
+}
+
+impl SmartCache {
+    /// Attaches SMART data to each disk, and polls the underlying devices again if the cache is
+    /// stale.
+    pub fn update_disks(&mut self, disks: &mut [DiskHarvest], current_instant: Instant) {
+        let physical_devices = disks
+            .iter()
+            .map(|disk| get_physical_device(&disk.name))
+            .collect::<Vec<_>>();
+
+        let devices = &self.devices;
+        let poll_sender = self
+            .poll_sender
+            .get_or_insert_with(|| spawn_smart_thread(devices.clone()));
+        if self.last_poll.is_none_or(|last_poll| {
+            current_instant.duration_since(last_poll) >= SMART_POLL_INTERVAL
+        }) {
+            let mut devices_to_poll = physical_devices
+                .iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>();
+            devices_to_poll.sort();
+            devices_to_poll.dedup();
+
+            // This only goes through if the thread is waiting, so that polls of slow drives do
+            // not pile up.
+            if poll_sender.try_send(devices_to_poll).is_ok() {
+                self.last_poll = Some(current_instant);
+            }
+        }
+
+        let devices = match self.devices.lock() {
+            Ok(devices) => devices,
+            Err(_) => return,
+        };
+        for (disk, device) in disks.iter_mut().zip(physical_devices) {
+            if let Some(device) = device {
+                disk.smart = devices.get(&device).cloned().flatten();
+            }
+        }
+    }
+}
+
+/// Starts the thread that polls the devices sent to it, which lives for as long as bottom, and
+/// stores the SMART data of each poll in `devices`.
+fn spawn_smart_thread(
+    devices: Arc<Mutex<FxHashMap<String, Option<SmartData>>>>,
+) -> mpsc::SyncSender<Vec<String>> {
+    let (sender, receiver) = mpsc::sync_channel::<Vec<String>>(0);
+    thread::spawn(move || {
+        for devices_to_poll in receiver {
+            let new_devices = devices_to_poll
+                .into_iter()
+                .map(|device| {
+                    let smart_data = get_smart_data(&device);
+                    (device, smart_data)
+                })
+                .collect();
+            if let Ok(mut devices) = devices.lock() {
+                *devices = new_devices;
+            }
+        }
+    });
+    sender
+}
+
+/// Returns the physical device (i.e. `sda`, `nvme0n1`) that a partition like `/dev/sda1` belongs to.
+#[cfg(target_os = "linux")]
+fn get_physical_device(name: &str) -> Option<String> {
+    let block_name = name.rsplit('/').next()?;
+    let sys_path = std::path::Path::new("/sys/class/block").join(block_name);
+
+    if sys_path.join("partition").exists() {
+        // Partitions are a subdirectory of their parent device in sysfs.
+        let canonical_path = std::fs::canonicalize(&sys_path).ok()?;
+        let parent = canonical_path.parent()?.file_name()?;
+        Some(parent.to_string_lossy().to_string())
+    } else if sys_path.join("device").exists() {
+        Some(block_name.to_string())
+    } else {
+        // Things like device mapper or loop devices have no SMART data.
+        None
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_physical_device(_name: &str) -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn get_smart_data(device: &str) -> Option<SmartData> {
+    // With `-n standby`, drives that are spun down are not woken up, and smartctl exits with 2
+    // instead, which is the same as when the drive could not be opened.
+    let mut smart_data = std::process::Command::new("smartctl")
+        .args(["-n", "standby", "-H", "-A"])
+        .arg(format!("/dev/{}", device))
+        .output()
+        .ok()
+        .filter(|output| output.status.code() != Some(2))
+        .map(|output| parse_smartctl_output(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+
+    // Prefer the hwmon temperature, as it doesn't need elevated permissions.
+    if let Some(temperature) = get_hwmon_temperature(device) {
+        smart_data.temperature = Some(temperature);
+    }
+
+    if smart_data == SmartData::default() {
+        None
+    } else {
+        Some(smart_data)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_smart_data(_device: &str) -> Option<SmartData> {
+    None
+}
+
+/// Reads the drive temperature exposed by the hwmon subsystem.  SATA drives expose it under
+/// `device/hwmon/hwmonX` (with the `drivetemp` module), while NVMe controllers expose it under
+/// `device/hwmonX`.
+#[cfg(target_os = "linux")]
+fn get_hwmon_temperature(device: &str) -> Option<f32> {
+    let device_path = std::path::Path::new("/sys/block")
+        .join(device)
+        .join("device");
+
+    [device_path.join("hwmon"), device_path]
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("hwmon"))
+        .find_map(|entry| {
+            let temp = std::fs::read_to_string(entry.path().join("temp1_input")).ok()?;
+            let millidegrees: f32 = temp.trim().parse().ok()?;
+            Some(millidegrees / 1000.0)
+        })
+}
+
+/// Parses the output of `smartctl -H -A`, which differs a bit between ATA and NVMe drives.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_smartctl_output(output: &str) -> SmartData {
+    let mut smart_data = SmartData::default();
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if let Some(result) = line
+            .strip_prefix("SMART overall-health self-assessment test result:")
+            .or_else(|| line.strip_prefix("SMART Health Status:"))
+        {
+            let result = result.trim();
+            smart_data.is_healthy = Some(result == "PASSED" || result == "OK");
+        } else if let Some(percentage_used) = line.strip_prefix("Percentage Used:") {
+            // NVMe
+            smart_data.wear_level = percentage_used.trim().trim_end_matches('%').parse().ok();
+        } else if let Some(temperature) = line.strip_prefix("Temperature:") {
+            // NVMe
+            smart_data.temperature = temperature
+                .split_whitespace()
+                .next()
+                .and_then(|val| val.parse().ok());
+        } else {
+            // ATA attribute table, which looks like:
+            // ID# ATTRIBUTE_NAME          FLAG     VALUE WORST THRESH TYPE      UPDATED  WHEN_FAILED RAW_VALUE
+            //   5 Reallocated_Sector_Ct   0x0033   100   100   010    Pre-fail  Always       -       0
+            let columns = line.split_whitespace().collect::<Vec<_>>();
+            if columns.len() < 10 {
+                continue;
+            }
+
+            let raw_value = columns[9].parse::<u64>().ok();
+            match columns[0] {
+                "5" => {
+                    smart_data.reallocated_sectors = raw_value;
+                }
+                "190" | "194" if smart_data.temperature.is_none() => {
+                    smart_data.temperature = raw_value.map(|val| val as f32);
+                }
+                // These are normalized to count down from 100 as the drive wears out.
+                "177" | "231" | "233" if smart_data.wear_level.is_none() => {
+                    smart_data.wear_level = columns[3]
+                        .parse::<u8>()
+                        .ok()
+                        .map(|remaining| 100_u8.saturating_sub(remaining));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    smart_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ata_smartctl() {
+        let output = "\
+=== START OF READ SMART DATA SECTION ===
+SMART overall-health self-assessment test result: PASSED
+
+SMART Attributes Data Structure revision number: 1
+Vendor Specific SMART Attributes with Thresholds:
+ID# ATTRIBUTE_NAME          FLAG     VALUE WORST THRESH TYPE      UPDATED  WHEN_FAILED RAW_VALUE
+  5 Reallocated_Sector_Ct   0x0033   100   100   010    Pre-fail  Always       -       8
+177 Wear_Leveling_Count     0x0013   097   097   000    Pre-fail  Always       -       34
+194 Temperature_Celsius     0x0022   064   049   000    Old_age   Always       -       36 (Min/Max 16/51)
+";
+
+        assert_eq!(
+            parse_smartctl_output(output),
+            SmartData {
+                temperature: Some(36.0),
+                is_healthy: Some(true),
+                reallocated_sectors: Some(8),
+                wear_level: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_nvme_smartctl() {
+        let output = "\
+=== START OF SMART DATA SECTION ===
+SMART overall-health self-assessment test result: FAILED!
+
+SMART/Health Information (NVMe Log 0x02)
+Critical Warning:                   0x04
+Temperature:                        41 Celsius
+Available Spare:                    100%
+Percentage Used:                    12%
+";
+
+        assert_eq!(
+            parse_smartctl_output(output),
+            SmartData {
+                temperature: Some(41.0),
+                is_healthy: Some(false),
+                reallocated_sectors: None,
+                wear_level: Some(12),
+            }
+        );
+    }
+}
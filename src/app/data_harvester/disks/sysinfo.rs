@@ -0,0 +1,60 @@
+//! Disk data collection through sysinfo.
+//! Supports FreeBSD.
+
+use sysinfo::{DiskExt, System, SystemExt};
+
+use super::{get_inode_usage, should_keep_disk, DiskHarvest, IoHarvest};
+use crate::app::Filter;
+
+/// sysinfo doesn't expose per-device IO counters, so IO is reported as unavailable.
+pub async fn get_io_usage(actually_get: bool) -> crate::utils::error::Result<Option<IoHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    Ok(Some(IoHarvest::default()))
+}
+
+pub async fn get_disk_usage(
+    sys: &System, actually_get: bool, disk_filter: &Option<Filter>, mount_filter: &Option<Filter>,
+) -> crate::utils::error::Result<Option<Vec<DiskHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let mut vec_disks: Vec<DiskHarvest> = sys
+        .disks()
+        .iter()
+        .filter_map(|disk| {
+            let name = disk.name().to_string_lossy().to_string();
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+
+            if should_keep_disk(disk_filter, mount_filter, &name, &mount_point) {
+                let (used_inodes, total_inodes) = match get_inode_usage(&mount_point) {
+                    Some((used_inodes, total_inodes)) => (Some(used_inodes), Some(total_inodes)),
+                    None => (None, None),
+                };
+                let free_space = disk.available_space();
+                let total_space = disk.total_space();
+
+                Some(DiskHarvest {
+                    free_space: Some(free_space),
+                    used_space: Some(total_space.saturating_sub(free_space)),
+                    total_space: Some(total_space),
+                    used_inodes,
+                    total_inodes,
+                    smart: None,
+                    is_tmpfs: false,
+                    mount_point,
+                    name,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    vec_disks.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Some(vec_disks))
+}
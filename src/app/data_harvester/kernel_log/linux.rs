@@ -0,0 +1,115 @@
+//! Linux-specific functions regarding the kernel log.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{ErrorKind, Read},
+    os::unix::fs::OpenOptionsExt,
+};
+
+use super::KernelLogHarvest;
+
+const KMSG_PATH: &str = "/dev/kmsg";
+
+/// The largest record the kernel will return from a single read.
+const KMSG_RECORD_MAX_LEN: usize = 8192;
+
+/// Keeps `/dev/kmsg` open between harvests, so that each harvest only returns the records that
+/// were logged since the last one.
+#[derive(Debug, Default)]
+pub struct KernelLogTracker {
+    kmsg: Option<File>,
+    /// Whether opening `/dev/kmsg` failed, which usually means it requires root.
+    is_unavailable: bool,
+}
+
+impl KernelLogTracker {
+    pub fn get_kernel_log_data(&mut self, actually_get: bool) -> Option<Vec<KernelLogHarvest>> {
+        if !actually_get || self.is_unavailable {
+            return None;
+        }
+
+        if self.kmsg.is_none() {
+            match OpenOptions::new()
+                .read(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(KMSG_PATH)
+            {
+                Ok(kmsg) => self.kmsg = Some(kmsg),
+                Err(_) => {
+                    self.is_unavailable = true;
+                    return None;
+                }
+            }
+        }
+        let kmsg = self.kmsg.as_mut()?;
+
+        // Each read returns exactly one record, and fails with `WouldBlock` once we've caught up.
+        let mut records = Vec::new();
+        let mut buffer = [0; KMSG_RECORD_MAX_LEN];
+        loop {
+            match kmsg.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => {
+                    if let Some(record) =
+                        parse_kmsg_record(&String::from_utf8_lossy(&buffer[..len]))
+                    {
+                        records.push(record);
+                    }
+                }
+                // Records were overwritten before we could read them, so just carry on.
+                Err(err) if err.raw_os_error() == Some(libc::EPIPE) => continue,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+
+        Some(records)
+    }
+}
+
+/// Parses a record from `/dev/kmsg`, which looks like `6,339,5140900,-;NET: Registered protocol`.
+/// Any continuation lines after the message (which hold device metadata) are ignored.
+fn parse_kmsg_record(record: &str) -> Option<KernelLogHarvest> {
+    let (prefix, message) = record.split_once(';')?;
+    let mut fields = prefix.split(',');
+    let priority = fields.next()?.parse::<u32>().ok()?;
+    let _sequence = fields.next()?;
+    let timestamp_usec = fields.next()?.parse::<u64>().ok()?;
+
+    Some(KernelLogHarvest {
+        level: (priority & 7) as u8,
+        timestamp_usec,
+        message: message.lines().next().unwrap_or_default().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kmsg_record() {
+        assert_eq!(
+            parse_kmsg_record("6,339,5140900,-;NET: Registered protocol family 10\n"),
+            Some(KernelLogHarvest {
+                level: 6,
+                timestamp_usec: 5140900,
+                message: "NET: Registered protocol family 10".to_string(),
+            })
+        );
+
+        // Userspace messages have a facility, and device messages have continuation lines.
+        assert_eq!(
+            parse_kmsg_record(
+                "11,1002,91663403,-;usb 1-1: device descriptor read/64, error -71\n SUBSYSTEM=usb\n DEVICE=c189:1\n"
+            ),
+            Some(KernelLogHarvest {
+                level: 3,
+                timestamp_usec: 91663403,
+                message: "usb 1-1: device descriptor read/64, error -71".to_string(),
+            })
+        );
+
+        assert_eq!(parse_kmsg_record("not a record"), None);
+    }
+}
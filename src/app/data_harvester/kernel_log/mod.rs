@@ -0,0 +1,47 @@
+//! Data collection for the kernel log.
+//!
+//! For Linux, this is handled by reading the kernel ring buffer from `/dev/kmsg`.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct KernelLogTracker;
+
+        impl KernelLogTracker {
+            pub fn get_kernel_log_data(&mut self, _actually_get: bool) -> Option<Vec<KernelLogHarvest>> {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KernelLogHarvest {
+    /// The syslog severity of the message, from 0 (emergency) to 7 (debug).
+    pub level: u8,
+    /// When the message was logged, as microseconds since boot.
+    pub timestamp_usec: u64,
+    pub message: String,
+}
+
+impl KernelLogHarvest {
+    /// Returns the name of the severity, as `dmesg` would display it.
+    pub fn level_name(&self) -> &'static str {
+        match self.level {
+            0 => "emerg",
+            1 => "alert",
+            2 => "crit",
+            3 => "err",
+            4 => "warn",
+            5 => "notice",
+            6 => "info",
+            _ => "debug",
+        }
+    }
+}
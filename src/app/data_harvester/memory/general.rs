@@ -1,11 +1,6 @@
 //! Data collection for memory via heim.
 
-#[derive(Debug, Clone, Default)]
-pub struct MemHarvest {
-    pub mem_total_in_kib: u64,
-    pub mem_used_in_kib: u64,
-    pub use_percent: Option<f64>,
-}
+use super::MemHarvest;
 
 pub async fn get_mem_data(
     actually_get: bool,
@@ -23,7 +18,7 @@ pub async fn get_mem_data(
 }
 
 pub async fn get_ram_data() -> crate::utils::error::Result<Option<MemHarvest>> {
-    let (mem_total_in_kib, mem_used_in_kib) = {
+    let (mem_total_in_kib, mem_used_in_kib, cache_in_kib) = {
         #[cfg(target_os = "linux")]
         {
             use smol::fs::read_to_string;
@@ -89,7 +84,7 @@ pub async fn get_ram_data() -> crate::utils::error::Result<Option<MemHarvest>> {
                 total - mem_free
             };
 
-            (total, used)
+            (total, used, Some(cached_mem + buffers))
         }
         #[cfg(target_os = "macos")]
         {
@@ -100,6 +95,7 @@ pub async fn get_ram_data() -> crate::utils::error::Result<Option<MemHarvest>> {
             (
                 memory.total().get::<kibibyte>(),
                 memory.active().get::<kibibyte>() + memory.wire().get::<kibibyte>(),
+                None,
             )
         }
         #[cfg(target_os = "windows")]
@@ -111,6 +107,7 @@ pub async fn get_ram_data() -> crate::utils::error::Result<Option<MemHarvest>> {
             (
                 mem_total_in_kib,
                 mem_total_in_kib - memory.available().get::<kibibyte>(),
+                None,
             )
         }
     };
@@ -123,6 +120,7 @@ pub async fn get_ram_data() -> crate::utils::error::Result<Option<MemHarvest>> {
         } else {
             Some(mem_used_in_kib as f64 / mem_total_in_kib as f64 * 100.0)
         },
+        cache_in_kib,
     }))
 }
 
@@ -157,5 +155,6 @@ pub async fn get_swap_data() -> crate::utils::error::Result<Option<MemHarvest>>
         } else {
             Some(mem_used_in_kib as f64 / mem_total_in_kib as f64 * 100.0)
         },
+        cache_in_kib: None,
     }))
 }
@@ -0,0 +1,61 @@
+//! Huge page statistics for Linux, from `/proc/meminfo`.
+
+use super::HugePagesHarvest;
+
+pub fn get_huge_page_data(actually_get: bool) -> Option<HugePagesHarvest> {
+    if !actually_get {
+        return None;
+    }
+
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    Some(parse_huge_pages(&meminfo))
+}
+
+fn parse_huge_pages(meminfo: &str) -> HugePagesHarvest {
+    let mut harvest = HugePagesHarvest::default();
+
+    for line in meminfo.lines() {
+        if let Some((label, value)) = line.split_once(':') {
+            let to_write = match label {
+                "HugePages_Total" => &mut harvest.total,
+                "HugePages_Free" => &mut harvest.free,
+                "HugePages_Rsvd" => &mut harvest.reserved,
+                "HugePages_Surp" => &mut harvest.surplus,
+                "Hugepagesize" => &mut harvest.page_size_in_kib,
+                _ => {
+                    continue;
+                }
+            };
+
+            // Page counts have no unit, while the page size is in KiB.
+            if let Some(Ok(number)) = value.split_whitespace().next().map(str::parse::<u64>) {
+                *to_write = number;
+            }
+        }
+    }
+
+    harvest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_huge_pages() {
+        let meminfo = "MemTotal:       16318612 kB\n\
+                       HugePages_Total:      64\n\
+                       HugePages_Free:       48\n\
+                       HugePages_Rsvd:        4\n\
+                       HugePages_Surp:        0\n\
+                       Hugepagesize:       2048 kB\n";
+
+        let harvest = parse_huge_pages(meminfo);
+        assert_eq!(harvest.total, 64);
+        assert_eq!(harvest.free, 48);
+        assert_eq!(harvest.reserved, 4);
+        assert_eq!(harvest.surplus, 0);
+        assert_eq!(harvest.page_size_in_kib, 2048);
+        assert_eq!(harvest.used(), 16);
+    }
+}
@@ -1,10 +1,61 @@
 //! Data collection for memory.
 //!
 //! For Linux, macOS, and Windows, this is handled by Heim.
+//! For FreeBSD, this is handled by sysinfo.
+//! Huge page and shared memory statistics are only collected on Linux.
+
+use serde::{Deserialize, Serialize};
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
         pub mod general;
         pub use self::general::*;
+    } else if #[cfg(target_os = "freebsd")] {
+        pub mod sysinfo;
+        pub use self::sysinfo::*;
     }
 }
+
+#[cfg(target_os = "linux")]
+pub mod huge_pages;
+#[cfg(target_os = "linux")]
+pub use self::huge_pages::*;
+#[cfg(target_os = "linux")]
+pub mod shared;
+#[cfg(target_os = "linux")]
+pub use self::shared::*;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemHarvest {
+    pub mem_total_in_kib: u64,
+    pub mem_used_in_kib: u64,
+    pub use_percent: Option<f64>,
+    /// The page cache and buffers, which are not counted as used.  Only collected for RAM on Linux.
+    pub cache_in_kib: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HugePagesHarvest {
+    pub total: u64,
+    pub free: u64,
+    /// Pages that have been committed to a mapping, but not yet faulted in.
+    pub reserved: u64,
+    /// Pages allocated past `total` through overcommitting.
+    pub surplus: u64,
+    pub page_size_in_kib: u64,
+}
+
+impl HugePagesHarvest {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.free)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SharedMemHarvest {
+    /// All shared memory, including tmpfs files, System V shared memory, and shared anonymous
+    /// mappings.
+    pub shmem_in_kib: u64,
+    /// The part of the shared memory taken up by files in tmpfs filesystems.
+    pub tmpfs_used_in_kib: u64,
+}
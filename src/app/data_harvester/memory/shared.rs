@@ -0,0 +1,93 @@
+//! Shared memory statistics for Linux, from `/proc/meminfo` and the tmpfs filesystems in
+//! `/proc/mounts`.
+
+use super::SharedMemHarvest;
+
+pub fn get_shared_memory_data(actually_get: bool) -> Option<SharedMemHarvest> {
+    if !actually_get {
+        return None;
+    }
+
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+
+    Some(SharedMemHarvest {
+        shmem_in_kib: parse_shmem(&meminfo)?,
+        tmpfs_used_in_kib: parse_tmpfs_mount_points(&mounts)
+            .into_iter()
+            .filter_map(get_used_kib)
+            .sum(),
+    })
+}
+
+fn parse_shmem(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let value = line.strip_prefix("Shmem:")?;
+        value.split_whitespace().next()?.parse().ok()
+    })
+}
+
+/// Returns the mount points of all tmpfs filesystems, skipping any that are mounted more than once.
+fn parse_tmpfs_mount_points(mounts: &str) -> Vec<&str> {
+    let mut mount_points = Vec::new();
+
+    for line in mounts.lines() {
+        let mut fields = line.split_whitespace();
+        if let (Some(_device), Some(mount_point), Some("tmpfs")) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            if !mount_points.contains(&mount_point) {
+                mount_points.push(mount_point);
+            }
+        }
+    }
+
+    mount_points
+}
+
+/// Returns the space used by the filesystem mounted at the given mount point, in KiB.
+fn get_used_kib(mount_point: &str) -> Option<u64> {
+    let path = std::ffi::CString::new(mount_point).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+
+    // SAFETY: `path` is a valid null-terminated string and `stats` is only read if statvfs succeeds.
+    let stats = unsafe {
+        if libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+
+    #[allow(clippy::useless_conversion)]
+    let used_blocks = u64::from(stats.f_blocks).saturating_sub(u64::from(stats.f_bfree));
+    #[allow(clippy::useless_conversion)]
+    let block_size = u64::from(stats.f_frsize);
+
+    Some(used_blocks * block_size / 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shmem() {
+        let meminfo = "MemTotal:       16318612 kB\n\
+                       Shmem:            812345 kB\n\
+                       ShmemHugePages:        0 kB\n";
+
+        assert_eq!(parse_shmem(meminfo), Some(812345));
+        assert_eq!(parse_shmem("MemTotal:       16318612 kB\n"), None);
+    }
+
+    #[test]
+    fn test_parse_tmpfs_mount_points() {
+        let mounts = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+                      tmpfs /run tmpfs rw,nosuid,nodev,size=1632k,mode=755 0 0\n\
+                      shm /dev/shm tmpfs rw,nosuid,nodev 0 0\n\
+                      devtmpfs /dev devtmpfs rw,nosuid 0 0\n\
+                      tmpfs /run tmpfs rw,nosuid,nodev 0 0\n";
+
+        assert_eq!(parse_tmpfs_mount_points(mounts), vec!["/run", "/dev/shm"]);
+    }
+}
@@ -0,0 +1,40 @@
+//! Data collection for memory via sysinfo.
+
+use sysinfo::{System, SystemExt};
+
+use super::MemHarvest;
+
+pub async fn get_mem_data(
+    sys: &System, actually_get: bool,
+) -> (
+    crate::utils::error::Result<Option<MemHarvest>>,
+    crate::utils::error::Result<Option<MemHarvest>>,
+) {
+    if !actually_get {
+        (Ok(None), Ok(None))
+    } else {
+        (get_ram_data(sys), get_swap_data(sys))
+    }
+}
+
+pub fn get_ram_data(sys: &System) -> crate::utils::error::Result<Option<MemHarvest>> {
+    Ok(Some(to_mem_harvest(sys.total_memory(), sys.used_memory())))
+}
+
+pub fn get_swap_data(sys: &System) -> crate::utils::error::Result<Option<MemHarvest>> {
+    Ok(Some(to_mem_harvest(sys.total_swap(), sys.used_swap())))
+}
+
+/// Note that sysinfo reports memory in KiB.
+fn to_mem_harvest(mem_total_in_kib: u64, mem_used_in_kib: u64) -> MemHarvest {
+    MemHarvest {
+        mem_total_in_kib,
+        mem_used_in_kib,
+        use_percent: if mem_total_in_kib == 0 {
+            None
+        } else {
+            Some(mem_used_in_kib as f64 / mem_total_in_kib as f64 * 100.0)
+        },
+        cache_in_kib: None,
+    }
+}
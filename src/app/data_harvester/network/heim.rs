@@ -1,12 +1,16 @@
 //! Gets network data via heim.
 
-use super::NetworkHarvest;
+use super::{
+    get_interface_harvests, InterfaceTotals, NetworkErrorCounts, NetworkHarvest,
+    PrevInterfaceTotals,
+};
 use std::time::Instant;
 
-// FIXME: Eventually make it so that this thing also takes individual usage into account, so we can allow for showing per-interface!
+#[allow(clippy::too_many_arguments)]
 pub async fn get_network_data(
     prev_net_access_time: Instant, prev_net_rx: &mut u64, prev_net_tx: &mut u64,
-    curr_time: Instant, actually_get: bool, filter: &Option<crate::app::Filter>,
+    prev_interface_totals: &mut PrevInterfaceTotals, curr_time: Instant, actually_get: bool,
+    filter: &Option<crate::app::Filter>, interface_filter: &Option<crate::app::Filter>,
 ) -> crate::utils::error::Result<Option<NetworkHarvest>> {
     use futures::StreamExt;
 
@@ -18,22 +22,13 @@ pub async fn get_network_data(
     futures::pin_mut!(io_data);
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut total_errors = NetworkErrorCounts::default();
+    let mut interface_totals = Vec::new();
 
     while let Some(io) = io_data.next().await {
         if let Ok(io) = io {
             let to_keep = if let Some(filter) = filter {
-                if filter.is_list_ignored {
-                    let mut ret = true;
-                    for r in &filter.list {
-                        if r.is_match(io.interface()) {
-                            ret = false;
-                            break;
-                        }
-                    }
-                    ret
-                } else {
-                    true
-                }
+                filter.is_shown(io.interface())
             } else {
                 true
             };
@@ -43,8 +38,36 @@ pub async fn get_network_data(
                 // Since you might have to do a double conversion (bytes -> bits -> bytes) in some cases;
                 // but if you stick to bytes, then in the bytes, case, you do no conversion, and in the bits case,
                 // you only do one conversion...
-                total_rx += io.bytes_recv().get::<heim::units::information::bit>();
-                total_tx += io.bytes_sent().get::<heim::units::information::bit>();
+                let interface_rx = io.bytes_recv().get::<heim::units::information::bit>();
+                let interface_tx = io.bytes_sent().get::<heim::units::information::bit>();
+
+                #[cfg(target_os = "linux")]
+                let (tx_drops, collisions) = (
+                    super::read_sysfs_counter(io.interface(), "tx_dropped"),
+                    super::read_sysfs_counter(io.interface(), "collisions"),
+                );
+                #[cfg(not(target_os = "linux"))]
+                let (tx_drops, collisions) = (0, 0);
+
+                let errors = NetworkErrorCounts {
+                    rx_errors: io.errors_recv(),
+                    tx_errors: io.errors_sent(),
+                    rx_drops: io.drop_recv(),
+                    tx_drops,
+                    collisions,
+                };
+
+                total_rx += interface_rx;
+                total_tx += interface_tx;
+                total_errors += errors;
+                interface_totals.push((
+                    io.interface().to_string(),
+                    InterfaceTotals {
+                        rx: interface_rx,
+                        tx: interface_tx,
+                        errors,
+                    },
+                ));
             }
         }
     }
@@ -60,6 +83,13 @@ pub async fn get_network_data(
         )
     };
 
+    let (interfaces, errors_increased) = get_interface_harvests(
+        interface_totals,
+        prev_interface_totals,
+        elapsed_time,
+        interface_filter,
+    );
+
     *prev_net_rx = total_rx;
     *prev_net_tx = total_tx;
     Ok(Some(NetworkHarvest {
@@ -67,5 +97,10 @@ pub async fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        interfaces,
+        errors: total_errors,
+        errors_increased,
+        wireless: Vec::new(),
+        tcp_counts: None,
     }))
 }
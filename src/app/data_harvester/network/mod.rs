@@ -1,30 +1,185 @@
 //! Data collection for network usage/IO.
 //!
 //! For Linux and macOS, this is handled by Heim.
-//! For Windows, this is handled by sysinfo.
+//! For Windows and FreeBSD, this is handled by sysinfo.
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos"))] {
         pub mod heim;
         pub use self::heim::*;
-    } else if #[cfg(target_os = "windows")] {
+    } else if #[cfg(any(target_os = "windows", target_os = "freebsd"))] {
         pub mod sysinfo;
         pub use self::sysinfo::*;
     }
 }
 
-#[derive(Default, Clone, Debug)]
+pub mod wireless;
+pub use wireless::{WirelessCache, WirelessHarvest};
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{data_harvester::connections::TcpStateCounts, Filter};
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 /// All units in bits.
 pub struct NetworkHarvest {
     pub rx: u64,
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    pub interfaces: Vec<InterfaceHarvest>,
+    /// Error counters summed over all non-filtered interfaces.
+    pub errors: NetworkErrorCounts,
+    /// Whether any error counter increased since the last harvest.
+    pub errors_increased: bool,
+    pub wireless: Vec<WirelessHarvest>,
+    /// Counts of TCP sockets by state, which are only collected on Linux, and only if requested.
+    pub tcp_counts: Option<TcpStateCounts>,
 }
 
 impl NetworkHarvest {
     pub fn first_run_cleanup(&mut self) {
         self.rx = 0;
         self.tx = 0;
+        self.errors_increased = false;
+        self.interfaces.iter_mut().for_each(|interface| {
+            interface.rx = 0;
+            interface.tx = 0;
+            interface.errors_increased = false;
+        });
+    }
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+/// Network usage for a single interface.  All units in bits.
+pub struct InterfaceHarvest {
+    pub name: String,
+    pub rx: u64,
+    pub tx: u64,
+    pub total_rx: u64,
+    pub total_tx: u64,
+    pub errors: NetworkErrorCounts,
+    pub errors_increased: bool,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Cumulative error, drop, and collision counters, in packets.  Counters that are not
+/// supported on a platform are left at zero.
+pub struct NetworkErrorCounts {
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_drops: u64,
+    pub tx_drops: u64,
+    pub collisions: u64,
+}
+
+impl NetworkErrorCounts {
+    pub fn total(&self) -> u64 {
+        self.rx_errors + self.tx_errors + self.rx_drops + self.tx_drops + self.collisions
+    }
+
+    /// Returns whether any counter went up compared to `prev`.  A counter going down (i.e. the
+    /// interface was reset) is not considered an increase.
+    pub fn is_increased_from(&self, prev: &NetworkErrorCounts) -> bool {
+        self.rx_errors > prev.rx_errors
+            || self.tx_errors > prev.tx_errors
+            || self.rx_drops > prev.rx_drops
+            || self.tx_drops > prev.tx_drops
+            || self.collisions > prev.collisions
+    }
+}
+
+impl std::ops::AddAssign for NetworkErrorCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.rx_errors += rhs.rx_errors;
+        self.tx_errors += rhs.tx_errors;
+        self.rx_drops += rhs.rx_drops;
+        self.tx_drops += rhs.tx_drops;
+        self.collisions += rhs.collisions;
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug)]
+/// The cumulative counters of an interface.  RX/TX are in bits.
+pub struct InterfaceTotals {
+    pub rx: u64,
+    pub tx: u64,
+    pub errors: NetworkErrorCounts,
+}
+
+/// Maps an interface name to its previously seen totals.
+pub type PrevInterfaceTotals = HashMap<String, InterfaceTotals>;
+
+/// Returns whether an interface should be broken out in the per-interface view.  Unlike
+/// `net_filter`, this does not affect the totals.
+fn is_interface_shown(interface_filter: &Option<Filter>, name: &str) -> bool {
+    if let Some(filter) = interface_filter {
+        filter.is_shown(name)
+    } else {
+        true
     }
 }
+
+/// Builds the per-interface harvest given the current totals for each interface, updating
+/// `prev_interface_totals` so that it only contains the interfaces that were just seen.
+/// Also returns whether the error counters of any interface increased.
+fn get_interface_harvests(
+    current_totals: Vec<(String, InterfaceTotals)>,
+    prev_interface_totals: &mut PrevInterfaceTotals, elapsed_time: f64,
+    interface_filter: &Option<Filter>,
+) -> (Vec<InterfaceHarvest>, bool) {
+    let mut new_interface_totals = PrevInterfaceTotals::with_capacity(current_totals.len());
+    let mut any_errors_increased = false;
+
+    let mut interfaces = current_totals
+        .into_iter()
+        .filter_map(|(name, totals)| {
+            let prev_totals = prev_interface_totals.get(&name);
+            let (rx, tx) = match prev_totals {
+                Some(prev) if elapsed_time > 0.0 => (
+                    ((totals.rx.saturating_sub(prev.rx)) as f64 / elapsed_time) as u64,
+                    ((totals.tx.saturating_sub(prev.tx)) as f64 / elapsed_time) as u64,
+                ),
+                _ => (0, 0),
+            };
+            let errors_increased = prev_totals
+                .map(|prev| totals.errors.is_increased_from(&prev.errors))
+                .unwrap_or(false);
+            any_errors_increased |= errors_increased;
+            new_interface_totals.insert(name.clone(), totals);
+
+            if is_interface_shown(interface_filter, &name) {
+                Some(InterfaceHarvest {
+                    name,
+                    rx,
+                    tx,
+                    total_rx: totals.rx,
+                    total_tx: totals.tx,
+                    errors: totals.errors,
+                    errors_increased,
+                })
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    *prev_interface_totals = new_interface_totals;
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+    (interfaces, any_errors_increased)
+}
+
+/// Reads a counter from `/sys/class/net/<interface>/statistics`, for counters that heim doesn't expose.
+#[cfg(target_os = "linux")]
+fn read_sysfs_counter(interface: &str, counter: &str) -> u64 {
+    std::fs::read_to_string(format!(
+        "/sys/class/net/{}/statistics/{}",
+        interface, counter
+    ))
+    .ok()
+    .and_then(|val| val.trim().parse().ok())
+    .unwrap_or(0)
+}
@@ -1,12 +1,17 @@
 //! Gets network data via sysinfo.
 
-use super::NetworkHarvest;
+use super::{
+    get_interface_harvests, InterfaceTotals, NetworkErrorCounts, NetworkHarvest,
+    PrevInterfaceTotals,
+};
 use std::time::Instant;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn get_network_data(
     sys: &sysinfo::System, prev_net_access_time: Instant, prev_net_rx: &mut u64,
-    prev_net_tx: &mut u64, curr_time: Instant, actually_get: bool,
-    filter: &Option<crate::app::Filter>,
+    prev_net_tx: &mut u64, prev_interface_totals: &mut PrevInterfaceTotals, curr_time: Instant,
+    actually_get: bool, filter: &Option<crate::app::Filter>,
+    interface_filter: &Option<crate::app::Filter>,
 ) -> crate::utils::error::Result<Option<NetworkHarvest>> {
     use sysinfo::{NetworkExt, SystemExt};
 
@@ -16,25 +21,39 @@ pub async fn get_network_data(
 
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut total_errors = NetworkErrorCounts::default();
+    let mut interface_totals = Vec::new();
 
     let networks = sys.networks();
     for (name, network) in networks {
         let to_keep = if let Some(filter) = filter {
-            let mut ret = filter.is_list_ignored;
-            for r in &filter.list {
-                if r.is_match(name) {
-                    ret = !filter.is_list_ignored;
-                    break;
-                }
-            }
-            ret
+            filter.is_shown(name)
         } else {
             true
         };
 
         if to_keep {
-            total_rx += network.total_received() * 8;
-            total_tx += network.total_transmitted() * 8;
+            let interface_rx = network.total_received() * 8;
+            let interface_tx = network.total_transmitted() * 8;
+
+            // sysinfo doesn't expose drops or collisions.
+            let errors = NetworkErrorCounts {
+                rx_errors: network.total_errors_on_received(),
+                tx_errors: network.total_errors_on_transmitted(),
+                ..NetworkErrorCounts::default()
+            };
+
+            total_rx += interface_rx;
+            total_tx += interface_tx;
+            total_errors += errors;
+            interface_totals.push((
+                name.to_string(),
+                InterfaceTotals {
+                    rx: interface_rx,
+                    tx: interface_tx,
+                    errors,
+                },
+            ));
         }
     }
 
@@ -49,6 +68,13 @@ pub async fn get_network_data(
         )
     };
 
+    let (interfaces, errors_increased) = get_interface_harvests(
+        interface_totals,
+        prev_interface_totals,
+        elapsed_time,
+        interface_filter,
+    );
+
     *prev_net_rx = total_rx;
     *prev_net_tx = total_tx;
     Ok(Some(NetworkHarvest {
@@ -56,5 +82,10 @@ pub async fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        interfaces,
+        errors: total_errors,
+        errors_increased,
+        wireless: Vec::new(),
+        tcp_counts: None,
     }))
 }
@@ -0,0 +1,187 @@
+//! Wireless link data collection.
+//!
+//! The signal level is read from `/proc/net/wireless`.  The SSID, bitrate, and frequency are read
+//! by parsing the output of `iw`, as getting them otherwise would require talking nl80211 over
+//! netlink.  This is currently only supported on Linux.
+
+use std::time::{Duration, Instant};
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// Spawning `iw` is relatively expensive (and the link rarely changes), so we only poll it this often.
+pub const IW_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WirelessHarvest {
+    pub interface: String,
+    pub ssid: Option<String>,
+    /// Signal level in dBm.
+    pub signal_dbm: Option<f64>,
+    /// Receive bitrate in Mbit/s.
+    pub bitrate_mbps: Option<f64>,
+    /// Frequency in MHz.
+    pub frequency_mhz: Option<f64>,
+}
+
+/// Caches the `iw` link data per interface, since it is only polled every [`IW_POLL_INTERVAL`].
+#[derive(Debug, Default)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct WirelessCache {
+    last_poll: Option<Instant>,
+    links: FxHashMap<String, WirelessHarvest>,
+}
+
+impl WirelessCache {
+    /// Returns the link data of each wireless interface, re-polling `iw` if the cache is stale.
+    #[cfg(target_os = "linux")]
+    pub fn get_wireless_data(&mut self, current_instant: Instant) -> Vec<WirelessHarvest> {
+        let is_stale = match self.last_poll {
+            Some(last_poll) => current_instant.duration_since(last_poll) >= IW_POLL_INTERVAL,
+            None => true,
+        };
+
+        if is_stale {
+            self.links.clear();
+            self.last_poll = Some(current_instant);
+        }
+
+        let proc_wireless = match std::fs::read_to_string("/proc/net/wireless") {
+            Ok(proc_wireless) => proc_wireless,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut wireless = parse_proc_net_wireless(&proc_wireless)
+            .into_iter()
+            .map(|(interface, signal_dbm)| {
+                let link = self
+                    .links
+                    .entry(interface)
+                    .or_insert_with_key(|interface| get_iw_link(interface));
+
+                WirelessHarvest {
+                    // The signal from /proc is fresher than the cached one from iw.
+                    signal_dbm: signal_dbm.or(link.signal_dbm),
+                    ..link.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        wireless.sort_by(|a, b| a.interface.cmp(&b.interface));
+
+        wireless
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn get_wireless_data(&mut self, _current_instant: Instant) -> Vec<WirelessHarvest> {
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn get_iw_link(interface: &str) -> WirelessHarvest {
+    let mut link = std::process::Command::new("iw")
+        .args(["dev", interface, "link"])
+        .output()
+        .ok()
+        .map(|output| parse_iw_link_output(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+    link.interface = interface.to_string();
+
+    link
+}
+
+/// Parses `/proc/net/wireless`, returning each interface and its signal level in dBm.  This looks like:
+///
+/// ```text
+/// Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+///  face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+/// wlp2s0: 0000   54.  -56.  -256        0      0      0      0    123        0
+/// ```
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_net_wireless(proc_wireless: &str) -> Vec<(String, Option<f64>)> {
+    proc_wireless
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (interface, stats) = line.split_once(':')?;
+            let signal_dbm = stats
+                .split_whitespace()
+                .nth(2)
+                .and_then(|level| level.trim_end_matches('.').parse().ok());
+
+            Some((interface.trim().to_string(), signal_dbm))
+        })
+        .collect()
+}
+
+/// Parses the output of `iw dev <interface> link`.  Note that the interface name is not filled in.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_iw_link_output(output: &str) -> WirelessHarvest {
+    let mut link = WirelessHarvest::default();
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if let Some(ssid) = line.strip_prefix("SSID:") {
+            link.ssid = Some(ssid.trim().to_string());
+        } else if let Some(freq) = line.strip_prefix("freq:") {
+            link.frequency_mhz = freq.trim().parse().ok();
+        } else if let Some(signal) = line.strip_prefix("signal:") {
+            link.signal_dbm = signal
+                .split_whitespace()
+                .next()
+                .and_then(|val| val.parse().ok());
+        } else if let Some(bitrate) = line.strip_prefix("rx bitrate:") {
+            link.bitrate_mbps = bitrate
+                .split_whitespace()
+                .next()
+                .and_then(|val| val.parse().ok());
+        }
+    }
+
+    link
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_wireless() {
+        let proc_wireless = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+wlp2s0: 0000   54.  -56.  -256        0      0      0      0    123        0
+";
+
+        assert_eq!(
+            parse_proc_net_wireless(proc_wireless),
+            vec![("wlp2s0".to_string(), Some(-56.0))]
+        );
+    }
+
+    #[test]
+    fn test_parse_iw_link_output() {
+        let output = "\
+Connected to 00:11:22:33:44:55 (on wlp2s0)
+	SSID: My Network
+	freq: 5180.0
+	RX: 123456 bytes (789 packets)
+	TX: 12345 bytes (67 packets)
+	signal: -56 dBm
+	rx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+	tx bitrate: 650.0 MBit/s VHT-MCS 7 80MHz short GI VHT-NSS 2
+";
+
+        assert_eq!(
+            parse_iw_link_output(output),
+            WirelessHarvest {
+                interface: String::new(),
+                ssid: Some("My Network".to_string()),
+                signal_dbm: Some(-56.0),
+                bitrate_mbps: Some(866.7),
+                frequency_mhz: Some(5180.0),
+            }
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! NUMA node memory collection for Linux.
+
+use std::{fs, path::Path};
+
+use super::NumaNodeHarvest;
+
+const NODE_DIR: &str = "/sys/devices/system/node";
+
+/// Returns the memory usage of each NUMA node, sorted by node number. Systems without NUMA
+/// support in the kernel return `None`.
+pub fn get_numa_data(actually_get: bool) -> Option<Vec<NumaNodeHarvest>> {
+    if !actually_get {
+        return None;
+    }
+
+    let entries = fs::read_dir(NODE_DIR).ok()?;
+    let mut nodes: Vec<NumaNodeHarvest> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let node = file_name.to_str()?.strip_prefix("node")?.parse().ok()?;
+
+            read_node(node, &entry.path())
+        })
+        .collect();
+    nodes.sort_by_key(|node| node.node);
+
+    Some(nodes)
+}
+
+fn read_node(node: usize, path: &Path) -> Option<NumaNodeHarvest> {
+    let meminfo = fs::read_to_string(path.join("meminfo")).ok()?;
+    let cpu_list = fs::read_to_string(path.join("cpulist"))
+        .map(|cpu_list| cpu_list.trim().to_string())
+        .unwrap_or_default();
+
+    let mut harvest = parse_node_meminfo(&meminfo)?;
+    harvest.node = node;
+    harvest.cpu_list = cpu_list;
+
+    Some(harvest)
+}
+
+/// Parses a node's `meminfo` file, where each line looks like `Node 0 MemTotal: 16318612 kB`.
+fn parse_node_meminfo(meminfo: &str) -> Option<NumaNodeHarvest> {
+    let mut mem_total_in_kib = None;
+    let mut mem_free_in_kib = None;
+
+    for line in meminfo.lines() {
+        let mut fields = line.split_whitespace().skip(2);
+        let (key, value) = match (fields.next(), fields.next()) {
+            (Some(key), Some(value)) => (key, value),
+            _ => continue,
+        };
+
+        match key {
+            "MemTotal:" => mem_total_in_kib = value.parse::<u64>().ok(),
+            "MemFree:" => mem_free_in_kib = value.parse::<u64>().ok(),
+            _ => {}
+        }
+    }
+
+    let mem_total_in_kib = mem_total_in_kib?;
+
+    Some(NumaNodeHarvest {
+        mem_total_in_kib,
+        mem_used_in_kib: mem_total_in_kib.saturating_sub(mem_free_in_kib.unwrap_or(0)),
+        ..NumaNodeHarvest::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_node_meminfo() {
+        let meminfo = "Node 1 MemTotal:       16318612 kB\n\
+                       Node 1 MemFree:         1318612 kB\n\
+                       Node 1 MemUsed:        15000000 kB\n\
+                       Node 1 HugePages_Total:     0\n";
+
+        let harvest = parse_node_meminfo(meminfo).unwrap();
+        assert_eq!(harvest.mem_total_in_kib, 16318612);
+        assert_eq!(harvest.mem_used_in_kib, 15000000);
+
+        assert!(parse_node_meminfo("").is_none());
+    }
+}
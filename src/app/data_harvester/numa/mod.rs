@@ -0,0 +1,37 @@
+//! Data collection for NUMA node memory.
+//!
+//! For Linux, this is handled by reading the per-node files in `/sys/devices/system/node`.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        pub fn get_numa_data(_actually_get: bool) -> Option<Vec<NumaNodeHarvest>> {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NumaNodeHarvest {
+    /// The node number, i.e. the `0` in `node0`.
+    pub node: usize,
+    /// The CPUs local to the node, in the kernel's list format like `0-7,16-23`.
+    pub cpu_list: String,
+    pub mem_total_in_kib: u64,
+    pub mem_used_in_kib: u64,
+}
+
+impl NumaNodeHarvest {
+    pub fn use_percent(&self) -> Option<f64> {
+        if self.mem_total_in_kib == 0 {
+            None
+        } else {
+            Some(self.mem_used_in_kib as f64 / self.mem_total_in_kib as f64 * 100.0)
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Data collection for Kubernetes pods running on the local node.
+//!
+//! This is handled by querying the kubelet's `/metrics/resource` endpoint with `curl`, as the
+//! kubelet only serves it over HTTPS.  If bottom is running in a pod, the pod's service account
+//! token is used to authenticate, and the cluster's CA to verify the kubelet.  Queries are made
+//! from a thread of their own, so that a slow kubelet does not hold up harvesting.
+
+use std::{
+    fmt::Write as _,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::gen_util::quote_curl_config;
+
+/// The kubelet that is queried if no other is configured.
+pub const DEFAULT_KUBELET_URL: &str = "https://127.0.0.1:10250";
+
+/// The kubelet only updates its metrics every few seconds, so it is not queried more often.
+const KUBELET_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const SERVICE_ACCOUNT_TOKEN_PATH: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+const SERVICE_ACCOUNT_CA_PATH: &str = "/var/run/secrets/kubernetes.io/serviceaccount/ca.crt";
+
+/// The total CPU time in seconds and the working set in bytes of each pod, keyed by namespace and
+/// name.
+type ResourceMetrics = FxHashMap<(String, String), (Option<f64>, Option<u64>)>;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PodHarvest {
+    pub namespace: String,
+    pub name: String,
+    /// The CPU usage of the pod, as a percentage of one core.
+    pub cpu_usage_percent: Option<f64>,
+    /// The working set of the pod, in bytes.
+    pub mem_usage_bytes: Option<u64>,
+}
+
+struct KubeletQuery {
+    url: String,
+    is_insecure: bool,
+}
+
+/// Queries the kubelet, and tracks the previous CPU time of each pod, as CPU usage is derived from
+/// the change in CPU time between two queries.
+#[derive(Debug, Default)]
+pub struct PodTracker {
+    /// Sends queries to the thread that runs them, once it has been started.
+    query_sender: Option<mpsc::SyncSender<KubeletQuery>>,
+    /// The metrics of the last query that succeeded and when it was made, until they are read.
+    latest_metrics: Arc<Mutex<Option<(ResourceMetrics, Instant)>>>,
+    last_query: Option<Instant>,
+    prev_cpu_usage: FxHashMap<(String, String), (f64, Instant)>,
+}
+
+impl PodTracker {
+    /// Returns the pods of the last query of the kubelet, if it succeeded since the last call, and
+    /// queries it again if it is due.
+    pub fn get_pod_data(
+        &mut self, actually_get: bool, kubelet_url: &str, is_insecure: bool,
+        current_instant: Instant,
+    ) -> Option<Vec<PodHarvest>> {
+        if !actually_get {
+            return None;
+        }
+
+        let latest_metrics = &self.latest_metrics;
+        let query_sender = self
+            .query_sender
+            .get_or_insert_with(|| spawn_kubelet_thread(latest_metrics.clone()));
+        if self.last_query.is_none_or(|last_query| {
+            current_instant.duration_since(last_query) >= KUBELET_POLL_INTERVAL
+        }) {
+            // This only goes through if the thread is waiting, so that a kubelet that is slow to
+            // answer does not have queries pile up for it.
+            let query = KubeletQuery {
+                url: kubelet_url.to_string(),
+                is_insecure,
+            };
+            if query_sender.try_send(query).is_ok() {
+                self.last_query = Some(current_instant);
+            }
+        }
+
+        let (pods, query_instant) = self
+            .latest_metrics
+            .lock()
+            .ok()
+            .and_then(|mut latest_metrics| latest_metrics.take())?;
+
+        let mut new_cpu_usage = FxHashMap::default();
+        let pod_harvest = pods
+            .into_iter()
+            .map(|((namespace, name), (cpu_usage_secs, mem_usage_bytes))| {
+                let key = (namespace, name);
+                let cpu_usage_percent = cpu_usage_secs.and_then(|cpu_usage_secs| {
+                    new_cpu_usage.insert(key.clone(), (cpu_usage_secs, query_instant));
+                    let (prev_cpu_usage_secs, prev_instant) = self.prev_cpu_usage.get(&key)?;
+                    let elapsed = query_instant.duration_since(*prev_instant).as_secs_f64();
+                    if elapsed > 0.0 {
+                        Some((cpu_usage_secs - prev_cpu_usage_secs).max(0.0) / elapsed * 100.0)
+                    } else {
+                        None
+                    }
+                });
+
+                let (namespace, name) = key;
+                PodHarvest {
+                    namespace,
+                    name,
+                    cpu_usage_percent,
+                    mem_usage_bytes,
+                }
+            })
+            .collect();
+        self.prev_cpu_usage = new_cpu_usage;
+
+        Some(pod_harvest)
+    }
+}
+
+/// Starts the thread that runs the queries sent to it, which lives for as long as bottom, and
+/// stores the metrics of each one that succeeds in `latest_metrics`.
+fn spawn_kubelet_thread(
+    latest_metrics: Arc<Mutex<Option<(ResourceMetrics, Instant)>>>,
+) -> mpsc::SyncSender<KubeletQuery> {
+    let (sender, receiver) = mpsc::sync_channel::<KubeletQuery>(0);
+    thread::spawn(move || {
+        for query in receiver {
+            let query_instant = Instant::now();
+            if let Some(metrics) = query_kubelet(&query) {
+                if let Ok(mut latest_metrics) = latest_metrics.lock() {
+                    *latest_metrics = Some((parse_resource_metrics(&metrics), query_instant));
+                }
+            }
+        }
+    });
+    sender
+}
+
+/// Returns the resource metrics served by the kubelet, or `None` if it could not be queried.
+fn query_kubelet(query: &KubeletQuery) -> Option<String> {
+    // The token is passed as a config on stdin, as anything in the arguments can be read by other
+    // users.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--fail", "--max-time", "2", "--config", "-"])
+        .arg(format!(
+            "{}/metrics/resource",
+            query.url.trim_end_matches('/')
+        ))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let token = std::fs::read_to_string(SERVICE_ACCOUNT_TOKEN_PATH).ok();
+        let config = get_curl_config(
+            token.as_deref(),
+            Path::new(SERVICE_ACCOUNT_CA_PATH).exists(),
+            query.is_insecure,
+        );
+        stdin.write_all(config.as_bytes()).ok()?;
+    }
+    let output = child
+        .wait_with_output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns the curl config that authenticates with `token`, if there is one, and verifies the
+/// kubelet with the cluster's CA if `has_cluster_ca`, unless `is_insecure`.
+fn get_curl_config(token: Option<&str>, has_cluster_ca: bool, is_insecure: bool) -> String {
+    let mut config = String::new();
+    if let Some(token) = token {
+        let _ = writeln!(
+            config,
+            "header = {}",
+            quote_curl_config(&format!("Authorization: Bearer {}", token.trim()))
+        );
+    }
+    if is_insecure {
+        config.push_str("insecure\n");
+    } else if has_cluster_ca {
+        let _ = writeln!(
+            config,
+            "cacert = {}",
+            quote_curl_config(SERVICE_ACCOUNT_CA_PATH)
+        );
+    }
+    config
+}
+
+/// Parses the Prometheus text format served by the kubelet's `/metrics/resource` endpoint into a
+/// map of each pod's total CPU time in seconds and its working set in bytes, keyed by namespace and
+/// name.  Lines look like `pod_memory_working_set_bytes{namespace="default",pod="web"} 1.2e+07
+/// 1690000000000`, where the last value is an optional timestamp.
+fn parse_resource_metrics(metrics: &str) -> ResourceMetrics {
+    let mut pods = ResourceMetrics::default();
+
+    for line in metrics.lines() {
+        let (metric, rest) = match line.split_once('{') {
+            Some(split) => split,
+            None => continue,
+        };
+        if metric != "pod_cpu_usage_seconds_total" && metric != "pod_memory_working_set_bytes" {
+            continue;
+        }
+        let (labels, value) = match rest.split_once('}') {
+            Some(split) => split,
+            None => continue,
+        };
+        let value = match value.split_whitespace().next().map(str::parse::<f64>) {
+            Some(Ok(value)) => value,
+            _ => continue,
+        };
+
+        let mut namespace = None;
+        let mut name = None;
+        for label in labels.split(',') {
+            if let Some((key, label_value)) = label.split_once('=') {
+                let label_value = label_value.trim_matches('"').to_string();
+                match key.trim() {
+                    "namespace" => namespace = Some(label_value),
+                    "pod" => name = Some(label_value),
+                    _ => {}
+                }
+            }
+        }
+
+        if let (Some(namespace), Some(name)) = (namespace, name) {
+            let entry = pods.entry((namespace, name)).or_default();
+            if metric == "pod_cpu_usage_seconds_total" {
+                entry.0 = Some(value);
+            } else {
+                entry.1 = Some(value as u64);
+            }
+        }
+    }
+
+    pods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_metrics() {
+        let metrics = r#"# HELP pod_cpu_usage_seconds_total [ALPHA] Cumulative cpu time consumed by the pod in core-seconds
+# TYPE pod_cpu_usage_seconds_total counter
+pod_cpu_usage_seconds_total{namespace="kube-system",pod="coredns-5d78c9869d-abcde"} 12.5 1690000000000
+pod_memory_working_set_bytes{namespace="kube-system",pod="coredns-5d78c9869d-abcde"} 1.2582912e+07 1690000000000
+container_cpu_usage_seconds_total{container="coredns",namespace="kube-system",pod="coredns-5d78c9869d-abcde"} 12.5 1690000000000
+pod_memory_working_set_bytes{namespace="default",pod="web"} 4096
+"#;
+
+        let pods = parse_resource_metrics(metrics);
+        assert_eq!(pods.len(), 2);
+        assert_eq!(
+            pods.get(&(
+                "kube-system".to_string(),
+                "coredns-5d78c9869d-abcde".to_string()
+            )),
+            Some(&(Some(12.5), Some(12582912)))
+        );
+        assert_eq!(
+            pods.get(&("default".to_string(), "web".to_string())),
+            Some(&(None, Some(4096)))
+        );
+    }
+
+    #[test]
+    fn test_curl_config() {
+        assert_eq!(
+            get_curl_config(Some("abc\n"), true, false),
+            "header = \"Authorization: Bearer abc\"\n\
+             cacert = \"/var/run/secrets/kubernetes.io/serviceaccount/ca.crt\"\n"
+        );
+        assert_eq!(get_curl_config(None, true, true), "insecure\n");
+        assert_eq!(get_curl_config(None, false, false), "");
+    }
+}
@@ -0,0 +1,169 @@
+//! Linux-specific functions regarding power consumption.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use fxhash::FxHashMap;
+
+use super::PowerHarvest;
+
+const POWERCAP_PATH: &str = "/sys/class/powercap";
+const HWMON_PATH: &str = "/sys/class/hwmon";
+
+/// An energy counter, in microjoules.
+struct EnergyCounter {
+    name: String,
+    path: PathBuf,
+    /// The value at which the counter wraps around, if known.
+    max_energy_uj: Option<u64>,
+}
+
+/// Tracks the previous reading of each energy counter, as power is derived from the change in
+/// energy between two harvests.
+#[derive(Debug, Default)]
+pub struct PowerTracker {
+    prev_readings: FxHashMap<PathBuf, (u64, Instant)>,
+}
+
+impl PowerTracker {
+    pub fn get_power_data(
+        &mut self, actually_get: bool, current_instant: Instant,
+    ) -> Option<Vec<PowerHarvest>> {
+        if !actually_get {
+            return None;
+        }
+
+        let mut counters = get_powercap_counters();
+        if counters.is_empty() {
+            counters = get_amd_energy_counters();
+        }
+
+        let mut power_harvest = Vec::with_capacity(counters.len());
+        let mut new_readings = FxHashMap::default();
+        for counter in counters {
+            let energy_uj = match read_u64(&counter.path) {
+                Some(energy_uj) => energy_uj,
+                None => continue, // Usually requires root since energy counters can leak secrets.
+            };
+
+            if let Some((prev_energy_uj, prev_instant)) = self.prev_readings.get(&counter.path) {
+                let elapsed = current_instant.duration_since(*prev_instant).as_secs_f64();
+                if elapsed > 0.0 {
+                    let delta_uj = if energy_uj >= *prev_energy_uj {
+                        energy_uj - prev_energy_uj
+                    } else if let Some(max_energy_uj) = counter.max_energy_uj {
+                        max_energy_uj
+                            .saturating_sub(*prev_energy_uj)
+                            .saturating_add(energy_uj)
+                    } else {
+                        0
+                    };
+
+                    power_harvest.push(PowerHarvest {
+                        name: counter.name,
+                        watts: delta_uj as f64 / 1_000_000.0 / elapsed,
+                    });
+                }
+            }
+
+            new_readings.insert(counter.path, (energy_uj, current_instant));
+        }
+        self.prev_readings = new_readings;
+
+        Some(power_harvest)
+    }
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| contents.trim().parse::<u64>().ok())
+}
+
+fn read_name(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+/// Returns the RAPL zones and subzones exposed through powercap, i.e. `intel-rapl:0` and
+/// `intel-rapl:0:0`.  Subzones are prefixed with the name of their parent zone.
+fn get_powercap_counters() -> Vec<EnergyCounter> {
+    let mut counters = match fs::read_dir(POWERCAP_PATH) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let zone = entry.file_name().to_string_lossy().to_string();
+                let zone_id = zone.strip_prefix("intel-rapl:")?;
+                let zone_path = entry.path();
+                let zone_name = read_name(&zone_path.join("name"))?;
+
+                let name = match zone_id.split_once(':') {
+                    Some((parent_id, _)) => {
+                        let parent_path =
+                            Path::new(POWERCAP_PATH).join(format!("intel-rapl:{}", parent_id));
+                        match read_name(&parent_path.join("name")) {
+                            Some(parent_name) => format!("{} {}", parent_name, zone_name),
+                            None => zone_name,
+                        }
+                    }
+                    None => zone_name,
+                };
+
+                Some(EnergyCounter {
+                    name,
+                    max_energy_uj: read_u64(&zone_path.join("max_energy_range_uj")),
+                    path: zone_path.join("energy_uj"),
+                })
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+
+    counters.sort_by(|a, b| a.path.cmp(&b.path));
+    counters
+}
+
+/// Returns the per-socket counters of the `amd_energy` hwmon driver, skipping the per-core ones
+/// to keep the graph readable.
+fn get_amd_energy_counters() -> Vec<EnergyCounter> {
+    let mut counters = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(HWMON_PATH) {
+        for hwmon_path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if read_name(&hwmon_path.join("name")).as_deref() != Some("amd_energy") {
+                continue;
+            }
+
+            if let Ok(files) = fs::read_dir(&hwmon_path) {
+                for file in files.filter_map(|file| file.ok()) {
+                    let file_name = file.file_name().to_string_lossy().to_string();
+                    if let Some(index) = file_name
+                        .strip_prefix("energy")
+                        .and_then(|rest| rest.strip_suffix("_label"))
+                    {
+                        if let Some(socket) = read_name(&file.path())
+                            .as_deref()
+                            .and_then(|label| label.strip_prefix("Esocket"))
+                        {
+                            counters.push(EnergyCounter {
+                                name: format!("socket{}", socket),
+                                path: hwmon_path.join(format!("energy{}_input", index)),
+                                max_energy_uj: None,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    counters.sort_by(|a, b| a.name.cmp(&b.name));
+    counters
+}
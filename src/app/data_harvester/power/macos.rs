@@ -0,0 +1,140 @@
+//! macOS-specific functions regarding power consumption.
+//!
+//! There is no public API for this, so this reads the output of `powermetrics`, which requires
+//! root. As `powermetrics` only outputs a sample per interval, it is left running in the
+//! background and the latest sample is used on each harvest.
+
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use super::PowerHarvest;
+
+/// The interval between `powermetrics` samples, in milliseconds.
+const SAMPLE_INTERVAL_MS: &str = "1000";
+
+#[derive(Debug, Default)]
+pub struct PowerTracker {
+    powermetrics: Option<Child>,
+    latest_sample: Arc<Mutex<Vec<PowerHarvest>>>,
+    has_started: bool,
+}
+
+impl PowerTracker {
+    pub fn get_power_data(
+        &mut self, actually_get: bool, _current_instant: Instant,
+    ) -> Option<Vec<PowerHarvest>> {
+        if !actually_get {
+            return None;
+        }
+
+        if !self.has_started {
+            self.has_started = true;
+            self.powermetrics = spawn_powermetrics(self.latest_sample.clone());
+        }
+
+        self.powermetrics.as_ref()?;
+        self.latest_sample
+            .lock()
+            .ok()
+            .map(|latest_sample| latest_sample.clone())
+    }
+}
+
+impl Drop for PowerTracker {
+    fn drop(&mut self) {
+        if let Some(powermetrics) = &mut self.powermetrics {
+            let _ = powermetrics.kill();
+            let _ = powermetrics.wait();
+        }
+    }
+}
+
+/// Spawns `powermetrics`, updating `latest_sample` as each line is read.
+fn spawn_powermetrics(latest_sample: Arc<Mutex<Vec<PowerHarvest>>>) -> Option<Child> {
+    if unsafe { libc::geteuid() } != 0 {
+        return None;
+    }
+
+    let mut powermetrics = Command::new("powermetrics")
+        .args([
+            "--samplers",
+            "cpu_power,gpu_power",
+            "-i",
+            SAMPLE_INTERVAL_MS,
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let stdout = powermetrics.stdout.take()?;
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+
+            if let Some(harvest) = parse_power_line(&line) {
+                if let Ok(mut latest_sample) = latest_sample.lock() {
+                    match latest_sample
+                        .iter_mut()
+                        .find(|existing| existing.name == harvest.name)
+                    {
+                        Some(existing) => existing.watts = harvest.watts,
+                        None => latest_sample.push(harvest),
+                    }
+                }
+            }
+        }
+    });
+
+    Some(powermetrics)
+}
+
+/// Parses a power line from `powermetrics`, i.e. `CPU Power: 1234 mW`.
+fn parse_power_line(line: &str) -> Option<PowerHarvest> {
+    let (label, value) = line.split_once(':')?;
+    let name = match label.trim() {
+        "CPU Power" => "CPU",
+        "GPU Power" => "GPU",
+        "ANE Power" => "ANE",
+        label if label.starts_with("Combined Power") => "package",
+        _ => return None,
+    };
+
+    let milliwatts = value
+        .trim()
+        .strip_suffix("mW")?
+        .trim()
+        .parse::<f64>()
+        .ok()?;
+    Some(PowerHarvest {
+        name: name.to_string(),
+        watts: milliwatts / 1000.0,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_power_line() {
+        let cpu = parse_power_line("CPU Power: 1234 mW").unwrap();
+        assert_eq!(cpu.name, "CPU");
+        assert!((cpu.watts - 1.234).abs() < f64::EPSILON);
+
+        let package = parse_power_line("Combined Power (CPU + GPU + ANE): 1500 mW").unwrap();
+        assert_eq!(package.name, "package");
+        assert!((package.watts - 1.5).abs() < f64::EPSILON);
+
+        assert!(parse_power_line("GPU HW active residency:  12.34%").is_none());
+        assert!(parse_power_line("E-Cluster HW active frequency: 1020 MHz").is_none());
+    }
+}
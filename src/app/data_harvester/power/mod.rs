@@ -0,0 +1,37 @@
+//! Data collection for power consumption.
+//!
+//! For Linux, this is handled by reading the energy counters exposed through powercap (Intel
+//! RAPL, which newer kernels also use for AMD), or through the `amd_energy` hwmon driver.
+//! For macOS, this is handled by `powermetrics`, which reports the CPU, GPU, and Neural Engine
+//! power of Apple Silicon chips when running as root.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else if #[cfg(target_os = "macos")] {
+        pub mod macos;
+        pub use self::macos::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct PowerTracker;
+
+        impl PowerTracker {
+            pub fn get_power_data(
+                &mut self, _actually_get: bool, _current_instant: std::time::Instant,
+            ) -> Option<Vec<PowerHarvest>> {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerHarvest {
+    /// The name of the power domain, i.e. `package-0`, `package-0 core`, or `GPU`.
+    pub name: String,
+    pub watts: f64,
+}
@@ -1,15 +1,19 @@
 //! Process data collection for Linux.
 
 use std::collections::hash_map::Entry;
+use std::os::unix::fs::MetadataExt;
+use std::time::Duration;
 
 use crate::utils::error::{self, BottomError};
 use crate::Pid;
 
-use super::ProcessHarvest;
+use super::{
+    MemoryMapSummary, OpenFileHarvest, OpenFileKind, ProcessDetails, ProcessHarvest, ThreadHarvest,
+};
 
 use sysinfo::ProcessStatus;
 
-use procfs::process::{Process, Stat};
+use procfs::process::{FDTarget, MMapPath, MemoryMap, Process, Stat};
 
 use fxhash::{FxHashMap, FxHashSet};
 
@@ -17,25 +21,116 @@ use fxhash::{FxHashMap, FxHashSet};
 /// If it's equal or greater, then we instead refer to the command for the name.
 const MAX_STAT_NAME_LEN: usize = 15;
 
+/// The flag in /proc/<PID>/stat marking kernel threads.
+const PF_KTHREAD: u32 = 0x00200000;
+
 #[derive(Debug, Clone)]
 pub struct PrevProcDetails {
     pub total_read_bytes: u64,
     pub total_write_bytes: u64,
     pub cpu_time: u64,
+    pub latency_counters: Option<LatencyCounters>,
     pub process: Process,
 }
 
+/// The total context switches and page faults of a process, used to calculate their rates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatencyCounters {
+    voluntary_switches: u64,
+    involuntary_switches: u64,
+    major_faults: u64,
+    minor_faults: u64,
+}
+
 impl PrevProcDetails {
     fn new(pid: Pid) -> error::Result<Self> {
         Ok(Self {
             total_read_bytes: 0,
             total_write_bytes: 0,
             cpu_time: 0,
+            latency_counters: None,
             process: Process::new(pid)?,
         })
     }
 }
 
+/// Tracks the process whose threads are currently being collected, if any.
+#[derive(Debug, Default)]
+pub struct ThreadTracker {
+    pid: Option<Pid>,
+    prev_cpu_times: FxHashMap<Pid, u64>,
+    threads: Option<Vec<ThreadHarvest>>,
+}
+
+impl ThreadTracker {
+    /// Sets which process to collect the threads of.  Previous thread CPU times are discarded if
+    /// this changes.
+    pub fn set_pid(&mut self, pid: Option<Pid>) {
+        if self.pid != pid {
+            self.pid = pid;
+            self.prev_cpu_times.clear();
+            self.threads = None;
+        }
+    }
+
+    /// Returns the most recently collected threads, if there is a tracked process.
+    pub fn take_threads(&mut self) -> Option<Vec<ThreadHarvest>> {
+        self.threads.take()
+    }
+
+    fn update(&mut self, cpu_usage: f64, cpu_fraction: f64, use_current_cpu_total: bool) {
+        let pid = match self.pid {
+            Some(pid) => pid,
+            None => return,
+        };
+
+        let tasks = match Process::new(pid).and_then(|process| process.tasks()) {
+            Ok(tasks) => tasks,
+            Err(_) => {
+                // The process is gone, so there's nothing to report.
+                self.prev_cpu_times.clear();
+                self.threads = Some(Vec::new());
+                return;
+            }
+        };
+
+        let mut new_cpu_times = FxHashMap::default();
+        let threads = tasks
+            .filter_map(|task| {
+                let task = task.ok()?;
+                let stat = task.stat().ok()?;
+
+                // A thread we haven't seen before has no previous CPU time to diff against, so just
+                // treat it as idle until the next collection.
+                let prev_cpu_time = self
+                    .prev_cpu_times
+                    .get(&task.tid)
+                    .copied()
+                    .unwrap_or(stat.utime + stat.stime);
+                let (cpu_usage_percent, new_cpu_time) = get_linux_cpu_usage(
+                    &stat,
+                    cpu_usage,
+                    cpu_fraction,
+                    prev_cpu_time,
+                    use_current_cpu_total,
+                );
+                new_cpu_times.insert(task.tid, new_cpu_time);
+
+                Some(ThreadHarvest {
+                    tid: task.tid,
+                    name: stat.comm.clone(),
+                    cpu_usage_percent,
+                    thread_state: ProcessStatus::from(stat.state).to_string(),
+                    thread_state_char: stat.state,
+                })
+            })
+            .collect();
+
+        self.prev_cpu_times = new_cpu_times;
+        self.threads = Some(threads);
+    }
+}
+
 fn calculate_idle_values(line: String) -> (f64, f64) {
     /// Converts a `Option<&str>` value to an f64. If it fails to parse or is `None`, then it will return `0_f64`.
     fn str_to_f64(val: Option<&str>) -> f64 {
@@ -116,18 +211,97 @@ fn get_linux_cpu_usage(
     }
 }
 
+/// The memory usage of a process from /proc/<PID>/smaps_rollup, in bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct MemoryBreakdown {
+    pss_bytes: u64,
+    uss_bytes: u64,
+    swap_bytes: u64,
+}
+
+/// Parses /proc/<PID>/smaps_rollup.  The USS is the memory that is only mapped by this process,
+/// so it's the sum of the private pages.
+fn parse_smaps_rollup(smaps_rollup: &str) -> MemoryBreakdown {
+    let mut breakdown = MemoryBreakdown::default();
+    for line in smaps_rollup.lines() {
+        let mut split = line.split_whitespace();
+        if let (Some(key), Some(Ok(value_kb))) = (split.next(), split.next().map(str::parse::<u64>))
+        {
+            let value_bytes = value_kb * 1024;
+            match key {
+                "Pss:" => breakdown.pss_bytes = value_bytes,
+                "Private_Clean:" | "Private_Dirty:" => breakdown.uss_bytes += value_bytes,
+                "Swap:" => breakdown.swap_bytes = value_bytes,
+                _ => {}
+            }
+        }
+    }
+
+    breakdown
+}
+
+/// Reads the LSM label of a process.  SELinux and AppArmor (as the major LSM) both use
+/// attr/current, but AppArmor may also be stacked under attr/apparmor/current.
+fn read_security_label(pid: Pid) -> Option<String> {
+    ["attr/current", "attr/apparmor/current"]
+        .iter()
+        .find_map(|file| std::fs::read_to_string(format!("/proc/{}/{}", pid, file)).ok())
+        .and_then(|contents| parse_security_label(&contents))
+}
+
+/// Parses the contents of an attr file, which may have a trailing newline or NUL.
+fn parse_security_label(contents: &str) -> Option<String> {
+    let label = contents.trim_end_matches(|c: char| c == '\0' || c.is_whitespace());
+    if label.is_empty() {
+        None
+    } else {
+        Some(label.to_string())
+    }
+}
+
+/// Reads which container a process runs in, from the paths of its control groups.
+fn read_container_id(pid: Pid) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .ok()
+        .and_then(|contents| parse_container_id(&contents))
+}
+
+/// Parses the ID of a container out of the contents of a cgroup file, where container runtimes
+/// name a cgroup after it, like `/docker/<id>` or `/system.slice/docker-<id>.scope`.  The ID is
+/// shortened to 12 characters, like `docker ps` does.
+fn parse_container_id(contents: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        path.split('/').find_map(|component| {
+            let id = component.trim_end_matches(".scope").rsplit('-').next()?;
+            if id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit()) {
+                Some(id[..12].to_string())
+            } else {
+                None
+            }
+        })
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn read_proc(
     prev_proc: &PrevProcDetails, stat: &Stat, cpu_usage: f64, cpu_fraction: f64,
     use_current_cpu_total: bool, time_difference_in_secs: u64, mem_total_kb: u64,
-) -> error::Result<(ProcessHarvest, u64)> {
+    collect_open_fds: bool, collect_memory_breakdown: bool, collect_latency: bool,
+    collect_security: bool, collect_io_priority: bool,
+) -> error::Result<(ProcessHarvest, u64, Option<LatencyCounters>)> {
     use std::convert::TryFrom;
 
     let process = &prev_proc.process;
+    let is_kernel_thread = stat.flags & PF_KTHREAD != 0;
 
     let (command, name) = {
         let truncated_name = stat.comm.as_str();
-        if let Ok(cmdline) = process.cmdline() {
+        if is_kernel_thread {
+            // Like htop and ps, show kernel threads with their names in brackets.
+            let bracketed_name = format!("[{}]", truncated_name);
+            (bracketed_name.clone(), bracketed_name)
+        } else if let Ok(cmdline) = process.cmdline() {
             if cmdline.is_empty() {
                 (format!("[{}]", truncated_name), truncated_name.to_string())
             } else {
@@ -199,8 +373,86 @@ fn read_proc(
             (0, 0, 0, 0)
         };
 
+    // Both of these are counted in clock ticks, the start time since boot.
+    let (start_time, cpu_time) = if let Ok(ticks_per_second) = procfs::ticks_per_second() {
+        let ticks_per_second = ticks_per_second as f64;
+        (
+            procfs::boot_time_secs()
+                .ok()
+                .map(|boot_time| boot_time + (stat.starttime as f64 / ticks_per_second) as u64),
+            Some(Duration::from_secs_f64(
+                (stat.utime + stat.stime) as f64 / ticks_per_second,
+            )),
+        )
+    } else {
+        (None, None)
+    };
+
     let uid = Some(process.owner);
 
+    // Like the owner, the group of the process' directory is the effective GID.
+    let gid = std::fs::metadata(format!("/proc/{}", process.pid))
+        .ok()
+        .map(|metadata| metadata.gid());
+
+    // This requires reading the entire fd directory, so it's skipped unless the column is used.
+    // The context switch counts and capabilities are only in the status file, which is skipped
+    // unless needed.
+    let status = if collect_latency || collect_security {
+        process.status().ok()
+    } else {
+        None
+    };
+    let latency_counters = if collect_latency {
+        status.as_ref().map(|status| LatencyCounters {
+            voluntary_switches: status.voluntary_ctxt_switches.unwrap_or(0),
+            involuntary_switches: status.nonvoluntary_ctxt_switches.unwrap_or(0),
+            major_faults: stat.majflt,
+            minor_faults: stat.minflt,
+        })
+    } else {
+        None
+    };
+    let latency_rate = |get_counter: fn(&LatencyCounters) -> u64| {
+        latency_counters.map(|counters| match &prev_proc.latency_counters {
+            Some(prev_counters) if time_difference_in_secs > 0 => {
+                get_counter(&counters).saturating_sub(get_counter(prev_counters))
+                    / time_difference_in_secs
+            }
+            _ => 0,
+        })
+    };
+
+    // Like the FD count, smaps_rollup is fairly expensive to read, and may need extra permissions.
+    let memory_breakdown = if collect_memory_breakdown {
+        std::fs::read_to_string(format!("/proc/{}/smaps_rollup", process.pid))
+            .ok()
+            .map(|smaps_rollup| parse_smaps_rollup(&smaps_rollup))
+    } else {
+        None
+    };
+
+    let (security_label, effective_caps) = if collect_security {
+        (
+            read_security_label(process.pid),
+            status.as_ref().map(|status| status.capeff),
+        )
+    } else {
+        (None, None)
+    };
+
+    let io_priority = if collect_io_priority {
+        crate::app::process_actions::get_process_io_priority(process.pid).ok()
+    } else {
+        None
+    };
+
+    let open_fds = if collect_open_fds {
+        process.fd_count().ok().map(|count| count as u64)
+    } else {
+        None
+    };
+
     Ok((
         ProcessHarvest {
             pid: process.pid,
@@ -208,24 +460,45 @@ fn read_proc(
             cpu_usage_percent,
             mem_usage_percent,
             mem_usage_bytes,
+            pss_bytes: memory_breakdown.map(|breakdown| breakdown.pss_bytes),
+            uss_bytes: memory_breakdown.map(|breakdown| breakdown.uss_bytes),
+            swap_bytes: memory_breakdown.map(|breakdown| breakdown.swap_bytes),
             name,
             command,
             read_bytes_per_sec,
             write_bytes_per_sec,
             total_read_bytes,
             total_write_bytes,
+            voluntary_switches_per_sec: latency_rate(|counters| counters.voluntary_switches),
+            involuntary_switches_per_sec: latency_rate(|counters| counters.involuntary_switches),
+            major_faults_per_sec: latency_rate(|counters| counters.major_faults),
+            minor_faults_per_sec: latency_rate(|counters| counters.minor_faults),
+            security_label,
+            effective_caps,
+            io_priority,
             process_state,
             process_state_char,
+            is_kernel_thread,
+            start_time,
+            cpu_time,
+            open_fds,
+            gpu_usage_percent: None,
+            container: read_container_id(process.pid),
             uid,
+            gid,
         },
         new_process_times,
+        latency_counters,
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_process_data(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
     pid_mapping: &mut FxHashMap<Pid, PrevProcDetails>, use_current_cpu_total: bool,
-    time_difference_in_secs: u64, mem_total_kb: u64,
+    time_difference_in_secs: u64, mem_total_kb: u64, collect_open_fds: bool,
+    collect_memory_breakdown: bool, collect_latency: bool, collect_security: bool,
+    collect_io_priority: bool, thread_tracker: &mut ThreadTracker,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     // TODO: [PROC THREADS] Add threads
 
@@ -260,16 +533,24 @@ pub fn get_process_data(
                                 return None;
                             }
 
-                            if let Ok((process_harvest, new_process_times)) = read_proc(
-                                prev_proc_details,
-                                stat,
-                                cpu_usage,
-                                cpu_fraction,
-                                use_current_cpu_total,
-                                time_difference_in_secs,
-                                mem_total_kb,
-                            ) {
+                            if let Ok((process_harvest, new_process_times, latency_counters)) =
+                                read_proc(
+                                    prev_proc_details,
+                                    stat,
+                                    cpu_usage,
+                                    cpu_fraction,
+                                    use_current_cpu_total,
+                                    time_difference_in_secs,
+                                    mem_total_kb,
+                                    collect_open_fds,
+                                    collect_memory_breakdown,
+                                    collect_latency,
+                                    collect_security,
+                                    collect_io_priority,
+                                )
+                            {
                                 prev_proc_details.cpu_time = new_process_times;
+                                prev_proc_details.latency_counters = latency_counters;
                                 prev_proc_details.total_read_bytes =
                                     process_harvest.total_read_bytes;
                                 prev_proc_details.total_write_bytes =
@@ -290,6 +571,8 @@ pub fn get_process_data(
             pid_mapping.remove(pid);
         });
 
+        thread_tracker.update(cpu_usage, cpu_fraction, use_current_cpu_total);
+
         Ok(process_vector)
     } else {
         Err(BottomError::GenericError(
@@ -298,10 +581,319 @@ pub fn get_process_data(
     }
 }
 
+/// Sums up the memory mappings of a process by kind.
+fn summarize_memory_maps(maps: &[MemoryMap]) -> Vec<MemoryMapSummary> {
+    let mut summaries: Vec<MemoryMapSummary> = Vec::new();
+    let mut files = FxHashSet::default();
+
+    for map in maps {
+        let kind = match &map.pathname {
+            MMapPath::Path(path) => {
+                files.insert(path);
+                "Files"
+            }
+            MMapPath::Heap => "Heap",
+            MMapPath::Stack | MMapPath::TStack(_) => "Stacks",
+            MMapPath::Anonymous => "Anonymous",
+            MMapPath::Vsys(_) => "Shared memory",
+            _ => "Other",
+        };
+        let size_bytes = map.address.1.saturating_sub(map.address.0);
+
+        if let Some(summary) = summaries.iter_mut().find(|summary| summary.kind == kind) {
+            summary.mappings += 1;
+            summary.size_bytes += size_bytes;
+        } else {
+            summaries.push(MemoryMapSummary {
+                kind: kind.to_string(),
+                mappings: 1,
+                size_bytes,
+            });
+        }
+    }
+
+    if let Some(summary) = summaries.iter_mut().find(|summary| summary.kind == "Files") {
+        summary.kind = format!("Files ({} distinct)", files.len());
+    }
+    summaries.sort_by_key(|summary| std::cmp::Reverse(summary.size_bytes));
+
+    summaries
+}
+
+/// Collects the details of a single process, for Linux.
+pub fn get_process_details(pid: Pid) -> error::Result<ProcessDetails> {
+    let process = Process::new(pid)?;
+
+    let environment = process.environ().ok().map(|environ| {
+        let mut environment = environ
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key.to_string_lossy(), value.to_string_lossy()))
+            .collect::<Vec<_>>();
+        environment.sort();
+        environment
+    });
+
+    let threads = process
+        .tasks()
+        .map(|tasks| {
+            tasks
+                .filter_map(|task| {
+                    let task = task.ok()?;
+                    let stat = task.stat().ok()?;
+                    Some((
+                        task.tid,
+                        stat.comm,
+                        ProcessStatus::from(stat.state).to_string(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let cgroups = process.cgroups().ok().map(|cgroups| {
+        cgroups
+            .into_iter()
+            .map(|cgroup| {
+                format!(
+                    "{}:{}:{}",
+                    cgroup.hierarchy,
+                    cgroup.controllers.join(","),
+                    cgroup.pathname
+                )
+            })
+            .collect()
+    });
+
+    Ok(ProcessDetails {
+        pid,
+        name: process.stat.comm.clone(),
+        command_line: process.cmdline().unwrap_or_default(),
+        cwd: process
+            .cwd()
+            .ok()
+            .map(|cwd| cwd.to_string_lossy().to_string()),
+        environment,
+        threads,
+        memory_maps: process.maps().ok().map(|maps| summarize_memory_maps(&maps)),
+        cgroups,
+    })
+}
+
+/// Turns what a file descriptor refers to into its kind and a name, looking up sockets in
+/// `socket_descriptions`.
+fn describe_fd_target(
+    target: FDTarget, socket_descriptions: &FxHashMap<u64, String>,
+) -> (OpenFileKind, String) {
+    match target {
+        FDTarget::Path(path) => (OpenFileKind::File, path.to_string_lossy().to_string()),
+        FDTarget::Socket(inode) => (
+            OpenFileKind::Socket,
+            socket_descriptions
+                .get(&inode)
+                .cloned()
+                .unwrap_or_else(|| format!("socket:[{}]", inode)),
+        ),
+        FDTarget::Pipe(inode) => (OpenFileKind::Pipe, format!("pipe:[{}]", inode)),
+        FDTarget::AnonInode(name) => (OpenFileKind::AnonInode, format!("anon_inode:{}", name)),
+        FDTarget::MemFD(name) => (OpenFileKind::File, format!("memfd:{}", name)),
+        FDTarget::Net(inode) => (OpenFileKind::Other, format!("net:[{}]", inode)),
+        FDTarget::Other(name, inode) => (OpenFileKind::Other, format!("{}:[{}]", name, inode)),
+    }
+}
+
+/// Lists the open files of a single process, for Linux.  Sockets are only looked up if the process
+/// has any, as that requires reading every socket table.
+pub fn get_open_files(pid: Pid) -> error::Result<Vec<OpenFileHarvest>> {
+    let fds = Process::new(pid)?.fd()?;
+
+    let socket_descriptions = if fds
+        .iter()
+        .any(|fd| matches!(fd.target, FDTarget::Socket(_)))
+    {
+        crate::app::data_harvester::connections::get_socket_descriptions()
+    } else {
+        FxHashMap::default()
+    };
+
+    let mut open_files = fds
+        .into_iter()
+        .map(|fd| {
+            let (kind, name) = describe_fd_target(fd.target, &socket_descriptions);
+            OpenFileHarvest {
+                fd: fd.fd,
+                kind,
+                name,
+            }
+        })
+        .collect::<Vec<_>>();
+    open_files.sort_by_key(|open_file| open_file.fd);
+
+    Ok(open_files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_describe_fd_target() {
+        let mut socket_descriptions = FxHashMap::default();
+        socket_descriptions.insert(10, "TCP 127.0.0.1:22 (LISTEN)".to_string());
+
+        assert_eq!(
+            describe_fd_target(FDTarget::Path("/etc/hosts".into()), &socket_descriptions),
+            (OpenFileKind::File, "/etc/hosts".to_string())
+        );
+        assert_eq!(
+            describe_fd_target(FDTarget::Socket(10), &socket_descriptions),
+            (
+                OpenFileKind::Socket,
+                "TCP 127.0.0.1:22 (LISTEN)".to_string()
+            )
+        );
+        assert_eq!(
+            describe_fd_target(FDTarget::Socket(11), &socket_descriptions),
+            (OpenFileKind::Socket, "socket:[11]".to_string())
+        );
+        assert_eq!(
+            describe_fd_target(FDTarget::Pipe(12), &socket_descriptions),
+            (OpenFileKind::Pipe, "pipe:[12]".to_string())
+        );
+        assert_eq!(
+            describe_fd_target(
+                FDTarget::AnonInode("[eventfd]".to_string()),
+                &socket_descriptions
+            ),
+            (OpenFileKind::AnonInode, "anon_inode:[eventfd]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_open_files() {
+        let file = std::fs::File::open("/proc/self/status").unwrap();
+        let open_files = get_open_files(std::process::id() as Pid).unwrap();
+        assert!(open_files
+            .iter()
+            .any(|open_file| open_file.kind == OpenFileKind::File
+                && open_file.name.ends_with("/status")));
+        drop(file);
+    }
+
+    #[test]
+    fn test_summarize_memory_maps() {
+        let map = |start: u64, end: u64, pathname: MMapPath| MemoryMap {
+            address: (start, end),
+            perms: "r--p".to_string(),
+            offset: 0,
+            dev: (0, 0),
+            inode: 0,
+            pathname,
+        };
+        let maps = [
+            map(0x1000, 0x3000, MMapPath::Path("/usr/bin/btm".into())),
+            map(0x3000, 0x4000, MMapPath::Path("/usr/bin/btm".into())),
+            map(0x4000, 0x5000, MMapPath::Path("/usr/lib/libc.so.6".into())),
+            map(0x10000, 0x20000, MMapPath::Heap),
+            map(0x20000, 0x21000, MMapPath::Stack),
+            map(0x21000, 0x22000, MMapPath::TStack(2)),
+            map(0x30000, 0x31000, MMapPath::Vdso),
+        ];
+
+        assert_eq!(
+            summarize_memory_maps(&maps),
+            vec![
+                MemoryMapSummary {
+                    kind: "Heap".to_string(),
+                    mappings: 1,
+                    size_bytes: 0x10000,
+                },
+                MemoryMapSummary {
+                    kind: "Files (2 distinct)".to_string(),
+                    mappings: 3,
+                    size_bytes: 0x4000,
+                },
+                MemoryMapSummary {
+                    kind: "Stacks".to_string(),
+                    mappings: 2,
+                    size_bytes: 0x2000,
+                },
+                MemoryMapSummary {
+                    kind: "Other".to_string(),
+                    mappings: 1,
+                    size_bytes: 0x1000,
+                },
+            ]
+        );
+        assert!(summarize_memory_maps(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_get_process_details() {
+        let pid = std::process::id() as Pid;
+        let details = get_process_details(pid).unwrap();
+        assert_eq!(details.pid, pid);
+        assert!(!details.command_line.is_empty());
+        assert!(details.threads.iter().any(|(tid, _, _)| *tid == pid));
+    }
+
+    #[test]
+    fn test_parse_security_label() {
+        assert_eq!(
+            parse_security_label("system_u:system_r:sshd_t:s0-s0:c0.c1023\0"),
+            Some("system_u:system_r:sshd_t:s0-s0:c0.c1023".to_string())
+        );
+        assert_eq!(
+            parse_security_label("/usr/sbin/cupsd (enforce)\n"),
+            Some("/usr/sbin/cupsd (enforce)".to_string())
+        );
+        assert_eq!(parse_security_label("\0"), None);
+    }
+
+    #[test]
+    fn test_parse_container_id() {
+        let id = "3f2a9c1e5b7d4f6a8c0e2b4d6f8a0c2e4b6d8f0a2c4e6b8d0f2a4c6e8b0d2f4a";
+        assert_eq!(
+            parse_container_id(&format!("0::/system.slice/docker-{}.scope\n", id)),
+            Some("3f2a9c1e5b7d".to_string())
+        );
+        assert_eq!(
+            parse_container_id(&format!(
+                "12:memory:/kubepods/burstable/pod1234/{}\n11:cpu:/kubepods\n",
+                id
+            )),
+            Some("3f2a9c1e5b7d".to_string())
+        );
+        assert_eq!(
+            parse_container_id("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_smaps_rollup() {
+        let smaps_rollup = "\
+55d1c6a1e000-7ffc8a5f9000 ---p 00000000 00:00 0                          [rollup]
+Rss:               12345 kB
+Pss:                8000 kB
+Pss_Anon:           5000 kB
+Shared_Clean:       3000 kB
+Shared_Dirty:        345 kB
+Private_Clean:      1000 kB
+Private_Dirty:      8000 kB
+Swap:                512 kB
+SwapPss:             256 kB
+";
+        assert_eq!(
+            parse_smaps_rollup(smaps_rollup),
+            MemoryBreakdown {
+                pss_bytes: 8000 * 1024,
+                uss_bytes: 9000 * 1024,
+                swap_bytes: 512 * 1024,
+            }
+        );
+        assert_eq!(parse_smaps_rollup(""), MemoryBreakdown::default());
+    }
+
     #[test]
     fn test_proc_cpu_parse() {
         assert_eq!(
@@ -97,14 +97,31 @@ pub fn get_process_data(
                 0.0
             },
             mem_usage_bytes: process_val.memory() * 1024,
+            pss_bytes: None,
+            uss_bytes: None,
+            swap_bytes: None,
             cpu_usage_percent: process_cpu_usage,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
+            voluntary_switches_per_sec: None,
+            involuntary_switches_per_sec: None,
+            major_faults_per_sec: None,
+            minor_faults_per_sec: None,
+            security_label: None,
+            effective_caps: None,
+            io_priority: None,
             process_state: process_val.status().to_string(),
             process_state_char: convert_process_status_to_char(process_val.status()),
+            is_kernel_thread: false,
+            start_time: Some(process_val.start_time()),
+            cpu_time: None,
+            open_fds: None,
+            gpu_usage_percent: None,
+            container: None,
             uid: Some(process_val.uid),
+            gid: Some(process_val.gid),
         });
     }
 
@@ -134,6 +151,7 @@ fn convert_process_status_to_char(status: ProcessStatus) -> char {
         ProcessStatus::Sleep => 'S',
         ProcessStatus::Idle => 'D',
         ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Stop => 'T',
         _ => '?',
     }
 }
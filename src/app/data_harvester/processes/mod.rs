@@ -1,7 +1,7 @@
 //! Data collection for processes.
 //!
 //! For Linux, this is handled by a custom set of functions.
-//! For Windows and macOS, this is handled by sysinfo.
+//! For Windows, macOS, and FreeBSD, this is handled by sysinfo.
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
@@ -13,6 +13,9 @@ cfg_if::cfg_if! {
     } else if #[cfg(target_os = "windows")] {
         pub mod windows;
         pub use self::windows::*;
+    } else if #[cfg(target_os = "freebsd")] {
+        pub mod freebsd;
+        pub use self::freebsd::*;
     }
 }
 
@@ -23,12 +26,22 @@ cfg_if::cfg_if! {
     }
 }
 
+cfg_if::cfg_if! {
+    if #[cfg(not(target_os = "linux"))] {
+        pub mod sysinfo_details;
+        pub use self::sysinfo_details::*;
+    }
+}
+
+use serde::{Deserialize, Serialize};
+
 use crate::Pid;
 
 // TODO: Add value so we know if it's sorted ascending or descending by default?
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ProcessSorting {
     CpuPercent,
+    CpuTrend,
     Mem,
     MemPercent,
     Pid,
@@ -40,7 +53,22 @@ pub enum ProcessSorting {
     TotalWrite,
     State,
     User,
+    Group,
     Count,
+    Pss,
+    Uss,
+    Swap,
+    VoluntarySwitches,
+    InvoluntarySwitches,
+    MajorFaults,
+    MinorFaults,
+    SecurityLabel,
+    Capabilities,
+    IoPriority,
+    StartTime,
+    CpuTime,
+    GpuPercent,
+    OpenFds,
 }
 
 impl std::fmt::Display for ProcessSorting {
@@ -50,6 +78,7 @@ impl std::fmt::Display for ProcessSorting {
             "{}",
             match &self {
                 ProcessSorting::CpuPercent => "CPU%",
+                ProcessSorting::CpuTrend => "CPU Trend",
                 ProcessSorting::MemPercent => "Mem%",
                 ProcessSorting::Mem => "Mem",
                 ProcessSorting::ReadPerSecond => "R/s",
@@ -62,24 +91,139 @@ impl std::fmt::Display for ProcessSorting {
                 ProcessSorting::Pid => "PID",
                 ProcessSorting::Count => "Count",
                 ProcessSorting::User => "User",
+                ProcessSorting::Group => "Group",
+                ProcessSorting::Pss => "PSS",
+                ProcessSorting::Uss => "USS",
+                ProcessSorting::Swap => "Swap",
+                ProcessSorting::VoluntarySwitches => "VCtx/s",
+                ProcessSorting::InvoluntarySwitches => "ICtx/s",
+                ProcessSorting::MajorFaults => "MajF/s",
+                ProcessSorting::MinorFaults => "MinF/s",
+                ProcessSorting::SecurityLabel => "Label",
+                ProcessSorting::Capabilities => "CapEff",
+                ProcessSorting::IoPriority => "IOprio",
+                ProcessSorting::StartTime => "Started",
+                ProcessSorting::CpuTime => "Time+",
+                ProcessSorting::GpuPercent => "GPU%",
+                ProcessSorting::OpenFds => "FDs",
             }
         )
     }
 }
 
+impl ProcessSorting {
+    /// Whether sorting by this column starts out descending, which is the case for anything but
+    /// columns sorted alphabetically or by PID.
+    pub fn is_descending_by_default(&self) -> bool {
+        !matches!(
+            self,
+            ProcessSorting::State
+                | ProcessSorting::User
+                | ProcessSorting::Group
+                | ProcessSorting::SecurityLabel
+                | ProcessSorting::Pid
+                | ProcessSorting::ProcessName
+                | ProcessSorting::Command
+        )
+    }
+
+    /// Returns the column shown in place of this one depending on the mode, such as the count in
+    /// place of the PID while grouping.
+    pub fn partner(&self) -> Option<ProcessSorting> {
+        match self {
+            ProcessSorting::Pid => Some(ProcessSorting::Count),
+            ProcessSorting::Count => Some(ProcessSorting::Pid),
+            ProcessSorting::Mem => Some(ProcessSorting::MemPercent),
+            ProcessSorting::MemPercent => Some(ProcessSorting::Mem),
+            ProcessSorting::ProcessName => Some(ProcessSorting::Command),
+            ProcessSorting::Command => Some(ProcessSorting::ProcessName),
+            _ => None,
+        }
+    }
+
+    /// Whether this is an optional column, whose data is only collected if it is enabled.
+    pub fn is_optional(&self) -> bool {
+        matches!(
+            self,
+            ProcessSorting::CpuTrend
+                | ProcessSorting::Pss
+                | ProcessSorting::Uss
+                | ProcessSorting::Swap
+                | ProcessSorting::VoluntarySwitches
+                | ProcessSorting::InvoluntarySwitches
+                | ProcessSorting::MajorFaults
+                | ProcessSorting::MinorFaults
+                | ProcessSorting::SecurityLabel
+                | ProcessSorting::Capabilities
+                | ProcessSorting::IoPriority
+                | ProcessSorting::StartTime
+                | ProcessSorting::CpuTime
+                | ProcessSorting::GpuPercent
+                | ProcessSorting::OpenFds
+        )
+    }
+}
+
+impl std::str::FromStr for ProcessSorting {
+    type Err = String;
+
+    /// Parses a column by its header (such as `CPU%` or `T.Read`), ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let column = match s.to_lowercase().as_str() {
+            "cpu%" | "cpu" => ProcessSorting::CpuPercent,
+            "cpu trend" => ProcessSorting::CpuTrend,
+            "mem%" => ProcessSorting::MemPercent,
+            "mem" => ProcessSorting::Mem,
+            "r/s" => ProcessSorting::ReadPerSecond,
+            "w/s" => ProcessSorting::WritePerSecond,
+            "t.read" => ProcessSorting::TotalRead,
+            "t.write" => ProcessSorting::TotalWrite,
+            "state" => ProcessSorting::State,
+            "name" => ProcessSorting::ProcessName,
+            "command" => ProcessSorting::Command,
+            "pid" => ProcessSorting::Pid,
+            "count" => ProcessSorting::Count,
+            "user" => ProcessSorting::User,
+            "group" => ProcessSorting::Group,
+            "pss" => ProcessSorting::Pss,
+            "uss" => ProcessSorting::Uss,
+            "swap" => ProcessSorting::Swap,
+            "vctx/s" => ProcessSorting::VoluntarySwitches,
+            "ictx/s" => ProcessSorting::InvoluntarySwitches,
+            "majf/s" => ProcessSorting::MajorFaults,
+            "minf/s" => ProcessSorting::MinorFaults,
+            "label" => ProcessSorting::SecurityLabel,
+            "capeff" => ProcessSorting::Capabilities,
+            "ioprio" => ProcessSorting::IoPriority,
+            "started" => ProcessSorting::StartTime,
+            "time+" => ProcessSorting::CpuTime,
+            "gpu%" => ProcessSorting::GpuPercent,
+            "fds" => ProcessSorting::OpenFds,
+            _ => return Err(format!("\"{}\" is not a process column.", s)),
+        };
+
+        Ok(column)
+    }
+}
+
 impl Default for ProcessSorting {
     fn default() -> Self {
         ProcessSorting::CpuPercent
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProcessHarvest {
     pub pid: Pid,
     pub parent_pid: Option<Pid>, // Remember, parent_pid 0 is root...
     pub cpu_usage_percent: f64,
     pub mem_usage_percent: f64,
     pub mem_usage_bytes: u64,
+    /// The proportional set size, unique set size, and swap usage in bytes; only collected on
+    /// Linux, and only if requested.
+    pub pss_bytes: Option<u64>,
+    pub uss_bytes: Option<u64>,
+    pub swap_bytes: Option<u64>,
     // pub rss_kb: u64,
     // pub virt_kb: u64,
     pub name: String,
@@ -88,10 +232,224 @@ pub struct ProcessHarvest {
     pub write_bytes_per_sec: u64,
     pub total_read_bytes: u64,
     pub total_write_bytes: u64,
+    /// The rates of voluntary and involuntary context switches, and of major and minor page
+    /// faults, per second; only collected on Linux, and only if requested.
+    pub voluntary_switches_per_sec: Option<u64>,
+    pub involuntary_switches_per_sec: Option<u64>,
+    pub major_faults_per_sec: Option<u64>,
+    pub minor_faults_per_sec: Option<u64>,
+    /// The SELinux or AppArmor label of the process; only collected on Linux, and only if requested.
+    pub security_label: Option<String>,
+    /// The effective capability set of the process as a bitmask; only collected on Linux, and only
+    /// if requested.
+    pub effective_caps: Option<u64>,
+    /// The I/O scheduling class and priority; only collected on Linux, and only if requested.
+    pub io_priority: Option<IoPriority>,
     pub process_state: String,
     pub process_state_char: char,
+    /// Whether this is a kernel thread; only collected on Linux.
+    pub is_kernel_thread: bool,
+    /// When the process was started, in seconds since the Unix epoch.
+    pub start_time: Option<u64>,
+    /// The total CPU time used by the process; only collected on Linux.
+    pub cpu_time: Option<std::time::Duration>,
+    /// The number of open file descriptors; only collected on Linux, and only if requested.
+    pub open_fds: Option<u64>,
+    /// The GPU engine utilization; only collected on Windows.
+    pub gpu_usage_percent: Option<f64>,
+    /// The shortened ID of the container that the process runs in, if any; only collected on Linux.
+    pub container: Option<String>,
 
     /// This is the *effective* user ID.
     #[cfg(target_family = "unix")]
     pub uid: Option<libc::uid_t>,
+
+    /// This is the *effective* group ID.
+    #[cfg(target_family = "unix")]
+    pub gid: Option<libc::gid_t>,
+}
+
+/// An I/O scheduling class, ordered from the highest to the lowest priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IoPriorityClass {
+    RealTime,
+    BestEffort,
+    /// No class was set, so the kernel picks a best effort level based on the nice value.
+    None,
+    Idle,
+}
+
+impl IoPriorityClass {
+    pub const ALL: [IoPriorityClass; 4] = [
+        IoPriorityClass::RealTime,
+        IoPriorityClass::BestEffort,
+        IoPriorityClass::None,
+        IoPriorityClass::Idle,
+    ];
+
+    /// Whether this class has priority levels; only the real-time and best effort classes do.
+    pub fn has_levels(&self) -> bool {
+        matches!(
+            self,
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort
+        )
+    }
+}
+
+impl std::fmt::Display for IoPriorityClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                IoPriorityClass::RealTime => "realtime",
+                IoPriorityClass::BestEffort => "best-effort",
+                IoPriorityClass::None => "none",
+                IoPriorityClass::Idle => "idle",
+            }
+        )
+    }
+}
+
+/// The I/O scheduling class and priority level of a process, as set with `ionice`.  Levels go from
+/// 0 (the highest priority) to 7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct IoPriority {
+    pub class: IoPriorityClass,
+    pub level: u8,
+}
+
+impl IoPriority {
+    pub const MAX_LEVEL: u8 = 7;
+    const CLASS_SHIFT: u32 = 13;
+
+    /// Converts from the value used by the `ioprio_get` and `ioprio_set` system calls.
+    pub fn from_raw(raw: i32) -> Self {
+        let class = match raw >> Self::CLASS_SHIFT {
+            1 => IoPriorityClass::RealTime,
+            2 => IoPriorityClass::BestEffort,
+            3 => IoPriorityClass::Idle,
+            _ => IoPriorityClass::None,
+        };
+        let level = if class.has_levels() {
+            (raw & ((1 << Self::CLASS_SHIFT) - 1)).clamp(0, Self::MAX_LEVEL.into()) as u8
+        } else {
+            0
+        };
+
+        IoPriority { class, level }
+    }
+
+    /// Converts to the value used by the `ioprio_get` and `ioprio_set` system calls.
+    pub fn to_raw(self) -> i32 {
+        let class = match self.class {
+            IoPriorityClass::None => 0,
+            IoPriorityClass::RealTime => 1,
+            IoPriorityClass::BestEffort => 2,
+            IoPriorityClass::Idle => 3,
+        };
+        let level = if self.class.has_levels() {
+            i32::from(self.level.min(Self::MAX_LEVEL))
+        } else {
+            0
+        };
+
+        (class << Self::CLASS_SHIFT) | level
+    }
+}
+
+impl Default for IoPriority {
+    /// The best effort class at level 4, which is what the kernel uses for a nice value of 0.
+    fn default() -> Self {
+        IoPriority {
+            class: IoPriorityClass::BestEffort,
+            level: 4,
+        }
+    }
+}
+
+impl std::fmt::Display for IoPriority {
+    /// Formats like `iotop`, such as "be/4".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.class {
+            IoPriorityClass::RealTime => write!(f, "rt/{}", self.level),
+            IoPriorityClass::BestEffort => write!(f, "be/{}", self.level),
+            IoPriorityClass::None => write!(f, "none"),
+            IoPriorityClass::Idle => write!(f, "idle"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThreadHarvest {
+    pub tid: Pid,
+    pub name: String,
+    pub cpu_usage_percent: f64,
+    pub thread_state: String,
+    pub thread_state_char: char,
+}
+
+/// How much of the address space of a process is taken up by one kind of memory mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryMapSummary {
+    pub kind: String,
+    pub mappings: usize,
+    pub size_bytes: u64,
+}
+
+/// Details about a single process that are too expensive to collect for every process, and so
+/// are only collected on demand.  Fields that could not be read, such as due to missing
+/// permissions or the platform not supporting them, are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessDetails {
+    pub pid: Pid,
+    pub name: String,
+    /// Each argument of the full command line, including the program.
+    pub command_line: Vec<String>,
+    pub cwd: Option<String>,
+    /// The environment variables as `KEY=VALUE` strings, sorted by key.
+    pub environment: Option<Vec<String>>,
+    /// The TID, name, and state of each thread; only collected on Linux.
+    pub threads: Vec<(Pid, String, String)>,
+    /// The kinds of memory mappings, from the largest to the smallest; only collected on Linux.
+    pub memory_maps: Option<Vec<MemoryMapSummary>>,
+    /// The control groups of the process, formatted like `/proc/<PID>/cgroup`; only collected on
+    /// Linux.
+    pub cgroups: Option<Vec<String>>,
+}
+
+/// What an open file descriptor of a process refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenFileKind {
+    File,
+    Socket,
+    Pipe,
+    /// A file descriptor without an inode, such as an eventfd or epoll instance.
+    AnonInode,
+    Other,
+}
+
+impl std::fmt::Display for OpenFileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OpenFileKind::File => "file",
+                OpenFileKind::Socket => "socket",
+                OpenFileKind::Pipe => "pipe",
+                OpenFileKind::AnonInode => "anon",
+                OpenFileKind::Other => "other",
+            }
+        )
+    }
+}
+
+/// An open file descriptor of a process, like a line of `lsof -p <PID>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenFileHarvest {
+    pub fd: u32,
+    pub kind: OpenFileKind,
+    /// The path of a file, or a description of a socket.
+    pub name: String,
 }
@@ -0,0 +1,41 @@
+//! Process details and open files collection for platforms that use sysinfo.
+
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+
+use super::{OpenFileHarvest, ProcessDetails};
+use crate::utils::error::{self, BottomError};
+use crate::Pid;
+
+/// Collects the details of a single process.  Threads, memory maps, and control groups are not
+/// collected.
+pub fn get_process_details(pid: Pid) -> error::Result<ProcessDetails> {
+    let sysinfo_pid = sysinfo::Pid::from_u32(pid as u32);
+    let mut sys = System::new();
+    sys.refresh_process(sysinfo_pid);
+
+    let process = sys.process(sysinfo_pid).ok_or_else(|| {
+        BottomError::GenericError(format!("The process with PID {} does not exist.", pid))
+    })?;
+
+    let mut environment = process.environ().to_vec();
+    environment.sort();
+    let cwd = process.cwd().to_string_lossy().to_string();
+
+    Ok(ProcessDetails {
+        pid,
+        name: process.name().to_string(),
+        command_line: process.cmd().to_vec(),
+        cwd: if cwd.is_empty() { None } else { Some(cwd) },
+        environment: Some(environment),
+        threads: Vec::new(),
+        memory_maps: None,
+        cgroups: None,
+    })
+}
+
+/// Listing open files is only supported on Linux.
+pub fn get_open_files(_pid: Pid) -> error::Result<Vec<OpenFileHarvest>> {
+    Err(BottomError::GenericError(
+        "Listing open files is currently only supported on Linux.".to_string(),
+    ))
+}
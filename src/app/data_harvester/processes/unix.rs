@@ -5,6 +5,7 @@ use crate::utils::error;
 #[derive(Debug, Default)]
 pub struct UserTable {
     pub uid_user_mapping: std::collections::HashMap<libc::uid_t, String>,
+    pub gid_group_mapping: std::collections::HashMap<libc::gid_t, String>,
 }
 
 impl UserTable {
@@ -27,4 +28,24 @@ impl UserTable {
             Ok(username)
         }
     }
+
+    pub fn get_gid_to_groupname_mapping(&mut self, gid: libc::gid_t) -> error::Result<String> {
+        if let Some(group) = self.gid_group_mapping.get(&gid) {
+            Ok(group.clone())
+        } else {
+            // SAFETY: getgrgid returns a null pointer if no group entry is found for the gid
+            let group = unsafe { libc::getgrgid(gid) };
+
+            if group.is_null() {
+                return Err(error::BottomError::QueryError("Missing group".into()));
+            }
+
+            let groupname = unsafe { std::ffi::CStr::from_ptr((*group).gr_name) }
+                .to_str()?
+                .to_string();
+            self.gid_group_mapping.insert(gid, groupname.clone());
+
+            Ok(groupname)
+        }
+    }
 }
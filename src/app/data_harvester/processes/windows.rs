@@ -1,7 +1,213 @@
 //! Process data collection for Windows.  Uses sysinfo.
 
 use super::ProcessHarvest;
+use crate::Pid;
+use fxhash::FxHashMap;
 use sysinfo::{PidExt, ProcessExt, ProcessorExt, System, SystemExt};
+use winapi::um::pdh::{
+    PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+    PdhOpenQueryW, PDH_FMT_COUNTERVALUE_ITEM_W, PDH_FMT_DOUBLE, PDH_HCOUNTER, PDH_HQUERY,
+};
+
+/// Returned by PDH if the passed buffer was too small.
+const PDH_MORE_DATA: i32 = 0x800007D2_u32 as i32;
+
+const GPU_ENGINE_COUNTER: &str = "\\GPU Engine(*)\\Utilization Percentage";
+const PROCESS_ID_COUNTER: &str = "\\Process(*)\\ID Process";
+const PROCESS_READ_COUNTER: &str = "\\Process(*)\\IO Read Bytes/sec";
+const PROCESS_WRITE_COUNTER: &str = "\\Process(*)\\IO Write Bytes/sec";
+
+/// The per-process values read from the performance counters on a harvest.
+#[derive(Debug, Default)]
+pub struct CounterSample {
+    /// The GPU usage of each process with any, as a percentage, or `None` if there are no "GPU
+    /// Engine" counters, like without a WDDM 2.0 driver.
+    pub gpu_usage: Option<FxHashMap<Pid, f64>>,
+    /// The bytes read and written per second by each process.
+    pub io_usage: FxHashMap<Pid, (u64, u64)>,
+}
+
+/// Tracks per-process GPU usage through the "GPU Engine" performance counters, and I/O rates
+/// through the "Process" ones.
+#[derive(Debug)]
+pub struct CounterTracker {
+    query: PDH_HQUERY,
+    gpu_counter: Option<PDH_HCOUNTER>,
+    pid_counter: Option<PDH_HCOUNTER>,
+    read_counter: Option<PDH_HCOUNTER>,
+    write_counter: Option<PDH_HCOUNTER>,
+}
+
+// The query handles are only ever used from the collection thread.
+unsafe impl Send for CounterTracker {}
+
+impl CounterTracker {
+    pub fn new() -> Option<Self> {
+        let mut query: PDH_HQUERY = std::ptr::null_mut();
+
+        unsafe {
+            if PdhOpenQueryW(std::ptr::null(), 0, &mut query) != 0 {
+                return None;
+            }
+            // Constructed early so the query is closed if adding the counters fails.
+            let mut tracker = CounterTracker {
+                query,
+                gpu_counter: None,
+                pid_counter: None,
+                read_counter: None,
+                write_counter: None,
+            };
+            tracker.gpu_counter = add_counter(query, GPU_ENGINE_COUNTER);
+            tracker.pid_counter = add_counter(query, PROCESS_ID_COUNTER);
+            tracker.read_counter = add_counter(query, PROCESS_READ_COUNTER);
+            tracker.write_counter = add_counter(query, PROCESS_WRITE_COUNTER);
+            if tracker.gpu_counter.is_none() && tracker.pid_counter.is_none() {
+                return None;
+            }
+
+            // Utilization and I/O are rates, so they need an initial sample to compare against.
+            PdhCollectQueryData(query);
+            Some(tracker)
+        }
+    }
+
+    /// Returns the GPU usage and I/O rates of each process.
+    pub fn get_sample(&mut self) -> CounterSample {
+        if unsafe { PdhCollectQueryData(self.query) } != 0 {
+            return CounterSample::default();
+        }
+
+        CounterSample {
+            gpu_usage: self.gpu_counter.map(get_gpu_usage),
+            io_usage: self.get_io_usage(),
+        }
+    }
+
+    fn get_io_usage(&self) -> FxHashMap<Pid, (u64, u64)> {
+        let (pids, reads, writes) = match (self.pid_counter, self.read_counter, self.write_counter)
+        {
+            (Some(pid_counter), Some(read_counter), Some(write_counter)) => (
+                get_counter_array(pid_counter),
+                get_counter_array(read_counter),
+                get_counter_array(write_counter),
+            ),
+            _ => return FxHashMap::default(),
+        };
+
+        // Instances are named after their executable, which several processes can share, so the
+        // counters are matched up by their position instead, which is the same for counters of
+        // the same object in a query.
+        pids.into_iter()
+            .zip(reads)
+            .zip(writes)
+            .filter(|(((pid_name, _), (read_name, _)), (write_name, _))| {
+                pid_name == read_name && pid_name == write_name
+            })
+            .filter_map(|(((_, pid), (_, read)), (_, write))| {
+                let (pid, read, write) = (pid?, read?, write?);
+                // The idle process and "_Total" both have an ID of 0.
+                (pid > 0.0).then_some((pid as Pid, (read as u64, write as u64)))
+            })
+            .collect()
+    }
+}
+
+impl Drop for CounterTracker {
+    fn drop(&mut self) {
+        unsafe {
+            PdhCloseQuery(self.query);
+        }
+    }
+}
+
+/// Adds the counter at the English `path` to `query`, or returns `None` if it does not exist.
+unsafe fn add_counter(query: PDH_HQUERY, path: &str) -> Option<PDH_HCOUNTER> {
+    let path: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut counter: PDH_HCOUNTER = std::ptr::null_mut();
+    if PdhAddEnglishCounterW(query, path.as_ptr(), 0, &mut counter) != 0 {
+        return None;
+    }
+    Some(counter)
+}
+
+/// Returns the GPU usage of each process with any, as a percentage.
+fn get_gpu_usage(counter: PDH_HCOUNTER) -> FxHashMap<Pid, f64> {
+    let mut engine_usage: FxHashMap<(Pid, String), f64> = FxHashMap::default();
+    for (name, value) in get_counter_array(counter) {
+        if let (Some((pid, engine)), Some(value)) = (parse_engine_instance(&name), value) {
+            *engine_usage.entry((pid, engine.to_string())).or_insert(0.0) += value;
+        }
+    }
+
+    // Like Task Manager, report the busiest engine type rather than the sum of all of them.
+    let mut usage: FxHashMap<Pid, f64> = FxHashMap::default();
+    for ((pid, _), value) in engine_usage {
+        let entry = usage.entry(pid).or_insert(0.0);
+        if value > *entry {
+            *entry = value;
+        }
+    }
+    usage
+}
+
+/// Returns the name and value of each instance of `counter` in the last sample of its query, or
+/// no value if it could not be read.
+fn get_counter_array(counter: PDH_HCOUNTER) -> Vec<(String, Option<f64>)> {
+    let mut values = Vec::new();
+
+    unsafe {
+        let mut buffer_size = 0;
+        let mut item_count = 0;
+        let status = PdhGetFormattedCounterArrayW(
+            counter,
+            PDH_FMT_DOUBLE,
+            &mut buffer_size,
+            &mut item_count,
+            std::ptr::null_mut(),
+        );
+        if status != PDH_MORE_DATA || buffer_size == 0 {
+            return values;
+        }
+
+        // The buffer also holds the instance names after the items themselves, so allocate in items
+        // rounded up to fit the whole thing.
+        let item_size = std::mem::size_of::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+        let mut buffer: Vec<PDH_FMT_COUNTERVALUE_ITEM_W> =
+            Vec::with_capacity(buffer_size as usize / item_size + 1);
+        if PdhGetFormattedCounterArrayW(
+            counter,
+            PDH_FMT_DOUBLE,
+            &mut buffer_size,
+            &mut item_count,
+            buffer.as_mut_ptr(),
+        ) != 0
+        {
+            return values;
+        }
+        buffer.set_len(item_count as usize);
+
+        for item in &buffer {
+            let name = if item.szName.is_null() {
+                String::new()
+            } else {
+                let name_len = (0..).take_while(|&i| *item.szName.offset(i) != 0).count();
+                String::from_utf16_lossy(std::slice::from_raw_parts(item.szName, name_len))
+            };
+            let value = (item.FmtValue.CStatus == 0).then(|| *item.FmtValue.u.doubleValue());
+            values.push((name, value));
+        }
+    }
+
+    values
+}
+
+/// Parses a "GPU Engine" instance name, like `pid_1234_luid_0x00000000_0x0000C54A_phys_0_eng_0_engtype_3D`,
+/// into the PID and engine type.
+fn parse_engine_instance(name: &str) -> Option<(Pid, &str)> {
+    let pid = name.strip_prefix("pid_")?.split('_').next()?.parse().ok()?;
+    let engine = name.rsplit_once("_engtype_").map(|(_, engine)| engine)?;
+    Some((pid, engine))
+}
 
 pub fn get_process_data(
     sys: &System, use_current_cpu_total: bool, mem_total_kb: u64,
@@ -66,15 +272,52 @@ pub fn get_process_data(
                 0.0
             },
             mem_usage_bytes: process_val.memory() * 1024,
+            pss_bytes: None,
+            uss_bytes: None,
+            swap_bytes: None,
             cpu_usage_percent: process_cpu_usage,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
+            voluntary_switches_per_sec: None,
+            involuntary_switches_per_sec: None,
+            major_faults_per_sec: None,
+            minor_faults_per_sec: None,
+            security_label: None,
+            effective_caps: None,
+            io_priority: None,
             process_state: process_val.status().to_string(),
             process_state_char: 'R',
+            is_kernel_thread: false,
+            start_time: Some(process_val.start_time()),
+            cpu_time: None,
+            open_fds: None,
+            gpu_usage_percent: None,
+            container: None,
         });
     }
 
     Ok(process_vector)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_engine_instance() {
+        assert_eq!(
+            parse_engine_instance("pid_1234_luid_0x00000000_0x0000C54A_phys_0_eng_0_engtype_3D"),
+            Some((1234, "3D"))
+        );
+        assert_eq!(
+            parse_engine_instance(
+                "pid_56_luid_0x00000000_0x0000C54A_phys_0_eng_3_engtype_VideoDecode"
+            ),
+            Some((56, "VideoDecode"))
+        );
+        assert_eq!(parse_engine_instance("_Total"), None);
+        assert_eq!(parse_engine_instance("pid_abc_engtype_3D"), None);
+    }
+}
@@ -0,0 +1,30 @@
+//! Data collection for logged-in user sessions.
+//!
+//! For Unix-like systems, this is handled by reading the utmp database, like `who` does.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        pub mod unix;
+        pub use self::unix::*;
+    } else {
+        pub fn get_session_data(_actually_get: bool) -> Option<Vec<SessionHarvest>> {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionHarvest {
+    pub user: String,
+    /// The terminal the session is on, like `pts/0`.
+    pub tty: String,
+    /// The remote host the session is from, if it isn't local.
+    pub host: Option<String>,
+    /// When the session started, as seconds since the epoch.
+    pub login_time: u64,
+    /// How long since the terminal last saw input, if it could be determined.
+    pub idle_secs: Option<u64>,
+}
@@ -0,0 +1,66 @@
+//! Unix-specific functions regarding logged-in user sessions.
+
+use std::{
+    os::unix::fs::MetadataExt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::SessionHarvest;
+
+/// Converts a fixed-size utmp field to a string. These are only NUL-terminated if they are
+/// shorter than the field.
+fn utmp_field_to_string(field: &[libc::c_char]) -> String {
+    let bytes = field
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect::<Vec<_>>();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Returns how long ago the terminal was last read from, which is when it last saw input.
+fn get_idle_secs(tty: &str, current_time: i64) -> Option<u64> {
+    let metadata = std::fs::metadata(format!("/dev/{}", tty)).ok()?;
+    Some(current_time.saturating_sub(metadata.atime()).max(0) as u64)
+}
+
+pub fn get_session_data(actually_get: bool) -> Option<Vec<SessionHarvest>> {
+    if !actually_get {
+        return None;
+    }
+
+    let current_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+    let mut sessions = Vec::new();
+
+    // SAFETY: The utmp functions aren't thread-safe, but they're only ever called from the
+    // collection thread, and each entry is copied out before the next call.
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+            let entry = &*entry;
+            if entry.ut_type != libc::USER_PROCESS {
+                continue;
+            }
+
+            let tty = utmp_field_to_string(&entry.ut_line);
+            let host = utmp_field_to_string(&entry.ut_host);
+            sessions.push(SessionHarvest {
+                user: utmp_field_to_string(&entry.ut_user),
+                idle_secs: get_idle_secs(&tty, current_time),
+                tty,
+                host: if host.is_empty() { None } else { Some(host) },
+                login_time: entry.ut_tv.tv_sec as u64,
+            });
+        }
+        libc::endutxent();
+    }
+
+    Some(sessions)
+}
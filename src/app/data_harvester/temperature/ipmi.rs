@@ -0,0 +1,150 @@
+//! IPMI sensor collection for server chassis temperatures.
+//!
+//! Sensors are read by parsing the output of `ipmi-sensors` from FreeIPMI, which talks to the BMC
+//! through `/dev/ipmi0` and usually requires root.  The BMC is polled from a thread of its own, so
+//! that a slow one does not hold up harvesting.  This is currently only supported on Linux and
+//! FreeBSD.
+
+use std::{
+    process::Command,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{
+    convert_celsius_to_fahrenheit, convert_celsius_to_kelvin, is_temp_filtered, temp_vec_sort,
+    TempHarvest, TemperatureType,
+};
+use crate::app::Filter;
+
+/// Querying the BMC can take a few seconds, so we only poll it this often.
+pub const IPMI_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Caches the IPMI temperature sensors, since they are only polled every [`IPMI_POLL_INTERVAL`].
+#[derive(Debug, Default)]
+pub struct IpmiCache {
+    /// Asks the thread that polls the BMC to poll it, once it has been started.
+    poll_sender: Option<mpsc::SyncSender<()>>,
+    last_poll: Option<Instant>,
+    /// The name and reading in degrees Celsius of each sensor, as of the last poll that finished.
+    sensors: Arc<Mutex<Vec<(String, f32)>>>,
+}
+
+impl IpmiCache {
+    /// Adds the IPMI temperature sensors to the given sensors, and polls the BMC again if the
+    /// cache is stale.
+    pub fn add_ipmi_data(
+        &mut self, temperature_vec: &mut Vec<TempHarvest>, temp_type: &TemperatureType,
+        filter: &Option<Filter>, current_instant: Instant,
+    ) {
+        let sensors = &self.sensors;
+        let poll_sender = self
+            .poll_sender
+            .get_or_insert_with(|| spawn_ipmi_thread(sensors.clone()));
+        if self
+            .last_poll
+            .is_none_or(|last_poll| current_instant.duration_since(last_poll) >= IPMI_POLL_INTERVAL)
+        {
+            // This only goes through if the thread is waiting, so that polls of a slow BMC do not
+            // pile up.
+            if poll_sender.try_send(()).is_ok() {
+                self.last_poll = Some(current_instant);
+            }
+        }
+
+        let sensors = match self.sensors.lock() {
+            Ok(sensors) => sensors,
+            Err(_) => return,
+        };
+        temperature_vec.extend(
+            sensors
+                .iter()
+                .filter(|(name, _)| is_temp_filtered(filter, name))
+                .map(|(name, celsius)| TempHarvest {
+                    name: name.clone(),
+                    temperature: match temp_type {
+                        TemperatureType::Celsius => *celsius,
+                        TemperatureType::Kelvin => convert_celsius_to_kelvin(*celsius),
+                        TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(*celsius),
+                    },
+                }),
+        );
+        temp_vec_sort(temperature_vec);
+    }
+}
+
+/// Starts the thread that polls the BMC whenever it is asked to, which lives for as long as
+/// bottom, and stores the sensors of each poll in `sensors`.
+fn spawn_ipmi_thread(sensors: Arc<Mutex<Vec<(String, f32)>>>) -> mpsc::SyncSender<()> {
+    let (sender, receiver) = mpsc::sync_channel::<()>(0);
+    thread::spawn(move || {
+        for () in receiver {
+            let new_sensors = get_ipmi_sensors();
+            if let Ok(mut sensors) = sensors.lock() {
+                *sensors = new_sensors;
+            }
+        }
+    });
+    sender
+}
+
+fn get_ipmi_sensors() -> Vec<(String, f32)> {
+    Command::new("ipmi-sensors")
+        .args([
+            "--sensor-types=Temperature",
+            "--comma-separated-output",
+            "--no-header-output",
+            "--ignore-not-available-sensors",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_ipmi_sensors(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parses the comma-separated output of `ipmi-sensors`, where each line is the record ID, name,
+/// type, reading, units, and event of a sensor.  Sensors are prefixed with `IPMI: ` so they can be
+/// told apart from the local ones.
+fn parse_ipmi_sensors(output: &str) -> Vec<(String, f32)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',');
+            let _record_id = fields.next()?;
+            let name = fields.next()?.trim();
+            let _sensor_type = fields.next()?;
+            let reading = fields.next()?.trim().parse::<f32>().ok()?;
+            let celsius = match fields.next()?.trim() {
+                "C" => reading,
+                "F" => (reading - 32.0) * 5.0 / 9.0,
+                _ => return None,
+            };
+
+            Some((format!("IPMI: {}", name), celsius))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ipmi_sensors() {
+        let output = "\
+4,CPU1 Temp,Temperature,45.00,C,'OK'
+5,Inlet Temp,Temperature,77.00,F,'OK'
+6,Exhaust Temp,Temperature,N/A,C,N/A
+";
+
+        assert_eq!(
+            parse_ipmi_sensors(output),
+            vec![
+                ("IPMI: CPU1 Temp".to_string(), 45.0),
+                ("IPMI: Inlet Temp".to_string(), 25.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Gets temperature data from sysfs, via hwmon and thermal zones.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    convert_celsius_to_fahrenheit, convert_celsius_to_kelvin, is_temp_filtered, temp_vec_sort,
+    TempHarvest, TemperatureType, ThrottleStatus,
+};
+use crate::app::Filter;
+
+const HWMON_PATH: &str = "/sys/class/hwmon";
+const THERMAL_ZONE_PATH: &str = "/sys/class/thermal";
+const THROTTLED_PATH: &str = "/sys/devices/platform/soc/soc:firmware/get_throttled";
+
+pub async fn get_temperature_data(
+    temp_type: &TemperatureType, actually_get: bool, filter: &Option<Filter>,
+) -> crate::utils::error::Result<Option<Vec<TempHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let mut sensors = get_hwmon_sensors();
+
+    // Thermal zones are often also exposed through hwmon (i.e. `acpitz`), so only add the ones
+    // that aren't to avoid listing the same sensor twice.
+    let hwmon_chips = sensors
+        .iter()
+        .map(|(chip, _, _)| chip.clone())
+        .collect::<Vec<_>>();
+    sensors.extend(
+        get_thermal_zone_sensors()
+            .into_iter()
+            .filter(|(zone_type, _, _)| !hwmon_chips.contains(zone_type)),
+    );
+
+    let mut temperature_vec: Vec<TempHarvest> = sensors
+        .into_iter()
+        .filter_map(|(chip, label, celsius)| {
+            let name = match label {
+                Some(label) => format!("{}: {}", chip, label),
+                None => chip,
+            };
+
+            if is_temp_filtered(filter, &name) {
+                Some(TempHarvest {
+                    name,
+                    temperature: match temp_type {
+                        TemperatureType::Celsius => celsius,
+                        TemperatureType::Kelvin => convert_celsius_to_kelvin(celsius),
+                        TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(celsius),
+                    },
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    #[cfg(feature = "nvidia")]
+    {
+        super::nvidia::add_nvidia_data(&mut temperature_vec, temp_type, filter)?;
+    }
+
+    temp_vec_sort(&mut temperature_vec);
+    Ok(Some(temperature_vec))
+}
+
+/// Returns the firmware throttling flags, if the board reports them (i.e. a Raspberry Pi).
+pub fn get_throttle_status() -> Option<ThrottleStatus> {
+    read_trimmed(Path::new(THROTTLED_PATH))
+        .and_then(|contents| u32::from_str_radix(contents.trim_start_matches("0x"), 16).ok())
+        .map(ThrottleStatus::from_bits)
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+        .filter(|contents| !contents.is_empty())
+}
+
+/// Reads a temperature in millidegrees Celsius, returning it in degrees.
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    read_trimmed(path)
+        .and_then(|contents| contents.parse::<f32>().ok())
+        .map(|millidegrees| millidegrees / 1000.0)
+}
+
+fn get_dir_entries(path: &str) -> Vec<PathBuf> {
+    let mut entries = fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+/// Returns the chip name, label, and temperature of each hwmon sensor.
+fn get_hwmon_sensors() -> Vec<(String, Option<String>, f32)> {
+    let mut sensors = Vec::new();
+
+    for hwmon_path in get_dir_entries(HWMON_PATH) {
+        let chip = match read_trimmed(&hwmon_path.join("name")) {
+            // Every NVMe drive uses the same chip name, so use the controller name instead (i.e.
+            // `nvme0`) to tell them apart.
+            Some(chip) if chip == "nvme" => fs::read_link(hwmon_path.join("device"))
+                .ok()
+                .and_then(|device| {
+                    device
+                        .file_name()
+                        .map(|file_name| file_name.to_string_lossy().to_string())
+                })
+                .unwrap_or(chip),
+            Some(chip) => chip,
+            None => continue,
+        };
+
+        let mut inputs = fs::read_dir(&hwmon_path)
+            .map(|files| {
+                files
+                    .filter_map(|file| file.ok())
+                    .filter_map(|file| {
+                        let file_name = file.file_name().to_string_lossy().to_string();
+                        file_name
+                            .strip_prefix("temp")
+                            .and_then(|rest| rest.strip_suffix("_input"))
+                            .map(|index| index.to_string())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        inputs.sort_by_key(|index| index.parse::<u32>().unwrap_or(u32::MAX));
+
+        for index in inputs {
+            if let Some(celsius) =
+                read_millidegrees(&hwmon_path.join(format!("temp{}_input", index)))
+            {
+                let label = read_trimmed(&hwmon_path.join(format!("temp{}_label", index)));
+                sensors.push((chip.clone(), label, celsius));
+            }
+        }
+    }
+
+    sensors
+}
+
+/// Returns the type and temperature of each thermal zone, i.e. `x86_pkg_temp` or `cpu-thermal`.
+fn get_thermal_zone_sensors() -> Vec<(String, Option<String>, f32)> {
+    get_dir_entries(THERMAL_ZONE_PATH)
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .map(|file_name| file_name.to_string_lossy().starts_with("thermal_zone"))
+                .unwrap_or(false)
+        })
+        .filter_map(|zone_path| {
+            let zone_type = read_trimmed(&zone_path.join("type"))?;
+            let celsius = read_millidegrees(&zone_path.join("temp"))?;
+            Some((zone_type, None, celsius))
+        })
+        .collect()
+}
@@ -1,13 +1,14 @@
 //! Data collection for temperature metrics.
 //!
-//! For Linux and macOS, this is handled by Heim.
-//! For Windows, this is handled by sysinfo.
+//! For Linux, this is handled by reading hwmon and thermal zones from sysfs.
+//! For macOS, Windows, and FreeBSD, this is handled by sysinfo.
+//! On Linux and FreeBSD, server chassis sensors can also be read over IPMI.
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
-        pub mod heim;
-        pub use self::heim::*;
-    } else if #[cfg(any(target_os = "macos", target_os = "windows"))] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else if #[cfg(any(target_os = "macos", target_os = "windows", target_os = "freebsd"))] {
         pub mod sysinfo;
         pub use self::sysinfo::*;
     }
@@ -16,16 +17,47 @@ cfg_if::cfg_if! {
 #[cfg(feature = "nvidia")]
 pub mod nvidia;
 
+pub mod ipmi;
+
 use std::cmp::Ordering;
 
+use serde::{Deserialize, Serialize};
+
 use crate::app::Filter;
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct TempHarvest {
     pub name: String,
     pub temperature: f32,
 }
 
+/// The columns that the temperature table can be sorted by.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TempSorting {
+    Sensor,
+    Temperature,
+}
+
+impl TempSorting {
+    /// Whether sorting by this column starts out descending, which is the case for temperatures.
+    pub fn is_descending_by_default(&self) -> bool {
+        matches!(self, TempSorting::Temperature)
+    }
+}
+
+impl std::str::FromStr for TempSorting {
+    type Err = String;
+
+    /// Parses a column by its header, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sensor" => Ok(TempSorting::Sensor),
+            "temp" => Ok(TempSorting::Temperature),
+            _ => Err(format!("\"{}\" is not a temperature column.", s)),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum TemperatureType {
     Celsius,
@@ -33,6 +65,67 @@ pub enum TemperatureType {
     Fahrenheit,
 }
 
+/// The firmware throttling flags reported by some ARM boards, like the Raspberry Pi.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThrottleStatus {
+    pub under_voltage: bool,
+    pub frequency_capped: bool,
+    pub throttled: bool,
+    pub soft_temp_limit: bool,
+    pub under_voltage_occurred: bool,
+    pub frequency_capped_occurred: bool,
+    pub throttled_occurred: bool,
+    pub soft_temp_limit_occurred: bool,
+}
+
+impl ThrottleStatus {
+    /// Parses the bitmask reported by the firmware, where the lower bits are the current state and
+    /// bits 16 to 19 are whether that state has occurred since boot.
+    pub fn from_bits(bits: u32) -> Self {
+        ThrottleStatus {
+            under_voltage: bits & 0x1 != 0,
+            frequency_capped: bits & 0x2 != 0,
+            throttled: bits & 0x4 != 0,
+            soft_temp_limit: bits & 0x8 != 0,
+            under_voltage_occurred: bits & 0x10000 != 0,
+            frequency_capped_occurred: bits & 0x20000 != 0,
+            throttled_occurred: bits & 0x40000 != 0,
+            soft_temp_limit_occurred: bits & 0x80000 != 0,
+        }
+    }
+
+    /// Returns the names of the states that are currently active.
+    pub fn active_indicators(&self) -> Vec<&'static str> {
+        Self::indicators([
+            self.under_voltage,
+            self.frequency_capped,
+            self.throttled,
+            self.soft_temp_limit,
+        ])
+    }
+
+    /// Returns the names of the states that have occurred since boot, but are no longer active.
+    pub fn past_indicators(&self) -> Vec<&'static str> {
+        Self::indicators([
+            self.under_voltage_occurred && !self.under_voltage,
+            self.frequency_capped_occurred && !self.frequency_capped,
+            self.throttled_occurred && !self.throttled,
+            self.soft_temp_limit_occurred && !self.soft_temp_limit,
+        ])
+    }
+
+    fn indicators(states: [bool; 4]) -> Vec<&'static str> {
+        const NAMES: [&str; 4] = ["Undervoltage", "Freq capped", "Throttled", "Temp limited"];
+
+        NAMES
+            .iter()
+            .zip(states)
+            .filter(|(_, state)| *state)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
 impl Default for TemperatureType {
     fn default() -> Self {
         TemperatureType::Celsius
@@ -40,7 +133,7 @@ impl Default for TemperatureType {
 }
 
 cfg_if::cfg_if! {
-    if #[cfg(any(feature = "nvidia", target_os = "macos", target_os = "windows"))] {
+    if #[cfg(any(feature = "nvidia", target_os = "linux", target_os = "macos", target_os = "windows", target_os = "freebsd"))] {
         fn convert_celsius_to_kelvin(celsius: f32) -> f32 {
             celsius + 273.15
         }
@@ -51,20 +144,11 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Returns whether a sensor should be kept, that is, whether it matches an allow list or does not
+/// match a deny list.
 fn is_temp_filtered(filter: &Option<Filter>, text: &str) -> bool {
     if let Some(filter) = filter {
-        if filter.is_list_ignored {
-            let mut ret = true;
-            for r in &filter.list {
-                if r.is_match(text) {
-                    ret = false;
-                    break;
-                }
-            }
-            ret
-        } else {
-            true
-        }
+        filter.is_shown(text)
     } else {
         true
     }
@@ -86,3 +170,27 @@ fn temp_vec_sort(temperature_vec: &mut [TempHarvest]) {
 
     temperature_vec.sort_by(|a, b| a.name.partial_cmp(&b.name).unwrap_or(Ordering::Equal));
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_throttle_status() {
+        let status = ThrottleStatus::from_bits(0x50005);
+        assert!(status.under_voltage);
+        assert!(status.throttled);
+        assert!(!status.frequency_capped);
+        assert_eq!(
+            status.active_indicators(),
+            vec!["Undervoltage", "Throttled"]
+        );
+        assert!(status.past_indicators().is_empty());
+
+        let status = ThrottleStatus::from_bits(0x80000);
+        assert!(status.active_indicators().is_empty());
+        assert_eq!(status.past_indicators(), vec!["Temp limited"]);
+
+        assert_eq!(ThrottleStatus::from_bits(0), ThrottleStatus::default());
+    }
+}
@@ -0,0 +1,173 @@
+//! Linux-specific functions regarding systemd units.
+
+use std::{
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+
+use super::UnitHarvest;
+
+/// Spawning `systemctl` is relatively expensive, so units are only polled this often.
+pub const UNIT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Tracks the previous CPU time of each unit, as CPU usage is derived from the change in CPU
+/// time between two polls.
+#[derive(Debug, Default)]
+pub struct UnitTracker {
+    last_poll: Option<Instant>,
+    prev_cpu_usage: FxHashMap<String, (u64, Instant)>,
+}
+
+impl UnitTracker {
+    /// Returns the units, or `None` if they were polled less than [`UNIT_POLL_INTERVAL`] ago, in
+    /// which case the last ones are still current.
+    pub fn get_unit_data(
+        &mut self, actually_get: bool, current_instant: Instant,
+    ) -> Option<Vec<UnitHarvest>> {
+        if !actually_get
+            || self.last_poll.is_some_and(|last_poll| {
+                current_instant.duration_since(last_poll) < UNIT_POLL_INTERVAL
+            })
+        {
+            return None;
+        }
+        self.last_poll = Some(current_instant);
+
+        // The states and accounting of every loaded service are read at once.  If systemd isn't
+        // running, there's nothing to show.
+        let output = Command::new("systemctl")
+            .args([
+                "show",
+                "--property=Id,ActiveState,SubState,MemoryCurrent,CPUUsageNSec",
+                "--",
+                "*.service",
+            ])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        let units = parse_unit_properties(&String::from_utf8_lossy(&output.stdout));
+
+        let mut new_cpu_usage = FxHashMap::default();
+        let unit_harvest = units
+            .into_iter()
+            .filter(|unit| unit.active_state == "active" || unit.active_state == "failed")
+            .map(|unit| {
+                let UnitProperties {
+                    name,
+                    active_state,
+                    sub_state,
+                    mem_usage_bytes,
+                    cpu_usage_nsec,
+                } = unit;
+
+                let cpu_usage_percent = cpu_usage_nsec.and_then(|cpu_usage_nsec| {
+                    new_cpu_usage.insert(name.clone(), (cpu_usage_nsec, current_instant));
+                    let (prev_cpu_usage_nsec, prev_instant) = self.prev_cpu_usage.get(&name)?;
+                    let elapsed = current_instant.duration_since(*prev_instant).as_secs_f64();
+                    if elapsed > 0.0 {
+                        Some(
+                            cpu_usage_nsec.saturating_sub(*prev_cpu_usage_nsec) as f64
+                                / 1_000_000_000.0
+                                / elapsed
+                                * 100.0,
+                        )
+                    } else {
+                        None
+                    }
+                });
+
+                UnitHarvest {
+                    name,
+                    active_state,
+                    sub_state,
+                    cpu_usage_percent,
+                    mem_usage_bytes,
+                }
+            })
+            .collect();
+        self.prev_cpu_usage = new_cpu_usage;
+
+        Some(unit_harvest)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct UnitProperties {
+    name: String,
+    active_state: String,
+    sub_state: String,
+    /// Unset if memory accounting is disabled.
+    mem_usage_bytes: Option<u64>,
+    /// Unset if CPU accounting is disabled.
+    cpu_usage_nsec: Option<u64>,
+}
+
+/// Parses the output of `systemctl show --property=Id,ActiveState,SubState,MemoryCurrent,
+/// CPUUsageNSec` for several units into the properties of each unit.
+fn parse_unit_properties(properties: &str) -> Vec<UnitProperties> {
+    // Each unit's properties are separated by an empty line.
+    properties
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut unit = UnitProperties::default();
+            for line in block.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    match key {
+                        "Id" => unit.name = value.to_string(),
+                        "ActiveState" => unit.active_state = value.to_string(),
+                        "SubState" => unit.sub_state = value.to_string(),
+                        "MemoryCurrent" => unit.mem_usage_bytes = value.parse::<u64>().ok(),
+                        "CPUUsageNSec" => unit.cpu_usage_nsec = value.parse::<u64>().ok(),
+                        _ => {}
+                    }
+                }
+            }
+
+            (!unit.name.is_empty()).then_some(unit)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unit_properties() {
+        let properties = "\
+Id=cron.service
+ActiveState=active
+SubState=running
+MemoryCurrent=2490368
+CPUUsageNSec=153004000
+
+Id=nginx.service
+ActiveState=failed
+SubState=failed
+MemoryCurrent=[not set]
+CPUUsageNSec=[not set]
+";
+
+        assert_eq!(
+            parse_unit_properties(properties),
+            vec![
+                UnitProperties {
+                    name: "cron.service".to_string(),
+                    active_state: "active".to_string(),
+                    sub_state: "running".to_string(),
+                    mem_usage_bytes: Some(2490368),
+                    cpu_usage_nsec: Some(153004000),
+                },
+                UnitProperties {
+                    name: "nginx.service".to_string(),
+                    active_state: "failed".to_string(),
+                    sub_state: "failed".to_string(),
+                    mem_usage_bytes: None,
+                    cpu_usage_nsec: None,
+                },
+            ]
+        );
+    }
+}
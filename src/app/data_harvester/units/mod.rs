@@ -0,0 +1,44 @@
+//! Data collection for systemd units.
+//!
+//! For Linux, this is handled by querying systemd through `systemctl`, at most every few seconds.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct UnitTracker;
+
+        impl UnitTracker {
+            pub fn get_unit_data(
+                &mut self, _actually_get: bool, _current_instant: std::time::Instant,
+            ) -> Option<Vec<UnitHarvest>> {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnitHarvest {
+    /// The name of the unit, i.e. `sshd.service`.
+    pub name: String,
+    /// The high-level state of the unit, i.e. `active` or `failed`.
+    pub active_state: String,
+    /// The unit type-specific state, i.e. `running` or `exited` for services.
+    pub sub_state: String,
+    /// The CPU usage of the unit's control group, as a percentage of one core.
+    pub cpu_usage_percent: Option<f64>,
+    /// The memory usage of the unit's control group, in bytes.
+    pub mem_usage_bytes: Option<u64>,
+}
+
+impl UnitHarvest {
+    pub fn is_failed(&self) -> bool {
+        self.active_state == "failed"
+    }
+}
@@ -0,0 +1,252 @@
+//! Linux-specific functions regarding libvirt virtual machines.  libvirt is queried from a thread
+//! of its own, so that a slow or hung `libvirtd` does not hold up harvesting.
+
+use std::{
+    process::Command,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+
+use super::VmHarvest;
+
+/// Spawning `virsh` is relatively expensive, so libvirt is only queried this often.
+const VM_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The cumulative counters of a domain, as reported by `virsh domstats`.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct DomainStats {
+    vcpus: Option<u64>,
+    cpu_time_nsec: Option<u64>,
+    /// The current balloon size, in KiB.
+    balloon_kib: Option<u64>,
+    read_bytes: Option<u64>,
+    write_bytes: Option<u64>,
+    rx_bytes: Option<u64>,
+    tx_bytes: Option<u64>,
+}
+
+/// Queries libvirt, and tracks the previous counters of each domain, as usage and throughput are
+/// derived from the change in counters between two queries.
+#[derive(Debug, Default)]
+pub struct VmTracker {
+    /// Asks the thread that queries libvirt to query it, once it has been started.
+    query_sender: Option<mpsc::SyncSender<()>>,
+    /// The domains of the last query that succeeded and when it was made, until they are read.
+    latest_domains: Arc<Mutex<Option<(Vec<(String, DomainStats)>, Instant)>>>,
+    last_query: Option<Instant>,
+    prev_stats: FxHashMap<String, (DomainStats, Instant)>,
+}
+
+impl VmTracker {
+    /// Returns the domains of the last query of libvirt, if it succeeded since the last call, and
+    /// queries it again if it is due.
+    pub fn get_vm_data(
+        &mut self, actually_get: bool, current_instant: Instant,
+    ) -> Option<Vec<VmHarvest>> {
+        if !actually_get {
+            return None;
+        }
+
+        let latest_domains = &self.latest_domains;
+        let query_sender = self
+            .query_sender
+            .get_or_insert_with(|| spawn_virsh_thread(latest_domains.clone()));
+        if self
+            .last_query
+            .is_none_or(|last_query| current_instant.duration_since(last_query) >= VM_POLL_INTERVAL)
+        {
+            // This only goes through if the thread is waiting, so that queries of a slow libvirtd
+            // do not pile up.
+            if query_sender.try_send(()).is_ok() {
+                self.last_query = Some(current_instant);
+            }
+        }
+
+        let (domains, query_instant) = self
+            .latest_domains
+            .lock()
+            .ok()
+            .and_then(|mut latest_domains| latest_domains.take())?;
+
+        let mut new_stats = FxHashMap::default();
+        let vm_harvest = domains
+            .into_iter()
+            .map(|(name, stats)| {
+                let prev = self
+                    .prev_stats
+                    .get(&name)
+                    .map(|(prev_stats, prev_instant)| {
+                        (
+                            prev_stats,
+                            query_instant.duration_since(*prev_instant).as_secs_f64(),
+                        )
+                    })
+                    .filter(|(_, elapsed)| *elapsed > 0.0);
+
+                // Returns the rate of change of a counter per second.
+                let rate = |get: fn(&DomainStats) -> Option<u64>| -> Option<f64> {
+                    let (prev_stats, elapsed) = prev?;
+                    Some(get(&stats)?.saturating_sub(get(prev_stats)?) as f64 / elapsed)
+                };
+
+                let harvest = VmHarvest {
+                    name: name.clone(),
+                    vcpus: stats.vcpus,
+                    cpu_usage_percent: rate(|stats| stats.cpu_time_nsec)
+                        .map(|nsec_per_sec| nsec_per_sec / 1_000_000_000.0 * 100.0),
+                    mem_bytes: stats.balloon_kib.map(|kib| kib * 1024),
+                    read_bytes_per_sec: rate(|stats| stats.read_bytes).map(|rate| rate as u64),
+                    write_bytes_per_sec: rate(|stats| stats.write_bytes).map(|rate| rate as u64),
+                    rx_bytes_per_sec: rate(|stats| stats.rx_bytes).map(|rate| rate as u64),
+                    tx_bytes_per_sec: rate(|stats| stats.tx_bytes).map(|rate| rate as u64),
+                };
+                new_stats.insert(name, (stats, query_instant));
+
+                harvest
+            })
+            .collect();
+        self.prev_stats = new_stats;
+
+        Some(vm_harvest)
+    }
+}
+
+/// Starts the thread that queries libvirt whenever it is asked to, which lives for as long as
+/// bottom, and stores the domains of each query that succeeds in `latest_domains`.
+fn spawn_virsh_thread(
+    latest_domains: Arc<Mutex<Option<(Vec<(String, DomainStats)>, Instant)>>>,
+) -> mpsc::SyncSender<()> {
+    let (sender, receiver) = mpsc::sync_channel::<()>(0);
+    thread::spawn(move || {
+        for () in receiver {
+            let query_instant = Instant::now();
+            if let Some(domains) = get_domstats() {
+                if let Ok(mut latest_domains) = latest_domains.lock() {
+                    *latest_domains = Some((domains, query_instant));
+                }
+            }
+        }
+    });
+    sender
+}
+
+/// Returns the name and counters of each running domain, or `None` if libvirt isn't installed or
+/// reachable.
+fn get_domstats() -> Option<Vec<(String, DomainStats)>> {
+    let output = Command::new("virsh")
+        .args([
+            "--readonly",
+            "domstats",
+            "--list-running",
+            "--vcpu",
+            "--cpu-total",
+            "--balloon",
+            "--block",
+            "--interface",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(parse_domstats(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses the output of `virsh domstats` into the name and counters of each domain.  Each domain
+/// starts with a `Domain: 'name'` line, followed by indented `key=value` lines.  Block and network
+/// counters are summed over all of the domain's devices.
+fn parse_domstats(domstats: &str) -> Vec<(String, DomainStats)> {
+    let mut domains: Vec<(String, DomainStats)> = Vec::new();
+
+    for line in domstats.lines() {
+        if let Some(name) = line.strip_prefix("Domain: ") {
+            domains.push((name.trim_matches('\'').to_string(), DomainStats::default()));
+            continue;
+        }
+
+        let (stats, (key, value)) = match (domains.last_mut(), line.trim().split_once('=')) {
+            (Some((_, stats)), Some(key_value)) => (stats, key_value),
+            _ => continue,
+        };
+        let value = match value.parse::<u64>() {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        let add = |counter: &mut Option<u64>| *counter = Some(counter.unwrap_or(0) + value);
+        match key {
+            "vcpu.current" => stats.vcpus = Some(value),
+            "cpu.time" => stats.cpu_time_nsec = Some(value),
+            "balloon.current" => stats.balloon_kib = Some(value),
+            _ => match key.split('.').collect::<Vec<_>>()[..] {
+                ["block", _, "rd", "bytes"] => add(&mut stats.read_bytes),
+                ["block", _, "wr", "bytes"] => add(&mut stats.write_bytes),
+                ["net", _, "rx", "bytes"] => add(&mut stats.rx_bytes),
+                ["net", _, "tx", "bytes"] => add(&mut stats.tx_bytes),
+                _ => {}
+            },
+        }
+    }
+
+    domains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_domstats() {
+        let domstats = "\
+Domain: 'web'
+  cpu.time=30000000000
+  cpu.user=20000000000
+  balloon.current=2097152
+  balloon.maximum=4194304
+  vcpu.current=2
+  vcpu.maximum=4
+  vcpu.0.state=1
+  net.count=1
+  net.0.name=vnet0
+  net.0.rx.bytes=1000
+  net.0.tx.bytes=2000
+  block.count=2
+  block.0.name=vda
+  block.0.rd.bytes=300
+  block.0.wr.bytes=400
+  block.1.name=vdb
+  block.1.rd.bytes=500
+  block.1.wr.bytes=600
+
+Domain: 'db'
+  cpu.time=5
+";
+
+        assert_eq!(
+            parse_domstats(domstats),
+            vec![
+                (
+                    "web".to_string(),
+                    DomainStats {
+                        vcpus: Some(2),
+                        cpu_time_nsec: Some(30000000000),
+                        balloon_kib: Some(2097152),
+                        read_bytes: Some(800),
+                        write_bytes: Some(1000),
+                        rx_bytes: Some(1000),
+                        tx_bytes: Some(2000),
+                    }
+                ),
+                (
+                    "db".to_string(),
+                    DomainStats {
+                        cpu_time_nsec: Some(5),
+                        ..DomainStats::default()
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Data collection for libvirt virtual machines.
+//!
+//! For Linux, this is handled by querying libvirt through `virsh domstats`.
+//! Other platforms are currently not supported.
+
+use serde::{Deserialize, Serialize};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct VmTracker;
+
+        impl VmTracker {
+            pub fn get_vm_data(
+                &mut self, _actually_get: bool, _current_instant: std::time::Instant,
+            ) -> Option<Vec<VmHarvest>> {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VmHarvest {
+    /// The name of the libvirt domain.
+    pub name: String,
+    pub vcpus: Option<u64>,
+    /// The CPU usage of the guest, as a percentage of one host core.
+    pub cpu_usage_percent: Option<f64>,
+    /// The memory currently assigned to the guest, in bytes.
+    pub mem_bytes: Option<u64>,
+    pub read_bytes_per_sec: Option<u64>,
+    pub write_bytes_per_sec: Option<u64>,
+    pub rx_bytes_per_sec: Option<u64>,
+    pub tx_bytes_per_sec: Option<u64>,
+}
@@ -0,0 +1,241 @@
+//! Matching of disk, mount point, temperature sensor, and network interface names against the
+//! filters in the config file.
+//!
+//! A filter has a list of patterns that allow entries and a list of patterns that deny them.  An
+//! entry that matches an allowing pattern is always kept, even if it also matches a denying one.
+//! Otherwise, an entry that matches a denying pattern is filtered out.  Anything else is kept,
+//! unless the filter has allowing patterns, in which case only the entries they match are kept.
+//!
+//! Patterns are literal names, globs, or regexes, as chosen by the `regex` and `glob` options of
+//! the filter.  A single pattern may also choose for itself by starting with `glob:` or `regex:`,
+//! in which case it is anchored, so that it must match the whole name.
+
+use regex::Regex;
+
+use crate::options::IgnoreList;
+
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub allow: Vec<Regex>,
+    pub deny: Vec<Regex>,
+}
+
+impl Filter {
+    /// Compiles the patterns of a filter from the config file.  The patterns in `list` allow or
+    /// deny entries depending on `is_list_ignored`, alongside those in `allow` and `deny`.
+    pub fn from_ignore_list(ignore_list: &IgnoreList) -> Result<Self, regex::Error> {
+        let compile = |patterns: &[String]| -> Result<Vec<Regex>, regex::Error> {
+            patterns
+                .iter()
+                .map(|pattern| compile_pattern(pattern, ignore_list))
+                .collect()
+        };
+
+        let mut filter = Filter {
+            allow: compile(ignore_list.allow.as_deref().unwrap_or_default())?,
+            deny: compile(ignore_list.deny.as_deref().unwrap_or_default())?,
+        };
+        let list = compile(&ignore_list.list)?;
+        if ignore_list.is_list_ignored {
+            filter.deny.extend(list);
+        } else {
+            filter.allow.extend(list);
+        }
+
+        Ok(filter)
+    }
+
+    /// Returns `Some(true)` if `name` is explicitly allowed by the filter, `Some(false)` if it is
+    /// explicitly denied, and `None` if no pattern matches it.
+    pub fn check(&self, name: &str) -> Option<bool> {
+        if self.allow.iter().any(|regex| regex.is_match(name)) {
+            Some(true)
+        } else if self.deny.iter().any(|regex| regex.is_match(name)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether an entry named `name` is shown, rather than filtered out.
+    pub fn is_shown(&self, name: &str) -> bool {
+        self.check(name).unwrap_or(self.allow.is_empty())
+    }
+}
+
+/// Compiles a single pattern of a filter into a regex, following the options of the filter.
+fn compile_pattern(pattern: &str, ignore_list: &IgnoreList) -> Result<Regex, regex::Error> {
+    let case_flag = if ignore_list.case_sensitive {
+        ""
+    } else {
+        "(?i)"
+    };
+
+    let res = if let Some(glob) = pattern.strip_prefix("glob:") {
+        format!("{}^{}$", case_flag, glob_to_regex(glob))
+    } else if let Some(regex) = pattern.strip_prefix("regex:") {
+        format!("{}^(?:{})$", case_flag, regex)
+    } else if ignore_list.glob {
+        // A glob always has to match the whole name, as `*` can stand in for the rest of it.
+        format!("{}^{}$", case_flag, glob_to_regex(pattern))
+    } else {
+        let escaped_string: String;
+        format!(
+            "{}{}{}{}",
+            if ignore_list.whole_word { "^" } else { "" },
+            case_flag,
+            if ignore_list.regex {
+                pattern
+            } else {
+                escaped_string = regex::escape(pattern);
+                &escaped_string
+            },
+            if ignore_list.whole_word { "$" } else { "" },
+        )
+    };
+
+    Regex::new(&res)
+}
+
+/// Translates a glob into the equivalent regex, without anchors.  `*` matches any run of
+/// characters, `?` matches any single character, and `[...]` matches any character in the
+/// brackets, or any character not in them if it starts with `!`.
+fn glob_to_regex(glob: &str) -> String {
+    let mut res = String::with_capacity(glob.len() * 2);
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => res.push_str(".*"),
+            '?' => res.push('.'),
+            '[' => {
+                let is_negated = chars.peek() == Some(&'!');
+                if is_negated {
+                    chars.next();
+                }
+                let mut class = Vec::new();
+                let mut is_closed = false;
+                for c in chars.by_ref() {
+                    // A `]` right after the opening bracket is part of the class.
+                    if c == ']' && !class.is_empty() {
+                        is_closed = true;
+                        break;
+                    }
+                    class.push(c);
+                }
+
+                if is_closed {
+                    res.push('[');
+                    if is_negated {
+                        res.push('^');
+                    }
+                    for c in class {
+                        if matches!(c, '\\' | '[' | ']' | '^' | '&' | '~') {
+                            res.push('\\');
+                        }
+                        res.push(c);
+                    }
+                    res.push(']');
+                } else {
+                    // An unclosed bracket is just a bracket.
+                    res.push_str(&regex::escape(if is_negated { "[!" } else { "[" }));
+                    res.push_str(&regex::escape(&class.into_iter().collect::<String>()));
+                }
+            }
+            c => res.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ignore_list(list: &[&str], is_list_ignored: bool) -> IgnoreList {
+        IgnoreList {
+            is_list_ignored,
+            list: list.iter().map(|entry| entry.to_string()).collect(),
+            ..IgnoreList::default()
+        }
+    }
+
+    #[test]
+    fn test_literal_filter() {
+        let filter = Filter::from_ignore_list(&ignore_list(&["lo"], true)).unwrap();
+        assert!(!filter.is_shown("lo"));
+        assert!(!filter.is_shown("LO"));
+        assert!(!filter.is_shown("flow"));
+        assert!(filter.is_shown("eth0"));
+
+        let filter = Filter::from_ignore_list(&IgnoreList {
+            case_sensitive: true,
+            whole_word: true,
+            ..ignore_list(&["lo"], true)
+        })
+        .unwrap();
+        assert!(!filter.is_shown("lo"));
+        assert!(filter.is_shown("LO"));
+        assert!(filter.is_shown("flow"));
+    }
+
+    #[test]
+    fn test_allow_list() {
+        let filter = Filter::from_ignore_list(&ignore_list(&["nvme"], false)).unwrap();
+        assert!(filter.is_shown("nvme0: Composite"));
+        assert!(!filter.is_shown("k10temp: Tctl"));
+    }
+
+    #[test]
+    fn test_glob_filter() {
+        let filter = Filter::from_ignore_list(&IgnoreList {
+            glob: true,
+            ..ignore_list(&["veth*", "docker?", "br-[0-9a-f]*"], true)
+        })
+        .unwrap();
+        assert!(!filter.is_shown("veth1234"));
+        assert!(!filter.is_shown("docker0"));
+        assert!(!filter.is_shown("br-3f2a"));
+        assert!(filter.is_shown("myveth0"));
+        assert!(filter.is_shown("docker10"));
+        assert!(filter.is_shown("br-zz"));
+        assert!(filter.is_shown("eth0"));
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        assert_eq!(glob_to_regex("a.b*"), "a\\.b.*");
+        assert_eq!(glob_to_regex("[!ab]?"), "[^ab].");
+        assert_eq!(glob_to_regex("[]]"), "[\\]]");
+        assert_eq!(glob_to_regex("[ab"), "\\[ab");
+    }
+
+    #[test]
+    fn test_prefixed_patterns() {
+        let filter = Filter::from_ignore_list(&ignore_list(
+            &["regex:/dev/sd[a-z]", "glob:/mnt/*", "boot"],
+            true,
+        ))
+        .unwrap();
+        assert!(!filter.is_shown("/dev/sda"));
+        assert!(filter.is_shown("/dev/sda1"));
+        assert!(!filter.is_shown("/mnt/usb"));
+        assert!(filter.is_shown("/media/mnt/usb"));
+        assert!(!filter.is_shown("/boot/efi"));
+    }
+
+    #[test]
+    fn test_allow_takes_precedence() {
+        let filter = Filter::from_ignore_list(&IgnoreList {
+            allow: Some(vec!["glob:veth-keep*".to_string()]),
+            deny: Some(vec!["glob:veth*".to_string()]),
+            ..ignore_list(&[], true)
+        })
+        .unwrap();
+        assert_eq!(filter.check("veth-keep0"), Some(true));
+        assert_eq!(filter.check("veth0"), Some(false));
+        assert_eq!(filter.check("eth0"), None);
+        assert!(!filter.is_shown("eth0"));
+    }
+}
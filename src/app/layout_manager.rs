@@ -1,12 +1,12 @@
 use crate::error::{BottomError, Result};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use typed_builder::*;
 
-use crate::constants::DEFAULT_WIDGET_ID;
+use crate::{app::GraphStyle, constants::DEFAULT_WIDGET_ID};
 
 /// Represents a more usable representation of the layout, derived from the
 /// config.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct BottomLayout {
     pub rows: Vec<BottomRow>,
     pub total_row_height_ratio: u32,
@@ -20,6 +20,15 @@ type ColumnRowMappings = (u32, BTreeMap<LineSegment, WidgetMappings>);
 type ColumnMappings = (u32, BTreeMap<LineSegment, ColumnRowMappings>);
 
 impl BottomLayout {
+    /// Returns every widget of the layout.
+    pub fn widgets(&self) -> impl Iterator<Item = &BottomWidget> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+    }
+
     pub fn get_movement_mappings(&mut self) {
         #[allow(clippy::suspicious_operation_groupings)] // Have to enable this, clippy really doesn't like me doing this with tuples...
         fn is_intersecting(a: LineSegment, b: LineSegment) -> bool {
@@ -879,6 +888,17 @@ pub struct BottomWidget {
     /// Bottom right corner when drawn, for mouse click detection.  (x, y)
     #[builder(default = None)]
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// The graph style set for this widget in the layout, if any.
+    #[builder(default = None)]
+    pub graph_style: Option<GraphStyle>,
+
+    /// Whether the areas below the lines of this widget's graph are filled, if set in the layout.
+    #[builder(default = None)]
+    pub is_filled: Option<bool>,
+    /// The name of the remote host whose data this widget shows, if it is not the local host.
+    #[builder(default = None)]
+    pub host: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -898,17 +918,43 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Conn,
+    Power,
+    Users,
+    Activity,
+    Pods,
+    Vm,
+    Numa,
+    Units,
+    KernelLog,
+    Alerts,
+    CpuHeatmap,
 }
 
 impl BottomWidgetType {
     pub fn is_widget_table(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend)
+        matches!(
+            self,
+            Disk | Proc
+                | ProcSort
+                | Temp
+                | CpuLegend
+                | Conn
+                | Users
+                | Activity
+                | Pods
+                | Vm
+                | Numa
+                | Units
+                | KernelLog
+                | Alerts
+        )
     }
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem)
+        matches!(self, Cpu | Net | Mem | Power)
     }
 
     pub fn get_pretty_name(&self) -> &str {
@@ -921,9 +967,77 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            Conn => "Connections",
+            Users => "Users",
+            Activity => "Activity",
+            Pods => "Pods",
+            Vm => "VMs",
+            Numa => "NUMA",
+            Units => "Units",
+            KernelLog => "Kernel Log",
+            Alerts => "Alerts",
+            CpuHeatmap => "CPU Heatmap",
+            Power => "Power",
             _ => "",
         }
     }
+
+    /// Returns the name of the widget type in the `type` field of a layout in the config file.
+    /// Returns whether a widget of this type can show the data of a remote host.
+    pub fn can_have_host(&self) -> bool {
+        matches!(self, BottomWidgetType::Cpu | BottomWidgetType::Mem)
+    }
+
+    pub fn get_config_name(&self) -> &str {
+        use BottomWidgetType::*;
+        match self {
+            Cpu => "cpu",
+            Mem => "mem",
+            Net => "net",
+            Proc => "proc",
+            Temp => "temp",
+            Disk => "disk",
+            Battery => "batt",
+            Conn => "conn",
+            Users => "users",
+            Activity => "activity",
+            Pods => "pods",
+            Vm => "vm",
+            Numa => "numa",
+            Units => "units",
+            KernelLog => "kernel_log",
+            Alerts => "alerts",
+            CpuHeatmap => "cpu_heatmap",
+            Power => "power",
+            _ => "empty",
+        }
+    }
+
+    /// Returns the widget types that can be placed in a layout, in the order that they are cycled
+    /// through when editing the layout.
+    pub fn get_layout_types() -> Vec<BottomWidgetType> {
+        use BottomWidgetType::*;
+        let mut layout_types = vec![
+            Cpu, CpuHeatmap, Mem, Net, Proc, Temp, Disk, Conn, Power, Users, Activity, Pods, Vm,
+            Numa, Units, KernelLog, Alerts,
+        ];
+        if cfg!(feature = "battery") {
+            layout_types.push(Battery);
+        }
+
+        layout_types
+    }
+
+    /// Returns how far the ID of this widget is from the ID of the widget it is a part of, like
+    /// a CPU legend is from its CPU widget.
+    pub fn get_parent_offset(&self) -> u64 {
+        use BottomWidgetType::*;
+        match self {
+            CpuLegend | ProcSearch => 1,
+            ProcSort => 2,
+            _ => 0,
+        }
+    }
 }
 
 impl Default for BottomWidgetType {
@@ -939,11 +1053,22 @@ impl std::str::FromStr for BottomWidgetType {
         let lower_case = s.to_lowercase();
         match lower_case.as_str() {
             "cpu" => Ok(BottomWidgetType::Cpu),
+            "cpu_heatmap" | "heatmap" => Ok(BottomWidgetType::CpuHeatmap),
             "mem" | "memory" => Ok(BottomWidgetType::Mem),
             "net" | "network" => Ok(BottomWidgetType::Net),
             "proc" | "process" | "processes" => Ok(BottomWidgetType::Proc),
             "temp" | "temperature" => Ok(BottomWidgetType::Temp),
             "disk" => Ok(BottomWidgetType::Disk),
+            "conn" | "connections" => Ok(BottomWidgetType::Conn),
+            "power" => Ok(BottomWidgetType::Power),
+            "users" | "who" => Ok(BottomWidgetType::Users),
+            "vm" | "vms" | "libvirt" => Ok(BottomWidgetType::Vm),
+            "pods" | "k8s" | "kubernetes" => Ok(BottomWidgetType::Pods),
+            "activity" | "interrupts" => Ok(BottomWidgetType::Activity),
+            "numa" => Ok(BottomWidgetType::Numa),
+            "units" | "systemd" => Ok(BottomWidgetType::Units),
+            "dmesg" | "kernel_log" => Ok(BottomWidgetType::KernelLog),
+            "alerts" | "watchdog" => Ok(BottomWidgetType::Alerts),
             "empty" => Ok(BottomWidgetType::Empty),
             "battery" | "batt" if cfg!(feature = "battery") => Ok(BottomWidgetType::Battery),
             _ => {
@@ -955,6 +1080,8 @@ Supported widget names:
 +--------------------------+
 |            cpu           |
 +--------------------------+
+|   cpu_heatmap, heatmap   |
++--------------------------+
 |        mem, memory       |
 +--------------------------+
 |       net, network       |
@@ -965,6 +1092,26 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|     conn, connections    |
++--------------------------+
+|           power          |
++--------------------------+
+|         users, who       |
++--------------------------+
+|           numa           |
++--------------------------+
+|      units, systemd      |
++--------------------------+
+|     dmesg, kernel_log    |
++--------------------------+
+|     vm, vms, libvirt     |
++--------------------------+
+|  pods, k8s, kubernetes   |
++--------------------------+
+|   activity, interrupts   |
++--------------------------+
+|     alerts, watchdog     |
++--------------------------+
 |       batt, battery      |
 +--------------------------+
                 ",
@@ -978,6 +1125,8 @@ Supported widget names:
 +--------------------------+
 |            cpu           |
 +--------------------------+
+|   cpu_heatmap, heatmap   |
++--------------------------+
 |        mem, memory       |
 +--------------------------+
 |       net, network       |
@@ -987,6 +1136,26 @@ Supported widget names:
 |     temp, temperature    |
 +--------------------------+
 |           disk           |
++--------------------------+
+|     conn, connections    |
++--------------------------+
+|           power          |
++--------------------------+
+|         users, who       |
++--------------------------+
+|           numa           |
++--------------------------+
+|      units, systemd      |
++--------------------------+
+|     dmesg, kernel_log    |
++--------------------------+
+|     vm, vms, libvirt     |
++--------------------------+
+|  pods, k8s, kubernetes   |
++--------------------------+
+|   activity, interrupts   |
++--------------------------+
+|     alerts, watchdog     |
 +--------------------------+
                 ",
                         s
@@ -997,7 +1166,7 @@ Supported widget names:
     }
 }
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq)]
 pub struct UsedWidgets {
     pub use_cpu: bool,
     pub use_mem: bool,
@@ -1006,4 +1175,68 @@ pub struct UsedWidgets {
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_conn: bool,
+    pub use_power: bool,
+    pub use_users: bool,
+    pub use_activity: bool,
+    pub use_pods: bool,
+    pub use_vm: bool,
+    pub use_numa: bool,
+    pub use_units: bool,
+    pub use_kernel_log: bool,
+    pub use_alerts: bool,
+}
+
+impl UsedWidgets {
+    /// Returns which data is needed for widgets of `used_widget_types`.  Processes are also needed
+    /// if there are process alerts to check or a global filter to apply, as given by
+    /// `needs_processes`.
+    pub fn from_widget_types(
+        used_widget_types: &HashSet<BottomWidgetType>, needs_processes: bool,
+    ) -> Self {
+        use BottomWidgetType::*;
+        let is_used = |widget_type: &BottomWidgetType| used_widget_types.contains(widget_type);
+        UsedWidgets {
+            use_cpu: is_used(&Cpu) || is_used(&BasicCpu) || is_used(&CpuHeatmap),
+            use_mem: is_used(&Mem) || is_used(&BasicMem),
+            use_net: is_used(&Net) || is_used(&BasicNet),
+            use_proc: is_used(&Proc) || needs_processes,
+            use_disk: is_used(&Disk),
+            use_temp: is_used(&Temp),
+            use_battery: is_used(&Battery),
+            use_conn: is_used(&Conn),
+            use_users: is_used(&Users),
+            use_activity: is_used(&Activity),
+            use_pods: is_used(&Pods),
+            use_vm: is_used(&Vm),
+            use_numa: is_used(&Numa),
+            use_units: is_used(&Units),
+            use_kernel_log: is_used(&KernelLog),
+            use_alerts: is_used(&Alerts),
+            use_power: is_used(&Power),
+        }
+    }
+
+    /// Returns which data is needed for the widgets of either `self` or `other`.
+    pub fn union(&self, other: &UsedWidgets) -> Self {
+        UsedWidgets {
+            use_cpu: self.use_cpu || other.use_cpu,
+            use_mem: self.use_mem || other.use_mem,
+            use_net: self.use_net || other.use_net,
+            use_proc: self.use_proc || other.use_proc,
+            use_disk: self.use_disk || other.use_disk,
+            use_temp: self.use_temp || other.use_temp,
+            use_battery: self.use_battery || other.use_battery,
+            use_conn: self.use_conn || other.use_conn,
+            use_power: self.use_power || other.use_power,
+            use_users: self.use_users || other.use_users,
+            use_activity: self.use_activity || other.use_activity,
+            use_pods: self.use_pods || other.use_pods,
+            use_vm: self.use_vm || other.use_vm,
+            use_numa: self.use_numa || other.use_numa,
+            use_units: self.use_units || other.use_units,
+            use_kernel_log: self.use_kernel_log || other.use_kernel_log,
+            use_alerts: self.use_alerts || other.use_alerts,
+        }
+    }
 }
@@ -0,0 +1,638 @@
+//! Alert rules for metrics of the whole system, like the CPU usage or the hottest sensor, which
+//! raise an alert when a metric stays past a threshold for long enough.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    app::{
+        data_farmer::DataCollection,
+        data_harvester::temperature::TemperatureType,
+        layout_manager::{BottomWidgetType, UsedWidgets},
+    },
+    utils::{error::BottomError, gen_util},
+};
+
+/// How many alerts are kept around for the alerts widget; the oldest are dropped first.
+const MAX_METRIC_ALERTS: usize = 500;
+
+/// The metrics that alert rules can watch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMetric {
+    /// The average usage of all cores, as a percentage.
+    Cpu,
+    /// The used RAM, as a percentage.
+    Memory,
+    /// The used swap, as a percentage.
+    Swap,
+    /// The bits received per second over all interfaces that are not filtered out.
+    NetworkRx,
+    /// The bits transmitted per second over all interfaces that are not filtered out.
+    NetworkTx,
+    /// The temperature of the hottest sensor, in the unit that bottom is set to show.
+    Temperature,
+    /// The used space of the fullest disk, as a percentage.
+    Disk,
+    /// The load average over the last minute.
+    Load,
+    /// The power drawn by the power domain drawing the most, in watts.
+    Power,
+    /// The charge of the emptiest battery, as a percentage.
+    #[cfg(feature = "battery")]
+    Battery,
+}
+
+impl AlertMetric {
+    /// The name of the metric as shown in alerts, like "CPU".
+    pub fn get_name(self) -> &'static str {
+        match self {
+            AlertMetric::Cpu => "CPU",
+            AlertMetric::Memory => "Mem",
+            AlertMetric::Swap => "Swap",
+            AlertMetric::NetworkRx => "RX",
+            AlertMetric::NetworkTx => "TX",
+            AlertMetric::Temperature => "Temp",
+            AlertMetric::Disk => "Disk",
+            AlertMetric::Load => "Load",
+            AlertMetric::Power => "Power",
+            #[cfg(feature = "battery")]
+            AlertMetric::Battery => "Battery",
+        }
+    }
+
+    /// The name of the metric as written in the config file, like "cpu".
+    pub fn get_key(self) -> &'static str {
+        match self {
+            AlertMetric::Cpu => "cpu",
+            AlertMetric::Memory => "memory",
+            AlertMetric::Swap => "swap",
+            AlertMetric::NetworkRx => "network_rx",
+            AlertMetric::NetworkTx => "network_tx",
+            AlertMetric::Temperature => "temperature",
+            AlertMetric::Disk => "disk",
+            AlertMetric::Load => "load",
+            AlertMetric::Power => "power",
+            #[cfg(feature = "battery")]
+            AlertMetric::Battery => "battery",
+        }
+    }
+
+    /// Returns the current value of the metric, or `None` if it was not harvested.
+    pub fn get_value(self, data_collection: &DataCollection) -> Option<f64> {
+        let get_use_percent = |used: u64, total: u64| {
+            if total > 0 {
+                Some(used as f64 / total as f64 * 100.0)
+            } else {
+                None
+            }
+        };
+        let cpu_harvest = &data_collection.cpu_harvest;
+
+        match self {
+            AlertMetric::Cpu => match cpu_harvest.iter().find(|cpu| cpu.cpu_count.is_none()) {
+                Some(average) => Some(average.cpu_usage),
+                None if !cpu_harvest.is_empty() => Some(
+                    cpu_harvest.iter().map(|cpu| cpu.cpu_usage).sum::<f64>()
+                        / cpu_harvest.len() as f64,
+                ),
+                None => None,
+            },
+            AlertMetric::Memory => get_use_percent(
+                data_collection.memory_harvest.mem_used_in_kib,
+                data_collection.memory_harvest.mem_total_in_kib,
+            ),
+            AlertMetric::Swap => get_use_percent(
+                data_collection.swap_harvest.mem_used_in_kib,
+                data_collection.swap_harvest.mem_total_in_kib,
+            ),
+            AlertMetric::NetworkRx | AlertMetric::NetworkTx => {
+                let network = &data_collection.network_harvest;
+                if network.total_rx == 0 && network.total_tx == 0 {
+                    None
+                } else if self == AlertMetric::NetworkRx {
+                    Some(network.rx as f64)
+                } else {
+                    Some(network.tx as f64)
+                }
+            }
+            AlertMetric::Temperature => data_collection
+                .temp_harvest
+                .iter()
+                .map(|sensor| f64::from(sensor.temperature))
+                .reduce(f64::max),
+            AlertMetric::Disk => data_collection
+                .disk_harvest
+                .iter()
+                .filter_map(|disk| get_use_percent(disk.used_space?, disk.total_space?))
+                .reduce(f64::max),
+            AlertMetric::Load => {
+                if cpu_harvest.is_empty() {
+                    None
+                } else {
+                    Some(f64::from(data_collection.load_avg_harvest[0]))
+                }
+            }
+            AlertMetric::Power => data_collection
+                .power_harvest
+                .iter()
+                .map(|domain| domain.watts)
+                .reduce(f64::max),
+            #[cfg(feature = "battery")]
+            AlertMetric::Battery => data_collection
+                .battery_harvest
+                .iter()
+                .map(|battery| battery.charge_percent)
+                .reduce(f64::min),
+        }
+    }
+
+    /// Formats a value of the metric along with its name, like "CPU 95.2%".
+    pub fn describe_value(self, value: f64, temperature_type: &TemperatureType) -> String {
+        format!(
+            "{} {}",
+            self.get_name(),
+            self.format_value(value, temperature_type)
+        )
+    }
+
+    /// Formats a value of the metric along with its unit, like "95.2%".
+    pub fn format_value(self, value: f64, temperature_type: &TemperatureType) -> String {
+        match self {
+            AlertMetric::NetworkRx | AlertMetric::NetworkTx => {
+                let (value, unit) = gen_util::get_decimal_prefix(value.max(0.0) as u64, "b");
+                format!("{:.1}{}/s", value, unit)
+            }
+            AlertMetric::Temperature => match temperature_type {
+                TemperatureType::Celsius => format!("{:.1}°C", value),
+                TemperatureType::Kelvin => format!("{:.1}K", value),
+                TemperatureType::Fahrenheit => format!("{:.1}°F", value),
+            },
+            AlertMetric::Load => format!("{:.2}", value),
+            AlertMetric::Power => format!("{:.1}W", value),
+            _ => format!("{:.1}%", value),
+        }
+    }
+
+    /// Returns whether widgets of `widget_type` show the metric, and are highlighted while it has
+    /// an active alert.
+    pub fn is_shown_by(self, widget_type: &BottomWidgetType) -> bool {
+        use BottomWidgetType::*;
+        match self {
+            AlertMetric::Cpu | AlertMetric::Load => {
+                matches!(widget_type, Cpu | CpuLegend | BasicCpu | CpuHeatmap)
+            }
+            AlertMetric::Memory | AlertMetric::Swap => matches!(widget_type, Mem | BasicMem),
+            AlertMetric::NetworkRx | AlertMetric::NetworkTx => {
+                matches!(widget_type, Net | BasicNet)
+            }
+            AlertMetric::Temperature => matches!(widget_type, Temp),
+            AlertMetric::Disk => matches!(widget_type, Disk),
+            AlertMetric::Power => matches!(widget_type, Power),
+            #[cfg(feature = "battery")]
+            AlertMetric::Battery => matches!(widget_type, Battery),
+        }
+    }
+}
+
+impl std::str::FromStr for AlertMetric {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cpu" => Ok(AlertMetric::Cpu),
+            "memory" | "mem" => Ok(AlertMetric::Memory),
+            "swap" => Ok(AlertMetric::Swap),
+            "network_rx" | "rx" => Ok(AlertMetric::NetworkRx),
+            "network_tx" | "tx" => Ok(AlertMetric::NetworkTx),
+            "temperature" | "temp" => Ok(AlertMetric::Temperature),
+            "disk" => Ok(AlertMetric::Disk),
+            "load" => Ok(AlertMetric::Load),
+            "power" => Ok(AlertMetric::Power),
+            #[cfg(feature = "battery")]
+            "battery" => Ok(AlertMetric::Battery),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid alert metric, use \"cpu\", \"memory\", \"swap\", \"network_rx\", \"network_tx\", \"temperature\", \"disk\", \"load\", \"power\", or \"battery\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// How the value of a metric is compared to the threshold of a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlertComparison {
+    #[default]
+    Above,
+    AtLeast,
+    Below,
+    AtMost,
+}
+
+impl AlertComparison {
+    pub fn get_symbol(self) -> &'static str {
+        match self {
+            AlertComparison::Above => ">",
+            AlertComparison::AtLeast => ">=",
+            AlertComparison::Below => "<",
+            AlertComparison::AtMost => "<=",
+        }
+    }
+
+    fn is_met(self, value: f64, threshold: f64) -> bool {
+        match self {
+            AlertComparison::Above => value > threshold,
+            AlertComparison::AtLeast => value >= threshold,
+            AlertComparison::Below => value < threshold,
+            AlertComparison::AtMost => value <= threshold,
+        }
+    }
+}
+
+impl std::str::FromStr for AlertComparison {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            ">" => Ok(AlertComparison::Above),
+            ">=" => Ok(AlertComparison::AtLeast),
+            "<" => Ok(AlertComparison::Below),
+            "<=" => Ok(AlertComparison::AtMost),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid alert comparison, use \">\", \">=\", \"<\", or \"<=\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// How serious an alert is, which decides how it is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for AlertSeverity {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(AlertSeverity::Info),
+            "warning" => Ok(AlertSeverity::Warning),
+            "critical" => Ok(AlertSeverity::Critical),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid alert severity, use \"info\", \"warning\", or \"critical\".",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricAlertRule {
+    /// The name that alerts raised by the rule are shown with.
+    pub name: String,
+    pub metric: AlertMetric,
+    pub comparison: AlertComparison,
+    pub threshold: f64,
+    /// How long the threshold must be passed for before alerting.
+    pub duration: Duration,
+    pub severity: AlertSeverity,
+    /// Whether to show a desktop notification when the rule starts alerting.
+    pub notify: bool,
+    /// The shell command to run when the rule starts and stops alerting.
+    pub command: Option<String>,
+}
+
+impl MetricAlertRule {
+    /// Describes the rule like "CPU > 90.0% for 30s".
+    pub fn describe(&self, temperature_type: &TemperatureType) -> String {
+        let condition = format!(
+            "{} {} {}",
+            self.metric.get_name(),
+            self.comparison.get_symbol(),
+            self.metric.format_value(self.threshold, temperature_type)
+        );
+
+        if self.duration.is_zero() {
+            condition
+        } else {
+            format!("{} for {}s", condition, self.duration.as_secs())
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricAlert {
+    rule_index: usize,
+    pub name: String,
+    pub severity: AlertSeverity,
+    /// The value of the metric when the alert was raised, along with its name.
+    pub value_description: String,
+    pub rule_description: String,
+    /// When the alert was raised, in seconds since the Unix epoch.
+    pub raised_time: u64,
+    /// Whether the metric is still past the threshold of the rule.
+    pub is_active: bool,
+}
+
+/// A rule starting or stopping alerting, for what has to happen when it does, like showing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertTransition {
+    pub rule_index: usize,
+    /// The value of the metric at the time, if it was harvested.
+    pub value: Option<f64>,
+    /// Whether the rule started alerting, rather than stopped.
+    pub is_firing: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct MetricAlerts {
+    rules: Vec<MetricAlertRule>,
+    temperature_type: TemperatureType,
+    /// When the metric of each rule started passing its threshold, if it is.
+    exceeding_since: Vec<Option<Instant>>,
+    /// Whether each rule is alerting.
+    active: Vec<bool>,
+    /// Every alert raised so far, oldest first.
+    pub alerts: Vec<MetricAlert>,
+}
+
+impl MetricAlerts {
+    pub fn new(rules: Vec<MetricAlertRule>, temperature_type: TemperatureType) -> Self {
+        MetricAlerts {
+            exceeding_since: vec![None; rules.len()],
+            active: vec![false; rules.len()],
+            rules,
+            temperature_type,
+            alerts: Vec::new(),
+        }
+    }
+
+    pub fn has_rules(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    pub fn get_rules(&self) -> &[MetricAlertRule] {
+        &self.rules
+    }
+
+    /// Formats a value of the metric of the rule at `rule_index`, along with the metric's name.
+    pub fn describe_value(&self, rule_index: usize, value: f64) -> String {
+        self.rules[rule_index]
+            .metric
+            .describe_value(value, &self.temperature_type)
+    }
+
+    /// Describes the rule at `rule_index` like "CPU > 90.0% for 30s".
+    pub fn describe_rule(&self, rule_index: usize) -> String {
+        self.rules[rule_index].describe(&self.temperature_type)
+    }
+
+    /// Returns the data that has to be harvested for the metrics of the rules.
+    pub fn get_used_widgets(&self) -> UsedWidgets {
+        let mut used_widgets = UsedWidgets::default();
+        for rule in &self.rules {
+            match rule.metric {
+                AlertMetric::Cpu | AlertMetric::Load => used_widgets.use_cpu = true,
+                AlertMetric::Memory | AlertMetric::Swap => used_widgets.use_mem = true,
+                AlertMetric::NetworkRx | AlertMetric::NetworkTx => used_widgets.use_net = true,
+                AlertMetric::Temperature => used_widgets.use_temp = true,
+                AlertMetric::Disk => used_widgets.use_disk = true,
+                AlertMetric::Power => used_widgets.use_power = true,
+                #[cfg(feature = "battery")]
+                AlertMetric::Battery => used_widgets.use_battery = true,
+            }
+        }
+        used_widgets
+    }
+
+    /// Checks the rules against the latest harvest, raising and resolving alerts, and returns the
+    /// rules that started or stopped alerting.
+    pub fn update(&mut self, data_collection: &DataCollection) -> Vec<AlertTransition> {
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let values = self
+            .rules
+            .iter()
+            .map(|rule| rule.metric.get_value(data_collection))
+            .collect::<Vec<_>>();
+        self.update_at(&values, Instant::now(), current_time)
+    }
+
+    fn update_at(
+        &mut self, values: &[Option<f64>], now: Instant, current_time: u64,
+    ) -> Vec<AlertTransition> {
+        let mut transitions = Vec::new();
+        for (rule_index, (rule, value)) in self.rules.iter().zip(values).enumerate() {
+            let is_exceeding =
+                value.is_some_and(|value| rule.comparison.is_met(value, rule.threshold));
+
+            if !is_exceeding {
+                // The threshold has to be passed for the whole duration again to alert again.
+                self.exceeding_since[rule_index] = None;
+                if self.active[rule_index] {
+                    self.active[rule_index] = false;
+                    for alert in &mut self.alerts {
+                        if alert.rule_index == rule_index {
+                            alert.is_active = false;
+                        }
+                    }
+                    transitions.push(AlertTransition {
+                        rule_index,
+                        value: *value,
+                        is_firing: false,
+                    });
+                }
+                continue;
+            }
+
+            let since = *self.exceeding_since[rule_index].get_or_insert(now);
+            if now.duration_since(since) >= rule.duration && !self.active[rule_index] {
+                self.active[rule_index] = true;
+                let value = value.unwrap_or_default();
+                self.alerts.push(MetricAlert {
+                    rule_index,
+                    name: rule.name.clone(),
+                    severity: rule.severity,
+                    value_description: rule.metric.describe_value(value, &self.temperature_type),
+                    rule_description: rule.describe(&self.temperature_type),
+                    raised_time: current_time,
+                    is_active: true,
+                });
+                transitions.push(AlertTransition {
+                    rule_index,
+                    value: Some(value),
+                    is_firing: true,
+                });
+            }
+        }
+
+        if self.alerts.len() > MAX_METRIC_ALERTS {
+            self.alerts.drain(..self.alerts.len() - MAX_METRIC_ALERTS);
+        }
+
+        transitions
+    }
+
+    /// Returns the highest severity of the active alerts whose metrics widgets of `widget_type`
+    /// show, if there are any.
+    pub fn get_active_severity(&self, widget_type: &BottomWidgetType) -> Option<AlertSeverity> {
+        self.rules
+            .iter()
+            .zip(&self.active)
+            .filter(|(rule, is_active)| **is_active && rule.metric.is_shown_by(widget_type))
+            .map(|(rule, _is_active)| rule.severity)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_rule() -> MetricAlertRule {
+        MetricAlertRule {
+            name: "High CPU".to_string(),
+            metric: AlertMetric::Cpu,
+            comparison: AlertComparison::Above,
+            threshold: 90.0,
+            duration: Duration::from_secs(30),
+            severity: AlertSeverity::Critical,
+            notify: false,
+            command: None,
+        }
+    }
+
+    #[test]
+    fn test_metric_alerts() {
+        let mut metric_alerts = MetricAlerts::new(
+            vec![
+                get_test_rule(),
+                MetricAlertRule {
+                    name: "Low memory".to_string(),
+                    metric: AlertMetric::Memory,
+                    comparison: AlertComparison::AtMost,
+                    threshold: 10.0,
+                    duration: Duration::ZERO,
+                    severity: AlertSeverity::Info,
+                    notify: false,
+                    command: None,
+                },
+            ],
+            TemperatureType::Celsius,
+        );
+        let start = Instant::now();
+
+        // Nothing is raised until the threshold has been passed for the whole duration.
+        let values = [Some(95.0), Some(50.0)];
+        assert!(metric_alerts.update_at(&values, start, 1000).is_empty());
+        assert!(metric_alerts
+            .update_at(&values, start + Duration::from_secs(29), 1029)
+            .is_empty());
+        assert!(metric_alerts
+            .get_active_severity(&BottomWidgetType::Cpu)
+            .is_none());
+
+        assert_eq!(
+            metric_alerts.update_at(&values, start + Duration::from_secs(30), 1030),
+            vec![AlertTransition {
+                rule_index: 0,
+                value: Some(95.0),
+                is_firing: true,
+            }]
+        );
+        assert_eq!(metric_alerts.alerts.len(), 1);
+        assert_eq!(metric_alerts.alerts[0].value_description, "CPU 95.0%");
+        assert_eq!(metric_alerts.alerts[0].raised_time, 1030);
+        assert_eq!(
+            metric_alerts.get_active_severity(&BottomWidgetType::BasicCpu),
+            Some(AlertSeverity::Critical)
+        );
+        assert!(metric_alerts
+            .get_active_severity(&BottomWidgetType::Mem)
+            .is_none());
+
+        // Staying past the threshold does not raise it again, but a rule without a duration
+        // alerts at once.
+        assert_eq!(
+            metric_alerts.update_at(
+                &[Some(99.0), Some(10.0)],
+                start + Duration::from_secs(31),
+                1031
+            ),
+            vec![AlertTransition {
+                rule_index: 1,
+                value: Some(10.0),
+                is_firing: true,
+            }]
+        );
+        assert_eq!(metric_alerts.alerts.len(), 2);
+
+        // Missing data resolves an alert, and the duration starts over afterwards.
+        assert_eq!(
+            metric_alerts.update_at(&[None, Some(10.0)], start + Duration::from_secs(32), 1032),
+            vec![AlertTransition {
+                rule_index: 0,
+                value: None,
+                is_firing: false,
+            }]
+        );
+        assert!(!metric_alerts.alerts[0].is_active);
+        assert!(metric_alerts.alerts[1].is_active);
+
+        metric_alerts.update_at(
+            &[Some(95.0), Some(10.0)],
+            start + Duration::from_secs(33),
+            1033,
+        );
+        assert_eq!(metric_alerts.alerts.len(), 2);
+        metric_alerts.update_at(
+            &[Some(95.0), Some(10.0)],
+            start + Duration::from_secs(63),
+            1063,
+        );
+        assert_eq!(metric_alerts.alerts.len(), 3);
+    }
+
+    #[test]
+    fn test_metric_alert_rule_description() {
+        assert_eq!(
+            get_test_rule().describe(&TemperatureType::Celsius),
+            "CPU > 90.0% for 30s"
+        );
+
+        let rule = MetricAlertRule {
+            metric: AlertMetric::NetworkRx,
+            comparison: AlertComparison::AtLeast,
+            threshold: 100_000_000.0,
+            duration: Duration::ZERO,
+            ..get_test_rule()
+        };
+        assert_eq!(rule.describe(&TemperatureType::Celsius), "RX >= 100.0Mb/s");
+
+        let rule = MetricAlertRule {
+            metric: AlertMetric::Temperature,
+            threshold: 80.0,
+            ..rule
+        };
+        assert_eq!(
+            rule.describe(&TemperatureType::Fahrenheit),
+            "Temp >= 80.0°F"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Desktop notifications for alerts, so that they are noticed even when the terminal is not.  They
+//! are sent through what each OS has for it, being `notify-send` on Linux and the BSDs,
+//! `osascript` on macOS, and a PowerShell toast on Windows.
+
+use std::{
+    io,
+    process::{Command, Stdio},
+};
+
+use crate::app::metric_alerts::AlertSeverity;
+
+/// The environment variables that the title and body are passed in, which spares escaping them
+/// for the scripts on macOS and Windows.
+const TITLE_VARIABLE: &str = "BTM_NOTIFICATION_TITLE";
+const BODY_VARIABLE: &str = "BTM_NOTIFICATION_BODY";
+
+#[cfg(target_os = "windows")]
+const TOAST_SCRIPT: &str = "\
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+$template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+$texts = $template.GetElementsByTagName('text'); \
+$texts.Item(0).AppendChild($template.CreateTextNode($env:BTM_NOTIFICATION_TITLE)) > $null; \
+$texts.Item(1).AppendChild($template.CreateTextNode($env:BTM_NOTIFICATION_BODY)) > $null; \
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('bottom').Show([Windows.UI.Notifications.ToastNotification]::new($template))";
+
+/// Returns the command that shows a notification with `title` and `body`.
+fn get_notification_command(title: &str, body: &str, severity: AlertSeverity) -> Command {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "macos")] {
+            let _ = severity;
+            let mut command = Command::new("osascript");
+            command.args([
+                "-e",
+                "display notification (system attribute \"BTM_NOTIFICATION_BODY\") with title (system attribute \"BTM_NOTIFICATION_TITLE\")",
+            ]);
+        } else if #[cfg(target_os = "windows")] {
+            let _ = severity;
+            let mut command = Command::new("powershell");
+            command.args(["-NoProfile", "-NonInteractive", "-Command", TOAST_SCRIPT]);
+        } else {
+            let urgency = match severity {
+                AlertSeverity::Info => "low",
+                AlertSeverity::Warning => "normal",
+                AlertSeverity::Critical => "critical",
+            };
+            let mut command = Command::new("notify-send");
+            command.args(["--app-name=bottom", "--urgency", urgency, title, body]);
+        }
+    }
+
+    command
+        .env(TITLE_VARIABLE, title)
+        .env(BODY_VARIABLE, body)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+/// Shows a desktop notification with `title` and `body`, without waiting for it to be shown.
+pub fn send_notification(title: &str, body: &str, severity: AlertSeverity) -> io::Result<()> {
+    let mut command = get_notification_command(title, body, severity);
+    let mut child = command.spawn().map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!(
+                "unable to run {}, {}",
+                command.get_program().to_string_lossy(),
+                err
+            ),
+        )
+    })?;
+
+    // The notifier is waited on elsewhere, so that it is not left behind once it exits.
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[test]
+    fn test_notification_command() {
+        let command =
+            get_notification_command("bottom: High CPU", "CPU 95.0%", AlertSeverity::Critical);
+        assert_eq!(command.get_program(), "notify-send");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![
+                "--app-name=bottom",
+                "--urgency",
+                "critical",
+                "bottom: High CPU",
+                "CPU 95.0%"
+            ]
+        );
+        assert!(command
+            .get_envs()
+            .any(|(key, value)| key == BODY_VARIABLE && value == Some("CPU 95.0%".as_ref())));
+    }
+}
@@ -0,0 +1,735 @@
+// Copied from SO: https://stackoverflow.com/a/55231715
+#[cfg(target_os = "windows")]
+use winapi::{
+    shared::{
+        basetsd::DWORD_PTR,
+        minwindef::{DWORD, FALSE},
+        ntdef::HANDLE,
+    },
+    um::{
+        handleapi::CloseHandle,
+        processthreadsapi::{OpenProcess, TerminateProcess},
+        winbase::{GetProcessAffinityMask, SetProcessAffinityMask},
+        winnt::{PROCESS_QUERY_INFORMATION, PROCESS_SET_INFORMATION, PROCESS_TERMINATE},
+    },
+};
+
+/// This file is meant to house (OS specific) implementations on how to kill processes, and how to
+/// otherwise control them (suspending, priorities, CPU affinity).
+use std::collections::{HashMap, HashSet};
+
+#[cfg(target_os = "linux")]
+use crate::app::data_harvester::processes::IoPriority;
+use crate::app::data_harvester::processes::ProcessHarvest;
+use crate::utils::error::BottomError;
+use crate::Pid;
+
+#[cfg(target_os = "windows")]
+struct Process(HANDLE);
+
+#[cfg(target_os = "windows")]
+impl Process {
+    fn open(pid: DWORD) -> Result<Process, String> {
+        let pc = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_TERMINATE, 0, pid) };
+        if pc.is_null() {
+            return Err("OpenProcess".to_string());
+        }
+        Ok(Process(pc))
+    }
+
+    fn kill(self) -> Result<(), String> {
+        let result = unsafe { TerminateProcess(self.0, 1) };
+        if result == 0 {
+            return Err("Failed to kill process".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// An action that can be taken on the selected process(es) in the process widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessAction {
+    Kill,
+    Suspend,
+    Resume,
+    Renice,
+    SetIoPriority,
+}
+
+impl Default for ProcessAction {
+    fn default() -> Self {
+        ProcessAction::Kill
+    }
+}
+
+impl ProcessAction {
+    /// The text shown in the error dialog if this action failed.
+    pub fn failure_text(&self) -> &'static str {
+        match self {
+            ProcessAction::Kill => "Failed to kill process.",
+            ProcessAction::Suspend => "Failed to suspend process.",
+            ProcessAction::Resume => "Failed to resume process.",
+            ProcessAction::Renice => "Failed to change process priority.",
+            ProcessAction::SetIoPriority => "Failed to change process I/O priority.",
+        }
+    }
+}
+
+/// The signals that can be sent to a process, as pairs of their number and name.
+#[cfg(target_os = "linux")]
+pub const SIGNALS: &[(usize, &str)] = &[
+    (1, "HUP"),
+    (2, "INT"),
+    (3, "QUIT"),
+    (4, "ILL"),
+    (5, "TRAP"),
+    (6, "ABRT"),
+    (7, "BUS"),
+    (8, "FPE"),
+    (9, "KILL"),
+    (10, "USR1"),
+    (11, "SEGV"),
+    (12, "USR2"),
+    (13, "PIPE"),
+    (14, "ALRM"),
+    (15, "TERM"),
+    (16, "STKFLT"),
+    (17, "CHLD"),
+    (18, "CONT"),
+    (19, "STOP"),
+    (20, "TSTP"),
+    (21, "TTIN"),
+    (22, "TTOU"),
+    (23, "URG"),
+    (24, "XCPU"),
+    (25, "XFSZ"),
+    (26, "VTALRM"),
+    (27, "PROF"),
+    (28, "WINCH"),
+    (29, "IO"),
+    (30, "PWR"),
+    (31, "SYS"),
+    (34, "RTMIN"),
+    (35, "RTMIN+1"),
+    (36, "RTMIN+2"),
+    (37, "RTMIN+3"),
+    (38, "RTMIN+4"),
+    (39, "RTMIN+5"),
+    (40, "RTMIN+6"),
+    (41, "RTMIN+7"),
+    (42, "RTMIN+8"),
+    (43, "RTMIN+9"),
+    (44, "RTMIN+10"),
+    (45, "RTMIN+11"),
+    (46, "RTMIN+12"),
+    (47, "RTMIN+13"),
+    (48, "RTMIN+14"),
+    (49, "RTMIN+15"),
+    (50, "RTMAX-14"),
+    (51, "RTMAX-13"),
+    (52, "RTMAX-12"),
+    (53, "RTMAX-11"),
+    (54, "RTMAX-10"),
+    (55, "RTMAX-9"),
+    (56, "RTMAX-8"),
+    (57, "RTMAX-7"),
+    (58, "RTMAX-6"),
+    (59, "RTMAX-5"),
+    (60, "RTMAX-4"),
+    (61, "RTMAX-3"),
+    (62, "RTMAX-2"),
+    (63, "RTMAX-1"),
+    (64, "RTMAX"),
+];
+
+/// The signals that can be sent to a process, as pairs of their number and name.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+pub const SIGNALS: &[(usize, &str)] = &[
+    (1, "HUP"),
+    (2, "INT"),
+    (3, "QUIT"),
+    (4, "ILL"),
+    (5, "TRAP"),
+    (6, "ABRT"),
+    (7, "EMT"),
+    (8, "FPE"),
+    (9, "KILL"),
+    (10, "BUS"),
+    (11, "SEGV"),
+    (12, "SYS"),
+    (13, "PIPE"),
+    (14, "ALRM"),
+    (15, "TERM"),
+    (16, "URG"),
+    (17, "STOP"),
+    (18, "TSTP"),
+    (19, "CONT"),
+    (20, "CHLD"),
+    (21, "TTIN"),
+    (22, "TTOU"),
+    (23, "IO"),
+    (24, "XCPU"),
+    (25, "XFSZ"),
+    (26, "VTALRM"),
+    (27, "PROF"),
+    (28, "WINCH"),
+    (29, "INFO"),
+    (30, "USR1"),
+    (31, "USR2"),
+];
+
+/// Returns the signals whose name or number matches `query`, preceded by a "Cancel" entry with
+/// the number 0.  Names are matched case-insensitively, with or without the "SIG" prefix, and
+/// numbers are matched by prefix.  An empty query matches every signal.
+#[cfg(target_family = "unix")]
+pub fn get_matching_signals(query: &str) -> Vec<(usize, &'static str)> {
+    let query = query.trim().to_uppercase();
+    let query = query.strip_prefix("SIG").unwrap_or(&query);
+
+    std::iter::once((0, "Cancel"))
+        .chain(
+            SIGNALS.iter().copied().filter(|(number, name)| {
+                name.contains(query) || number.to_string().starts_with(query)
+            }),
+        )
+        .collect()
+}
+
+/// Describes a signal number like "SIGTERM (15)", falling back to just the number for signals
+/// without a name.
+#[cfg(target_family = "unix")]
+pub fn describe_signal(signal: usize) -> String {
+    match SIGNALS.iter().find(|(number, _name)| *number == signal) {
+        Some((_number, name)) => format!("SIG{} ({})", name, signal),
+        None => signal.to_string(),
+    }
+}
+
+/// Returns the given PIDs followed by all of their descendants in `processes`, ordered such that
+/// each process comes after its parent.
+pub fn get_process_tree_pids(processes: &[ProcessHarvest], roots: &[Pid]) -> Vec<Pid> {
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for process in processes {
+        if let Some(parent_pid) = process.parent_pid {
+            if parent_pid != process.pid {
+                children.entry(parent_pid).or_default().push(process.pid);
+            }
+        }
+    }
+
+    let mut tree_pids = roots.to_vec();
+    let mut seen = roots.iter().copied().collect::<HashSet<_>>();
+    let mut itx = 0;
+    while let Some(pid) = tree_pids.get(itx).copied() {
+        if let Some(child_pids) = children.get(&pid) {
+            for child_pid in child_pids {
+                if seen.insert(*child_pid) {
+                    tree_pids.push(*child_pid);
+                }
+            }
+        }
+        itx += 1;
+    }
+
+    tree_pids
+}
+
+/// Kills a process, given a PID, for unix.
+#[cfg(target_family = "unix")]
+pub fn kill_process_given_pid(pid: Pid, signal: usize) -> crate::utils::error::Result<()> {
+    let output = unsafe { libc::kill(pid as i32, signal as i32) };
+    if output != 0 {
+        // We had an error...
+        let err_code = std::io::Error::last_os_error().raw_os_error();
+        let err = match err_code {
+            Some(libc::ESRCH) => "the target process did not exist.",
+            Some(libc::EPERM) => "the calling process does not have the permissions to terminate the target process(es).",
+            Some(libc::EINVAL) => "an invalid signal was specified.",
+            _ => "Unknown error occurred."
+        };
+
+        let err = if let Some(err_code) = err_code {
+            format!("Error code {} - {}", err_code, err)
+        } else {
+            format!("Error code ??? - {}", err)
+        };
+
+        // Permission errors are kept separate so that killing can be retried with more privileges.
+        return if err_code == Some(libc::EPERM) {
+            Err(BottomError::PermissionError(err))
+        } else {
+            Err(BottomError::GenericError(err))
+        };
+    }
+
+    Ok(())
+}
+
+/// Kills processes by running `kill` through a privilege escalation helper command, such as
+/// `pkexec` or `sudo -n`, for unix.  The helper is run without a terminal, so it must be able to
+/// authenticate on its own, like `pkexec` does with a polkit agent.
+#[cfg(target_family = "unix")]
+pub fn kill_processes_with_helper(
+    helper: &str, pids: &[Pid], signal: usize,
+) -> crate::utils::error::Result<()> {
+    let mut helper_args = helper.split_whitespace();
+    let helper_program = helper_args.next().ok_or_else(|| {
+        BottomError::GenericError("No privilege helper command was set.".to_string())
+    })?;
+
+    let output = std::process::Command::new(helper_program)
+        .args(helper_args)
+        .arg("kill")
+        .arg(format!("-{}", signal))
+        .args(pids.iter().map(|pid| pid.to_string()))
+        .stdin(std::process::Stdio::null())
+        .output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = stderr.trim();
+        Err(BottomError::GenericError(if stderr.is_empty() {
+            format!("{} exited with {}.", helper_program, output.status)
+        } else {
+            stderr.to_string()
+        }))
+    }
+}
+
+/// Suspends a process by sending it `SIGSTOP`, given a PID, for unix.
+#[cfg(target_family = "unix")]
+pub fn suspend_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
+    kill_process_given_pid(pid, libc::SIGSTOP as usize)
+}
+
+/// Resumes a suspended process by sending it `SIGCONT`, given a PID, for unix.
+#[cfg(target_family = "unix")]
+pub fn resume_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
+    kill_process_given_pid(pid, libc::SIGCONT as usize)
+}
+
+/// The lowest and highest nice values a process can have.
+pub const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+#[cfg(target_os = "linux")]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno_location()
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__error()
+}
+
+/// Turns the last OS error from a `getpriority` or `setpriority` call into an error.
+#[cfg(target_family = "unix")]
+fn priority_error() -> BottomError {
+    let err_code = std::io::Error::last_os_error().raw_os_error();
+    let err = match err_code {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) => "the calling process does not have the permissions to change the priority of the target process(es).",
+        Some(libc::EACCES) => "lowering the nice value of a process requires elevated privileges.",
+        Some(libc::EINVAL) => "an invalid nice value was specified.",
+        _ => "Unknown error occurred.",
+    };
+
+    if let Some(err_code) = err_code {
+        BottomError::GenericError(format!("Error code {} - {}", err_code, err))
+    } else {
+        BottomError::GenericError(format!("Error code ??? - {}", err))
+    }
+}
+
+/// Gets the nice value of a process, given a PID, for unix.
+#[cfg(target_family = "unix")]
+pub fn get_process_priority(pid: Pid) -> crate::utils::error::Result<i32> {
+    // -1 is a valid nice value, so errno has to be cleared beforehand to tell it apart from an error.
+    let priority = unsafe {
+        *errno_location() = 0;
+        libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t)
+    };
+    if priority == -1 && std::io::Error::last_os_error().raw_os_error() != Some(0) {
+        return Err(priority_error());
+    }
+
+    Ok(priority)
+}
+
+/// Sets the nice value of a process, given a PID, for unix.
+#[cfg(target_family = "unix")]
+pub fn set_process_priority(pid: Pid, nice: i32) -> crate::utils::error::Result<()> {
+    let output = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+    if output != 0 {
+        return Err(priority_error());
+    }
+
+    Ok(())
+}
+
+/// Turns the last OS error from a `sched_getaffinity` or `sched_setaffinity` call into an error.
+#[cfg(target_os = "linux")]
+fn affinity_error() -> BottomError {
+    let err_code = std::io::Error::last_os_error().raw_os_error();
+    let err = match err_code {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) => "the calling process does not have the permissions to change the CPU affinity of the target process(es).",
+        Some(libc::EINVAL) => "none of the selected CPUs are available.",
+        _ => "Unknown error occurred.",
+    };
+
+    if let Some(err_code) = err_code {
+        BottomError::GenericError(format!("Error code {} - {}", err_code, err))
+    } else {
+        BottomError::GenericError(format!("Error code ??? - {}", err))
+    }
+}
+
+/// Gets which CPUs a process may run on, given a PID, for Linux.  The returned list has one entry
+/// per CPU.
+#[cfg(target_os = "linux")]
+pub fn get_process_affinity(pid: Pid) -> crate::utils::error::Result<Vec<bool>> {
+    let num_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) }.max(1) as usize;
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let output = unsafe {
+        libc::sched_getaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &mut cpu_set,
+        )
+    };
+    if output != 0 {
+        return Err(affinity_error());
+    }
+
+    Ok((0..num_cpus.min(libc::CPU_SETSIZE as usize))
+        .map(|cpu| unsafe { libc::CPU_ISSET(cpu, &cpu_set) })
+        .collect())
+}
+
+/// Sets which CPUs a process may run on, given a PID and one entry per CPU, for Linux.
+#[cfg(target_os = "linux")]
+pub fn set_process_affinity(pid: Pid, cpus: &[bool]) -> crate::utils::error::Result<()> {
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for (cpu, _) in cpus
+        .iter()
+        .enumerate()
+        .filter(|(_, is_allowed)| **is_allowed)
+    {
+        unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+    }
+
+    let output = unsafe {
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &cpu_set,
+        )
+    };
+    if output != 0 {
+        return Err(affinity_error());
+    }
+
+    Ok(())
+}
+
+/// See `include/uapi/linux/ioprio.h`; we only ever get and set the priority of a single process.
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+/// Turns the last OS error from an `ioprio_get` or `ioprio_set` call into an error.
+#[cfg(target_os = "linux")]
+fn io_priority_error() -> BottomError {
+    let err_code = std::io::Error::last_os_error().raw_os_error();
+    let err = match err_code {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) => "the calling process does not have the permissions to change the I/O priority of the target process(es).",
+        Some(libc::EINVAL) => "the I/O priority is not valid.",
+        _ => "Unknown error occurred.",
+    };
+
+    if let Some(err_code) = err_code {
+        BottomError::GenericError(format!("Error code {} - {}", err_code, err))
+    } else {
+        BottomError::GenericError(format!("Error code ??? - {}", err))
+    }
+}
+
+/// Gets the I/O scheduling class and priority of a process, given a PID, for Linux.
+#[cfg(target_os = "linux")]
+pub fn get_process_io_priority(pid: Pid) -> crate::utils::error::Result<IoPriority> {
+    let output = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid) };
+    if output == -1 {
+        return Err(io_priority_error());
+    }
+
+    Ok(IoPriority::from_raw(output as i32))
+}
+
+/// Sets the I/O scheduling class and priority of a process, given a PID, for Linux.
+#[cfg(target_os = "linux")]
+pub fn set_process_io_priority(pid: Pid, priority: IoPriority) -> crate::utils::error::Result<()> {
+    let output = unsafe {
+        libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            pid,
+            priority.to_raw(),
+        )
+    };
+    if output == -1 {
+        return Err(io_priority_error());
+    }
+
+    Ok(())
+}
+
+/// Setting CPU affinity is only supported on Linux and Windows.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+pub fn get_process_affinity(_pid: Pid) -> crate::utils::error::Result<Vec<bool>> {
+    Err(BottomError::GenericError(
+        "Changing process CPU affinity is not supported on this platform.".to_string(),
+    ))
+}
+
+/// Setting CPU affinity is only supported on Linux and Windows.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+pub fn set_process_affinity(_pid: Pid, _cpus: &[bool]) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Changing process CPU affinity is not supported on this platform.".to_string(),
+    ))
+}
+
+/// I/O priorities are only supported on Linux.
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_io_priority(
+    _pid: Pid,
+) -> crate::utils::error::Result<crate::app::data_harvester::processes::IoPriority> {
+    Err(BottomError::GenericError(
+        "Changing process I/O priorities is not supported on this platform.".to_string(),
+    ))
+}
+
+/// I/O priorities are only supported on Linux.
+#[cfg(not(target_os = "linux"))]
+pub fn set_process_io_priority(
+    _pid: Pid, _priority: crate::app::data_harvester::processes::IoPriority,
+) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Changing process I/O priorities is not supported on this platform.".to_string(),
+    ))
+}
+
+/// Suspending processes is not supported on windows.
+#[cfg(target_os = "windows")]
+pub fn suspend_process_given_pid(_pid: Pid) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Suspending processes is not supported on Windows.".to_string(),
+    ))
+}
+
+/// Resuming processes is not supported on windows.
+#[cfg(target_os = "windows")]
+pub fn resume_process_given_pid(_pid: Pid) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Resuming processes is not supported on Windows.".to_string(),
+    ))
+}
+
+/// Nice values are not supported on windows.
+#[cfg(target_os = "windows")]
+pub fn get_process_priority(_pid: Pid) -> crate::utils::error::Result<i32> {
+    Err(BottomError::GenericError(
+        "Changing process priorities is not supported on Windows.".to_string(),
+    ))
+}
+
+/// Nice values are not supported on windows.
+#[cfg(target_os = "windows")]
+pub fn set_process_priority(_pid: Pid, _nice: i32) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Changing process priorities is not supported on Windows.".to_string(),
+    ))
+}
+
+/// Runs `f` with a handle to a process that can be used to query and set its information, for
+/// windows.
+#[cfg(target_os = "windows")]
+fn with_process_info_handle<T>(
+    pid: Pid, f: impl FnOnce(HANDLE) -> crate::utils::error::Result<T>,
+) -> crate::utils::error::Result<T> {
+    let handle = unsafe {
+        OpenProcess(
+            PROCESS_QUERY_INFORMATION | PROCESS_SET_INFORMATION,
+            FALSE,
+            pid as DWORD,
+        )
+    };
+    if handle.is_null() {
+        return Err(BottomError::GenericError(format!(
+            "Could not open the process: {}",
+            std::io::Error::last_os_error()
+        )));
+    }
+
+    let result = f(handle);
+    unsafe { CloseHandle(handle) };
+    result
+}
+
+/// Gets which CPUs a process may run on, given a PID, for windows.  The returned list has one
+/// entry per CPU.
+#[cfg(target_os = "windows")]
+pub fn get_process_affinity(pid: Pid) -> crate::utils::error::Result<Vec<bool>> {
+    with_process_info_handle(pid, |handle| {
+        let mut process_mask: DWORD_PTR = 0;
+        let mut system_mask: DWORD_PTR = 0;
+        if unsafe { GetProcessAffinityMask(handle, &mut process_mask, &mut system_mask) } == 0 {
+            return Err(BottomError::GenericError(format!(
+                "Could not get the CPU affinity: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+
+        let num_cpus = (DWORD_PTR::BITS - system_mask.leading_zeros()) as usize;
+        Ok((0..num_cpus)
+            .map(|cpu| process_mask & (1 << cpu) != 0)
+            .collect())
+    })
+}
+
+/// Sets which CPUs a process may run on, given a PID and one entry per CPU, for windows.
+#[cfg(target_os = "windows")]
+pub fn set_process_affinity(pid: Pid, cpus: &[bool]) -> crate::utils::error::Result<()> {
+    let process_mask = cpus
+        .iter()
+        .enumerate()
+        .filter(|(_, is_allowed)| **is_allowed)
+        .fold(0 as DWORD_PTR, |mask, (cpu, _)| mask | (1 << cpu));
+
+    with_process_info_handle(pid, |handle| {
+        if unsafe { SetProcessAffinityMask(handle, process_mask) } == 0 {
+            return Err(BottomError::GenericError(format!(
+                "Could not set the CPU affinity: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+
+        Ok(())
+    })
+}
+
+/// Kills a process, given a PID, for windows.
+#[cfg(target_os = "windows")]
+pub fn kill_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
+    {
+        let process = Process::open(pid as DWORD)?;
+        process.kill()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_process_tree_pids() {
+        let processes = [
+            (1, None),
+            (2, Some(1)),
+            (3, Some(2)),
+            (4, Some(1)),
+            (5, Some(5)),
+        ]
+        .iter()
+        .map(|&(pid, parent_pid)| ProcessHarvest {
+            pid,
+            parent_pid,
+            ..ProcessHarvest::default()
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(get_process_tree_pids(&processes, &[1]), vec![1, 2, 4, 3]);
+        assert_eq!(get_process_tree_pids(&processes, &[2, 4]), vec![2, 4, 3]);
+        assert_eq!(get_process_tree_pids(&processes, &[5]), vec![5]);
+        assert_eq!(get_process_tree_pids(&processes, &[6]), vec![6]);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_process_priority() {
+        let pid = std::process::id() as Pid;
+        let nice = get_process_priority(pid).unwrap();
+        assert!(NICE_RANGE.contains(&nice));
+
+        // Setting the same nice value again never requires elevated privileges.
+        set_process_priority(pid, nice).unwrap();
+        assert_eq!(get_process_priority(pid).unwrap(), nice);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_process_affinity() {
+        let pid = std::process::id() as Pid;
+        let cpus = get_process_affinity(pid).unwrap();
+        assert!(cpus.iter().any(|is_allowed| *is_allowed));
+
+        set_process_affinity(pid, &cpus).unwrap();
+        assert_eq!(get_process_affinity(pid).unwrap(), cpus);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_process_io_priority() {
+        let pid = std::process::id() as Pid;
+        let priority = get_process_io_priority(pid).unwrap();
+
+        // Setting the same I/O priority again never requires elevated privileges, except for the
+        // real-time class.
+        if priority.class != crate::app::data_harvester::processes::IoPriorityClass::RealTime {
+            set_process_io_priority(pid, priority).unwrap();
+            assert_eq!(get_process_io_priority(pid).unwrap(), priority);
+        }
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_kill_processes_with_helper() {
+        // `env` just runs the command it is given, and signal 0 only checks that the process exists.
+        let pid = std::process::id() as Pid;
+        kill_processes_with_helper("env", &[pid], 0).unwrap();
+        assert!(kill_processes_with_helper("false", &[pid], 0).is_err());
+        assert!(kill_processes_with_helper("bottom-missing-helper", &[pid], 0).is_err());
+        assert!(kill_processes_with_helper("  ", &[pid], 0).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_get_matching_signals() {
+        assert_eq!(get_matching_signals("").len(), SIGNALS.len() + 1);
+        assert_eq!(
+            get_matching_signals("sigterm"),
+            vec![(0, "Cancel"), (15, "TERM")]
+        );
+        assert_eq!(
+            get_matching_signals("usr"),
+            vec![(0, "Cancel"), (10, "USR1"), (12, "USR2")]
+        );
+        assert_eq!(
+            get_matching_signals("64"),
+            vec![(0, "Cancel"), (64, "RTMAX")]
+        );
+        assert_eq!(get_matching_signals("nothing"), vec![(0, "Cancel")]);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_describe_signal() {
+        assert_eq!(describe_signal(15), "SIGTERM (15)");
+        assert_eq!(describe_signal(1000), "1000");
+    }
+}
@@ -0,0 +1,250 @@
+//! Watchdog rules for processes, which raise an alert when a matching process stays above a
+//! threshold for long enough.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+
+use crate::{
+    app::{data_harvester::processes::ProcessHarvest, metric_alerts::AlertSeverity},
+    Pid,
+};
+
+/// How many alerts are kept around for the alerts widget; the oldest are dropped first.
+const MAX_PROCESS_ALERTS: usize = 500;
+
+/// Identifies a process matching a rule, by the rule's index and the process' PID and start time,
+/// so that a reused PID is not mistaken for the original process.
+type AlertKey = (usize, Pid, Option<u64>);
+
+#[derive(Debug, Clone)]
+pub struct ProcessAlertRule {
+    /// The regex that the name of a process must match.
+    pub name: Regex,
+    /// The CPU usage percentage that a process must exceed, if any.
+    pub cpu_above: Option<f64>,
+    /// The memory usage percentage that a process must exceed, if any.
+    pub mem_above: Option<f64>,
+    /// How long the thresholds must be exceeded for before alerting.
+    pub duration: Duration,
+    pub severity: AlertSeverity,
+}
+
+impl ProcessAlertRule {
+    fn is_exceeded_by(&self, process: &ProcessHarvest) -> bool {
+        self.name.is_match(&process.name)
+            && self
+                .cpu_above
+                .map_or(true, |cpu_above| process.cpu_usage_percent > cpu_above)
+            && self
+                .mem_above
+                .map_or(true, |mem_above| process.mem_usage_percent > mem_above)
+    }
+
+    /// Describes the rule like "firefox: CPU > 90% for 30s".
+    pub fn describe(&self) -> String {
+        let mut thresholds = Vec::new();
+        if let Some(cpu_above) = self.cpu_above {
+            thresholds.push(format!("CPU > {}%", cpu_above));
+        }
+        if let Some(mem_above) = self.mem_above {
+            thresholds.push(format!("Mem > {}%", mem_above));
+        }
+
+        if self.duration.is_zero() {
+            format!("{}: {}", self.name, thresholds.join(" and "))
+        } else {
+            format!(
+                "{}: {} for {}s",
+                self.name,
+                thresholds.join(" and "),
+                self.duration.as_secs()
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessAlert {
+    key: AlertKey,
+    pub pid: Pid,
+    pub process_name: String,
+    pub rule_description: String,
+    pub severity: AlertSeverity,
+    /// The CPU and memory usage percentages of the process when the alert was raised.
+    pub cpu_usage_percent: f64,
+    pub mem_usage_percent: f64,
+    /// When the alert was raised, in seconds since the Unix epoch.
+    pub raised_time: u64,
+    /// Whether the process is still exceeding the thresholds of the rule.
+    pub is_active: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ProcessAlerts {
+    rules: Vec<ProcessAlertRule>,
+    /// When each process started exceeding the thresholds of each rule it matches.
+    exceeding_since: HashMap<AlertKey, Instant>,
+    active: HashSet<AlertKey>,
+    /// Every alert raised so far, oldest first.
+    pub alerts: Vec<ProcessAlert>,
+}
+
+impl ProcessAlerts {
+    pub fn new(rules: Vec<ProcessAlertRule>) -> Self {
+        ProcessAlerts {
+            rules,
+            ..ProcessAlerts::default()
+        }
+    }
+
+    pub fn has_rules(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// Checks the rules against the latest process harvest, raising and resolving alerts.
+    pub fn update(&mut self, processes: &[ProcessHarvest]) {
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        self.update_at(processes, Instant::now(), current_time);
+    }
+
+    fn update_at(&mut self, processes: &[ProcessHarvest], now: Instant, current_time: u64) {
+        if self.rules.is_empty() {
+            return;
+        }
+
+        let mut exceeding = HashSet::new();
+        for process in processes {
+            for (rule_index, rule) in self.rules.iter().enumerate() {
+                if !rule.is_exceeded_by(process) {
+                    continue;
+                }
+
+                let key = (rule_index, process.pid, process.start_time);
+                exceeding.insert(key);
+                let since = *self.exceeding_since.entry(key).or_insert(now);
+                if now.duration_since(since) >= rule.duration && self.active.insert(key) {
+                    self.alerts.push(ProcessAlert {
+                        key,
+                        pid: process.pid,
+                        process_name: process.name.clone(),
+                        rule_description: rule.describe(),
+                        severity: rule.severity,
+                        cpu_usage_percent: process.cpu_usage_percent,
+                        mem_usage_percent: process.mem_usage_percent,
+                        raised_time: current_time,
+                        is_active: true,
+                    });
+                }
+            }
+        }
+
+        // Anything no longer exceeding its rule, including processes that have exited, has to
+        // exceed it for the whole duration again to alert again.
+        self.exceeding_since
+            .retain(|key, _since| exceeding.contains(key));
+        self.active.retain(|key| exceeding.contains(key));
+        for alert in &mut self.alerts {
+            if alert.is_active && !exceeding.contains(&alert.key) {
+                alert.is_active = false;
+            }
+        }
+
+        if self.alerts.len() > MAX_PROCESS_ALERTS {
+            self.alerts.drain(..self.alerts.len() - MAX_PROCESS_ALERTS);
+        }
+    }
+
+    /// Returns whether the process with `pid` currently has an active alert.
+    pub fn is_alerting(&self, pid: Pid) -> bool {
+        self.active
+            .iter()
+            .any(|(_rule_index, alert_pid, _start_time)| *alert_pid == pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: Pid, name: &str, cpu_usage_percent: f64) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            name: name.to_string(),
+            cpu_usage_percent,
+            start_time: Some(100),
+            ..ProcessHarvest::default()
+        }
+    }
+
+    #[test]
+    fn test_process_alerts() {
+        let mut process_alerts = ProcessAlerts::new(vec![ProcessAlertRule {
+            name: Regex::new("^firefox$").unwrap(),
+            cpu_above: Some(90.0),
+            mem_above: None,
+            duration: Duration::from_secs(30),
+            severity: AlertSeverity::Warning,
+        }]);
+        let start = Instant::now();
+
+        // Nothing is raised until the rule has been exceeded for its whole duration.
+        let processes = vec![process(1, "firefox", 95.0), process(2, "bash", 99.0)];
+        process_alerts.update_at(&processes, start, 1000);
+        process_alerts.update_at(&processes, start + Duration::from_secs(29), 1029);
+        assert!(process_alerts.alerts.is_empty());
+        assert!(!process_alerts.is_alerting(1));
+
+        process_alerts.update_at(&processes, start + Duration::from_secs(30), 1030);
+        assert_eq!(process_alerts.alerts.len(), 1);
+        assert_eq!(process_alerts.alerts[0].pid, 1);
+        assert_eq!(process_alerts.alerts[0].raised_time, 1030);
+        assert!(process_alerts.is_alerting(1));
+        assert!(!process_alerts.is_alerting(2));
+
+        // Staying over the threshold does not raise it again.
+        process_alerts.update_at(&processes, start + Duration::from_secs(60), 1060);
+        assert_eq!(process_alerts.alerts.len(), 1);
+
+        // Dropping below it resolves the alert, and the duration starts over afterwards.
+        let calm_processes = vec![process(1, "firefox", 10.0)];
+        process_alerts.update_at(&calm_processes, start + Duration::from_secs(61), 1061);
+        assert!(!process_alerts.alerts[0].is_active);
+        assert!(!process_alerts.is_alerting(1));
+
+        process_alerts.update_at(&processes, start + Duration::from_secs(62), 1062);
+        assert_eq!(process_alerts.alerts.len(), 1);
+        process_alerts.update_at(&processes, start + Duration::from_secs(92), 1092);
+        assert_eq!(process_alerts.alerts.len(), 2);
+        assert!(process_alerts.alerts[1].is_active);
+    }
+
+    #[test]
+    fn test_process_alert_rule_description() {
+        let rule = ProcessAlertRule {
+            name: Regex::new("firefox").unwrap(),
+            cpu_above: Some(90.0),
+            mem_above: Some(50.5),
+            duration: Duration::from_secs(30),
+            severity: AlertSeverity::Warning,
+        };
+        assert_eq!(
+            rule.describe(),
+            "firefox: CPU > 90% and Mem > 50.5% for 30s"
+        );
+
+        let rule = ProcessAlertRule {
+            duration: Duration::ZERO,
+            mem_above: None,
+            ..rule
+        };
+        assert_eq!(rule.describe(), "firefox: CPU > 90%");
+    }
+}
@@ -1,4 +1,4 @@
-use super::ProcWidgetState;
+use super::ProcessSearchState;
 use crate::{
     data_conversion::ConvertedProcessData,
     utils::error::{
@@ -7,12 +7,14 @@ use crate::{
     },
 };
 use std::fmt::Debug;
-use std::{borrow::Cow, collections::VecDeque};
+use std::{borrow::Cow, collections::VecDeque, ops::Range};
 
 const DELIMITER_LIST: [char; 6] = ['=', '>', '<', '(', ')', '\"'];
 const COMPARISON_LIST: [&str; 3] = [">", "=", "<"];
 const OR_LIST: [&str; 2] = ["or", "||"];
 const AND_LIST: [&str; 2] = ["and", "&&"];
+/// Prefixes that can also be written as `prefix:value`, like `user:root`.
+const COLON_PREFIX_LIST: [&str; 6] = ["pid", "user", "group", "state", "label", "container"];
 
 /// I only separated this as otherwise, the states.rs file gets huge... and this should
 /// belong in another file anyways, IMO.
@@ -25,13 +27,23 @@ pub trait ProcessQuery {
     ///   rather than a prefix.
     /// - PIDs: Use prefix `pid`, can use regex or match word (case is irrelevant).
     /// - CPU: Use prefix `cpu`, cannot use r/m/c (regex, match word, case).  Can compare.
-    /// - MEM: Use prefix `mem`, cannot use r/m/c.  Can compare.
+    /// - MEM: Use prefix `mem`, cannot use r/m/c.  Can compare, in bytes if given a byte unit.
     /// - STATE: Use prefix `state`, can use regex, match word, or case.
     /// - USER: Use prefix `user`, can use regex, match word, or case.
+    /// - GROUP: Use prefix `group`, can use regex, match word, or case.
+    /// - CONTAINER: Use prefix `container`, can use regex, match word, or case.
     /// - Read/s: Use prefix `r`.  Can compare.
     /// - Write/s: Use prefix `w`.  Can compare.
     /// - Total read: Use prefix `read`.  Can compare.
     /// - Total write: Use prefix `write`.  Can compare.
+    /// - Runtime: Use prefix `runtime`.  Can compare, with durations like `10m` or `1h30m`.
+    /// - CPU time: Use prefix `cputime`.  Can compare, with durations.
+    /// - GPU: Use prefix `gpu`.  Can compare.
+    /// - Open FDs: Use prefix `fds`.  Can compare.
+    ///
+    /// Units can be attached to values, like `1.5gb` or `50%`, or be separate, like `1.5 gb`.
+    ///
+    /// String prefixes (PID, state, user, and group) can also be written with a colon, like `user:root`.
     ///
     /// For queries, whitespaces are our delimiters.  We will merge together any adjacent non-prefixed
     /// or quoted elements after splitting to treat as process names.
@@ -39,7 +51,7 @@ pub trait ProcessQuery {
     fn parse_query(&self) -> Result<Query>;
 }
 
-impl ProcessQuery for ProcWidgetState {
+impl ProcessQuery for ProcessSearchState {
     fn parse_query(&self) -> Result<Query> {
         fn process_string_to_filter(query: &mut VecDeque<String>) -> Result<Query> {
             let lhs = process_or(query)?;
@@ -257,7 +269,12 @@ impl ProcessQuery for ProcWidgetState {
                                     compare_prefix: None,
                                 })
                             }
-                            PrefixType::Pid | PrefixType::State | PrefixType::User => {
+                            PrefixType::Pid
+                            | PrefixType::State
+                            | PrefixType::User
+                            | PrefixType::Group
+                            | PrefixType::Label
+                            | PrefixType::Container => {
                                 // We have to check if someone put an "="...
                                 if content == "=" {
                                     // Check next string if possible
@@ -295,12 +312,12 @@ impl ProcessQuery for ProcWidgetState {
                                 // Now we gotta parse the content... yay.
 
                                 let mut condition: Option<QueryComparison> = None;
-                                let mut value: Option<f64> = None;
+                                let mut value: Option<String> = None;
 
                                 if content == "=" {
                                     condition = Some(QueryComparison::Equal);
                                     if let Some(queue_next) = query.pop_front() {
-                                        value = queue_next.parse::<f64>().ok();
+                                        value = Some(queue_next);
                                     } else {
                                         return Err(QueryError("Missing value".into()));
                                     }
@@ -314,7 +331,7 @@ impl ProcessQuery for ProcWidgetState {
                                                 QueryComparison::LessOrEqual
                                             });
                                             if let Some(queue_next_next) = query.pop_front() {
-                                                value = queue_next_next.parse::<f64>().ok();
+                                                value = Some(queue_next_next);
                                             } else {
                                                 return Err(QueryError("Missing value".into()));
                                             }
@@ -324,83 +341,25 @@ impl ProcessQuery for ProcWidgetState {
                                             } else {
                                                 QueryComparison::Less
                                             });
-                                            value = queue_next.parse::<f64>().ok();
+                                            value = Some(queue_next);
                                         }
                                     } else {
                                         return Err(QueryError("Missing value".into()));
                                     }
                                 }
 
-                                if let Some(condition) = condition {
-                                    if let Some(read_value) = value {
-                                        // Now we want to check one last thing - is there a unit?
-                                        // If no unit, assume base.
-                                        // Furthermore, base must be PEEKED at initially, and will
-                                        // require (likely) prefix_type specific checks
-                                        // Lastly, if it *is* a unit, remember to POP!
-
-                                        let mut value = read_value;
-
-                                        match prefix_type {
-                                            PrefixType::MemBytes
-                                            | PrefixType::Rps
-                                            | PrefixType::Wps
-                                            | PrefixType::TRead
-                                            | PrefixType::TWrite => {
-                                                if let Some(potential_unit) = query.front() {
-                                                    match potential_unit.to_lowercase().as_str() {
-                                                        "tb" => {
-                                                            value *= 1_000_000_000_000.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "tib" => {
-                                                            value *= 1_099_511_627_776.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "gb" => {
-                                                            value *= 1_000_000_000.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "gib" => {
-                                                            value *= 1_073_741_824.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "mb" => {
-                                                            value *= 1_000_000.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "mib" => {
-                                                            value *= 1_048_576.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "kb" => {
-                                                            value *= 1000.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "kib" => {
-                                                            value *= 1024.0;
-                                                            query.pop_front();
-                                                        }
-                                                        "b" => {
-                                                            // Just gotta pop.
-                                                            query.pop_front();
-                                                        }
-                                                        _ => {}
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
+                                if let (Some(condition), Some(value)) = (condition, value) {
+                                    let (prefix_type, value) =
+                                        parse_comparison_value(prefix_type, &value, query)?;
 
-                                        return Ok(Prefix {
-                                            or: None,
-                                            regex_prefix: None,
-                                            compare_prefix: Some((
-                                                prefix_type,
-                                                NumericalQuery { condition, value },
-                                            )),
-                                        });
-                                    }
+                                    return Ok(Prefix {
+                                        or: None,
+                                        regex_prefix: None,
+                                        compare_prefix: Some((
+                                            prefix_type,
+                                            NumericalQuery { condition, value },
+                                        )),
+                                    });
                                 }
                             }
                         }
@@ -418,9 +377,22 @@ impl ProcessQuery for ProcWidgetState {
 
         let mut split_query = VecDeque::new();
 
-        self.get_current_search_query()
+        self.search_state
+            .current_search_query
             .split_whitespace()
             .for_each(|s| {
+                // Treat `prefix:value` as `prefix = value`.
+                let s = match s.split_once(':') {
+                    Some((prefix, value))
+                        if COLON_PREFIX_LIST.contains(&prefix.to_lowercase().as_str()) =>
+                    {
+                        split_query.push_back(prefix.to_owned());
+                        split_query.push_back("=".to_owned());
+                        value
+                    }
+                    _ => s,
+                };
+
                 // From https://stackoverflow.com/a/56923739 in order to get a split but include the parentheses
                 let mut last = 0;
                 for (index, matched) in s.match_indices(|x| DELIMITER_LIST.contains(&x)) {
@@ -437,15 +409,141 @@ impl ProcessQuery for ProcWidgetState {
 
         let mut process_filter = process_string_to_filter(&mut split_query)?;
         process_filter.process_regexes(
-            self.process_search_state.is_searching_whole_word,
-            self.process_search_state.is_ignoring_case,
-            self.process_search_state.is_searching_with_regex,
+            self.is_searching_whole_word,
+            self.is_ignoring_case,
+            self.is_searching_with_regex,
+            self.is_searching_with_fuzzy,
         )?;
 
         Ok(process_filter)
     }
 }
 
+/// Parses the value of a comparison, along with its unit, which may be attached to it like
+/// `1.5gb` or be the next string like `1.5 gb`.  Byte sizes are converted to bytes and durations
+/// to seconds.
+///
+/// As `mem` compares percentages, giving it a byte size compares the memory in bytes instead.
+fn parse_comparison_value(
+    prefix_type: PrefixType, value: &str, query: &mut VecDeque<String>,
+) -> Result<(PrefixType, f64)> {
+    let unit_start = value
+        .find(|c: char| c.is_alphabetic() || c == '%')
+        .unwrap_or(value.len());
+    let (number, attached_unit) = value.split_at(unit_start);
+    let number = number
+        .parse::<f64>()
+        .map_err(|_| QueryError(format!("Invalid value '{}'", value).into()))?;
+
+    // Only take the next string as the unit if it is one that this prefix can use.
+    let mut take_unit = |is_unit: &dyn Fn(&str) -> bool| {
+        if !attached_unit.is_empty() {
+            Some(attached_unit.to_lowercase())
+        } else if query
+            .front()
+            .map_or(false, |next| is_unit(&next.to_lowercase()))
+        {
+            query.pop_front().map(|unit| unit.to_lowercase())
+        } else {
+            None
+        }
+    };
+    let invalid_unit = |unit: &str| QueryError(format!("Invalid unit '{}'", unit).into());
+
+    match prefix_type {
+        PrefixType::Runtime | PrefixType::CpuTime => {
+            if attached_unit.is_empty() {
+                let multiplier = take_unit(&|unit| duration_multiplier(unit).is_some())
+                    .and_then(|unit| duration_multiplier(&unit))
+                    .unwrap_or(1.0);
+                Ok((prefix_type, number * multiplier))
+            } else {
+                // Durations can be written in parts, like `1h30m`.
+                parse_duration(value)
+                    .map(|seconds| (prefix_type, seconds))
+                    .ok_or_else(|| invalid_unit(attached_unit))
+            }
+        }
+        PrefixType::MemBytes
+        | PrefixType::Rps
+        | PrefixType::Wps
+        | PrefixType::TRead
+        | PrefixType::TWrite
+        | PrefixType::PMem => match take_unit(&|unit| byte_multiplier(unit).is_some()) {
+            None => Ok((prefix_type, number)),
+            Some(unit) if unit == "%" && matches!(prefix_type, PrefixType::PMem) => {
+                Ok((prefix_type, number))
+            }
+            Some(unit) => {
+                let multiplier = byte_multiplier(&unit).ok_or_else(|| invalid_unit(&unit))?;
+                if let PrefixType::PMem = prefix_type {
+                    Ok((PrefixType::MemBytes, number * multiplier))
+                } else {
+                    Ok((prefix_type, number * multiplier))
+                }
+            }
+        },
+        PrefixType::PCpu | PrefixType::PGpu => match attached_unit {
+            "" | "%" => Ok((prefix_type, number)),
+            unit => Err(invalid_unit(unit)),
+        },
+        _ => {
+            if attached_unit.is_empty() {
+                Ok((prefix_type, number))
+            } else {
+                Err(invalid_unit(attached_unit))
+            }
+        }
+    }
+}
+
+/// Returns how many bytes a (lowercase) unit is.
+fn byte_multiplier(unit: &str) -> Option<f64> {
+    match unit {
+        "b" => Some(1.0),
+        "kb" => Some(1000.0),
+        "kib" => Some(1024.0),
+        "mb" => Some(1_000_000.0),
+        "mib" => Some(1_048_576.0),
+        "gb" => Some(1_000_000_000.0),
+        "gib" => Some(1_073_741_824.0),
+        "tb" => Some(1_000_000_000_000.0),
+        "tib" => Some(1_099_511_627_776.0),
+        _ => None,
+    }
+}
+
+/// Returns how many seconds a (lowercase) unit is.
+fn duration_multiplier(unit: &str) -> Option<f64> {
+    match unit {
+        "s" | "sec" | "secs" => Some(1.0),
+        "m" | "min" | "mins" => Some(60.0),
+        "h" | "hr" | "hrs" => Some(60.0 * 60.0),
+        "d" | "day" | "days" => Some(24.0 * 60.0 * 60.0),
+        _ => None,
+    }
+}
+
+/// Parses a duration made up of numbers each followed by a unit, like `10m` or `1h30m`, into
+/// seconds.
+fn parse_duration(duration: &str) -> Option<f64> {
+    let duration = duration.to_lowercase();
+    let mut seconds = 0.0;
+    let mut rest = duration.as_str();
+    while !rest.is_empty() {
+        let unit_start = rest.find(|c: char| c.is_alphabetic())?;
+        let unit_end = rest[unit_start..]
+            .find(|c: char| !c.is_alphabetic())
+            .map_or(rest.len(), |unit_len| unit_start + unit_len);
+
+        let number = rest[..unit_start].parse::<f64>().ok()?;
+        seconds += number * duration_multiplier(&rest[unit_start..unit_end])?;
+        rest = &rest[unit_end..];
+    }
+
+    Some(seconds)
+}
+
 pub struct Query {
     /// Remember, AND > OR, but AND must come after OR when we parse.
     pub query: Vec<Or>,
@@ -454,13 +552,14 @@ pub struct Query {
 impl Query {
     pub fn process_regexes(
         &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        is_searching_with_regex: bool, is_searching_with_fuzzy: bool,
     ) -> Result<()> {
         for or in &mut self.query {
             or.process_regexes(
                 is_searching_whole_word,
                 is_ignoring_case,
                 is_searching_with_regex,
+                is_searching_with_fuzzy,
             )?;
         }
 
@@ -472,6 +571,34 @@ impl Query {
             .iter()
             .all(|ok| ok.check(process, is_using_command))
     }
+
+    /// Returns how well the fuzzy parts of the query match the process, where higher is better.
+    pub fn fuzzy_score(&self, process: &ConvertedProcessData, is_using_command: bool) -> i64 {
+        self.query
+            .iter()
+            .filter_map(|or| or.fuzzy_score(process, is_using_command))
+            .sum()
+    }
+
+    /// Returns the byte ranges of the given name or command that are matched by the query, sorted
+    /// and without overlaps.
+    pub fn matched_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        for or in &self.query {
+            or.matched_ranges(text, &mut ranges);
+        }
+
+        ranges.sort_by_key(|range| range.start);
+        let mut merged_ranges: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged_ranges.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged_ranges.push(range),
+            }
+        }
+
+        merged_ranges
+    }
 }
 
 impl Debug for Query {
@@ -489,18 +616,20 @@ pub struct Or {
 impl Or {
     pub fn process_regexes(
         &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        is_searching_with_regex: bool, is_searching_with_fuzzy: bool,
     ) -> Result<()> {
         self.lhs.process_regexes(
             is_searching_whole_word,
             is_ignoring_case,
             is_searching_with_regex,
+            is_searching_with_fuzzy,
         )?;
         if let Some(rhs) = &mut self.rhs {
             rhs.process_regexes(
                 is_searching_whole_word,
                 is_ignoring_case,
                 is_searching_with_regex,
+                is_searching_with_fuzzy,
             )?;
         }
 
@@ -514,6 +643,25 @@ impl Or {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub fn fuzzy_score(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<i64> {
+        let lhs_score = self.lhs.fuzzy_score(process, is_using_command);
+        let rhs_score = self
+            .rhs
+            .as_ref()
+            .and_then(|rhs| rhs.fuzzy_score(process, is_using_command));
+
+        lhs_score.max(rhs_score)
+    }
+
+    fn matched_ranges(&self, text: &str, ranges: &mut Vec<Range<usize>>) {
+        self.lhs.matched_ranges(text, ranges);
+        if let Some(rhs) = &self.rhs {
+            rhs.matched_ranges(text, ranges);
+        }
+    }
 }
 
 impl Debug for Or {
@@ -534,18 +682,20 @@ pub struct And {
 impl And {
     pub fn process_regexes(
         &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        is_searching_with_regex: bool, is_searching_with_fuzzy: bool,
     ) -> Result<()> {
         self.lhs.process_regexes(
             is_searching_whole_word,
             is_ignoring_case,
             is_searching_with_regex,
+            is_searching_with_fuzzy,
         )?;
         if let Some(rhs) = &mut self.rhs {
             rhs.process_regexes(
                 is_searching_whole_word,
                 is_ignoring_case,
                 is_searching_with_regex,
+                is_searching_with_fuzzy,
             )?;
         }
 
@@ -559,6 +709,28 @@ impl And {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub fn fuzzy_score(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<i64> {
+        let lhs_score = self.lhs.fuzzy_score(process, is_using_command);
+        let rhs_score = self
+            .rhs
+            .as_ref()
+            .and_then(|rhs| rhs.fuzzy_score(process, is_using_command));
+
+        match (lhs_score, rhs_score) {
+            (Some(lhs_score), Some(rhs_score)) => Some(lhs_score + rhs_score),
+            (lhs_score, rhs_score) => lhs_score.or(rhs_score),
+        }
+    }
+
+    fn matched_ranges(&self, text: &str, ranges: &mut Vec<Range<usize>>) {
+        self.lhs.matched_ranges(text, ranges);
+        if let Some(rhs) = &self.rhs {
+            rhs.matched_ranges(text, ranges);
+        }
+    }
 }
 
 impl Debug for And {
@@ -583,6 +755,13 @@ pub enum PrefixType {
     Name,
     State,
     User,
+    Group,
+    Label,
+    Container,
+    Runtime,
+    CpuTime,
+    PGpu,
+    OpenFds,
     __Nonexhaustive,
 }
 
@@ -606,6 +785,13 @@ impl std::str::FromStr for PrefixType {
             "pid" => Ok(Pid),
             "state" => Ok(State),
             "user" => Ok(User),
+            "group" => Ok(Group),
+            "label" => Ok(Label),
+            "container" => Ok(Container),
+            "runtime" => Ok(Runtime),
+            "cputime" | "time+" => Ok(CpuTime),
+            "gpu" | "gpu%" => Ok(PGpu),
+            "fds" => Ok(OpenFds),
             _ => Ok(Name),
         }
     }
@@ -621,18 +807,47 @@ pub struct Prefix {
 impl Prefix {
     pub fn process_regexes(
         &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
-        is_searching_with_regex: bool,
+        is_searching_with_regex: bool, is_searching_with_fuzzy: bool,
     ) -> Result<()> {
         if let Some(or) = &mut self.or {
             return or.process_regexes(
                 is_searching_whole_word,
                 is_ignoring_case,
                 is_searching_with_regex,
+                is_searching_with_fuzzy,
             );
         } else if let Some((prefix_type, StringQuery::Value(regex_string))) = &mut self.regex_prefix
         {
             match prefix_type {
-                PrefixType::Pid | PrefixType::Name | PrefixType::State | PrefixType::User => {
+                // Fuzzy matching takes over from whole word and regex matching, but PIDs are
+                // still matched as usual as there is little point in fuzzily matching numbers.
+                PrefixType::Name
+                | PrefixType::State
+                | PrefixType::User
+                | PrefixType::Group
+                | PrefixType::Label
+                | PrefixType::Container
+                    if is_searching_with_fuzzy =>
+                {
+                    let pattern = std::mem::take(regex_string);
+                    let taken_pwc = self.regex_prefix.take();
+                    if let Some((taken_pt, _)) = taken_pwc {
+                        self.regex_prefix = Some((
+                            taken_pt,
+                            StringQuery::Fuzzy {
+                                pattern,
+                                is_ignoring_case,
+                            },
+                        ));
+                    }
+                }
+                PrefixType::Pid
+                | PrefixType::Name
+                | PrefixType::State
+                | PrefixType::User
+                | PrefixType::Group
+                | PrefixType::Label
+                | PrefixType::Container => {
                     let escaped_regex: String;
                     let final_regex_string = &format!(
                         "{}{}{}{}",
@@ -675,6 +890,8 @@ impl Prefix {
 
         if let Some(and) = &self.or {
             and.check(process, is_using_command)
+        } else if let Some((_, StringQuery::Fuzzy { .. })) = &self.regex_prefix {
+            self.fuzzy_score(process, is_using_command).is_some()
         } else if let Some((prefix_type, query_content)) = &self.regex_prefix {
             if let StringQuery::Regex(r) = query_content {
                 match prefix_type {
@@ -692,6 +909,27 @@ impl Prefix {
                             false
                         }
                     }
+                    PrefixType::Group => {
+                        if let Some(group) = &process.group {
+                            r.is_match(group.as_str())
+                        } else {
+                            false
+                        }
+                    }
+                    PrefixType::Label => {
+                        if let Some(security_label) = &process.security_label {
+                            r.is_match(security_label.as_str())
+                        } else {
+                            false
+                        }
+                    }
+                    PrefixType::Container => {
+                        if let Some(container) = &process.container {
+                            r.is_match(container.as_str())
+                        } else {
+                            false
+                        }
+                    }
                     _ => true,
                 }
             } else {
@@ -734,6 +972,40 @@ impl Prefix {
                     process.tw_f64,
                     numerical_query.value,
                 ),
+                PrefixType::Runtime => process.start_time.map_or(false, |start_time| {
+                    let current_time = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|duration| duration.as_secs())
+                        .unwrap_or(0);
+                    matches_condition(
+                        &numerical_query.condition,
+                        current_time.saturating_sub(start_time) as f64,
+                        numerical_query.value,
+                    )
+                }),
+                PrefixType::CpuTime => process.cpu_time.map_or(false, |cpu_time| {
+                    matches_condition(
+                        &numerical_query.condition,
+                        cpu_time.as_secs_f64(),
+                        numerical_query.value,
+                    )
+                }),
+                PrefixType::PGpu => process
+                    .gpu_percent_usage
+                    .map_or(false, |gpu_percent_usage| {
+                        matches_condition(
+                            &numerical_query.condition,
+                            gpu_percent_usage,
+                            numerical_query.value,
+                        )
+                    }),
+                PrefixType::OpenFds => process.open_fds.map_or(false, |open_fds| {
+                    matches_condition(
+                        &numerical_query.condition,
+                        open_fds as f64,
+                        numerical_query.value,
+                    )
+                }),
                 _ => true,
             }
         } else {
@@ -741,6 +1013,72 @@ impl Prefix {
             true
         }
     }
+
+    /// Returns how well a fuzzy prefix matches the process, or `None` if it does not match or is
+    /// not a fuzzy prefix.
+    pub fn fuzzy_score(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<i64> {
+        if let Some(or) = &self.or {
+            return or.fuzzy_score(process, is_using_command);
+        }
+
+        if let Some((
+            prefix_type,
+            StringQuery::Fuzzy {
+                pattern,
+                is_ignoring_case,
+            },
+        )) = &self.regex_prefix
+        {
+            let text = match prefix_type {
+                PrefixType::Name => Some(if is_using_command {
+                    process.command.as_str()
+                } else {
+                    process.name.as_str()
+                }),
+                PrefixType::State => Some(process.process_state.as_str()),
+                PrefixType::User => process.user.as_deref(),
+                PrefixType::Group => process.group.as_deref(),
+                PrefixType::Label => process.security_label.as_deref(),
+                PrefixType::Container => process.container.as_deref(),
+                _ => None,
+            };
+
+            text.and_then(|text| fuzzy_match(pattern, text, *is_ignoring_case))
+                .map(|(score, _indices)| score)
+        } else {
+            None
+        }
+    }
+
+    fn matched_ranges(&self, text: &str, ranges: &mut Vec<Range<usize>>) {
+        if let Some(or) = &self.or {
+            or.matched_ranges(text, ranges);
+        } else if let Some((PrefixType::Name, query_content)) = &self.regex_prefix {
+            match query_content {
+                StringQuery::Regex(r) => ranges.extend(
+                    r.find_iter(text)
+                        .filter(|found| !found.range().is_empty())
+                        .map(|found| found.range()),
+                ),
+                StringQuery::Fuzzy {
+                    pattern,
+                    is_ignoring_case,
+                } => {
+                    if let Some((_score, indices)) = fuzzy_match(pattern, text, *is_ignoring_case) {
+                        ranges.extend(
+                            text.char_indices()
+                                .enumerate()
+                                .filter(|(index, _)| indices.contains(index))
+                                .map(|(_, (byte_index, c))| byte_index..byte_index + c.len_utf8()),
+                        );
+                    }
+                }
+                StringQuery::Value(_) => {}
+            }
+        }
+    }
 }
 
 impl Debug for Prefix {
@@ -770,6 +1108,10 @@ pub enum QueryComparison {
 pub enum StringQuery {
     Value(String),
     Regex(regex::Regex),
+    Fuzzy {
+        pattern: String,
+        is_ignoring_case: bool,
+    },
 }
 
 #[derive(Debug)]
@@ -777,3 +1119,241 @@ pub struct NumericalQuery {
     pub condition: QueryComparison,
     pub value: f64,
 }
+
+const FUZZY_SCORE_MATCH: i64 = 16;
+const FUZZY_BONUS_BOUNDARY: i64 = 8;
+const FUZZY_BONUS_CONSECUTIVE: i64 = 4;
+const FUZZY_PENALTY_GAP_START: i64 = 3;
+const FUZZY_PENALTY_GAP_EXTENSION: i64 = 1;
+
+/// Fuzzily matches `pattern` against `text` in the style of fzf and skim, where every character
+/// of the pattern must appear in the text in order, but not necessarily next to each other.
+///
+/// Returns the score of the best alignment if it matches, where higher is better, along with the
+/// indices of the characters of the text that were matched.  Matches at the start of words and
+/// runs of consecutive matches score more, and gaps between matches score less.
+fn fuzzy_match(pattern: &str, text: &str, is_ignoring_case: bool) -> Option<(i64, Vec<usize>)> {
+    let chars_eq = |a: char, b: char| {
+        if is_ignoring_case {
+            a.to_lowercase().eq(b.to_lowercase())
+        } else {
+            a == b
+        }
+    };
+
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+
+    // Quickly bail out if the pattern isn't even a subsequence of the text.
+    let mut remaining_text = text.iter();
+    if !pattern
+        .iter()
+        .all(|p| remaining_text.any(|t| chars_eq(*p, *t)))
+    {
+        return None;
+    }
+
+    let bonuses = text
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            let is_boundary = match index.checked_sub(1).map(|prev| text[prev]) {
+                None => true,
+                Some(prev) => {
+                    (!prev.is_alphanumeric() && c.is_alphanumeric())
+                        || (prev.is_lowercase() && c.is_uppercase())
+                }
+            };
+            if is_boundary {
+                FUZZY_BONUS_BOUNDARY
+            } else {
+                0
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Each row holds, for every position in the text, the best score with that pattern character
+    // matched there, and where the previous pattern character was matched for it.  `gap_best` is
+    // the best score with the previous pattern character matched before text[j - 1], less the
+    // penalty for the gap after it.
+    let better = |a: Option<(i64, usize)>, b: Option<(i64, usize)>| match (a, b) {
+        (Some(a), Some(b)) => Some(if b.0 > a.0 { b } else { a }),
+        (a, b) => a.or(b),
+    };
+    let mut rows: Vec<Vec<Option<(i64, usize)>>> = Vec::with_capacity(pattern.len());
+    for (pattern_index, p) in pattern.iter().enumerate() {
+        let matched = rows.last();
+        let matched_score = |index: usize| {
+            matched
+                .and_then(|matched| matched[index])
+                .map(|(score, _)| score)
+        };
+
+        let mut row = vec![None; text.len()];
+        let mut gap_best: Option<(i64, usize)> = None;
+        for (text_index, t) in text.iter().enumerate() {
+            if chars_eq(*p, *t) {
+                let previous = if pattern_index == 0 {
+                    Some((0, 0))
+                } else {
+                    let consecutive = text_index.checked_sub(1).and_then(|prev| {
+                        matched_score(prev).map(|score| (score + FUZZY_BONUS_CONSECUTIVE, prev))
+                    });
+                    better(consecutive, gap_best)
+                };
+                row[text_index] = previous
+                    .map(|(score, prev)| (score + FUZZY_SCORE_MATCH + bonuses[text_index], prev));
+            }
+
+            if let Some(prev) = text_index.checked_sub(1) {
+                gap_best = better(
+                    gap_best.map(|(score, index)| (score - FUZZY_PENALTY_GAP_EXTENSION, index)),
+                    matched_score(prev).map(|score| (score - FUZZY_PENALTY_GAP_START, prev)),
+                );
+            }
+        }
+        rows.push(row);
+    }
+
+    let (score, mut text_index) = rows.last()?.iter().enumerate().fold(
+        None,
+        |best: Option<(i64, usize)>, (text_index, matched)| {
+            better(best, matched.map(|(score, _)| (score, text_index)))
+        },
+    )?;
+
+    // Walk back through the rows to find where each pattern character was matched.
+    let mut indices = Vec::with_capacity(pattern.len());
+    for row in rows.iter().rev() {
+        indices.push(text_index);
+        text_index = row[text_index]?.1;
+    }
+    indices.reverse();
+
+    Some((score, indices))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_subsequences() {
+        assert!(fuzzy_match("frfx", "firefox", true).is_some());
+        assert!(fuzzy_match("FRFX", "firefox", true).is_some());
+        assert!(fuzzy_match("FRFX", "firefox", false).is_none());
+        assert!(fuzzy_match("xf", "firefox", true).is_none());
+        assert!(fuzzy_match("firefoxes", "firefox", true).is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_ranks_better_matches_higher() {
+        let score = |pattern, text| fuzzy_match(pattern, text, true).unwrap().0;
+
+        // Consecutive matches beat scattered ones.
+        assert!(score("fire", "firefox") > score("fire", "fixture"));
+        // Matches at the start of words beat ones in the middle of them.
+        assert!(score("fx", "firefox-xdg") < score("fx", "foo-xdg"));
+        assert!(score("gs", "gnome-shell") > score("gs", "bugs"));
+        assert!(score("ns", "NetworkService") > score("ns", "ransom"));
+    }
+
+    #[test]
+    fn fuzzy_match_indices() {
+        let indices = |pattern, text| fuzzy_match(pattern, text, true).unwrap().1;
+
+        assert_eq!(indices("frfx", "firefox"), vec![0, 2, 4, 6]);
+        assert_eq!(indices("fox", "firefox"), vec![4, 5, 6]);
+        assert_eq!(indices("gs", "bugs gnome-shell"), vec![5, 11]);
+    }
+
+    #[test]
+    fn comparison_values_with_units() {
+        let parse = |prefix_type, value: &str, rest: &[&str]| {
+            let mut query = rest.iter().map(|s| s.to_string()).collect::<VecDeque<_>>();
+            let parsed = parse_comparison_value(prefix_type, value, &mut query);
+            parsed.map(|(prefix_type, value)| (format!("{:?}", prefix_type), value, query.len()))
+        };
+
+        assert_eq!(
+            parse(PrefixType::PMem, "1.5gb", &[]).unwrap(),
+            ("MemBytes".to_string(), 1_500_000_000.0, 0)
+        );
+        assert_eq!(
+            parse(PrefixType::PMem, "1.5", &["GiB"]).unwrap(),
+            ("MemBytes".to_string(), 1_610_612_736.0, 0)
+        );
+        assert_eq!(
+            parse(PrefixType::PMem, "50%", &[]).unwrap(),
+            ("PMem".to_string(), 50.0, 0)
+        );
+        assert_eq!(
+            parse(PrefixType::PCpu, "50%", &[]).unwrap(),
+            ("PCpu".to_string(), 50.0, 0)
+        );
+        assert_eq!(
+            parse(PrefixType::Rps, "1", &["firefox"]).unwrap(),
+            ("Rps".to_string(), 1.0, 1)
+        );
+        assert_eq!(
+            parse(PrefixType::Runtime, "10m", &[]).unwrap(),
+            ("Runtime".to_string(), 600.0, 0)
+        );
+        assert_eq!(
+            parse(PrefixType::Runtime, "1h30m", &[]).unwrap(),
+            ("Runtime".to_string(), 5400.0, 0)
+        );
+        assert_eq!(
+            parse(PrefixType::CpuTime, "2", &["min"]).unwrap(),
+            ("CpuTime".to_string(), 120.0, 0)
+        );
+
+        assert!(parse(PrefixType::PCpu, "50gb", &[]).is_err());
+        assert!(parse(PrefixType::Runtime, "10x", &[]).is_err());
+        assert!(parse(PrefixType::MemBytes, "lots", &[]).is_err());
+    }
+
+    #[test]
+    fn matched_ranges_of_names() {
+        let name_query = |name: &str, is_searching_with_fuzzy| {
+            let mut query = Query {
+                query: vec![Or {
+                    lhs: And {
+                        lhs: Prefix {
+                            regex_prefix: Some((
+                                PrefixType::Name,
+                                StringQuery::Value(name.to_string()),
+                            )),
+                            ..Prefix::default()
+                        },
+                        rhs: Some(Box::new(Prefix {
+                            regex_prefix: Some((
+                                PrefixType::User,
+                                StringQuery::Value("fire".to_string()),
+                            )),
+                            ..Prefix::default()
+                        })),
+                    },
+                    rhs: None,
+                }],
+            };
+            query
+                .process_regexes(false, true, false, is_searching_with_fuzzy)
+                .unwrap();
+            query
+        };
+
+        // Only the name is highlighted, not the user.
+        assert_eq!(
+            name_query("fire", false).matched_ranges("firefox"),
+            vec![0..4]
+        );
+        assert_eq!(
+            name_query("frfx", true).matched_ranges("firefox"),
+            vec![0..1, 2..3, 4..5, 6..7]
+        );
+        assert!(name_query("chrome", false)
+            .matched_ranges("firefox")
+            .is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+//! The remote hosts listed as `[[host]]` tables in the config file, whose CPU and memory widgets
+//! can be placed in the layout next to those of the local host.
+
+use crate::{app::data_farmer::DataCollection, canvas::DisplayableData};
+
+pub struct RemoteHost {
+    /// The name that widgets are assigned to the host by, which is also shown in their titles.
+    pub name: String,
+    /// The address of a `btm --serve`, which may start with `tls://` or `ssh://`.
+    pub address: String,
+    /// The token to send to the server, if it needs one.
+    pub token: Option<String>,
+    pub data_collection: DataCollection,
+    /// The data of the host's widgets, of which only the CPU and memory parts are used.
+    pub canvas_data: DisplayableData,
+}
+
+impl RemoteHost {
+    pub fn new(name: String, address: String, token: Option<String>) -> Self {
+        RemoteHost {
+            name,
+            address,
+            token,
+            data_collection: DataCollection::default(),
+            canvas_data: DisplayableData::default(),
+        }
+    }
+}
+
+/// Returns the index of the host called `name`, if there is one.
+pub fn get_host_index(hosts: &[RemoteHost], name: &str) -> Option<usize> {
+    hosts.iter().position(|host| host.name == name)
+}
+
+/// Returns the name, the data collection, and the canvas data of the remote host at `host`, or
+/// the local `data_collection` and `canvas_data` without a name if it is `None`.
+pub fn get_host_data<'a>(
+    data_collection: &'a DataCollection, canvas_data: &'a mut DisplayableData,
+    remote_hosts: &'a mut [RemoteHost], host: Option<usize>,
+) -> (Option<&'a str>, &'a DataCollection, &'a mut DisplayableData) {
+    match host.and_then(move |host| remote_hosts.get_mut(host)) {
+        Some(RemoteHost {
+            name,
+            data_collection,
+            canvas_data,
+            ..
+        }) => (Some(name.as_str()), data_collection, canvas_data),
+        None => (None, data_collection, canvas_data),
+    }
+}
@@ -1,13 +1,31 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+    time::Instant,
+};
 
 use unicode_segmentation::GraphemeCursor;
+use unicode_width::UnicodeWidthStr;
 
-use tui::widgets::TableState;
+use tui::{symbols::Marker, widgets::TableState};
 
 use crate::{
-    app::{layout_manager::BottomWidgetType, query::*},
+    app::{
+        layout_manager::{BottomLayout, BottomWidgetType},
+        process_actions::ProcessAction,
+        query::*,
+        AxisScaling, DataFilterKind,
+    },
+    canvas::ColourScheme,
     constants,
-    data_harvester::processes::{self, ProcessSorting},
+    data_harvester::{
+        connections::ConnectionSorting,
+        disks::DiskSorting,
+        processes::{self, IoPriority, ProcessDetails, ProcessSorting},
+    },
+    options::layout_options::Row,
+    utils::error::BottomError,
+    Pid,
 };
 use ProcessSorting::*;
 
@@ -25,6 +43,149 @@ impl Default for ScrollDirection {
     }
 }
 
+/// Whether the selection of a process widget follows its process as rows move around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FollowMode {
+    /// The selection stays on the same row.
+    #[default]
+    Off,
+    /// The selection stays on the same process across sorts and refreshes.
+    Following,
+    /// Like [`FollowMode::Following`], but the followed process is also moved to the top.
+    Pinned,
+}
+
+impl FollowMode {
+    /// Returns the next mode when toggling, in the order of off, following, and pinned.
+    pub fn next(self) -> Self {
+        match self {
+            FollowMode::Off => FollowMode::Following,
+            FollowMode::Following => FollowMode::Pinned,
+            FollowMode::Pinned => FollowMode::Off,
+        }
+    }
+}
+
+/// How the lines of a graph are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphStyle {
+    /// Braille characters, which fit up to eight points in a cell.
+    #[default]
+    Braille,
+    /// Half blocks, which fit two points in a cell.
+    Block,
+    /// One dot per cell, which most fonts support.
+    Dot,
+    /// Plain lines made of box-drawing characters.
+    Line,
+}
+
+impl GraphStyle {
+    /// Returns the next style when toggling, in the order of braille, block, dot, and line.
+    pub fn next(self) -> Self {
+        match self {
+            GraphStyle::Braille => GraphStyle::Block,
+            GraphStyle::Block => GraphStyle::Dot,
+            GraphStyle::Dot => GraphStyle::Line,
+            GraphStyle::Line => GraphStyle::Braille,
+        }
+    }
+
+    /// Returns the marker that datasets are drawn with.  The line style has no marker, so its
+    /// lines are drawn separately and this is only used for anything else in the chart.
+    pub fn get_marker(self) -> Marker {
+        match self {
+            GraphStyle::Braille | GraphStyle::Line => Marker::Braille,
+            GraphStyle::Block => Marker::Block,
+            GraphStyle::Dot => Marker::Dot,
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            GraphStyle::Braille => "braille",
+            GraphStyle::Block => "block",
+            GraphStyle::Dot => "dot",
+            GraphStyle::Line => "line",
+        }
+    }
+}
+
+impl std::str::FromStr for GraphStyle {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "braille" => Ok(GraphStyle::Braille),
+            "block" => Ok(GraphStyle::Block),
+            "dot" => Ok(GraphStyle::Dot),
+            "line" => Ok(GraphStyle::Line),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid graph style, use one of \"braille\", \"block\", \"dot\", or \"line\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// How the cores in the CPU legend are grouped, each group getting a line of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CpuGrouping {
+    /// Cores are listed on their own.
+    #[default]
+    None,
+    /// Cores are grouped by the physical package they are on.
+    Socket,
+    /// Performance and efficiency cores are grouped separately.
+    CoreType,
+    /// Cores are grouped by the cluster they are in, like the modules of efficiency cores.
+    Cluster,
+    /// Hardware threads are grouped by the physical core they share.
+    Sibling,
+}
+
+impl CpuGrouping {
+    /// Returns the next grouping when toggling, in the order of none, socket, core type, cluster,
+    /// and sibling.
+    pub fn next(self) -> Self {
+        match self {
+            CpuGrouping::None => CpuGrouping::Socket,
+            CpuGrouping::Socket => CpuGrouping::CoreType,
+            CpuGrouping::CoreType => CpuGrouping::Cluster,
+            CpuGrouping::Cluster => CpuGrouping::Sibling,
+            CpuGrouping::Sibling => CpuGrouping::None,
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            CpuGrouping::None => "none",
+            CpuGrouping::Socket => "socket",
+            CpuGrouping::CoreType => "type",
+            CpuGrouping::Cluster => "cluster",
+            CpuGrouping::Sibling => "sibling",
+        }
+    }
+}
+
+impl std::str::FromStr for CpuGrouping {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(CpuGrouping::None),
+            "socket" => Ok(CpuGrouping::Socket),
+            "type" => Ok(CpuGrouping::CoreType),
+            "cluster" => Ok(CpuGrouping::Cluster),
+            "sibling" => Ok(CpuGrouping::Sibling),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid CPU grouping, use one of \"none\", \"socket\", \"type\", \"cluster\", or \"sibling\".",
+                s
+            ))),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum CursorDirection {
     Left,
@@ -66,6 +227,35 @@ pub struct AppDeleteDialogState {
     pub keyboard_signal_select: usize,
     pub last_number_press: Option<Instant>,
     pub scroll_pos: usize,
+    /// Whether key presses are currently being typed into the signal search.
+    pub is_searching_signals: bool,
+    /// Only signals matching this are listed in the signal picker.
+    pub signal_search_query: String,
+    /// Whether the processes to kill include all descendants of the selected process.
+    pub is_killing_tree: bool,
+    /// Whether the processes to kill are the tagged processes rather than the selected one.
+    pub is_killing_tagged: bool,
+    /// The action that caused the current error, if any, for the error dialog.
+    pub failed_action: ProcessAction,
+    /// The PIDs and signal to retry killing with the privilege helper, if killing failed because
+    /// of missing permissions.
+    pub privileged_retry: Option<(Vec<Pid>, usize)>,
+}
+
+/// Where a graph started being dragged with the mouse, which scrolls through its history.
+pub struct HistoryDrag {
+    pub start_x: u16,
+    /// How far back the graphs were scrolled when the drag started, in milliseconds.
+    pub start_offset: u64,
+    /// How much time a column of the dragged graph covers, in milliseconds.
+    pub millis_per_column: f64,
+}
+
+/// A short message shown in a corner of the screen for a few seconds.
+pub struct AppToast {
+    pub text: String,
+    pub is_error: bool,
+    pub created_instant: Instant,
 }
 
 pub struct AppHelpDialogState {
@@ -84,6 +274,187 @@ impl Default for AppHelpDialogState {
     }
 }
 
+#[derive(Default)]
+pub struct AppProcessDetailsDialogState {
+    pub is_showing_details: bool,
+    /// The PID of the process whose details are being shown.
+    pub pid: Option<Pid>,
+    /// The most recently collected details, if they could be collected.
+    pub details: Option<ProcessDetails>,
+    /// The error from the last attempt to collect the details, if any.
+    pub error: Option<String>,
+    pub scroll_state: ParagraphScrollState,
+}
+
+#[derive(Default)]
+pub struct AppOpenFilesDialogState {
+    pub is_showing_open_files: bool,
+    /// The PID and name of the process whose open files are being shown.
+    pub process: Option<(Pid, String)>,
+    /// The open files as rows of FD, type, and name, collected when the dialog was opened.
+    pub open_files: Vec<Vec<String>>,
+    /// The error from collecting the open files, if any.
+    pub error: Option<String>,
+    pub scroll_state: AppScrollWidgetState,
+}
+
+#[derive(Default)]
+pub struct AppReniceDialogState {
+    pub is_showing_renice: bool,
+    /// The name and PIDs of the process(es) whose nice value is being set.
+    pub process: Option<(String, Vec<Pid>)>,
+    /// The nice value of the first process when the dialog was opened, if it could be read.
+    pub current_nice: Option<i32>,
+    /// The nice value typed in so far.
+    pub nice_input: String,
+    /// The error from the last attempt to set the nice value, if any.
+    pub error: Option<String>,
+}
+
+/// A filter that applies to every process widget at once, written like a process search.  The
+/// CPU and memory widgets also show how much of their usage is from the matching processes.
+#[derive(Default)]
+pub struct GlobalFilterState {
+    /// The query of the filter that is applied, if any.
+    pub query: Option<Query>,
+    pub query_text: String,
+    /// How much the processes matching the filter use in total, as of the last refresh.
+    pub usage: Option<GlobalFilterUsage>,
+    pub is_editing: bool,
+    /// The query typed in so far while editing the filter.
+    pub input: String,
+    /// Why the query typed in could not be applied, if it could not be.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GlobalFilterUsage {
+    pub cpu_percent: f64,
+    pub mem_percent: f64,
+    pub mem_bytes: u64,
+}
+
+impl GlobalFilterState {
+    /// Applies `query_text` as the filter, or removes the filter if it is blank.
+    pub fn set_query(&mut self, query_text: &str) -> Result<(), BottomError> {
+        let query_text = query_text.trim();
+        if query_text.is_empty() {
+            self.query = None;
+            self.query_text.clear();
+            self.usage = None;
+            return Ok(());
+        }
+
+        let mut process_search_state = ProcessSearchState::default();
+        process_search_state.search_state.current_search_query = query_text.to_string();
+        self.query = Some(process_search_state.parse_query()?);
+        self.query_text = query_text.to_string();
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct AppIoPriorityDialogState {
+    pub is_showing_io_priority: bool,
+    /// The name and PIDs of the process(es) whose I/O priority is being set.
+    pub process: Option<(String, Vec<Pid>)>,
+    /// The I/O priority to set, starting with the I/O priority of the first process.
+    pub priority: IoPriority,
+    /// The error from the last attempt to get or set the I/O priority, if any.
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AppAffinityDialogState {
+    pub is_showing_affinity: bool,
+    /// The name and PIDs of the process(es) whose CPU affinity is being set.
+    pub process: Option<(String, Vec<Pid>)>,
+    /// Whether each CPU is checked, starting with the affinity of the first process.
+    pub cpus: Vec<bool>,
+    pub scroll_state: AppScrollWidgetState,
+    /// The error from the last attempt to get or set the CPU affinity, if any.
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AppColumnsDialogState {
+    pub is_showing_columns: bool,
+    /// The ID of the process widget whose columns are being chosen.
+    pub widget_id: u64,
+    pub scroll_state: AppScrollWidgetState,
+    /// The result of the last attempt to save the columns to the config file, if any.
+    pub save_result: Option<std::result::Result<String, String>>,
+}
+
+#[derive(Default)]
+pub struct AppFiltersDialogState {
+    pub is_showing_filters: bool,
+    /// Which filter is being edited.
+    pub kind: Option<DataFilterKind>,
+    /// The names of the entries that can be hidden or shown, in order.
+    pub entries: Vec<String>,
+    pub scroll_state: AppScrollWidgetState,
+    /// The result of the last toggle or save, if any.
+    pub message: Option<std::result::Result<String, String>>,
+}
+
+#[derive(Default)]
+pub struct AppColourSchemeState {
+    /// The colour schemes that can be switched between, in order.
+    pub colour_schemes: Vec<ColourScheme>,
+    pub current_index: usize,
+    /// Whether the canvas still has to switch to the current colour scheme.
+    pub is_outdated: bool,
+}
+
+impl AppColourSchemeState {
+    pub fn init(colour_schemes: Vec<ColourScheme>, current_colour_scheme: &ColourScheme) -> Self {
+        AppColourSchemeState {
+            current_index: colour_schemes
+                .iter()
+                .position(|colour_scheme| colour_scheme == current_colour_scheme)
+                .unwrap_or(0),
+            colour_schemes,
+            is_outdated: false,
+        }
+    }
+
+    pub fn get_current(&self) -> Option<&ColourScheme> {
+        self.colour_schemes.get(self.current_index)
+    }
+}
+
+/// One of the named layouts that can be switched between.
+#[derive(Clone, Debug)]
+pub struct LayoutPage {
+    pub name: String,
+    /// The rows of the layout, as they would be in the config file.
+    pub rows: Vec<Row>,
+    pub layout: BottomLayout,
+    /// The ID of the selected widget of the page, kept while another page is shown.
+    pub current_widget_id: u64,
+}
+
+#[derive(Default)]
+pub struct AppLayoutEditState {
+    pub is_editing: bool,
+    /// The edited layout or that of the page switched to, until the canvas has switched to
+    /// drawing it.
+    pub new_layout: Option<BottomLayout>,
+    /// Whether the collection thread still has to be told which widgets are used now.
+    pub is_used_widgets_outdated: bool,
+}
+
+#[derive(Default)]
+pub struct AppThreadDialogState {
+    pub is_showing_threads: bool,
+    /// The PID and name of the process whose threads are being shown.
+    pub process: Option<(Pid, String)>,
+    pub scroll_state: AppScrollWidgetState,
+    /// Whether the collection thread still has to be told which process to collect threads for.
+    pub is_pid_outdated: bool,
+}
+
 /// AppSearchState deals with generic searching (I might do this in the future).
 pub struct AppSearchState {
     pub is_enabled: bool,
@@ -144,6 +515,11 @@ pub struct ProcessSearchState {
     pub is_ignoring_case: bool,
     pub is_searching_whole_word: bool,
     pub is_searching_with_regex: bool,
+    /// Whether names are matched fuzzily, with the matches ranked by how well they match.
+    pub is_searching_with_fuzzy: bool,
+    /// Where the case, whole word, regex, and fuzzy options were last drawn, in that order, as the
+    /// x start and end bounds and the y position.
+    pub option_locs: Vec<(u16, u16, u16)>,
 }
 
 impl Default for ProcessSearchState {
@@ -153,6 +529,8 @@ impl Default for ProcessSearchState {
             is_ignoring_case: true,
             is_searching_whole_word: false,
             is_searching_with_regex: false,
+            is_searching_with_fuzzy: false,
+            option_locs: Vec::new(),
         }
     }
 }
@@ -169,6 +547,10 @@ impl ProcessSearchState {
     pub fn search_toggle_regex(&mut self) {
         self.is_searching_with_regex = !self.is_searching_with_regex;
     }
+
+    pub fn search_toggle_fuzzy(&mut self) {
+        self.is_searching_with_fuzzy = !self.is_searching_with_fuzzy;
+    }
 }
 
 pub struct ColumnInfo {
@@ -186,6 +568,10 @@ pub struct ProcColumn {
     /// The x start and end bounds for each header.
     pub column_header_x_locs: Option<Vec<(u16, u16)>>,
     pub column_mapping: HashMap<ProcessSorting, ColumnInfo>,
+    /// The columns hidden by the user, which are not shown even while enabled.
+    pub hidden_columns: HashSet<ProcessSorting>,
+    /// The widths chosen by the user for columns, instead of sizing them to fit.
+    pub column_widths: HashMap<ProcessSorting, u16>,
     pub longest_header_len: u16,
     pub column_state: TableState,
     pub scroll_direction: ScrollDirection,
@@ -202,13 +588,29 @@ impl Default for ProcColumn {
             ProcessName,
             Command,
             CpuPercent,
+            CpuTrend,
             Mem,
             MemPercent,
             ReadPerSecond,
             WritePerSecond,
             TotalRead,
             TotalWrite,
+            Pss,
+            Uss,
+            Swap,
+            VoluntarySwitches,
+            InvoluntarySwitches,
+            MajorFaults,
+            MinorFaults,
+            SecurityLabel,
+            Capabilities,
+            IoPriority,
+            StartTime,
+            CpuTime,
+            GpuPercent,
+            OpenFds,
             User,
+            Group,
             State,
         ];
 
@@ -294,7 +696,7 @@ impl Default for ProcColumn {
                         },
                     );
                 }
-                User => {
+                User | Group => {
                     column_mapping.insert(
                         column,
                         ColumnInfo {
@@ -303,6 +705,26 @@ impl Default for ProcColumn {
                         },
                     );
                 }
+                GpuPercent => {
+                    column_mapping.insert(
+                        column,
+                        ColumnInfo {
+                            enabled: cfg!(target_os = "windows"),
+                            shortcut: None,
+                        },
+                    );
+                }
+                CpuTrend | Pss | Uss | Swap | VoluntarySwitches | InvoluntarySwitches
+                | MajorFaults | MinorFaults | SecurityLabel | Capabilities | IoPriority
+                | StartTime | CpuTime | OpenFds => {
+                    column_mapping.insert(
+                        column,
+                        ColumnInfo {
+                            enabled: false,
+                            shortcut: None,
+                        },
+                    );
+                }
                 _ => {
                     column_mapping.insert(
                         column,
@@ -321,6 +743,8 @@ impl Default for ProcColumn {
         ProcColumn {
             ordered_columns,
             column_mapping,
+            hidden_columns: HashSet::new(),
+            column_widths: HashMap::new(),
             longest_header_len,
             column_state: TableState::default(),
             scroll_direction: ScrollDirection::default(),
@@ -379,21 +803,138 @@ impl ProcColumn {
         }
     }
 
-    pub fn get_enabled_columns_len(&self) -> usize {
+    /// Whether the column is shown, which it is if it is enabled and not hidden by the user.
+    pub fn is_shown(&self, column: &ProcessSorting) -> bool {
+        self.is_enabled(column) && !self.hidden_columns.contains(column)
+    }
+
+    /// Returns the shown columns, in the order they are displayed.
+    pub fn get_shown_columns(&self) -> Vec<ProcessSorting> {
         self.ordered_columns
             .iter()
-            .filter_map(|column_type| {
-                if let Some(col_map) = self.column_mapping.get(column_type) {
-                    if col_map.enabled {
-                        Some(1)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+            .filter(|column| self.is_shown(column))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the columns that can be shown or hidden by the user in the current mode, which are
+    /// the enabled ones.
+    pub fn get_choosable_columns(&self) -> Vec<ProcessSorting> {
+        self.ordered_columns
+            .iter()
+            .filter(|column| self.is_enabled(column))
+            .cloned()
+            .collect()
+    }
+
+    /// Hides the column if it is shown, and shows it otherwise.  Returns whether it is now shown.
+    pub fn toggle_hidden(&mut self, column: &ProcessSorting) -> bool {
+        if !self.hidden_columns.remove(column) {
+            self.hidden_columns.insert(column.clone());
+        }
+        self.is_shown(column)
+    }
+
+    /// Moves the column past the previous (for a negative `offset`) or next choosable column.
+    pub fn move_column(&mut self, column: &ProcessSorting, offset: i64) {
+        let choosable_columns = self.get_choosable_columns();
+        if let Some(choosable_index) = choosable_columns.iter().position(|c| c == column) {
+            let target_index = choosable_index as i64 + offset;
+            if target_index < 0 || target_index >= choosable_columns.len() as i64 {
+                return;
+            }
+
+            let target = &choosable_columns[target_index as usize];
+            if let (Some(from), Some(to)) = (
+                self.ordered_columns.iter().position(|c| c == column),
+                self.ordered_columns.iter().position(|c| c == target),
+            ) {
+                let column = self.ordered_columns.remove(from);
+                self.ordered_columns.insert(to, column);
+            }
+        }
+    }
+
+    /// Changes the width of the column by `change`, starting from its header width if it has no
+    /// width chosen yet.  A width of zero goes back to sizing it to fit.
+    pub fn change_column_width(&mut self, column: &ProcessSorting, change: i64) {
+        let current_width = match self.column_widths.get(column) {
+            Some(width) => i64::from(*width),
+            None => column.to_string().len() as i64,
+        };
+
+        let new_width = (current_width + change).clamp(0, i64::from(u16::MAX));
+        if new_width == 0 {
+            self.column_widths.remove(column);
+        } else {
+            self.column_widths.insert(column.clone(), new_width as u16);
+        }
+    }
+
+    /// Shows only the given columns, in the given order and with the given widths, followed by the
+    /// rest in their current order.  A column shown in place of a given one depending on the mode,
+    /// such as the count in place of the PID, is also shown, right after it.
+    pub fn set_columns(&mut self, columns: &[(ProcessSorting, Option<u16>)]) {
+        let mut ordered_columns: Vec<ProcessSorting> = Vec::new();
+        for (column, _width) in columns {
+            for column in std::iter::once(column.clone()).chain(column.partner()) {
+                if !ordered_columns.contains(&column) {
+                    ordered_columns.push(column);
                 }
-            })
-            .sum()
+            }
+        }
+
+        self.hidden_columns = self
+            .ordered_columns
+            .iter()
+            .filter(|column| !ordered_columns.contains(column))
+            .cloned()
+            .collect();
+        for column in &self.ordered_columns {
+            if !ordered_columns.contains(column) {
+                ordered_columns.push(column.clone());
+            }
+        }
+        self.ordered_columns = ordered_columns;
+
+        self.column_widths = columns
+            .iter()
+            .filter_map(|(column, width)| width.map(|width| (column.clone(), width)))
+            .collect();
+    }
+
+    /// Returns the columns in the form taken by [`ProcColumn::set_columns`], to restore them
+    /// later.  Columns that are only disabled by the current mode are kept, but optional columns
+    /// that are disabled are not.
+    pub fn get_columns(&self) -> Vec<(ProcessSorting, Option<u16>)> {
+        let mut columns: Vec<(ProcessSorting, Option<u16>)> = Vec::new();
+        for column in &self.ordered_columns {
+            // Of a column and its partner, only the one enabled in the current mode is listed.
+            let column = match column.partner() {
+                Some(partner) if !self.is_enabled(column) && self.is_enabled(&partner) => partner,
+                _ => column.clone(),
+            };
+            let is_listed = columns.iter().any(|(listed, _width)| {
+                *listed == column || Some(listed.clone()) == column.partner()
+            });
+
+            if !is_listed
+                && !self.hidden_columns.contains(&column)
+                && (self.is_enabled(&column) || !column.is_optional())
+            {
+                let width = self.column_widths.get(&column).cloned();
+                columns.push((column, width));
+            }
+        }
+
+        columns
+    }
+
+    pub fn get_enabled_columns_len(&self) -> usize {
+        self.ordered_columns
+            .iter()
+            .filter(|column| self.is_shown(column))
+            .count()
     }
 
     /// NOTE: ALWAYS call this when opening the sorted window.
@@ -404,7 +945,7 @@ impl ProcColumn {
             if *column == *proc_sorting_type {
                 break;
             }
-            if self.column_mapping.get(column).unwrap().enabled {
+            if self.is_shown(column) {
                 true_index += 1;
             }
         }
@@ -435,7 +976,7 @@ impl ProcColumn {
                     command_str = format!("({})", command);
                 }
 
-                if mapping.enabled {
+                if self.is_shown(column_type) {
                     Some(format!(
                         "{}{}{}",
                         column_type,
@@ -458,9 +999,59 @@ impl ProcColumn {
     }
 }
 
+/// The settings that process widgets start with, as set by the config file or launch options.
+#[derive(Clone, Default)]
+pub struct ProcWidgetDefaults {
+    pub is_case_sensitive: bool,
+    pub is_match_whole_word: bool,
+    pub is_use_regex: bool,
+    pub is_use_fuzzy: bool,
+    pub is_grouped: bool,
+    pub show_memory_as_values: bool,
+    pub is_tree_mode: bool,
+    pub is_using_command: bool,
+    pub show_cpu_trend: bool,
+    pub show_open_fds: bool,
+    pub show_memory_breakdown: bool,
+    pub show_latency: bool,
+    pub show_security: bool,
+    pub show_io_priority: bool,
+    pub show_times: bool,
+    pub is_hiding_kernel_threads: bool,
+    pub process_columns: Vec<(ProcessSorting, Option<u16>)>,
+    pub default_sort: Option<(ProcessSorting, bool)>,
+}
+
+impl ProcWidgetDefaults {
+    pub fn init_state(&self) -> ProcWidgetState {
+        ProcWidgetState::init(
+            self.is_case_sensitive,
+            self.is_match_whole_word,
+            self.is_use_regex,
+            self.is_use_fuzzy,
+            self.is_grouped,
+            self.show_memory_as_values,
+            self.is_tree_mode,
+            self.is_using_command,
+            self.show_cpu_trend,
+            self.show_open_fds,
+            self.show_memory_breakdown,
+            self.show_latency,
+            self.show_security,
+            self.show_io_priority,
+            self.show_times,
+            self.is_hiding_kernel_threads,
+            &self.process_columns,
+            &self.default_sort,
+        )
+    }
+}
+
 pub struct ProcWidgetState {
     pub process_search_state: ProcessSearchState,
     pub is_grouped: bool,
+    /// Whether grouping collapses all processes of a user rather than those with the same name.
+    pub is_grouped_by_user: bool,
     pub scroll_state: AppScrollWidgetState,
     pub process_sorting_type: processes::ProcessSorting,
     pub is_process_sort_descending: bool,
@@ -469,14 +1060,23 @@ pub struct ProcWidgetState {
     pub is_sort_open: bool,
     pub columns: ProcColumn,
     pub is_tree_mode: bool,
+    pub is_hiding_kernel_threads: bool,
     pub table_width_state: CanvasTableWidthState,
     pub requires_redraw: bool,
+    /// The PIDs tagged for batch actions, which apply to these instead of the selected row.
+    pub tagged_pids: BTreeSet<Pid>,
+    pub follow_mode: FollowMode,
 }
 
 impl ProcWidgetState {
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
-        is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool, is_grouped: bool,
-        show_memory_as_values: bool, is_tree_mode: bool, is_using_command: bool,
+        is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool, is_use_fuzzy: bool,
+        is_grouped: bool, show_memory_as_values: bool, is_tree_mode: bool, is_using_command: bool,
+        show_cpu_trend: bool, show_open_fds: bool, show_memory_breakdown: bool, show_latency: bool,
+        show_security: bool, show_io_priority: bool, show_times: bool,
+        is_hiding_kernel_threads: bool, process_columns: &[(ProcessSorting, Option<u16>)],
+        default_sort: &Option<(ProcessSorting, bool)>,
     ) -> Self {
         let mut process_search_state = ProcessSearchState::default();
 
@@ -490,16 +1090,14 @@ impl ProcWidgetState {
         if is_use_regex {
             process_search_state.search_toggle_regex();
         }
+        if is_use_fuzzy {
+            process_search_state.search_toggle_fuzzy();
+        }
 
-        let (process_sorting_type, is_process_sort_descending) = if is_tree_mode {
-            (processes::ProcessSorting::Pid, false)
-        } else {
-            (processes::ProcessSorting::CpuPercent, true)
-        };
-
-        // TODO: If we add customizable columns, this should pull from config
         let mut columns = ProcColumn::default();
-        columns.set_to_sorted_index_from_type(&process_sorting_type);
+        if !process_columns.is_empty() {
+            columns.set_columns(process_columns);
+        }
         if is_grouped {
             // Normally defaults to showing by PID, toggle count on instead.
             columns.toggle(&ProcessSorting::Count);
@@ -514,10 +1112,53 @@ impl ProcWidgetState {
             columns.toggle(&ProcessSorting::ProcessName);
             columns.toggle(&ProcessSorting::Command);
         }
+        if show_cpu_trend {
+            columns.try_enable(&ProcessSorting::CpuTrend);
+        }
+        if show_open_fds {
+            columns.try_enable(&ProcessSorting::OpenFds);
+        }
+        if show_memory_breakdown {
+            columns.try_enable(&ProcessSorting::Pss);
+            columns.try_enable(&ProcessSorting::Uss);
+            columns.try_enable(&ProcessSorting::Swap);
+        }
+        if show_latency {
+            columns.try_enable(&ProcessSorting::VoluntarySwitches);
+            columns.try_enable(&ProcessSorting::InvoluntarySwitches);
+            columns.try_enable(&ProcessSorting::MajorFaults);
+            columns.try_enable(&ProcessSorting::MinorFaults);
+        }
+        if show_security {
+            columns.try_enable(&ProcessSorting::SecurityLabel);
+            columns.try_enable(&ProcessSorting::Capabilities);
+        }
+        if show_io_priority {
+            columns.try_enable(&ProcessSorting::IoPriority);
+        }
+        if show_times {
+            columns.try_enable(&ProcessSorting::StartTime);
+            columns.try_enable(&ProcessSorting::CpuTime);
+        }
+
+        let (process_sorting_type, is_process_sort_descending) = match default_sort {
+            _ if is_tree_mode => (Pid, false),
+            // Sort by the column shown in place of the given one in this mode, if any, such as the
+            // count in place of the PID while grouping.
+            Some((column, is_descending)) => match column.partner() {
+                Some(partner) if !columns.is_shown(column) && columns.is_shown(&partner) => {
+                    (partner, *is_descending)
+                }
+                _ => (column.clone(), *is_descending),
+            },
+            None => (CpuPercent, true),
+        };
+        columns.set_to_sorted_index_from_type(&process_sorting_type);
 
         ProcWidgetState {
             process_search_state,
             is_grouped,
+            is_grouped_by_user: false,
             scroll_state: AppScrollWidgetState::default(),
             process_sorting_type,
             is_process_sort_descending,
@@ -526,8 +1167,11 @@ impl ProcWidgetState {
             is_sort_open: false,
             columns,
             is_tree_mode,
+            is_hiding_kernel_threads,
             table_width_state: CanvasTableWidthState::default(),
             requires_redraw: false,
+            tagged_pids: BTreeSet::new(),
+            follow_mode: FollowMode::default(),
         }
     }
 
@@ -542,7 +1186,7 @@ impl ProcWidgetState {
         let mut enabled_index = 0;
         let target_itx = self.columns.current_scroll_position;
         for column in &self.columns.ordered_columns {
-            let enabled = self.columns.column_mapping.get(column).unwrap().enabled;
+            let enabled = self.columns.is_shown(column);
             if enabled_index == target_itx && enabled {
                 break;
             }
@@ -558,18 +1202,8 @@ impl ProcWidgetState {
                 self.is_process_sort_descending = !(self.is_process_sort_descending);
             } else {
                 self.process_sorting_type = new_sort_type.clone();
-                match self.process_sorting_type {
-                    ProcessSorting::State
-                    | ProcessSorting::Pid
-                    | ProcessSorting::ProcessName
-                    | ProcessSorting::Command => {
-                        // Also invert anything that uses alphabetical sorting by default.
-                        self.is_process_sort_descending = false;
-                    }
-                    _ => {
-                        self.is_process_sort_descending = true;
-                    }
-                }
+                // Also invert anything that uses alphabetical sorting by default.
+                self.is_process_sort_descending = new_sort_type.is_descending_by_default();
             }
         }
     }
@@ -621,7 +1255,7 @@ impl ProcWidgetState {
             self.process_search_state.search_state.is_invalid_search = false;
             self.process_search_state.search_state.error_message = None;
         } else {
-            let parsed_query = self.parse_query();
+            let parsed_query = self.process_search_state.parse_query();
             // debug!("Parsed query: {:#?}", parsed_query);
 
             if let Ok(parsed_query) = parsed_query {
@@ -643,6 +1277,18 @@ impl ProcWidgetState {
         self.process_search_state.search_state.reset();
     }
 
+    /// Replaces the search query, such as with one from the search history, with the cursor at
+    /// the end of it.
+    pub fn set_search_query(&mut self, query: String) {
+        let search_state = &mut self.process_search_state.search_state;
+        search_state.grapheme_cursor = GraphemeCursor::new(query.len(), query.len(), true);
+        search_state.char_cursor_position = UnicodeWidthStr::width(query.as_str());
+        search_state.cursor_direction = CursorDirection::Right;
+        search_state.current_search_query = query;
+
+        self.update_query();
+    }
+
     pub fn search_walk_forward(&mut self, start_position: usize) {
         self.process_search_state
             .search_state
@@ -666,6 +1312,99 @@ impl ProcWidgetState {
     }
 }
 
+/// The queries previously searched for in the process widgets, oldest first, which can be gone
+/// back through while searching.
+#[derive(Default)]
+pub struct SearchHistory {
+    pub queries: Vec<String>,
+    /// Which query is shown while going through the history.
+    pub position: Option<usize>,
+    /// What was typed before going through the history, which is returned to after the newest.
+    pub draft: String,
+    /// Where the history is kept between sessions, if it is.
+    pub path: Option<PathBuf>,
+}
+
+impl SearchHistory {
+    /// Reads the history kept at `path`, which is then kept up to date with new queries.
+    pub fn load(path: PathBuf) -> Self {
+        let queries = std::fs::read_to_string(&path)
+            .map(|history| {
+                history
+                    .lines()
+                    .filter(|query| !query.trim().is_empty())
+                    .map(|query| query.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        SearchHistory {
+            queries,
+            path: Some(path),
+            ..SearchHistory::default()
+        }
+    }
+
+    /// Adds a query as the newest, moving it if it was already searched for before.
+    pub fn record(&mut self, query: &str) {
+        self.position = None;
+
+        let query = query.trim();
+        if query.is_empty() || self.queries.last().map(String::as_str) == Some(query) {
+            return;
+        }
+
+        self.queries
+            .retain(|previous_query| previous_query != query);
+        self.queries.push(query.to_string());
+        if self.queries.len() > constants::MAX_SEARCH_HISTORY_LENGTH {
+            self.queries
+                .drain(..self.queries.len() - constants::MAX_SEARCH_HISTORY_LENGTH);
+        }
+
+        if let Some(path) = &self.path {
+            // Failing to keep the history is not worth interrupting a search over.
+            let _ = std::fs::write(path, format!("{}\n", self.queries.join("\n")));
+        }
+    }
+
+    /// Returns the query before the one shown, remembering `current_query` if starting to go
+    /// through the history.
+    pub fn select_previous(&mut self, current_query: &str) -> Option<&str> {
+        let position = match self.position {
+            Some(position) => position.checked_sub(1)?,
+            None => {
+                let position = self.queries.len().checked_sub(1)?;
+                self.draft = current_query.to_string();
+                position
+            }
+        };
+
+        self.position = Some(position);
+        self.queries.get(position).map(String::as_str)
+    }
+
+    /// Returns the query after the one shown, or what was typed before going through the history
+    /// after the newest one.
+    pub fn select_next(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+        if position < self.queries.len() {
+            self.position = Some(position);
+            self.queries.get(position).map(String::as_str)
+        } else {
+            self.position = None;
+            Some(self.draft.as_str())
+        }
+    }
+}
+
+/// A process search saved under a name in the config, which can be switched to quickly.
+#[derive(Clone, Debug)]
+pub struct SavedProcessFilter {
+    pub name: String,
+    pub query: String,
+}
+
 pub struct ProcState {
     pub widget_states: HashMap<u64, ProcWidgetState>,
     pub force_update: Option<u64>,
@@ -693,29 +1432,44 @@ impl ProcState {
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    pub graph_style: GraphStyle,
+    /// Whether the areas below the lines of the graph are filled.
+    pub is_filled: bool,
+    /// The last value that the y-axis was scaled to if it is automatic, which is kept while the
+    /// data still fits.
+    pub auto_max_entry: Option<f64>,
+    /// Likewise for the TX graph, when RX and TX are graphed separately.
+    pub auto_max_tx_entry: Option<f64>,
+    /// Whether the y-axis is linear or logarithmic, which can be switched while running.
+    pub scale_type: AxisScaling,
     // pub draw_max_range_cache: f64,
     // pub draw_labels_cache: Vec<String>,
     // pub draw_time_start_cache: f64,
-    // TODO: Re-enable these when we move net details state-side!
+    // TODO: Re-enable this when we move net details state-side!
     // pub unit_type: DataUnitTypes,
-    // pub scale_type: AxisScaling,
 }
 
 impl NetWidgetState {
     pub fn init(
         current_display_time: u64,
         autohide_timer: Option<Instant>,
+        graph_style: GraphStyle,
+        is_filled: bool,
+        scale_type: AxisScaling,
         // unit_type: DataUnitTypes,
-        // scale_type: AxisScaling,
     ) -> Self {
         NetWidgetState {
             current_display_time,
             autohide_timer,
+            graph_style,
+            is_filled,
+            auto_max_entry: None,
+            auto_max_tx_entry: None,
+            scale_type,
             // draw_max_range_cache: 0.0,
             // draw_labels_cache: vec![],
             // draw_time_start_cache: 0.0,
             // unit_type,
-            // scale_type,
         }
     }
 }
@@ -723,13 +1477,22 @@ impl NetWidgetState {
 pub struct NetState {
     pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, NetWidgetState>,
+    /// The interface to show usage for, or all interfaces if `None`.
+    pub selected_interface: Option<String>,
+    /// Whether to draw one line per interface in the network graph.
+    pub is_per_interface: bool,
+    /// Whether to graph RX and TX separately, one above the other.
+    pub is_split: bool,
 }
 
 impl NetState {
-    pub fn init(widget_states: HashMap<u64, NetWidgetState>) -> Self {
+    pub fn init(widget_states: HashMap<u64, NetWidgetState>, is_split: bool) -> Self {
         NetState {
             force_update: None,
             widget_states,
+            selected_interface: None,
+            is_per_interface: false,
+            is_split,
         }
     }
 
@@ -749,14 +1512,25 @@ pub struct CpuWidgetState {
     pub scroll_state: AppScrollWidgetState,
     pub is_multi_graph_mode: bool,
     pub table_width_state: CanvasTableWidthState,
+    pub graph_style: GraphStyle,
+    /// Whether the areas below the lines of the graph are filled.
+    pub is_filled: bool,
+    /// The index of the remote host whose CPU is shown, or `None` for the local host.
+    pub host: Option<usize>,
 }
 
 impl CpuWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, graph_style: GraphStyle,
+        is_filled: bool, host: Option<usize>,
+    ) -> Self {
         CpuWidgetState {
             current_display_time,
             is_legend_hidden: false,
             autohide_timer,
+            graph_style,
+            is_filled,
+            host,
             scroll_state: AppScrollWidgetState::default(),
             is_multi_graph_mode: false,
             table_width_state: CanvasTableWidthState::default(),
@@ -767,13 +1541,15 @@ impl CpuWidgetState {
 pub struct CpuState {
     pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, CpuWidgetState>,
+    pub grouping: CpuGrouping,
 }
 
 impl CpuState {
-    pub fn init(widget_states: HashMap<u64, CpuWidgetState>) -> Self {
+    pub fn init(widget_states: HashMap<u64, CpuWidgetState>, grouping: CpuGrouping) -> Self {
         CpuState {
             force_update: None,
             widget_states,
+            grouping,
         }
     }
 
@@ -789,26 +1565,43 @@ impl CpuState {
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    pub graph_style: GraphStyle,
+    /// Whether the areas below the lines of the graph are filled.
+    pub is_filled: bool,
+    /// The last maximum of the y-axis if it is automatic, which is kept while the data still fits.
+    pub auto_y_max: Option<f64>,
+    /// The index of the remote host whose memory is shown, or `None` for the local host.
+    pub host: Option<usize>,
 }
 
 impl MemWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, graph_style: GraphStyle,
+        is_filled: bool, host: Option<usize>,
+    ) -> Self {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            graph_style,
+            is_filled,
+            auto_y_max: None,
+            host,
         }
     }
 }
 pub struct MemState {
     pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, MemWidgetState>,
+    /// Whether to stack used memory, the cache, and swap as bands in the memory graph.
+    pub is_stacked: bool,
 }
 
 impl MemState {
-    pub fn init(widget_states: HashMap<u64, MemWidgetState>) -> Self {
+    pub fn init(widget_states: HashMap<u64, MemWidgetState>, is_stacked: bool) -> Self {
         MemState {
             force_update: None,
             widget_states,
+            is_stacked,
         }
     }
 
@@ -821,6 +1614,50 @@ impl MemState {
     }
 }
 
+pub struct PowerWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+    pub graph_style: GraphStyle,
+    /// Whether the areas below the lines of the graph are filled.
+    pub is_filled: bool,
+}
+
+impl PowerWidgetState {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, graph_style: GraphStyle,
+        is_filled: bool,
+    ) -> Self {
+        PowerWidgetState {
+            current_display_time,
+            autohide_timer,
+            graph_style,
+            is_filled,
+        }
+    }
+}
+
+pub struct PowerState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, PowerWidgetState>,
+}
+
+impl PowerState {
+    pub fn init(widget_states: HashMap<u64, PowerWidgetState>) -> Self {
+        PowerState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PowerWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PowerWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct TempWidgetState {
     pub scroll_state: AppScrollWidgetState,
     pub table_width_state: CanvasTableWidthState,
@@ -853,27 +1690,319 @@ impl TempState {
     }
 }
 
+pub struct UsersWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl UsersWidgetState {
+    pub fn init() -> Self {
+        UsersWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct UsersState {
+    pub widget_states: HashMap<u64, UsersWidgetState>,
+}
+
+impl UsersState {
+    pub fn init(widget_states: HashMap<u64, UsersWidgetState>) -> Self {
+        UsersState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut UsersWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&UsersWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct ActivityWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl ActivityWidgetState {
+    pub fn init() -> Self {
+        ActivityWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct ActivityState {
+    pub widget_states: HashMap<u64, ActivityWidgetState>,
+}
+
+impl ActivityState {
+    pub fn init(widget_states: HashMap<u64, ActivityWidgetState>) -> Self {
+        ActivityState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ActivityWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ActivityWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct PodsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl PodsWidgetState {
+    pub fn init() -> Self {
+        PodsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct PodsState {
+    pub widget_states: HashMap<u64, PodsWidgetState>,
+}
+
+impl PodsState {
+    pub fn init(widget_states: HashMap<u64, PodsWidgetState>) -> Self {
+        PodsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PodsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PodsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct VmWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl VmWidgetState {
+    pub fn init() -> Self {
+        VmWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct VmState {
+    pub widget_states: HashMap<u64, VmWidgetState>,
+}
+
+impl VmState {
+    pub fn init(widget_states: HashMap<u64, VmWidgetState>) -> Self {
+        VmState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut VmWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&VmWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct NumaWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl NumaWidgetState {
+    pub fn init() -> Self {
+        NumaWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct NumaState {
+    pub widget_states: HashMap<u64, NumaWidgetState>,
+}
+
+impl NumaState {
+    pub fn init(widget_states: HashMap<u64, NumaWidgetState>) -> Self {
+        NumaState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut NumaWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&NumaWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct UnitsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl UnitsWidgetState {
+    pub fn init() -> Self {
+        UnitsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct UnitsState {
+    pub widget_states: HashMap<u64, UnitsWidgetState>,
+}
+
+impl UnitsState {
+    pub fn init(widget_states: HashMap<u64, UnitsWidgetState>) -> Self {
+        UnitsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut UnitsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&UnitsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct KernelLogWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl KernelLogWidgetState {
+    pub fn init() -> Self {
+        KernelLogWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct KernelLogState {
+    pub widget_states: HashMap<u64, KernelLogWidgetState>,
+}
+
+impl KernelLogState {
+    pub fn init(widget_states: HashMap<u64, KernelLogWidgetState>) -> Self {
+        KernelLogState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut KernelLogWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&KernelLogWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct AlertsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl AlertsWidgetState {
+    pub fn init() -> Self {
+        AlertsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct AlertsState {
+    pub widget_states: HashMap<u64, AlertsWidgetState>,
+}
+
+impl AlertsState {
+    pub fn init(widget_states: HashMap<u64, AlertsWidgetState>) -> Self {
+        AlertsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut AlertsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&AlertsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct DiskWidgetState {
     pub scroll_state: AppScrollWidgetState,
     pub table_width_state: CanvasTableWidthState,
+    /// The column that disks are sorted by and whether it is descending, if they are sorted.
+    pub sorting: Option<(DiskSorting, bool)>,
+    /// Whether typed characters currently go to the search query.
+    pub is_searching: bool,
+    /// Only disks that contain this (case-insensitive) in their name or mount point are shown.
+    pub search_query: String,
+    /// The mount points of the disks hidden from this widget.
+    pub hidden_mounts: HashSet<String>,
 }
 
 impl DiskWidgetState {
-    pub fn init() -> Self {
+    pub fn init(sorting: Option<(DiskSorting, bool)>) -> Self {
         DiskWidgetState {
             scroll_state: AppScrollWidgetState::default(),
             table_width_state: CanvasTableWidthState::default(),
+            sorting,
+            is_searching: false,
+            search_query: String::new(),
+            hidden_mounts: HashSet::new(),
         }
     }
+
+    /// Moves the sort to the next column, wrapping around.  The inode column is skipped if it
+    /// isn't shown.
+    pub fn cycle_sorting_type(&mut self, use_disk_inodes: bool) {
+        let order = DiskSorting::ORDER
+            .iter()
+            .filter(|sorting| use_disk_inodes || **sorting != DiskSorting::InodePercent)
+            .collect::<Vec<_>>();
+        let next_sorting = match self.sorting {
+            Some((current_sorting, _is_descending)) => {
+                let current_index = order
+                    .iter()
+                    .position(|sorting| **sorting == current_sorting)
+                    .unwrap_or(0);
+                *order[(current_index + 1) % order.len()]
+            }
+            None => DiskSorting::Disk,
+        };
+        self.sorting = Some((next_sorting, next_sorting.is_descending_by_default()));
+    }
 }
 
 pub struct DiskState {
+    pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, DiskWidgetState>,
 }
 
 impl DiskState {
     pub fn init(widget_states: HashMap<u64, DiskWidgetState>) -> Self {
-        DiskState { widget_states }
+        DiskState {
+            force_update: None,
+            widget_states,
+        }
     }
 
     pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut DiskWidgetState> {
@@ -921,6 +2050,62 @@ impl BatteryState {
     }
 }
 
+pub struct ConnWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+    pub sorting_type: ConnectionSorting,
+    pub is_sort_descending: bool,
+    /// Whether typed characters currently go to the search query.
+    pub is_searching: bool,
+    /// Only connections that contain this (case-insensitive) in any column are shown.
+    pub search_query: String,
+}
+
+impl ConnWidgetState {
+    pub fn init() -> Self {
+        ConnWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+            sorting_type: ConnectionSorting::default(),
+            is_sort_descending: false,
+            is_searching: false,
+            search_query: String::new(),
+        }
+    }
+
+    /// Moves the sort to the next column, wrapping around.
+    pub fn cycle_sorting_type(&mut self) {
+        let order = &ConnectionSorting::ORDER;
+        let current_index = order
+            .iter()
+            .position(|sorting| *sorting == self.sorting_type)
+            .unwrap_or(0);
+        self.sorting_type = order[(current_index + 1) % order.len()].clone();
+    }
+}
+
+pub struct ConnState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, ConnWidgetState>,
+}
+
+impl ConnState {
+    pub fn init(widget_states: HashMap<u64, ConnWidgetState>) -> Self {
+        ConnState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ConnWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ConnWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
@@ -8,14 +8,14 @@ use bottom::{canvas, constants::*, data_conversion::*, options::*, *};
 
 use std::{
     boxed::Box,
-    io::stdout,
+    io::{stdout, Write},
     panic,
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc, Arc, Condvar, Mutex,
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
@@ -53,6 +53,52 @@ fn main() -> Result<()> {
         config_path,
     )?;
 
+    // Print the metrics of a single harvest instead of starting the TUI, if asked to.
+    if matches.is_present("dump") {
+        let data_collection = export::harvest_once(&app.app_config_fields, app.filters.clone());
+        writeln!(
+            stdout(),
+            "{}",
+            export::json::format_metrics(
+                &export::get_metrics(&data_collection, &app.app_config_fields.temperature_type),
+                export::get_unix_timestamp()
+            )
+        )?;
+        return Ok(());
+    }
+
+    // Serve the metrics to Prometheus, push them to InfluxDB, and log them to a file, if asked to.
+    let mut exporters = export::Exporters::start(&app.app_config_fields)?;
+
+    // Only run the collection pipeline, without the TUI, if asked to.
+    if let Some(address) = &app.app_config_fields.serve_address {
+        let remote_server = remote::server::RemoteServer::start(
+            address,
+            app.app_config_fields.remote_token.clone(),
+            app.app_config_fields.serve_tls.clone(),
+        )
+        .with_context(|| format!("Unable to serve remote clients at '{}'.", address))?;
+        let used_widgets = app.used_widgets.clone();
+        return run_headless(app, exporters, Some(remote_server), used_widgets);
+    } else if matches.is_present("serve_stdio") {
+        let remote_server = remote::server::RemoteServer::start_stdio();
+        let used_widgets = app.used_widgets.clone();
+        return run_headless(app, exporters, Some(remote_server), used_widgets);
+    } else if matches.is_present("headless") {
+        if exporters.is_empty() {
+            anyhow::bail!(
+                "Headless mode has nothing to do; set --prometheus_address, --influxdb_url, --metrics_log, or --record."
+            );
+        }
+        // A recording is of the widgets in the layout, so that it can be replayed in it.
+        let used_widgets = if app.app_config_fields.record_path.is_some() {
+            export::get_exported_widgets().union(&app.used_widgets)
+        } else {
+            export::get_exported_widgets()
+        };
+        return run_headless(app, exporters, None, used_widgets);
+    }
+
     // Create painter and set colours.
     let mut painter = canvas::Painter::init(
         widget_layout,
@@ -96,17 +142,61 @@ fn main() -> Result<()> {
         })
     };
 
+    // Receive the data of the remote hosts that widgets are assigned to, unless replaying, as a
+    // recording only has the data of the local host.
+    let _host_threads = if app.app_config_fields.replay_path.is_some() {
+        Vec::new()
+    } else {
+        app.remote_hosts
+            .iter()
+            .enumerate()
+            .map(|(host_index, host)| {
+                remote::client::create_host_thread(
+                    sender.clone(),
+                    host_index,
+                    host,
+                    thread_termination_lock.clone(),
+                    thread_termination_cvar.clone(),
+                    &app.app_config_fields,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
     // Event loop
     let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
-    let _collection_thread = create_collection_thread(
-        sender,
-        collection_thread_ctrl_receiver,
-        thread_termination_lock.clone(),
-        thread_termination_cvar.clone(),
-        &app.app_config_fields,
-        app.filters.clone(),
-        app.used_widgets.clone(),
-    );
+    let _collection_thread = if let Some(replay_path) = &app.app_config_fields.replay_path {
+        let recording = recording::Recording::open(replay_path).with_context(|| {
+            format!(
+                "Unable to open the recording at '{}'.",
+                replay_path.display()
+            )
+        })?;
+        recording::create_replay_thread(
+            sender,
+            collection_thread_ctrl_receiver,
+            thread_termination_lock.clone(),
+            recording,
+        )
+    } else if app.app_config_fields.connect_address.is_some() {
+        remote::client::create_remote_thread(
+            sender,
+            collection_thread_ctrl_receiver,
+            thread_termination_lock.clone(),
+            thread_termination_cvar.clone(),
+            &app.app_config_fields,
+        )
+    } else {
+        create_collection_thread(
+            sender,
+            collection_thread_ctrl_receiver,
+            thread_termination_lock.clone(),
+            thread_termination_cvar.clone(),
+            &app.app_config_fields,
+            app.filters.clone(),
+            app.used_widgets.clone(),
+        )
+    };
 
     // Set up up tui and crossterm
     let mut stdout_val = stdout();
@@ -142,8 +232,29 @@ fn main() -> Result<()> {
                     handle_force_redraws(&mut app);
                 }
                 BottomEvent::Update(data) => {
+                    if let Err(err) = exporters.record(&data) {
+                        app.show_toast(format!("Unable to write the recording: {}", err), true);
+                    }
                     app.data_collection.eat_data(data);
 
+                    if let Err(err) = exporters.export(
+                        &app.data_collection,
+                        &app.app_config_fields.temperature_type,
+                    ) {
+                        app.show_toast(format!("Unable to export the metrics: {}", err), true);
+                    }
+
+                    // Alerts are checked even while frozen, so that none are missed.
+                    if app.process_alerts.has_rules() || app.metric_alerts.has_rules() {
+                        app.process_alerts
+                            .update(&app.data_collection.process_harvest);
+                        let transitions = app.metric_alerts.update(&app.data_collection);
+                        handle_alert_transitions(&mut app, &transitions);
+                        if app.used_widgets.use_alerts {
+                            app.canvas_data.alert_data = convert_alert_rows(&app);
+                        }
+                    }
+
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
                     if first_run {
@@ -156,35 +267,65 @@ fn main() -> Result<()> {
 
                         // Network
                         if app.used_widgets.use_net {
-                            let network_data = convert_network_data_points(
-                                &app.data_collection,
-                                false,
-                                app.app_config_fields.use_basic_mode
-                                    || app.app_config_fields.use_old_network_legend,
-                                &app.app_config_fields.network_scale_type,
-                                &app.app_config_fields.network_unit_type,
-                                app.app_config_fields.network_use_binary_prefix,
-                            );
-                            app.canvas_data.network_data_rx = network_data.rx;
-                            app.canvas_data.network_data_tx = network_data.tx;
-                            app.canvas_data.rx_display = network_data.rx_display;
-                            app.canvas_data.tx_display = network_data.tx_display;
-                            if let Some(total_rx_display) = network_data.total_rx_display {
-                                app.canvas_data.total_rx_display = total_rx_display;
-                            }
-                            if let Some(total_tx_display) = network_data.total_tx_display {
-                                app.canvas_data.total_tx_display = total_tx_display;
-                            }
+                            update_network_data(&mut app);
                         }
 
                         // Disk
                         if app.used_widgets.use_disk {
-                            app.canvas_data.disk_data = convert_disk_row(&app.data_collection);
+                            update_disk_data(&mut app);
+                        }
+
+                        // Connections
+                        if app.used_widgets.use_conn {
+                            update_all_connection_data(&mut app);
+                        }
+
+                        // Users
+                        if app.used_widgets.use_users {
+                            app.canvas_data.session_data = convert_session_rows(&app);
+                        }
+
+                        // NUMA
+                        if app.used_widgets.use_numa {
+                            app.canvas_data.numa_data = convert_numa_rows(&app);
+                        }
+
+                        // VMs
+                        if app.used_widgets.use_vm {
+                            app.canvas_data.vm_data = convert_vm_rows(&app);
+                        }
+
+                        // Activity
+                        if app.used_widgets.use_activity {
+                            app.canvas_data.activity_data = convert_activity_rows(&app);
+                        }
+
+                        // Pods
+                        if app.used_widgets.use_pods {
+                            app.canvas_data.pod_data = convert_pod_rows(&app);
+                        }
+
+                        // Units
+                        if app.used_widgets.use_units {
+                            app.canvas_data.unit_data = convert_unit_rows(&app);
+                        }
+
+                        // Kernel log
+                        if app.used_widgets.use_kernel_log {
+                            update_kernel_log_data(&mut app);
+                        }
+
+                        // Power
+                        if app.used_widgets.use_power {
+                            app.canvas_data.power_data =
+                                convert_power_data_points(&app.data_collection, false);
                         }
 
                         // Temperatures
                         if app.used_widgets.use_temp {
                             app.canvas_data.temp_sensor_data = convert_temp_row(&app);
+                            app.canvas_data.temp_throttle_status =
+                                app.data_collection.throttle_harvest;
                         }
 
                         // Memory
@@ -193,11 +334,19 @@ fn main() -> Result<()> {
                                 convert_mem_data_points(&app.data_collection, false);
                             app.canvas_data.swap_data =
                                 convert_swap_data_points(&app.data_collection, false);
+                            app.canvas_data.stacked_mem_data =
+                                convert_stacked_mem_data_points(&app.data_collection, false);
                             let (memory_labels, swap_labels) =
                                 convert_mem_labels(&app.data_collection);
 
                             app.canvas_data.mem_labels = memory_labels;
                             app.canvas_data.swap_labels = swap_labels;
+                            app.canvas_data.huge_pages_labels =
+                                convert_huge_pages_labels(&app.data_collection);
+                            app.canvas_data.shared_memory_labels =
+                                convert_shared_memory_labels(&app.data_collection);
+                            app.canvas_data.cache_labels =
+                                convert_cache_labels(&app.data_collection);
                         }
 
                         if app.used_widgets.use_cpu {
@@ -208,12 +357,32 @@ fn main() -> Result<()> {
                                 &mut app.canvas_data.cpu_data,
                                 false,
                             );
+                            let (cpu_group_data, cpu_legend_entries) = convert_cpu_groups(
+                                &app.data_collection,
+                                &app.canvas_data.cpu_data,
+                                app.cpu_state.grouping,
+                            );
+                            app.canvas_data.cpu_group_data = cpu_group_data;
+                            app.canvas_data.cpu_legend_entries = cpu_legend_entries;
+                            if app.app_config_fields.cpu_frequency_graph {
+                                app.canvas_data.cpu_frequency_data =
+                                    convert_cpu_frequency_points(&app.data_collection, false);
+                            }
                             app.canvas_data.load_avg_data = app.data_collection.load_avg_harvest;
                         }
 
                         // Processes
                         if app.used_widgets.use_proc {
                             update_all_process_lists(&mut app);
+
+                            if app.thread_dialog_state.is_showing_threads {
+                                app.canvas_data.thread_data =
+                                    convert_thread_rows(&app.data_collection);
+                            }
+
+                            if app.process_details_dialog_state.is_showing_details {
+                                app.refresh_process_details();
+                            }
                         }
 
                         // Battery
@@ -226,13 +395,39 @@ fn main() -> Result<()> {
                         }
                     }
                 }
+                BottomEvent::HostUpdate(host_index, data) => {
+                    if let Some(host) = app.remote_hosts.get_mut(host_index) {
+                        host.data_collection.eat_data(data);
+                        if !app.is_frozen {
+                            update_remote_host_data(&mut app, host_index);
+                        }
+                    }
+                }
                 BottomEvent::Clean => {
                     app.data_collection
                         .clean_data(constants::STALE_MAX_MILLISECONDS);
+                    for host in &mut app.remote_hosts {
+                        host.data_collection
+                            .clean_data(constants::STALE_MAX_MILLISECONDS);
+                    }
+                }
+                BottomEvent::RemoteError(err) => {
+                    app.show_toast(err, true);
+                }
+                BottomEvent::ReplayReset(history) => {
+                    app.data_collection.reset();
+                    for data in history {
+                        app.data_collection.eat_data(data);
+                    }
+                }
+                BottomEvent::ReplayStatus(status, is_error) => {
+                    app.show_toast(status, is_error);
                 }
             }
         }
 
+        app.poll_privileged_kill();
+
         // TODO: [OPT] Should not draw if no change (ie: scroll max)
         try_drawing(&mut terminal, &mut app, &mut painter)?;
     }
@@ -247,3 +442,76 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Harvests the data of `used_widgets` and passes it on to the exporters, loggers, and remote
+/// clients until terminated, without drawing anything.
+fn run_headless(
+    mut app: app::App, mut exporters: export::Exporters,
+    remote_server: Option<remote::server::RemoteServer>,
+    used_widgets: app::layout_manager::UsedWidgets,
+) -> Result<()> {
+    #[allow(clippy::mutex_atomic)]
+    let thread_termination_lock = Arc::new(Mutex::new(false));
+    let thread_termination_cvar = Arc::new(Condvar::new());
+
+    let (sender, receiver) = mpsc::channel();
+    let (_collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
+    let _collection_thread = create_collection_thread(
+        sender,
+        collection_thread_ctrl_receiver,
+        thread_termination_lock.clone(),
+        thread_termination_cvar.clone(),
+        &app.app_config_fields,
+        app.filters.clone(),
+        used_widgets,
+    );
+
+    let is_terminated = Arc::new(AtomicBool::new(false));
+    let ist_clone = is_terminated.clone();
+    ctrlc::set_handler(move || {
+        ist_clone.store(true, Ordering::SeqCst);
+    })?;
+
+    // There is no cleaning thread, so stale data is cleaned here instead.
+    const CLEAN_INTERVAL: Duration =
+        Duration::from_millis(constants::STALE_MAX_MILLISECONDS + 60000);
+    let mut last_clean = Instant::now();
+
+    while !is_terminated.load(Ordering::SeqCst) {
+        if let Some(remote_server) = &remote_server {
+            if remote_server.is_closed() {
+                break;
+            }
+        }
+
+        if let Ok(BottomEvent::Update(data)) =
+            receiver.recv_timeout(Duration::from_millis(TICK_RATE_IN_MILLISECONDS))
+        {
+            if let Some(remote_server) = &remote_server {
+                remote_server.publish(&data);
+            }
+            if let Err(err) = exporters.record(&data) {
+                eprintln!("Unable to write the recording: {}", err);
+            }
+
+            app.data_collection.eat_data(data);
+            if let Err(err) = exporters.export(
+                &app.data_collection,
+                &app.app_config_fields.temperature_type,
+            ) {
+                eprintln!("Unable to export the metrics: {}", err);
+            }
+        }
+
+        if last_clean.elapsed() >= CLEAN_INTERVAL {
+            app.data_collection
+                .clean_data(constants::STALE_MAX_MILLISECONDS);
+            last_clean = Instant::now();
+        }
+    }
+
+    *thread_termination_lock.lock().unwrap() = true;
+    thread_termination_cvar.notify_all();
+
+    Ok(())
+}
@@ -19,11 +19,16 @@ use widgets::*;
 use crate::{
     app::{
         self,
-        layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
+        data_harvester::temperature::ThrottleStatus,
+        layout_manager::{BottomColRow, BottomLayout, BottomWidget, BottomWidgetType},
+        metric_alerts::AlertSeverity,
         App,
     },
     constants::*,
-    data_conversion::{ConvertedBatteryData, ConvertedCpuData, ConvertedProcessData},
+    data_conversion::{
+        ConvertedBatteryData, ConvertedCpuData, ConvertedInterfaceData, ConvertedPowerData,
+        ConvertedProcessData, ConvertedStackedMemData, CpuLegendEntry,
+    },
     options::Config,
     utils::error,
     utils::error::BottomError,
@@ -31,9 +36,13 @@ use crate::{
 };
 
 mod canvas_colours;
+mod components;
 mod dialogs;
 mod drawing_utils;
+mod gradient;
 mod screens;
+pub mod snapshot;
+mod time_chart;
 mod widgets;
 
 /// Point is of time, data
@@ -45,25 +54,51 @@ pub struct DisplayableData {
     pub tx_display: String,
     pub total_rx_display: String,
     pub total_tx_display: String,
+    pub net_errors_display: String,
+    pub are_net_errors_increased: bool,
+    pub wireless_display: Vec<String>,
+    pub tcp_counts_display: Option<String>,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
-    pub disk_data: Vec<Vec<String>>,
+    pub network_interface_data: Vec<ConvertedInterfaceData>,
+    pub disk_data: HashMap<u64, Vec<Vec<String>>>, // Key is the widget ID
     pub temp_sensor_data: Vec<Vec<String>>,
+    pub temp_throttle_status: Option<ThrottleStatus>,
+    pub connection_data: HashMap<u64, Vec<Vec<String>>>, // Key is the widget ID
+    pub session_data: Vec<Vec<String>>,
+    pub activity_data: Vec<Vec<String>>,
+    pub pod_data: Vec<Vec<String>>,
+    pub vm_data: Vec<Vec<String>>,
+    pub numa_data: Vec<Vec<String>>,
+    pub unit_data: Vec<(Vec<String>, bool)>, // Represents the row and whether the unit failed
+    pub kernel_log_data: Vec<(Vec<String>, u8)>, // Represents the row and the message's severity
+    pub alert_data: Vec<(Vec<String>, Option<AlertSeverity>)>, // Represents the row and the severity of the alert if it is active
     pub single_process_data: HashMap<Pid, ConvertedProcessData>, // Contains single process data, key is PID
     pub finalized_process_data_map: HashMap<u64, Vec<ConvertedProcessData>>, // What's actually displayed, key is the widget ID.
     pub stringified_process_data_map: HashMap<u64, Vec<(Vec<(String, Option<String>)>, bool)>>, // Represents the row and whether it is disabled, key is the widget ID
+    pub thread_data: Vec<Vec<String>>, // The threads of the process being shown in the thread dialog
 
     pub mem_labels: Option<(String, String)>,
     pub swap_labels: Option<(String, String)>,
+    pub huge_pages_labels: Option<(String, String)>,
+    pub shared_memory_labels: Option<(String, String)>,
+    pub cache_labels: Option<(String, String)>,
 
     pub mem_data: Vec<Point>, // TODO: Switch this and all data points over to a better data structure...
     pub swap_data: Vec<Point>,
+    pub stacked_mem_data: ConvertedStackedMemData,
     pub load_avg_data: [f32; 3],
     pub cpu_data: Vec<ConvertedCpuData>,
+    /// The average usage of each group of cores, if the cores are grouped.
+    pub cpu_group_data: Vec<ConvertedCpuData>,
+    /// The order of the CPU legend, which lists each group before its cores.
+    pub cpu_legend_entries: Vec<CpuLegendEntry>,
+    pub cpu_frequency_data: Vec<Point>,
+    pub power_data: Vec<ConvertedPowerData>,
     pub battery_data: Vec<ConvertedBatteryData>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ColourScheme {
     Default,
     DefaultLight,
@@ -71,7 +106,39 @@ pub enum ColourScheme {
     GruvboxLight,
     Nord,
     NordLight,
+    Dracula,
+    /// The colours in `[colors]`.
     Custom,
+    /// One of the themes in `[themes]`, by name.
+    Theme(String),
+}
+
+impl ColourScheme {
+    /// The built-in colour schemes, in the order they are switched between.
+    pub const BUILT_IN: [ColourScheme; 7] = [
+        ColourScheme::Default,
+        ColourScheme::DefaultLight,
+        ColourScheme::Gruvbox,
+        ColourScheme::GruvboxLight,
+        ColourScheme::Nord,
+        ColourScheme::NordLight,
+        ColourScheme::Dracula,
+    ];
+
+    /// Returns the name that the colour scheme is picked with.
+    pub fn get_name(&self) -> &str {
+        match self {
+            ColourScheme::Default => "default",
+            ColourScheme::DefaultLight => "default-light",
+            ColourScheme::Gruvbox => "gruvbox",
+            ColourScheme::GruvboxLight => "gruvbox-light",
+            ColourScheme::Nord => "nord",
+            ColourScheme::NordLight => "nord-light",
+            ColourScheme::Dracula => "dracula",
+            ColourScheme::Custom => "custom",
+            ColourScheme::Theme(theme_name) => theme_name,
+        }
+    }
 }
 
 impl FromStr for ColourScheme {
@@ -86,8 +153,9 @@ impl FromStr for ColourScheme {
             "gruvbox-light" => Ok(ColourScheme::GruvboxLight),
             "nord" => Ok(ColourScheme::Nord),
             "nord-light" => Ok(ColourScheme::NordLight),
+            "dracula" => Ok(ColourScheme::Dracula),
             _ => Err(BottomError::ConfigError(format!(
-                "\"{}\" is an invalid built-in color scheme.",
+                "\"{}\" is neither a built-in color scheme nor a theme in your config file.",
                 s
             ))),
         }
@@ -115,8 +183,49 @@ impl Painter {
         widget_layout: BottomLayout, table_gap: u16, is_basic_mode: bool, config: &Config,
         colour_scheme: ColourScheme,
     ) -> anyhow::Result<Self> {
+        let mut painter = Painter {
+            colours: CanvasColours::default(),
+            height: 0,
+            width: 0,
+            styled_help_text: Vec::default(),
+            is_mac_os: cfg!(target_os = "macos"),
+            row_constraints: Vec::default(),
+            col_constraints: Vec::default(),
+            col_row_constraints: Vec::default(),
+            layout_constraints: Vec::default(),
+            widget_layout: BottomLayout::default(),
+            derived_widget_draw_locs: Vec::default(),
+            table_height_offset: if is_basic_mode { 2 } else { 4 } + table_gap,
+        };
+
+        painter.set_colour_scheme(config, &colour_scheme)?;
+        painter.set_widget_layout(widget_layout);
+
+        Ok(painter)
+    }
+
+    /// Switches to the colours of `colour_scheme`, keeping the current ones if they fail to apply.
+    fn set_colour_scheme(
+        &mut self, config: &Config, colour_scheme: &ColourScheme,
+    ) -> anyhow::Result<()> {
+        let previous_colours = std::mem::take(&mut self.colours);
+        let result = match colour_scheme {
+            ColourScheme::Custom => self.generate_config_colours(config),
+            ColourScheme::Theme(theme_name) => self.generate_theme_colours(config, theme_name),
+            _ => self.generate_colour_scheme(colour_scheme.clone()),
+        };
+        if result.is_err() {
+            self.colours = previous_colours;
+        }
+        self.complete_painter_init();
+
+        result
+    }
+
+    /// Switches to drawing `widget_layout`.
+    fn set_widget_layout(&mut self, widget_layout: BottomLayout) {
         // Now for modularity; we have to also initialize the base layouts!
-        // We want to do this ONCE and reuse; after this we can just construct
+        // We want to do this ONCE per layout and reuse; after this we can just construct
         // based on the console size.
 
         let mut row_constraints = Vec::new();
@@ -182,33 +291,28 @@ impl Painter {
             col_constraints.push(new_col_constraints);
         });
 
-        let mut painter = Painter {
-            colours: CanvasColours::default(),
-            height: 0,
-            width: 0,
-            styled_help_text: Vec::default(),
-            is_mac_os: cfg!(target_os = "macos"),
-            row_constraints,
-            col_constraints,
-            col_row_constraints,
-            layout_constraints,
-            widget_layout,
-            derived_widget_draw_locs: Vec::default(),
-            table_height_offset: if is_basic_mode { 2 } else { 4 } + table_gap,
-        };
+        self.row_constraints = row_constraints;
+        self.col_constraints = col_constraints;
+        self.col_row_constraints = col_row_constraints;
+        self.layout_constraints = layout_constraints;
+        self.widget_layout = widget_layout;
+        self.derived_widget_draw_locs = Vec::default();
+    }
 
-        if let ColourScheme::Custom = colour_scheme {
-            painter.generate_config_colours(config)?;
-        } else {
-            painter.generate_colour_scheme(colour_scheme)?;
+    fn generate_config_colours(&mut self, config: &Config) -> anyhow::Result<()> {
+        if let Some(colours) = &config.colors {
+            self.colours.set_colours_from_palette(colours)?;
         }
-        painter.complete_painter_init();
 
-        Ok(painter)
+        Ok(())
     }
 
-    fn generate_config_colours(&mut self, config: &Config) -> anyhow::Result<()> {
-        if let Some(colours) = &config.colors {
+    fn generate_theme_colours(&mut self, config: &Config, theme_name: &str) -> anyhow::Result<()> {
+        if let Some(colours) = config
+            .themes
+            .as_ref()
+            .and_then(|themes| themes.get(theme_name))
+        {
             self.colours.set_colours_from_palette(colours)?;
         }
 
@@ -240,8 +344,12 @@ impl Painter {
                 self.colours
                     .set_colours_from_palette(&*NORD_LIGHT_COLOUR_PALETTE)?;
             }
-            ColourScheme::Custom => {
-                // This case should never occur, just do nothing.
+            ColourScheme::Dracula => {
+                self.colours
+                    .set_colours_from_palette(&DRACULA_COLOUR_PALETTE)?;
+            }
+            ColourScheme::Custom | ColourScheme::Theme(_) => {
+                // These cases should never occur, just do nothing.
             }
         }
 
@@ -282,10 +390,31 @@ impl Painter {
         self.styled_help_text = styled_help_spans.into_iter().map(Spans::from).collect();
     }
 
-    fn draw_frozen_indicator<B: Backend>(&self, f: &mut Frame<'_, B>, draw_loc: Rect) {
+    fn draw_frozen_indicator<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        // Editing the layout takes over the line, as the keys to do so are harder to remember.
+        let text = if app_state.layout_edit_state.is_editing {
+            "Editing layout: H/J/K/L move, +/- resize, [/] resize row, t/T type, a add, d remove, Esc to finish".to_string()
+        } else {
+            let mut messages = Vec::new();
+            if app_state.is_frozen {
+                messages.push("Frozen, press 'f' to unfreeze".to_string());
+            }
+            if app_state.data_collection.history_instant.is_some() {
+                messages.push(format!(
+                    "Graphs end {}s ago, press ',' and '.' to scroll",
+                    app_state
+                        .data_collection
+                        .get_history_offset(app_state.is_frozen)
+                        / 1000
+                ));
+            }
+            messages.join(" | ")
+        };
         f.render_widget(
             Paragraph::new(Span::styled(
-                "Frozen, press 'f' to unfreeze",
+                text,
                 self.colours.currently_selected_text_style,
             )),
             Layout::default()
@@ -295,13 +424,76 @@ impl Painter {
         )
     }
 
+    /// Recolours the border of a widget by the most severe active alert on a metric that it shows,
+    /// unless it is selected, which would hide that it is.
+    fn draw_alert_highlight<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, widget: &BottomWidget, draw_loc: Rect,
+    ) {
+        let widget_id = widget.widget_id;
+        let is_selected = app_state.current_widget.widget_id == widget_id
+            || (widget.widget_type == BottomWidgetType::Cpu
+                && app_state.current_widget.widget_id == widget_id + 1);
+        // Widgets of remote hosts do not show the metrics of the local one.
+        let is_remote = match widget.widget_type {
+            BottomWidgetType::Cpu => app_state
+                .cpu_state
+                .widget_states
+                .get(&widget_id)
+                .and_then(|cpu_widget_state| cpu_widget_state.host)
+                .is_some(),
+            BottomWidgetType::Mem => app_state
+                .mem_state
+                .widget_states
+                .get(&widget_id)
+                .and_then(|mem_widget_state| mem_widget_state.host)
+                .is_some(),
+            _ => false,
+        };
+        if is_selected || is_remote {
+            return;
+        }
+
+        if let Some(style) = app_state
+            .metric_alerts
+            .get_active_severity(&widget.widget_type)
+            .and_then(|severity| self.colours.get_alert_style(severity))
+        {
+            f.render_widget(drawing_utils::BorderHighlight { style }, draw_loc);
+        }
+    }
+
     pub fn draw_data<B: Backend>(
         &mut self, terminal: &mut Terminal<B>, app_state: &mut app::App,
     ) -> error::Result<()> {
         use BottomWidgetType::*;
 
-        terminal.draw(|f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.is_frozen {
+        if let Some(widget_layout) = app_state.layout_edit_state.new_layout.take() {
+            self.set_widget_layout(widget_layout);
+            app_state.is_force_redraw = true;
+        }
+
+        if app_state.colour_scheme_state.is_outdated {
+            app_state.colour_scheme_state.is_outdated = false;
+            if let Some(colour_scheme) = app_state.colour_scheme_state.get_current().cloned() {
+                if let Err(err) = self.set_colour_scheme(&app_state.config, &colour_scheme) {
+                    app_state.show_toast(
+                        format!(
+                            "Failed to switch to \"{}\": {}",
+                            colour_scheme.get_name(),
+                            err
+                        ),
+                        true,
+                    );
+                }
+            }
+            app_state.is_force_redraw = true;
+        }
+
+        let frame = terminal.draw(|f| {
+            let (terminal_size, frozen_draw_loc) = if app_state.is_frozen
+                || app_state.layout_edit_state.is_editing
+                || app_state.data_collection.history_instant.is_some()
+            {
                 let split_loc = Layout::default()
                     .constraints([Constraint::Min(0), Constraint::Length(1)])
                     .split(f.size());
@@ -373,6 +565,264 @@ impl Painter {
                     .split(vertical_dialog_chunk[1]);
 
                 self.draw_help_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.thread_dialog_state.is_showing_threads {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(15),
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(15),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_thread_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_details_dialog_state.is_showing_details {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(15),
+                            Constraint::Percentage(70),
+                            Constraint::Percentage(15),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_details_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.open_files_dialog_state.is_showing_open_files {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(15),
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(15),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(15),
+                            Constraint::Percentage(70),
+                            Constraint::Percentage(15),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_open_files_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.renice_dialog_state.is_showing_renice {
+                let renice_height = 9;
+                let border_len = terminal_height.saturating_sub(renice_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(renice_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(25),
+                            Constraint::Percentage(50),
+                            Constraint::Percentage(25),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_renice_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.global_filter.is_editing {
+                let global_filter_height = 10;
+                let border_len = terminal_height.saturating_sub(global_filter_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(global_filter_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_global_filter_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.io_priority_dialog_state.is_showing_io_priority {
+                let io_priority_height = 9;
+                let border_len = terminal_height.saturating_sub(io_priority_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(io_priority_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(25),
+                            Constraint::Percentage(50),
+                            Constraint::Percentage(25),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_io_priority_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.affinity_dialog_state.is_showing_affinity {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(20),
+                        Constraint::Percentage(60),
+                        Constraint::Percentage(20),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(30),
+                            Constraint::Percentage(40),
+                            Constraint::Percentage(30),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_affinity_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.columns_dialog_state.is_showing_columns {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(20),
+                        Constraint::Percentage(60),
+                        Constraint::Percentage(20),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(30),
+                            Constraint::Percentage(40),
+                            Constraint::Percentage(30),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_columns_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.filters_dialog_state.is_showing_filters {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(20),
+                        Constraint::Percentage(60),
+                        Constraint::Percentage(20),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(30),
+                            Constraint::Percentage(40),
+                            Constraint::Percentage(30),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_filters_dialog(f, app_state, middle_dialog_chunk[1]);
             } else if app_state.delete_dialog_state.is_showing_dd {
                 // TODO: This needs the paragraph wrap feature from tui-rs to be pushed to complete... but for now it's pretty close!
                 // The main problem right now is that I cannot properly calculate the height offset since
@@ -394,6 +844,24 @@ impl Painter {
                     7
                 } else {
                     22
+                } + if app_state.delete_dialog_state.is_killing_tree {
+                    // Leave room for the list of PIDs.
+                    3
+                } else if app_state.delete_dialog_state.is_killing_tagged {
+                    // Leave room for the lists of names and PIDs.
+                    4
+                } else if app_state.is_grouped(app_state.current_widget.widget_id) {
+                    // Leave room for the list of PIDs.
+                    3
+                } else {
+                    0
+                } + if cfg!(target_family = "unix")
+                    && !app_state.app_config_fields.is_advanced_kill
+                {
+                    // Leave room for the signal that will be sent.
+                    1
+                } else {
+                    0
                 };
 
                 // let (text_width, text_height) = if let Some(dd_text) = &dd_text {
@@ -452,7 +920,7 @@ impl Painter {
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
             } else if app_state.is_expanded {
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                    self.draw_frozen_indicator(f, app_state, frozen_draw_loc);
                 }
 
                 let rect = Layout::default()
@@ -467,7 +935,32 @@ impl Painter {
                         rect[0],
                         app_state.current_widget.widget_id - 1,
                     ),
-                    Mem | BasicMem => self.draw_memory_graph(
+                    Mem => self.draw_memory_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    // The basic widgets have no graphs to show, so they just get more room.
+                    BasicCpu => self.draw_basic_cpu(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    BasicMem => self.draw_basic_memory(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    BasicNet => self.draw_basic_network(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Power => self.draw_power_graph(
                         f,
                         app_state,
                         rect[0],
@@ -487,6 +980,75 @@ impl Painter {
                         true,
                         app_state.current_widget.widget_id,
                     ),
+                    Conn => self.draw_connection_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Users => self.draw_users_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Activity => self.draw_activity_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Pods => self.draw_pods_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Vm => self.draw_vm_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Numa => self.draw_numa_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Units => self.draw_units_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    KernelLog => self.draw_kernel_log_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Alerts => self.draw_alerts_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    CpuHeatmap => self.draw_cpu_heatmap(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     Net => self.draw_network_graph(
                         f,
                         app_state,
@@ -524,7 +1086,7 @@ impl Painter {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                    self.draw_frozen_indicator(f, app_state, frozen_draw_loc);
                 }
 
                 let actual_cpu_data_len = app_state.canvas_data.cpu_data.len().saturating_sub(1);
@@ -533,6 +1095,10 @@ impl Painter {
                 let cpu_height = {
                     let c = (actual_cpu_data_len / 4) as u16
                         + (if actual_cpu_data_len % 4 == 0 { 0 } else { 1 });
+                    let c = match app_state.app_config_fields.basic_cpu_rows {
+                        Some(basic_cpu_rows) => c.min(basic_cpu_rows),
+                        None => c,
+                    };
 
                     if c <= 1 {
                         1
@@ -621,13 +1187,13 @@ impl Painter {
             } else {
                 // Draws using the passed in (or default) layout.
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                    self.draw_frozen_indicator(f, app_state, frozen_draw_loc);
                 }
 
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
                     let draw_locs = Layout::default()
                         .margin(0)
-                        .constraints(self.row_constraints.as_ref())
+                        .constraints::<&[Constraint]>(self.row_constraints.as_ref())
                         .direction(Direction::Vertical)
                         .split(terminal_size);
 
@@ -648,7 +1214,7 @@ impl Painter {
                         )| {
                             izip!(
                                 Layout::default()
-                                    .constraints(col_constraint.as_ref())
+                                    .constraints::<&[Constraint]>(col_constraint.as_ref())
                                     .direction(Direction::Horizontal)
                                     .split(draw_loc)
                                     .into_iter(),
@@ -659,7 +1225,7 @@ impl Painter {
                             .map(|(split_loc, constraint, col_constraint_vec, col_rows)| {
                                 izip!(
                                     Layout::default()
-                                        .constraints(constraint.as_ref())
+                                        .constraints::<&[Constraint]>(constraint.as_ref())
                                         .direction(Direction::Vertical)
                                         .split(split_loc)
                                         .into_iter(),
@@ -669,7 +1235,7 @@ impl Painter {
                                 .map(|(draw_loc, col_row_constraint_vec, widgets)| {
                                     // Note that col_row_constraint_vec CONTAINS the widget constraints
                                     let widget_draw_locs = Layout::default()
-                                        .constraints(col_row_constraint_vec.as_ref())
+                                        .constraints::<&[Constraint]>(col_row_constraint_vec.as_ref())
                                         .direction(Direction::Horizontal)
                                         .split(draw_loc);
 
@@ -706,8 +1272,28 @@ impl Painter {
                         });
                 }
             }
+
+            if let Some(toast) = app_state.get_toast() {
+                self.draw_toast(f, toast, terminal_size);
+            }
         })?;
 
+        if app_state.is_snapshot_requested {
+            app_state.is_snapshot_requested = false;
+            match snapshot::write_snapshot(
+                frame.buffer,
+                app_state.app_config_fields.snapshot_format,
+                &app_state.app_config_fields.snapshot_dir,
+            ) {
+                Ok(path) => {
+                    app_state.show_toast(format!("Saved a snapshot to {}", path.display()), false)
+                }
+                Err(err) => {
+                    app_state.show_toast(format!("Failed to save a snapshot: {}", err), true)
+                }
+            }
+        }
+
         if let Some(updated_current_widget) = app_state
             .widget_map
             .get(&app_state.current_widget.widget_id)
@@ -732,6 +1318,9 @@ impl Painter {
                     Empty => {}
                     Cpu => self.draw_cpu(f, app_state, *widget_draw_loc, widget.widget_id),
                     Mem => self.draw_memory_graph(f, app_state, *widget_draw_loc, widget.widget_id),
+                    Power => {
+                        self.draw_power_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
                     Net => self.draw_network(f, app_state, *widget_draw_loc, widget.widget_id),
                     Temp => {
                         self.draw_temp_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
@@ -739,6 +1328,60 @@ impl Painter {
                     Disk => {
                         self.draw_disk_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
                     }
+                    Conn => self.draw_connection_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    Users => self.draw_users_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    Activity => self.draw_activity_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    Pods => {
+                        self.draw_pods_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                    }
+                    Vm => {
+                        self.draw_vm_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                    }
+                    Numa => {
+                        self.draw_numa_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                    }
+                    Units => self.draw_units_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    KernelLog => self.draw_kernel_log_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    Alerts => self.draw_alerts_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    CpuHeatmap => {
+                        self.draw_cpu_heatmap(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
                     Proc => self.draw_process_features(
                         f,
                         app_state,
@@ -755,6 +1398,7 @@ impl Painter {
                     ),
                     _ => {}
                 }
+                self.draw_alert_highlight(f, app_state, widget, *widget_draw_loc);
             }
         }
     }
@@ -1,7 +1,9 @@
-use crate::{options::ConfigColours, utils::error};
+use crate::{
+    app::metric_alerts::AlertSeverity, canvas::gradient::*, options::ConfigColours, utils::error,
+};
 use anyhow::Context;
 use colour_utils::*;
-use tui::style::{Color, Style};
+use tui::style::{Color, Modifier, Style};
 mod colour_utils;
 
 pub struct CanvasColours {
@@ -11,6 +13,7 @@ pub struct CanvasColours {
     pub table_header_style: Style,
     pub ram_style: Style,
     pub swap_style: Style,
+    pub cache_style: Style,
     pub rx_style: Style,
     pub tx_style: Style,
     pub total_rx_style: Style,
@@ -23,9 +26,24 @@ pub struct CanvasColours {
     pub text_style: Style,
     pub widget_title_style: Style,
     pub graph_style: Style,
+    /// The colours that the CPU and memory graph lines and the CPU heatmap cells go through as their
+    /// values rise, if set.
+    pub graph_gradient_colours: Vec<Color>,
+    /// The colours of the CPU heatmap cells, from idle to fully used, unless a graph gradient is set.
+    pub heatmap_colours: Vec<Color>,
     pub high_battery_colour: Style,
     pub medium_battery_colour: Style,
     pub low_battery_colour: Style,
+    pub zombie_process_style: Style,
+    pub uninterruptible_process_style: Style,
+    pub stopped_process_style: Style,
+    pub tagged_process_style: Style,
+    pub alerted_process_style: Style,
+    pub critical_alert_style: Style,
+    pub search_match_style: Style,
+    pub failed_unit_style: Style,
+    pub kernel_error_style: Style,
+    pub kernel_warning_style: Style,
     pub invalid_query_style: Style,
     pub disabled_text_style: Style,
 }
@@ -43,6 +61,7 @@ impl Default for CanvasColours {
             table_header_style: Style::default().fg(STANDARD_HIGHLIGHT_COLOUR),
             ram_style: Style::default().fg(STANDARD_FIRST_COLOUR),
             swap_style: Style::default().fg(STANDARD_SECOND_COLOUR),
+            cache_style: Style::default().fg(STANDARD_THIRD_COLOUR),
             rx_style: Style::default().fg(STANDARD_FIRST_COLOUR),
             tx_style: Style::default().fg(STANDARD_SECOND_COLOUR),
             total_rx_style: Style::default().fg(STANDARD_THIRD_COLOUR),
@@ -66,9 +85,26 @@ impl Default for CanvasColours {
             text_style: Style::default().fg(text_colour),
             widget_title_style: Style::default().fg(text_colour),
             graph_style: Style::default().fg(text_colour),
+            graph_gradient_colours: Vec::new(),
+            heatmap_colours: get_gradient_colours(
+                &[(0, 175, 0), (215, 215, 0), (215, 0, 0)],
+                is_true_colour_supported(),
+            ),
             high_battery_colour: Style::default().fg(Color::Green),
             medium_battery_colour: Style::default().fg(Color::Yellow),
             low_battery_colour: Style::default().fg(Color::Red),
+            zombie_process_style: Style::default().fg(Color::Red),
+            uninterruptible_process_style: Style::default().fg(Color::Yellow),
+            stopped_process_style: Style::default().fg(Color::Cyan),
+            tagged_process_style: Style::default().fg(Color::Magenta),
+            alerted_process_style: Style::default().fg(Color::LightRed),
+            critical_alert_style: Style::default().fg(Color::Red),
+            search_match_style: Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            failed_unit_style: Style::default().fg(Color::Red),
+            kernel_error_style: Style::default().fg(Color::Red),
+            kernel_warning_style: Style::default().fg(Color::Yellow),
             invalid_query_style: Style::default().fg(tui::style::Color::Red),
             disabled_text_style: Style::default().fg(Color::DarkGray),
         }
@@ -76,6 +112,16 @@ impl Default for CanvasColours {
 }
 
 impl CanvasColours {
+    /// Returns the style of active alerts of `severity`, and of the widgets showing their metrics,
+    /// or `None` if they are not highlighted.
+    pub fn get_alert_style(&self, severity: AlertSeverity) -> Option<Style> {
+        match severity {
+            AlertSeverity::Info => None,
+            AlertSeverity::Warning => Some(self.alerted_process_style),
+            AlertSeverity::Critical => Some(self.critical_alert_style),
+        }
+    }
+
     pub fn set_colours_from_palette(&mut self, colours: &ConfigColours) -> anyhow::Result<()> {
         if let Some(border_color) = &colours.border_color {
             self.set_border_colour(border_color)
@@ -117,6 +163,11 @@ impl CanvasColours {
                 .context("Update 'swap_color' in your config file..")?;
         }
 
+        if let Some(cache_color) = &colours.cache_color {
+            self.set_cache_colour(cache_color)
+                .context("Update 'cache_color' in your config file..")?;
+        }
+
         if let Some(rx_color) = &colours.rx_color {
             self.set_rx_colour(rx_color)
                 .context("Update 'rx_color' in your config file..")?;
@@ -152,6 +203,11 @@ impl CanvasColours {
                 .context("Update 'graph_color' in your config file..")?;
         }
 
+        if let Some(graph_gradient_colors) = &colours.graph_gradient_colors {
+            self.set_graph_gradient_colours(graph_gradient_colors)
+                .context("Update 'graph_gradient_colors' in your config file.")?;
+        }
+
         if let Some(high_battery_color) = &colours.high_battery_color {
             self.set_high_battery_color(high_battery_color)
                 .context("Update 'high_battery_color' in your config file.")?;
@@ -167,6 +223,56 @@ impl CanvasColours {
                 .context("Update 'low_battery_color' in your config file.")?;
         }
 
+        if let Some(zombie_process_color) = &colours.zombie_process_color {
+            self.set_zombie_process_colour(zombie_process_color)
+                .context("Update 'zombie_process_color' in your config file.")?;
+        }
+
+        if let Some(uninterruptible_process_color) = &colours.uninterruptible_process_color {
+            self.set_uninterruptible_process_colour(uninterruptible_process_color)
+                .context("Update 'uninterruptible_process_color' in your config file.")?;
+        }
+
+        if let Some(stopped_process_color) = &colours.stopped_process_color {
+            self.set_stopped_process_colour(stopped_process_color)
+                .context("Update 'stopped_process_color' in your config file.")?;
+        }
+
+        if let Some(tagged_process_color) = &colours.tagged_process_color {
+            self.set_tagged_process_colour(tagged_process_color)
+                .context("Update 'tagged_process_color' in your config file.")?;
+        }
+
+        if let Some(alerted_process_color) = &colours.alerted_process_color {
+            self.set_alerted_process_colour(alerted_process_color)
+                .context("Update 'alerted_process_color' in your config file.")?;
+        }
+
+        if let Some(critical_alert_color) = &colours.critical_alert_color {
+            self.set_critical_alert_colour(critical_alert_color)
+                .context("Update 'critical_alert_color' in your config file.")?;
+        }
+
+        if let Some(search_match_color) = &colours.search_match_color {
+            self.set_search_match_colour(search_match_color)
+                .context("Update 'search_match_color' in your config file.")?;
+        }
+
+        if let Some(failed_unit_color) = &colours.failed_unit_color {
+            self.set_failed_unit_colour(failed_unit_color)
+                .context("Update 'failed_unit_color' in your config file.")?;
+        }
+
+        if let Some(kernel_error_color) = &colours.kernel_error_color {
+            self.set_kernel_error_colour(kernel_error_color)
+                .context("Update 'kernel_error_color' in your config file.")?;
+        }
+
+        if let Some(kernel_warning_color) = &colours.kernel_warning_color {
+            self.set_kernel_warning_colour(kernel_warning_color)
+                .context("Update 'kernel_warning_color' in your config file.")?;
+        }
+
         if let Some(disabled_text_color) = &colours.disabled_text_color {
             self.set_disabled_text_colour(disabled_text_color)
                 .context("Update 'disabled_text_color' in your config file.")?;
@@ -220,6 +326,11 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_cache_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.cache_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
     pub fn set_rx_colour(&mut self, colour: &str) -> error::Result<()> {
         self.rx_style = get_style_from_config(colour)?;
         Ok(())
@@ -284,6 +395,28 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_graph_gradient_colours(&mut self, colours: &[String]) -> error::Result<()> {
+        if colours.len() < 2 {
+            return Err(error::BottomError::ConfigError(
+                "a gradient needs at least two colours.".to_string(),
+            ));
+        }
+
+        let stops = colours
+            .iter()
+            .map(|colour| {
+                get_rgb_of_colour(get_colour_from_config(colour)?).ok_or_else(|| {
+                    error::BottomError::ConfigError(format!(
+                        "\"{}\" cannot be used in a gradient.",
+                        colour
+                    ))
+                })
+            })
+            .collect::<error::Result<Vec<_>>>()?;
+        self.graph_gradient_colours = get_gradient_colours(&stops, is_true_colour_supported());
+        Ok(())
+    }
+
     pub fn set_high_battery_color(&mut self, colour: &str) -> error::Result<()> {
         self.high_battery_colour = get_style_from_config(colour)?;
         Ok(())
@@ -298,4 +431,55 @@ impl CanvasColours {
         self.low_battery_colour = get_style_from_config(colour)?;
         Ok(())
     }
+
+    pub fn set_zombie_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.zombie_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_uninterruptible_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.uninterruptible_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_stopped_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.stopped_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_tagged_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.tagged_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_alerted_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.alerted_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_critical_alert_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.critical_alert_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_search_match_colour(&mut self, colour: &str) -> error::Result<()> {
+        // Keep matches bold so they still stand out in rows that are already coloured.
+        self.search_match_style = get_style_from_config(colour)?.add_modifier(Modifier::BOLD);
+        Ok(())
+    }
+
+    pub fn set_failed_unit_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.failed_unit_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_kernel_error_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.kernel_error_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_kernel_warning_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.kernel_warning_style = get_style_from_config(colour)?;
+        Ok(())
+    }
 }
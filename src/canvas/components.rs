@@ -0,0 +1,6 @@
+pub mod text_table;
+
+pub use text_table::{
+    TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableOptions, TextTableRow,
+    TextTableSort,
+};
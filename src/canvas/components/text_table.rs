@@ -0,0 +1,429 @@
+//! A scrolling table of text, whose columns are fit to their entries and the space there is.
+//!
+//! The widgets and dialogs that show one only say what its columns are, where its rows and state
+//! are, and what each row shows; scrolling, sorting arrows, column widths, and cutting entries
+//! short are all handled here.
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Row, Table},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{App, AppScrollWidgetState, CanvasTableWidthState},
+    canvas::{
+        drawing_utils::{get_column_widths, get_start_position},
+        Painter,
+    },
+    constants::*,
+};
+
+const DOWN_ARROW: char = '▼';
+const UP_ARROW: char = '▲';
+
+/// A column of a text table.
+#[derive(Debug, Clone, Copy)]
+pub struct TextTableColumn {
+    pub header: &'static str,
+    /// The width of the column, if it is always the same.
+    pub hard_width: Option<u16>,
+    /// The largest share of the width of the table that the column grows to, to fit its entries.
+    pub soft_width: Option<f64>,
+}
+
+impl TextTableColumn {
+    /// A column that is always `width` wide.
+    pub const fn hard(header: &'static str, width: u16) -> Self {
+        TextTableColumn {
+            header,
+            hard_width: Some(width),
+            soft_width: None,
+        }
+    }
+
+    /// A column that grows to fit its entries, up to `max_share` of the width of the table, past
+    /// which they are cut short.
+    pub const fn soft(header: &'static str, max_share: f64) -> Self {
+        TextTableColumn {
+            header,
+            hard_width: None,
+            soft_width: Some(max_share),
+        }
+    }
+}
+
+/// What a row of a text table shows.
+pub struct TextTableRow<'a> {
+    /// The entries of the row, in the order of the columns.
+    pub entries: &'a [String],
+    /// The style of the row, if it stands out from the others.
+    pub style: Option<Style>,
+}
+
+impl<'a> TextTableRow<'a> {
+    pub fn new(entries: &'a [String]) -> Self {
+        TextTableRow {
+            entries,
+            style: None,
+        }
+    }
+
+    pub fn with_style(mut self, style: Option<Style>) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// The column that the rows of a text table are sorted by, which is marked with an arrow.
+#[derive(Debug, Clone, Copy)]
+pub struct TextTableSort {
+    pub column: usize,
+    pub is_descending: bool,
+}
+
+/// The columns and rows of a text table, and where it keeps its state between draws.
+pub struct TextTableData<'a, T> {
+    pub columns: &'a [TextTableColumn],
+    pub rows: &'a [T],
+    pub sort: Option<TextTableSort>,
+    pub scroll_state: &'a mut AppScrollWidgetState,
+    pub width_state: &'a mut CanvasTableWidthState,
+}
+
+impl<'a, T> TextTableData<'a, T> {
+    pub fn new(
+        columns: &'a [TextTableColumn], rows: &'a [T], scroll_state: &'a mut AppScrollWidgetState,
+        width_state: &'a mut CanvasTableWidthState,
+    ) -> Self {
+        TextTableData {
+            columns,
+            rows,
+            sort: None,
+            scroll_state,
+            width_state,
+        }
+    }
+
+    pub fn sort(mut self, sort: TextTableSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+}
+
+/// How a text table is laid out and highlighted.
+pub struct TextTableOptions {
+    /// The gap between the header and the rows.
+    pub table_gap: u16,
+    pub highlight_style: Style,
+    /// Whether to fit the columns to the rows again, rather than keeping their last widths.
+    pub recalculate_widths: bool,
+    pub is_force_redraw: bool,
+}
+
+/// How a text table is framed when it is drawn as a widget.
+pub struct TextTableFrame<'a> {
+    pub widget_id: u64,
+    /// The name of the widget, which its title starts with.
+    pub name: &'a str,
+    /// What the title ends with, like the search query.
+    pub title_extra: String,
+    pub draw_border: bool,
+}
+
+pub trait TextTable {
+    /// Draws `table` within `block` at `draw_loc`, with each of its rows turned into what is shown
+    /// for it by `to_row`.
+    fn draw_text_table<B: Backend, T, F>(
+        &self, f: &mut Frame<'_, B>, draw_loc: Rect, block: Block<'_>, table: TextTableData<'_, T>,
+        options: TextTableOptions, to_row: F,
+    ) where
+        F: Fn(&T) -> TextTableRow<'_>;
+
+    /// Draws the table that `get_table` finds in `app_state` as a widget, along with its title
+    /// and borders, and records where it was drawn.  Nothing is drawn if there is no table.
+    fn draw_text_table_widget<B: Backend, T, G, F>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+        frame: TextTableFrame<'_>, get_table: G, to_row: F,
+    ) where
+        G: FnOnce(&mut App) -> Option<TextTableData<'_, T>>,
+        F: Fn(&T) -> TextTableRow<'_>;
+}
+
+impl TextTable for Painter {
+    fn draw_text_table<B: Backend, T, F>(
+        &self, f: &mut Frame<'_, B>, draw_loc: Rect, block: Block<'_>, table: TextTableData<'_, T>,
+        options: TextTableOptions, to_row: F,
+    ) where
+        F: Fn(&T) -> TextTableRow<'_>,
+    {
+        let TextTableData {
+            columns,
+            rows,
+            sort,
+            scroll_state,
+            width_state,
+        } = table;
+
+        let start_position = get_start_position(
+            usize::from(
+                (draw_loc.height + (1 - options.table_gap))
+                    .saturating_sub(self.table_height_offset),
+            ),
+            &scroll_state.scroll_direction,
+            &mut scroll_state.previous_scroll_position,
+            scroll_state.current_scroll_position,
+            options.is_force_redraw,
+        );
+        scroll_state.table_state.select(Some(
+            scroll_state
+                .current_scroll_position
+                .saturating_sub(start_position),
+        ));
+        let sliced_rows = rows
+            .get(start_position..)
+            .unwrap_or(&[])
+            .iter()
+            .map(to_row)
+            .collect::<Vec<_>>();
+
+        let headers = columns
+            .iter()
+            .enumerate()
+            .map(|(column_index, column)| match sort {
+                Some(sort) if sort.column == column_index => format!(
+                    "{}{}",
+                    column.header,
+                    if sort.is_descending {
+                        DOWN_ARROW
+                    } else {
+                        UP_ARROW
+                    }
+                ),
+                _ => column.header.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let headers_lens = headers
+            .iter()
+            .map(|header| get_grapheme_count(header))
+            .collect::<Vec<_>>();
+
+        // Calculate widths
+        let hard_widths = columns
+            .iter()
+            .map(|column| column.hard_width)
+            .collect::<Vec<_>>();
+        if options.recalculate_widths {
+            width_state.desired_column_widths = {
+                let mut column_widths = headers_lens.clone();
+                for row in &sliced_rows {
+                    for (column_width, entry) in column_widths.iter_mut().zip(row.entries) {
+                        *column_width = (*column_width).max(get_grapheme_count(entry));
+                    }
+                }
+                column_widths
+            };
+            width_state.calculated_column_widths = get_column_widths(
+                draw_loc.width,
+                &hard_widths,
+                &(headers_lens.iter().map(|w| Some(*w)).collect::<Vec<_>>()),
+                &(columns
+                    .iter()
+                    .map(|column| column.soft_width)
+                    .collect::<Vec<_>>()),
+                &(width_state
+                    .desired_column_widths
+                    .iter()
+                    .map(|w| Some(*w))
+                    .collect::<Vec<_>>()),
+                true,
+            );
+        }
+
+        let dcw = &width_state.desired_column_widths;
+        let ccw = &width_state.calculated_column_widths;
+        let table_rows = sliced_rows.iter().map(|row| {
+            let truncated_data = row.entries.iter().enumerate().map(|(itx, entry)| {
+                // Only the columns that grow are cut short, as the others always fit.
+                if let (Some(None), Some(desired_col_width), Some(calculated_col_width)) =
+                    (hard_widths.get(itx), dcw.get(itx), ccw.get(itx))
+                {
+                    if *desired_col_width > *calculated_col_width && *calculated_col_width > 0 {
+                        return truncate_entry(entry, (*calculated_col_width).into());
+                    }
+                }
+
+                Text::raw(entry.as_str())
+            });
+
+            match row.style {
+                Some(style) => Row::new(truncated_data).style(style),
+                None => Row::new(truncated_data),
+            }
+        });
+
+        f.render_stateful_widget(
+            Table::new(table_rows)
+                .block(block)
+                .header(
+                    Row::new(headers)
+                        .style(self.colours.table_header_style)
+                        .bottom_margin(options.table_gap),
+                )
+                .highlight_style(options.highlight_style)
+                .style(self.colours.text_style)
+                .widths(
+                    &(ccw
+                        .iter()
+                        .map(|calculated_width| Constraint::Length(*calculated_width))
+                        .collect::<Vec<_>>()),
+                ),
+            draw_loc,
+            &mut scroll_state.table_state,
+        );
+    }
+
+    fn draw_text_table_widget<B: Backend, T, G, F>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+        frame: TextTableFrame<'_>, get_table: G, to_row: F,
+    ) where
+        G: FnOnce(&mut App) -> Option<TextTableData<'_, T>>,
+        F: Fn(&T) -> TextTableRow<'_>,
+    {
+        let is_on_widget = frame.widget_id == app_state.current_widget.widget_id;
+        let is_expanded = app_state.is_expanded;
+        let show_table_scroll_position = app_state.app_config_fields.show_table_scroll_position;
+        let (border_style, highlight_style) = if is_on_widget {
+            (
+                self.colours.highlighted_border_style,
+                self.colours.currently_selected_text_style,
+            )
+        } else {
+            (self.colours.border_style, self.colours.text_style)
+        };
+        let options = TextTableOptions {
+            table_gap: if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
+                0
+            } else {
+                app_state.app_config_fields.table_gap
+            },
+            highlight_style,
+            recalculate_widths: app_state.should_get_widget_bounds(),
+            is_force_redraw: app_state.is_force_redraw,
+        };
+        let recalculate_widths = options.recalculate_widths;
+
+        let table = match get_table(app_state) {
+            Some(table) => table,
+            None => return,
+        };
+
+        let title_base = if show_table_scroll_position {
+            let title_string = format!(
+                " {} ({} of {}) {}",
+                frame.name,
+                table.scroll_state.current_scroll_position.saturating_add(1),
+                table.rows.len(),
+                frame.title_extra,
+            );
+
+            if title_string.len() <= draw_loc.width.into() {
+                title_string
+            } else {
+                format!(" {} {}", frame.name, frame.title_extra)
+            }
+        } else {
+            format!(" {} {}", frame.name, frame.title_extra)
+        };
+
+        let title = if is_expanded {
+            const ESCAPE_ENDING: &str = "── Esc to go back ";
+
+            let (chosen_title_base, expanded_title_base) = {
+                let temp_title_base = format!("{}{}", title_base, ESCAPE_ENDING);
+
+                if temp_title_base.len() > draw_loc.width.into() {
+                    (
+                        format!(" {} ", frame.name),
+                        format!(" {} {}", frame.name, ESCAPE_ENDING),
+                    )
+                } else {
+                    (title_base, temp_title_base)
+                }
+            };
+
+            Spans::from(vec![
+                Span::styled(chosen_title_base, self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(
+                            usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(expanded_title_base.as_str(), true)
+                                    .count()
+                                    + 2
+                            )
+                        )
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(title_base, self.colours.widget_title_style))
+        };
+
+        let block = if frame.draw_border {
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style)
+        } else if is_on_widget {
+            Block::default()
+                .borders(SIDE_BORDERS)
+                .border_style(self.colours.highlighted_border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        let margined_draw_loc = Layout::default()
+            .constraints([Constraint::Percentage(100)])
+            .horizontal_margin(if is_on_widget || frame.draw_border {
+                0
+            } else {
+                1
+            })
+            .direction(Direction::Horizontal)
+            .split(draw_loc)[0];
+
+        self.draw_text_table(f, margined_draw_loc, block, table, options, to_row);
+
+        if recalculate_widths {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&frame.widget_id) {
+                widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                widget.bottom_right_corner = Some((
+                    margined_draw_loc.x + margined_draw_loc.width,
+                    margined_draw_loc.y + margined_draw_loc.height,
+                ));
+            }
+        }
+    }
+}
+
+fn get_grapheme_count(text: &str) -> u16 {
+    UnicodeSegmentation::graphemes(text, true).count() as u16
+}
+
+/// Cuts `entry` short with an ellipsis if it is wider than `width`.
+fn truncate_entry(entry: &str, width: usize) -> Text<'_> {
+    let graphemes = UnicodeSegmentation::graphemes(entry, true).collect::<Vec<&str>>();
+    if graphemes.len() > width && width > 1 {
+        Text::raw(format!("{}…", graphemes[..(width - 1)].concat()))
+    } else {
+        Text::raw(entry)
+    }
+}
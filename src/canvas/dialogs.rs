@@ -1,5 +1,25 @@
+pub mod affinity_dialog;
+pub mod columns_dialog;
 pub mod dd_dialog;
+pub mod filters_dialog;
+pub mod global_filter_dialog;
 pub mod help_dialog;
+pub mod io_priority_dialog;
+pub mod open_files_dialog;
+pub mod process_details_dialog;
+pub mod renice_dialog;
+pub mod thread_dialog;
+pub mod toast;
 
+pub use affinity_dialog::AffinityDialog;
+pub use columns_dialog::ColumnsDialog;
 pub use dd_dialog::KillDialog;
+pub use filters_dialog::FiltersDialog;
+pub use global_filter_dialog::GlobalFilterDialog;
 pub use help_dialog::HelpDialog;
+pub use io_priority_dialog::IoPriorityDialog;
+pub use open_files_dialog::OpenFilesDialog;
+pub use process_details_dialog::ProcessDetailsDialog;
+pub use renice_dialog::ReniceDialog;
+pub use thread_dialog::ThreadDialog;
+pub use toast::Toast;
@@ -0,0 +1,109 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::get_start_position, Painter},
+};
+
+const AFFINITY_HINT: &str = "Space to toggle, a to toggle all, ENTER to apply";
+
+pub trait AffinityDialog {
+    fn draw_affinity_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl AffinityDialog for Painter {
+    fn draw_affinity_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let affinity_state = &mut app_state.affinity_dialog_state;
+
+        let title_base = match &affinity_state.process {
+            Some((name, pids)) if pids.len() == 1 => {
+                format!(" CPU Affinity of {} ({}) ", name, pids[0])
+            }
+            Some((name, pids)) => format!(" CPU Affinity of {} ({} processes) ", name, pids.len()),
+            None => " CPU Affinity ".to_string(),
+        };
+        let title = Spans::from(vec![
+            Span::styled(title_base.clone(), self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                            + " Esc to close ".len()
+                            + 4
+                    ))
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+        let affinity_block = Block::default()
+            .title(title)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style);
+
+        let inner_loc = affinity_block.inner(draw_loc);
+        f.render_widget(affinity_block, draw_loc);
+
+        let split_loc = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner_loc);
+
+        let start_position = get_start_position(
+            usize::from(split_loc[0].height),
+            &affinity_state.scroll_state.scroll_direction,
+            &mut affinity_state.scroll_state.previous_scroll_position,
+            affinity_state.scroll_state.current_scroll_position,
+            app_state.is_force_redraw,
+        );
+        affinity_state.scroll_state.table_state.select(Some(
+            affinity_state
+                .scroll_state
+                .current_scroll_position
+                .saturating_sub(start_position),
+        ));
+
+        let cpu_rows = affinity_state
+            .cpus
+            .iter()
+            .enumerate()
+            .skip(start_position)
+            .map(|(cpu, is_allowed)| {
+                Row::new(vec![format!(
+                    "[{}] CPU{}",
+                    if *is_allowed { "x" } else { " " },
+                    cpu
+                )])
+            });
+
+        f.render_stateful_widget(
+            Table::new(cpu_rows)
+                .highlight_style(self.colours.currently_selected_text_style)
+                .style(self.colours.text_style)
+                .widths(&[Constraint::Percentage(100)]),
+            split_loc[0],
+            &mut affinity_state.scroll_state.table_state,
+        );
+
+        let hint = match &affinity_state.error {
+            Some(error) => Span::styled(error.as_str(), self.colours.invalid_query_style),
+            None => Span::styled(AFFINITY_HINT, self.colours.text_style),
+        };
+        f.render_widget(
+            Paragraph::new(Spans::from(hint)).wrap(Wrap { trim: true }),
+            split_loc[1],
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::get_start_position, Painter},
+};
+
+const COLUMNS_BASE: &str = " Process Columns ── Esc to close ";
+const COLUMNS_HINT: &str = "Space to toggle, [ ] to move, + - = to resize, w to save";
+
+pub trait ColumnsDialog {
+    fn draw_columns_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ColumnsDialog for Painter {
+    fn draw_columns_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let title = Spans::from(vec![
+            Span::styled(" Process Columns ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(COLUMNS_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+        let columns_block = Block::default()
+            .title(title)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style);
+
+        let inner_loc = columns_block.inner(draw_loc);
+        f.render_widget(columns_block, draw_loc);
+
+        let split_loc = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner_loc);
+
+        let columns_state = &mut app_state.columns_dialog_state;
+        let column_rows: Vec<Row<'_>> = match app_state
+            .proc_state
+            .widget_states
+            .get(&columns_state.widget_id)
+        {
+            Some(proc_widget_state) => {
+                let columns = &proc_widget_state.columns;
+                columns
+                    .get_choosable_columns()
+                    .iter()
+                    .map(|column| {
+                        Row::new(vec![
+                            format!(
+                                "[{}] {}",
+                                if columns.is_shown(column) { "x" } else { " " },
+                                column
+                            ),
+                            match columns.column_widths.get(column) {
+                                Some(width) => width.to_string(),
+                                None => "auto".to_string(),
+                            },
+                        ])
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        let start_position = get_start_position(
+            usize::from(split_loc[0].height),
+            &columns_state.scroll_state.scroll_direction,
+            &mut columns_state.scroll_state.previous_scroll_position,
+            columns_state.scroll_state.current_scroll_position,
+            app_state.is_force_redraw,
+        );
+        columns_state.scroll_state.table_state.select(Some(
+            columns_state
+                .scroll_state
+                .current_scroll_position
+                .saturating_sub(start_position),
+        ));
+
+        f.render_stateful_widget(
+            Table::new(column_rows.into_iter().skip(start_position))
+                .highlight_style(self.colours.currently_selected_text_style)
+                .style(self.colours.text_style)
+                .widths(&[Constraint::Min(16), Constraint::Length(6)]),
+            split_loc[0],
+            &mut columns_state.scroll_state.table_state,
+        );
+
+        let hint = match &columns_state.save_result {
+            Some(Ok(message)) => Span::styled(message.as_str(), self.colours.text_style),
+            Some(Err(error)) => Span::styled(error.as_str(), self.colours.invalid_query_style),
+            None => Span::styled(COLUMNS_HINT, self.colours.text_style),
+        };
+        f.render_widget(
+            Paragraph::new(Spans::from(hint)).wrap(Wrap { trim: true }),
+            split_loc[1],
+        );
+    }
+}
@@ -8,6 +8,8 @@ use tui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+#[cfg(target_family = "unix")]
+use crate::app::process_actions;
 use crate::{
     app::{App, KillSignal},
     canvas::Painter,
@@ -31,36 +33,111 @@ pub trait KillDialog {
 impl KillDialog for Painter {
     fn get_dd_spans(&self, app_state: &App) -> Option<Text<'_>> {
         if let Some(dd_err) = &app_state.dd_err {
+            let close_text = match (
+                &app_state.delete_dialog_state.privileged_retry,
+                &app_state.app_config_fields.privilege_helper,
+            ) {
+                (Some(_), Some(helper)) => Spans::from(format!(
+                    "Press ENTER to retry with \"{}\", or ESC to close this dialog.",
+                    helper
+                )),
+                _ => Spans::from("Please press ENTER or ESC to close this dialog."),
+            };
+
             return Some(Text::from(vec![
                 Spans::default(),
-                Spans::from("Failed to kill process."),
+                Spans::from(app_state.delete_dialog_state.failed_action.failure_text()),
                 Spans::from(dd_err.clone()),
-                Spans::from("Please press ENTER or ESC to close this dialog."),
+                close_text,
             ]));
         } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
             if let Some(first_pid) = to_kill_processes.1.first() {
-                return Some(Text::from(vec![
-                    Spans::from(""),
-                    if app_state.is_grouped(app_state.current_widget.widget_id) {
-                        if to_kill_processes.1.len() != 1 {
+                let pid_list = || {
+                    to_kill_processes
+                        .1
+                        .iter()
+                        .map(|pid| pid.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+
+                let lines = if app_state.delete_dialog_state.is_killing_tree {
+                    vec![
+                        Spans::from(""),
+                        Spans::from(format!(
+                            "Kill the process tree of \"{}\" ({} {})?  Press ENTER to confirm.",
+                            to_kill_processes.0,
+                            to_kill_processes.1.len(),
+                            if to_kill_processes.1.len() == 1 {
+                                "process"
+                            } else {
+                                "processes"
+                            }
+                        )),
+                        Spans::from(format!("PIDs: {}", pid_list())),
+                    ]
+                } else if app_state.delete_dialog_state.is_killing_tagged {
+                    vec![
+                        Spans::from(""),
+                        Spans::from(format!(
+                            "Kill {} tagged {}?  Press ENTER to confirm.",
+                            to_kill_processes.1.len(),
+                            if to_kill_processes.1.len() == 1 {
+                                "process"
+                            } else {
+                                "processes"
+                            }
+                        )),
+                        Spans::from(format!("Names: {}", to_kill_processes.0)),
+                        Spans::from(format!("PIDs: {}", pid_list())),
+                    ]
+                } else if app_state.is_grouped(app_state.current_widget.widget_id) {
+                    if to_kill_processes.1.len() != 1 {
+                        vec![
+                            Spans::from(""),
                             Spans::from(format!(
                                 "Kill {} processes with the name \"{}\"?  Press ENTER to confirm.",
                                 to_kill_processes.1.len(),
                                 to_kill_processes.0
-                            ))
-                        } else {
-                            Spans::from(format!(
-                                "Kill 1 process with the name \"{}\"?  Press ENTER to confirm.",
-                                to_kill_processes.0
-                            ))
-                        }
+                            )),
+                            Spans::from(format!("PIDs: {}", pid_list())),
+                        ]
                     } else {
+                        vec![
+                            Spans::from(""),
+                            Spans::from(format!(
+                                "Kill 1 process with the name \"{}\" (PID {})?  Press ENTER to confirm.",
+                                to_kill_processes.0, first_pid
+                            )),
+                        ]
+                    }
+                } else {
+                    vec![
+                        Spans::from(""),
                         Spans::from(format!(
                             "Kill process \"{}\" with PID {}?  Press ENTER to confirm.",
                             to_kill_processes.0, first_pid
-                        ))
-                    },
-                ]));
+                        )),
+                    ]
+                };
+
+                // The advanced kill menu lists the signals itself, so only say which signal the
+                // simple confirmation will send.
+                #[cfg(target_family = "unix")]
+                let lines = {
+                    let mut lines = lines;
+                    if !app_state.app_config_fields.is_advanced_kill {
+                        if let KillSignal::Kill(signal) = KillSignal::default() {
+                            lines.push(Spans::from(format!(
+                                "Signal: {}",
+                                process_actions::describe_signal(signal)
+                            )));
+                        }
+                    }
+                    lines
+                };
+
+                return Some(Text::from(lines));
             }
         }
 
@@ -139,134 +216,57 @@ impl KillDialog for Painter {
         } else {
             #[cfg(target_family = "unix")]
             {
-                let signal_text;
-                #[cfg(target_os = "linux")]
-                {
-                    signal_text = vec![
-                        "0: Cancel",
-                        "1: HUP",
-                        "2: INT",
-                        "3: QUIT",
-                        "4: ILL",
-                        "5: TRAP",
-                        "6: ABRT",
-                        "7: BUS",
-                        "8: FPE",
-                        "9: KILL",
-                        "10: USR1",
-                        "11: SEGV",
-                        "12: USR2",
-                        "13: PIPE",
-                        "14: ALRM",
-                        "15: TERM",
-                        "16: STKFLT",
-                        "17: CHLD",
-                        "18: CONT",
-                        "19: STOP",
-                        "20: TSTP",
-                        "21: TTIN",
-                        "22: TTOU",
-                        "23: URG",
-                        "24: XCPU",
-                        "25: XFSZ",
-                        "26: VTALRM",
-                        "27: PROF",
-                        "28: WINCH",
-                        "29: IO",
-                        "30: PWR",
-                        "31: SYS",
-                        "34: RTMIN",
-                        "35: RTMIN+1",
-                        "36: RTMIN+2",
-                        "37: RTMIN+3",
-                        "38: RTMIN+4",
-                        "39: RTMIN+5",
-                        "40: RTMIN+6",
-                        "41: RTMIN+7",
-                        "42: RTMIN+8",
-                        "43: RTMIN+9",
-                        "44: RTMIN+10",
-                        "45: RTMIN+11",
-                        "46: RTMIN+12",
-                        "47: RTMIN+13",
-                        "48: RTMIN+14",
-                        "49: RTMIN+15",
-                        "50: RTMAX-14",
-                        "51: RTMAX-13",
-                        "52: RTMAX-12",
-                        "53: RTMAX-11",
-                        "54: RTMAX-10",
-                        "55: RTMAX-9",
-                        "56: RTMAX-8",
-                        "57: RTMAX-7",
-                        "58: RTMAX-6",
-                        "59: RTMAX-5",
-                        "60: RTMAX-4",
-                        "61: RTMAX-3",
-                        "62: RTMAX-2",
-                        "63: RTMAX-1",
-                        "64: RTMAX",
-                    ];
-                }
-                #[cfg(target_os = "macos")]
-                {
-                    signal_text = vec![
-                        "0: Cancel",
-                        "1: HUP",
-                        "2: INT",
-                        "3: QUIT",
-                        "4: ILL",
-                        "5: TRAP",
-                        "6: ABRT",
-                        "7: EMT",
-                        "8: FPE",
-                        "9: KILL",
-                        "10: BUS",
-                        "11: SEGV",
-                        "12: SYS",
-                        "13: PIPE",
-                        "14: ALRM",
-                        "15: TERM",
-                        "16: URG",
-                        "17: STOP",
-                        "18: TSTP",
-                        "19: CONT",
-                        "20: CHLD",
-                        "21: TTIN",
-                        "22: TTOU",
-                        "23: IO",
-                        "24: XCPU",
-                        "25: XFSZ",
-                        "26: VTALRM",
-                        "27: PROF",
-                        "28: WINCH",
-                        "29: INFO",
-                        "30: USR1",
-                        "31: USR2",
-                    ];
-                }
+                let signals = process_actions::get_matching_signals(
+                    &app_state.delete_dialog_state.signal_search_query,
+                );
+                let signal_text = signals
+                    .iter()
+                    .map(|(signal, name)| format!("{}: {}", signal, name))
+                    .collect::<Vec<_>>();
+
+                let search_split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+                    .split(*button_draw_loc);
+
+                let search_text = if app_state.delete_dialog_state.is_searching_signals {
+                    Spans::from(vec![
+                        Span::styled("Search: ", self.colours.table_header_style),
+                        Span::raw(app_state.delete_dialog_state.signal_search_query.as_str()),
+                        Span::styled(" ", self.colours.currently_selected_text_style),
+                    ])
+                } else {
+                    Spans::from(Span::styled(
+                        "Press / to search signals",
+                        self.colours.table_header_style,
+                    ))
+                };
+                f.render_widget(
+                    Paragraph::new(search_text).alignment(Alignment::Center),
+                    search_split[0],
+                );
 
                 let button_rect = Layout::default()
                     .direction(Direction::Horizontal)
-                    .margin(1)
                     .constraints(
                         [
-                            Constraint::Length((button_draw_loc.width - 14) / 2),
+                            Constraint::Length((search_split[1].width - 12) / 2),
                             Constraint::Min(0),
-                            Constraint::Length((button_draw_loc.width - 14) / 2),
+                            Constraint::Length((search_split[1].width - 12) / 2),
                         ]
                         .as_ref(),
                     )
-                    .split(*button_draw_loc)[1];
+                    .split(search_split[1])[1];
 
-                let mut selected = match app_state.delete_dialog_state.selected_signal {
+                let selected_signal = match app_state.delete_dialog_state.selected_signal {
                     KillSignal::Cancel => 0,
                     KillSignal::Kill(signal) => signal,
                 };
-                // 32+33 are skipped
-                if selected > 31 {
-                    selected -= 2;
-                }
+                let selected = signals
+                    .iter()
+                    .position(|(signal, _name)| *signal == selected_signal)
+                    .unwrap_or(0);
 
                 let layout = Layout::default()
                     .direction(Direction::Vertical)
@@ -288,24 +288,28 @@ impl KillDialog for Painter {
                 let mut buttons = signal_text
                     [scroll_offset + 1..min((layout.len()) + scroll_offset, signal_text.len())]
                     .iter()
-                    .map(|text| Span::raw(*text))
+                    .map(|text| Span::raw(text.as_str()))
                     .collect::<Vec<Span<'_>>>();
-                buttons.insert(0, Span::raw(signal_text[0]));
+                buttons.insert(0, Span::raw(signal_text[0].as_str()));
                 buttons[selected - scroll_offset] = Span::styled(
-                    signal_text[selected],
+                    signal_text[selected].as_str(),
                     self.colours.currently_selected_text_style,
                 );
 
                 app_state.delete_dialog_state.button_positions = layout
                     .iter()
                     .enumerate()
-                    .map(|(i, pos)| {
-                        (
-                            pos.x,
-                            pos.y,
-                            pos.x + pos.width - 1,
-                            pos.y + pos.height - 1,
-                            if i == 0 { 0 } else { scroll_offset } + i,
+                    .filter_map(|(i, pos)| {
+                        signals.get(if i == 0 { 0 } else { scroll_offset } + i).map(
+                            |(signal, _name)| {
+                                (
+                                    pos.x,
+                                    pos.y,
+                                    pos.x + pos.width - 1,
+                                    pos.y + pos.height - 1,
+                                    *signal,
+                                )
+                            },
                         )
                     })
                     .collect::<Vec<(u16, u16, u16, u16, usize)>>();
@@ -376,7 +380,7 @@ impl KillDialog for Painter {
             // Now draw buttons if needed...
             let split_draw_loc = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints(
+                .constraints::<&[Constraint]>(
                     if app_state.dd_err.is_some() {
                         vec![Constraint::Percentage(100)]
                     } else {
@@ -0,0 +1,108 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::get_start_position, Painter},
+};
+
+const FILTERS_HINT: &str = "Space to toggle, w to save";
+
+pub trait FiltersDialog {
+    fn draw_filters_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl FiltersDialog for Painter {
+    fn draw_filters_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let filters_state = &mut app_state.filters_dialog_state;
+        let kind = match filters_state.kind {
+            Some(kind) => kind,
+            None => return,
+        };
+
+        let title_text = format!(" {} ", kind.get_entry_name());
+        let title = Spans::from(vec![
+            Span::styled(title_text.as_str(), self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        title_text.chars().count() + "── Esc to close ".chars().count() + 2
+                    ))
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+        let filters_block = Block::default()
+            .title(title)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style);
+
+        let inner_loc = filters_block.inner(draw_loc);
+        f.render_widget(filters_block, draw_loc);
+
+        let split_loc = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner_loc);
+
+        let filter = kind.get_filter(&app_state.filters);
+        let entry_rows: Vec<Row<'_>> = filters_state
+            .entries
+            .iter()
+            .map(|entry| {
+                let is_shown = filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.is_shown(entry));
+                Row::new(vec![format!(
+                    "[{}] {}",
+                    if is_shown { "x" } else { " " },
+                    entry
+                )])
+            })
+            .collect();
+
+        let start_position = get_start_position(
+            usize::from(split_loc[0].height),
+            &filters_state.scroll_state.scroll_direction,
+            &mut filters_state.scroll_state.previous_scroll_position,
+            filters_state.scroll_state.current_scroll_position,
+            app_state.is_force_redraw,
+        );
+        filters_state.scroll_state.table_state.select(Some(
+            filters_state
+                .scroll_state
+                .current_scroll_position
+                .saturating_sub(start_position),
+        ));
+
+        f.render_stateful_widget(
+            Table::new(entry_rows.into_iter().skip(start_position))
+                .highlight_style(self.colours.currently_selected_text_style)
+                .style(self.colours.text_style)
+                .widths(&[Constraint::Percentage(100)]),
+            split_loc[0],
+            &mut filters_state.scroll_state.table_state,
+        );
+
+        let hint = match &filters_state.message {
+            Some(Ok(message)) => Span::styled(message.as_str(), self.colours.text_style),
+            Some(Err(error)) => Span::styled(error.as_str(), self.colours.invalid_query_style),
+            None => Span::styled(FILTERS_HINT, self.colours.text_style),
+        };
+        f.render_widget(
+            Paragraph::new(Spans::from(hint)).wrap(Wrap { trim: true }),
+            split_loc[1],
+        );
+    }
+}
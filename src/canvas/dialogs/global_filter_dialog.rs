@@ -0,0 +1,81 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{app::App, canvas::Painter};
+
+const GLOBAL_FILTER_BASE: &str = " Global Filter ── Esc to close ";
+
+pub trait GlobalFilterDialog {
+    fn draw_global_filter_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    );
+}
+
+impl GlobalFilterDialog for Painter {
+    fn draw_global_filter_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let global_filter = &app_state.global_filter;
+
+        let mut text = vec![
+            Spans::from("Filter every process widget, written like a process search."),
+            Spans::from("The CPU and memory widgets show how much matching processes use."),
+            Spans::default(),
+            Spans::from(vec![
+                Span::styled("Query: ", self.colours.text_style),
+                Span::styled(
+                    format!("{}_", global_filter.input),
+                    self.colours.currently_selected_text_style,
+                ),
+            ]),
+            Spans::default(),
+        ];
+        if let Some(error) = &global_filter.error {
+            text.push(Spans::from(Span::styled(
+                error.as_str(),
+                self.colours.invalid_query_style,
+            )));
+        } else {
+            text.push(Spans::from(
+                "For example: user=alice, container=3f2a9c1e5b7d, or firefox.",
+            ));
+        }
+        text.push(Spans::from(
+            "Press ENTER to apply, or apply an empty query to remove the filter.",
+        ));
+
+        let title = Spans::from(vec![
+            Span::styled(" Global Filter ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(GLOBAL_FILTER_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(Text::from(text))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
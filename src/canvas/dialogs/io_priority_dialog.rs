@@ -0,0 +1,113 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{data_harvester::processes::IoPriorityClass, App},
+    canvas::Painter,
+};
+
+const IO_PRIORITY_BASE: &str = " Set I/O Priority ── Esc to close ";
+
+pub trait IoPriorityDialog {
+    fn draw_io_priority_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    );
+}
+
+impl IoPriorityDialog for Painter {
+    fn draw_io_priority_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let io_priority_state = &app_state.io_priority_dialog_state;
+        let priority = io_priority_state.priority;
+
+        let process_text = match &io_priority_state.process {
+            Some((name, pids)) if pids.len() == 1 => format!("\"{}\" (PID {})", name, pids[0]),
+            Some((name, pids)) => format!("\"{}\" ({} processes)", name, pids.len()),
+            None => "the selected process".to_string(),
+        };
+
+        let mut class_spans = vec![Span::styled("Class: ", self.colours.text_style)];
+        for (itx, class) in IoPriorityClass::ALL.iter().enumerate() {
+            if itx > 0 {
+                class_spans.push(Span::raw("  "));
+            }
+            if *class == priority.class {
+                class_spans.push(Span::styled(
+                    class.to_string(),
+                    self.colours.currently_selected_text_style,
+                ));
+            } else {
+                class_spans.push(Span::styled(class.to_string(), self.colours.text_style));
+            }
+        }
+
+        let level_spans = if priority.class.has_levels() {
+            Spans::from(vec![
+                Span::styled("Level (0 to 7): ", self.colours.text_style),
+                Span::styled(
+                    priority.level.to_string(),
+                    self.colours.currently_selected_text_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(
+                "Level: this class has no levels",
+                self.colours.text_style,
+            ))
+        };
+
+        let mut text = vec![
+            Spans::from(format!("Set the I/O priority of {}.", process_text)),
+            Spans::default(),
+            Spans::from(class_spans),
+            level_spans,
+            Spans::default(),
+        ];
+        if let Some(error) = &io_priority_state.error {
+            text.push(Spans::from(Span::styled(
+                error.as_str(),
+                self.colours.invalid_query_style,
+            )));
+        } else {
+            text.push(Spans::from(
+                "Left/right to change the class, up/down to change the level.",
+            ));
+        }
+        text.push(Spans::from("Press ENTER to apply."));
+
+        let title = Spans::from(vec![
+            Span::styled(" Set I/O Priority ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(IO_PRIORITY_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(Text::from(text))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
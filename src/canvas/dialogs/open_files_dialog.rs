@@ -0,0 +1,92 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{App, CanvasTableWidthState},
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableOptions, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_open_file_rows` generates entries.
+const OPEN_FILES_COLUMNS: [TextTableColumn; 3] = [
+    TextTableColumn::hard("FD", 6),
+    TextTableColumn::hard("Type", 8),
+    TextTableColumn::soft("Name", 0.8),
+];
+
+pub trait OpenFilesDialog {
+    fn draw_open_files_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl OpenFilesDialog for Painter {
+    fn draw_open_files_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let title_base = match &app_state.open_files_dialog_state.process {
+            Some((pid, name)) => format!(" Open Files of {} ({}) ", name, pid),
+            None => " Open Files ".to_string(),
+        };
+        let title = Spans::from(vec![
+            Span::styled(title_base.clone(), self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                            + " Esc to close ".len()
+                            + 4
+                    ))
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+        let open_files_block = Block::default()
+            .title(title)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style);
+
+        if let Some(error) = &app_state.open_files_dialog_state.error {
+            f.render_widget(
+                Paragraph::new(Text::styled(
+                    error.clone(),
+                    self.colours.invalid_query_style,
+                ))
+                .block(open_files_block)
+                .wrap(Wrap { trim: true }),
+                draw_loc,
+            );
+            return;
+        }
+
+        let is_force_redraw = app_state.is_force_redraw;
+        self.draw_text_table(
+            f,
+            draw_loc,
+            open_files_block,
+            TextTableData::new(
+                &OPEN_FILES_COLUMNS,
+                &app_state.open_files_dialog_state.open_files,
+                &mut app_state.open_files_dialog_state.scroll_state,
+                &mut CanvasTableWidthState::default(),
+            ),
+            TextTableOptions {
+                table_gap: 1,
+                highlight_style: self.colours.currently_selected_text_style,
+                recalculate_widths: true,
+                is_force_redraw,
+            },
+            |open_file_row| TextTableRow::new(open_file_row),
+        );
+    }
+}
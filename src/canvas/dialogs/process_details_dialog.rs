@@ -0,0 +1,175 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{data_harvester::processes::ProcessDetails, App},
+    canvas::Painter,
+    utils::gen_util::get_binary_bytes,
+};
+
+pub trait ProcessDetailsDialog {
+    fn get_process_details_spans(&self, details: &ProcessDetails) -> Vec<Spans<'_>>;
+
+    fn draw_process_details_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ProcessDetailsDialog for Painter {
+    fn get_process_details_spans(&self, details: &ProcessDetails) -> Vec<Spans<'_>> {
+        let unavailable = || Spans::from("  Unavailable");
+        let section =
+            |header: String| Spans::from(Span::styled(header, self.colours.table_header_style));
+
+        let mut text = vec![section("Command line".to_string())];
+        if details.command_line.is_empty() {
+            text.push(unavailable());
+        } else {
+            text.extend(
+                details
+                    .command_line
+                    .iter()
+                    .map(|arg| Spans::from(format!("  {}", arg))),
+            );
+        }
+
+        text.push(Spans::default());
+        text.push(section("Working directory".to_string()));
+        text.push(match &details.cwd {
+            Some(cwd) => Spans::from(format!("  {}", cwd)),
+            None => unavailable(),
+        });
+
+        if !details.threads.is_empty() {
+            text.push(Spans::default());
+            text.push(section(format!("Threads ({})", details.threads.len())));
+            text.extend(details.threads.iter().map(|(tid, name, state)| {
+                Spans::from(format!("  {:<8} {:<16} {}", tid, name, state))
+            }));
+        }
+
+        if cfg!(target_os = "linux") {
+            text.push(Spans::default());
+            text.push(section("Memory maps".to_string()));
+            match &details.memory_maps {
+                Some(memory_maps) => {
+                    text.extend(memory_maps.iter().map(|summary| {
+                        let (size, unit) = get_binary_bytes(summary.size_bytes);
+                        Spans::from(format!(
+                            "  {:<22} {:>5} {:<10} {:>7.1}{}",
+                            summary.kind,
+                            summary.mappings,
+                            if summary.mappings == 1 {
+                                "mapping"
+                            } else {
+                                "mappings"
+                            },
+                            size,
+                            unit
+                        ))
+                    }));
+                }
+                None => text.push(unavailable()),
+            }
+
+            text.push(Spans::default());
+            text.push(section("Control groups".to_string()));
+            match &details.cgroups {
+                Some(cgroups) => {
+                    text.extend(
+                        cgroups
+                            .iter()
+                            .map(|cgroup| Spans::from(format!("  {}", cgroup))),
+                    );
+                }
+                None => text.push(unavailable()),
+            }
+        }
+
+        text.push(Spans::default());
+        match &details.environment {
+            Some(environment) => {
+                text.push(section(format!("Environment ({})", environment.len())));
+                text.extend(
+                    environment
+                        .iter()
+                        .map(|variable| Spans::from(format!("  {}", variable))),
+                );
+            }
+            None => {
+                text.push(section("Environment".to_string()));
+                text.push(unavailable());
+            }
+        }
+
+        text
+    }
+
+    fn draw_process_details_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let details_state = &app_state.process_details_dialog_state;
+
+        let title_base = match &details_state.details {
+            Some(details) => format!(" Details of {} ({}) ", details.name, details.pid),
+            None => " Process Details ".to_string(),
+        };
+        let title = Spans::from(vec![
+            Span::styled(title_base.clone(), self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                            + " Esc to close ".len()
+                            + 4
+                    ))
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let mut text = Vec::new();
+        if let Some(error) = &details_state.error {
+            // Keep showing the last details we got, if any, but make it obvious they are stale.
+            text.push(Spans::from(Span::styled(
+                error.clone(),
+                self.colours.invalid_query_style,
+            )));
+            text.push(Spans::default());
+        }
+        if let Some(details) = &details_state.details {
+            text.extend(self.get_process_details_spans(details));
+        }
+
+        // Lines are not wrapped, so the number of lines is exactly how far this can be scrolled.
+        let max_scroll_index =
+            (text.len() as u16).saturating_sub(draw_loc.height.saturating_sub(2));
+        let scroll_state = &mut app_state.process_details_dialog_state.scroll_state;
+        scroll_state.max_scroll_index = max_scroll_index;
+        if scroll_state.current_scroll_index > max_scroll_index {
+            scroll_state.current_scroll_index = max_scroll_index;
+        }
+
+        f.render_widget(
+            Paragraph::new(Text::from(text))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .scroll((scroll_state.current_scroll_index, 0)),
+            draw_loc,
+        );
+    }
+}
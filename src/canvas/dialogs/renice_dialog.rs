@@ -0,0 +1,94 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{process_actions, App},
+    canvas::Painter,
+};
+
+const RENICE_BASE: &str = " Set Nice Value ── Esc to close ";
+
+pub trait ReniceDialog {
+    fn draw_renice_dialog<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect);
+}
+
+impl ReniceDialog for Painter {
+    fn draw_renice_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let renice_state = &app_state.renice_dialog_state;
+
+        let process_text = match &renice_state.process {
+            Some((name, pids)) if pids.len() == 1 => format!("\"{}\" (PID {})", name, pids[0]),
+            Some((name, pids)) => format!("\"{}\" ({} processes)", name, pids.len()),
+            None => "the selected process".to_string(),
+        };
+        let current_text = match renice_state.current_nice {
+            Some(nice) => format!("Current nice value: {}", nice),
+            None => "Current nice value: unknown".to_string(),
+        };
+
+        let mut text = vec![
+            Spans::from(format!("Set the nice value of {}.", process_text)),
+            Spans::from(current_text),
+            Spans::default(),
+            Spans::from(vec![
+                Span::styled(
+                    format!(
+                        "New nice value ({} to {}): ",
+                        process_actions::NICE_RANGE.start(),
+                        process_actions::NICE_RANGE.end()
+                    ),
+                    self.colours.text_style,
+                ),
+                Span::styled(
+                    format!("{}_", renice_state.nice_input),
+                    self.colours.currently_selected_text_style,
+                ),
+            ]),
+            Spans::default(),
+        ];
+        if let Some(error) = &renice_state.error {
+            text.push(Spans::from(Span::styled(
+                error.as_str(),
+                self.colours.invalid_query_style,
+            )));
+        } else {
+            text.push(Spans::from("Lower values mean a higher priority."));
+        }
+        text.push(Spans::from("Press ENTER to apply."));
+
+        let title = Spans::from(vec![
+            Span::styled(" Set Nice Value ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width).saturating_sub(RENICE_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(Text::from(text))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{App, CanvasTableWidthState},
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableOptions, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_thread_rows` generates entries.
+const THREAD_COLUMNS: [TextTableColumn; 4] = [
+    TextTableColumn::hard("TID", 8),
+    TextTableColumn::soft("Name", 0.5),
+    TextTableColumn::hard("CPU%", 8),
+    TextTableColumn::soft("State", 0.3),
+];
+
+pub trait ThreadDialog {
+    fn draw_thread_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ThreadDialog for Painter {
+    fn draw_thread_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let title_base = match &app_state.thread_dialog_state.process {
+            Some((pid, name)) => format!(" Threads of {} ({}) ", name, pid),
+            None => " Threads ".to_string(),
+        };
+        let title = Spans::from(vec![
+            Span::styled(title_base.clone(), self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                            + " Esc to close ".len()
+                            + 4
+                    ))
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+        let thread_block = Block::default()
+            .title(title)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style);
+
+        if cfg!(not(target_os = "linux")) {
+            f.render_widget(
+                Paragraph::new(Text::styled(
+                    "Listing threads is currently only supported on Linux.",
+                    self.colours.text_style,
+                ))
+                .block(thread_block)
+                .wrap(Wrap { trim: true }),
+                draw_loc,
+            );
+            return;
+        }
+
+        let is_force_redraw = app_state.is_force_redraw;
+        self.draw_text_table(
+            f,
+            draw_loc,
+            thread_block,
+            TextTableData::new(
+                &THREAD_COLUMNS,
+                &app_state.canvas_data.thread_data,
+                &mut app_state.thread_dialog_state.scroll_state,
+                &mut CanvasTableWidthState::default(),
+            ),
+            TextTableOptions {
+                table_gap: 1,
+                highlight_style: self.colours.currently_selected_text_style,
+                recalculate_widths: true,
+                is_force_redraw,
+            },
+            |thread_row| TextTableRow::new(thread_row),
+        );
+    }
+}
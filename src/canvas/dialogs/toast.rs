@@ -0,0 +1,44 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::Span,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{app::AppToast, canvas::Painter};
+
+pub trait Toast {
+    fn draw_toast<B: Backend>(&self, f: &mut Frame<'_, B>, toast: &AppToast, draw_loc: Rect);
+}
+
+impl Toast for Painter {
+    fn draw_toast<B: Backend>(&self, f: &mut Frame<'_, B>, toast: &AppToast, draw_loc: Rect) {
+        // Put the toast in the bottom right corner, just above the bottom row.
+        let width = (toast.text.width() as u16 + 4).min(draw_loc.width);
+        let height = 3.min(draw_loc.height);
+        let toast_loc = Rect::new(
+            draw_loc.x + draw_loc.width - width,
+            draw_loc.y + draw_loc.height.saturating_sub(height + 1),
+            width,
+            height,
+        );
+
+        let text_style = if toast.is_error {
+            self.colours.invalid_query_style
+        } else {
+            self.colours.text_style
+        };
+
+        f.render_widget(Clear, toast_loc);
+        f.render_widget(
+            Paragraph::new(Span::styled(toast.text.as_str(), text_style)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.colours.highlighted_border_style),
+            ),
+            toast_loc,
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app;
 use std::cmp::{max, min};
+use tui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
 
 /// Return a (hard)-width vector for column widths.
 ///
@@ -195,6 +196,25 @@ pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize)
     )
 }
 
+/// Returns an automatic maximum for a y-axis whose highest value is `max_value`.  The previous
+/// maximum is kept while the value still fits well under it, so that the axis does not rescale on
+/// every small change.
+pub fn get_auto_y_max(previous_y_max: Option<f64>, max_value: f64) -> f64 {
+    /// How much higher than the highest value a new maximum is.
+    const HEADROOM: f64 = 1.25;
+    /// How far the highest value has to fall below the maximum before it shrinks.
+    const SHRINK_RATIO: f64 = 0.4;
+
+    match previous_y_max {
+        Some(previous_y_max)
+            if max_value <= previous_y_max && max_value >= previous_y_max * SHRINK_RATIO =>
+        {
+            previous_y_max
+        }
+        _ => max_value * HEADROOM,
+    }
+}
+
 /// Interpolates between two points.  Mainly used to help fill in tui-rs blanks in certain situations.
 /// It is expected point_one is "further left" compared to point_two.
 /// A point is two floats, in (x, y) form.  x is time, y is value.
@@ -206,10 +226,44 @@ pub fn interpolate_points(point_one: &(f64, f64), point_two: &(f64, f64), time:
     (point_one.1 + (time - point_one.0) * slope).max(0.0)
 }
 
+/// Restyles the outermost cells of an area, which recolours the border of a widget that has already
+/// been drawn there, title and all, without redrawing it.
+pub struct BorderHighlight {
+    pub style: Style,
+}
+
+impl Widget for BorderHighlight {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        for x in area.left()..area.right() {
+            buf.get_mut(x, area.top()).set_style(self.style);
+            buf.get_mut(x, area.bottom() - 1).set_style(self.style);
+        }
+        for y in area.top()..area.bottom() {
+            buf.get_mut(area.left(), y).set_style(self.style);
+            buf.get_mut(area.right() - 1, y).set_style(self.style);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn test_auto_y_max() {
+        assert_eq!(get_auto_y_max(None, 100.0), 125.0);
+        // Small changes, up or down, keep the maximum.
+        assert_eq!(get_auto_y_max(Some(125.0), 110.0), 125.0);
+        assert_eq!(get_auto_y_max(Some(125.0), 60.0), 125.0);
+        // Going over it or falling far below it does not.
+        assert_eq!(get_auto_y_max(Some(125.0), 200.0), 250.0);
+        assert_eq!(get_auto_y_max(Some(125.0), 40.0), 50.0);
+    }
+
     #[test]
     fn test_zero_width() {
         assert_eq!(
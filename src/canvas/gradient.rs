@@ -0,0 +1,139 @@
+//! Colouring graph lines by their value, like going from green to red as the CPU usage rises.
+
+use tui::style::Color;
+
+/// How many distinct colours a gradient is split into.
+const GRADIENT_STEPS: usize = 32;
+
+/// Returns whether the terminal claims to support 24-bit colours, as there is no reliable way to
+/// ask it directly.
+pub fn is_true_colour_supported() -> bool {
+    match std::env::var("COLORTERM") {
+        Ok(colour_term) => colour_term == "truecolor" || colour_term == "24bit",
+        Err(_) => false,
+    }
+}
+
+/// Approximates a colour as RGB, using the usual xterm values for the named colours.
+pub fn get_rgb_of_colour(colour: Color) -> Option<(u8, u8, u8)> {
+    match colour {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Black => Some((0, 0, 0)),
+        Color::Red => Some((205, 0, 0)),
+        Color::Green => Some((0, 205, 0)),
+        Color::Yellow => Some((205, 205, 0)),
+        Color::Blue => Some((0, 0, 238)),
+        Color::Magenta => Some((205, 0, 205)),
+        Color::Cyan => Some((0, 205, 205)),
+        Color::Gray => Some((229, 229, 229)),
+        Color::DarkGray => Some((127, 127, 127)),
+        Color::LightRed => Some((255, 0, 0)),
+        Color::LightGreen => Some((0, 255, 0)),
+        Color::LightYellow => Some((255, 255, 0)),
+        Color::LightBlue => Some((92, 92, 255)),
+        Color::LightMagenta => Some((255, 0, 255)),
+        Color::LightCyan => Some((0, 255, 255)),
+        Color::White => Some((255, 255, 255)),
+        Color::Reset | Color::Indexed(_) => None,
+    }
+}
+
+/// Returns the closest colour in the 6x6x6 cube or grayscale ramp of the 256 colour palette.
+pub fn get_closest_indexed_colour((r, g, b): (u8, u8, u8)) -> u8 {
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    fn closest_cube_index(value: u8) -> usize {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_index, level)| (i32::from(**level) - i32::from(value)).abs())
+            .map(|(index, _level)| index)
+            .unwrap_or(0)
+    }
+
+    fn distance(one: (u8, u8, u8), two: (u8, u8, u8)) -> i32 {
+        let delta = |a: u8, b: u8| i32::from(a) - i32::from(b);
+        delta(one.0, two.0).pow(2) + delta(one.1, two.1).pow(2) + delta(one.2, two.2).pow(2)
+    }
+
+    let (r_index, g_index, b_index) = (
+        closest_cube_index(r),
+        closest_cube_index(g),
+        closest_cube_index(b),
+    );
+    let cube_colour = (
+        CUBE_LEVELS[r_index],
+        CUBE_LEVELS[g_index],
+        CUBE_LEVELS[b_index],
+    );
+
+    // The grayscale ramp goes from 8 to 238 in steps of 10.
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + gray_index * 10;
+
+    if distance((r, g, b), (gray_level, gray_level, gray_level)) < distance((r, g, b), cube_colour)
+    {
+        232 + gray_index
+    } else {
+        (16 + 36 * r_index + 6 * g_index + b_index) as u8
+    }
+}
+
+/// Spreads the given colours evenly over a gradient, from the lowest value to the highest.  If the
+/// terminal does not support 24-bit colours, the closest ones of the 256 colour palette are used.
+pub fn get_gradient_colours(stops: &[(u8, u8, u8)], is_true_colour: bool) -> Vec<Color> {
+    if stops.len() < 2 {
+        return Vec::new();
+    }
+
+    (0..GRADIENT_STEPS)
+        .map(|step| {
+            let position = step as f64 / (GRADIENT_STEPS - 1) as f64 * (stops.len() - 1) as f64;
+            let start_index = (position.floor() as usize).min(stops.len() - 2);
+            let ratio = position - start_index as f64;
+            let (start, end) = (stops[start_index], stops[start_index + 1]);
+            let mix =
+                |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * ratio).round() as u8;
+            let rgb = (
+                mix(start.0, end.0),
+                mix(start.1, end.1),
+                mix(start.2, end.2),
+            );
+
+            if is_true_colour {
+                Color::Rgb(rgb.0, rgb.1, rgb.2)
+            } else {
+                Color::Indexed(get_closest_indexed_colour(rgb))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_closest_indexed_colour() {
+        assert_eq!(get_closest_indexed_colour((0, 0, 0)), 16);
+        assert_eq!(get_closest_indexed_colour((255, 255, 255)), 231);
+        assert_eq!(get_closest_indexed_colour((255, 0, 0)), 196);
+        assert_eq!(get_closest_indexed_colour((0, 255, 0)), 46);
+        assert_eq!(get_closest_indexed_colour((128, 128, 128)), 244);
+    }
+
+    #[test]
+    fn test_gradient_colours() {
+        let colours = get_gradient_colours(&[(0, 255, 0), (255, 0, 0)], true);
+        assert_eq!(colours.len(), GRADIENT_STEPS);
+        assert_eq!(colours[0], Color::Rgb(0, 255, 0));
+        assert_eq!(colours[GRADIENT_STEPS - 1], Color::Rgb(255, 0, 0));
+
+        let colours = get_gradient_colours(&[(0, 255, 0), (255, 255, 0), (255, 0, 0)], false);
+        assert_eq!(colours[0], Color::Indexed(46));
+        assert_eq!(colours[GRADIENT_STEPS - 1], Color::Indexed(196));
+
+        assert!(get_gradient_colours(&[(0, 255, 0)], true).is_empty());
+    }
+}
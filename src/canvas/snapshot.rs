@@ -0,0 +1,318 @@
+//! Saving what is drawn on the screen to a file, as ANSI text or a standalone HTML page, so that it
+//! can be pasted elsewhere, such as into an incident report.
+
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use tui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::utils::{error::BottomError, gen_util};
+
+/// The format that snapshots are saved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotFormat {
+    #[default]
+    Html,
+    Ansi,
+}
+
+impl SnapshotFormat {
+    fn get_extension(self) -> &'static str {
+        match self {
+            SnapshotFormat::Html => "html",
+            SnapshotFormat::Ansi => "ans",
+        }
+    }
+}
+
+impl std::str::FromStr for SnapshotFormat {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(SnapshotFormat::Html),
+            "ansi" => Ok(SnapshotFormat::Ansi),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid snapshot format, use \"html\" or \"ansi\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// The modifiers that can be shown, along with their SGR codes and CSS.
+const MODIFIERS: [(Modifier, u8, &str); 6] = [
+    (Modifier::BOLD, 1, "font-weight:bold;"),
+    (Modifier::DIM, 2, "opacity:0.6;"),
+    (Modifier::ITALIC, 3, "font-style:italic;"),
+    (Modifier::UNDERLINED, 4, "text-decoration:underline;"),
+    (Modifier::REVERSED, 7, ""),
+    (Modifier::CROSSED_OUT, 9, "text-decoration:line-through;"),
+];
+
+/// The colours of the 16 named colours, as xterm shows them by default.
+const NAMED_COLOURS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The colours that HTML snapshots are drawn with where the terminal's defaults would be used.
+const DEFAULT_FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+const DEFAULT_BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+
+/// Returns the rows of `buffer`, each as the cells that are drawn, skipping the ones that are
+/// covered by a wide character before them.
+fn get_rows(buffer: &Buffer) -> impl Iterator<Item = Vec<&Cell>> {
+    let width = usize::from(buffer.area.width).max(1);
+    buffer.content.chunks(width).map(|row| {
+        let mut cells = Vec::with_capacity(row.len());
+        let mut to_skip = 0;
+        for cell in row {
+            if to_skip == 0 {
+                cells.push(cell);
+                to_skip = cell.symbol.width().saturating_sub(1);
+            } else {
+                to_skip -= 1;
+            }
+        }
+        cells
+    })
+}
+
+/// Formats what is drawn as text with ANSI escape codes for its colours and styles, which shows as
+/// drawn when printed to a terminal.
+pub fn format_ansi(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for row in get_rows(buffer) {
+        let mut style = None;
+        for cell in row {
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                text.push_str("\x1b[0");
+                for (modifier, code, _css) in MODIFIERS.iter() {
+                    if cell.modifier.contains(*modifier) {
+                        let _ = write!(text, ";{}", code);
+                    }
+                }
+                push_sgr_colour(&mut text, cell.fg, 30);
+                push_sgr_colour(&mut text, cell.bg, 40);
+                text.push('m');
+                style = Some(cell_style);
+            }
+            text.push_str(&cell.symbol);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// Adds the SGR parameters of `colour`, where `base` is 30 for the foreground and 40 for the
+/// background.
+fn push_sgr_colour(text: &mut String, colour: Color, base: u8) {
+    let _ = match colour {
+        Color::Reset => Ok(()),
+        Color::Rgb(r, g, b) => write!(text, ";{};2;{};{};{}", base + 8, r, g, b),
+        Color::Indexed(index) => write!(text, ";{};5;{}", base + 8, index),
+        named => {
+            let index = get_named_index(named).unwrap_or_default();
+            if index < 8 {
+                write!(text, ";{}", base + index)
+            } else {
+                write!(text, ";{}", base + 60 + index - 8)
+            }
+        }
+    };
+}
+
+/// Returns the index of a named colour among the 16 that terminals have.
+fn get_named_index(colour: Color) -> Option<u8> {
+    Some(match colour {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        _ => return None,
+    })
+}
+
+/// Returns the RGB values of `colour`, or `None` if it is the terminal's default.
+fn get_rgb(colour: Color) -> Option<(u8, u8, u8)> {
+    match colour {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) if index < 16 => Some(NAMED_COLOURS[usize::from(index)]),
+        Color::Indexed(index) if index < 232 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            Some((level(index / 36), level(index / 6 % 6), level(index % 6)))
+        }
+        Color::Indexed(index) => {
+            let level = 8 + (index - 232) * 10;
+            Some((level, level, level))
+        }
+        named => get_named_index(named).map(|index| NAMED_COLOURS[usize::from(index)]),
+    }
+}
+
+/// Formats what is drawn as a standalone HTML page, which needs nothing else to be viewed.
+pub fn format_html(buffer: &Buffer) -> String {
+    let (bg_r, bg_g, bg_b) = DEFAULT_BACKGROUND;
+    let (fg_r, fg_g, fg_b) = DEFAULT_FOREGROUND;
+    let mut text = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>bottom snapshot</title>\n\
+         <style>\npre {{ display: inline-block; margin: 0; padding: 1em; line-height: 1.2; \
+         font-family: monospace; background-color: #{:02x}{:02x}{:02x}; color: #{:02x}{:02x}{:02x}; }}\n\
+         </style>\n</head>\n<body>\n<pre>",
+        bg_r, bg_g, bg_b, fg_r, fg_g, fg_b
+    );
+
+    for row in get_rows(buffer) {
+        let mut style = None;
+        for cell in row {
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                if style.is_some() {
+                    text.push_str("</span>");
+                }
+                text.push_str("<span style=\"");
+                let (fg, bg) = if cell.modifier.contains(Modifier::REVERSED) {
+                    (
+                        Some(get_rgb(cell.bg).unwrap_or(DEFAULT_BACKGROUND)),
+                        Some(get_rgb(cell.fg).unwrap_or(DEFAULT_FOREGROUND)),
+                    )
+                } else {
+                    (get_rgb(cell.fg), get_rgb(cell.bg))
+                };
+                if let Some((r, g, b)) = fg {
+                    let _ = write!(text, "color:#{:02x}{:02x}{:02x};", r, g, b);
+                }
+                if let Some((r, g, b)) = bg {
+                    let _ = write!(text, "background-color:#{:02x}{:02x}{:02x};", r, g, b);
+                }
+                for (modifier, _code, css) in MODIFIERS.iter() {
+                    if cell.modifier.contains(*modifier) {
+                        text.push_str(css);
+                    }
+                }
+                text.push_str("\">");
+                style = Some(cell_style);
+            }
+
+            for c in cell.symbol.chars() {
+                match c {
+                    '&' => text.push_str("&amp;"),
+                    '<' => text.push_str("&lt;"),
+                    '>' => text.push_str("&gt;"),
+                    '"' => text.push_str("&quot;"),
+                    c => text.push(c),
+                }
+            }
+        }
+        if style.is_some() {
+            text.push_str("</span>");
+        }
+        text.push('\n');
+    }
+
+    text.push_str("</pre>\n</body>\n</html>\n");
+    text
+}
+
+/// Saves what is drawn to a new file in `directory`, named after the local time, and returns the
+/// path of the file.
+pub fn write_snapshot(
+    buffer: &Buffer, format: SnapshotFormat, directory: &Path,
+) -> io::Result<PathBuf> {
+    let path = directory.join(format!(
+        "bottom-snapshot-{}.{}",
+        gen_util::get_file_name_time(),
+        format.get_extension()
+    ));
+    let text = match format {
+        SnapshotFormat::Html => format_html(buffer),
+        SnapshotFormat::Ansi => format_ansi(buffer),
+    };
+    fs::write(&path, text)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use tui::{layout::Rect, style::Style};
+
+    use super::*;
+
+    fn get_test_buffer() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "CPU", Style::default().fg(Color::LightBlue));
+        buffer.set_string(3, 0, "<&>", Style::default().add_modifier(Modifier::BOLD));
+        buffer.set_string(0, 1, "世", Style::default().bg(Color::Rgb(1, 2, 3)));
+        buffer
+    }
+
+    #[test]
+    fn test_format_ansi() {
+        assert_eq!(
+            format_ansi(&get_test_buffer()),
+            "\x1b[0;94mCPU\x1b[0;1m<&>\x1b[0m\n\
+             \x1b[0;48;2;1;2;3m世\x1b[0m    \x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_format_html() {
+        let html = format_html(&get_test_buffer());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<span style=\"color:#5c5cff;\">CPU</span>\
+             <span style=\"font-weight:bold;\">&lt;&amp;&gt;</span>\n"
+        ));
+        assert!(html.contains(
+            "<span style=\"background-color:#010203;\">世</span><span style=\"\">    </span>\n"
+        ));
+        assert!(html.ends_with("</pre>\n</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn test_get_rgb() {
+        assert_eq!(get_rgb(Color::Reset), None);
+        assert_eq!(get_rgb(Color::Indexed(9)), Some((255, 0, 0)));
+        assert_eq!(get_rgb(Color::Indexed(16)), Some((0, 0, 0)));
+        assert_eq!(get_rgb(Color::Indexed(231)), Some((255, 255, 255)));
+        assert_eq!(get_rgb(Color::Indexed(232)), Some((8, 8, 8)));
+        assert_eq!(get_rgb(Color::Gray), Some((229, 229, 229)));
+    }
+}
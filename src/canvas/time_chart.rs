@@ -0,0 +1,552 @@
+//! A [`Chart`] of values over time, which also draws the parts that tui's charts cannot: lines made
+//! of box-drawing characters, filled areas, colours that follow a gradient, and gridlines at times
+//! along the x-axis.
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    symbols,
+    widgets::{Chart, Dataset, GraphType, Widget},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{app::GraphStyle, canvas::drawing_utils::interpolate_points};
+
+/// The intervals that time gridlines can be apart, in seconds.
+const GRIDLINE_INTERVALS: [u64; 13] = [1, 2, 5, 10, 15, 30, 60, 120, 300, 600, 900, 1800, 3600];
+
+/// How many columns time gridlines are apart at least, which leaves room for their labels.
+const MIN_GRIDLINE_SPACING: u16 = 10;
+
+const GRIDLINE_SYMBOL: &str = "┊";
+
+/// The symbols of a filled area, by how many eighths of a cell they fill.
+const AREA_SYMBOLS: [&str; 9] = [
+    " ",
+    symbols::bar::ONE_EIGHTH,
+    symbols::bar::ONE_QUARTER,
+    symbols::bar::THREE_EIGHTHS,
+    symbols::bar::HALF,
+    symbols::bar::FIVE_EIGHTHS,
+    symbols::bar::THREE_QUARTERS,
+    symbols::bar::SEVEN_EIGHTHS,
+    symbols::bar::FULL,
+];
+
+/// A line that the [`TimeChart`] draws itself, as it is in the line style or filled.
+pub struct TimeChartLine<'a> {
+    data: &'a [(f64, f64)],
+    colour: Color,
+    is_filled: bool,
+}
+
+/// Returns the dataset for a line of a time graph in `graph_style`, or for the area below it if
+/// `is_filled`.  Those in the line style and filled ones are drawn by the [`TimeChart`] instead, so
+/// they are added to `lines` and their dataset is empty, keeping it only for the legend.
+pub fn get_graph_dataset<'a>(
+    data: &'a [(f64, f64)], style: Style, graph_style: GraphStyle, is_filled: bool,
+    lines: &mut Vec<TimeChartLine<'a>>,
+) -> Dataset<'a> {
+    let dataset = Dataset::default()
+        .marker(graph_style.get_marker())
+        .style(style)
+        .graph_type(GraphType::Line);
+
+    if graph_style == GraphStyle::Line || is_filled {
+        lines.push(TimeChartLine {
+            data,
+            colour: style.fg.unwrap_or(Color::Reset),
+            is_filled,
+        });
+        dataset
+    } else {
+        dataset.data(data)
+    }
+}
+
+pub struct TimeChart<'a> {
+    /// Only optional so that it can be taken out to render it.
+    chart: Option<Chart<'a>>,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    lines: Vec<TimeChartLine<'a>>,
+    gradient_colours: &'a [Color],
+    /// Lines of this colour, like ones that are not for a value, keep their colour.
+    ignored_colour: Option<Color>,
+    /// The style of the time gridlines and their labels, if they are shown.
+    gridline_style: Option<Style>,
+}
+
+impl<'a> TimeChart<'a> {
+    /// Wraps a chart, which must have a bordered block and y-axis labels, with the same bounds as
+    /// its axes.
+    pub fn new(chart: Chart<'a>, x_bounds: [f64; 2], y_bounds: [f64; 2]) -> Self {
+        TimeChart {
+            chart: Some(chart),
+            x_bounds,
+            y_bounds,
+            lines: Vec::new(),
+            gradient_colours: &[],
+            ignored_colour: None,
+            gridline_style: None,
+        }
+    }
+
+    pub fn lines(mut self, lines: Vec<TimeChartLine<'a>>) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    pub fn gradient_colours(mut self, gradient_colours: &'a [Color]) -> Self {
+        self.gradient_colours = gradient_colours;
+        self
+    }
+
+    pub fn ignored_colour(mut self, ignored_colour: Option<Color>) -> Self {
+        self.ignored_colour = ignored_colour;
+        self
+    }
+
+    pub fn gridline_style(mut self, gridline_style: Option<Style>) -> Self {
+        self.gridline_style = gridline_style;
+        self
+    }
+
+    /// Returns the colour for a line of `colour` in row `y` of the graph.
+    fn get_colour(&self, colour: Color, graph_area: Rect, y: u16) -> Color {
+        if self.gradient_colours.is_empty() || Some(colour) == self.ignored_colour {
+            return colour;
+        }
+
+        let fraction =
+            f64::from(graph_area.bottom() - 1 - y) / f64::from(graph_area.height.max(2) - 1);
+        self.gradient_colours
+            [(fraction * (self.gradient_colours.len() - 1) as f64).round() as usize]
+    }
+
+    /// Returns the time in the middle of a column of the graph.
+    fn get_column_time(&self, column: u16, graph_area: Rect) -> f64 {
+        let [x_min, x_max] = self.x_bounds;
+        x_min + (f64::from(column) + 0.5) / f64::from(graph_area.width) * (x_max - x_min)
+    }
+
+    /// Returns how much of the graph's height a value reaches, from 0 to 1.
+    fn get_height_fraction(&self, value: f64) -> f64 {
+        let [y_min, y_max] = self.y_bounds;
+        ((value - y_min) / (y_max - y_min)).clamp(0.0, 1.0)
+    }
+
+    /// Fills the areas below the filled lines.  In each column the tallest area is drawn first, so
+    /// that every area shows up as the band between its line and the next lower one.
+    fn draw_areas(&self, graph_area: Rect, is_blank: &[bool], buf: &mut Buffer) {
+        let filled_lines: Vec<_> = self.lines.iter().filter(|line| line.is_filled).collect();
+        if filled_lines.is_empty() {
+            return;
+        }
+
+        for column in 0..graph_area.width {
+            let time = self.get_column_time(column, graph_area);
+            let mut heights: Vec<(u16, Color)> = filled_lines
+                .iter()
+                .filter_map(|line| {
+                    get_value_at(line.data, time).map(|value| {
+                        let eighths =
+                            (self.get_height_fraction(value) * f64::from(graph_area.height) * 8.0)
+                                .round() as u16;
+                        (eighths, line.colour)
+                    })
+                })
+                .collect();
+            heights.sort_by(|(one, _), (two, _)| two.cmp(one));
+
+            let x = graph_area.left() + column;
+            for (eighths, colour) in heights {
+                for row in 0..eighths.div_ceil(8) {
+                    let y = graph_area.bottom() - 1 - row;
+                    if !is_blank[get_cell_index(graph_area, x, y)] {
+                        continue;
+                    }
+
+                    let symbol = AREA_SYMBOLS[usize::from((eighths - row * 8).min(8))];
+                    let colour = self.get_colour(colour, graph_area, y);
+                    let cell = buf.get_mut(x, y);
+                    // The top of a lower area leaves the rest of the cell to the area behind it.
+                    if cell.symbol == symbols::bar::FULL && symbol != symbols::bar::FULL {
+                        let behind_colour = cell.fg;
+                        cell.set_bg(behind_colour);
+                    }
+                    cell.set_symbol(symbol).set_fg(colour);
+                }
+            }
+        }
+    }
+
+    /// Draws faint gridlines at round times ago, as many as fit at the current zoom, and labels
+    /// them below the x-axis if there is one.
+    fn draw_time_grid(&self, gridline_style: Style, graph_area: Rect, buf: &mut Buffer) {
+        let [x_min, x_max] = self.x_bounds;
+        let time_span = x_max - x_min;
+        let interval = match GRIDLINE_INTERVALS.iter().find(|interval| {
+            (**interval * 1000) as f64 / time_span * f64::from(graph_area.width)
+                >= f64::from(MIN_GRIDLINE_SPACING)
+        }) {
+            Some(interval) => *interval,
+            None => return,
+        };
+
+        // The x-axis labels are on the row below the x-axis, if the chart drew one.
+        let label_y = if buf.get(graph_area.left() - 1, graph_area.bottom()).symbol
+            == symbols::line::BOTTOM_LEFT
+            && graph_area.bottom() + 1 < buf.area.bottom()
+        {
+            Some(graph_area.bottom() + 1)
+        } else {
+            None
+        };
+
+        let mut seconds_ago = interval;
+        while ((seconds_ago * 1000) as f64) < -x_min {
+            let fraction = (-((seconds_ago * 1000) as f64) - x_min) / time_span;
+            let column = (fraction * f64::from(graph_area.width)) as u16;
+            if column < graph_area.width {
+                let x = graph_area.left() + column;
+                for y in graph_area.top()..graph_area.bottom() {
+                    let cell = buf.get_mut(x, y);
+                    if cell.symbol == " " {
+                        cell.set_symbol(GRIDLINE_SYMBOL)
+                            .set_style(gridline_style.add_modifier(Modifier::DIM));
+                    }
+                }
+                if let Some(label_y) = label_y {
+                    draw_label(
+                        &get_time_label(seconds_ago),
+                        x,
+                        label_y,
+                        gridline_style,
+                        buf,
+                    );
+                }
+            }
+            seconds_ago += interval;
+        }
+    }
+
+    fn draw_line(
+        &self, line: &TimeChartLine<'_>, graph_area: Rect, is_blank: &[bool], buf: &mut Buffer,
+    ) {
+        let mut set_symbol = |x: u16, y: u16, symbol: &str| {
+            let colour = self.get_colour(line.colour, graph_area, y);
+            let cell = buf.get_mut(x, y);
+            // Anything the chart drew, like the legend, and other lines stay on top, but lines are
+            // drawn over areas.
+            if is_blank[get_cell_index(graph_area, x, y)]
+                && AREA_SYMBOLS.contains(&cell.symbol.as_str())
+            {
+                cell.set_symbol(symbol).set_fg(colour).set_bg(Color::Reset);
+            }
+        };
+
+        let mut previous_y = None;
+        for column in 0..graph_area.width {
+            let time = self.get_column_time(column, graph_area);
+            let current_y = get_value_at(line.data, time).map(|value| {
+                let fraction = self.get_height_fraction(value);
+                let rows_above_bottom =
+                    ((fraction * f64::from(graph_area.height)) as u16).min(graph_area.height - 1);
+                graph_area.bottom() - 1 - rows_above_bottom
+            });
+
+            let x = graph_area.left() + column;
+            match (previous_y, current_y) {
+                (Some(previous_y), Some(current_y)) if previous_y != current_y => {
+                    let (from_symbol, to_symbol) = if current_y < previous_y {
+                        (
+                            symbols::line::ROUNDED_BOTTOM_RIGHT,
+                            symbols::line::ROUNDED_TOP_LEFT,
+                        )
+                    } else {
+                        (
+                            symbols::line::ROUNDED_TOP_RIGHT,
+                            symbols::line::ROUNDED_BOTTOM_LEFT,
+                        )
+                    };
+                    set_symbol(x, previous_y, from_symbol);
+                    set_symbol(x, current_y, to_symbol);
+                    for y in previous_y.min(current_y) + 1..previous_y.max(current_y) {
+                        set_symbol(x, y, symbols::line::VERTICAL);
+                    }
+                }
+                (_, Some(current_y)) => set_symbol(x, current_y, symbols::line::HORIZONTAL),
+                (_, None) => {}
+            }
+            previous_y = current_y;
+        }
+    }
+}
+
+impl<'a> Widget for TimeChart<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        if let Some(chart) = self.chart.take() {
+            chart.render(area, buf);
+        }
+
+        let graph_area = match get_graph_area(area, buf) {
+            Some(graph_area) => graph_area,
+            None => return,
+        };
+
+        if !self.gradient_colours.is_empty() {
+            for y in graph_area.top()..graph_area.bottom() {
+                for x in graph_area.left()..graph_area.right() {
+                    let cell = buf.get(x, y);
+                    if is_marker_symbol(&cell.symbol) {
+                        let colour = self.get_colour(cell.fg, graph_area, y);
+                        buf.get_mut(x, y).set_fg(colour);
+                    }
+                }
+            }
+        }
+
+        let [x_min, x_max] = self.x_bounds;
+        let [y_min, y_max] = self.y_bounds;
+        if x_max <= x_min || y_max <= y_min {
+            return;
+        }
+
+        if !self.lines.is_empty() {
+            // Only draw over what the chart left empty.
+            let is_blank: Vec<bool> = (graph_area.top()..graph_area.bottom())
+                .flat_map(|y| (graph_area.left()..graph_area.right()).map(move |x| (x, y)))
+                .map(|(x, y)| buf.get(x, y).symbol == " ")
+                .collect();
+
+            self.draw_areas(graph_area, &is_blank, buf);
+            for line in self.lines.iter().filter(|line| !line.is_filled) {
+                self.draw_line(line, graph_area, &is_blank, buf);
+            }
+        }
+
+        // Gridlines go behind everything else, so they are drawn last, in what is still empty.
+        if let Some(gridline_style) = self.gridline_style {
+            self.draw_time_grid(gridline_style, graph_area, buf);
+        }
+    }
+}
+
+/// Finds where the chart in `area` drew its graph, which is right of its y-axis and as tall as it.
+fn get_graph_area(area: Rect, buf: &Buffer) -> Option<Rect> {
+    if area.width < 3 || area.height < 3 {
+        return None;
+    }
+    let (left, top) = (area.left() + 1, area.top() + 1);
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+
+    let axis_x = (left..right).find(|x| buf.get(*x, top).symbol == symbols::line::VERTICAL)?;
+    let graph_bottom = (top..bottom)
+        .take_while(|y| buf.get(axis_x, *y).symbol == symbols::line::VERTICAL)
+        .last()?
+        + 1;
+
+    if axis_x + 1 < right {
+        Some(Rect::new(
+            axis_x + 1,
+            top,
+            right - axis_x - 1,
+            graph_bottom - top,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns a label for a time gridline, like "30s", "5m", or "1h".
+fn get_time_label(seconds_ago: u64) -> String {
+    if seconds_ago >= 3600 && seconds_ago.is_multiple_of(3600) {
+        format!("{}h", seconds_ago / 3600)
+    } else if seconds_ago >= 60 && seconds_ago.is_multiple_of(60) {
+        format!("{}m", seconds_ago / 60)
+    } else {
+        format!("{}s", seconds_ago)
+    }
+}
+
+/// Draws a label centred on column `x`, unless it would touch anything else on the row, like the
+/// labels at the ends of the x-axis.
+fn draw_label(label: &str, x: u16, y: u16, style: Style, buf: &mut Buffer) {
+    let graphemes: Vec<&str> = UnicodeSegmentation::graphemes(label, true).collect();
+    let width = graphemes.len() as u16;
+    let left = match x.checked_sub(width / 2) {
+        Some(left) if left > buf.area.left() && left + width < buf.area.right() => left,
+        _ => return,
+    };
+
+    if (left - 1..=left + width).all(|x| buf.get(x, y).symbol == " ") {
+        for (offset, grapheme) in graphemes.into_iter().enumerate() {
+            buf.get_mut(left + offset as u16, y)
+                .set_symbol(grapheme)
+                .set_style(style);
+        }
+    }
+}
+
+/// Returns the index of a cell of the graph in row-major order.
+fn get_cell_index(graph_area: Rect, x: u16, y: u16) -> usize {
+    usize::from(y - graph_area.top()) * usize::from(graph_area.width)
+        + usize::from(x - graph_area.left())
+}
+
+/// Returns the value of the line at `time`, between the points around it.
+fn get_value_at(data: &[(f64, f64)], time: f64) -> Option<f64> {
+    let after_index = data
+        .iter()
+        .position(|(point_time, _value)| *point_time >= time)?;
+    if after_index == 0 {
+        // Only a point right at the start of the line has a value there.
+        let (point_time, value) = data[0];
+        if point_time == time {
+            Some(value)
+        } else {
+            None
+        }
+    } else {
+        Some(interpolate_points(
+            &data[after_index - 1],
+            &data[after_index],
+            time,
+        ))
+    }
+}
+
+/// Returns whether the symbol is one drawn by a chart's markers, rather than text or borders.
+fn is_marker_symbol(symbol: &str) -> bool {
+    match symbol.chars().next() {
+        Some(character) => {
+            ('\u{2801}'..='\u{28ff}').contains(&character)
+                || character == '•'
+                || character == '▄'
+                || character == '█'
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tui::{
+        text::Span,
+        widgets::{Axis, Block, Borders},
+    };
+
+    use super::*;
+
+    fn render_line(data: &[(f64, f64)], is_filled: bool) -> Buffer {
+        let area = Rect::new(0, 0, 12, 7);
+        let mut lines = Vec::new();
+        let dataset = get_graph_dataset(
+            data,
+            Style::default(),
+            GraphStyle::Line,
+            is_filled,
+            &mut lines,
+        );
+        let chart = Chart::new(vec![dataset])
+            .block(Block::default().borders(Borders::ALL))
+            .x_axis(Axis::default().bounds([0.0, 10.0]))
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, 10.0])
+                    .labels(vec![Span::raw("0"), Span::raw("10")]),
+            );
+
+        let mut buf = Buffer::empty(area);
+        TimeChart::new(chart, [0.0, 10.0], [0.0, 10.0])
+            .lines(lines)
+            .render(area, &mut buf);
+        buf
+    }
+
+    fn get_row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width)
+            .map(|x| buf.get(x, y).symbol.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_flat_line() {
+        let buf = render_line(&[(0.0, 5.0), (10.0, 5.0)], false);
+        // The graph is the 5 rows inside the borders, right of the "10" labels and the axis.
+        assert_eq!(get_row(&buf, 3), "│  │───────│");
+        assert_eq!(get_row(&buf, 2), "│  │       │");
+    }
+
+    #[test]
+    fn test_rising_line() {
+        let buf = render_line(&[(0.0, 0.0), (10.0, 10.0)], false);
+        assert_eq!(get_row(&buf, 1), "│10│      ╭│");
+        assert_eq!(get_row(&buf, 2), "│  │    ╭─╯│");
+        assert_eq!(get_row(&buf, 3), "│  │   ╭╯  │");
+        assert_eq!(get_row(&buf, 4), "│  │ ╭─╯   │");
+        assert_eq!(get_row(&buf, 5), "│0 │─╯     │");
+    }
+
+    #[test]
+    fn test_filled_area() {
+        let buf = render_line(&[(0.0, 0.0), (10.0, 10.0)], true);
+        assert_eq!(get_row(&buf, 1), "│10│      ▅│");
+        assert_eq!(get_row(&buf, 2), "│  │    ▂▇█│");
+        assert_eq!(get_row(&buf, 3), "│  │   ▄███│");
+        assert_eq!(get_row(&buf, 4), "│  │ ▁▆████│");
+        assert_eq!(get_row(&buf, 5), "│0 │▃██████│");
+    }
+
+    #[test]
+    fn test_time_grid() {
+        let area = Rect::new(0, 0, 42, 6);
+        let chart = Chart::new(vec![])
+            .block(Block::default().borders(Borders::ALL))
+            .x_axis(
+                Axis::default()
+                    .bounds([-60000.0, 0.0])
+                    .labels(vec![Span::raw("60s"), Span::raw("0s")]),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, 10.0])
+                    .labels(vec![Span::raw("0"), Span::raw("10")]),
+            );
+
+        let mut buf = Buffer::empty(area);
+        TimeChart::new(chart, [-60000.0, 0.0], [0.0, 10.0])
+            .gridline_style(Some(Style::default()))
+            .render(area, &mut buf);
+        // The 37 columns fit one gridline 30s apart, but no more, and the label at 60s would touch
+        // the one at the end of the axis.
+        assert_eq!(
+            get_row(&buf, 1),
+            "│10│                  ┊                  │"
+        );
+        assert_eq!(
+            get_row(&buf, 4),
+            "│60s                 30s               0s│"
+        );
+    }
+
+    #[test]
+    fn test_time_label() {
+        assert_eq!(get_time_label(30), "30s");
+        assert_eq!(get_time_label(90), "90s");
+        assert_eq!(get_time_label(300), "5m");
+        assert_eq!(get_time_label(5400), "90m");
+        assert_eq!(get_time_label(7200), "2h");
+    }
+
+    #[test]
+    fn test_value_at() {
+        let data = [(0.0, 0.0), (10.0, 10.0)];
+        assert_eq!(get_value_at(&data, 5.0), Some(5.0));
+        assert_eq!(get_value_at(&data, 0.0), Some(0.0));
+        assert_eq!(get_value_at(&data, -1.0), None);
+        assert_eq!(get_value_at(&data, 11.0), None);
+    }
+}
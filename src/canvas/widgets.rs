@@ -1,23 +1,45 @@
+pub mod activity_table;
+pub mod alerts_table;
 pub mod basic_table_arrows;
 pub mod battery_display;
+pub mod connection_table;
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod cpu_heatmap;
 pub mod disk_table;
+pub mod kernel_log_table;
 pub mod mem_basic;
 pub mod mem_graph;
 pub mod network_basic;
 pub mod network_graph;
+pub mod numa_table;
+pub mod pods_table;
+pub mod power_graph;
 pub mod process_table;
 pub mod temp_table;
+pub mod units_table;
+pub mod users_table;
+pub mod vm_table;
 
+pub use activity_table::ActivityTableWidget;
+pub use alerts_table::AlertsTableWidget;
 pub use basic_table_arrows::BasicTableArrows;
 pub use battery_display::BatteryDisplayWidget;
+pub use connection_table::ConnectionTableWidget;
 pub use cpu_basic::CpuBasicWidget;
 pub use cpu_graph::CpuGraphWidget;
+pub use cpu_heatmap::CpuHeatmapWidget;
 pub use disk_table::DiskTableWidget;
+pub use kernel_log_table::KernelLogTableWidget;
 pub use mem_basic::MemBasicWidget;
 pub use mem_graph::MemGraphWidget;
 pub use network_basic::NetworkBasicWidget;
 pub use network_graph::NetworkGraphWidget;
+pub use numa_table::NumaTableWidget;
+pub use pods_table::PodsTableWidget;
+pub use power_graph::PowerGraphWidget;
 pub use process_table::ProcessTableWidget;
 pub use temp_table::TempTableWidget;
+pub use units_table::UnitsTableWidget;
+pub use users_table::UsersTableWidget;
+pub use vm_table::VmTableWidget;
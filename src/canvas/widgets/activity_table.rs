@@ -0,0 +1,53 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_activity_rows` generates entries.
+const ACTIVITY_COLUMNS: [TextTableColumn; 3] = [
+    TextTableColumn::soft("Source", 1.0),
+    TextTableColumn::hard("Rate", 10),
+    TextTableColumn::hard("Total", 12),
+];
+
+pub trait ActivityTableWidget {
+    fn draw_activity_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl ActivityTableWidget for Painter {
+    fn draw_activity_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Activity",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let activity_widget_state =
+                    app_state.activity_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &ACTIVITY_COLUMNS,
+                    &app_state.canvas_data.activity_data,
+                    &mut activity_widget_state.scroll_state,
+                    &mut activity_widget_state.table_width_state,
+                ))
+            },
+            |activity_row| TextTableRow::new(activity_row),
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_alert_rows` generates entries.
+const ALERTS_COLUMNS: [TextTableColumn; 7] = [
+    TextTableColumn::hard("Time", 8),
+    TextTableColumn::hard("State", 8),
+    TextTableColumn::hard("Severity", 8),
+    TextTableColumn::hard("PID", 7),
+    TextTableColumn::soft("Name", 0.3),
+    TextTableColumn::hard("Usage", 21),
+    TextTableColumn::soft("Rule", 0.5),
+];
+
+pub trait AlertsTableWidget {
+    fn draw_alerts_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl AlertsTableWidget for Painter {
+    fn draw_alerts_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Alerts",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let alerts_widget_state =
+                    app_state.alerts_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &ALERTS_COLUMNS,
+                    &app_state.canvas_data.alert_data,
+                    &mut alerts_widget_state.scroll_state,
+                    &mut alerts_widget_state.table_width_state,
+                ))
+            },
+            |(alert_row, active_severity)| {
+                TextTableRow::new(alert_row).with_style(
+                    active_severity.and_then(|severity| self.colours.get_alert_style(severity)),
+                )
+            },
+        );
+    }
+}
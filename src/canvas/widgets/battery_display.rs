@@ -9,10 +9,16 @@ use tui::{
     layout::{Constraint, Direction, Layout, Rect},
     terminal::Frame,
     text::{Span, Spans},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Tabs},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, Paragraph, Row, Table, Tabs},
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+/// The number of rows in the battery details table, not counting the UPS load row.
+const BATTERY_TABLE_ROWS: u16 = 6;
+
+/// The minimum height of the charge history graph; it is hidden if there isn't enough room.
+const BATTERY_GRAPH_MIN_HEIGHT: u16 = 7;
+
 pub trait BatteryDisplayWidget {
     fn draw_battery_display<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
@@ -62,10 +68,43 @@ impl BatteryDisplayWidget for Painter {
                 ))
             };
 
+            let margined_draw_loc = Layout::default()
+                .constraints([Constraint::Percentage(100)])
+                .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+                .direction(Direction::Horizontal)
+                .split(draw_loc)[0];
+
+            // Leave room for the top border, the tabs, the table gap, and the rows.
+            let selected_battery = app_state
+                .canvas_data
+                .battery_data
+                .get(battery_widget_state.currently_selected_battery_index);
+            let num_rows = BATTERY_TABLE_ROWS
+                + match selected_battery {
+                    Some(battery) if battery.load.is_some() => 1,
+                    _ => 0,
+                };
+            let table_height = num_rows + table_gap + if draw_border { 2 } else { 1 };
+            let (table_draw_loc, graph_draw_loc) = if selected_battery.is_some()
+                && margined_draw_loc.height >= table_height + BATTERY_GRAPH_MIN_HEIGHT
+            {
+                let split_draw_loc = Layout::default()
+                    .constraints([Constraint::Length(table_height), Constraint::Min(0)])
+                    .direction(Direction::Vertical)
+                    .split(margined_draw_loc);
+                (split_draw_loc[0], Some(split_draw_loc[1]))
+            } else {
+                (margined_draw_loc, None)
+            };
+
             let battery_block = if draw_border {
                 Block::default()
                     .title(title)
-                    .borders(Borders::ALL)
+                    .borders(if graph_draw_loc.is_some() {
+                        Borders::TOP | Borders::LEFT | Borders::RIGHT
+                    } else {
+                        Borders::ALL
+                    })
                     .border_style(border_style)
             } else if is_on_widget {
                 Block::default()
@@ -106,12 +145,6 @@ impl BatteryDisplayWidget for Painter {
                 tab_draw_loc,
             );
 
-            let margined_draw_loc = Layout::default()
-                .constraints([Constraint::Percentage(100)])
-                .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
-                .direction(Direction::Horizontal)
-                .split(draw_loc)[0];
-
             if let Some(battery_details) = app_state
                 .canvas_data
                 .battery_data
@@ -129,7 +162,7 @@ impl BatteryDisplayWidget for Painter {
                     charge_percentage,
                 );
 
-                let battery_rows = vec![
+                let mut battery_rows = vec![
                     Row::new(vec![
                         Cell::from("Charge %").style(self.colours.text_style),
                         Cell::from(bars).style(if charge_percentage < 10.0 {
@@ -142,6 +175,8 @@ impl BatteryDisplayWidget for Painter {
                     ]),
                     Row::new(vec!["Consumption", &battery_details.watt_consumption])
                         .style(self.colours.text_style),
+                    Row::new(vec!["Voltage", &battery_details.voltage])
+                        .style(self.colours.text_style),
                     if let Some(duration_until_full) = &battery_details.duration_until_full {
                         Row::new(vec!["Time to full", duration_until_full])
                             .style(self.colours.text_style)
@@ -154,7 +189,15 @@ impl BatteryDisplayWidget for Painter {
                     },
                     Row::new(vec!["Health %", &battery_details.health])
                         .style(self.colours.text_style),
+                    Row::new(vec!["Cycle count", &battery_details.cycle_count])
+                        .style(self.colours.text_style),
                 ];
+                if let Some(load) = &battery_details.load {
+                    battery_rows.insert(
+                        2,
+                        Row::new(vec!["Load %", load]).style(self.colours.text_style),
+                    );
+                }
 
                 // Draw
                 f.render_widget(
@@ -162,8 +205,65 @@ impl BatteryDisplayWidget for Painter {
                         .block(battery_block)
                         .header(Row::new(vec![""]).bottom_margin(table_gap))
                         .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]),
-                    margined_draw_loc,
+                    table_draw_loc,
                 );
+
+                if let Some(graph_draw_loc) = graph_draw_loc {
+                    let time_start = -(STALE_MAX_MILLISECONDS as f64);
+                    let x_axis = if app_state.app_config_fields.hide_time
+                        || graph_draw_loc.height < TIME_LABEL_HEIGHT_LIMIT
+                    {
+                        Axis::default().bounds([time_start, 0.0])
+                    } else {
+                        Axis::default()
+                            .bounds([time_start, 0.0])
+                            .style(self.colours.graph_style)
+                            .labels(vec![
+                                Span::styled(
+                                    format!("{}s", STALE_MAX_MILLISECONDS / 1000),
+                                    self.colours.graph_style,
+                                ),
+                                Span::styled("0s".to_string(), self.colours.graph_style),
+                            ])
+                    };
+                    let y_axis = Axis::default()
+                        .style(self.colours.graph_style)
+                        .bounds([0.0, 100.5])
+                        .labels(vec![
+                            Span::styled("  0%", self.colours.graph_style),
+                            Span::styled("100%", self.colours.graph_style),
+                        ]);
+
+                    let graph_block = if draw_border {
+                        Block::default()
+                            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+                            .border_style(border_style)
+                    } else if is_on_widget {
+                        Block::default()
+                            .borders(SIDE_BORDERS)
+                            .border_style(self.colours.highlighted_border_style)
+                    } else {
+                        Block::default().borders(Borders::NONE)
+                    };
+
+                    f.render_widget(
+                        Chart::new(vec![Dataset::default()
+                            .marker(app_state.app_config_fields.graph_style.get_marker())
+                            .style(if charge_percentage < 10.0 {
+                                self.colours.low_battery_colour
+                            } else if charge_percentage < 50.0 {
+                                self.colours.medium_battery_colour
+                            } else {
+                                self.colours.high_battery_colour
+                            })
+                            .data(&battery_details.charge_data)
+                            .graph_type(tui::widgets::GraphType::Line)])
+                        .block(graph_block)
+                        .x_axis(x_axis)
+                        .y_axis(y_axis),
+                        graph_draw_loc,
+                    );
+                }
             } else {
                 let mut contents = vec![Spans::default(); table_gap.into()];
 
@@ -0,0 +1,84 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app::{self, data_harvester::connections::ConnectionSorting},
+    canvas::{
+        components::{
+            TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow, TextTableSort,
+        },
+        Painter,
+    },
+};
+
+/// In the order that `convert_connection_rows` generates entries, as well as
+/// [`ConnectionSorting::ORDER`].
+const CONN_COLUMNS: [TextTableColumn; 6] = [
+    TextTableColumn::hard("Proto", 6),
+    TextTableColumn::soft("Local", 0.3),
+    TextTableColumn::soft("Remote", 0.3),
+    TextTableColumn::hard("State", 12),
+    TextTableColumn::hard("PID", 8),
+    TextTableColumn::soft("Name", 0.2),
+];
+
+pub trait ConnectionTableWidget {
+    fn draw_connection_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl ConnectionTableWidget for Painter {
+    fn draw_connection_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let search_base = match app_state.conn_state.widget_states.get(&widget_id) {
+            Some(conn_widget_state) if conn_widget_state.is_searching => {
+                format!("─ Search: {}_ ", conn_widget_state.search_query)
+            }
+            Some(conn_widget_state) if !conn_widget_state.search_query.is_empty() => {
+                format!("─ Search: {} ", conn_widget_state.search_query)
+            }
+            _ => String::new(),
+        };
+
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Connections",
+                title_extra: search_base,
+                draw_border,
+            },
+            |app_state| {
+                let conn_widget_state = app_state.conn_state.widget_states.get_mut(&widget_id)?;
+                let connection_data = app_state
+                    .canvas_data
+                    .connection_data
+                    .get(&widget_id)
+                    .map(|data| data.as_slice())
+                    .unwrap_or(&[]);
+                let sort_column = ConnectionSorting::ORDER
+                    .iter()
+                    .position(|sorting| *sorting == conn_widget_state.sorting_type)
+                    .unwrap_or(0);
+                Some(
+                    TextTableData::new(
+                        &CONN_COLUMNS,
+                        connection_data,
+                        &mut conn_widget_state.scroll_state,
+                        &mut conn_widget_state.table_width_state,
+                    )
+                    .sort(TextTableSort {
+                        column: sort_column,
+                        is_descending: conn_widget_state.is_sort_descending,
+                    }),
+                )
+            },
+            |conn_row| TextTableRow::new(conn_row),
+        );
+    }
+}
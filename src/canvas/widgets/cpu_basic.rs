@@ -1,4 +1,4 @@
-use std::cmp::min;
+use std::cmp::{max, min};
 
 use crate::{
     app::App,
@@ -53,10 +53,11 @@ impl CpuBasicWidget for Painter {
 
             if draw_loc.height > 0 {
                 let remaining_height = usize::from(draw_loc.height);
-                const REQUIRED_COLUMNS: usize = 4;
+                const MIN_COLUMNS: usize = 4;
 
-                let chunk_vec =
-                    vec![Constraint::Percentage((100 / REQUIRED_COLUMNS) as u16); REQUIRED_COLUMNS];
+                // If the rows are capped, spread the CPUs over as many more columns as it takes.
+                let num_columns = max(MIN_COLUMNS, num_cpus.div_ceil(remaining_height));
+                let chunk_vec = vec![Constraint::Ratio(1, num_columns as u32); num_columns];
                 let chunks = Layout::default()
                     .constraints(chunk_vec)
                     .direction(Direction::Horizontal)
@@ -148,8 +149,8 @@ impl CpuBasicWidget for Painter {
                 for (itx, chunk) in chunks.iter().enumerate() {
                     // Explicitly check... don't want an accidental DBZ or underflow, this ensures
                     // to_divide is > 0
-                    if REQUIRED_COLUMNS > itx {
-                        let to_divide = REQUIRED_COLUMNS - itx;
+                    if num_columns > itx {
+                        let to_divide = num_columns - itx;
                         let how_many_cpus = min(
                             remaining_height,
                             (row_counter / to_divide)
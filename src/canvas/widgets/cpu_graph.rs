@@ -2,27 +2,32 @@ use once_cell::sync::Lazy;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App},
+    app::{layout_manager::WidgetDirection, remote_hosts::get_host_data, App},
     canvas::{
+        canvas_colours::CanvasColours,
         drawing_utils::{get_column_widths, get_start_position, interpolate_points},
+        time_chart::{get_graph_dataset, TimeChart},
         Painter,
     },
     constants::*,
-    data_conversion::ConvertedCpuData,
+    data_conversion::{ConvertedCpuData, CpuLegendEntry},
 };
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
+    style::Style,
     terminal::Frame,
     text::Span,
     text::{Spans, Text},
     widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table},
 };
 
-const CPU_LEGEND_HEADER: [&str; 2] = ["CPU", "Use%"];
+const CPU_LEGEND_HEADER: [&str; 3] = ["CPU", "Use%", "Freq"];
+/// The minimum legend width needed to fit the frequency column.
+const FREQUENCY_WIDTH_LIMIT: u16 = 22;
 const AVG_POSITION: usize = 1;
+const CPU_Y_BOUNDS: [f64; 2] = [0.0, 100.5];
 const ALL_POSITION: usize = 0;
 
 static CPU_LEGEND_HEADER_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
@@ -32,6 +37,47 @@ static CPU_LEGEND_HEADER_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
         .collect::<Vec<_>>()
 });
 
+/// Returns the data of a legend entry, which is either one of the CPUs or a group of them.
+fn get_entry_data<'a>(
+    entry: CpuLegendEntry, cpu_data: &'a [ConvertedCpuData], cpu_group_data: &'a [ConvertedCpuData],
+) -> Option<&'a ConvertedCpuData> {
+    match entry {
+        CpuLegendEntry::Cpu(index) | CpuLegendEntry::GroupedCpu(index) => cpu_data.get(index),
+        CpuLegendEntry::Group(index) => cpu_group_data.get(index),
+    }
+}
+
+fn get_entry_data_mut<'a>(
+    entry: CpuLegendEntry, cpu_data: &'a mut [ConvertedCpuData],
+    cpu_group_data: &'a mut [ConvertedCpuData],
+) -> Option<&'a mut ConvertedCpuData> {
+    match entry {
+        CpuLegendEntry::Cpu(index) | CpuLegendEntry::GroupedCpu(index) => cpu_data.get_mut(index),
+        CpuLegendEntry::Group(index) => cpu_group_data.get_mut(index),
+    }
+}
+
+/// Returns the style of a legend entry, which is also used for its line in the graph.  Each CPU
+/// keeps its colour whether or not it is grouped.
+fn get_entry_style(colours: &CanvasColours, entry: CpuLegendEntry, show_avg_cpu: bool) -> Style {
+    match entry {
+        CpuLegendEntry::Cpu(ALL_POSITION) => colours.all_colour_style,
+        CpuLegendEntry::Cpu(AVG_POSITION) if show_avg_cpu => colours.avg_colour_style,
+        CpuLegendEntry::Cpu(index) | CpuLegendEntry::GroupedCpu(index) => {
+            let first_core = if show_avg_cpu {
+                AVG_POSITION + 1
+            } else {
+                ALL_POSITION + 1
+            };
+            colours.cpu_colour_styles
+                [index.saturating_sub(first_core) % colours.cpu_colour_styles.len()]
+        }
+        CpuLegendEntry::Group(index) => {
+            colours.cpu_colour_styles[index % colours.cpu_colour_styles.len()]
+        }
+    }
+}
+
 pub trait CpuGraphWidget {
     fn draw_cpu<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
@@ -135,14 +181,28 @@ impl CpuGraphWidget for Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&widget_id) {
-            let cpu_data: &mut [ConvertedCpuData] = &mut app_state.canvas_data.cpu_data;
+            let (host_name, data_collection, canvas_data) = get_host_data(
+                &app_state.data_collection,
+                &mut app_state.canvas_data,
+                &mut app_state.remote_hosts,
+                cpu_widget_state.host,
+            );
+            let history_offset = data_collection.get_history_offset(app_state.is_frozen);
+            let time_end = -(history_offset as f64);
+            let time_start = time_end - cpu_widget_state.current_display_time as f64;
 
             let display_time_labels = vec![
                 Span::styled(
-                    format!("{}s", cpu_widget_state.current_display_time / 1000),
+                    format!(
+                        "{}s",
+                        (cpu_widget_state.current_display_time + history_offset) / 1000
+                    ),
+                    self.colours.graph_style,
+                ),
+                Span::styled(
+                    format!("{}s", history_offset / 1000),
                     self.colours.graph_style,
                 ),
-                Span::styled("0s".to_string(), self.colours.graph_style),
             ];
 
             let y_axis_labels = vec![
@@ -150,150 +210,119 @@ impl CpuGraphWidget for Painter {
                 Span::styled("100%", self.colours.graph_style),
             ];
 
-            let time_start = -(cpu_widget_state.current_display_time as f64);
-
-            let x_axis = if app_state.app_config_fields.hide_time
+            let is_showing_time = if app_state.app_config_fields.hide_time
                 || (app_state.app_config_fields.autohide_time
                     && cpu_widget_state.autohide_timer.is_none())
             {
-                Axis::default().bounds([time_start, 0.0])
+                false
             } else if let Some(time) = cpu_widget_state.autohide_timer {
                 if std::time::Instant::now().duration_since(time).as_millis()
                     < AUTOHIDE_TIMEOUT_MILLISECONDS.into()
                 {
-                    Axis::default()
-                        .bounds([time_start, 0.0])
-                        .style(self.colours.graph_style)
-                        .labels(display_time_labels)
+                    true
                 } else {
                     cpu_widget_state.autohide_timer = None;
-                    Axis::default().bounds([time_start, 0.0])
+                    false
                 }
-            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
-                Axis::default().bounds([time_start, 0.0])
             } else {
+                draw_loc.height >= TIME_LABEL_HEIGHT_LIMIT
+            };
+
+            let x_axis = if is_showing_time {
                 Axis::default()
-                    .bounds([time_start, 0.0])
+                    .bounds([time_start, time_end])
                     .style(self.colours.graph_style)
                     .labels(display_time_labels)
+            } else {
+                Axis::default().bounds([time_start, time_end])
             };
 
             let y_axis = Axis::default()
                 .style(self.colours.graph_style)
-                .bounds([0.0, 100.5])
+                .bounds(CPU_Y_BOUNDS)
                 .labels(y_axis_labels);
 
-            let use_dot = app_state.app_config_fields.use_dot;
+            let graph_style = cpu_widget_state.graph_style;
+            let is_filled = cpu_widget_state.is_filled;
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
             let current_scroll_position = cpu_widget_state.scroll_state.current_scroll_position;
 
-            let interpolated_cpu_points = cpu_data
-                .iter_mut()
-                .enumerate()
-                .map(|(itx, cpu)| {
-                    let to_show = if current_scroll_position == ALL_POSITION {
-                        true
-                    } else {
-                        itx == current_scroll_position
-                    };
+            let cpu_data = &mut canvas_data.cpu_data;
+            let cpu_group_data = &mut canvas_data.cpu_group_data;
+            let legend_entries = &canvas_data.cpu_legend_entries;
 
-                    if to_show {
-                        if let Some(end_pos) = cpu
-                            .cpu_data
-                            .iter()
-                            .position(|(time, _data)| *time >= time_start)
-                        {
-                            if end_pos > 1 {
-                                let start_pos = end_pos - 1;
-                                let outside_point = cpu.cpu_data.get(start_pos);
-                                let inside_point = cpu.cpu_data.get(end_pos);
-
-                                if let (Some(outside_point), Some(inside_point)) =
-                                    (outside_point, inside_point)
-                                {
-                                    let old = *outside_point;
-
-                                    let new_point = (
-                                        time_start,
-                                        interpolate_points(outside_point, inside_point, time_start),
-                                    );
-
-                                    if let Some(to_replace) = cpu.cpu_data.get_mut(start_pos) {
-                                        *to_replace = new_point;
-                                        Some((start_pos, old))
-                                    } else {
-                                        None // Failed to get mutable reference.
-                                    }
-                                } else {
-                                    None // Point somehow doesn't exist in our data
-                                }
-                            } else {
-                                None // Point is already "leftmost", no need to interpolate.
-                            }
-                        } else {
-                            None // There is no point.
-                        }
+            // The CPUs of each group are left out of "All", as the group has a line of its own.
+            let shown_entries = if current_scroll_position == ALL_POSITION {
+                legend_entries
+                    .iter()
+                    .filter(|entry| !matches!(entry, CpuLegendEntry::GroupedCpu(_)))
+                    .copied()
+                    .collect::<Vec<_>>()
+            } else {
+                legend_entries
+                    .get(current_scroll_position)
+                    .copied()
+                    .into_iter()
+                    .collect()
+            };
+
+            let interpolated_cpu_points = shown_entries
+                .iter()
+                .map(|entry| {
+                    let cpu = get_entry_data_mut(*entry, cpu_data, cpu_group_data)?;
+                    let end_pos = cpu
+                        .cpu_data
+                        .iter()
+                        .position(|(time, _data)| *time >= time_start)?;
+
+                    // Nothing to do if the point is already "leftmost".
+                    if end_pos > 1 {
+                        let start_pos = end_pos - 1;
+                        let old = *cpu.cpu_data.get(start_pos)?;
+                        let inside_point = cpu.cpu_data.get(end_pos)?;
+                        let new_point = (
+                            time_start,
+                            interpolate_points(&old, inside_point, time_start),
+                        );
+
+                        let to_replace = cpu.cpu_data.get_mut(start_pos)?;
+                        *to_replace = new_point;
+                        Some((*entry, start_pos, old))
                     } else {
                         None
                     }
                 })
                 .collect::<Vec<_>>();
 
-            let dataset_vector: Vec<Dataset<'_>> = if current_scroll_position == ALL_POSITION {
-                cpu_data
-                    .iter()
-                    .enumerate()
-                    .rev()
-                    .map(|(itx, cpu)| {
-                        Dataset::default()
-                            .marker(if use_dot {
-                                Marker::Dot
-                            } else {
-                                Marker::Braille
-                            })
-                            .style(if show_avg_cpu && itx == AVG_POSITION {
-                                self.colours.avg_colour_style
-                            } else if itx == ALL_POSITION {
-                                self.colours.all_colour_style
-                            } else {
-                                self.colours.cpu_colour_styles[(itx - 1 // Because of the all position
-                                        - (if show_avg_cpu {
-                                            AVG_POSITION
-                                        } else {
-                                            0
-                                        }))
-                                    % self.colours.cpu_colour_styles.len()]
-                            })
-                            .data(&cpu.cpu_data[..])
-                            .graph_type(tui::widgets::GraphType::Line)
-                    })
-                    .collect()
-            } else if let Some(cpu) = cpu_data.get(current_scroll_position) {
-                vec![Dataset::default()
-                    .marker(if use_dot {
-                        Marker::Dot
-                    } else {
-                        Marker::Braille
-                    })
-                    .style(if show_avg_cpu && current_scroll_position == AVG_POSITION {
-                        self.colours.avg_colour_style
-                    } else {
-                        self.colours.cpu_colour_styles[(cpu_widget_state
-                            .scroll_state
-                            .current_scroll_position
-                            - 1 // Because of the all position
-                            - (if show_avg_cpu {
-                                AVG_POSITION
-                            } else {
-                                0
-                            }))
-                            % self.colours.cpu_colour_styles.len()]
-                    })
-                    .data(&cpu.cpu_data[..])
-                    .graph_type(tui::widgets::GraphType::Line)]
-            } else {
-                vec![]
-            };
+            let mut lines = Vec::new();
+            let mut dataset_vector: Vec<Dataset<'_>> = shown_entries
+                .iter()
+                .rev()
+                .filter_map(|entry| {
+                    let cpu = get_entry_data(*entry, cpu_data, cpu_group_data)?;
+                    Some(get_graph_dataset(
+                        &cpu.cpu_data[..],
+                        get_entry_style(&self.colours, *entry, show_avg_cpu),
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    ))
+                })
+                .collect();
+
+            let cpu_frequency_data = &canvas_data.cpu_frequency_data;
+            let is_showing_frequency =
+                app_state.app_config_fields.cpu_frequency_graph && !cpu_frequency_data.is_empty();
+            if is_showing_frequency {
+                // Drawn over the usage lines in a neutral style, as it isn't a legend entry.
+                dataset_vector.push(get_graph_dataset(
+                    &cpu_frequency_data[..],
+                    self.colours.graph_style,
+                    graph_style,
+                    false,
+                    &mut lines,
+                ));
+            }
 
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
@@ -302,8 +331,24 @@ impl CpuGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            // The global filter only applies to the processes of the local host.
+            let host_str = host_name
+                .map(|host_name| format!("─ {} ", host_name))
+                .unwrap_or_default();
+            let host_str_size = UnicodeSegmentation::graphemes(host_str.as_str(), true).count();
+
+            // How much of the usage is from the processes matching the global filter, if any.
+            let global_filter_str = app_state
+                .global_filter
+                .usage
+                .filter(|_| host_name.is_none())
+                .map(|usage| format!("─ global: {:.1}% ", usage.cpu_percent))
+                .unwrap_or_default();
+            let global_filter_str_size =
+                UnicodeSegmentation::graphemes(global_filter_str.as_str(), true).count();
+
             let title = if cfg!(target_family = "unix") {
-                let load_avg = app_state.canvas_data.load_avg_data;
+                let load_avg = canvas_data.load_avg_data;
                 let load_avg_str = format!(
                     "─ {:.2} {:.2} {:.2} ",
                     load_avg[0], load_avg[1], load_avg[2]
@@ -316,12 +361,16 @@ impl CpuGraphWidget for Painter {
 
                     Spans::from(vec![
                         Span::styled(" CPU ", self.colours.widget_title_style),
+                        Span::styled(host_str, self.colours.widget_title_style),
                         Span::styled(load_avg_str, self.colours.widget_title_style),
+                        Span::styled(global_filter_str, self.colours.widget_title_style),
                         Span::styled(
                             format!(
                                 "─{}─ Esc to go back ",
                                 "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                    load_avg_str_size
+                                    host_str_size
+                                        + load_avg_str_size
+                                        + global_filter_str_size
                                         + UnicodeSegmentation::graphemes(TITLE_BASE, true).count()
                                         + 2
                                 ))
@@ -332,7 +381,9 @@ impl CpuGraphWidget for Painter {
                 } else {
                     Spans::from(vec![
                         Span::styled(" CPU ", self.colours.widget_title_style),
+                        Span::styled(host_str, self.colours.widget_title_style),
                         Span::styled(load_avg_str, self.colours.widget_title_style),
+                        Span::styled(global_filter_str, self.colours.widget_title_style),
                     ])
                 }
             } else if app_state.is_expanded {
@@ -340,44 +391,68 @@ impl CpuGraphWidget for Painter {
 
                 Spans::from(vec![
                     Span::styled(" CPU ", self.colours.widget_title_style),
+                    Span::styled(host_str, self.colours.widget_title_style),
+                    Span::styled(global_filter_str, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
                             "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                                host_str_size
+                                    + global_filter_str_size
+                                    + UnicodeSegmentation::graphemes(TITLE_BASE, true).count()
+                                    + 2
                             ))
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(vec![Span::styled(" CPU ", self.colours.widget_title_style)])
+                Spans::from(vec![
+                    Span::styled(" CPU ", self.colours.widget_title_style),
+                    Span::styled(host_str, self.colours.widget_title_style),
+                    Span::styled(global_filter_str, self.colours.widget_title_style),
+                ])
             };
 
             f.render_widget(
-                Chart::new(dataset_vector)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(border_style),
-                    )
-                    .x_axis(x_axis)
-                    .y_axis(y_axis),
+                TimeChart::new(
+                    Chart::new(dataset_vector)
+                        .block(
+                            Block::default()
+                                .title(title)
+                                .borders(Borders::ALL)
+                                .border_style(border_style),
+                        )
+                        .x_axis(x_axis)
+                        .y_axis(y_axis),
+                    [time_start, time_end],
+                    CPU_Y_BOUNDS,
+                )
+                .lines(lines)
+                .gradient_colours(&self.colours.graph_gradient_colours)
+                // The frequency line is not a usage, so it keeps its colour.
+                .ignored_colour(if is_showing_frequency {
+                    self.colours.graph_style.fg
+                } else {
+                    None
+                })
+                .gridline_style(
+                    app_state
+                        .app_config_fields
+                        .show_time_gridlines
+                        .then_some(self.colours.graph_style),
+                ),
                 draw_loc,
             );
 
             // Reset interpolated points
-            cpu_data
-                .iter_mut()
-                .zip(interpolated_cpu_points)
-                .for_each(|(cpu, interpolation)| {
-                    if let Some((index, old_value)) = interpolation {
-                        if let Some(to_replace) = cpu.cpu_data.get_mut(index) {
-                            *to_replace = old_value;
-                        }
-                    }
-                });
+            for (entry, index, old_value) in interpolated_cpu_points.into_iter().flatten() {
+                if let Some(to_replace) = get_entry_data_mut(entry, cpu_data, cpu_group_data)
+                    .and_then(|cpu| cpu.cpu_data.get_mut(index))
+                {
+                    *to_replace = old_value;
+                }
+            }
         }
     }
 
@@ -388,7 +463,15 @@ impl CpuGraphWidget for Painter {
         if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&(widget_id - 1))
         {
             cpu_widget_state.is_legend_hidden = false;
-            let cpu_data: &mut [ConvertedCpuData] = &mut app_state.canvas_data.cpu_data;
+            let (_host_name, _data_collection, canvas_data) = get_host_data(
+                &app_state.data_collection,
+                &mut app_state.canvas_data,
+                &mut app_state.remote_hosts,
+                cpu_widget_state.host,
+            );
+            let cpu_data = &canvas_data.cpu_data;
+            let cpu_group_data = &canvas_data.cpu_group_data;
+            let legend_entries = &canvas_data.cpu_legend_entries;
             let cpu_table_state = &mut cpu_widget_state.scroll_state.table_state;
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
@@ -412,25 +495,53 @@ impl CpuGraphWidget for Painter {
                     .saturating_sub(start_position),
             ));
 
-            let sliced_cpu_data = &cpu_data[start_position..];
+            let sliced_cpu_data = legend_entries
+                .get(start_position..)
+                .unwrap_or(&[])
+                .iter()
+                .enumerate()
+                .filter_map(|(itx, entry)| {
+                    Some((
+                        itx,
+                        *entry,
+                        get_entry_data(*entry, cpu_data, cpu_group_data)?,
+                    ))
+                })
+                .collect::<Vec<_>>();
 
             let offset_scroll_index = cpu_widget_state
                 .scroll_state
                 .current_scroll_position
                 .saturating_sub(start_position);
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
+            let show_frequency = draw_loc.width >= FREQUENCY_WIDTH_LIMIT
+                && cpu_data.iter().any(|cpu| !cpu.frequency_value.is_empty());
+            let num_columns = if show_frequency { 3 } else { 2 };
 
             // Calculate widths
-            if recalculate_column_widths {
-                cpu_widget_state.table_width_state.desired_column_widths = vec![6, 4];
+            if recalculate_column_widths
+                || cpu_widget_state
+                    .table_width_state
+                    .desired_column_widths
+                    .len()
+                    != num_columns
+            {
+                let (desired_column_widths, soft_widths): (Vec<u16>, &[Option<f64>]) =
+                    if show_frequency {
+                        (vec![6, 4, 6], &[Some(0.4), Some(0.3), Some(0.3)])
+                    } else {
+                        (vec![6, 4], &[Some(0.5), Some(0.5)])
+                    };
+                cpu_widget_state.table_width_state.desired_column_widths = desired_column_widths;
                 cpu_widget_state.table_width_state.calculated_column_widths = get_column_widths(
                     draw_loc.width,
-                    &[None, None],
+                    &vec![None; num_columns],
                     &(CPU_LEGEND_HEADER_LENS
                         .iter()
+                        .take(num_columns)
                         .map(|width| Some(*width))
                         .collect::<Vec<_>>()),
-                    &[Some(0.5), Some(0.5)],
+                    soft_widths,
                     &(cpu_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -443,19 +554,23 @@ impl CpuGraphWidget for Painter {
 
             let dcw = &cpu_widget_state.table_width_state.desired_column_widths;
             let ccw = &cpu_widget_state.table_width_state.calculated_column_widths;
-            let cpu_rows = sliced_cpu_data.iter().enumerate().map(|(itx, cpu)| {
-                let mut truncated_name =
-                    if let (Some(desired_column_width), Some(calculated_column_width)) =
-                        (dcw.get(0), ccw.get(0))
-                    {
-                        if *desired_column_width > *calculated_column_width {
-                            Text::raw(&cpu.short_cpu_name)
-                        } else {
-                            Text::raw(&cpu.cpu_name)
-                        }
-                    } else {
-                        Text::raw(&cpu.cpu_name)
-                    };
+            let is_short_name = matches!(
+                (dcw.first(), ccw.first()),
+                (Some(desired_column_width), Some(calculated_column_width))
+                    if desired_column_width > calculated_column_width
+            );
+            let cpu_rows = sliced_cpu_data.into_iter().map(|(itx, entry, cpu)| {
+                let name = if is_short_name {
+                    &cpu.short_cpu_name
+                } else {
+                    &cpu.cpu_name
+                };
+                // Indent the CPUs of a group under it.
+                let truncated_name = if let CpuLegendEntry::GroupedCpu(_) = entry {
+                    Text::raw(format!(" {}", name))
+                } else {
+                    Text::raw(name.as_str())
+                };
 
                 let is_first_column_hidden = if let Some(calculated_column_width) = ccw.get(0) {
                     *calculated_column_width == 0
@@ -470,31 +585,22 @@ impl CpuGraphWidget for Painter {
                     Text::raw(&cpu.legend_value)
                 };
 
+                let mut cpu_string_row = vec![truncated_name, truncated_legend];
+                if show_frequency {
+                    cpu_string_row.push(Text::raw(&cpu.frequency_value));
+                }
+
                 if !is_first_column_hidden
                     && itx == offset_scroll_index
                     && itx + start_position == ALL_POSITION
                 {
-                    truncated_name.patch_style(self.colours.currently_selected_text_style);
-                    Row::new(vec![truncated_name, truncated_legend])
+                    cpu_string_row[0].patch_style(self.colours.currently_selected_text_style);
+                    Row::new(cpu_string_row)
                 } else {
-                    let cpu_string_row = vec![truncated_name, truncated_legend];
-
                     Row::new(cpu_string_row).style(if itx == offset_scroll_index {
                         self.colours.currently_selected_text_style
-                    } else if itx + start_position == ALL_POSITION {
-                        self.colours.all_colour_style
-                    } else if show_avg_cpu {
-                        if itx + start_position == AVG_POSITION {
-                            self.colours.avg_colour_style
-                        } else {
-                            self.colours.cpu_colour_styles[(itx + start_position
-                                - AVG_POSITION
-                                - 1)
-                                % self.colours.cpu_colour_styles.len()]
-                        }
                     } else {
-                        self.colours.cpu_colour_styles[(itx + start_position - ALL_POSITION - 1)
-                            % self.colours.cpu_colour_styles.len()]
+                        get_entry_style(&self.colours, entry, show_avg_cpu)
                     })
                 }
             });
@@ -515,7 +621,7 @@ impl CpuGraphWidget for Painter {
                             .border_style(border_and_title_style),
                     )
                     .header(
-                        Row::new(CPU_LEGEND_HEADER.to_vec())
+                        Row::new(CPU_LEGEND_HEADER[..num_columns].to_vec())
                             .style(self.colours.table_header_style)
                             .bottom_margin(table_gap),
                     )
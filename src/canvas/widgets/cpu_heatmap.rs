@@ -0,0 +1,167 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The widths a cell can take, from widest to narrowest.  Only the widest cells have room to show
+/// the usage as a number; the others are just coloured.
+const CELL_WIDTHS: [usize; 3] = [4, 2, 1];
+
+pub trait CpuHeatmapWidget {
+    fn draw_cpu_heatmap<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+/// Returns the width of each cell and how many cells fit in a row, so that `num_cells` cells fit
+/// in `width` by `height` using the widest cells possible.  If even the narrowest cells don't
+/// fit, they are used anyway and the rest are cut off.
+fn get_cell_layout(num_cells: usize, width: usize, height: usize) -> (usize, usize) {
+    CELL_WIDTHS
+        .iter()
+        .map(|cell_width| (*cell_width, width / cell_width))
+        .find(|(_cell_width, cells_per_row)| {
+            *cells_per_row > 0 && num_cells.div_ceil(*cells_per_row) <= height
+        })
+        .unwrap_or((1, width))
+}
+
+impl CpuHeatmapWidget for Painter {
+    fn draw_cpu_heatmap<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = " CPU Heatmap ── Esc to go back ";
+            Spans::from(vec![
+                Span::styled(" CPU Heatmap ", self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                            UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                        ))
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(
+                " CPU Heatmap ",
+                self.colours.widget_title_style,
+            ))
+        };
+
+        // The first entry is the "All" entry of the CPU graph legend, and the average is next if
+        // it is shown; neither is a core.
+        let skipped_entries = if app_state.app_config_fields.show_average_cpu {
+            2
+        } else {
+            1
+        };
+        let core_usages = app_state
+            .canvas_data
+            .cpu_data
+            .iter()
+            .skip(skipped_entries)
+            .map(|cpu| cpu.cpu_data.last().map_or(0.0, |(_time, usage)| *usage))
+            .collect::<Vec<_>>();
+
+        let gradient_colours = if self.colours.graph_gradient_colours.is_empty() {
+            &self.colours.heatmap_colours
+        } else {
+            &self.colours.graph_gradient_colours
+        };
+        let get_cell_style = |usage: f64| {
+            let fraction = (usage / 100.0).clamp(0.0, 1.0);
+            let colour = gradient_colours
+                .get(
+                    (fraction * (gradient_colours.len().saturating_sub(1)) as f64).round() as usize,
+                )
+                .copied()
+                .unwrap_or(Color::Reset);
+            Style::default().fg(Color::Black).bg(colour)
+        };
+
+        let inner_width = usize::from(draw_loc.width.saturating_sub(2));
+        let inner_height = usize::from(draw_loc.height.saturating_sub(2));
+        let (cell_width, cells_per_row) =
+            get_cell_layout(core_usages.len(), inner_width, inner_height);
+
+        let rows = if cells_per_row > 0 {
+            core_usages
+                .chunks(cells_per_row)
+                .take(inner_height)
+                .map(|row_usages| {
+                    Spans::from(
+                        row_usages
+                            .iter()
+                            .map(|usage| {
+                                let text = if cell_width >= 4 {
+                                    format!("{:>3.0} ", usage.round())
+                                } else {
+                                    " ".repeat(cell_width)
+                                };
+                                Span::styled(text, get_cell_style(*usage))
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        f.render_widget(
+            Paragraph::new(Text::from(rows))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cell_layout() {
+        // Wide cells for a handful of cores...
+        assert_eq!(get_cell_layout(8, 40, 4), (4, 10));
+        // ...narrower ones once they don't fit...
+        assert_eq!(get_cell_layout(128, 40, 4), (1, 40));
+        assert_eq!(get_cell_layout(64, 40, 4), (2, 20));
+        // ...and the narrowest ones even if some have to be cut off.
+        assert_eq!(get_cell_layout(256, 40, 4), (1, 40));
+        assert_eq!(get_cell_layout(8, 0, 4), (1, 0));
+    }
+}
@@ -1,4 +1,3 @@
-use once_cell::sync::Lazy;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -9,7 +8,7 @@ use tui::{
 };
 
 use crate::{
-    app,
+    app::{self, data_harvester::disks::DiskSorting},
     canvas::{
         drawing_utils::{get_column_widths, get_start_position},
         Painter,
@@ -18,14 +17,49 @@ use crate::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-const DISK_HEADERS: [&str; 7] = ["Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s"];
+/// The header, hard width, and soft width of a column in the disk table.
+type DiskColumn = (&'static str, Option<u16>, Option<f64>);
 
-static DISK_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
-    DISK_HEADERS
-        .iter()
-        .map(|entry| entry.len() as u16)
-        .collect::<Vec<_>>()
-});
+const DISK_NAME_COLUMNS: [DiskColumn; 3] = [
+    ("Disk", None, Some(0.2)),
+    ("Mount", None, Some(0.2)),
+    ("Used", Some(4), None),
+];
+
+const DISK_INODE_COLUMNS: [DiskColumn; 1] = [("Inode%", Some(6), None)];
+
+const DISK_SPACE_IO_COLUMNS: [DiskColumn; 4] = [
+    ("Free", Some(6), None),
+    ("Total", Some(6), None),
+    ("R/s", Some(7), None),
+    ("W/s", Some(7), None),
+];
+
+const DISK_LATENCY_COLUMNS: [DiskColumn; 2] = [("Await", Some(7), None), ("Util", Some(4), None)];
+
+const DISK_SMART_COLUMNS: [DiskColumn; 4] = [
+    ("Temp", Some(5), None),
+    ("Health", Some(7), None),
+    ("Realloc", Some(7), None),
+    ("Wear", Some(4), None),
+];
+
+/// Returns the columns to display.  This must match the order in which `convert_disk_row` generates entries.
+fn get_disk_columns(app_config_fields: &app::AppConfigFields) -> Vec<DiskColumn> {
+    let mut columns = DISK_NAME_COLUMNS.to_vec();
+    if app_config_fields.use_disk_inodes {
+        columns.extend(DISK_INODE_COLUMNS);
+    }
+    columns.extend(DISK_SPACE_IO_COLUMNS);
+    if app_config_fields.use_disk_latency {
+        columns.extend(DISK_LATENCY_COLUMNS);
+    }
+    if app_config_fields.use_disk_smart {
+        columns.extend(DISK_SMART_COLUMNS);
+    }
+
+    columns
+}
 
 pub trait DiskTableWidget {
     fn draw_disk_table<B: Backend>(
@@ -39,8 +73,18 @@ impl DiskTableWidget for Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
+        const DOWN_ARROW: char = '▼';
+        const UP_ARROW: char = '▲';
+
         let recalculate_column_widths = app_state.should_get_widget_bounds();
         if let Some(disk_widget_state) = app_state.disk_state.widget_states.get_mut(&widget_id) {
+            let disk_data = app_state
+                .canvas_data
+                .disk_data
+                .get(&widget_id)
+                .map(|data| data.as_slice())
+                .unwrap_or(&[]);
+
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
                 0
             } else {
@@ -56,6 +100,10 @@ impl DiskTableWidget for Painter {
                 app_state.is_force_redraw,
             );
             let is_on_widget = app_state.current_widget.widget_id == widget_id;
+            let sorting = disk_widget_state.sorting;
+            let is_searching = disk_widget_state.is_searching;
+            let search_query = &disk_widget_state.search_query;
+            let num_hidden = disk_widget_state.hidden_mounts.len();
             let disk_table_state = &mut disk_widget_state.scroll_state.table_state;
             disk_table_state.select(Some(
                 disk_widget_state
@@ -63,13 +111,45 @@ impl DiskTableWidget for Painter {
                     .current_scroll_position
                     .saturating_sub(start_position),
             ));
-            let sliced_vec = &app_state.canvas_data.disk_data[start_position..];
+            let sliced_vec = disk_data.get(start_position..).unwrap_or(&[]);
+
+            let columns = get_disk_columns(&app_state.app_config_fields);
+            let headers = columns
+                .iter()
+                .map(|(header, _, _)| match sorting {
+                    Some((sorting_type, is_sort_descending))
+                        if header.parse::<DiskSorting>() == Ok(sorting_type) =>
+                    {
+                        format!(
+                            "{}{}",
+                            header,
+                            if is_sort_descending {
+                                DOWN_ARROW
+                            } else {
+                                UP_ARROW
+                            }
+                        )
+                    }
+                    _ => header.to_string(),
+                })
+                .collect::<Vec<_>>();
+            let headers_lens = headers
+                .iter()
+                .map(|header| UnicodeSegmentation::graphemes(header.as_str(), true).count() as u16)
+                .collect::<Vec<_>>();
 
             // Calculate widths
-            let hard_widths = [None, None, Some(4), Some(6), Some(6), Some(7), Some(7)];
+            let hard_widths = columns
+                .iter()
+                .map(|(_, hard_width, _)| *hard_width)
+                .collect::<Vec<_>>();
+            let soft_widths = columns
+                .iter()
+                .map(|(_, _, soft_width)| *soft_width)
+                .collect::<Vec<_>>();
             if recalculate_column_widths {
                 disk_widget_state.table_width_state.desired_column_widths = {
-                    let mut column_widths = DISK_HEADERS_LENS.clone();
+                    let mut column_widths = headers_lens.clone();
                     for row in sliced_vec {
                         for (col, entry) in row.iter().enumerate() {
                             if entry.len() as u16 > column_widths[col] {
@@ -100,11 +180,8 @@ impl DiskTableWidget for Painter {
                 disk_widget_state.table_width_state.calculated_column_widths = get_column_widths(
                     draw_loc.width,
                     &hard_widths,
-                    &(DISK_HEADERS_LENS
-                        .iter()
-                        .map(|w| Some(*w))
-                        .collect::<Vec<_>>()),
-                    &[Some(0.2), Some(0.2), None, None, None, None, None],
+                    &(headers_lens.iter().map(|w| Some(*w)).collect::<Vec<_>>()),
+                    &soft_widths,
                     &(disk_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -163,23 +240,38 @@ impl DiskTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
+            let search_base = if is_searching {
+                format!("─ Search: {}_ ", search_query)
+            } else if !search_query.is_empty() {
+                format!("─ Search: {} ", search_query)
+            } else {
+                String::new()
+            };
+            let hidden_base = if num_hidden > 0 {
+                format!("({} hidden) ", num_hidden)
+            } else {
+                String::new()
+            };
+
             let title_base = if app_state.app_config_fields.show_table_scroll_position {
                 let title_string = format!(
-                    " Disk ({} of {}) ",
+                    " Disk ({} of {}) {}{}",
                     disk_widget_state
                         .scroll_state
                         .current_scroll_position
                         .saturating_add(1),
-                    app_state.canvas_data.disk_data.len()
+                    disk_data.len(),
+                    hidden_base,
+                    search_base,
                 );
 
                 if title_string.len() <= draw_loc.width.into() {
                     title_string
                 } else {
-                    " Disk ".to_string()
+                    format!(" Disk {}{}", hidden_base, search_base)
                 }
             } else {
-                " Disk ".to_string()
+                format!(" Disk {}{}", hidden_base, search_base)
             };
 
             let title = if app_state.is_expanded {
@@ -245,7 +337,7 @@ impl DiskTableWidget for Painter {
                 Table::new(disk_rows)
                     .block(disk_block)
                     .header(
-                        Row::new(DISK_HEADERS.to_vec())
+                        Row::new(headers)
                             .style(self.colours.table_header_style)
                             .bottom_margin(table_gap),
                     )
@@ -0,0 +1,62 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_kernel_log_rows` generates entries.
+const KERNEL_LOG_COLUMNS: [TextTableColumn; 3] = [
+    TextTableColumn::hard("Time", 13),
+    TextTableColumn::hard("Level", 6),
+    TextTableColumn::soft("Message", 1.0),
+];
+
+pub trait KernelLogTableWidget {
+    fn draw_kernel_log_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl KernelLogTableWidget for Painter {
+    fn draw_kernel_log_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Kernel Log",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let kernel_log_widget_state = app_state
+                    .kernel_log_state
+                    .widget_states
+                    .get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &KERNEL_LOG_COLUMNS,
+                    &app_state.canvas_data.kernel_log_data,
+                    &mut kernel_log_widget_state.scroll_state,
+                    &mut kernel_log_widget_state.table_width_state,
+                ))
+            },
+            |(kernel_log_row, level)| {
+                let style = match level {
+                    0..=3 => Some(self.colours.kernel_error_style),
+                    4 => Some(self.colours.kernel_warning_style),
+                    _ => None,
+                };
+                TextTableRow::new(kernel_log_row).with_style(style)
+            },
+        );
+    }
+}
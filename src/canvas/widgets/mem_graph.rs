@@ -1,13 +1,17 @@
 use crate::{
-    app::App,
-    canvas::{drawing_utils::interpolate_points, Painter},
+    app::{remote_hosts::get_host_data, App, AxisRange},
+    canvas::{
+        drawing_utils::{get_auto_y_max, interpolate_points},
+        time_chart::{get_graph_dataset, TimeChart},
+        Painter,
+    },
     constants::*,
+    utils::gen_util::get_binary_bytes,
 };
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
-    symbols::Marker,
     terminal::Frame,
     text::Span,
     text::Spans,
@@ -21,159 +25,203 @@ pub trait MemGraphWidget {
     );
 }
 
+/// Replaces the last point before `time_start` with one interpolated at it, so that the line starts
+/// at the edge of the graph.  Returns the index and the value of the replaced point, to put it
+/// back after drawing.
+fn interpolate_start(data: &mut [(f64, f64)], time_start: f64) -> Option<(usize, (f64, f64))> {
+    let end_pos = data.iter().position(|(time, _data)| *time >= time_start)?;
+    if end_pos > 1 {
+        let start_pos = end_pos - 1;
+        let old = data[start_pos];
+        data[start_pos] = (
+            time_start,
+            interpolate_points(&old, &data[end_pos], time_start),
+        );
+        Some((start_pos, old))
+    } else {
+        None // Point is already "leftmost", no need to interpolate.
+    }
+}
+
 impl MemGraphWidget for Painter {
     fn draw_memory_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         if let Some(mem_widget_state) = app_state.mem_state.widget_states.get_mut(&widget_id) {
-            let mem_data: &mut [(f64, f64)] = &mut app_state.canvas_data.mem_data;
-            let swap_data: &mut [(f64, f64)] = &mut app_state.canvas_data.swap_data;
+            let (host_name, data_collection, canvas_data) = get_host_data(
+                &app_state.data_collection,
+                &mut app_state.canvas_data,
+                &mut app_state.remote_hosts,
+                mem_widget_state.host,
+            );
+
+            // When stacked, every line is the top of a band sitting on the ones below it.
+            let is_stacked = app_state.mem_state.is_stacked;
+            let (mem_data, cache_data, swap_data): (
+                &mut [(f64, f64)],
+                &mut [(f64, f64)],
+                &mut [(f64, f64)],
+            ) = if is_stacked {
+                (
+                    &mut canvas_data.stacked_mem_data.used,
+                    &mut canvas_data.stacked_mem_data.cache,
+                    &mut canvas_data.stacked_mem_data.swap,
+                )
+            } else {
+                (
+                    &mut canvas_data.mem_data,
+                    &mut [],
+                    &mut canvas_data.swap_data,
+                )
+            };
 
-            let time_start = -(mem_widget_state.current_display_time as f64);
+            let history_offset = data_collection.get_history_offset(app_state.is_frozen);
+            let time_end = -(history_offset as f64);
+            let time_start = time_end - mem_widget_state.current_display_time as f64;
 
             let display_time_labels = vec![
                 Span::styled(
-                    format!("{}s", mem_widget_state.current_display_time / 1000),
+                    format!(
+                        "{}s",
+                        (mem_widget_state.current_display_time + history_offset) / 1000
+                    ),
+                    self.colours.graph_style,
+                ),
+                Span::styled(
+                    format!("{}s", history_offset / 1000),
                     self.colours.graph_style,
                 ),
-                Span::styled("0s".to_string(), self.colours.graph_style),
             ];
+            let y_max = match app_state.app_config_fields.memory_y_range {
+                AxisRange::Fixed(y_max) => y_max,
+                AxisRange::Auto => {
+                    let max_value = mem_data
+                        .iter()
+                        .chain(cache_data.iter())
+                        .chain(swap_data.iter())
+                        .filter(|(time, _value)| *time >= time_start)
+                        .map(|(_time, value)| *value)
+                        .fold(0.0, f64::max);
+                    // Round it up to a multiple of 10%, so that the label stays round too.
+                    let y_max = (get_auto_y_max(mem_widget_state.auto_y_max, max_value) / 10.0)
+                        .ceil()
+                        .clamp(1.0, 10.0)
+                        * 10.0;
+                    mem_widget_state.auto_y_max = Some(y_max);
+                    y_max
+                }
+            };
+            let y_bounds = [0.0, y_max + 0.5];
             let y_axis_label = vec![
                 Span::styled("  0%", self.colours.graph_style),
-                Span::styled("100%", self.colours.graph_style),
+                Span::styled(format!("{:>3.0}%", y_max), self.colours.graph_style),
             ];
 
-            let x_axis = if app_state.app_config_fields.hide_time
+            let is_showing_time = if app_state.app_config_fields.hide_time
                 || (app_state.app_config_fields.autohide_time
                     && mem_widget_state.autohide_timer.is_none())
             {
-                Axis::default().bounds([time_start, 0.0])
+                false
             } else if let Some(time) = mem_widget_state.autohide_timer {
                 if std::time::Instant::now().duration_since(time).as_millis()
                     < AUTOHIDE_TIMEOUT_MILLISECONDS.into()
                 {
-                    Axis::default()
-                        .bounds([time_start, 0.0])
-                        .style(self.colours.graph_style)
-                        .labels(display_time_labels)
+                    true
                 } else {
                     mem_widget_state.autohide_timer = None;
-                    Axis::default().bounds([time_start, 0.0])
+                    false
                 }
-            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
-                Axis::default().bounds([time_start, 0.0])
             } else {
+                draw_loc.height >= TIME_LABEL_HEIGHT_LIMIT
+            };
+
+            let x_axis = if is_showing_time {
                 Axis::default()
-                    .bounds([time_start, 0.0])
+                    .bounds([time_start, time_end])
                     .style(self.colours.graph_style)
                     .labels(display_time_labels)
+            } else {
+                Axis::default().bounds([time_start, time_end])
             };
 
             let y_axis = Axis::default()
                 .style(self.colours.graph_style)
-                .bounds([0.0, 100.5])
+                .bounds(y_bounds)
                 .labels(y_axis_label);
 
             // Interpolate values to avoid ugly gaps
-            let interpolated_mem_point = if let Some(end_pos) = mem_data
-                .iter()
-                .position(|(time, _data)| *time >= time_start)
-            {
-                if end_pos > 1 {
-                    let start_pos = end_pos - 1;
-                    let outside_point = mem_data.get(start_pos);
-                    let inside_point = mem_data.get(end_pos);
-
-                    if let (Some(outside_point), Some(inside_point)) = (outside_point, inside_point)
-                    {
-                        let old = *outside_point;
-
-                        let new_point = (
-                            time_start,
-                            interpolate_points(outside_point, inside_point, time_start),
-                        );
-
-                        if let Some(to_replace) = mem_data.get_mut(start_pos) {
-                            *to_replace = new_point;
-                            Some((start_pos, old))
-                        } else {
-                            None // Failed to get mutable reference.
-                        }
-                    } else {
-                        None // Point somehow doesn't exist in our data
-                    }
-                } else {
-                    None // Point is already "leftmost", no need to interpolate.
-                }
-            } else {
-                None // There is no point.
-            };
-
-            let interpolated_swap_point = if let Some(end_pos) = swap_data
-                .iter()
-                .position(|(time, _data)| *time >= time_start)
-            {
-                if end_pos > 1 {
-                    let start_pos = end_pos - 1;
-                    let outside_point = swap_data.get(start_pos);
-                    let inside_point = swap_data.get(end_pos);
-
-                    if let (Some(outside_point), Some(inside_point)) = (outside_point, inside_point)
-                    {
-                        let old = *outside_point;
-
-                        let new_point = (
-                            time_start,
-                            interpolate_points(outside_point, inside_point, time_start),
-                        );
-
-                        if let Some(to_replace) = swap_data.get_mut(start_pos) {
-                            *to_replace = new_point;
-                            Some((start_pos, old))
-                        } else {
-                            None // Failed to get mutable reference.
-                        }
-                    } else {
-                        None // Point somehow doesn't exist in our data
-                    }
-                } else {
-                    None // Point is already "leftmost", no need to interpolate.
-                }
-            } else {
-                None // There is no point.
-            };
+            let interpolated_mem_point = interpolate_start(mem_data, time_start);
+            let interpolated_cache_point = interpolate_start(cache_data, time_start);
+            let interpolated_swap_point = interpolate_start(swap_data, time_start);
 
+            let graph_style = mem_widget_state.graph_style;
+            let is_filled = mem_widget_state.is_filled || is_stacked;
+            let mut lines = Vec::new();
             let mut mem_canvas_vec: Vec<Dataset<'_>> = vec![];
 
-            if let Some((label_percent, label_frac)) = &app_state.canvas_data.mem_labels {
+            if let Some((label_percent, label_frac)) = &canvas_data.mem_labels {
                 let mem_label = format!("RAM:{}{}", label_percent, label_frac);
                 mem_canvas_vec.push(
-                    Dataset::default()
-                        .name(mem_label)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.ram_style)
-                        .data(mem_data)
-                        .graph_type(tui::widgets::GraphType::Line),
+                    get_graph_dataset(
+                        mem_data,
+                        self.colours.ram_style,
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(mem_label),
                 );
             }
 
-            if let Some((label_percent, label_frac)) = &app_state.canvas_data.swap_labels {
+            if let Some((label_percent, label_frac)) = &canvas_data.cache_labels {
+                if !cache_data.is_empty() {
+                    let cache_label = format!("CACHE:{}{}", label_percent, label_frac);
+                    mem_canvas_vec.push(
+                        get_graph_dataset(
+                            cache_data,
+                            self.colours.cache_style,
+                            graph_style,
+                            is_filled,
+                            &mut lines,
+                        )
+                        .name(cache_label),
+                    );
+                }
+            }
+
+            if let Some((label_percent, label_frac)) = &canvas_data.swap_labels {
                 let swap_label = format!("SWP:{}{}", label_percent, label_frac);
+                mem_canvas_vec.push(
+                    get_graph_dataset(
+                        swap_data,
+                        self.colours.swap_style,
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(swap_label),
+                );
+            }
+
+            // Huge pages aren't graphed, so this only adds an entry to the legend.
+            if let Some((label_percent, label_frac)) = &canvas_data.huge_pages_labels {
+                let huge_pages_label = format!("HUGE:{}{}", label_percent, label_frac);
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(huge_pages_label)
+                        .style(self.colours.text_style)
+                        .data(&[]),
+                );
+            }
+
+            // Likewise, shared memory is already part of the RAM usage, so it's only in the legend.
+            if let Some((label_percent, label_frac)) = &canvas_data.shared_memory_labels {
+                let shared_memory_label = format!("SHM:{}{}", label_percent, label_frac);
                 mem_canvas_vec.push(
                     Dataset::default()
-                        .name(swap_label)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.swap_style)
-                        .data(swap_data)
-                        .graph_type(tui::widgets::GraphType::Line),
+                        .name(shared_memory_label)
+                        .style(self.colours.text_style)
+                        .data(&[]),
                 );
             }
 
@@ -184,55 +232,98 @@ impl MemGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            // The global filter only applies to the processes of the local host.
+            let host_str = host_name
+                .map(|host_name| format!("─ {} ", host_name))
+                .unwrap_or_default();
+            let host_str_size = UnicodeSegmentation::graphemes(host_str.as_str(), true).count();
+
+            // How much memory the processes matching the global filter use, if there is one.
+            let global_filter_str = app_state
+                .global_filter
+                .usage
+                .filter(|_| host_name.is_none())
+                .map(|usage| {
+                    let (mem_value, mem_unit) = get_binary_bytes(usage.mem_bytes);
+                    format!(
+                        "─ global: {:.1}{} ({:.1}%) ",
+                        mem_value, mem_unit, usage.mem_percent
+                    )
+                })
+                .unwrap_or_default();
+            let global_filter_str_size =
+                UnicodeSegmentation::graphemes(global_filter_str.as_str(), true).count();
+
             let title = if app_state.is_expanded {
                 const TITLE_BASE: &str = " Memory ── Esc to go back ";
                 Spans::from(vec![
                     Span::styled(" Memory ", self.colours.widget_title_style),
+                    Span::styled(host_str, self.colours.widget_title_style),
+                    Span::styled(global_filter_str, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
                             "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                                host_str_size
+                                    + global_filter_str_size
+                                    + UnicodeSegmentation::graphemes(TITLE_BASE, true).count()
+                                    + 2
                             ))
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(
-                    " Memory ".to_string(),
-                    self.colours.widget_title_style,
-                ))
+                Spans::from(vec![
+                    Span::styled(" Memory ", self.colours.widget_title_style),
+                    Span::styled(host_str, self.colours.widget_title_style),
+                    Span::styled(global_filter_str, self.colours.widget_title_style),
+                ])
             };
 
             f.render_widget(
-                Chart::new(mem_canvas_vec)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(if app_state.current_widget.widget_id == widget_id {
-                                self.colours.highlighted_border_style
-                            } else {
-                                self.colours.border_style
-                            }),
-                    )
-                    .x_axis(x_axis)
-                    .y_axis(y_axis)
-                    .hidden_legend_constraints((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                TimeChart::new(
+                    Chart::new(mem_canvas_vec)
+                        .block(
+                            Block::default()
+                                .title(title)
+                                .borders(Borders::ALL)
+                                .border_style(if app_state.current_widget.widget_id == widget_id {
+                                    self.colours.highlighted_border_style
+                                } else {
+                                    self.colours.border_style
+                                }),
+                        )
+                        .x_axis(x_axis)
+                        .y_axis(y_axis)
+                        .hidden_legend_constraints((
+                            Constraint::Ratio(3, 4),
+                            Constraint::Ratio(3, 4),
+                        )),
+                    [time_start, time_end],
+                    y_bounds,
+                )
+                .lines(lines)
+                .gradient_colours(&self.colours.graph_gradient_colours)
+                .gridline_style(
+                    app_state
+                        .app_config_fields
+                        .show_time_gridlines
+                        .then_some(self.colours.graph_style),
+                ),
                 draw_loc,
             );
 
             // Now if you're done, reset any interpolated points!
-            if let Some((index, old_value)) = interpolated_mem_point {
-                if let Some(to_replace) = mem_data.get_mut(index) {
-                    *to_replace = old_value;
-                }
-            }
-
-            if let Some((index, old_value)) = interpolated_swap_point {
-                if let Some(to_replace) = swap_data.get_mut(index) {
-                    *to_replace = old_value;
+            for (data, interpolation) in [
+                (mem_data, interpolated_mem_point),
+                (cache_data, interpolated_cache_point),
+                (swap_data, interpolated_swap_point),
+            ] {
+                if let Some((index, old_value)) = interpolation {
+                    if let Some(to_replace) = data.get_mut(index) {
+                        *to_replace = old_value;
+                    }
                 }
             }
         }
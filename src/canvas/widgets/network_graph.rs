@@ -1,14 +1,16 @@
 use once_cell::sync::Lazy;
-use std::cmp::max;
+use std::{borrow::Cow, cmp::max};
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{App, AxisScaling},
+    app::{App, AxisRange, AxisScaling},
     canvas::{
-        drawing_utils::{get_column_widths, interpolate_points},
+        drawing_utils::{get_auto_y_max, get_column_widths, interpolate_points},
+        time_chart::{get_graph_dataset, TimeChart},
         Painter,
     },
     constants::*,
+    data_conversion::{get_log_network_points, scale_network_value},
     units::data_units::DataUnit,
     utils::gen_util::*,
 };
@@ -16,14 +18,13 @@ use crate::{
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
     terminal::Frame,
     text::Span,
     text::{Spans, Text},
     widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table},
 };
 
-const NETWORK_HEADERS: [&str; 4] = ["RX", "TX", "Total RX", "Total TX"];
+const NETWORK_HEADERS: [&str; 5] = ["RX", "TX", "Total RX", "Total TX", "Errors"];
 
 static NETWORK_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
     NETWORK_HEADERS
@@ -419,40 +420,75 @@ impl NetworkGraphWidget for Painter {
         }
 
         if let Some(network_widget_state) = app_state.net_state.widget_states.get_mut(&widget_id) {
-            let network_data_rx: &mut [(f64, f64)] = &mut app_state.canvas_data.network_data_rx;
-            let network_data_tx: &mut [(f64, f64)] = &mut app_state.canvas_data.network_data_tx;
+            let network_scale_type = &network_widget_state.scale_type.clone();
+            let network_unit_type = &app_state.app_config_fields.network_unit_type;
+            let network_use_binary_prefix = app_state.app_config_fields.network_use_binary_prefix;
 
-            let time_start = -(network_widget_state.current_display_time as f64);
+            // The data is kept on a linear scale, as each widget can switch its scale, so a log
+            // scale is worked out here.
+            let (mut log_rx, mut log_tx);
+            let (network_data_rx, network_data_tx): (&mut [(f64, f64)], &mut [(f64, f64)]) =
+                match network_scale_type {
+                    AxisScaling::Log => {
+                        log_rx = get_log_network_points(
+                            &app_state.canvas_data.network_data_rx,
+                            network_unit_type,
+                            network_use_binary_prefix,
+                        );
+                        log_tx = get_log_network_points(
+                            &app_state.canvas_data.network_data_tx,
+                            network_unit_type,
+                            network_use_binary_prefix,
+                        );
+                        (&mut log_rx, &mut log_tx)
+                    }
+                    AxisScaling::Linear => (
+                        &mut app_state.canvas_data.network_data_rx,
+                        &mut app_state.canvas_data.network_data_tx,
+                    ),
+                };
+
+            let history_offset = app_state
+                .data_collection
+                .get_history_offset(app_state.is_frozen);
+            let time_end = -(history_offset as f64);
+            let time_start = time_end - network_widget_state.current_display_time as f64;
 
             let display_time_labels = vec![
                 Span::styled(
-                    format!("{}s", network_widget_state.current_display_time / 1000),
+                    format!(
+                        "{}s",
+                        (network_widget_state.current_display_time + history_offset) / 1000
+                    ),
+                    self.colours.graph_style,
+                ),
+                Span::styled(
+                    format!("{}s", history_offset / 1000),
                     self.colours.graph_style,
                 ),
-                Span::styled("0s".to_string(), self.colours.graph_style),
             ];
             let x_axis = if app_state.app_config_fields.hide_time
                 || (app_state.app_config_fields.autohide_time
                     && network_widget_state.autohide_timer.is_none())
             {
-                Axis::default().bounds([time_start, 0.0])
+                Axis::default().bounds([time_start, time_end])
             } else if let Some(time) = network_widget_state.autohide_timer {
                 if std::time::Instant::now().duration_since(time).as_millis()
                     < AUTOHIDE_TIMEOUT_MILLISECONDS.into()
                 {
                     Axis::default()
-                        .bounds([time_start, 0.0])
+                        .bounds([time_start, time_end])
                         .style(self.colours.graph_style)
                         .labels(display_time_labels)
                 } else {
                     network_widget_state.autohide_timer = None;
-                    Axis::default().bounds([time_start, 0.0])
+                    Axis::default().bounds([time_start, time_end])
                 }
             } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
-                Axis::default().bounds([time_start, 0.0])
+                Axis::default().bounds([time_start, time_end])
             } else {
                 Axis::default()
-                    .bounds([time_start, 0.0])
+                    .bounds([time_start, time_end])
                     .style(self.colours.graph_style)
                     .labels(display_time_labels)
             };
@@ -547,20 +583,93 @@ impl NetworkGraphWidget for Painter {
 
             // Find the maximal rx/tx so we know how to scale, and return it.
 
-            let (_best_time, max_entry) = get_max_entry(
-                network_data_rx,
-                network_data_tx,
-                time_start,
-                &app_state.app_config_fields.network_scale_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+            let is_per_interface = app_state.net_state.is_per_interface;
+            // RX and TX each get a graph of their own, scaled separately, so they aren't split
+            // when there is a line per interface instead.
+            let is_split = app_state.net_state.is_split && !is_per_interface;
+            let network_interface_data = &app_state.canvas_data.network_interface_data;
+            let interface_points = network_interface_data
+                .iter()
+                .map(|interface| match network_scale_type {
+                    AxisScaling::Log => Cow::Owned(get_log_network_points(
+                        &interface.points,
+                        network_unit_type,
+                        network_use_binary_prefix,
+                    )),
+                    AxisScaling::Linear => Cow::Borrowed(interface.points.as_slice()),
+                })
+                .collect::<Vec<_>>();
+            let graph_style = network_widget_state.graph_style;
+            let is_filled = network_widget_state.is_filled;
 
-            let (max_range, labels) = adjust_network_data_point(
-                max_entry,
-                &app_state.app_config_fields.network_scale_type,
-                &app_state.app_config_fields.network_unit_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+            let (_best_time, max_entry) = if is_per_interface {
+                let (missing_time, missing_max) = get_max_entry(
+                    &[],
+                    &[],
+                    time_start,
+                    network_scale_type,
+                    network_use_binary_prefix,
+                );
+
+                interface_points
+                    .iter()
+                    .map(|points| {
+                        get_max_entry(
+                            points,
+                            &[],
+                            time_start,
+                            network_scale_type,
+                            network_use_binary_prefix,
+                        )
+                    })
+                    .fold((missing_time, missing_max), |acc, entry| {
+                        if entry.1 > acc.1 {
+                            entry
+                        } else {
+                            acc
+                        }
+                    })
+            } else {
+                get_max_entry(
+                    network_data_rx,
+                    if is_split { &[] } else { network_data_tx },
+                    time_start,
+                    network_scale_type,
+                    network_use_binary_prefix,
+                )
+            };
+
+            let network_y_range = app_state.app_config_fields.network_y_range;
+            let get_y_range = |max_entry: f64, auto_max_entry: &mut Option<f64>| {
+                // A log scale already goes up in whole units, so it is left alone.
+                let max_entry = match (network_scale_type, network_y_range) {
+                    (AxisScaling::Linear, AxisRange::Fixed(max_rate)) => {
+                        // The axis goes up to 1.5 times the entry that it is scaled for.
+                        scale_network_value(
+                            max_rate,
+                            network_scale_type,
+                            network_unit_type,
+                            network_use_binary_prefix,
+                        ) / 1.5
+                    }
+                    (AxisScaling::Linear, AxisRange::Auto) => {
+                        let max_entry = get_auto_y_max(*auto_max_entry, max_entry);
+                        *auto_max_entry = Some(max_entry);
+                        max_entry
+                    }
+                    (AxisScaling::Log, _) => max_entry,
+                };
+
+                adjust_network_data_point(
+                    max_entry,
+                    network_scale_type,
+                    network_unit_type,
+                    network_use_binary_prefix,
+                )
+            };
+
+            let (max_range, labels) =
+                get_y_range(max_entry, &mut network_widget_state.auto_max_entry);
 
             // Cache results.
             // network_widget_state.draw_max_range_cache = max_range;
@@ -576,6 +685,23 @@ impl NetworkGraphWidget for Painter {
                 .bounds([0.0, max_range])
                 .labels(y_axis_labels);
 
+            let (tx_max_range, tx_labels) = if is_split {
+                let (_best_time, tx_max_entry) = get_max_entry(
+                    &[],
+                    network_data_tx,
+                    time_start,
+                    network_scale_type,
+                    network_use_binary_prefix,
+                );
+                get_y_range(tx_max_entry, &mut network_widget_state.auto_max_tx_entry)
+            } else {
+                (max_range, Vec::new())
+            };
+            let tx_y_axis_labels = tx_labels
+                .iter()
+                .map(|label| Span::styled(label, self.colours.graph_style))
+                .collect::<Vec<_>>();
+
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
                 self.colours.highlighted_border_style
@@ -583,22 +709,32 @@ impl NetworkGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            let title_base = if is_per_interface {
+                " Network (per interface) ".to_string()
+            } else if let Some(selected_interface) = &app_state.net_state.selected_interface {
+                format!(" Network ({}) ", selected_interface)
+            } else {
+                " Network ".to_string()
+            };
+
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " Network ── Esc to go back ";
+                let expanded_title_base = format!("{}── Esc to go back ", title_base);
                 Spans::from(vec![
-                    Span::styled(" Network ", self.colours.widget_title_style),
+                    Span::styled(title_base, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
                             "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                                UnicodeSegmentation::graphemes(expanded_title_base.as_str(), true)
+                                    .count()
+                                    + 2
                             ))
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" Network ", self.colours.widget_title_style))
+                Spans::from(Span::styled(title_base, self.colours.widget_title_style))
             };
 
             let legend_constraints = if hide_legend {
@@ -607,29 +743,72 @@ impl NetworkGraphWidget for Painter {
                 (Constraint::Ratio(1, 1), Constraint::Ratio(3, 4))
             };
 
+            let errors_style = if app_state.app_config_fields.network_highlight_errors
+                && app_state.canvas_data.are_net_errors_increased
+            {
+                self.colours.invalid_query_style
+            } else {
+                self.colours.text_style
+            };
+            // The scale shares a line with the errors, as another line would not fit in smaller
+            // widgets, which hides the legend entirely.
+            let errors_dataset = Dataset::default()
+                .name(format!(
+                    "{} Scale: {}",
+                    app_state.canvas_data.net_errors_display,
+                    match network_scale_type {
+                        AxisScaling::Log => "log",
+                        AxisScaling::Linear => "linear",
+                    }
+                ))
+                .style(errors_style);
+
             // TODO: Add support for clicking on legend to only show that value on chart.
-            let dataset = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let mut lines = Vec::new();
+            let mut dataset = if is_per_interface {
+                network_interface_data
+                    .iter()
+                    .zip(&interface_points)
+                    .enumerate()
+                    .map(|(itx, (interface, points))| {
+                        get_graph_dataset(
+                            points,
+                            self.colours.cpu_colour_styles
+                                [itx % self.colours.cpu_colour_styles.len()],
+                            graph_style,
+                            is_filled,
+                            &mut lines,
+                        )
+                        .name(&interface.legend)
+                    })
+                    .collect::<Vec<_>>()
+            } else if is_split {
+                vec![get_graph_dataset(
+                    network_data_rx,
+                    self.colours.rx_style,
+                    graph_style,
+                    is_filled,
+                    &mut lines,
+                )
+                .name(&app_state.canvas_data.rx_display)]
+            } else if app_state.app_config_fields.use_old_network_legend && !hide_legend {
                 vec![
-                    Dataset::default()
-                        .name(format!("RX: {:7}", app_state.canvas_data.rx_display))
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.rx_style)
-                        .data(network_data_rx)
-                        .graph_type(tui::widgets::GraphType::Line),
-                    Dataset::default()
-                        .name(format!("TX: {:7}", app_state.canvas_data.tx_display))
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.tx_style)
-                        .data(network_data_tx)
-                        .graph_type(tui::widgets::GraphType::Line),
+                    get_graph_dataset(
+                        network_data_rx,
+                        self.colours.rx_style,
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(format!("RX: {:7}", app_state.canvas_data.rx_display)),
+                    get_graph_dataset(
+                        network_data_tx,
+                        self.colours.tx_style,
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(format!("TX: {:7}", app_state.canvas_data.tx_display)),
                     Dataset::default()
                         .name(format!(
                             "Total RX: {:7}",
@@ -645,45 +824,123 @@ impl NetworkGraphWidget for Painter {
                 ]
             } else {
                 vec![
-                    Dataset::default()
-                        .name(&app_state.canvas_data.rx_display)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.rx_style)
-                        .data(network_data_rx)
-                        .graph_type(tui::widgets::GraphType::Line),
-                    Dataset::default()
-                        .name(&app_state.canvas_data.tx_display)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.tx_style)
-                        .data(network_data_tx)
-                        .graph_type(tui::widgets::GraphType::Line),
+                    get_graph_dataset(
+                        network_data_rx,
+                        self.colours.rx_style,
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(&app_state.canvas_data.rx_display),
+                    get_graph_dataset(
+                        network_data_tx,
+                        self.colours.tx_style,
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(&app_state.canvas_data.tx_display),
                 ]
             };
 
-            f.render_widget(
-                Chart::new(dataset)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(if app_state.current_widget.widget_id == widget_id {
-                                self.colours.highlighted_border_style
-                            } else {
-                                self.colours.border_style
-                            }),
+            if !hide_legend {
+                dataset.push(errors_dataset);
+                if let Some(tcp_counts) = &app_state.canvas_data.tcp_counts_display {
+                    dataset.push(
+                        Dataset::default()
+                            .name(tcp_counts)
+                            .style(self.colours.text_style),
+                    );
+                }
+                dataset.extend(
+                    app_state
+                        .canvas_data
+                        .wireless_display
+                        .iter()
+                        .map(|wireless| {
+                            Dataset::default()
+                                .name(wireless)
+                                .style(self.colours.text_style)
+                        }),
+                );
+            }
+
+            let gridline_style = app_state
+                .app_config_fields
+                .show_time_gridlines
+                .then_some(self.colours.graph_style);
+
+            // When split, RX is graphed at the top and TX below it, with the time labels only at
+            // the very bottom.
+            let (rx_draw_loc, rx_x_axis) = if is_split {
+                let split_draw_loc = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                    .split(draw_loc);
+                let mut tx_lines = Vec::new();
+                let tx_dataset = get_graph_dataset(
+                    network_data_tx,
+                    self.colours.tx_style,
+                    graph_style,
+                    is_filled,
+                    &mut tx_lines,
+                )
+                .name(&app_state.canvas_data.tx_display);
+
+                f.render_widget(
+                    TimeChart::new(
+                        Chart::new(vec![tx_dataset])
+                            .block(
+                                Block::default()
+                                    .borders(Borders::ALL)
+                                    .border_style(border_style),
+                            )
+                            .x_axis(x_axis)
+                            .y_axis(
+                                Axis::default()
+                                    .style(self.colours.graph_style)
+                                    .bounds([0.0, tx_max_range])
+                                    .labels(tx_y_axis_labels),
+                            )
+                            .hidden_legend_constraints(legend_constraints),
+                        [time_start, time_end],
+                        [0.0, tx_max_range],
                     )
-                    .x_axis(x_axis)
-                    .y_axis(y_axis)
-                    .hidden_legend_constraints(legend_constraints),
-                draw_loc,
+                    .lines(tx_lines)
+                    .gridline_style(gridline_style),
+                    split_draw_loc[1],
+                );
+
+                (
+                    split_draw_loc[0],
+                    Axis::default().bounds([time_start, time_end]),
+                )
+            } else {
+                (draw_loc, x_axis)
+            };
+
+            f.render_widget(
+                TimeChart::new(
+                    Chart::new(dataset)
+                        .block(
+                            Block::default()
+                                .title(title)
+                                .borders(Borders::ALL)
+                                .border_style(if app_state.current_widget.widget_id == widget_id {
+                                    self.colours.highlighted_border_style
+                                } else {
+                                    self.colours.border_style
+                                }),
+                        )
+                        .x_axis(rx_x_axis)
+                        .y_axis(y_axis)
+                        .hidden_legend_constraints(legend_constraints),
+                    [time_start, time_end],
+                    [0.0, max_range],
+                )
+                .lines(lines)
+                .gridline_style(gridline_style),
+                rx_draw_loc,
             );
 
             // Now if you're done, reset any interpolated points!
@@ -714,6 +971,14 @@ impl NetworkGraphWidget for Painter {
         let tx_display = &app_state.canvas_data.tx_display;
         let total_rx_display = &app_state.canvas_data.total_rx_display;
         let total_tx_display = &app_state.canvas_data.total_tx_display;
+        let net_errors_display = &app_state.canvas_data.net_errors_display;
+        let errors_style = if app_state.app_config_fields.network_highlight_errors
+            && app_state.canvas_data.are_net_errors_increased
+        {
+            self.colours.invalid_query_style
+        } else {
+            self.colours.text_style
+        };
 
         // Gross but I need it to work...
         let total_network = vec![vec![
@@ -721,6 +986,7 @@ impl NetworkGraphWidget for Painter {
             Text::raw(tx_display),
             Text::raw(total_rx_display),
             Text::raw(total_tx_display),
+            Text::styled(net_errors_display, errors_style),
         ]];
         let mapped_network = total_network
             .into_iter()
@@ -729,12 +995,12 @@ impl NetworkGraphWidget for Painter {
         // Calculate widths
         let intrinsic_widths = get_column_widths(
             draw_loc.width,
-            &[None, None, None, None],
+            &[None, None, None, None, None],
             &(NETWORK_HEADERS_LENS
                 .iter()
                 .map(|s| Some(*s))
                 .collect::<Vec<_>>()),
-            &[Some(0.25); 4],
+            &[Some(0.15), Some(0.15), Some(0.15), Some(0.15), Some(0.4)],
             &(NETWORK_HEADERS_LENS
                 .iter()
                 .map(|s| Some(*s))
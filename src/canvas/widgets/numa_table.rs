@@ -0,0 +1,54 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_numa_rows` generates entries.
+const NUMA_COLUMNS: [TextTableColumn; 5] = [
+    TextTableColumn::hard("Node", 7),
+    TextTableColumn::soft("CPUs", 1.0),
+    TextTableColumn::hard("Total", 9),
+    TextTableColumn::hard("Used", 9),
+    TextTableColumn::hard("Use%", 7),
+];
+
+pub trait NumaTableWidget {
+    fn draw_numa_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl NumaTableWidget for Painter {
+    fn draw_numa_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "NUMA",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let numa_widget_state = app_state.numa_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &NUMA_COLUMNS,
+                    &app_state.canvas_data.numa_data,
+                    &mut numa_widget_state.scroll_state,
+                    &mut numa_widget_state.table_width_state,
+                ))
+            },
+            |numa_row| TextTableRow::new(numa_row),
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_pod_rows` generates entries.
+const PODS_COLUMNS: [TextTableColumn; 4] = [
+    TextTableColumn::soft("Namespace", 0.3),
+    TextTableColumn::soft("Pod", 0.7),
+    TextTableColumn::hard("CPU%", 7),
+    TextTableColumn::hard("Mem", 9),
+];
+
+pub trait PodsTableWidget {
+    fn draw_pods_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl PodsTableWidget for Painter {
+    fn draw_pods_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Pods",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let pods_widget_state = app_state.pods_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &PODS_COLUMNS,
+                    &app_state.canvas_data.pod_data,
+                    &mut pods_widget_state.scroll_state,
+                    &mut pods_widget_state.table_width_state,
+                ))
+            },
+            |pods_row| TextTableRow::new(pods_row),
+        );
+    }
+}
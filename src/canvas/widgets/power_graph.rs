@@ -0,0 +1,236 @@
+use crate::{
+    app::App,
+    canvas::{
+        drawing_utils::interpolate_points,
+        time_chart::{get_graph_dataset, TimeChart},
+        Painter,
+    },
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    terminal::Frame,
+    text::Span,
+    text::Spans,
+    widgets::{Axis, Block, Borders, Chart, Dataset},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait PowerGraphWidget {
+    fn draw_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl PowerGraphWidget for Painter {
+    fn draw_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(power_widget_state) = app_state.power_state.widget_states.get_mut(&widget_id) {
+            let power_data = &mut app_state.canvas_data.power_data;
+
+            let history_offset = app_state
+                .data_collection
+                .get_history_offset(app_state.is_frozen);
+            let time_end = -(history_offset as f64);
+            let time_start = time_end - power_widget_state.current_display_time as f64;
+
+            let display_time_labels = vec![
+                Span::styled(
+                    format!(
+                        "{}s",
+                        (power_widget_state.current_display_time + history_offset) / 1000
+                    ),
+                    self.colours.graph_style,
+                ),
+                Span::styled(
+                    format!("{}s", history_offset / 1000),
+                    self.colours.graph_style,
+                ),
+            ];
+
+            // Scale to the highest visible draw, rounded up to the nearest 5W.
+            let max_watts = power_data
+                .iter()
+                .flat_map(|domain| domain.power_data.iter())
+                .filter(|(time, _watts)| *time >= time_start)
+                .fold(0.0_f64, |max, (_time, watts)| max.max(*watts));
+            let max_watts = ((max_watts / 5.0).ceil() * 5.0).max(5.0);
+
+            let y_axis_label = vec![
+                Span::styled("  0W", self.colours.graph_style),
+                Span::styled(format!("{:.0}W", max_watts), self.colours.graph_style),
+            ];
+
+            let x_axis = if app_state.app_config_fields.hide_time
+                || (app_state.app_config_fields.autohide_time
+                    && power_widget_state.autohide_timer.is_none())
+            {
+                Axis::default().bounds([time_start, time_end])
+            } else if let Some(time) = power_widget_state.autohide_timer {
+                if std::time::Instant::now().duration_since(time).as_millis()
+                    < AUTOHIDE_TIMEOUT_MILLISECONDS.into()
+                {
+                    Axis::default()
+                        .bounds([time_start, time_end])
+                        .style(self.colours.graph_style)
+                        .labels(display_time_labels)
+                } else {
+                    power_widget_state.autohide_timer = None;
+                    Axis::default().bounds([time_start, time_end])
+                }
+            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
+                Axis::default().bounds([time_start, time_end])
+            } else {
+                Axis::default()
+                    .bounds([time_start, time_end])
+                    .style(self.colours.graph_style)
+                    .labels(display_time_labels)
+            };
+
+            let y_axis = Axis::default()
+                .style(self.colours.graph_style)
+                .bounds([0.0, max_watts + 0.5])
+                .labels(y_axis_label);
+
+            // Interpolate values to avoid ugly gaps
+            let interpolated_power_points = power_data
+                .iter_mut()
+                .map(|domain| {
+                    if let Some(end_pos) = domain
+                        .power_data
+                        .iter()
+                        .position(|(time, _data)| *time >= time_start)
+                    {
+                        if end_pos > 1 {
+                            let start_pos = end_pos - 1;
+                            let outside_point = domain.power_data.get(start_pos);
+                            let inside_point = domain.power_data.get(end_pos);
+
+                            if let (Some(outside_point), Some(inside_point)) =
+                                (outside_point, inside_point)
+                            {
+                                let old = *outside_point;
+
+                                let new_point = (
+                                    time_start,
+                                    interpolate_points(outside_point, inside_point, time_start),
+                                );
+
+                                if let Some(to_replace) = domain.power_data.get_mut(start_pos) {
+                                    *to_replace = new_point;
+                                    Some((start_pos, old))
+                                } else {
+                                    None // Failed to get mutable reference.
+                                }
+                            } else {
+                                None // Point somehow doesn't exist in our data
+                            }
+                        } else {
+                            None // Point is already "leftmost", no need to interpolate.
+                        }
+                    } else {
+                        None // There is no point.
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let graph_style = power_widget_state.graph_style;
+            let is_filled = power_widget_state.is_filled;
+            let mut lines = Vec::new();
+            let power_canvas_vec: Vec<Dataset<'_>> = power_data
+                .iter()
+                .enumerate()
+                .map(|(itx, domain)| {
+                    get_graph_dataset(
+                        &domain.power_data,
+                        self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()],
+                        graph_style,
+                        is_filled,
+                        &mut lines,
+                    )
+                    .name(format!("{}: {}", domain.name, domain.legend_value))
+                })
+                .collect();
+
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+
+            let title = if app_state.is_expanded {
+                const TITLE_BASE: &str = " Power ── Esc to go back ";
+                Spans::from(vec![
+                    Span::styled(" Power ", self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(
+                    " Power ".to_string(),
+                    self.colours.widget_title_style,
+                ))
+            };
+
+            f.render_widget(
+                TimeChart::new(
+                    Chart::new(power_canvas_vec)
+                        .block(
+                            Block::default()
+                                .title(title)
+                                .borders(Borders::ALL)
+                                .border_style(border_style),
+                        )
+                        .x_axis(x_axis)
+                        .y_axis(y_axis)
+                        .hidden_legend_constraints((
+                            Constraint::Ratio(3, 4),
+                            Constraint::Ratio(3, 4),
+                        )),
+                    [time_start, time_end],
+                    [0.0, max_watts + 0.5],
+                )
+                .lines(lines)
+                .gridline_style(
+                    app_state
+                        .app_config_fields
+                        .show_time_gridlines
+                        .then_some(self.colours.graph_style),
+                ),
+                draw_loc,
+            );
+
+            // Now if you're done, reset any interpolated points!
+            power_data
+                .iter_mut()
+                .zip(interpolated_power_points)
+                .for_each(|(domain, interpolation)| {
+                    if let Some((index, old_value)) = interpolation {
+                        if let Some(to_replace) = domain.power_data.get_mut(index) {
+                            *to_replace = old_value;
+                        }
+                    }
+                });
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
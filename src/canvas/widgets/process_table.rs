@@ -1,5 +1,5 @@
 use crate::{
-    app::App,
+    app::{data_harvester::processes::ProcessSorting, App, FollowMode},
     canvas::{
         drawing_utils::{get_column_widths, get_search_start_position, get_start_position},
         Painter,
@@ -10,83 +10,17 @@ use crate::{
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
     terminal::Frame,
     text::{Span, Spans, Text},
     widgets::{Block, Borders, Paragraph, Row, Table},
 };
 
+use std::{borrow::Cow, ops::Range};
+
 use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
 use unicode_width::UnicodeWidthStr;
 
-const PROCESS_HEADERS_HARD_WIDTH_NO_GROUP: &[Option<u16>] = &[
-    Some(7),
-    None,
-    Some(8),
-    Some(8),
-    Some(8),
-    Some(8),
-    Some(7),
-    Some(8),
-    #[cfg(target_family = "unix")]
-    None,
-    None,
-];
-const PROCESS_HEADERS_HARD_WIDTH_GROUPED: &[Option<u16>] = &[
-    Some(7),
-    None,
-    Some(8),
-    Some(8),
-    Some(8),
-    Some(8),
-    Some(7),
-    Some(8),
-];
-
-const PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_COMMAND: &[Option<f64>] =
-    &[None, Some(0.7), None, None, None, None, None, None];
-const PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_ELSE: &[Option<f64>] =
-    &[None, Some(0.3), None, None, None, None, None, None];
-
-const PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_COMMAND: &[Option<f64>] = &[
-    None,
-    Some(0.7),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    #[cfg(target_family = "unix")]
-    Some(0.05),
-    Some(0.2),
-];
-const PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_TREE: &[Option<f64>] = &[
-    None,
-    Some(0.5),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    #[cfg(target_family = "unix")]
-    Some(0.05),
-    Some(0.2),
-];
-const PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_ELSE: &[Option<f64>] = &[
-    None,
-    Some(0.3),
-    None,
-    None,
-    None,
-    None,
-    None,
-    None,
-    #[cfg(target_family = "unix")]
-    Some(0.05),
-    Some(0.2),
-];
-
 pub trait ProcessTableWidget {
     /// Draws and handles all process-related drawing.  Use this.
     /// - `widget_id` here represents the widget ID of the process widget itself!
@@ -226,14 +160,68 @@ impl ProcessTableWidget for Painter {
             } else {
                 " Processes ".to_string()
             };
+            let title_base = match proc_widget_state.follow_mode {
+                FollowMode::Off => title_base,
+                FollowMode::Following => format!("{}(following) ", title_base),
+                FollowMode::Pinned => format!("{}(pinned) ", title_base),
+            };
+            let title_base = if proc_widget_state.is_grouped && proc_widget_state.is_grouped_by_user
+            {
+                format!("{}(by user) ", title_base)
+            } else {
+                title_base
+            };
+            let title_base = if app_state.global_filter.query.is_some() {
+                format!(
+                    "{}(global: {}) ",
+                    title_base, app_state.global_filter.query_text
+                )
+            } else {
+                title_base
+            };
 
-            let title = if app_state.is_expanded
+            let is_showing_escape = app_state.is_expanded
                 && !proc_widget_state
                     .process_search_state
                     .search_state
                     .is_enabled
-                && !proc_widget_state.is_sort_open
-            {
+                && !proc_widget_state.is_sort_open;
+
+            // The number of zombies across all processes, regardless of the current search.
+            let zombie_count = app_state
+                .canvas_data
+                .single_process_data
+                .values()
+                .filter(|process| process.process_char == 'Z')
+                .count();
+            let zombie_title = if zombie_count > 0 {
+                Some(format!(
+                    "─ {} zombie{} ",
+                    zombie_count,
+                    if zombie_count == 1 { "" } else { "s" }
+                ))
+            } else {
+                None
+            }
+            .filter(|zombie_text| {
+                let escape_len = if is_showing_escape {
+                    "── Esc to go back ".len()
+                } else {
+                    0
+                };
+                UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                    + UnicodeSegmentation::graphemes(zombie_text.as_str(), true).count()
+                    + escape_len
+                    <= draw_loc.width.into()
+            });
+            let zombie_len = zombie_title
+                .as_ref()
+                .map(|zombie_text| {
+                    UnicodeSegmentation::graphemes(zombie_text.as_str(), true).count()
+                })
+                .unwrap_or(0);
+
+            let title = if is_showing_escape {
                 const ESCAPE_ENDING: &str = "── Esc to go back ";
 
                 let (chosen_title_base, expanded_title_base) = {
@@ -249,27 +237,36 @@ impl ProcessTableWidget for Painter {
                     }
                 };
 
-                Spans::from(vec![
-                    Span::styled(chosen_title_base, self.colours.widget_title_style),
-                    Span::styled(
-                        format!(
-                            "─{}─ Esc to go back ",
-                            "─".repeat(
-                                usize::from(draw_loc.width).saturating_sub(
-                                    UnicodeSegmentation::graphemes(
-                                        expanded_title_base.as_str(),
-                                        true
-                                    )
+                let mut spans = vec![Span::styled(
+                    chosen_title_base,
+                    self.colours.widget_title_style,
+                )];
+                if let Some(zombie_text) = zombie_title {
+                    spans.push(Span::styled(zombie_text, self.colours.zombie_process_style));
+                }
+                spans.push(Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(
+                            usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(expanded_title_base.as_str(), true)
                                     .count()
-                                        + 2
-                                )
+                                    + zombie_len
+                                    + 2
                             )
-                        ),
-                        border_style,
+                        )
                     ),
-                ])
+                    border_style,
+                ));
+
+                Spans::from(spans)
             } else {
-                Spans::from(Span::styled(title_base, self.colours.widget_title_style))
+                let mut spans = vec![Span::styled(title_base, self.colours.widget_title_style)];
+                if let Some(zombie_text) = zombie_title {
+                    spans.push(Span::styled(zombie_text, self.colours.zombie_process_style));
+                }
+
+                Spans::from(spans)
             };
 
             let process_block = if draw_border {
@@ -338,12 +335,15 @@ impl ProcessTableWidget for Painter {
                 );
 
                 // Calculate widths
-                // FIXME: See if we can move this into the recalculate block?  I want to move column widths into the column widths
-                let hard_widths = if proc_widget_state.is_grouped {
-                    PROCESS_HEADERS_HARD_WIDTH_GROUPED
-                } else {
-                    PROCESS_HEADERS_HARD_WIDTH_NO_GROUP
-                };
+                let shown_columns = proc_widget_state.columns.get_shown_columns();
+                let column_widths = &proc_widget_state.columns.column_widths;
+                let hard_widths = shown_columns
+                    .iter()
+                    .map(|column| match column_widths.get(column) {
+                        Some(width) => Some(*width),
+                        None => get_hard_width(column),
+                    })
+                    .collect::<Vec<_>>();
 
                 if recalculate_column_widths {
                     let mut column_widths = process_headers
@@ -374,7 +374,7 @@ impl ProcessTableWidget for Painter {
                         .table_width_state
                         .desired_column_widths
                         .iter()
-                        .zip(hard_widths)
+                        .zip(&hard_widths)
                         .map(|(current, hard)| {
                             if let Some(hard) = hard {
                                 if *hard > *current {
@@ -388,28 +388,24 @@ impl ProcessTableWidget for Painter {
                         })
                         .collect::<Vec<_>>();
 
-                    let soft_widths_max = if proc_widget_state.is_grouped {
-                        // Note grouped trees are not a thing.
-
-                        if proc_widget_state.is_using_command {
-                            PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_COMMAND
-                        } else {
-                            PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_ELSE
-                        }
-                    } else if proc_widget_state.is_using_command {
-                        PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_COMMAND
-                    } else if proc_widget_state.is_tree_mode {
-                        PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_TREE
-                    } else {
-                        PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_ELSE
-                    };
+                    let (is_grouped, is_using_command, is_tree_mode) = (
+                        proc_widget_state.is_grouped,
+                        proc_widget_state.is_using_command,
+                        proc_widget_state.is_tree_mode,
+                    );
+                    let soft_widths_max = shown_columns
+                        .iter()
+                        .map(|column| {
+                            get_soft_width_max(column, is_grouped, is_using_command, is_tree_mode)
+                        })
+                        .collect::<Vec<_>>();
 
                     proc_widget_state.table_width_state.calculated_column_widths =
                         get_column_widths(
                             draw_loc.width,
-                            hard_widths,
+                            &hard_widths,
                             &soft_widths_min,
-                            soft_widths_max,
+                            &soft_widths_max,
                             &(proc_widget_state
                                 .table_width_state
                                 .desired_column_widths
@@ -432,53 +428,147 @@ impl ProcessTableWidget for Painter {
                 let dcw = &proc_widget_state.table_width_state.desired_column_widths;
                 let ccw = &proc_widget_state.table_width_state.calculated_column_widths;
 
-                let process_rows = sliced_vec.iter().map(|(data, disabled)| {
-                    let truncated_data = data.iter().zip(hard_widths).enumerate().map(
-                        |(itx, ((entry, alternative), width))| {
-                            if let (Some(desired_col_width), Some(calculated_col_width)) =
-                                (dcw.get(itx), ccw.get(itx))
-                            {
-                                if width.is_none() {
-                                    if *desired_col_width > *calculated_col_width
-                                        && *calculated_col_width > 0
+                // The state of each row is needed to highlight zombie, uninterruptible, and stopped
+                // processes, and the PID to highlight tagged and alerted processes.
+                let finalized_process_data = app_state
+                    .canvas_data
+                    .finalized_process_data_map
+                    .get(&widget_id);
+                let is_grouped = proc_widget_state.is_grouped;
+                let is_using_command = proc_widget_state.is_using_command;
+                let tagged_pids = &proc_widget_state.tagged_pids;
+                let search_state = &proc_widget_state.process_search_state.search_state;
+                let search_query = if search_state.is_invalid_or_blank_search() {
+                    None
+                } else {
+                    search_state.query.as_ref()
+                };
+                let name_column = shown_columns.iter().position(|column| {
+                    matches!(
+                        column,
+                        ProcessSorting::ProcessName | ProcessSorting::Command
+                    )
+                });
+                let process_alerts = &app_state.process_alerts;
+                let process_rows = sliced_vec
+                    .iter()
+                    .enumerate()
+                    .map(|(row, (data, disabled))| {
+                        let process = finalized_process_data
+                            .and_then(|processes| processes.get(start_position + row));
+                        let process_char = process.map(|process| process.process_char);
+                        let is_tagged = match process {
+                            Some(process) if is_grouped => process
+                                .group_pids
+                                .iter()
+                                .any(|pid| tagged_pids.contains(pid)),
+                            Some(process) => tagged_pids.contains(&process.pid),
+                            None => false,
+                        };
+                        let is_alerted = match process {
+                            Some(process) if is_grouped => process
+                                .group_pids
+                                .iter()
+                                .any(|pid| process_alerts.is_alerting(*pid)),
+                            Some(process) => process_alerts.is_alerting(process.pid),
+                            None => false,
+                        };
+                        // Highlight what the search matched in the name or command, which in tree
+                        // mode comes after the branches.
+                        let matched_ranges = match (search_query, process) {
+                            (Some(search_query), Some(process)) if !*disabled => {
+                                let matched_text = if is_using_command {
+                                    &process.command
+                                } else {
+                                    &process.name
+                                };
+                                name_column
+                                    .and_then(|name_column| data.get(name_column))
+                                    .filter(|(entry, _alternative)| entry.ends_with(matched_text))
+                                    .map(|(entry, _alternative)| {
+                                        let offset = entry.len() - matched_text.len();
+                                        search_query
+                                            .matched_ranges(matched_text)
+                                            .into_iter()
+                                            .map(|range| range.start + offset..range.end + offset)
+                                            .collect::<Vec<_>>()
+                                    })
+                                    .unwrap_or_default()
+                            }
+                            _ => Vec::new(),
+                        };
+
+                        let truncated_data = data.iter().zip(&hard_widths).enumerate().map(
+                            |(itx, ((entry, alternative), width))| {
+                                // The text shown, and how much of the start of it matches the entry.
+                                let (shown_text, matching_len): (Cow<'_, str>, usize) =
+                                    if let (Some(desired_col_width), Some(calculated_col_width)) =
+                                        (dcw.get(itx), ccw.get(itx))
                                     {
-                                        let calculated_col_width: usize =
-                                            (*calculated_col_width).into();
-
-                                        let graphemes =
-                                            UnicodeSegmentation::graphemes(entry.as_str(), true)
-                                                .collect::<Vec<&str>>();
-
-                                        if let Some(alternative) = alternative {
-                                            Text::raw(alternative)
-                                        } else if graphemes.len() > calculated_col_width
-                                            && calculated_col_width > 1
+                                        if width.is_none()
+                                            && *desired_col_width > *calculated_col_width
+                                            && *calculated_col_width > 0
                                         {
-                                            // Truncate with ellipsis
-                                            let first_n =
-                                                graphemes[..(calculated_col_width - 1)].concat();
-                                            Text::raw(format!("{}…", first_n))
+                                            let calculated_col_width: usize =
+                                                (*calculated_col_width).into();
+
+                                            let graphemes = UnicodeSegmentation::graphemes(
+                                                entry.as_str(),
+                                                true,
+                                            )
+                                            .collect::<Vec<&str>>();
+
+                                            if let Some(alternative) = alternative {
+                                                (alternative.into(), 0)
+                                            } else if graphemes.len() > calculated_col_width
+                                                && calculated_col_width > 1
+                                            {
+                                                // Truncate with ellipsis
+                                                let first_n = graphemes
+                                                    [..(calculated_col_width - 1)]
+                                                    .concat();
+                                                let matching_len = first_n.len();
+                                                (format!("{}…", first_n).into(), matching_len)
+                                            } else {
+                                                (entry.into(), entry.len())
+                                            }
                                         } else {
-                                            Text::raw(entry)
+                                            (entry.into(), entry.len())
                                         }
                                     } else {
-                                        Text::raw(entry)
-                                    }
+                                        (entry.into(), entry.len())
+                                    };
+
+                                if Some(itx) == name_column && !matched_ranges.is_empty() {
+                                    highlight_matches(
+                                        &shown_text,
+                                        &matched_ranges,
+                                        matching_len,
+                                        self.colours.search_match_style,
+                                    )
                                 } else {
-                                    Text::raw(entry)
+                                    Text::raw(shown_text)
                                 }
-                            } else {
-                                Text::raw(entry)
-                            }
-                        },
-                    );
+                            },
+                        );
 
-                    if *disabled {
-                        Row::new(truncated_data).style(self.colours.disabled_text_style)
-                    } else {
-                        Row::new(truncated_data)
-                    }
-                });
+                        if *disabled {
+                            Row::new(truncated_data).style(self.colours.disabled_text_style)
+                        } else if is_tagged {
+                            Row::new(truncated_data).style(self.colours.tagged_process_style)
+                        } else if is_alerted {
+                            Row::new(truncated_data).style(self.colours.alerted_process_style)
+                        } else if process_char == Some('Z') {
+                            Row::new(truncated_data).style(self.colours.zombie_process_style)
+                        } else if process_char == Some('D') {
+                            Row::new(truncated_data)
+                                .style(self.colours.uninterruptible_process_style)
+                        } else if process_char == Some('T') {
+                            Row::new(truncated_data).style(self.colours.stopped_process_style)
+                        } else {
+                            Row::new(truncated_data)
+                        }
+                    });
 
                 f.render_stateful_widget(
                     Table::new(process_rows)
@@ -676,24 +766,47 @@ impl ProcessTableWidget for Painter {
                 self.colours.text_style
             };
 
-            // FIXME: [MOUSE] Mouse support for these in search
+            let fuzzy_style = if proc_widget_state
+                .process_search_state
+                .is_searching_with_fuzzy
+            {
+                self.colours.currently_selected_text_style
+            } else {
+                self.colours.text_style
+            };
+
             // FIXME: [MOVEMENT] Movement support for these in search
-            let option_text = Spans::from(vec![
-                Span::styled(
+            const OPTION_GAP: &str = "  ";
+            let options = [
+                (
                     format!("Case({})", if self.is_mac_os { "F1" } else { "Alt+C" }),
                     case_style,
                 ),
-                Span::raw("  "),
-                Span::styled(
+                (
                     format!("Whole({})", if self.is_mac_os { "F2" } else { "Alt+W" }),
                     whole_word_style,
                 ),
-                Span::raw("  "),
-                Span::styled(
+                (
                     format!("Regex({})", if self.is_mac_os { "F3" } else { "Alt+R" }),
                     regex_style,
                 ),
-            ]);
+                (
+                    format!("Fuzzy({})", if self.is_mac_os { "F4" } else { "Alt+F" }),
+                    fuzzy_style,
+                ),
+            ];
+            let option_lens = options
+                .iter()
+                .map(|(option, _style)| option.chars().count() as u16)
+                .collect::<Vec<_>>();
+            let mut option_spans = Vec::new();
+            for (option, style) in options {
+                if !option_spans.is_empty() {
+                    option_spans.push(Span::raw(OPTION_GAP));
+                }
+                option_spans.push(Span::styled(option, style));
+            }
+            let option_text = Spans::from(option_spans);
 
             search_text.push(Spans::from(Span::styled(
                 if let Some(err) = &proc_widget_state
@@ -760,6 +873,19 @@ impl ProcessTableWidget for Painter {
                 margined_draw_loc,
             );
 
+            // The options are on the third line, after the query and the error message.
+            let border_offset = u16::from(draw_border || is_on_widget);
+            let options_y = margined_draw_loc.y + u16::from(draw_border) + 2;
+            let mut option_x = margined_draw_loc.x + border_offset;
+            proc_widget_state.process_search_state.option_locs = option_lens
+                .iter()
+                .map(|option_len| {
+                    let option_loc = (option_x, option_x + option_len, options_y);
+                    option_x += option_len + OPTION_GAP.len() as u16;
+                    option_loc
+                })
+                .collect();
+
             if app_state.should_get_widget_bounds() {
                 // Update draw loc in widget map
                 if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
@@ -897,3 +1023,85 @@ impl ProcessTableWidget for Painter {
         }
     }
 }
+
+/// Styles the parts of `text` within `matched_ranges` with `match_style`, for the first
+/// `matching_len` bytes of it, which are the same as the text the ranges were found in.
+fn highlight_matches(
+    text: &str, matched_ranges: &[Range<usize>], matching_len: usize, match_style: Style,
+) -> Text<'static> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in matched_ranges {
+        let start = range.start.min(matching_len);
+        let end = range.end.min(matching_len);
+        if start >= end {
+            continue;
+        }
+        if last < start {
+            spans.push(Span::raw(text[last..start].to_string()));
+        }
+        spans.push(Span::styled(text[start..end].to_string(), match_style));
+        last = end;
+    }
+    if last < text.len() {
+        spans.push(Span::raw(text[last..].to_string()));
+    }
+
+    Text::from(Spans::from(spans))
+}
+
+/// Returns the fixed width of a column, or `None` if it is sized to fit its contents.
+fn get_hard_width(column: &ProcessSorting) -> Option<u16> {
+    match column {
+        ProcessSorting::Count | ProcessSorting::Pid | ProcessSorting::TotalRead => Some(7),
+        ProcessSorting::CpuPercent
+        | ProcessSorting::Mem
+        | ProcessSorting::MemPercent
+        | ProcessSorting::ReadPerSecond
+        | ProcessSorting::WritePerSecond
+        | ProcessSorting::TotalWrite
+        | ProcessSorting::Pss
+        | ProcessSorting::Uss
+        | ProcessSorting::Swap
+        | ProcessSorting::VoluntarySwitches
+        | ProcessSorting::InvoluntarySwitches
+        | ProcessSorting::MajorFaults
+        | ProcessSorting::MinorFaults
+        | ProcessSorting::StartTime
+        | ProcessSorting::GpuPercent => Some(8),
+        ProcessSorting::CpuTime => Some(9),
+        ProcessSorting::CpuTrend => Some(10),
+        ProcessSorting::Capabilities => Some(11),
+        ProcessSorting::IoPriority => Some(6),
+        ProcessSorting::OpenFds => Some(5),
+        ProcessSorting::ProcessName
+        | ProcessSorting::Command
+        | ProcessSorting::User
+        | ProcessSorting::Group
+        | ProcessSorting::State
+        | ProcessSorting::SecurityLabel => None,
+    }
+}
+
+/// Returns the largest share of the table width that a column sized to fit may take, if limited.
+fn get_soft_width_max(
+    column: &ProcessSorting, is_grouped: bool, is_using_command: bool, is_tree: bool,
+) -> Option<f64> {
+    match column {
+        ProcessSorting::ProcessName | ProcessSorting::Command => {
+            // Note grouped trees are not a thing.
+            if is_using_command {
+                Some(0.7)
+            } else if is_tree && !is_grouped {
+                Some(0.5)
+            } else {
+                Some(0.3)
+            }
+        }
+        ProcessSorting::User | ProcessSorting::Group => Some(0.05),
+        ProcessSorting::State => Some(0.2),
+        // Labels can be quite long, so don't let them crowd out the name.
+        ProcessSorting::SecurityLabel => Some(0.2),
+        _ => None,
+    }
+}
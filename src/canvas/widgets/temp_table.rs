@@ -18,7 +18,10 @@ use crate::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-const TEMP_HEADERS: [&str; 2] = ["Sensor", "Temp"];
+const TEMP_HEADERS: [&str; 3] = ["Sensor", "Temp", "Trend"];
+
+/// The index of the column with each sensor's sparkline.
+const TREND_COLUMN: usize = 2;
 
 static TEMP_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
     TEMP_HEADERS
@@ -66,14 +69,16 @@ impl TempTableWidget for Painter {
             let sliced_vec = &app_state.canvas_data.temp_sensor_data[start_position..];
 
             // Calculate widths
-            let hard_widths = [None, None];
+            let hard_widths = [None, None, None];
             if recalculate_column_widths {
                 temp_widget_state.table_width_state.desired_column_widths = {
                     let mut column_widths = TEMP_HEADERS_LENS.clone();
                     for row in sliced_vec {
                         for (col, entry) in row.iter().enumerate() {
-                            if entry.len() as u16 > column_widths[col] {
-                                column_widths[col] = entry.len() as u16;
+                            let entry_len =
+                                UnicodeSegmentation::graphemes(entry.as_str(), true).count() as u16;
+                            if entry_len > column_widths[col] {
+                                column_widths[col] = entry_len;
                             }
                         }
                     }
@@ -87,7 +92,7 @@ impl TempTableWidget for Painter {
                         .iter()
                         .map(|width| Some(*width))
                         .collect::<Vec<_>>()),
-                    &[Some(0.80), Some(-1.0)],
+                    &[Some(0.80), Some(-1.0), Some(0.3)],
                     &temp_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -118,7 +123,15 @@ impl TempTableWidget for Painter {
                                             UnicodeSegmentation::graphemes(entry.as_str(), true)
                                                 .collect::<Vec<&str>>();
 
-                                        if graphemes.len() > calculated_col_width
+                                        if itx == TREND_COLUMN {
+                                            // Keep the most recent readings
+                                            Text::raw(
+                                                graphemes[graphemes
+                                                    .len()
+                                                    .saturating_sub(calculated_col_width)..]
+                                                    .concat(),
+                                            )
+                                        } else if graphemes.len() > calculated_col_width
                                             && calculated_col_width > 1
                                         {
                                             // Truncate with ellipsis
@@ -171,6 +184,50 @@ impl TempTableWidget for Painter {
                 " Temperatures ".to_string()
             };
 
+            let is_expanded = app_state.is_expanded;
+
+            // Firmware throttling flags, if the board reports any. Active states take priority
+            // over ones that only occurred earlier.
+            let throttle_title = app_state
+                .canvas_data
+                .temp_throttle_status
+                .and_then(|status| {
+                    let active = status.active_indicators();
+                    if !active.is_empty() {
+                        Some((
+                            format!("─ {} ", active.join(", ")),
+                            self.colours.low_battery_colour,
+                        ))
+                    } else {
+                        let past = status.past_indicators();
+                        if !past.is_empty() {
+                            Some((
+                                format!("─ {} earlier ", past.join(", ")),
+                                self.colours.medium_battery_colour,
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+                })
+                .filter(|(throttle_text, _)| {
+                    let escape_len = if is_expanded {
+                        "── Esc to go back ".len()
+                    } else {
+                        0
+                    };
+                    UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                        + UnicodeSegmentation::graphemes(throttle_text.as_str(), true).count()
+                        + escape_len
+                        <= draw_loc.width.into()
+                });
+            let throttle_len = throttle_title
+                .as_ref()
+                .map(|(throttle_text, _)| {
+                    UnicodeSegmentation::graphemes(throttle_text.as_str(), true).count()
+                })
+                .unwrap_or(0);
+
             let title = if app_state.is_expanded {
                 const ESCAPE_ENDING: &str = "── Esc to go back ";
 
@@ -187,27 +244,36 @@ impl TempTableWidget for Painter {
                     }
                 };
 
-                Spans::from(vec![
-                    Span::styled(chosen_title_base, self.colours.widget_title_style),
-                    Span::styled(
-                        format!(
-                            "─{}─ Esc to go back ",
-                            "─".repeat(
-                                usize::from(draw_loc.width).saturating_sub(
-                                    UnicodeSegmentation::graphemes(
-                                        expanded_title_base.as_str(),
-                                        true
-                                    )
+                let mut spans = vec![Span::styled(
+                    chosen_title_base,
+                    self.colours.widget_title_style,
+                )];
+                if let Some((throttle_text, throttle_style)) = throttle_title {
+                    spans.push(Span::styled(throttle_text, throttle_style));
+                }
+                spans.push(Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(
+                            usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(expanded_title_base.as_str(), true)
                                     .count()
-                                        + 2
-                                )
+                                    + throttle_len
+                                    + 2
                             )
-                        ),
-                        border_style,
+                        )
                     ),
-                ])
+                    border_style,
+                ));
+
+                Spans::from(spans)
             } else {
-                Spans::from(Span::styled(title_base, self.colours.widget_title_style))
+                let mut spans = vec![Span::styled(title_base, self.colours.widget_title_style)];
+                if let Some((throttle_text, throttle_style)) = throttle_title {
+                    spans.push(Span::styled(throttle_text, throttle_style));
+                }
+
+                Spans::from(spans)
             };
 
             let temp_block = if draw_border {
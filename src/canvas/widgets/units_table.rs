@@ -0,0 +1,57 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_unit_rows` generates entries.
+const UNITS_COLUMNS: [TextTableColumn; 5] = [
+    TextTableColumn::soft("Unit", 0.5),
+    TextTableColumn::hard("Active", 8),
+    TextTableColumn::hard("Sub", 10),
+    TextTableColumn::hard("CPU%", 7),
+    TextTableColumn::hard("Mem", 9),
+];
+
+pub trait UnitsTableWidget {
+    fn draw_units_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl UnitsTableWidget for Painter {
+    fn draw_units_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Units",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let units_widget_state = app_state.units_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &UNITS_COLUMNS,
+                    &app_state.canvas_data.unit_data,
+                    &mut units_widget_state.scroll_state,
+                    &mut units_widget_state.table_width_state,
+                ))
+            },
+            |(units_row, is_failed)| {
+                TextTableRow::new(units_row)
+                    .with_style(is_failed.then_some(self.colours.failed_unit_style))
+            },
+        );
+    }
+}
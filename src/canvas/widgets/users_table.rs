@@ -0,0 +1,54 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_session_rows` generates entries.
+const USERS_COLUMNS: [TextTableColumn; 5] = [
+    TextTableColumn::soft("User", 0.3),
+    TextTableColumn::soft("TTY", 0.2),
+    TextTableColumn::soft("Host", 0.5),
+    TextTableColumn::hard("Login", 7),
+    TextTableColumn::hard("Idle", 6),
+];
+
+pub trait UsersTableWidget {
+    fn draw_users_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl UsersTableWidget for Painter {
+    fn draw_users_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "Users",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let users_widget_state = app_state.users_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &USERS_COLUMNS,
+                    &app_state.canvas_data.session_data,
+                    &mut users_widget_state.scroll_state,
+                    &mut users_widget_state.table_width_state,
+                ))
+            },
+            |users_row| TextTableRow::new(users_row),
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        components::{TextTable, TextTableColumn, TextTableData, TextTableFrame, TextTableRow},
+        Painter,
+    },
+};
+
+/// In the order that `convert_vm_rows` generates entries.
+const VM_COLUMNS: [TextTableColumn; 8] = [
+    TextTableColumn::soft("Name", 1.0),
+    TextTableColumn::hard("vCPUs", 5),
+    TextTableColumn::hard("CPU%", 7),
+    TextTableColumn::hard("Mem", 9),
+    TextTableColumn::hard("R/s", 8),
+    TextTableColumn::hard("W/s", 8),
+    TextTableColumn::hard("RX/s", 8),
+    TextTableColumn::hard("TX/s", 8),
+];
+
+pub trait VmTableWidget {
+    fn draw_vm_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl VmTableWidget for Painter {
+    fn draw_vm_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_text_table_widget(
+            f,
+            app_state,
+            draw_loc,
+            TextTableFrame {
+                widget_id,
+                name: "VMs",
+                title_extra: String::new(),
+                draw_border,
+            },
+            |app_state| {
+                let vm_widget_state = app_state.vm_state.widget_states.get_mut(&widget_id)?;
+                Some(TextTableData::new(
+                    &VM_COLUMNS,
+                    &app_state.canvas_data.vm_data,
+                    &mut vm_widget_state.scroll_state,
+                    &mut vm_widget_state.table_width_state,
+                ))
+            },
+            |vm_row| TextTableRow::new(vm_row),
+        );
+    }
+}
@@ -46,6 +46,26 @@ Supported widget names:
 +--------------------------+
 |       batt, battery      |
 +--------------------------+
+|     conn, connections    |
++--------------------------+
+|           power          |
++--------------------------+
+|         users, who       |
++--------------------------+
+|           numa           |
++--------------------------+
+|      units, systemd      |
++--------------------------+
+|     dmesg, kernel_log    |
++--------------------------+
+|     vm, vms, libvirt     |
++--------------------------+
+|  pods, k8s, kubernetes   |
++--------------------------+
+|   activity, interrupts   |
++--------------------------+
+|     alerts, watchdog     |
++--------------------------+
 "
 } else {
     "\
@@ -77,6 +97,26 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|     conn, connections    |
++--------------------------+
+|           power          |
++--------------------------+
+|         users, who       |
++--------------------------+
+|           numa           |
++--------------------------+
+|      units, systemd      |
++--------------------------+
+|     dmesg, kernel_log    |
++--------------------------+
+|     vm, vms, libvirt     |
++--------------------------+
+|  pods, k8s, kubernetes   |
++--------------------------+
+|   activity, interrupts   |
++--------------------------+
+|     alerts, watchdog     |
++--------------------------+
 "
 };
 
@@ -142,12 +182,40 @@ pub fn build_app() -> Command<'static> {
         .help("Disables mouse clicks.")
         .long_help("Disables mouse clicks from interacting with the program.");
 
+    let disk_inodes = Arg::new("disk_inodes")
+        .long("disk_inodes")
+        .help("Shows inode usage in the disk widget.")
+        .long_help(
+            "Shows the percentage of used inodes in the disk widget. Filesystems that allocate inodes dynamically will show N/A. Not supported on Windows.",
+        );
+
+    let disk_latency = Arg::new("disk_latency")
+        .long("disk_latency")
+        .help("Shows I/O wait time and utilization in the disk widget.")
+        .long_help(
+            "Shows the average time each I/O request took, including time spent queued, and the percentage of time the device was busy in the disk widget. Only supported on Linux.",
+        );
+
+    let disk_smart = Arg::new("disk_smart")
+        .long("disk_smart")
+        .help("Shows SMART health and temperature in the disk widget.")
+        .long_help(
+            "Shows SMART health, drive temperature, reallocated sectors, and wear level in the disk widget. Health, reallocated sectors, and wear level are read using smartctl, which usually requires root. Only supported on Linux.",
+        );
+
     let dot_marker = Arg::new("dot_marker")
         .short('m')
         .long("dot_marker")
         .help("Uses a dot marker for graphs.")
         .long_help("Uses a dot marker for graphs as opposed to the default braille marker.");
 
+    let fuzzy = Arg::new("fuzzy")
+        .long("fuzzy")
+        .help("Enables fuzzy matching by default.")
+        .long_help(
+            "When searching for a process, enables fuzzy matching by default, which ranks the matching processes by how well they match.",
+        );
+
     let group = Arg::new("group") // FIXME: Rename this to something like "group_process", would be "breaking" though.
         .short('g')
         .long("group")
@@ -170,17 +238,279 @@ pub fn build_app() -> Command<'static> {
         .help("Hides the time scale.")
         .long_help("Completely hides the time scale from being shown.");
 
+    let ipmi = Arg::new("ipmi")
+        .long("ipmi")
+        .help("Shows IPMI chassis sensors in the temperature widget.")
+        .long_help(
+            "Shows server chassis temperature sensors read over IPMI in the temperature widget. Sensors are read using ipmi-sensors from FreeIPMI, which usually requires root. Only supported on Linux and FreeBSD.",
+        );
+
     let process_command = Arg::new("process_command")
         .long("process_command")
         .help("Show processes as their commands by default.")
         .long_help("Show processes as their commands by default in the process widget.");
 
+    let process_cpu_trend = Arg::new("process_cpu_trend")
+        .long("process_cpu_trend")
+        .help("Shows a sparkline of the recent CPU usage in the process widget.")
+        .long_help(
+            "Shows a CPU Trend column with a sparkline of the CPU usage of each process over its last 10 refreshes in the process widget.",
+        );
+
+    let process_fds = Arg::new("process_fds")
+        .long("process_fds")
+        .help("Shows the number of open file descriptors in the process widget.")
+        .long_help(
+            "Shows an FDs column with the number of open file descriptors of each process in the process widget. Only supported on Linux.",
+        );
+
+    let process_memory_breakdown = Arg::new("process_memory_breakdown")
+        .long("process_memory_breakdown")
+        .help("Shows the PSS, USS, and swap usage of processes in the process widget.")
+        .long_help(
+            "Shows PSS, USS, and Swap columns with the proportional set size, unique set size, and swap usage of each process in the process widget. Only supported on Linux.",
+        );
+
+    let process_latency = Arg::new("process_latency")
+        .long("process_latency")
+        .help("Shows context switch and page fault rates in the process widget.")
+        .long_help(
+            "Shows VCtx/s, ICtx/s, MajF/s, and MinF/s columns with the voluntary and involuntary context switches and the major and minor page faults per second of each process in the process widget. Only supported on Linux.",
+        );
+
+    let process_security = Arg::new("process_security")
+        .long("process_security")
+        .help("Shows the security label and capabilities of processes in the process widget.")
+        .long_help(
+            "Shows Label and CapEff columns with the SELinux or AppArmor label and the effective capability set (as a hex bitmask) of each process in the process widget. Only supported on Linux.",
+        );
+
+    let process_io_priority = Arg::new("process_io_priority")
+        .long("process_io_priority")
+        .help("Shows the I/O priority of processes in the process widget.")
+        .long_help(
+            "Shows an IOprio column with the I/O scheduling class and priority of each process in the process widget, in the same format as iotop (such as be/4). Only supported on Linux.",
+        );
+
+    let process_times = Arg::new("process_times")
+        .long("process_times")
+        .help("Shows the start time and CPU time of processes in the process widget.")
+        .long_help(
+            "Shows Started and Time+ columns with the start time and total CPU time of each process in the process widget. The CPU time is only supported on Linux.",
+        );
+
+    let hide_kernel_threads = Arg::new("hide_kernel_threads")
+        .long("hide_kernel_threads")
+        .help("Hides kernel threads in the process widget by default.")
+        .long_help(
+            "Hides kernel threads in the process widget by default. They can still be shown with 'x'. Only supported on Linux.",
+        );
+
+    let persist_search_history = Arg::new("persist_search_history")
+        .long("persist_search_history")
+        .help("Keeps the process search history between sessions.")
+        .long_help(
+            "Keeps the history of process searches between sessions, in a search_history file next to the config file.",
+        );
+
+    let global_filter = Arg::new("global_filter")
+        .long("global_filter")
+        .takes_value(true)
+        .value_name("QUERY")
+        .help("Filters every process widget with a query.")
+        .long_help(
+            "Filters every process widget with a query, written like a process search, such as \"user=alice\" or \"container=3f2a9c1e5b7d\". The CPU and memory widgets also show how much the matching processes use. It can be changed with '\\'.",
+        );
+
+    let cpu_frequency_graph = Arg::new("cpu_frequency_graph")
+        .long("cpu_frequency_graph")
+        .help("Graphs the average CPU frequency in the CPU widget.")
+        .long_help(
+            "Graphs the average CPU frequency in the CPU widget, as a percentage of the maximum frequency. This can be useful for spotting thermal throttling. Only supported on Linux.",
+        );
+
+    let nut_server = Arg::new("nut_server")
+        .long("nut_server")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .help("Queries UPSes from a NUT daemon for the battery widget.")
+        .long_help(
+            "Queries UPSes from the Network UPS Tools daemon at the given host, with an optional port (defaults to 3493), and shows their charge, load, and runtime in the battery widget.",
+        );
+
+    let kubelet_url = Arg::new("kubelet_url")
+        .long("kubelet_url")
+        .takes_value(true)
+        .value_name("URL")
+        .help("Sets the kubelet that the pods widget queries.")
+        .long_help(
+            "Sets the URL of the kubelet that the pods widget queries for pod metrics. Defaults to https://127.0.0.1:10250.",
+        );
+
+    let kubelet_insecure = Arg::new("kubelet_insecure")
+        .long("kubelet_insecure")
+        .help("Skips verifying the certificate of the kubelet.")
+        .long_help(
+            "Skips verifying the certificate of the kubelet that the pods widget queries, which is usually self-signed. Otherwise, it is verified with the cluster's CA if bottom is running in a pod, or with the system's CAs if not.",
+        );
+
+    let dump = Arg::new("dump")
+        .long("dump")
+        .help("Prints the harvested metrics as JSON once, without starting the TUI.")
+        .long_help(
+            "Harvests the metrics once and prints them as JSON to stdout, without starting the TUI, which is useful for scripts and cron jobs. This takes about a second, as rates like the CPU usage are measured over a short interval.",
+        );
+
+    let headless = Arg::new("headless")
+        .long("headless")
+        .conflicts_with("dump")
+        .help("Runs only the exporters and loggers, without the TUI.")
+        .long_help(
+            "Keeps harvesting data and passing it on to the Prometheus endpoint, the InfluxDB endpoint, the metrics log, and the recording, without starting the TUI, which is useful on servers. At least one of --prometheus_address, --influxdb_url, --metrics_log, and --record has to be set. Runs until interrupted.",
+        );
+
+    let influxdb_url = Arg::new("influxdb_url")
+        .long("influxdb_url")
+        .takes_value(true)
+        .value_name("URL")
+        .help("Pushes the harvested metrics to InfluxDB at this URL.")
+        .long_help(
+            "Pushes the harvested metrics in the InfluxDB line protocol to the write endpoint at URL every 10 seconds, for example to http://localhost:8086/api/v2/write?org=me&bucket=bottom. The token, the interval, which metrics are pushed, and the tags added to them can be set under [influxdb] in the config file. Needs curl.",
+        );
+
+    let metrics_log = Arg::new("metrics_log")
+        .long("metrics_log")
+        .takes_value(true)
+        .value_name("PATH")
+        .help("Appends the harvested metrics to a CSV or JSON lines file on every refresh.")
+        .long_help(
+            "Appends the harvested metrics to the file at PATH on every refresh, as JSON lines if it ends in .jsonl or .json and as CSV otherwise. Which metrics are logged and when the file is rotated can be set under [metrics_log] in the config file.",
+        );
+
+    let prometheus_address = Arg::new("prometheus_address")
+        .long("prometheus_address")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .help("Serves the harvested metrics to Prometheus at this address.")
+        .long_help(
+            "Serves the harvested metrics in the Prometheus text format at http://ADDRESS/metrics while bottom runs, for example at 127.0.0.1:9184. Only the metrics of the widgets in the layout are harvested.",
+        );
+
+    let serve = Arg::new("serve")
+        .long("serve")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .conflicts_with_all(&["connect", "dump", "headless"])
+        .help("Serves the harvested data to remote clients at this address, without the TUI.")
+        .long_help(
+            "Keeps harvesting data and sending it to the clients that connect to ADDRESS with --connect, without starting the TUI. The data of the widgets in the layout is harvested. Clients have to send the token set with --remote_token, if any, and have to connect over TLS if --serve_tls_cert and --serve_tls_key are set. Runs until interrupted.",
+        );
+
+    let serve_tls_cert = Arg::new("serve_tls_cert")
+        .long("serve_tls_cert")
+        .takes_value(true)
+        .value_name("PATH")
+        .requires("serve_tls_key")
+        .help("Serves remote clients over TLS with this certificate.")
+        .long_help(
+            "Serves the clients of --serve over TLS with the PEM certificate at PATH, along with the key set with --serve_tls_key. Clients have to connect with a tls:// address. Requires openssl, and is not supported on Windows.",
+        );
+
+    let serve_tls_key = Arg::new("serve_tls_key")
+        .long("serve_tls_key")
+        .takes_value(true)
+        .value_name("PATH")
+        .requires("serve_tls_cert")
+        .help("Sets the key of the certificate set with --serve_tls_cert.")
+        .long_help(
+            "Sets the unencrypted PEM key at PATH of the certificate set with --serve_tls_cert, which the clients of --serve are served over TLS with.",
+        );
+
+    let connect = Arg::new("connect")
+        .long("connect")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .conflicts_with_all(&["dump", "headless"])
+        .help("Shows the data of a remote server started with --serve.")
+        .long_help(
+            "Shows the data of the server started with --serve at ADDRESS rather than the data of this host, reconnecting if the connection is lost. Prefix the address with tls:// to connect over TLS, which requires openssl. Actions on processes are disabled.",
+        );
+
+    let serve_stdio = Arg::new("serve_stdio")
+        .long("serve_stdio")
+        .conflicts_with_all(&["connect", "dump", "headless", "serve"])
+        .help("Serves the harvested data over stdin and stdout, for --ssh.")
+        .long_help(
+            "Keeps harvesting data and sending it over stdout to the client on the other end of stdin, without starting the TUI, until the client goes away. This is what --ssh runs on the remote host.",
+        );
+
+    let ssh = Arg::new("ssh")
+        .long("ssh")
+        .takes_value(true)
+        .value_name("DESTINATION")
+        .conflicts_with_all(&["connect", "dump", "headless", "serve"])
+        .help("Shows the data of a remote host over SSH.")
+        .long_help(
+            "Shows the data of the host at DESTINATION, like user@host, by running bottom there through ssh, rather than the data of this host. The remote host needs bottom installed, and ssh has to be able to log in without a password, such as with a key or an agent. Actions on processes are disabled.",
+        );
+
+    let ssh_command = Arg::new("ssh_command")
+        .long("ssh_command")
+        .takes_value(true)
+        .value_name("COMMAND")
+        .help("Sets the command that --ssh runs on the remote host.")
+        .long_help(
+            "Sets the command that --ssh runs on the remote host, which has to serve its data with --serve_stdio. Defaults to \"btm --serve_stdio\".",
+        );
+
+    let remote_token = Arg::new("remote_token")
+        .long("remote_token")
+        .takes_value(true)
+        .value_name("TOKEN")
+        .help("Sets the token that remote clients have to send to the server.")
+        .long_help(
+            "Sets the token that clients have to send to a server started with --serve, and that --connect sends. Setting it in the config file keeps it out of the process list.",
+        );
+
+    let record = Arg::new("record")
+        .long("record")
+        .takes_value(true)
+        .value_name("PATH")
+        .conflicts_with("dump")
+        .help("Records every harvest to a file, for --replay.")
+        .long_help(
+            "Appends every harvest to the recording at PATH, creating it if needed, which --replay can play back later. Works with the TUI, --headless, --serve, and --connect.",
+        );
+
+    let replay = Arg::new("replay")
+        .long("replay")
+        .takes_value(true)
+        .value_name("PATH")
+        .conflicts_with_all(&[
+            "connect",
+            "dump",
+            "headless",
+            "record",
+            "serve",
+            "serve_stdio",
+            "ssh",
+        ])
+        .help("Plays back a recording made with --record.")
+        .long_help(
+            "Plays back the recording at PATH, made with --record, in place of the data of this host. Space pauses and resumes, Z steps a frame forward, ( and ) seek back and forward by 10 seconds, and 0 to 9 seek to that tenth of the recording. Actions on processes are disabled.",
+        );
+
     let left_legend = Arg::new("left_legend")
         .short('l')
         .long("left_legend")
         .help("Puts the CPU chart legend to the left side.")
         .long_help("Puts the CPU chart legend to the left side rather than the right side.");
 
+    let no_write = Arg::new("no_write")
+        .long("no_write")
+        .help("Disables writing to the config file.")
+        .long_help("Disables writing to the config file, such as when saving the columns of the process widget.");
+
     let regex = Arg::new("regex")
         .short('R')
         .long("regex")
@@ -192,6 +522,15 @@ pub fn build_app() -> Command<'static> {
         .help("Hides advanced process killing.")
         .long_help("Hides advanced options to stop a process on Unix-like systems. The only option shown is 15 (TERM).");
 
+    let privilege_helper = Arg::new("privilege_helper")
+        .long("privilege_helper")
+        .takes_value(true)
+        .value_name("COMMAND")
+        .help("Sets the command used to retry killing a process as root.")
+        .long_help(
+            "Sets the command, such as pkexec or \"sudo -n\", used to retry killing a process as root if it was not permitted. The command is run without a terminal, so it must be able to authenticate on its own. An empty string disables retrying. Defaults to pkexec. Only supported on Unix-like systems.",
+        );
+
     let show_table_scroll_position = Arg::new("show_table_scroll_position")
         .long("show_table_scroll_position")
         .help("Shows the scroll position tracker in table widgets.")
@@ -230,19 +569,11 @@ pub fn build_app() -> Command<'static> {
         .long("color")
         .takes_value(true)
         .value_name("COLOR SCHEME")
-        .possible_values(&[
-            "default",
-            "default-light",
-            "gruvbox",
-            "gruvbox-light",
-            "nord",
-            "nord-light",
-        ])
-        .hide_possible_values(true)
         .help("Use a color scheme, use --help for info.")
         .long_help(
             "\
-Use a pre-defined color scheme. Currently supported values are:
+Use a pre-defined color scheme, or one of the themes in the config file by
+name. Currently supported pre-defined values are:
 
 +------------------------------------------------------------+
 | default                                                    |
@@ -257,6 +588,8 @@ Use a pre-defined color scheme. Currently supported values are:
 +------------------------------------------------------------+
 | nord-light (nord but for use with light backgrounds)       |
 +------------------------------------------------------------+
+| dracula (a dark theme with vivid, Dracula colors)          |
++------------------------------------------------------------+
 
 Defaults to \"default\".
 ",
@@ -341,6 +674,27 @@ use CPU (3) as the default instead.
         .help("Displays the network widget with a log scale.")
         .long_help("Displays the network widget with a log scale. Defaults to a non-log scale.");
 
+    let network_highlight_errors = Arg::new("network_highlight_errors")
+        .long("network_highlight_errors")
+        .help("Highlights network errors and drops when they increase.")
+        .long_help(
+            "Highlights the network error, drop, and collision counters in the network legend whenever any of them increase.",
+        );
+
+    let network_wireless = Arg::new("network_wireless")
+        .long("network_wireless")
+        .help("Shows Wi-Fi link info in the network legend.")
+        .long_help(
+            "Shows the SSID, signal level, bitrate, and frequency of wireless interfaces in the network legend. The SSID, bitrate, and frequency are read using iw. Only supported on Linux.",
+        );
+
+    let network_connections = Arg::new("network_connections")
+        .long("network_connections")
+        .help("Shows TCP connection counts in the network legend.")
+        .long_help(
+            "Shows the number of established, TIME-WAIT, and listening TCP sockets in the network legend, counted over both IPv4 and IPv6. Only supported on Linux.",
+        );
+
     let network_use_binary_prefix = Arg::new("network_use_binary_prefix")
         .long("network_use_binary_prefix")
         .help("Displays the network widget with binary prefixes.")
@@ -366,27 +720,66 @@ use CPU (3) as the default instead.
         .arg(basic)
         .arg(case_sensitive)
         .arg(process_command)
+        .arg(process_cpu_trend)
+        .arg(process_fds)
+        .arg(process_memory_breakdown)
+        .arg(process_latency)
+        .arg(process_security)
+        .arg(process_io_priority)
+        .arg(process_times)
+        .arg(hide_kernel_threads)
+        .arg(persist_search_history)
         .arg(config_location)
         .arg(color)
+        .arg(connect)
+        .arg(cpu_frequency_graph)
         .arg(mem_as_value)
         .arg(default_time_value)
         .arg(default_widget_count)
         .arg(default_widget_type)
         .arg(disable_click)
+        .arg(disk_inodes)
+        .arg(disk_latency)
+        .arg(disk_smart)
         .arg(dot_marker)
+        .arg(dump)
+        .arg(fuzzy)
+        .arg(global_filter)
         .arg(group)
+        .arg(headless)
         .arg(hide_avg_cpu)
         .arg(hide_table_gap)
         .arg(hide_time)
+        .arg(influxdb_url)
+        .arg(ipmi)
+        .arg(kubelet_insecure)
+        .arg(kubelet_url)
         .arg(show_table_scroll_position)
         .arg(left_legend)
+        .arg(metrics_log)
+        .arg(no_write)
         .arg(disable_advanced_kill)
+        .arg(privilege_helper)
+        .arg(prometheus_address)
         .arg(rate)
+        .arg(record)
         .arg(regex)
+        .arg(remote_token)
+        .arg(replay)
+        .arg(serve)
+        .arg(serve_stdio)
+        .arg(serve_tls_cert)
+        .arg(serve_tls_key)
+        .arg(ssh)
+        .arg(ssh_command)
         .arg(time_delta)
         .arg(tree)
+        .arg(network_highlight_errors)
         .arg(network_use_bytes)
         .arg(network_use_log)
+        .arg(nut_server)
+        .arg(network_wireless)
+        .arg(network_connections)
         .arg(network_use_binary_prefix)
         .arg(current_usage)
         .arg(use_old_network_legend)
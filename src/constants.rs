@@ -4,6 +4,11 @@ use once_cell::sync::Lazy;
 // Default widget ID
 pub const DEFAULT_WIDGET_ID: u64 = 56709;
 
+// How many widget IDs each layout page gets, so that widgets on different pages never share one.
+pub const LAYOUT_PAGE_WIDGET_IDS: u64 = 1000;
+// The name of the layout page made from the [[row]] layout.
+pub const DEFAULT_LAYOUT_PAGE_NAME: &str = "Main";
+
 // How long to store data.
 pub const STALE_MAX_MILLISECONDS: u64 = 600 * 1000; // Keep 10 minutes of data.
 
@@ -12,10 +17,19 @@ pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
 pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
 pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
+pub const TOAST_TIMEOUT_MILLISECONDS: u64 = 4000; // 4 seconds to hide a toast
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+pub const DEFAULT_PRIVILEGE_HELPER: &str = "pkexec";
+/// How many rotated metrics log files are kept by default.
+pub const DEFAULT_METRICS_LOG_MAX_FILES: usize = 5;
+/// How often metrics are pushed to InfluxDB by default.
+pub const DEFAULT_INFLUXDB_INTERVAL_IN_SECONDS: u64 = 10;
+
+// Remote
+pub const DEFAULT_SSH_COMMAND: &str = "btm --serve_stdio";
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
 
 // Limits for when we should stop showing table gaps/labels (anything less means not shown)
@@ -84,6 +98,7 @@ pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColo
     ]),
     ram_color: Some("#8ec07c".to_string()),
     swap_color: Some("#fabd2f".to_string()),
+    cache_color: Some("#83a598".to_string()),
     rx_color: Some("#8ec07c".to_string()),
     tx_color: Some("#fabd2f".to_string()),
     rx_total_color: Some("#689d6a".to_string()),
@@ -96,9 +111,20 @@ pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColo
     selected_bg_color: Some("#ebdbb2".to_string()),
     widget_title_color: Some("#ebdbb2".to_string()),
     graph_color: Some("#ebdbb2".to_string()),
+    graph_gradient_colors: None,
     high_battery_color: Some("#98971a".to_string()),
     medium_battery_color: Some("#fabd2f".to_string()),
     low_battery_color: Some("#fb4934".to_string()),
+    zombie_process_color: Some("#fb4934".to_string()),
+    uninterruptible_process_color: Some("#fabd2f".to_string()),
+    stopped_process_color: Some("#83a598".to_string()),
+    tagged_process_color: Some("#d3869b".to_string()),
+    alerted_process_color: Some("#fe8019".to_string()),
+    critical_alert_color: Some("#fb4934".to_string()),
+    search_match_color: Some("#fabd2f".to_string()),
+    failed_unit_color: Some("#fb4934".to_string()),
+    kernel_error_color: Some("#fb4934".to_string()),
+    kernel_warning_color: Some("#fabd2f".to_string()),
 });
 
 pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -129,6 +155,7 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
     ]),
     ram_color: Some("#427b58".to_string()),
     swap_color: Some("#cc241d".to_string()),
+    cache_color: Some("#076678".to_string()),
     rx_color: Some("#427b58".to_string()),
     tx_color: Some("#cc241d".to_string()),
     rx_total_color: Some("#689d6a".to_string()),
@@ -141,9 +168,20 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
     selected_bg_color: Some("#3c3836".to_string()),
     widget_title_color: Some("#3c3836".to_string()),
     graph_color: Some("#3c3836".to_string()),
+    graph_gradient_colors: None,
     high_battery_color: Some("#98971a".to_string()),
     medium_battery_color: Some("#d79921".to_string()),
     low_battery_color: Some("#cc241d".to_string()),
+    zombie_process_color: Some("#cc241d".to_string()),
+    uninterruptible_process_color: Some("#d79921".to_string()),
+    stopped_process_color: Some("#458588".to_string()),
+    tagged_process_color: Some("#b16286".to_string()),
+    alerted_process_color: Some("#d65d0e".to_string()),
+    critical_alert_color: Some("#cc241d".to_string()),
+    search_match_color: Some("#b57614".to_string()),
+    failed_unit_color: Some("#cc241d".to_string()),
+    kernel_error_color: Some("#cc241d".to_string()),
+    kernel_warning_color: Some("#d79921".to_string()),
 });
 
 pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -162,6 +200,7 @@ pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours
     ]),
     ram_color: Some("#88c0d0".to_string()),
     swap_color: Some("#d08770".to_string()),
+    cache_color: Some("#5e81ac".to_string()),
     rx_color: Some("#88c0d0".to_string()),
     tx_color: Some("#d08770".to_string()),
     rx_total_color: Some("#5e81ac".to_string()),
@@ -174,9 +213,20 @@ pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours
     selected_bg_color: Some("#88c0d0".to_string()),
     widget_title_color: Some("#e5e9f0".to_string()),
     graph_color: Some("#e5e9f0".to_string()),
+    graph_gradient_colors: None,
     high_battery_color: Some("#a3be8c".to_string()),
     medium_battery_color: Some("#ebcb8b".to_string()),
     low_battery_color: Some("#bf616a".to_string()),
+    zombie_process_color: Some("#bf616a".to_string()),
+    uninterruptible_process_color: Some("#ebcb8b".to_string()),
+    stopped_process_color: Some("#88c0d0".to_string()),
+    tagged_process_color: Some("#b48ead".to_string()),
+    alerted_process_color: Some("#d08770".to_string()),
+    critical_alert_color: Some("#bf616a".to_string()),
+    search_match_color: Some("#ebcb8b".to_string()),
+    failed_unit_color: Some("#bf616a".to_string()),
+    kernel_error_color: Some("#bf616a".to_string()),
+    kernel_warning_color: Some("#ebcb8b".to_string()),
 });
 
 pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -195,6 +245,7 @@ pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigC
     ]),
     ram_color: Some("#81a1c1".to_string()),
     swap_color: Some("#d08770".to_string()),
+    cache_color: Some("#88c0d0".to_string()),
     rx_color: Some("#81a1c1".to_string()),
     tx_color: Some("#d08770".to_string()),
     rx_total_color: Some("#5e81ac".to_string()),
@@ -207,13 +258,69 @@ pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigC
     selected_bg_color: Some("#5e81ac".to_string()),
     widget_title_color: Some("#2e3440".to_string()),
     graph_color: Some("#2e3440".to_string()),
+    graph_gradient_colors: None,
     high_battery_color: Some("#a3be8c".to_string()),
     medium_battery_color: Some("#ebcb8b".to_string()),
     low_battery_color: Some("#bf616a".to_string()),
+    zombie_process_color: Some("#bf616a".to_string()),
+    uninterruptible_process_color: Some("#ebcb8b".to_string()),
+    stopped_process_color: Some("#5e81ac".to_string()),
+    tagged_process_color: Some("#b48ead".to_string()),
+    alerted_process_color: Some("#d08770".to_string()),
+    critical_alert_color: Some("#bf616a".to_string()),
+    search_match_color: Some("#d08770".to_string()),
+    failed_unit_color: Some("#bf616a".to_string()),
+    kernel_error_color: Some("#bf616a".to_string()),
+    kernel_warning_color: Some("#ebcb8b".to_string()),
+});
+
+pub static DRACULA_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
+    table_header_color: Some("#bd93f9".to_string()),
+    all_cpu_color: Some("#50fa7b".to_string()),
+    avg_cpu_color: Some("#ff79c6".to_string()),
+    cpu_core_colors: Some(vec![
+        "#8be9fd".to_string(),
+        "#50fa7b".to_string(),
+        "#ffb86c".to_string(),
+        "#ff79c6".to_string(),
+        "#bd93f9".to_string(),
+        "#ff5555".to_string(),
+        "#f1fa8c".to_string(),
+        "#6272a4".to_string(),
+    ]),
+    ram_color: Some("#50fa7b".to_string()),
+    swap_color: Some("#ffb86c".to_string()),
+    cache_color: Some("#8be9fd".to_string()),
+    rx_color: Some("#8be9fd".to_string()),
+    tx_color: Some("#ff79c6".to_string()),
+    rx_total_color: Some("#6272a4".to_string()),
+    tx_total_color: Some("#bd93f9".to_string()),
+    border_color: Some("#6272a4".to_string()),
+    highlighted_border_color: Some("#bd93f9".to_string()),
+    disabled_text_color: Some("#44475a".to_string()),
+    text_color: Some("#f8f8f2".to_string()),
+    selected_text_color: Some("#282a36".to_string()),
+    selected_bg_color: Some("#bd93f9".to_string()),
+    widget_title_color: Some("#f8f8f2".to_string()),
+    graph_color: Some("#6272a4".to_string()),
+    graph_gradient_colors: None,
+    high_battery_color: Some("#50fa7b".to_string()),
+    medium_battery_color: Some("#f1fa8c".to_string()),
+    low_battery_color: Some("#ff5555".to_string()),
+    zombie_process_color: Some("#ff5555".to_string()),
+    uninterruptible_process_color: Some("#f1fa8c".to_string()),
+    stopped_process_color: Some("#8be9fd".to_string()),
+    tagged_process_color: Some("#ff79c6".to_string()),
+    alerted_process_color: Some("#ffb86c".to_string()),
+    critical_alert_color: Some("#ff5555".to_string()),
+    search_match_color: Some("#f1fa8c".to_string()),
+    failed_unit_color: Some("#ff5555".to_string()),
+    kernel_error_color: Some("#ff5555".to_string()),
+    kernel_warning_color: Some("#f1fa8c".to_string()),
 });
 
 // Help text
-pub const HELP_CONTENTS_TEXT: [&str; 8] = [
+pub const HELP_CONTENTS_TEXT: [&str; 11] = [
     "Press the corresponding numbers to jump to the section, or scroll:",
     "1 - General",
     "2 - CPU widget",
@@ -221,12 +328,15 @@ pub const HELP_CONTENTS_TEXT: [&str; 8] = [
     "4 - Process search widget",
     "5 - Process sort widget",
     "6 - Battery widget",
-    "7 - Basic memory widget",
+    "7 - Memory widget",
+    "8 - Network widget",
+    "9 - Connections widget",
+    "0 - Disk widget",
 ];
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const GENERAL_HELP_TEXT: [&str; 31] = [
+pub const GENERAL_HELP_TEXT: [&str; 42] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
@@ -252,22 +362,35 @@ pub const GENERAL_HELP_TEXT: [&str; 31] = [
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
     "e                Toggle expanding the currently selected widget",
+    "M                Toggle editing the layout",
+    "<, >             Switch to the previous/next layout page",
+    "Ctrl-s           Save the layout and settings to the config file",
+    "Ctrl-t           Switch to the next color scheme",
+    "Ctrl-p           Save a snapshot of the screen to a file",
+    "v                Switch the graph style of the selected chart",
+    "w                Export the history of the selected chart to a file",
+    "\\                Set a filter for every process widget, or clear it",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
+    ",                Scroll back in time on charts",
+    ".                Scroll forward in time on charts",
     "PgUp, PgDown     Scroll up/down a table by a page",
-    "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
+    "Mouse scroll     Scroll through the tables or zoom in/out of the chart under the mouse",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
+    "Mouse drag       Scroll back/forward in time on charts by dragging right/left",
 ];
 
-pub const CPU_HELP_TEXT: [&str; 2] = [
+pub const CPU_HELP_TEXT: [&str; 3] = [
     "2 - CPU widget\n",
+    "O                Cycle grouping cores by socket, core type, cluster, or sibling threads",
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
 ];
 
-pub const PROCESS_HELP_TEXT: [&str; 15] = [
+pub const PROCESS_HELP_TEXT: [&str; 33] = [
     "3 - Process widget",
     "dd, F9           Kill the selected process",
+    "X                Kill the selected process and all of its descendants",
     "c                Sort by CPU usage, press again to reverse sorting order",
     "m                Sort by memory usage, press again to reverse sorting order",
     "p                Sort by PID name, press again to reverse sorting order",
@@ -279,11 +402,28 @@ pub const PROCESS_HELP_TEXT: [&str; 15] = [
     "I                Invert current sort",
     "%                Toggle between values and percentages for memory usage",
     "t, F5            Toggle tree mode",
+    "T                Show the threads of the selected process",
+    "Enter            Show the details of the selected process",
+    "F                Show the open files, sockets, and pipes of the selected process",
+    "Space            Tag or untag the selected process for batch actions",
+    "U                Untag all processes",
+    "u                Toggle grouping processes by user",
+    "Y                Cycle between following, pinning, and not following the selection",
+    "z                Suspend the selected process",
+    "r                Resume the selected process",
+    "[, ]             Raise/lower the priority (nice value) of the selected process",
+    "N                Set an exact nice value for the selected process",
+    "o                Set the I/O scheduling class and priority of the selected process",
+    "a                Choose which CPUs the selected process may run on",
+    "x                Toggle hiding kernel threads",
+    "E                Choose, reorder, and resize the columns of the process widget",
+    "{, }             Switch to the previous/next saved filter from the config",
     "+, -, click      Collapse/expand a branch while in tree mode",
+    "Left, Right      Collapse (or go to the parent of)/expand a branch in tree mode",
     "click on header  Sorts the entries by that column, click again to invert the sort",
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 48] = [
+pub const SEARCH_HELP_TEXT: [&str; 57] = [
     "4 - Process search widget",
     "Esc              Close the search widget (retains the filter)",
     "Ctrl-a           Skip to the start of the search query",
@@ -296,21 +436,28 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "Alt-c, F1        Toggle matching case",
     "Alt-w, F2        Toggle matching the entire word",
     "Alt-r, F3        Toggle using regex",
+    "Alt-f, F4        Toggle fuzzy matching",
     "Left, Alt-h      Move cursor left",
     "Right, Alt-l     Move cursor right",
+    "Up, Down         Go through previous searches",
     "",
     "Supported search types:",
     "<by name/cmd>    ex: btm",
     "pid              ex: pid 825",
     "cpu, cpu%        ex: cpu > 4.2",
-    "mem, mem%        ex: mem < 4.2",
+    "mem, mem%        ex: mem < 4.2, mem > 1.5gb",
     "memb             ex: memb < 100 kb",
     "read, r/s        ex: read >= 1 b",
     "write, w/s       ex: write <= 1 tb",
     "tread, t.read    ex: tread = 1",
     "twrite, t.write  ex: twrite = 1",
-    "user            ex: user = root",
+    "user             ex: user = root, user:root",
+    "group            ex: group = wheel, group:wheel",
     "state            ex: state = running",
+    "runtime          ex: runtime > 10m",
+    "cputime, time+   ex: cputime >= 1h30m",
+    "gpu, gpu%        ex: gpu > 10%",
+    "fds              ex: fds > 1000",
     "",
     "Comparison operators:",
     "=                ex: cpu = 1",
@@ -332,6 +479,8 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "MiB              ex: read > 1 mib",
     "GiB              ex: read > 1 gib",
     "TiB              ex: read > 1 tib",
+    "%                ex: cpu >= 50%",
+    "s, m, h, d       ex: runtime > 1h30m",
 ];
 
 pub const SORT_HELP_TEXT: [&str; 6] = [
@@ -349,9 +498,40 @@ pub const BATTERY_HELP_TEXT: [&str; 3] = [
     "Right            Go to next battery",
 ];
 
-pub const BASIC_MEM_HELP_TEXT: [&str; 2] = [
-    "7 - Basic memory widget",
+pub const MEM_HELP_TEXT: [&str; 3] = [
+    "7 - Memory widget",
     "%                Toggle between values and percentages for memory usage",
+    "b                Toggle stacking used memory, the cache, and swap as bands",
+];
+
+pub const NETWORK_HELP_TEXT: [&str; 6] = [
+    "8 - Network widget",
+    "i                Cycle between all interfaces and a single interface",
+    "b                Toggle drawing one line per interface",
+    "B                Toggle graphing RX and TX separately",
+    "y                Toggle between a linear and a log scale",
+    "F                Choose which interfaces are filtered out",
+];
+
+pub const CONN_HELP_TEXT: [&str; 6] = [
+    "9 - Connections widget",
+    "/                Search the connections list",
+    "Enter            Stop typing the search query (retains the filter)",
+    "Esc              Stop searching and clear the search query",
+    "s                Cycle the sorting column",
+    "I                Invert current sort",
+];
+
+pub const DISK_HELP_TEXT: [&str; 9] = [
+    "0 - Disk widget",
+    "/                Search the disks by name or mount point",
+    "Enter            Stop typing the search query (retains the filter)",
+    "Esc              Stop searching and clear the search query",
+    "s                Cycle the sorting column",
+    "I                Invert current sort",
+    "x                Hide the selected mount point",
+    "U                Show all hidden mount points again",
+    "F                Choose which mount points are filtered out",
 ];
 
 pub const HELP_TEXT: &[&[&str]] = &[
@@ -362,7 +542,10 @@ pub const HELP_TEXT: &[&[&str]] = &[
     &SEARCH_HELP_TEXT,
     &SORT_HELP_TEXT,
     &BATTERY_HELP_TEXT,
-    &BASIC_MEM_HELP_TEXT,
+    &MEM_HELP_TEXT,
+    &NETWORK_HELP_TEXT,
+    &CONN_HELP_TEXT,
+    &DISK_HELP_TEXT,
 ];
 
 // Default layouts
@@ -422,6 +605,10 @@ pub const DEFAULT_BATTERY_LAYOUT: &str = r##"
 
 // Config and flags
 pub const DEFAULT_CONFIG_FILE_PATH: &str = "bottom/bottom.toml";
+/// The file the process search history is kept in if it is persisted, next to the config file.
+pub const SEARCH_HISTORY_FILE_NAME: &str = "search_history";
+/// The most queries kept in the process search history.
+pub const MAX_SEARCH_HISTORY_LENGTH: usize = 100;
 
 // TODO: Eventually deprecate this.
 pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  All of the settings are commented
@@ -437,6 +624,10 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #hide_avg_cpu = false
 # Whether to use dot markers rather than braille.
 #dot_marker = false
+# How to draw graphs, one of "braille", "block", "dot", or "line".
+#graph_style = "braille"
+# Whether to fill the areas below the lines of graphs.
+#filled_graphs = false
 # The update rate of the application.
 #rate = 1000
 # Whether to put the CPU legend to the left.
@@ -451,6 +642,8 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #whole_word = false
 # Whether to make process searching use regex by default.
 #regex = false
+# Whether to make process searching use fuzzy matching by default.
+#fuzzy = false
 # Defaults to Celsius.  Temperature is one of:
 #temperature_type = "k"
 #temperature_type = "f"
@@ -464,11 +657,17 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #time_delta = 15000
 # Hides the time scale.
 #hide_time = false
+# Draws faint gridlines with labels at round times along graphs, as many as fit.
+#time_gridlines = false
 # Override layout default widget
 #default_widget_type = "proc"
 #default_widget_count = 1
+# Groups the cores in the CPU legend, one of "none", "socket", "type", "cluster", or "sibling".
+#cpu_grouping = "none"
 # Use basic mode
 #basic = false
+# The most rows the per-core CPU bars take in basic mode, using more columns past that.
+#basic_cpu_rows = 8
 # Use the old network legend style
 #use_old_network_legend = false
 # Remove space in tables
@@ -477,7 +676,8 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #battery = false
 # Disable mouse clicks
 #disable_click = false
-# Built-in themes.  Valid values are "default", "default-light", "gruvbox", "gruvbox-light", "nord", "nord-light"
+# Built-in themes.  Valid values are "default", "default-light", "gruvbox", "gruvbox-light", "nord", "nord-light",
+# "dracula", or the name of one of the themes below
 #color = "default"
 # Show memory values in the processes widget as values by default
 #mem_as_value = false
@@ -487,14 +687,84 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #show_table_scroll_position = false
 # Show processes as their commands by default in the process widget.
 #process_command = false
+# Shows a sparkline of the recent CPU usage of each process in the process widget.
+#process_cpu_trend = false
+# Shows the number of open file descriptors of each process in the process widget.
+#process_fds = false
+# Shows the PSS, USS, and swap usage of each process in the process widget.
+#process_memory_breakdown = false
+# Shows the context switch and page fault rates of each process in the process widget.
+#process_latency = false
+# Shows the security label and effective capabilities of each process in the process widget.
+#process_security = false
+# Shows the I/O scheduling class and priority of each process in the process widget.
+#process_io_priority = false
+# Shows the start time and CPU time of each process in the process widget.
+#process_times = false
+# Hides kernel threads in the process widget by default.
+#hide_kernel_threads = false
+# Keeps the history of process searches between sessions, in a file next to this one.
+#persist_search_history = false
+# Filters every process widget with a query written like a process search, and shows how much the matching
+# processes use in the CPU and memory widgets.
+#global_filter = "user=alice"
+# Graphs the average CPU frequency in the CPU widget.
+#cpu_frequency_graph = false
+# Queries UPSes from the NUT daemon at this address for the battery widget.
+#nut_server = "localhost:3493"
+# Serves the harvested metrics in the Prometheus text format at http://<this address>/metrics.
+#prometheus_address = "127.0.0.1:9184"
+# The token that remote clients have to send to a server started with --serve, and that --connect sends.
+#remote_token = "a long random string"
+# The PEM certificate and unencrypted key to serve the clients of --serve over TLS with, through openssl.
+#serve_tls_cert = "/etc/bottom/cert.pem"
+#serve_tls_key = "/etc/bottom/key.pem"
+# The command that --ssh runs on the remote host, which has to serve its data with --serve_stdio.
+#ssh_command = "btm --serve_stdio"
+# The format that w exports the history of the selected graph in, either "csv" or "json".
+#history_export_format = "csv"
+# The directory that w exports histories to, which defaults to the current one.
+#history_export_dir = "/tmp"
+# The format that Ctrl-p saves snapshots of the screen in, either "html" or "ansi".
+#snapshot_format = "html"
+# The directory that Ctrl-p saves snapshots to, which defaults to the current one.
+#snapshot_dir = "/tmp"
 # Displays the network widget with binary prefixes.
 #network_use_binary_prefix = false
 # Displays the network widget using bytes.
 #network_use_bytes = false
 # Displays the network widget with a log scale.
 #network_use_log = false
+# The top of the network graph with a linear scale, either "auto" or a rate like "1Gb" or "125MiB".
+#network_y_max = "auto"
+# The top of the memory graph, either "auto" or a percentage like "50%".
+#memory_y_max = "100%"
+# Stacks used memory, the page cache and buffers, and swap as bands in the memory graph.
+#stacked_memory_graph = false
+# Highlights the network error, drop, and collision counters when they increase.
+#network_highlight_errors = false
+# Shows the SSID, signal level, bitrate, and frequency of wireless interfaces in the network legend.
+#network_wireless = false
+# Shows the number of established, TIME-WAIT, and listening TCP sockets in the network legend.
+#network_connections = false
+# Graphs RX and TX separately in the network widget, one above the other.
+#network_split_graph = false
 # Hides advanced options to stop a process on Unix-like systems.
 #disable_advanced_kill = false
+# Sets the command used to retry killing a process as root if it was not permitted.  An empty string disables retrying.
+#privilege_helper = "pkexec"
+# Shows the percentage of used inodes in the disk widget.
+#disk_inodes = false
+# Shows the average I/O wait time and utilization of each device in the disk widget.
+#disk_latency = false
+# Shows server chassis temperature sensors read over IPMI in the temperature widget.
+#ipmi = false
+# Sets the kubelet that the pods widget queries.
+#kubelet_url = "https://127.0.0.1:10250"
+# Skips verifying the certificate of the kubelet, which is usually self-signed.
+#kubelet_insecure = false
+# Shows SMART health, drive temperature, reallocated sectors, and wear level in the disk widget.
+#disk_smart = false
 
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
@@ -512,6 +782,8 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #ram_color="LightMagenta"
 # Represents the colour SWAP will use in the memory legend and graph.
 #swap_color="LightYellow"
+# Represents the colour the page cache and buffers will use in the stacked memory graph.
+#cache_color="LightCyan"
 # Represents the colour rx will use in the network legend and graph.
 #rx_color="LightCyan"
 # Represents the colour tx will use in the network legend and graph.
@@ -528,10 +800,37 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #selected_bg_color="LightBlue"
 # Represents the colour of the lines and text of the graph.
 #graph_color="Gray"
+# Colours the CPU and memory graph lines by their value instead, going through these colours as it rises
+#graph_gradient_colors=["green", "yellow", "red"]
 # Represents the colours of the battery based on charge
 #high_battery_color="green"
 #medium_battery_color="yellow"
 #low_battery_color="red"
+# Represents the colours of zombie, uninterruptible, and stopped processes in the process widget
+#zombie_process_color="red"
+#uninterruptible_process_color="yellow"
+#stopped_process_color="cyan"
+# Represents the colour of processes tagged for batch actions in the process widget
+#tagged_process_color="magenta"
+# Represents the colour of processes with an active alert, of active warnings in the alerts widget, and of widgets
+# showing a metric with an active warning
+#alerted_process_color="lightred"
+# Represents the colour of active critical alerts in the alerts widget, and of widgets showing a metric with one
+#critical_alert_color="red"
+# Represents the colour of the text matched by a search in the process widget
+#search_match_color="lightyellow"
+# Represents the colour of failed units in the units widget
+#failed_unit_color="red"
+# Represents the colours of error and warning messages in the kernel log widget
+#kernel_error_color="red"
+#kernel_warning_color="yellow"
+
+# Themes of your own, which take the same colours as [colors] and are picked by name with the color flag.
+# Any colour a theme does not set is left as the default.
+#[themes.mine]
+#text_color="#d0d0d0"
+#border_color="#5f5f87"
+#highlighted_border_color="#ffaf00"
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
@@ -540,6 +839,7 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #
 # All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "disk", "empty"].
 # All layout components have a ratio value - if this is not set, then it defaults to 1.
+# Graph widgets can also set graph_style and filled values, which override the ones in [flags].
 # The default widget layout:
 #[[row]]
 #  ratio=30
@@ -564,6 +864,23 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #    type="proc"
 #    default=true
 
+# More layouts can be added as named pages, which are switched between with < and >.  The layout
+# above is always the first page.
+#[[layout]]
+#name = "Processes"
+#  [[layout.row]]
+#    [[layout.row.child]]
+#      type="proc"
+#    [[layout.row.child]]
+#      type="cpu"
+
+# Remote hosts served by "btm --serve", which CPU and memory widgets can show by setting host to the name of one,
+# like host="web1".  The token defaults to remote_token.
+#[[host]]
+#name = "web1"
+#address = "web1:9185"
+#token = "a long random string"
+
 
 # Filters - you can hide specific temperature sensors, network interfaces, and disks using filters.  This is admittedly
 # a bit hard to use as of now, and there is a planned in-app interface for managing this in the future:
@@ -588,12 +905,104 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #case_sensitive = false
 #whole_word = false
 
+# Display names for temperature sensors, keyed by the sensor name shown in the temperature widget.
+# Note that filters still match the original sensor names.
+#[temp_names]
+#"nvme0: Composite" = "SSD"
+#"k10temp: Tctl" = "CPU"
+
+# Watchdog rules for processes, which highlight a process and list an alert for it in the alerts widget
+# when its name matches and it stays above every given threshold for the given number of seconds.
+#[[process_alert]]
+#name = "firefox"
+#regex = false
+#cpu_above = 90.0
+#mem_above = 50.0
+#duration = 30
+#severity = "warning"
+
+# Alert rules for metrics of the whole system, which list an alert in the alerts widget and highlight the widgets showing
+# the metric when it stays past the threshold for the given number of seconds.  The metric is one of "cpu", "memory",
+# "swap", "network_rx", "network_tx", "temperature", "disk", "load", "power", or "battery", the comparison one of ">",
+# ">=", "<", or "<=", and the severity one of "info", "warning", or "critical".  With notify, a desktop notification is
+# also shown when the rule starts alerting, and command is run by the shell when the rule starts and stops alerting, with
+# the details of the alert in BTM_ALERT_* environment variables.
+#[[alert]]
+#name = "High CPU"
+#metric = "cpu"
+#comparison = ">"
+#threshold = 90.0
+#duration = 30
+#severity = "critical"
+#notify = true
+#command = "curl -d \"$BTM_ALERT_NAME is $BTM_ALERT_STATE\" https://ntfy.sh/my-alerts"
+
+# Process searches saved under a name, which the process widget switches between with { and }.
+#[[process_filter]]
+#name = "browsers"
+#query = "firefox or chrome"
+
+# Appends the harvested metrics to a file on every refresh, as CSV or as JSON lines (the default for a path ending in
+# .jsonl or .json).  The file is rotated past max_size, keeping max_files older files.  All metrics are logged unless
+# some are listed.
+#[metrics_log]
+#path = "/var/log/bottom/metrics.csv"
+#format = "csv"
+#metrics = ["bottom_cpu_usage_percent", "bottom_memory_used_bytes"]
+#max_size = "10MiB"
+#max_files = 5
+
+# Pushes the harvested metrics in the InfluxDB line protocol to a write endpoint, every interval seconds.  All metrics
+# are pushed unless some are listed, and the tags are added to every point.
+#[influxdb]
+#url = "http://localhost:8086/api/v2/write?org=me&bucket=bottom"
+#token = "my-token"
+#interval = 10
+#metrics = ["bottom_cpu_usage_percent", "bottom_memory_used_bytes"]
+#tags = { host = "web1" }
+
+# The refresh rates in milliseconds of widgets that are not refreshed at the update rate.
+#[refresh_rates]
+#proc = 2000
+#temp = 10000
+
+# The default sort of the process, disk, and temperature widgets, by column header.
+#[default_sort]
+#proc = { column = "CPU%", descending = true }
+#disk = { column = "Mount" }
+#temp = { column = "Temp", descending = true }
+
+# The columns of the process widget, in the order they are shown, optionally with a fixed width.
+# Any column that is not listed is hidden.
+#[[process_column]]
+#name = "PID"
+#[[process_column]]
+#name = "Name"
+#width = 30
+#[[process_column]]
+#name = "CPU%"
+
 #[net_filter]
 #is_list_ignored = true
 #list = ["virbr0.*"]
 #regex = true
 #case_sensitive = false
 #whole_word = false
+
+# Filters can also use globs, either for the whole list with glob = true or for a single pattern starting with
+# "glob:", and have explicit allow and deny lists, where allowing takes precedence over denying.  For example, instead:
+#[net_filter]
+#allow = ["veth-keep"]
+#deny = ["glob:veth*", "regex:docker\\d+"]
+#whole_word = true
+
+# Which interfaces are broken out in the network widget's per-interface view.
+#[interface_filter]
+#is_list_ignored = true
+#list = ["lo"]
+#regex = false
+#case_sensitive = false
+#whole_word = true
 "##;
 
 pub const CONFIG_TOP_HEAD: &str = r##"# This is bottom's config file.
@@ -2,13 +2,16 @@
 //! can actually handle.
 use crate::{app::AxisScaling, units::data_units::DataUnit, Pid};
 use crate::{
-    app::{data_farmer, data_harvester, App, ProcWidgetState},
+    app::{
+        data_farmer, data_harvester, metric_alerts::AlertSeverity, states::CpuGrouping, App,
+        ProcWidgetState,
+    },
     utils::{self, gen_util::*},
 };
-use data_harvester::processes::ProcessSorting;
+use data_harvester::processes::{IoPriority, ProcessSorting};
 use fxhash::FxBuildHasher;
 use indexmap::IndexSet;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 /// Point is of time, data
 type Point = (f64, f64);
@@ -18,9 +21,15 @@ pub struct ConvertedBatteryData {
     pub battery_name: String,
     pub charge_percentage: f64,
     pub watt_consumption: String,
+    pub voltage: String,
     pub duration_until_full: Option<String>,
     pub duration_until_empty: Option<String>,
     pub health: String,
+    pub cycle_count: String,
+    /// The load of a UPS, if this is one.
+    pub load: Option<String>,
+    /// Tuple is time, charge percentage
+    pub charge_data: Vec<Point>,
 }
 
 #[derive(Default, Debug)]
@@ -31,6 +40,8 @@ pub struct ConvertedNetworkData {
     pub tx_display: String,
     pub total_rx_display: Option<String>,
     pub total_tx_display: Option<String>,
+    pub errors_display: String,
+    pub are_errors_increased: bool,
     // TODO: [NETWORKING] add min/max/mean of each
     // min_rx : f64,
     // max_rx : f64,
@@ -40,6 +51,13 @@ pub struct ConvertedNetworkData {
     // mean_tx: f64,
 }
 
+#[derive(Default, Debug)]
+pub struct ConvertedInterfaceData {
+    pub name: String,
+    pub points: Vec<Point>,
+    pub legend: String,
+}
+
 // TODO: [REFACTOR] Process data... stuff really needs a rewrite.  Again.
 #[derive(Clone, Default, Debug)]
 pub struct ConvertedProcessData {
@@ -49,6 +67,8 @@ pub struct ConvertedProcessData {
     pub command: String,
     pub is_thread: Option<bool>,
     pub cpu_percent_usage: f64,
+    /// The most recent CPU usage readings, oldest first.
+    pub cpu_history: Vec<f64>,
     pub mem_percent_usage: f64,
     pub mem_usage_bytes: u64,
     pub mem_usage_str: (f64, String),
@@ -63,7 +83,24 @@ pub struct ConvertedProcessData {
     pub tw_f64: f64,
     pub process_state: String,
     pub process_char: char,
+    pub is_kernel_thread: bool,
     pub user: Option<String>,
+    pub group: Option<String>,
+    pub pss_bytes: Option<u64>,
+    pub uss_bytes: Option<u64>,
+    pub swap_bytes: Option<u64>,
+    pub voluntary_switches_per_sec: Option<u64>,
+    pub involuntary_switches_per_sec: Option<u64>,
+    pub major_faults_per_sec: Option<u64>,
+    pub minor_faults_per_sec: Option<u64>,
+    pub security_label: Option<String>,
+    pub effective_caps: Option<u64>,
+    pub io_priority: Option<IoPriority>,
+    pub start_time: Option<u64>,
+    pub cpu_time: Option<std::time::Duration>,
+    pub open_fds: Option<u64>,
+    pub gpu_percent_usage: Option<f64>,
+    pub container: Option<String>,
 
     /// Prefix printed before the process when displayed.
     pub process_description_prefix: Option<String>,
@@ -73,6 +110,18 @@ pub struct ConvertedProcessData {
     pub is_collapsed_entry: bool,
 }
 
+impl ConvertedProcessData {
+    /// Returns the average of the most recent CPU usage readings, which the CPU trend column is
+    /// sorted by.
+    pub fn get_average_cpu_history(&self) -> f64 {
+        if self.cpu_history.is_empty() {
+            0.0
+        } else {
+            self.cpu_history.iter().sum::<f64>() / self.cpu_history.len() as f64
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct ConvertedCpuData {
     pub cpu_name: String,
@@ -81,43 +130,641 @@ pub struct ConvertedCpuData {
     pub cpu_data: Vec<Point>,
     /// Represents the value displayed on the legend.
     pub legend_value: String,
+    /// Represents the current frequency displayed on the legend, if any.
+    pub frequency_value: String,
+}
+
+/// An entry of the CPU legend, pointing at one of the converted CPUs or groups of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuLegendEntry {
+    Cpu(usize),
+    Group(usize),
+    /// A CPU listed under its group, which is drawn with the other CPUs only when selected.
+    GroupedCpu(usize),
+}
+
+/// The tops of the bands of the stacked memory graph, which are all percentages of the total RAM.
+/// Each band sits on the ones before it, so the swap band is at the top.
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedStackedMemData {
+    pub used: Vec<Point>,
+    pub cache: Vec<Point>,
+    pub swap: Vec<Point>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedPowerData {
+    pub name: String,
+    /// Represents the latest power draw displayed on the legend.
+    pub legend_value: String,
+    /// Tuple is time, value
+    pub power_data: Vec<Point>,
+}
+
+/// The smallest range of temperatures that a sensor's sparkline spans, so that a sensor going back
+/// and forth by a degree doesn't look like it's swinging wildly.
+const TEMP_SPARKLINE_MIN_RANGE: f64 = 10.0;
+
+/// Draws `values` as a line of block characters, one per value, scaled from `lowest` to `highest`.
+fn get_sparkline(values: &[f64], lowest: f64, highest: f64) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let range = highest - lowest;
+    values
+        .iter()
+        .map(|value| {
+            let index = if range > 0.0 {
+                ((value - lowest) / range * (BARS.len() - 1) as f64).round() as usize
+            } else {
+                0
+            };
+            BARS[index.min(BARS.len() - 1)]
+        })
+        .collect()
 }
 
 pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
     let current_data = &app.data_collection;
     let temp_type = &app.app_config_fields.temperature_type;
 
-    let mut sensor_vector: Vec<Vec<String>> = current_data
+    let mut temp_harvest = current_data
         .temp_harvest
         .iter()
-        .map(|temp_harvest| {
+        .zip(&current_data.temp_history)
+        .collect::<Vec<_>>();
+    if let Some((sorting, is_descending)) = &app.app_config_fields.temp_sorting {
+        use data_harvester::temperature::TempSorting;
+
+        temp_harvest.sort_by(|(a, _), (b, _)| match sorting {
+            TempSorting::Sensor => get_ordering(&a.name, &b.name, *is_descending),
+            TempSorting::Temperature => get_ordering(a.temperature, b.temperature, *is_descending),
+        });
+    }
+
+    let mut sensor_vector: Vec<Vec<String>> = temp_harvest
+        .into_iter()
+        .map(|(temp_harvest, temp_history)| {
             vec![
-                temp_harvest.name.clone(),
+                app.app_config_fields
+                    .temp_names
+                    .get(&temp_harvest.name)
+                    .unwrap_or(&temp_harvest.name)
+                    .clone(),
                 (temp_harvest.temperature.ceil() as u64).to_string()
                     + match temp_type {
                         data_harvester::temperature::TemperatureType::Celsius => "°C",
                         data_harvester::temperature::TemperatureType::Kelvin => "K",
                         data_harvester::temperature::TemperatureType::Fahrenheit => "°F",
                     },
+                {
+                    let temp_history = temp_history
+                        .iter()
+                        .map(|temperature| f64::from(*temperature))
+                        .collect::<Vec<_>>();
+                    let lowest = temp_history.iter().copied().fold(f64::INFINITY, f64::min);
+                    let highest = temp_history
+                        .iter()
+                        .copied()
+                        .fold(lowest + TEMP_SPARKLINE_MIN_RANGE, f64::max);
+                    get_sparkline(&temp_history, lowest, highest)
+                },
             ]
         })
         .collect();
 
     if sensor_vector.is_empty() {
-        sensor_vector.push(vec!["No Sensors Found".to_string(), "".to_string()]);
+        sensor_vector.push(vec![
+            "No Sensors Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]);
     }
 
     sensor_vector
 }
 
-pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+/// Returns the sorted and filtered rows of a connections widget, in the order of protocol, local
+/// address, remote address, state, PID, and process name.
+pub fn convert_connection_rows(app: &App, widget_id: u64) -> Vec<Vec<String>> {
+    use data_harvester::connections::ConnectionSorting;
+
+    let conn_widget_state = match app.conn_state.get_widget_state(widget_id) {
+        Some(conn_widget_state) => conn_widget_state,
+        None => return Vec::new(),
+    };
+
+    let mut connections = app
+        .data_collection
+        .connection_harvest
+        .iter()
+        .collect::<Vec<_>>();
+    connections.sort_by(|a, b| {
+        let ordering = match conn_widget_state.sorting_type {
+            ConnectionSorting::Protocol => a.protocol_name().cmp(b.protocol_name()),
+            ConnectionSorting::LocalAddress => a.local_address.cmp(&b.local_address),
+            ConnectionSorting::RemoteAddress => a.remote_address.cmp(&b.remote_address),
+            ConnectionSorting::State => a.state.cmp(&b.state),
+            ConnectionSorting::Pid => a.pid.cmp(&b.pid),
+            ConnectionSorting::ProcessName => a.process_name.cmp(&b.process_name),
+        };
+
+        if conn_widget_state.is_sort_descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    let search_query = conn_widget_state.search_query.to_lowercase();
+
+    connections
+        .into_iter()
+        .map(|connection| {
+            vec![
+                connection.protocol_name().to_string(),
+                connection.local_address.to_string(),
+                connection.remote_address.to_string(),
+                connection.state.clone(),
+                connection
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                connection
+                    .process_name
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .filter(|row| {
+            search_query.is_empty()
+                || row
+                    .iter()
+                    .any(|entry| entry.to_lowercase().contains(&search_query))
+        })
+        .collect()
+}
+
+/// Returns the rows of the thread dialog sorted by CPU usage, in the order of TID, name, CPU usage,
+/// and state.
+pub fn convert_thread_rows(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+    let mut threads = current_data.thread_harvest.iter().collect::<Vec<_>>();
+    threads.sort_by(|a, b| {
+        utils::gen_util::get_ordering(a.cpu_usage_percent, b.cpu_usage_percent, true)
+            .then_with(|| a.tid.cmp(&b.tid))
+    });
+
+    threads
+        .into_iter()
+        .map(|thread| {
+            vec![
+                thread.tid.to_string(),
+                thread.name.clone(),
+                format!("{:.1}%", thread.cpu_usage_percent),
+                thread.thread_state.clone(),
+            ]
+        })
+        .collect()
+}
+
+/// Returns the rows of the open files dialog, in the order of FD, type, and name.
+pub fn convert_open_file_rows(
+    open_files: &[data_harvester::processes::OpenFileHarvest],
+) -> Vec<Vec<String>> {
+    open_files
+        .iter()
+        .map(|open_file| {
+            vec![
+                open_file.fd.to_string(),
+                open_file.kind.to_string(),
+                open_file.name.clone(),
+            ]
+        })
+        .collect()
+}
+
+/// Returns the rows of the users widget, in the order of user, TTY, host, login time, and idle
+/// time.
+pub fn convert_session_rows(app: &App) -> Vec<Vec<String>> {
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let mut sessions = app
+        .data_collection
+        .session_harvest
+        .iter()
+        .collect::<Vec<_>>();
+    sessions.sort_by(|a, b| a.user.cmp(&b.user).then_with(|| a.tty.cmp(&b.tty)));
+
+    let mut session_vector: Vec<Vec<String>> = sessions
+        .into_iter()
+        .map(|session| {
+            vec![
+                session.user.clone(),
+                session.tty.clone(),
+                session.host.clone().unwrap_or_else(|| "-".to_string()),
+                format_start_time(session.login_time, current_time),
+                session
+                    .idle_secs
+                    .map(format_idle_time)
+                    .unwrap_or_else(|| "?".to_string()),
+            ]
+        })
+        .collect();
+
+    if session_vector.is_empty() {
+        session_vector.push(vec![
+            "No Sessions Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]);
+    }
+
+    session_vector
+}
+
+/// Returns the rows of the NUMA widget, in the order of node, local CPUs, total memory, used
+/// memory, and use percentage.
+pub fn convert_numa_rows(app: &App) -> Vec<Vec<String>> {
+    let mut numa_vector: Vec<Vec<String>> = app
+        .data_collection
+        .numa_harvest
+        .iter()
+        .map(|node| {
+            vec![
+                format!("node{}", node.node),
+                if node.cpu_list.is_empty() {
+                    "-".to_string()
+                } else {
+                    node.cpu_list.clone()
+                },
+                format_memory_bytes(node.mem_total_in_kib * 1024),
+                format_memory_bytes(node.mem_used_in_kib * 1024),
+                node.use_percent()
+                    .map(|use_percent| format!("{:.1}%", use_percent))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    if numa_vector.is_empty() {
+        numa_vector.push(vec![
+            "No NUMA Nodes Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]);
+    }
+
+    numa_vector
+}
+
+/// Returns the rows of the pods widget, in the order of namespace, name, CPU usage, and memory
+/// usage.
+pub fn convert_pod_rows(app: &App) -> Vec<Vec<String>> {
+    let mut pods = app.data_collection.pod_harvest.iter().collect::<Vec<_>>();
+    pods.sort_by(|a, b| {
+        a.namespace
+            .cmp(&b.namespace)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut pod_vector: Vec<Vec<String>> = pods
+        .into_iter()
+        .map(|pod| {
+            vec![
+                pod.namespace.clone(),
+                pod.name.clone(),
+                pod.cpu_usage_percent
+                    .map(|cpu_usage_percent| format!("{:.1}%", cpu_usage_percent))
+                    .unwrap_or_else(|| "-".to_string()),
+                pod.mem_usage_bytes
+                    .map(format_memory_bytes)
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    if pod_vector.is_empty() {
+        pod_vector.push(vec![
+            "No Pods Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]);
+    }
+
+    pod_vector
+}
+
+/// Returns the rows of the VM widget, in the order of name, vCPUs, CPU usage, memory, disk read
+/// and write rates, and network receive and transmit rates.
+pub fn convert_vm_rows(app: &App) -> Vec<Vec<String>> {
+    let mut vms = app.data_collection.vm_harvest.iter().collect::<Vec<_>>();
+    vms.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let format_rate = |rate: Option<u64>| {
+        rate.map(|rate| {
+            let (value, unit) = get_decimal_bytes(rate);
+            format!("{:.0}{}/s", value, unit)
+        })
+        .unwrap_or_else(|| "-".to_string())
+    };
+
+    let mut vm_vector: Vec<Vec<String>> = vms
+        .into_iter()
+        .map(|vm| {
+            vec![
+                vm.name.clone(),
+                vm.vcpus
+                    .map(|vcpus| vcpus.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                vm.cpu_usage_percent
+                    .map(|cpu_usage_percent| format!("{:.1}%", cpu_usage_percent))
+                    .unwrap_or_else(|| "-".to_string()),
+                vm.mem_bytes
+                    .map(format_memory_bytes)
+                    .unwrap_or_else(|| "-".to_string()),
+                format_rate(vm.read_bytes_per_sec),
+                format_rate(vm.write_bytes_per_sec),
+                format_rate(vm.rx_bytes_per_sec),
+                format_rate(vm.tx_bytes_per_sec),
+            ]
+        })
+        .collect();
+
+    if vm_vector.is_empty() {
+        let mut row = vec!["No VMs Found".to_string()];
+        row.resize(8, "".to_string());
+        vm_vector.push(row);
+    }
+
+    vm_vector
+}
+
+/// Returns the rows of the activity widget, in the order of source, rate per second, and total.
+pub fn convert_activity_rows(app: &App) -> Vec<Vec<String>> {
+    let mut activity_vector: Vec<Vec<String>> = app
+        .data_collection
+        .activity_harvest
+        .iter()
+        .map(|activity| {
+            // Gauges like the available entropy have no rate, only a current value.
+            vec![
+                activity.name.clone(),
+                activity
+                    .rate_per_sec
+                    .map(|rate_per_sec| format!("{:.0}/s", rate_per_sec))
+                    .unwrap_or_else(|| "-".to_string()),
+                activity.value.to_string(),
+            ]
+        })
+        .collect();
+
+    if activity_vector.is_empty() {
+        activity_vector.push(vec![
+            "No Activity Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]);
+    }
+
+    activity_vector
+}
+
+/// Returns the rows of the units widget, in the order of unit, active state, sub-state, CPU
+/// usage, and memory usage, along with whether each unit failed.  Failed units are listed first.
+pub fn convert_unit_rows(app: &App) -> Vec<(Vec<String>, bool)> {
+    let mut units = app.data_collection.unit_harvest.iter().collect::<Vec<_>>();
+    units.sort_by(|a, b| {
+        b.is_failed()
+            .cmp(&a.is_failed())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut unit_vector: Vec<(Vec<String>, bool)> = units
+        .into_iter()
+        .map(|unit| {
+            (
+                vec![
+                    unit.name.clone(),
+                    unit.active_state.clone(),
+                    unit.sub_state.clone(),
+                    unit.cpu_usage_percent
+                        .map(|cpu_usage_percent| format!("{:.1}%", cpu_usage_percent))
+                        .unwrap_or_else(|| "-".to_string()),
+                    unit.mem_usage_bytes
+                        .map(format_memory_bytes)
+                        .unwrap_or_else(|| "-".to_string()),
+                ],
+                unit.is_failed(),
+            )
+        })
+        .collect();
+
+    if unit_vector.is_empty() {
+        unit_vector.push((
+            vec![
+                "No Units Found".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ],
+            false,
+        ));
+    }
+
+    unit_vector
+}
+
+/// Returns the rows of the alerts widget for both process and metric alerts, newest first, in the
+/// order of the time it was raised, state, severity, PID, name, value when raised, and rule, along
+/// with the severity of each alert that is active.
+pub fn convert_alert_rows(app: &App) -> Vec<(Vec<String>, Option<AlertSeverity>)> {
+    let format_state = |is_active: bool| {
+        if is_active {
+            "active".to_string()
+        } else {
+            "resolved".to_string()
+        }
+    };
+
+    let mut timed_rows: Vec<(u64, (Vec<String>, Option<AlertSeverity>))> = app
+        .process_alerts
+        .alerts
+        .iter()
+        .map(|alert| {
+            (
+                alert.raised_time,
+                (
+                    vec![
+                        format_alert_time(alert.raised_time),
+                        format_state(alert.is_active),
+                        alert.severity.get_name().to_string(),
+                        alert.pid.to_string(),
+                        alert.process_name.clone(),
+                        format!(
+                            "CPU {:.1}% Mem {:.1}%",
+                            alert.cpu_usage_percent, alert.mem_usage_percent
+                        ),
+                        alert.rule_description.clone(),
+                    ],
+                    alert.is_active.then_some(alert.severity),
+                ),
+            )
+        })
+        .chain(app.metric_alerts.alerts.iter().map(|alert| {
+            (
+                alert.raised_time,
+                (
+                    vec![
+                        format_alert_time(alert.raised_time),
+                        format_state(alert.is_active),
+                        alert.severity.get_name().to_string(),
+                        "".to_string(),
+                        alert.name.clone(),
+                        alert.value_description.clone(),
+                        alert.rule_description.clone(),
+                    ],
+                    alert.is_active.then_some(alert.severity),
+                ),
+            )
+        }))
+        .collect();
+
+    // Both lists are oldest first, so a stable sort keeps the order of alerts raised together.
+    timed_rows.sort_by_key(|(raised_time, _row)| *raised_time);
+    let mut alert_vector: Vec<(Vec<String>, Option<AlertSeverity>)> =
+        timed_rows.into_iter().rev().map(|(_, row)| row).collect();
+
+    if alert_vector.is_empty() {
+        alert_vector.push((
+            vec![
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "No Alerts".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ],
+            None,
+        ));
+    }
+
+    alert_vector
+}
+
+/// Returns the rows of the kernel log widget, in the order of timestamp, severity, and message,
+/// along with the severity of each message.
+pub fn convert_kernel_log_rows(app: &App) -> Vec<(Vec<String>, u8)> {
+    let mut kernel_log_vector: Vec<(Vec<String>, u8)> = app
+        .data_collection
+        .kernel_log_harvest
+        .iter()
+        .map(|entry| {
+            (
+                vec![
+                    format!(
+                        "{}.{:06}",
+                        entry.timestamp_usec / 1_000_000,
+                        entry.timestamp_usec % 1_000_000
+                    ),
+                    entry.level_name().to_string(),
+                    entry.message.clone(),
+                ],
+                entry.level,
+            )
+        })
+        .collect();
+
+    if kernel_log_vector.is_empty() {
+        kernel_log_vector.push((
+            vec![
+                "".to_string(),
+                "".to_string(),
+                "No Messages Found".to_string(),
+            ],
+            6,
+        ));
+    }
+
+    kernel_log_vector
+}
+
+/// Returns the sorted and filtered rows of a disk widget.
+pub fn convert_disk_row(app: &App, widget_id: u64) -> Vec<Vec<String>> {
+    let disk_widget_state = match app.disk_state.get_widget_state(widget_id) {
+        Some(disk_widget_state) => disk_widget_state,
+        None => return Vec::new(),
+    };
+    let current_data = &app.data_collection;
+    let use_disk_smart = app.app_config_fields.use_disk_smart;
+    let use_disk_inodes = app.app_config_fields.use_disk_inodes;
+    let use_disk_latency = app.app_config_fields.use_disk_latency;
+    let temp_type = &app.app_config_fields.temperature_type;
     let mut disk_vector: Vec<Vec<String>> = Vec::new();
 
-    current_data
+    let mut disks = current_data
         .disk_harvest
         .iter()
         .zip(&current_data.io_labels)
-        .for_each(|(disk, (io_read, io_write))| {
+        .enumerate()
+        .collect::<Vec<_>>();
+
+    // Filter before sorting, so that the I/O rates are still looked up by the harvest index.
+    let search_query = disk_widget_state.search_query.to_lowercase();
+    disks.retain(|(_itx, (disk, _io))| {
+        !disk_widget_state.hidden_mounts.contains(&disk.mount_point)
+            && (search_query.is_empty()
+                || disk.name.to_lowercase().contains(&search_query)
+                || disk.mount_point.to_lowercase().contains(&search_query))
+    });
+
+    if let Some((sorting, is_descending)) = &disk_widget_state.sorting {
+        use data_harvester::disks::DiskSorting;
+
+        let get_io_rates = |itx: usize| current_data.io_labels_and_prev.get(itx).map(|io| io.0);
+        let get_percent = |used: Option<u64>, total: Option<u64>| match (used, total) {
+            (Some(used), Some(total)) if total > 0 => Some(used as f64 / total as f64),
+            _ => None,
+        };
+        disks.sort_by(|(a_itx, (a, _)), (b_itx, (b, _))| match sorting {
+            DiskSorting::Disk => get_ordering(&a.name, &b.name, *is_descending),
+            DiskSorting::Mount => get_ordering(&a.mount_point, &b.mount_point, *is_descending),
+            DiskSorting::UsedPercent => get_ordering(
+                get_percent(a.used_space, a.total_space),
+                get_percent(b.used_space, b.total_space),
+                *is_descending,
+            ),
+            DiskSorting::InodePercent => get_ordering(
+                get_percent(a.used_inodes, a.total_inodes),
+                get_percent(b.used_inodes, b.total_inodes),
+                *is_descending,
+            ),
+            DiskSorting::Free => get_ordering(a.free_space, b.free_space, *is_descending),
+            DiskSorting::Total => get_ordering(a.total_space, b.total_space, *is_descending),
+            DiskSorting::ReadPerSecond => get_ordering(
+                get_io_rates(*a_itx).map(|(read, _write)| read),
+                get_io_rates(*b_itx).map(|(read, _write)| read),
+                *is_descending,
+            ),
+            DiskSorting::WritePerSecond => get_ordering(
+                get_io_rates(*a_itx).map(|(_read, write)| write),
+                get_io_rates(*b_itx).map(|(_read, write)| write),
+                *is_descending,
+            ),
+        });
+    }
+
+    disks
+        .into_iter()
+        .for_each(|(itx, (disk, (io_read, io_write)))| {
             let free_space_fmt = if let Some(free_space) = disk.free_space {
                 let converted_free_space = get_decimal_bytes(free_space);
                 format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1)
@@ -142,15 +789,39 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<S
                 "N/A".to_string()
             };
 
-            disk_vector.push(vec![
-                disk.name.to_string(),
-                disk.mount_point.to_string(),
-                usage_fmt,
+            let mut row = vec![disk.name.to_string(), disk.mount_point.to_string(), usage_fmt];
+
+            if use_disk_inodes {
+                row.push(
+                    if let (Some(used_inodes), Some(total_inodes)) =
+                        (disk.used_inodes, disk.total_inodes)
+                    {
+                        format!("{:.0}%", used_inodes as f64 / total_inodes as f64 * 100_f64)
+                    } else {
+                        "N/A".to_string()
+                    },
+                );
+            }
+
+            row.extend([
                 free_space_fmt,
                 total_space_fmt,
                 io_read.to_string(),
                 io_write.to_string(),
             ]);
+
+            if use_disk_latency {
+                match current_data.io_latency_labels.get(itx) {
+                    Some((io_await, io_util)) => row.extend([io_await.clone(), io_util.clone()]),
+                    None => row.extend(["N/A".to_string(), "N/A".to_string()]),
+                }
+            }
+
+            if use_disk_smart {
+                row.extend(convert_disk_smart(&disk.smart, temp_type));
+            }
+
+            disk_vector.push(row);
         });
 
     if disk_vector.is_empty() {
@@ -160,19 +831,58 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<S
     disk_vector
 }
 
+/// Returns the temperature, health, reallocated sector, and wear level strings for a disk.
+fn convert_disk_smart(
+    smart: &Option<data_harvester::disks::SmartData>,
+    temp_type: &data_harvester::temperature::TemperatureType,
+) -> [String; 4] {
+    use data_harvester::temperature::TemperatureType;
+
+    if let Some(smart) = smart {
+        [
+            if let Some(temperature) = smart.temperature {
+                match temp_type {
+                    TemperatureType::Celsius => format!("{:.0}°C", temperature),
+                    TemperatureType::Kelvin => format!("{:.0}K", temperature + 273.15),
+                    TemperatureType::Fahrenheit => {
+                        format!("{:.0}°F", (temperature * (9.0 / 5.0)) + 32.0)
+                    }
+                }
+            } else {
+                "N/A".to_string()
+            },
+            match smart.is_healthy {
+                Some(true) => "OK".to_string(),
+                Some(false) => "FAILING".to_string(),
+                None => "N/A".to_string(),
+            },
+            if let Some(reallocated_sectors) = smart.reallocated_sectors {
+                reallocated_sectors.to_string()
+            } else {
+                "N/A".to_string()
+            },
+            if let Some(wear_level) = smart.wear_level {
+                format!("{}%", wear_level)
+            } else {
+                "N/A".to_string()
+            },
+        ]
+    } else {
+        [
+            "N/A".to_string(),
+            "N/A".to_string(),
+            "N/A".to_string(),
+            "N/A".to_string(),
+        ]
+    }
+}
+
 pub fn convert_cpu_data_points(
     current_data: &data_farmer::DataCollection, existing_cpu_data: &mut Vec<ConvertedCpuData>,
     is_frozen: bool,
 ) {
-    let current_time = if is_frozen {
-        if let Some(frozen_instant) = current_data.frozen_instant {
-            frozen_instant
-        } else {
-            current_data.current_instant
-        }
-    } else {
-        current_data.current_instant
-    };
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
 
     // Initialize cpu_data_vector if the lengths don't match...
     if let Some((_time, data)) = &current_data.timed_data_vec.last() {
@@ -182,6 +892,7 @@ pub fn convert_cpu_data_points(
                 short_cpu_name: "All".to_string(),
                 cpu_data: vec![],
                 legend_value: String::new(),
+                frequency_value: String::new(),
             }];
 
             existing_cpu_data.extend(
@@ -209,6 +920,7 @@ pub fn convert_cpu_data_points(
                             String::default()
                         },
                         legend_value: format!("{:.0}%", cpu_usage.round()),
+                        frequency_value: get_frequency_value(current_data.cpu_harvest.get(itx)),
                         cpu_data: vec![],
                     })
                     .collect::<Vec<ConvertedCpuData>>(),
@@ -218,9 +930,11 @@ pub fn convert_cpu_data_points(
                 .iter_mut()
                 .skip(1)
                 .zip(&data.cpu_data)
-                .for_each(|(cpu, cpu_usage)| {
+                .enumerate()
+                .for_each(|(itx, (cpu, cpu_usage))| {
                     cpu.cpu_data = vec![];
                     cpu.legend_value = format!("{:.0}%", cpu_usage.round());
+                    cpu.frequency_value = get_frequency_value(current_data.cpu_harvest.get(itx));
                 });
         }
     }
@@ -234,32 +948,246 @@ pub fn convert_cpu_data_points(
             }
         }
 
-        if *time == current_time {
+        if *time == end_time {
             break;
         }
     }
 }
 
-pub fn convert_mem_data_points(
+/// Groups the cores of `cpu_data` by `grouping`, returning the average usage of each group along
+/// with the order of the legend, in which each group is followed by its cores.  Anything that
+/// isn't grouped, like the average or cores of an unknown topology, is listed as usual.
+pub fn convert_cpu_groups(
+    current_data: &data_farmer::DataCollection, cpu_data: &[ConvertedCpuData],
+    grouping: CpuGrouping,
+) -> (Vec<ConvertedCpuData>, Vec<CpuLegendEntry>) {
+    use data_harvester::cpu::CoreType;
+
+    fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+        let (sum, count) =
+            values.fold((0.0, 0_u32), |(sum, count), value| (sum + value, count + 1));
+        if count > 0 {
+            Some(sum / f64::from(count))
+        } else {
+            None
+        }
+    }
+
+    let topologies = current_data
+        .cpu_harvest
+        .iter()
+        .map(|cpu| cpu.topology)
+        .collect::<Vec<_>>();
+    let is_multi_package = topologies
+        .iter()
+        .flatten()
+        .any(|topology| topology.package_id != 0);
+
+    // The "All" entry comes first, so each CPU is one entry after its harvest.
+    let mut groups: BTreeMap<(usize, usize), (String, String, Vec<usize>)> = BTreeMap::new();
+    let mut is_grouped = vec![false; cpu_data.len()];
+    for (itx, topology) in topologies.iter().enumerate() {
+        let topology = match topology {
+            Some(topology) if itx + 1 < cpu_data.len() => topology,
+            _ => continue,
+        };
+        let package_prefix = if is_multi_package {
+            format!("S{} ", topology.package_id)
+        } else {
+            String::new()
+        };
+
+        let group = match grouping {
+            CpuGrouping::None => None,
+            CpuGrouping::Socket => Some((
+                (topology.package_id, 0),
+                format!("Socket {}", topology.package_id),
+                format!("S{}", topology.package_id),
+            )),
+            CpuGrouping::CoreType => match topology.core_type {
+                Some(CoreType::Performance) => {
+                    Some(((0, 0), "P-cores".to_string(), "P".to_string()))
+                }
+                Some(CoreType::Efficiency) => {
+                    Some(((1, 0), "E-cores".to_string(), "E".to_string()))
+                }
+                None => None,
+            },
+            CpuGrouping::Cluster => topology.cluster_id.map(|cluster_id| {
+                (
+                    (topology.package_id, cluster_id),
+                    format!("{}Cluster {}", package_prefix, cluster_id),
+                    format!("{}Cl{}", package_prefix, cluster_id),
+                )
+            }),
+            CpuGrouping::Sibling => Some((
+                (topology.package_id, topology.core_id),
+                format!("{}Core {}", package_prefix, topology.core_id),
+                format!("{}C{}", package_prefix, topology.core_id),
+            )),
+        };
+
+        if let Some((key, cpu_name, short_cpu_name)) = group {
+            groups
+                .entry(key)
+                .or_insert_with(|| (cpu_name, short_cpu_name, Vec::new()))
+                .2
+                .push(itx + 1);
+            is_grouped[itx + 1] = true;
+        }
+    }
+
+    if groups.is_empty() {
+        return (
+            Vec::new(),
+            (0..cpu_data.len()).map(CpuLegendEntry::Cpu).collect(),
+        );
+    }
+
+    let latest_usages = current_data
+        .timed_data_vec
+        .last()
+        .map(|(_time, data)| &data.cpu_data[..])
+        .unwrap_or(&[]);
+
+    let mut group_data = Vec::with_capacity(groups.len());
+    let mut legend_entries = vec![CpuLegendEntry::Cpu(0)];
+    // Anything listed before the first grouped core, like the average, stays at the top.
+    let first_grouped = is_grouped
+        .iter()
+        .position(|is_grouped| *is_grouped)
+        .unwrap_or(cpu_data.len());
+    legend_entries.extend((1..first_grouped).map(CpuLegendEntry::Cpu));
+
+    for (group_index, (cpu_name, short_cpu_name, members)) in groups.into_values().enumerate() {
+        let num_points = members
+            .iter()
+            .map(|member| cpu_data[*member].cpu_data.len())
+            .min()
+            .unwrap_or(0);
+        let points = (0..num_points)
+            .map(|point_index| {
+                let time = cpu_data[members[0]].cpu_data[point_index].0;
+                let usage = average(
+                    members
+                        .iter()
+                        .map(|member| cpu_data[*member].cpu_data[point_index].1),
+                );
+                (time, usage.unwrap_or(0.0))
+            })
+            .collect();
+        let usage = average(
+            members
+                .iter()
+                .filter_map(|member| latest_usages.get(member - 1).copied()),
+        )
+        .unwrap_or(0.0);
+        let frequency_value = match average(
+            members
+                .iter()
+                .filter_map(|member| current_data.cpu_harvest.get(member - 1)?.frequency_mhz),
+        ) {
+            Some(frequency_mhz) => format!("{:.1}GHz", frequency_mhz / 1000.0),
+            None => String::new(),
+        };
+
+        group_data.push(ConvertedCpuData {
+            cpu_name,
+            short_cpu_name,
+            cpu_data: points,
+            legend_value: format!("{:.0}%", usage.round()),
+            frequency_value,
+        });
+        legend_entries.push(CpuLegendEntry::Group(group_index));
+        legend_entries.extend(members.into_iter().map(CpuLegendEntry::GroupedCpu));
+    }
+
+    legend_entries.extend(
+        (first_grouped..cpu_data.len())
+            .filter(|itx| !is_grouped[*itx])
+            .map(CpuLegendEntry::Cpu),
+    );
+
+    (group_data, legend_entries)
+}
+
+fn get_frequency_value(cpu_harvest: Option<&data_harvester::cpu::CpuData>) -> String {
+    if let Some(frequency_mhz) = cpu_harvest.and_then(|cpu| cpu.frequency_mhz) {
+        format!("{:.1}GHz", frequency_mhz / 1000.0)
+    } else {
+        String::new()
+    }
+}
+
+/// Returns the average CPU frequency over time, as a percentage of the maximum frequency.
+pub fn convert_cpu_frequency_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
-    let current_time = if is_frozen {
-        if let Some(frozen_instant) = current_data.frozen_instant {
-            frozen_instant
-        } else {
-            current_data.current_instant
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
+
+    for (time, data) in &current_data.timed_data_vec {
+        if let Some(cpu_frequency_data) = data.cpu_frequency_data {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            result.push((-time_from_start, cpu_frequency_data));
+            if *time == end_time {
+                break;
+            }
         }
-    } else {
-        current_data.current_instant
-    };
+    }
+
+    result
+}
+
+/// Returns the power draw of each power domain over time, ordered as in the latest harvest.
+pub fn convert_power_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<ConvertedPowerData> {
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
+
+    let mut result = current_data
+        .power_harvest
+        .iter()
+        .map(|domain| ConvertedPowerData {
+            name: domain.name.clone(),
+            legend_value: format!("{:.1}W", domain.watts),
+            power_data: vec![],
+        })
+        .collect::<Vec<_>>();
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for domain in &mut result {
+            if let Some(watts) = data.power_data.get(&domain.name) {
+                domain.power_data.push((-time_from_start, *watts));
+            }
+        }
+
+        if *time == end_time {
+            break;
+        }
+    }
+
+    result
+}
+
+pub fn convert_mem_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<Point> {
+    let mut result: Vec<Point> = Vec::new();
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
 
     for (time, data) in &current_data.timed_data_vec {
         if let Some(mem_data) = data.mem_data {
             let time_from_start: f64 =
                 (current_time.duration_since(*time).as_millis() as f64).floor();
             result.push((-time_from_start, mem_data));
-            if *time == current_time {
+            if *time == end_time {
                 break;
             }
         }
@@ -272,22 +1200,55 @@ pub fn convert_swap_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
-    let current_time = if is_frozen {
-        if let Some(frozen_instant) = current_data.frozen_instant {
-            frozen_instant
-        } else {
-            current_data.current_instant
-        }
-    } else {
-        current_data.current_instant
-    };
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
 
     for (time, data) in &current_data.timed_data_vec {
         if let Some(swap_data) = data.swap_data {
             let time_from_start: f64 =
                 (current_time.duration_since(*time).as_millis() as f64).floor();
             result.push((-time_from_start, swap_data));
-            if *time == current_time {
+            if *time == end_time {
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+pub fn convert_stacked_mem_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> ConvertedStackedMemData {
+    let mut result = ConvertedStackedMemData::default();
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
+
+    // Swap is a percentage of the total swap, so it has to be scaled to be stacked on RAM.
+    let mem_total_in_kib = current_data.memory_harvest.mem_total_in_kib;
+    let swap_to_mem_ratio = if mem_total_in_kib > 0 {
+        current_data.swap_harvest.mem_total_in_kib as f64 / mem_total_in_kib as f64
+    } else {
+        0.0
+    };
+
+    for (time, data) in &current_data.timed_data_vec {
+        if let Some(mem_data) = data.mem_data {
+            let time_from_start: f64 =
+                -(current_time.duration_since(*time).as_millis() as f64).floor();
+            result.used.push((time_from_start, mem_data));
+
+            let mut top = mem_data;
+            if let Some(cache_data) = data.cache_data {
+                top += cache_data;
+                result.cache.push((time_from_start, top));
+            }
+            if let Some(swap_data) = data.swap_data {
+                top += swap_data * swap_to_mem_ratio;
+                result.swap.push((time_from_start, top));
+            }
+
+            if *time == end_time {
                 break;
             }
         }
@@ -366,54 +1327,172 @@ pub fn convert_mem_labels(
     )
 }
 
+/// Returns the huge page label, as the percentage in use and the page counts, if any huge pages
+/// are configured.
+pub fn convert_huge_pages_labels(
+    current_data: &data_farmer::DataCollection,
+) -> Option<(String, String)> {
+    let huge_pages = current_data.huge_pages_harvest.as_ref()?;
+    if huge_pages.total == 0 {
+        return None;
+    }
+
+    Some((
+        format!(
+            "{:3.0}%",
+            huge_pages.used() as f64 / huge_pages.total as f64 * 100.0
+        ),
+        format!(
+            "   {}/{} free, {} rsvd ({})",
+            huge_pages.free,
+            huge_pages.total,
+            huge_pages.reserved,
+            format_memory_bytes(huge_pages.page_size_in_kib * 1024)
+        ),
+    ))
+}
+
+/// Returns the cache label, as the percentage of RAM used for the page cache and buffers and the
+/// amount of it.
+pub fn convert_cache_labels(
+    current_data: &data_farmer::DataCollection,
+) -> Option<(String, String)> {
+    let cache_in_kib = current_data.memory_harvest.cache_in_kib?;
+    let mem_total_in_kib = current_data.memory_harvest.mem_total_in_kib;
+    if mem_total_in_kib == 0 {
+        return None;
+    }
+
+    Some((
+        format!(
+            "{:3.0}%",
+            cache_in_kib as f64 / mem_total_in_kib as f64 * 100.0
+        ),
+        format!("   {}", format_memory_bytes(cache_in_kib * 1024)),
+    ))
+}
+
+/// Returns the shared memory label, as the percentage of RAM used for shared memory and the amount
+/// of it in use, including how much of it is in tmpfs filesystems.
+pub fn convert_shared_memory_labels(
+    current_data: &data_farmer::DataCollection,
+) -> Option<(String, String)> {
+    let shared_memory = current_data.shared_memory_harvest.as_ref()?;
+    let mem_total_in_kib = current_data.memory_harvest.mem_total_in_kib;
+    if mem_total_in_kib == 0 {
+        return None;
+    }
+
+    Some((
+        format!(
+            "{:3.0}%",
+            shared_memory.shmem_in_kib as f64 / mem_total_in_kib as f64 * 100.0
+        ),
+        format!(
+            "   {} ({} in tmpfs)",
+            format_memory_bytes(shared_memory.shmem_in_kib * 1024),
+            format_memory_bytes(shared_memory.tmpfs_used_in_kib * 1024)
+        ),
+    ))
+}
+
+/// Scales a network value (in bits) based on the unit type and the axis scaling.
+pub fn scale_network_value(
+    value: f64, network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool,
+) -> f64 {
+    match network_scale_type {
+        AxisScaling::Log => {
+            if network_use_binary_prefix {
+                match network_unit_type {
+                    DataUnit::Byte => {
+                        // As dividing by 8 is equal to subtracting 4 in base 2!
+                        value.log2() - 4.0
+                    }
+                    DataUnit::Bit => value.log2(),
+                }
+            } else {
+                match network_unit_type {
+                    DataUnit::Byte => (value / 8.0).log10(),
+                    DataUnit::Bit => value.log10(),
+                }
+            }
+        }
+        AxisScaling::Linear => match network_unit_type {
+            DataUnit::Byte => value / 8.0,
+            DataUnit::Bit => value,
+        },
+    }
+}
+
+/// Turns network data points on a linear scale into ones on a log scale.  The points are kept
+/// linear until drawing, as each network widget can switch between the two.
+pub fn get_log_network_points(
+    points: &[Point], network_unit_type: &DataUnit, network_use_binary_prefix: bool,
+) -> Vec<Point> {
+    points
+        .iter()
+        .map(|(time, value)| {
+            let bits = match network_unit_type {
+                DataUnit::Byte => value * 8.0,
+                DataUnit::Bit => *value,
+            };
+            (
+                *time,
+                scale_network_value(
+                    bits,
+                    &AxisScaling::Log,
+                    network_unit_type,
+                    network_use_binary_prefix,
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Returns the linearly scaled RX and TX data points.  If an interface is given, then only that
+/// interface's usage is used, otherwise the total usage is.
 pub fn get_rx_tx_data_points(
-    current_data: &data_farmer::DataCollection, is_frozen: bool, network_scale_type: &AxisScaling,
-    network_unit_type: &DataUnit, network_use_binary_prefix: bool,
+    current_data: &data_farmer::DataCollection, is_frozen: bool, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool, interface: Option<&str>,
 ) -> (Vec<Point>, Vec<Point>) {
     let mut rx: Vec<Point> = Vec::new();
     let mut tx: Vec<Point> = Vec::new();
 
-    let current_time = if is_frozen {
-        if let Some(frozen_instant) = current_data.frozen_instant {
-            frozen_instant
-        } else {
-            current_data.current_instant
-        }
-    } else {
-        current_data.current_instant
-    };
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
 
     for (time, data) in &current_data.timed_data_vec {
-        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
-
-        let (rx_data, tx_data) = match network_scale_type {
-            AxisScaling::Log => {
-                if network_use_binary_prefix {
-                    match network_unit_type {
-                        DataUnit::Byte => {
-                            // As dividing by 8 is equal to subtracting 4 in base 2!
-                            ((data.rx_data).log2() - 4.0, (data.tx_data).log2() - 4.0)
-                        }
-                        DataUnit::Bit => ((data.rx_data).log2(), (data.tx_data).log2()),
-                    }
-                } else {
-                    match network_unit_type {
-                        DataUnit::Byte => {
-                            ((data.rx_data / 8.0).log10(), (data.tx_data / 8.0).log10())
-                        }
-                        DataUnit::Bit => ((data.rx_data).log10(), (data.tx_data).log10()),
-                    }
-                }
-            }
-            AxisScaling::Linear => match network_unit_type {
-                DataUnit::Byte => (data.rx_data / 8.0, data.tx_data / 8.0),
-                DataUnit::Bit => (data.rx_data, data.tx_data),
-            },
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        let (rx_data, tx_data) = if let Some(interface) = interface {
+            data.interface_data
+                .get(interface)
+                .copied()
+                .unwrap_or((0.0, 0.0))
+        } else {
+            (data.rx_data, data.tx_data)
         };
 
-        rx.push((-time_from_start, rx_data));
-        tx.push((-time_from_start, tx_data));
-        if *time == current_time {
+        rx.push((
+            -time_from_start,
+            scale_network_value(
+                rx_data,
+                &AxisScaling::Linear,
+                network_unit_type,
+                network_use_binary_prefix,
+            ),
+        ));
+        tx.push((
+            -time_from_start,
+            scale_network_value(
+                tx_data,
+                &AxisScaling::Linear,
+                network_unit_type,
+                network_use_binary_prefix,
+            ),
+        ));
+        if *time == end_time {
             break;
         }
     }
@@ -421,17 +1500,131 @@ pub fn get_rx_tx_data_points(
     (rx, tx)
 }
 
+/// Returns a data point series and legend entry for each interface, where each point is the
+/// combined RX and TX of that interface.
+pub fn convert_interface_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool,
+) -> Vec<ConvertedInterfaceData> {
+    let current_time = current_data.get_graph_instant(is_frozen);
+    let end_time = current_data.get_graph_end_instant(is_frozen);
+
+    let unit = match network_unit_type {
+        DataUnit::Byte => "B/s",
+        DataUnit::Bit => "b/s",
+    };
+
+    let get_rate_string = |rate: u64| {
+        let rate = match network_unit_type {
+            DataUnit::Byte => rate / 8,
+            DataUnit::Bit => rate,
+        };
+        let (value, unit) = if network_use_binary_prefix {
+            get_binary_prefix(rate, unit)
+        } else {
+            get_decimal_prefix(rate, unit)
+        };
+        format!("{:.1}{}", value, unit)
+    };
+
+    current_data
+        .network_harvest
+        .interfaces
+        .iter()
+        .map(|interface| {
+            let mut points = Vec::new();
+            for (time, data) in &current_data.timed_data_vec {
+                let time_from_start: f64 =
+                    (current_time.duration_since(*time).as_millis() as f64).floor();
+                let (rx_data, tx_data) = data
+                    .interface_data
+                    .get(&interface.name)
+                    .copied()
+                    .unwrap_or((0.0, 0.0));
+
+                points.push((
+                    -time_from_start,
+                    scale_network_value(
+                        rx_data + tx_data,
+                        &AxisScaling::Linear,
+                        network_unit_type,
+                        network_use_binary_prefix,
+                    ),
+                ));
+                if *time == end_time {
+                    break;
+                }
+            }
+
+            let errors = interface.errors.total();
+            ConvertedInterfaceData {
+                name: interface.name.clone(),
+                points,
+                legend: if errors > 0 {
+                    format!(
+                        "{} RX: {} TX: {} Err: {}",
+                        interface.name,
+                        get_rate_string(interface.rx),
+                        get_rate_string(interface.tx),
+                        errors
+                    )
+                } else {
+                    format!(
+                        "{} RX: {} TX: {}",
+                        interface.name,
+                        get_rate_string(interface.rx),
+                        get_rate_string(interface.tx)
+                    )
+                },
+            }
+        })
+        .collect()
+}
+
 pub fn convert_network_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool, need_four_points: bool,
-    network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
-    network_use_binary_prefix: bool,
+    network_unit_type: &DataUnit, network_use_binary_prefix: bool, interface: Option<&str>,
 ) -> ConvertedNetworkData {
     let (rx, tx) = get_rx_tx_data_points(
         current_data,
         is_frozen,
-        network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
+        interface,
+    );
+
+    let (harvest_rx, harvest_tx, harvest_total_rx, harvest_total_tx, errors, are_errors_increased) =
+        if let Some(interface) = interface {
+            current_data
+                .network_harvest
+                .interfaces
+                .iter()
+                .find(|harvest| harvest.name == interface)
+                .map(|harvest| {
+                    (
+                        harvest.rx,
+                        harvest.tx,
+                        harvest.total_rx,
+                        harvest.total_tx,
+                        harvest.errors,
+                        harvest.errors_increased,
+                    )
+                })
+                .unwrap_or_default()
+        } else {
+            (
+                current_data.network_harvest.rx,
+                current_data.network_harvest.tx,
+                current_data.network_harvest.total_rx,
+                current_data.network_harvest.total_tx,
+                current_data.network_harvest.errors,
+                current_data.network_harvest.errors_increased,
+            )
+        };
+
+    let errors_display = format!(
+        "Err: {}/{} Drop: {}/{} Coll: {}",
+        errors.rx_errors, errors.tx_errors, errors.rx_drops, errors.tx_drops, errors.collisions
     );
 
     let unit = match network_unit_type {
@@ -441,16 +1634,16 @@ pub fn convert_network_data_points(
 
     let (rx_data, tx_data, total_rx_data, total_tx_data) = match network_unit_type {
         DataUnit::Byte => (
-            current_data.network_harvest.rx / 8,
-            current_data.network_harvest.tx / 8,
-            current_data.network_harvest.total_rx / 8,
-            current_data.network_harvest.total_tx / 8,
+            harvest_rx / 8,
+            harvest_tx / 8,
+            harvest_total_rx / 8,
+            harvest_total_tx / 8,
         ),
         DataUnit::Bit => (
-            current_data.network_harvest.rx,
-            current_data.network_harvest.tx,
-            current_data.network_harvest.total_rx / 8, // We always make this bytes...
-            current_data.network_harvest.total_tx / 8,
+            harvest_rx,
+            harvest_tx,
+            harvest_total_rx / 8, // We always make this bytes...
+            harvest_total_tx / 8,
         ),
     };
 
@@ -498,6 +1691,8 @@ pub fn convert_network_data_points(
             tx_display,
             total_rx_display,
             total_tx_display,
+            errors_display,
+            are_errors_increased,
         }
     } else {
         let rx_display = format!(
@@ -546,10 +1741,56 @@ pub fn convert_network_data_points(
             tx_display,
             total_rx_display: None,
             total_tx_display: None,
+            errors_display,
+            are_errors_increased,
         }
     }
 }
 
+/// Returns a legend entry for each wireless interface, or only the selected interface if there is one.
+pub fn convert_wireless_data(
+    current_data: &data_farmer::DataCollection, interface: Option<&str>,
+) -> Vec<String> {
+    current_data
+        .network_harvest
+        .wireless
+        .iter()
+        .filter(|wireless| interface.map_or(true, |interface| wireless.interface == interface))
+        .map(|wireless| {
+            let mut entries = vec![format!(
+                "{}: {}",
+                wireless.interface,
+                wireless.ssid.as_deref().unwrap_or("Not connected")
+            )];
+            if let Some(signal_dbm) = wireless.signal_dbm {
+                entries.push(format!("{:.0} dBm", signal_dbm));
+            }
+            if let Some(bitrate_mbps) = wireless.bitrate_mbps {
+                entries.push(format!("{:.1} Mb/s", bitrate_mbps));
+            }
+            if let Some(frequency_mhz) = wireless.frequency_mhz {
+                entries.push(format!("{:.2} GHz", frequency_mhz / 1000.0));
+            }
+
+            entries.join("  ")
+        })
+        .collect()
+}
+
+/// Returns a legend entry with the TCP socket counts, if they were collected.
+pub fn convert_tcp_counts_data(current_data: &data_farmer::DataCollection) -> Option<String> {
+    current_data
+        .network_harvest
+        .tcp_counts
+        .as_ref()
+        .map(|counts| {
+            format!(
+                "TCP: {} estab  {} time-wait  {} listen",
+                counts.established, counts.time_wait, counts.listen
+            )
+        })
+}
+
 pub enum ProcessGroupingType {
     Grouped,
     Ungrouped,
@@ -622,6 +1863,11 @@ pub fn convert_process_data(
         );
 
         let mem_usage_str = get_binary_bytes(process.mem_usage_bytes);
+        let cpu_history = current_data
+            .process_cpu_history
+            .get(&(process.pid, process.start_time))
+            .map(|history| history.iter().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
 
         let user = {
             #[cfg(target_family = "unix")]
@@ -638,6 +1884,21 @@ pub fn convert_process_data(
             }
         };
 
+        let group = {
+            #[cfg(target_family = "unix")]
+            {
+                if let Some(gid) = process.gid {
+                    user_table.get_gid_to_groupname_mapping(gid).ok()
+                } else {
+                    None
+                }
+            }
+            #[cfg(not(target_family = "unix"))]
+            {
+                None
+            }
+        };
+
         if let Some(process_entry) = existing_converted_process_data.get_mut(&process.pid) {
             complete_pid_set.remove(&process.pid);
 
@@ -646,6 +1907,7 @@ pub fn convert_process_data(
                 process_entry.name = process.name.to_string();
                 process_entry.command = process.command.to_string();
                 process_entry.cpu_percent_usage = process.cpu_usage_percent;
+                process_entry.cpu_history = cpu_history;
                 process_entry.mem_percent_usage = process.mem_usage_percent;
                 process_entry.mem_usage_bytes = process.mem_usage_bytes;
                 process_entry.mem_usage_str = mem_usage_str;
@@ -660,9 +1922,26 @@ pub fn convert_process_data(
                 process_entry.tw_f64 = process.total_write_bytes as f64;
                 process_entry.process_state = process.process_state.to_owned();
                 process_entry.process_char = process.process_state_char;
+                process_entry.is_kernel_thread = process.is_kernel_thread;
                 process_entry.process_description_prefix = None;
                 process_entry.is_disabled_entry = false;
                 process_entry.user = user;
+                process_entry.group = group;
+                process_entry.pss_bytes = process.pss_bytes;
+                process_entry.uss_bytes = process.uss_bytes;
+                process_entry.swap_bytes = process.swap_bytes;
+                process_entry.voluntary_switches_per_sec = process.voluntary_switches_per_sec;
+                process_entry.involuntary_switches_per_sec = process.involuntary_switches_per_sec;
+                process_entry.major_faults_per_sec = process.major_faults_per_sec;
+                process_entry.minor_faults_per_sec = process.minor_faults_per_sec;
+                process_entry.security_label = process.security_label.clone();
+                process_entry.effective_caps = process.effective_caps;
+                process_entry.io_priority = process.io_priority;
+                process_entry.start_time = process.start_time;
+                process_entry.cpu_time = process.cpu_time;
+                process_entry.open_fds = process.open_fds;
+                process_entry.gpu_percent_usage = process.gpu_usage_percent;
+                process_entry.container = process.container.clone();
             } else {
                 // ...I hate that I can't combine if let and an if statement in one line...
                 *process_entry = ConvertedProcessData {
@@ -672,6 +1951,7 @@ pub fn convert_process_data(
                     name: process.name.to_string(),
                     command: process.command.to_string(),
                     cpu_percent_usage: process.cpu_usage_percent,
+                    cpu_history,
                     mem_percent_usage: process.mem_usage_percent,
                     mem_usage_bytes: process.mem_usage_bytes,
                     mem_usage_str,
@@ -686,10 +1966,27 @@ pub fn convert_process_data(
                     tw_f64: process.total_write_bytes as f64,
                     process_state: process.process_state.to_owned(),
                     process_char: process.process_state_char,
+                    is_kernel_thread: process.is_kernel_thread,
                     process_description_prefix: None,
                     is_disabled_entry: false,
                     is_collapsed_entry: false,
                     user,
+                    group,
+                    pss_bytes: process.pss_bytes,
+                    uss_bytes: process.uss_bytes,
+                    swap_bytes: process.swap_bytes,
+                    voluntary_switches_per_sec: process.voluntary_switches_per_sec,
+                    involuntary_switches_per_sec: process.involuntary_switches_per_sec,
+                    major_faults_per_sec: process.major_faults_per_sec,
+                    minor_faults_per_sec: process.minor_faults_per_sec,
+                    security_label: process.security_label.clone(),
+                    effective_caps: process.effective_caps,
+                    io_priority: process.io_priority,
+                    start_time: process.start_time,
+                    cpu_time: process.cpu_time,
+                    open_fds: process.open_fds,
+                    gpu_percent_usage: process.gpu_usage_percent,
+                    container: process.container.clone(),
                 };
             }
         } else {
@@ -702,6 +1999,7 @@ pub fn convert_process_data(
                     name: process.name.to_string(),
                     command: process.command.to_string(),
                     cpu_percent_usage: process.cpu_usage_percent,
+                    cpu_history,
                     mem_percent_usage: process.mem_usage_percent,
                     mem_usage_bytes: process.mem_usage_bytes,
                     mem_usage_str,
@@ -716,10 +2014,27 @@ pub fn convert_process_data(
                     tw_f64: process.total_write_bytes as f64,
                     process_state: process.process_state.to_owned(),
                     process_char: process.process_state_char,
+                    is_kernel_thread: process.is_kernel_thread,
                     process_description_prefix: None,
                     is_disabled_entry: false,
                     is_collapsed_entry: false,
                     user,
+                    group,
+                    pss_bytes: process.pss_bytes,
+                    uss_bytes: process.uss_bytes,
+                    swap_bytes: process.swap_bytes,
+                    voluntary_switches_per_sec: process.voluntary_switches_per_sec,
+                    involuntary_switches_per_sec: process.involuntary_switches_per_sec,
+                    major_faults_per_sec: process.major_faults_per_sec,
+                    minor_faults_per_sec: process.minor_faults_per_sec,
+                    security_label: process.security_label.clone(),
+                    effective_caps: process.effective_caps,
+                    io_priority: process.io_priority,
+                    start_time: process.start_time,
+                    cpu_time: process.cpu_time,
+                    open_fds: process.open_fds,
+                    gpu_percent_usage: process.gpu_usage_percent,
+                    container: process.container.clone(),
                 },
             );
         }
@@ -898,6 +2213,15 @@ pub fn tree_process_data(
                     )
                 });
             }
+            ProcessSorting::CpuTrend => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.get_average_cpu_history(),
+                        b.1.get_average_cpu_history(),
+                        is_sort_descending,
+                    )
+                });
+            }
             ProcessSorting::Mem => {
                 to_sort_vec.sort_by(|a, b| {
                     utils::gen_util::get_ordering(
@@ -976,6 +2300,127 @@ pub fn tree_process_data(
                 (None, Some(_)) => std::cmp::Ordering::Greater,
                 (None, None) => std::cmp::Ordering::Less,
             }),
+            ProcessSorting::Group => to_sort_vec.sort_by(|a, b| match (&a.1.group, &b.1.group) {
+                (Some(group_a), Some(group_b)) => utils::gen_util::get_ordering(
+                    group_a.to_lowercase(),
+                    group_b.to_lowercase(),
+                    is_sort_descending,
+                ),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Less,
+            }),
+            ProcessSorting::Pss => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.pss_bytes, b.1.pss_bytes, is_sort_descending)
+                });
+            }
+            ProcessSorting::Uss => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.uss_bytes, b.1.uss_bytes, is_sort_descending)
+                });
+            }
+            ProcessSorting::Swap => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.swap_bytes,
+                        b.1.swap_bytes,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::VoluntarySwitches => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.voluntary_switches_per_sec,
+                        b.1.voluntary_switches_per_sec,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::InvoluntarySwitches => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.involuntary_switches_per_sec,
+                        b.1.involuntary_switches_per_sec,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::MajorFaults => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.major_faults_per_sec,
+                        b.1.major_faults_per_sec,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::MinorFaults => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.minor_faults_per_sec,
+                        b.1.minor_faults_per_sec,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::SecurityLabel => {
+                to_sort_vec.sort_by(|a, b| match (&a.1.security_label, &b.1.security_label) {
+                    (Some(label_a), Some(label_b)) => {
+                        utils::gen_util::get_ordering(label_a, label_b, is_sort_descending)
+                    }
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Less,
+                })
+            }
+            ProcessSorting::Capabilities => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.effective_caps,
+                        b.1.effective_caps,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::IoPriority => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.io_priority,
+                        b.1.io_priority,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::StartTime => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.start_time,
+                        b.1.start_time,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::CpuTime => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.cpu_time, b.1.cpu_time, is_sort_descending)
+                });
+            }
+            ProcessSorting::GpuPercent => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.gpu_percent_usage,
+                        b.1.gpu_percent_usage,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::OpenFds => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.open_fds, b.1.open_fds, is_sort_descending)
+                });
+            }
             ProcessSorting::Count => {
                 // Should never occur in this case, tree mode explicitly disables grouping.
             }
@@ -1207,71 +2652,97 @@ pub fn tree_process_data(
 pub fn stringify_process_data(
     proc_widget_state: &ProcWidgetState, finalized_process_data: &[ConvertedProcessData],
 ) -> Vec<(Vec<(String, Option<String>)>, bool)> {
-    let is_proc_widget_grouped = proc_widget_state.is_grouped;
-    let is_using_command = proc_widget_state.is_using_command;
     let is_tree = proc_widget_state.is_tree_mode;
-    let mem_enabled = proc_widget_state.columns.is_enabled(&ProcessSorting::Mem);
+    let shown_columns = proc_widget_state.columns.get_shown_columns();
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
 
     finalized_process_data
         .iter()
         .map(|process| {
-            (
-                vec![
-                    (
-                        if is_proc_widget_grouped {
-                            process.group_pids.len().to_string()
-                        } else {
-                            process.pid.to_string()
-                        },
-                        None,
-                    ),
-                    (
-                        if is_tree {
-                            if let Some(prefix) = &process.process_description_prefix {
-                                prefix.clone()
-                            } else {
-                                String::default()
-                            }
-                        } else if is_using_command {
-                            process.command.clone()
-                        } else {
-                            process.name.clone()
-                        },
-                        None,
-                    ),
-                    (format!("{:.1}%", process.cpu_percent_usage), None),
-                    (
-                        if mem_enabled {
-                            if process.mem_usage_bytes <= GIBI_LIMIT {
-                                format!("{:.0}{}", process.mem_usage_str.0, process.mem_usage_str.1)
-                            } else {
-                                format!("{:.1}{}", process.mem_usage_str.0, process.mem_usage_str.1)
-                            }
-                        } else {
-                            format!("{:.1}%", process.mem_percent_usage)
-                        },
-                        None,
-                    ),
-                    (process.read_per_sec.clone(), None),
-                    (process.write_per_sec.clone(), None),
-                    (process.total_read.clone(), None),
-                    (process.total_write.clone(), None),
-                    #[cfg(target_family = "unix")]
-                    (
-                        if let Some(user) = &process.user {
-                            user.clone()
+            let stringified_process = shown_columns
+                .iter()
+                .map(|column| {
+                    let entry = match column {
+                        ProcessSorting::Count => Some(process.group_pids.len().to_string()),
+                        ProcessSorting::Pid => Some(process.pid.to_string()),
+                        ProcessSorting::ProcessName | ProcessSorting::Command if is_tree => Some(
+                            process
+                                .process_description_prefix
+                                .clone()
+                                .unwrap_or_default(),
+                        ),
+                        ProcessSorting::ProcessName => Some(process.name.clone()),
+                        ProcessSorting::Command => Some(process.command.clone()),
+                        ProcessSorting::CpuPercent => {
+                            Some(format!("{:.1}%", process.cpu_percent_usage))
+                        }
+                        ProcessSorting::CpuTrend => {
+                            // Usage over 100% is possible with multiple cores.
+                            let highest = process.cpu_history.iter().copied().fold(100.0, f64::max);
+                            Some(get_sparkline(&process.cpu_history, 0.0, highest))
+                        }
+                        ProcessSorting::Mem => Some(if process.mem_usage_bytes <= GIBI_LIMIT {
+                            format!("{:.0}{}", process.mem_usage_str.0, process.mem_usage_str.1)
                         } else {
-                            "N/A".to_string()
-                        },
-                        None,
-                    ),
-                    (
-                        process.process_state.clone(),
-                        Some(process.process_char.to_string()),
-                    ),
-                ],
-                process.is_disabled_entry,
-            )
+                            format!("{:.1}{}", process.mem_usage_str.0, process.mem_usage_str.1)
+                        }),
+                        ProcessSorting::MemPercent => {
+                            Some(format!("{:.1}%", process.mem_percent_usage))
+                        }
+                        ProcessSorting::ReadPerSecond => Some(process.read_per_sec.clone()),
+                        ProcessSorting::WritePerSecond => Some(process.write_per_sec.clone()),
+                        ProcessSorting::TotalRead => Some(process.total_read.clone()),
+                        ProcessSorting::TotalWrite => Some(process.total_write.clone()),
+                        ProcessSorting::User => process.user.clone(),
+                        ProcessSorting::Group => process.group.clone(),
+                        ProcessSorting::State => {
+                            return (
+                                process.process_state.clone(),
+                                Some(process.process_char.to_string()),
+                            );
+                        }
+                        ProcessSorting::Pss => process.pss_bytes.map(format_memory_bytes),
+                        ProcessSorting::Uss => process.uss_bytes.map(format_memory_bytes),
+                        ProcessSorting::Swap => process.swap_bytes.map(format_memory_bytes),
+                        ProcessSorting::VoluntarySwitches => process
+                            .voluntary_switches_per_sec
+                            .map(|rate| rate.to_string()),
+                        ProcessSorting::InvoluntarySwitches => process
+                            .involuntary_switches_per_sec
+                            .map(|rate| rate.to_string()),
+                        ProcessSorting::MajorFaults => {
+                            process.major_faults_per_sec.map(|rate| rate.to_string())
+                        }
+                        ProcessSorting::MinorFaults => {
+                            process.minor_faults_per_sec.map(|rate| rate.to_string())
+                        }
+                        ProcessSorting::SecurityLabel => process.security_label.clone(),
+                        ProcessSorting::Capabilities => {
+                            process.effective_caps.map(|caps| format!("{:x}", caps))
+                        }
+                        ProcessSorting::IoPriority => {
+                            process.io_priority.map(|priority| priority.to_string())
+                        }
+                        ProcessSorting::StartTime => process
+                            .start_time
+                            .map(|start_time| format_start_time(start_time, current_time)),
+                        ProcessSorting::CpuTime => process.cpu_time.map(format_cpu_time),
+                        ProcessSorting::GpuPercent => process
+                            .gpu_percent_usage
+                            .map(|gpu_percent_usage| format!("{:.1}%", gpu_percent_usage)),
+                        ProcessSorting::OpenFds => {
+                            process.open_fds.map(|open_fds| open_fds.to_string())
+                        }
+                    };
+
+                    (entry.unwrap_or_else(|| "N/A".to_string()), None)
+                })
+                .collect::<Vec<_>>();
+
+            (stringified_process, process.is_disabled_entry)
         })
         .collect()
 }
@@ -1279,13 +2750,16 @@ pub fn stringify_process_data(
 /// Takes a set of converted process data and groups it together.
 ///
 /// To be honest, I really don't like how this is done, even though I've rewritten this like 3 times.
+/// Groups processes with the same name (or command), or with the same user if
+/// `is_grouped_by_user` is set, into single rows with summed usage.
 pub fn group_process_data(
-    single_process_data: &[ConvertedProcessData], is_using_command: bool,
+    single_process_data: &[ConvertedProcessData], is_using_command: bool, is_grouped_by_user: bool,
 ) -> Vec<ConvertedProcessData> {
     #[derive(Clone, Default, Debug)]
     struct SingleProcessData {
         pub pid: Pid,
         pub cpu_percent_usage: f64,
+        pub cpu_history: Vec<f64>,
         pub mem_percent_usage: f64,
         pub mem_usage_bytes: u64,
         pub group_pids: Vec<Pid>,
@@ -1294,13 +2768,26 @@ pub fn group_process_data(
         pub total_read: f64,
         pub total_write: f64,
         pub process_state: String,
+        pub pss_bytes: Option<u64>,
+        pub uss_bytes: Option<u64>,
+        pub swap_bytes: Option<u64>,
+        pub voluntary_switches_per_sec: Option<u64>,
+        pub involuntary_switches_per_sec: Option<u64>,
+        pub major_faults_per_sec: Option<u64>,
+        pub minor_faults_per_sec: Option<u64>,
+        pub start_time: Option<u64>,
+        pub cpu_time: Option<std::time::Duration>,
+        pub open_fds: Option<u64>,
+        pub gpu_percent_usage: Option<f64>,
     }
 
     let mut grouped_hashmap: HashMap<String, SingleProcessData> = std::collections::HashMap::new();
 
     single_process_data.iter().for_each(|process| {
         let entry = grouped_hashmap
-            .entry(if is_using_command {
+            .entry(if is_grouped_by_user {
+                process.user.clone().unwrap_or_else(|| "N/A".to_string())
+            } else if is_using_command {
                 process.command.to_string()
             } else {
                 process.name.to_string()
@@ -1311,6 +2798,19 @@ pub fn group_process_data(
             });
 
         (*entry).cpu_percent_usage += process.cpu_percent_usage;
+        // Histories are lined up by their latest readings, as processes may have started at
+        // different times.
+        if process.cpu_history.len() > entry.cpu_history.len() {
+            let missing_len = process.cpu_history.len() - entry.cpu_history.len();
+            entry.cpu_history.splice(0..0, vec![0.0; missing_len]);
+        }
+        let offset = entry.cpu_history.len() - process.cpu_history.len();
+        for (grouped_usage, usage) in entry.cpu_history[offset..]
+            .iter_mut()
+            .zip(&process.cpu_history)
+        {
+            *grouped_usage += usage;
+        }
         (*entry).mem_percent_usage += process.mem_percent_usage;
         (*entry).mem_usage_bytes += process.mem_usage_bytes;
         (*entry).group_pids.push(process.pid);
@@ -1318,6 +2818,50 @@ pub fn group_process_data(
         (*entry).write_per_sec += process.wps_f64;
         (*entry).total_read += process.tr_f64;
         (*entry).total_write += process.tw_f64;
+        if let Some(pss_bytes) = process.pss_bytes {
+            (*entry).pss_bytes = Some((*entry).pss_bytes.unwrap_or(0) + pss_bytes);
+        }
+        if let Some(uss_bytes) = process.uss_bytes {
+            (*entry).uss_bytes = Some((*entry).uss_bytes.unwrap_or(0) + uss_bytes);
+        }
+        if let Some(swap_bytes) = process.swap_bytes {
+            (*entry).swap_bytes = Some((*entry).swap_bytes.unwrap_or(0) + swap_bytes);
+        }
+        if let Some(voluntary_switches_per_sec) = process.voluntary_switches_per_sec {
+            (*entry).voluntary_switches_per_sec =
+                Some((*entry).voluntary_switches_per_sec.unwrap_or(0) + voluntary_switches_per_sec);
+        }
+        if let Some(involuntary_switches_per_sec) = process.involuntary_switches_per_sec {
+            (*entry).involuntary_switches_per_sec = Some(
+                (*entry).involuntary_switches_per_sec.unwrap_or(0) + involuntary_switches_per_sec,
+            );
+        }
+        if let Some(major_faults_per_sec) = process.major_faults_per_sec {
+            (*entry).major_faults_per_sec =
+                Some((*entry).major_faults_per_sec.unwrap_or(0) + major_faults_per_sec);
+        }
+        if let Some(minor_faults_per_sec) = process.minor_faults_per_sec {
+            (*entry).minor_faults_per_sec =
+                Some((*entry).minor_faults_per_sec.unwrap_or(0) + minor_faults_per_sec);
+        }
+        if let Some(start_time) = process.start_time {
+            // A group started when its earliest process did.
+            (*entry).start_time = Some(
+                (*entry)
+                    .start_time
+                    .map_or(start_time, |earliest| earliest.min(start_time)),
+            );
+        }
+        if let Some(cpu_time) = process.cpu_time {
+            (*entry).cpu_time = Some((*entry).cpu_time.unwrap_or_default() + cpu_time);
+        }
+        if let Some(open_fds) = process.open_fds {
+            (*entry).open_fds = Some((*entry).open_fds.unwrap_or(0) + open_fds);
+        }
+        if let Some(gpu_percent_usage) = process.gpu_percent_usage {
+            (*entry).gpu_percent_usage =
+                Some((*entry).gpu_percent_usage.unwrap_or(0.0) + gpu_percent_usage);
+        }
     });
 
     grouped_hashmap
@@ -1339,6 +2883,7 @@ pub fn group_process_data(
                 name: identifier.to_string(),
                 command: identifier.to_string(),
                 cpu_percent_usage: p.cpu_percent_usage,
+                cpu_history: p.cpu_history,
                 mem_percent_usage: p.mem_percent_usage,
                 mem_usage_bytes: p.mem_usage_bytes,
                 mem_usage_str: get_decimal_bytes(p.mem_usage_bytes),
@@ -1354,59 +2899,208 @@ pub fn group_process_data(
                 process_state: p.process_state,
                 process_description_prefix: None,
                 process_char: char::default(),
+                is_kernel_thread: false,
                 is_disabled_entry: false,
                 is_collapsed_entry: false,
-                user: None,
+                user: if is_grouped_by_user {
+                    Some(identifier.to_string())
+                } else {
+                    None
+                },
+                group: None,
+                pss_bytes: p.pss_bytes,
+                uss_bytes: p.uss_bytes,
+                swap_bytes: p.swap_bytes,
+                voluntary_switches_per_sec: p.voluntary_switches_per_sec,
+                involuntary_switches_per_sec: p.involuntary_switches_per_sec,
+                major_faults_per_sec: p.major_faults_per_sec,
+                minor_faults_per_sec: p.minor_faults_per_sec,
+                security_label: None,
+                effective_caps: None,
+                io_priority: None,
+                start_time: p.start_time,
+                cpu_time: p.cpu_time,
+                open_fds: p.open_fds,
+                gpu_percent_usage: p.gpu_percent_usage,
+                container: None,
             }
         })
         .collect::<Vec<_>>()
 }
 
+/// Formats a memory amount in the same way as the memory column of the process widget.
+fn format_memory_bytes(bytes: u64) -> String {
+    let (value, unit) = get_binary_bytes(bytes);
+    if bytes <= GIBI_LIMIT {
+        format!("{:.0}{}", value, unit)
+    } else {
+        format!("{:.1}{}", value, unit)
+    }
+}
+
+/// Formats a process start time like `ps`, as the time of day if it was within the last day, and
+/// as the date otherwise.
+fn format_start_time(start_time: u64, current_time: u64) -> String {
+    let local_time = time::OffsetDateTime::from_unix_timestamp(start_time as i64)
+        .map(|utc_time| utc_time.to_offset(utils::gen_util::get_local_offset(start_time as i64)));
+
+    match local_time {
+        Ok(local_time) if current_time.saturating_sub(start_time) < 24 * 60 * 60 => local_time
+            .format(&time::macros::format_description!("[hour]:[minute]"))
+            .unwrap_or_default(),
+        Ok(local_time) => local_time
+            .format(&time::macros::format_description!(
+                "[month repr:short] [day]"
+            ))
+            .unwrap_or_default(),
+        Err(_) => "N/A".to_string(),
+    }
+}
+
+/// Formats when an alert was raised as the local time of day, with seconds.
+fn format_alert_time(raised_time: u64) -> String {
+    time::OffsetDateTime::from_unix_timestamp(raised_time as i64)
+        .map(|utc_time| utc_time.to_offset(utils::gen_util::get_local_offset(raised_time as i64)))
+        .ok()
+        .and_then(|local_time| {
+            local_time
+                .format(&time::macros::format_description!(
+                    "[hour]:[minute]:[second]"
+                ))
+                .ok()
+        })
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Formats how long a terminal has been idle like `who -u`, as `.` if it was active in the last
+/// minute, `hours:minutes` if it was within a day, or `old` otherwise.
+fn format_idle_time(idle_secs: u64) -> String {
+    if idle_secs < 60 {
+        ".".to_string()
+    } else if idle_secs < 24 * 60 * 60 {
+        format!("{:02}:{:02}", idle_secs / 3600, idle_secs / 60 % 60)
+    } else {
+        "old".to_string()
+    }
+}
+
+/// Formats the CPU time of a process like the `TIME+` column of `top`, as `minutes:seconds.hundredths`.
+fn format_cpu_time(cpu_time: std::time::Duration) -> String {
+    let hundredths = cpu_time.as_millis() / 10;
+    format!(
+        "{}:{:02}.{:02}",
+        hundredths / 6000,
+        hundredths / 100 % 60,
+        hundredths % 100
+    )
+}
+
+#[cfg(feature = "battery")]
+fn format_battery_duration(secs: i64) -> String {
+    let time = time::Duration::seconds(secs);
+    let num_minutes = time.whole_minutes() - time.whole_hours() * 60;
+    let num_seconds = time.whole_seconds() - time.whole_minutes() * 60;
+    format!(
+        "{} hour{}, {} minute{}, {} second{}",
+        time.whole_hours(),
+        if time.whole_hours() == 1 { "" } else { "s" },
+        num_minutes,
+        if num_minutes == 1 { "" } else { "s" },
+        num_seconds,
+        if num_seconds == 1 { "" } else { "s" },
+    )
+}
+
+/// Estimates the seconds until the battery is full (if positive) or empty (if negative) from
+/// how the charge changed over the retained history.
+#[cfg(feature = "battery")]
+fn estimate_battery_secs(charge_data: &[Point]) -> Option<i64> {
+    /// The minimum span of history needed before trying to estimate, in milliseconds.
+    const MIN_ESTIMATE_SPAN: f64 = 60_000.0;
+
+    if let (Some((first_time, first_charge)), Some((last_time, last_charge))) =
+        (charge_data.first(), charge_data.last())
+    {
+        let span = last_time - first_time;
+        let rate = (last_charge - first_charge) / span; // Percent per millisecond
+        if span >= MIN_ESTIMATE_SPAN && rate.abs() > f64::EPSILON {
+            return Some(if rate > 0.0 {
+                ((100.0 - last_charge) / rate / 1000.0) as i64
+            } else {
+                -((last_charge / -rate / 1000.0) as i64)
+            });
+        }
+    }
+
+    None
+}
+
 #[cfg(feature = "battery")]
 pub fn convert_battery_harvest(
     current_data: &data_farmer::DataCollection,
 ) -> Vec<ConvertedBatteryData> {
+    let current_time = current_data.current_instant;
+
     current_data
         .battery_harvest
         .iter()
         .enumerate()
-        .map(|(itx, battery_harvest)| ConvertedBatteryData {
-            battery_name: format!("Battery {}", itx),
-            charge_percentage: battery_harvest.charge_percent,
-            watt_consumption: format!("{:.2}W", battery_harvest.power_consumption_rate_watts),
-            duration_until_empty: if let Some(secs_till_empty) = battery_harvest.secs_until_empty {
-                let time = time::Duration::seconds(secs_till_empty);
-                let num_minutes = time.whole_minutes() - time.whole_hours() * 60;
-                let num_seconds = time.whole_seconds() - time.whole_minutes() * 60;
-                Some(format!(
-                    "{} hour{}, {} minute{}, {} second{}",
-                    time.whole_hours(),
-                    if time.whole_hours() == 1 { "" } else { "s" },
-                    num_minutes,
-                    if num_minutes == 1 { "" } else { "s" },
-                    num_seconds,
-                    if num_seconds == 1 { "" } else { "s" },
-                ))
-            } else {
-                None
-            },
-            duration_until_full: if let Some(secs_till_full) = battery_harvest.secs_until_full {
-                let time = time::Duration::seconds(secs_till_full);
-                let num_minutes = time.whole_minutes() - time.whole_hours() * 60;
-                let num_seconds = time.whole_seconds() - time.whole_minutes() * 60;
-                Some(format!(
-                    "{} hour{}, {} minute{}, {} second{}",
-                    time.whole_hours(),
-                    if time.whole_hours() == 1 { "" } else { "s" },
-                    num_minutes,
-                    if num_minutes == 1 { "" } else { "s" },
-                    num_seconds,
-                    if num_seconds == 1 { "" } else { "s" },
-                ))
-            } else {
-                None
-            },
-            health: format!("{:.2}%", battery_harvest.health_percent),
+        .map(|(itx, battery_harvest)| {
+            let charge_data = current_data
+                .timed_data_vec
+                .iter()
+                .filter_map(|(time, data)| {
+                    data.battery_data.get(itx).map(|charge| {
+                        (
+                            -(current_time.duration_since(*time).as_millis() as f64).floor(),
+                            *charge,
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let (mut duration_until_full, mut duration_until_empty) = (
+                battery_harvest.secs_until_full.map(format_battery_duration),
+                battery_harvest
+                    .secs_until_empty
+                    .map(format_battery_duration),
+            );
+            if duration_until_full.is_none() && duration_until_empty.is_none() {
+                // Fall back to our own estimate if the battery doesn't report one.
+                match estimate_battery_secs(&charge_data) {
+                    Some(secs) if secs > 0 => {
+                        duration_until_full = Some(format!("~{}", format_battery_duration(secs)));
+                    }
+                    Some(secs) if secs < 0 => {
+                        duration_until_empty = Some(format!("~{}", format_battery_duration(-secs)));
+                    }
+                    _ => {}
+                }
+            }
+
+            ConvertedBatteryData {
+                battery_name: battery_harvest
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("Battery {}", itx)),
+                charge_percentage: battery_harvest.charge_percent,
+                watt_consumption: format!("{:.2}W", battery_harvest.power_consumption_rate_watts),
+                voltage: format!("{:.2}V", battery_harvest.voltage_volts),
+                duration_until_empty,
+                duration_until_full,
+                health: battery_harvest
+                    .health_percent
+                    .map(|health_percent| format!("{:.2}%", health_percent))
+                    .unwrap_or_else(|| "N/A".to_string()),
+                cycle_count: battery_harvest
+                    .cycle_count
+                    .map(|cycle_count| cycle_count.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                load: battery_harvest
+                    .load_percent
+                    .map(|load_percent| format!("{:.0}%", load_percent)),
+                charge_data,
+            }
         })
         .collect()
 }
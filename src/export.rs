@@ -0,0 +1,473 @@
+//! Exporting the harvested metrics to other tools, outside of the TUI.
+
+pub mod history;
+pub mod influxdb;
+pub mod json;
+pub mod metrics_log;
+pub mod prometheus;
+
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+
+use crate::{
+    app::{
+        data_farmer::DataCollection,
+        data_harvester::{temperature::TemperatureType, Data, DataCollector},
+        layout_manager::UsedWidgets,
+        AppConfigFields, DataFilters,
+    },
+    recording::Recorder,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// A value that can go up and down, like a usage percentage.
+    Gauge,
+    /// A total that only goes up, like the number of bytes received, until the system restarts.
+    Counter,
+}
+
+/// A single value of a metric, told apart from the other values of the metric by its labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+/// A named metric, along with all of its current values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: MetricKind,
+    pub samples: Vec<Sample>,
+}
+
+impl Metric {
+    fn new(name: &'static str, help: &'static str, kind: MetricKind) -> Self {
+        Metric {
+            name,
+            help,
+            kind,
+            samples: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, labels: Vec<(&'static str, String)>, value: f64) {
+        self.samples.push(Sample { labels, value });
+    }
+}
+
+/// The exporters and loggers that were asked for, which are fed every harvest.
+pub struct Exporters {
+    prometheus_exporter: Option<prometheus::PrometheusExporter>,
+    influxdb_exporter: Option<influxdb::InfluxDbExporter>,
+    metrics_logger: Option<metrics_log::MetricsLogger>,
+    recorder: Option<Recorder>,
+}
+
+impl Exporters {
+    /// Starts the Prometheus endpoint and the InfluxDB pushes, and opens the metrics log and the
+    /// recording, if they are set.
+    pub fn start(app_config_fields: &AppConfigFields) -> anyhow::Result<Self> {
+        // A replay shows data that is not the host's, so none of it is exported.
+        if app_config_fields.replay_path.is_some() {
+            return Ok(Exporters {
+                prometheus_exporter: None,
+                influxdb_exporter: None,
+                metrics_logger: None,
+                recorder: None,
+            });
+        }
+
+        let prometheus_exporter = match &app_config_fields.prometheus_address {
+            Some(address) => Some(prometheus::PrometheusExporter::start(address).with_context(
+                || format!("Unable to serve Prometheus metrics at '{}'.", address),
+            )?),
+            None => None,
+        };
+
+        let influxdb_exporter = app_config_fields
+            .influxdb
+            .clone()
+            .map(influxdb::InfluxDbExporter::start);
+
+        let metrics_logger = match &app_config_fields.metrics_log {
+            Some(metrics_log) => Some(
+                metrics_log::MetricsLogger::new(metrics_log.clone()).with_context(|| {
+                    format!(
+                        "Unable to open the metrics log at '{}'.",
+                        metrics_log.path.display()
+                    )
+                })?,
+            ),
+            None => None,
+        };
+
+        let recorder = match &app_config_fields.record_path {
+            Some(record_path) => Some(Recorder::open(record_path).with_context(|| {
+                format!(
+                    "Unable to open the recording at '{}'.",
+                    record_path.display()
+                )
+            })?),
+            None => None,
+        };
+
+        Ok(Exporters {
+            prometheus_exporter,
+            influxdb_exporter,
+            metrics_logger,
+            recorder,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prometheus_exporter.is_none()
+            && self.influxdb_exporter.is_none()
+            && self.metrics_logger.is_none()
+            && self.recorder.is_none()
+    }
+
+    /// Adds a harvest to the recording, if there is one.  This has to be done before the harvest is
+    /// eaten by the data collection.
+    pub fn record(&mut self, data: &Data) -> io::Result<()> {
+        match &mut self.recorder {
+            Some(recorder) => recorder.record(data),
+            None => Ok(()),
+        }
+    }
+
+    /// Passes the most recently harvested metrics on to every exporter and logger.  A failure of
+    /// one does not stop the others from being passed the metrics.
+    pub fn export(
+        &mut self, data_collection: &DataCollection, temperature_type: &TemperatureType,
+    ) -> io::Result<()> {
+        if self.prometheus_exporter.is_none()
+            && self.influxdb_exporter.is_none()
+            && self.metrics_logger.is_none()
+        {
+            return Ok(());
+        }
+
+        let metrics = get_metrics(data_collection, temperature_type);
+        let timestamp = get_unix_timestamp();
+        if let Some(prometheus_exporter) = &self.prometheus_exporter {
+            prometheus_exporter.update(&metrics);
+        }
+        let push_result = match &mut self.influxdb_exporter {
+            Some(influxdb_exporter) => influxdb_exporter.push(&metrics, timestamp),
+            None => Ok(()),
+        };
+        let log_result = match &mut self.metrics_logger {
+            Some(metrics_logger) => metrics_logger.log(&metrics, timestamp).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("unable to write the metrics log, {}", err),
+                )
+            }),
+            None => Ok(()),
+        };
+
+        push_result.and(log_result)
+    }
+}
+
+/// Returns the widgets to harvest the data of when there is no TUI, which is everything that
+/// [`get_metrics`] exports.
+pub fn get_exported_widgets() -> UsedWidgets {
+    UsedWidgets {
+        use_cpu: true,
+        use_mem: true,
+        use_net: true,
+        use_proc: true,
+        use_disk: true,
+        use_temp: true,
+        use_battery: true,
+        use_power: true,
+        ..UsedWidgets::default()
+    }
+}
+
+/// Returns the current Unix time in seconds, to stamp exported metrics with.
+pub fn get_unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Harvests everything that [`get_metrics`] exports once, outside of the collection thread.
+pub fn harvest_once(app_config_fields: &AppConfigFields, filters: DataFilters) -> DataCollection {
+    let mut data_state = DataCollector::new(filters);
+    data_state.set_collected_data(get_exported_widgets());
+    data_state.set_config_fields(app_config_fields);
+
+    // Rates like the CPU usage are measured from the harvest done while initializing.
+    data_state.init();
+    futures::executor::block_on(data_state.update_data());
+
+    let mut data_collection = DataCollection::default();
+    data_collection.eat_data(Box::new(data_state.data));
+    data_collection
+}
+
+/// Returns the most recently harvested metrics.  Metrics that were not harvested, as their widgets
+/// are not used, have no samples.
+pub fn get_metrics(
+    data_collection: &DataCollection, temperature_type: &TemperatureType,
+) -> Vec<Metric> {
+    use MetricKind::*;
+
+    let mut cpu_usage = Metric::new(
+        "bottom_cpu_usage_percent",
+        "The CPU usage of each core, or the average of all cores.",
+        Gauge,
+    );
+    let mut cpu_frequency = Metric::new(
+        "bottom_cpu_frequency_mhz",
+        "The current frequency of each core.",
+        Gauge,
+    );
+    for cpu in &data_collection.cpu_harvest {
+        let label = match cpu.cpu_count {
+            Some(cpu_count) => cpu_count.to_string(),
+            None => cpu.cpu_prefix.to_lowercase(),
+        };
+        cpu_usage.push(vec![("cpu", label.clone())], cpu.cpu_usage);
+        if let Some(frequency_mhz) = cpu.frequency_mhz {
+            cpu_frequency.push(vec![("cpu", label)], frequency_mhz);
+        }
+    }
+
+    let mut load_average = Metric::new(
+        "bottom_load_average",
+        "The load average over the last 1, 5, and 15 minutes.",
+        Gauge,
+    );
+    if !data_collection.cpu_harvest.is_empty() {
+        for (period, load) in ["1m", "5m", "15m"]
+            .iter()
+            .zip(data_collection.load_avg_harvest.iter())
+        {
+            load_average.push(vec![("period", period.to_string())], f64::from(*load));
+        }
+    }
+
+    let mut memory_used = Metric::new("bottom_memory_used_bytes", "The used RAM or swap.", Gauge);
+    let mut memory_total =
+        Metric::new("bottom_memory_total_bytes", "The total RAM or swap.", Gauge);
+    for (kind, harvest) in [
+        ("ram", &data_collection.memory_harvest),
+        ("swap", &data_collection.swap_harvest),
+    ] {
+        if harvest.mem_total_in_kib > 0 {
+            let labels = vec![("memory", kind.to_string())];
+            memory_used.push(labels.clone(), (harvest.mem_used_in_kib * 1024) as f64);
+            memory_total.push(labels, (harvest.mem_total_in_kib * 1024) as f64);
+        }
+    }
+
+    let network = &data_collection.network_harvest;
+    let mut network_rate = Metric::new(
+        "bottom_network_rate_bits",
+        "The bits received or transmitted per second over all interfaces that are not filtered out.",
+        Gauge,
+    );
+    let mut network_total = Metric::new(
+        "bottom_network_bits_total",
+        "The bits received or transmitted over all interfaces that are not filtered out.",
+        Counter,
+    );
+    let mut interface_rate = Metric::new(
+        "bottom_interface_rate_bits",
+        "The bits received or transmitted per second over each interface.",
+        Gauge,
+    );
+    let mut interface_total = Metric::new(
+        "bottom_interface_bits_total",
+        "The bits received or transmitted over each interface.",
+        Counter,
+    );
+    let mut interface_errors = Metric::new(
+        "bottom_interface_errors_total",
+        "The packets received or transmitted with errors over each interface.",
+        Counter,
+    );
+    if network.total_rx > 0 || network.total_tx > 0 {
+        for (direction, rate, total) in [
+            ("rx", network.rx, network.total_rx),
+            ("tx", network.tx, network.total_tx),
+        ] {
+            network_rate.push(vec![("direction", direction.to_string())], rate as f64);
+            network_total.push(vec![("direction", direction.to_string())], total as f64);
+        }
+    }
+    for interface in &network.interfaces {
+        for (direction, rate, total, errors) in [
+            (
+                "rx",
+                interface.rx,
+                interface.total_rx,
+                interface.errors.rx_errors,
+            ),
+            (
+                "tx",
+                interface.tx,
+                interface.total_tx,
+                interface.errors.tx_errors,
+            ),
+        ] {
+            let labels = vec![
+                ("interface", interface.name.clone()),
+                ("direction", direction.to_string()),
+            ];
+            interface_rate.push(labels.clone(), rate as f64);
+            interface_total.push(labels.clone(), total as f64);
+            interface_errors.push(labels, errors as f64);
+        }
+    }
+
+    let mut disk_used = Metric::new(
+        "bottom_disk_used_bytes",
+        "The used space of each mounted disk.",
+        Gauge,
+    );
+    let mut disk_total = Metric::new(
+        "bottom_disk_total_bytes",
+        "The total space of each mounted disk.",
+        Gauge,
+    );
+    for disk in &data_collection.disk_harvest {
+        let labels = vec![
+            ("disk", disk.name.clone()),
+            ("mount", disk.mount_point.clone()),
+        ];
+        if let Some(used_space) = disk.used_space {
+            disk_used.push(labels.clone(), used_space as f64);
+        }
+        if let Some(total_space) = disk.total_space {
+            disk_total.push(labels, total_space as f64);
+        }
+    }
+
+    let mut disk_io = Metric::new(
+        "bottom_disk_io_bytes_total",
+        "The bytes read from or written to each disk.",
+        Counter,
+    );
+    let mut io_harvest = data_collection.io_harvest.iter().collect::<Vec<_>>();
+    io_harvest.sort_by(|a, b| a.0.cmp(b.0));
+    for (device, io) in io_harvest {
+        if let Some(io) = io {
+            for (direction, bytes) in [("read", io.read_bytes), ("write", io.write_bytes)] {
+                disk_io.push(
+                    vec![
+                        ("device", device.clone()),
+                        ("direction", direction.to_string()),
+                    ],
+                    bytes as f64,
+                );
+            }
+        }
+    }
+
+    let mut temperature = Metric::new(
+        "bottom_temperature",
+        "The temperature of each sensor, in the unit that bottom is set to show.",
+        Gauge,
+    );
+    let unit = match temperature_type {
+        TemperatureType::Celsius => "celsius",
+        TemperatureType::Kelvin => "kelvin",
+        TemperatureType::Fahrenheit => "fahrenheit",
+    };
+    for sensor in &data_collection.temp_harvest {
+        temperature.push(
+            vec![("sensor", sensor.name.clone()), ("unit", unit.to_string())],
+            f64::from(sensor.temperature),
+        );
+    }
+
+    let mut power = Metric::new(
+        "bottom_power_watts",
+        "The power drawn by each power domain.",
+        Gauge,
+    );
+    for domain in &data_collection.power_harvest {
+        power.push(vec![("domain", domain.name.clone())], domain.watts);
+    }
+
+    let mut process_cpu = Metric::new(
+        "bottom_process_cpu_usage_percent",
+        "The CPU usage of each process.",
+        Gauge,
+    );
+    let mut process_memory = Metric::new(
+        "bottom_process_memory_bytes",
+        "The memory used by each process.",
+        Gauge,
+    );
+    for process in &data_collection.process_harvest {
+        let labels = vec![
+            ("pid", process.pid.to_string()),
+            ("name", process.name.clone()),
+        ];
+        process_cpu.push(labels.clone(), process.cpu_usage_percent);
+        process_memory.push(labels, process.mem_usage_bytes as f64);
+    }
+
+    #[allow(unused_mut)]
+    let mut metrics = vec![
+        cpu_usage,
+        cpu_frequency,
+        load_average,
+        memory_used,
+        memory_total,
+        network_rate,
+        network_total,
+        interface_rate,
+        interface_total,
+        interface_errors,
+        disk_used,
+        disk_total,
+        disk_io,
+        temperature,
+        power,
+        process_cpu,
+        process_memory,
+    ];
+
+    #[cfg(feature = "battery")]
+    {
+        let mut battery_charge = Metric::new(
+            "bottom_battery_charge_percent",
+            "The charge of each battery.",
+            Gauge,
+        );
+        let mut battery_power = Metric::new(
+            "bottom_battery_power_watts",
+            "The rate at which each battery is charging or discharging.",
+            Gauge,
+        );
+        for (index, battery) in data_collection.battery_harvest.iter().enumerate() {
+            let name = battery
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("Battery {}", index));
+            battery_charge.push(vec![("battery", name.clone())], battery.charge_percent);
+            battery_power.push(
+                vec![("battery", name)],
+                battery.power_consumption_rate_watts,
+            );
+        }
+        metrics.push(battery_charge);
+        metrics.push(battery_power);
+    }
+
+    metrics
+}
@@ -0,0 +1,369 @@
+//! Writing the history of a graph that is kept in memory to a CSV or JSON file, on demand from the
+//! TUI.
+
+use std::{
+    collections::BTreeSet,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    json::{format_number, format_string},
+    metrics_log::escape_csv_field,
+};
+use crate::{
+    app::{data_farmer::DataCollection, layout_manager::BottomWidgetType},
+    utils::{error::BottomError, gen_util},
+};
+
+/// The format that histories are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistoryFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl HistoryFormat {
+    fn get_extension(self) -> &'static str {
+        match self {
+            HistoryFormat::Csv => "csv",
+            HistoryFormat::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for HistoryFormat {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(HistoryFormat::Csv),
+            "json" => Ok(HistoryFormat::Json),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid history export format, use \"csv\" or \"json\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// The values of a graph over the time that its data is kept for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct History {
+    /// What the history is of, such as `cpu`, which the file is named after.
+    pub name: String,
+    /// The name of each value, along with its unit.
+    pub columns: Vec<String>,
+    /// The Unix time in milliseconds of each point, along with its value in each column, if any.
+    pub rows: Vec<(u64, Vec<Option<f64>>)>,
+}
+
+/// Returns the history of the graph that widgets of `widget_type` show up to `end_instant`, which
+/// is where the graph ends if it is frozen or scrolled back, or `None` if they do not show one.
+pub fn get_history(
+    data_collection: &DataCollection, widget_type: &BottomWidgetType, end_instant: Instant,
+) -> Option<History> {
+    let timed_data_vec = &data_collection.timed_data_vec[..data_collection
+        .timed_data_vec
+        .partition_point(|(instant, _timed_data)| *instant <= end_instant)];
+    let (name, columns, rows): (&str, Vec<String>, Vec<Vec<Option<f64>>>) = match widget_type {
+        BottomWidgetType::Cpu
+        | BottomWidgetType::CpuLegend
+        | BottomWidgetType::BasicCpu
+        | BottomWidgetType::CpuHeatmap => {
+            let columns = data_collection
+                .cpu_harvest
+                .iter()
+                .map(|cpu| match cpu.cpu_count {
+                    Some(cpu_count) => format!("cpu{}_percent", cpu_count),
+                    None => format!("{}_percent", cpu.cpu_prefix.to_lowercase()),
+                })
+                .collect::<Vec<_>>();
+            let rows = timed_data_vec
+                .iter()
+                .map(|(_instant, timed_data)| {
+                    (0..columns.len())
+                        .map(|index| timed_data.cpu_data.get(index).copied())
+                        .collect()
+                })
+                .collect();
+            ("cpu", columns, rows)
+        }
+        BottomWidgetType::Mem | BottomWidgetType::BasicMem => {
+            let columns = vec![
+                "ram_percent".to_string(),
+                "swap_percent".to_string(),
+                "cache_percent".to_string(),
+            ];
+            let rows = timed_data_vec
+                .iter()
+                .map(|(_instant, timed_data)| {
+                    vec![
+                        timed_data.mem_data,
+                        timed_data.swap_data,
+                        timed_data.cache_data,
+                    ]
+                })
+                .collect();
+            ("memory", columns, rows)
+        }
+        BottomWidgetType::Net | BottomWidgetType::BasicNet => {
+            let interfaces = timed_data_vec
+                .iter()
+                .flat_map(|(_instant, timed_data)| timed_data.interface_data.keys())
+                .collect::<BTreeSet<_>>();
+            let mut columns = vec![
+                "rx_bits_per_second".to_string(),
+                "tx_bits_per_second".to_string(),
+            ];
+            for interface in &interfaces {
+                columns.push(format!("{}_rx_bits_per_second", interface));
+                columns.push(format!("{}_tx_bits_per_second", interface));
+            }
+            let rows = timed_data_vec
+                .iter()
+                .map(|(_instant, timed_data)| {
+                    let mut row = vec![Some(timed_data.rx_data), Some(timed_data.tx_data)];
+                    for interface in &interfaces {
+                        let (rx, tx) = match timed_data.interface_data.get(*interface) {
+                            Some((rx, tx)) => (Some(*rx), Some(*tx)),
+                            None => (None, None),
+                        };
+                        row.push(rx);
+                        row.push(tx);
+                    }
+                    row
+                })
+                .collect();
+            ("network", columns, rows)
+        }
+        BottomWidgetType::Power => {
+            let domains = timed_data_vec
+                .iter()
+                .flat_map(|(_instant, timed_data)| timed_data.power_data.keys())
+                .collect::<BTreeSet<_>>();
+            let columns = domains
+                .iter()
+                .map(|domain| format!("{}_watts", domain))
+                .collect();
+            let rows = timed_data_vec
+                .iter()
+                .map(|(_instant, timed_data)| {
+                    domains
+                        .iter()
+                        .map(|domain| timed_data.power_data.get(*domain).copied())
+                        .collect()
+                })
+                .collect();
+            ("power", columns, rows)
+        }
+        #[cfg(feature = "battery")]
+        BottomWidgetType::Battery => {
+            let battery_count = timed_data_vec
+                .iter()
+                .map(|(_instant, timed_data)| timed_data.battery_data.len())
+                .max()
+                .unwrap_or_default();
+            let columns = (0..battery_count)
+                .map(|index| format!("battery{}_percent", index))
+                .collect();
+            let rows = timed_data_vec
+                .iter()
+                .map(|(_instant, timed_data)| {
+                    (0..battery_count)
+                        .map(|index| timed_data.battery_data.get(index).copied())
+                        .collect()
+                })
+                .collect();
+            ("battery", columns, rows)
+        }
+        _ => return None,
+    };
+
+    let now = Instant::now();
+    let now_millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    Some(History {
+        name: name.to_string(),
+        columns,
+        rows: timed_data_vec
+            .iter()
+            .map(|(instant, _timed_data)| {
+                now_millis
+                    .saturating_sub(now.saturating_duration_since(*instant).as_millis() as u64)
+            })
+            .zip(rows)
+            .collect(),
+    })
+}
+
+/// Formats a history as CSV, with a row for each point, which starts with its Unix time in
+/// milliseconds.  Missing values are left empty.
+pub fn format_csv(history: &History) -> String {
+    let mut text = String::from("timestamp");
+    for column in &history.columns {
+        let _ = write!(text, ",{}", escape_csv_field(column));
+    }
+    text.push('\n');
+
+    for (timestamp, values) in &history.rows {
+        let _ = write!(text, "{}", timestamp);
+        for value in values {
+            text.push(',');
+            if let Some(value) = value {
+                let _ = write!(text, "{}", value);
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Formats a history as a JSON list of an object for each point, which holds its Unix time in
+/// milliseconds under `timestamp`, and each of its values under the name of its column.  Missing
+/// values are left out.
+pub fn format_json(history: &History) -> String {
+    let mut text = String::from("[");
+    for (index, (timestamp, values)) in history.rows.iter().enumerate() {
+        if index > 0 {
+            text.push(',');
+        }
+        let _ = write!(text, "{{\"timestamp\":{}", timestamp);
+        for (column, value) in history.columns.iter().zip(values) {
+            if let Some(value) = value {
+                let _ = write!(text, ",{}:{}", format_string(column), format_number(*value));
+            }
+        }
+        text.push('}');
+    }
+    text.push_str("]\n");
+    text
+}
+
+/// Writes a history to a new file in `directory`, named after it and the local time, and returns
+/// the path of the file.  `prefix` goes at the start of the name, such as the remote host that the
+/// history is of.
+pub fn write_history(
+    history: &History, format: HistoryFormat, directory: &Path, prefix: Option<&str>,
+) -> io::Result<PathBuf> {
+    let name = match prefix {
+        Some(prefix) => format!("{}-{}", prefix, history.name),
+        None => history.name.clone(),
+    };
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let path = directory.join(format!(
+        "bottom-{}-history-{}.{}",
+        name,
+        gen_util::get_file_name_time(),
+        format.get_extension()
+    ));
+
+    let text = match format {
+        HistoryFormat::Csv => format_csv(history),
+        HistoryFormat::Json => format_json(history),
+    };
+    fs::write(&path, text)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::data_farmer::TimedData;
+
+    fn get_test_history() -> History {
+        History {
+            name: "memory".to_string(),
+            columns: vec!["ram_percent".to_string(), "swap_percent".to_string()],
+            rows: vec![
+                (1700000000000, vec![Some(50.5), None]),
+                (1700000001000, vec![Some(51.0), Some(f64::NAN)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_format_csv() {
+        assert_eq!(
+            format_csv(&get_test_history()),
+            "timestamp,ram_percent,swap_percent\n\
+             1700000000000,50.5,\n\
+             1700000001000,51,NaN\n"
+        );
+    }
+
+    #[test]
+    fn test_format_json() {
+        assert_eq!(
+            format_json(&get_test_history()),
+            "[{\"timestamp\":1700000000000,\"ram_percent\":50.5},\
+             {\"timestamp\":1700000001000,\"ram_percent\":51,\"swap_percent\":null}]\n"
+        );
+    }
+
+    #[test]
+    fn test_get_history() {
+        let mut data_collection = DataCollection::default();
+        let now = Instant::now();
+        for (offset, rx) in [(2, 100.0), (1, 200.0)] {
+            let mut timed_data = TimedData {
+                rx_data: rx,
+                ..TimedData::default()
+            };
+            if offset == 1 {
+                timed_data
+                    .interface_data
+                    .insert("eth0".to_string(), (rx, 0.0));
+            }
+            data_collection.timed_data_vec.push((
+                now.checked_sub(std::time::Duration::from_secs(offset))
+                    .unwrap_or(now),
+                timed_data,
+            ));
+        }
+
+        let history = get_history(&data_collection, &BottomWidgetType::Net, now).unwrap();
+        assert_eq!(history.name, "network");
+        assert_eq!(
+            history.columns,
+            vec![
+                "rx_bits_per_second",
+                "tx_bits_per_second",
+                "eth0_rx_bits_per_second",
+                "eth0_tx_bits_per_second"
+            ]
+        );
+        assert_eq!(history.rows.len(), 2);
+        assert_eq!(history.rows[0].1, vec![Some(100.0), Some(0.0), None, None]);
+        assert_eq!(
+            history.rows[1].1,
+            vec![Some(200.0), Some(0.0), Some(200.0), Some(0.0)]
+        );
+        assert!(history.rows[0].0 <= history.rows[1].0);
+
+        let history = get_history(
+            &data_collection,
+            &BottomWidgetType::Net,
+            now.checked_sub(std::time::Duration::from_millis(1500))
+                .unwrap_or(now),
+        )
+        .unwrap();
+        assert_eq!(history.rows.len(), 1);
+
+        assert!(get_history(&data_collection, &BottomWidgetType::Proc, now).is_none());
+    }
+}
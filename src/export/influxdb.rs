@@ -0,0 +1,281 @@
+//! Pushing the harvested metrics to InfluxDB, or anything else that takes the line protocol, at an
+//! interval of its own.  Metrics are sent with `curl`, so that HTTPS endpoints like InfluxDB Cloud
+//! work, from a thread of its own, so that a slow endpoint does not hold up refreshing.
+
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    process::{Command, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::Metric;
+use crate::utils::gen_util::quote_curl_config;
+
+/// How long to wait on the endpoint before giving up on a push.
+const PUSH_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluxDbOptions {
+    /// The endpoint to write to, like `http://localhost:8086/api/v2/write?org=me&bucket=bottom`.
+    pub url: String,
+    /// The token that is sent as `Authorization: Token <token>`, if any.
+    pub token: Option<String>,
+    /// How often to push the metrics.
+    pub interval: Duration,
+    /// The names of the metrics to push, or `None` to push all of them.
+    pub metrics: Option<Vec<String>>,
+    /// The tags that are added to every point, like the name of the host.
+    pub tags: Vec<(String, String)>,
+}
+
+/// Pushes the metrics passed to [`InfluxDbExporter::push`] to an endpoint, at most once per
+/// interval.
+pub struct InfluxDbExporter {
+    interval: Duration,
+    metrics: Option<Vec<String>>,
+    tags: Vec<(String, String)>,
+    last_push: Option<Instant>,
+    sender: mpsc::SyncSender<String>,
+    /// Why the last push failed, if it did and has not been reported yet.
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl InfluxDbExporter {
+    /// Starts the thread that pushes to the endpoint, which lives for as long as bottom.
+    pub fn start(options: InfluxDbOptions) -> Self {
+        // At most one push waits behind the one being sent, so that pushes do not pile up while the
+        // endpoint is slow or down.
+        let (sender, receiver) = mpsc::sync_channel::<String>(1);
+        let error = Arc::new(Mutex::new(None));
+
+        let thread_error = error.clone();
+        let InfluxDbOptions {
+            url,
+            token,
+            interval,
+            metrics,
+            tags,
+        } = options;
+        thread::spawn(move || {
+            for lines in receiver {
+                if let Err(err) = send_lines(&url, token.as_deref(), &lines) {
+                    if let Ok(mut error) = thread_error.lock() {
+                        *error = Some(err.to_string());
+                    }
+                }
+            }
+        });
+
+        InfluxDbExporter {
+            interval,
+            metrics,
+            tags,
+            last_push: None,
+            sender,
+            error,
+        }
+    }
+
+    /// Queues `metrics` to be pushed if the interval has passed since the last push, and returns
+    /// why the last push failed, if it did.
+    pub fn push(&mut self, metrics: &[Metric], timestamp: u64) -> io::Result<()> {
+        if self
+            .last_push
+            .is_none_or(|last_push| last_push.elapsed() >= self.interval)
+        {
+            self.last_push = Some(Instant::now());
+            let metrics = match &self.metrics {
+                Some(names) => metrics
+                    .iter()
+                    .filter(|metric| names.iter().any(|name| name == metric.name))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                None => metrics.to_vec(),
+            };
+            let lines = format_lines(&metrics, &self.tags, timestamp);
+            if !lines.is_empty() {
+                // If a push is already waiting, this one is dropped, and the next has newer metrics.
+                let _ = self.sender.try_send(lines);
+            }
+        }
+
+        match self.error.lock().ok().and_then(|mut error| error.take()) {
+            Some(error) => Err(io::Error::other(error)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Sends lines of the line protocol to `url`, and waits for them to be accepted.
+fn send_lines(url: &str, token: Option<&str>, lines: &str) -> io::Result<()> {
+    // The token and the lines are passed as a config on stdin, as anything in the arguments can be
+    // read by other users.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--max-time"])
+        .arg(PUSH_TIMEOUT_SECS.to_string())
+        .args(["--config", "-"])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| io::Error::new(err.kind(), format!("unable to run curl, {}", err)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(get_curl_config(token, lines).as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "unable to push metrics to {}, {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Returns the curl config that sends `lines`, with `token` in its headers if there is one.
+fn get_curl_config(token: Option<&str>, lines: &str) -> String {
+    let mut config = String::new();
+    let _ = writeln!(
+        config,
+        "header = {}",
+        quote_curl_config("Content-Type: text/plain; charset=utf-8")
+    );
+    if let Some(token) = token {
+        let _ = writeln!(
+            config,
+            "header = {}",
+            quote_curl_config(&format!("Authorization: Token {}", token))
+        );
+    }
+    let _ = writeln!(config, "data-binary = {}", quote_curl_config(lines));
+    config
+}
+
+/// Formats metrics as lines of the line protocol, with a point for each sample.  Each point is
+/// named after its metric, tagged with its labels and `tags`, and holds its value in the `value`
+/// field.  `timestamp` is in seconds, and is written in nanoseconds, which is what endpoints expect
+/// by default.  Values that are not finite are left out, as the line protocol has no way to write
+/// them.
+pub fn format_lines(metrics: &[Metric], tags: &[(String, String)], timestamp: u64) -> String {
+    let mut text = String::new();
+    for metric in metrics {
+        for sample in metric
+            .samples
+            .iter()
+            .filter(|sample| sample.value.is_finite())
+        {
+            text.push_str(&escape(metric.name, &[',', ' ']));
+            let sample_tags = sample
+                .labels
+                .iter()
+                .map(|(name, value)| (*name, value.as_str()))
+                .chain(
+                    tags.iter()
+                        .map(|(name, value)| (name.as_str(), value.as_str())),
+                );
+            for (name, value) in sample_tags {
+                // Tags cannot be empty.
+                if !value.is_empty() {
+                    let _ = write!(
+                        text,
+                        ",{}={}",
+                        escape(name, &[',', '=', ' ']),
+                        escape(value, &[',', '=', ' '])
+                    );
+                }
+            }
+            let _ = writeln!(
+                text,
+                " value={} {}",
+                sample.value,
+                u128::from(timestamp) * 1_000_000_000
+            );
+        }
+    }
+    text
+}
+
+/// Escapes `special` characters and backslashes with a backslash.  Line breaks cannot be escaped,
+/// so they are replaced with spaces first.
+fn escape(text: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        let c = if c == '\n' || c == '\r' { ' ' } else { c };
+        if c == '\\' || special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::export::{MetricKind, Sample};
+
+    #[test]
+    fn test_format_lines() {
+        let metrics = vec![
+            Metric {
+                name: "bottom_disk_used_bytes",
+                help: "The used space of each mounted disk.",
+                kind: MetricKind::Gauge,
+                samples: vec![
+                    Sample {
+                        labels: vec![
+                            ("disk", "/dev/sda1".to_string()),
+                            ("mount", "/mnt/my disk,a=b".to_string()),
+                        ],
+                        value: 1024.0,
+                    },
+                    Sample {
+                        labels: vec![("disk", "".to_string()), ("mount", "/".to_string())],
+                        value: 0.5,
+                    },
+                ],
+            },
+            Metric {
+                name: "bottom_temperature",
+                help: "The temperature of each sensor.",
+                kind: MetricKind::Gauge,
+                samples: vec![Sample {
+                    labels: vec![("sensor", "acpi".to_string())],
+                    value: f64::NAN,
+                }],
+            },
+        ];
+
+        assert_eq!(
+            format_lines(
+                &metrics,
+                &[("host".to_string(), "web 1".to_string())],
+                1700000000
+            ),
+            "bottom_disk_used_bytes,disk=/dev/sda1,mount=/mnt/my\\ disk\\,a\\=b,host=web\\ 1 value=1024 1700000000000000000\n\
+             bottom_disk_used_bytes,mount=/,host=web\\ 1 value=0.5 1700000000000000000\n"
+        );
+    }
+
+    #[test]
+    fn test_curl_config() {
+        assert_eq!(
+            get_curl_config(Some("abc\"def"), "cpu,host=a\\ b value=1 0\n"),
+            "header = \"Content-Type: text/plain; charset=utf-8\"\n\
+             header = \"Authorization: Token abc\\\"def\"\n\
+             data-binary = \"cpu,host=a\\\\ b value=1 0\\n\"\n"
+        );
+        assert_eq!(
+            get_curl_config(None, ""),
+            "header = \"Content-Type: text/plain; charset=utf-8\"\n\
+             data-binary = \"\"\n"
+        );
+    }
+}
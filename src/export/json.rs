@@ -0,0 +1,118 @@
+//! Formatting the harvested metrics as JSON, for scripts.
+
+use std::fmt::Write as _;
+
+use super::Metric;
+
+/// Formats metrics as a JSON object, with the Unix time that they were harvested at under
+/// `timestamp`, and each metric under `metrics` as a list of its samples.  A sample is an object of
+/// its labels along with its `value`.  Metrics without any samples are left out.
+pub fn format_metrics(metrics: &[Metric], timestamp: u64) -> String {
+    let mut text = String::new();
+    let _ = write!(text, "{{\"timestamp\":{},\"metrics\":{{", timestamp);
+
+    for (index, metric) in metrics
+        .iter()
+        .filter(|metric| !metric.samples.is_empty())
+        .enumerate()
+    {
+        if index > 0 {
+            text.push(',');
+        }
+        let _ = write!(text, "{}:[", format_string(metric.name));
+
+        for (index, sample) in metric.samples.iter().enumerate() {
+            if index > 0 {
+                text.push(',');
+            }
+            text.push('{');
+            for (name, value) in &sample.labels {
+                let _ = write!(text, "{}:{},", format_string(name), format_string(value));
+            }
+            let _ = write!(text, "\"value\":{}}}", format_number(sample.value));
+        }
+
+        text.push(']');
+    }
+
+    text.push_str("}}");
+    text
+}
+
+pub(crate) fn format_string(string: &str) -> String {
+    let mut res = String::with_capacity(string.len() + 2);
+    res.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(res, "\\u{:04x}", c as u32);
+            }
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+/// JSON has no NaN or infinity, so those are written as `null`.
+pub(crate) fn format_number(number: f64) -> String {
+    if number.is_finite() {
+        number.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::export::{MetricKind, Sample};
+
+    #[test]
+    fn test_format_metrics() {
+        let metrics = vec![
+            Metric {
+                name: "bottom_load_average",
+                help: "The load average.",
+                kind: MetricKind::Gauge,
+                samples: vec![
+                    Sample {
+                        labels: vec![("period", "1m".to_string())],
+                        value: 0.5,
+                    },
+                    Sample {
+                        labels: vec![("period", "5m".to_string())],
+                        value: f64::NAN,
+                    },
+                ],
+            },
+            Metric {
+                name: "bottom_power_watts",
+                help: "The power drawn by each power domain.",
+                kind: MetricKind::Gauge,
+                samples: vec![],
+            },
+            Metric {
+                name: "bottom_temperature",
+                help: "The temperature of each sensor.",
+                kind: MetricKind::Gauge,
+                samples: vec![Sample {
+                    labels: vec![("sensor", "acpi\t\"1\"".to_string())],
+                    value: 40.0,
+                }],
+            },
+        ];
+
+        assert_eq!(
+            format_metrics(&metrics, 1000),
+            "{\"timestamp\":1000,\"metrics\":{\
+             \"bottom_load_average\":[{\"period\":\"1m\",\"value\":0.5},{\"period\":\"5m\",\"value\":null}],\
+             \"bottom_temperature\":[{\"sensor\":\"acpi\\t\\\"1\\\"\",\"value\":40}]}}"
+        );
+    }
+}
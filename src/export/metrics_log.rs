@@ -0,0 +1,248 @@
+//! Appending the harvested metrics to a CSV or JSON lines file on every refresh, rotating the file
+//! once it grows too large.
+
+use std::{
+    fmt::Write as _,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{json, Metric};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsLogFormat {
+    /// One `timestamp,metric,labels,value` row per sample.
+    Csv,
+    /// One JSON object per refresh, in the same shape as `--dump`.
+    JsonLines,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsLogOptions {
+    pub path: PathBuf,
+    pub format: MetricsLogFormat,
+    /// The names of the metrics to log, or `None` to log all of them.
+    pub metrics: Option<Vec<String>>,
+    /// The size in bytes past which the file is rotated, if any.
+    pub max_size: Option<u64>,
+    /// How many rotated files to keep, as `<path>.1` (the newest) to `<path>.<max_files>`.
+    pub max_files: usize,
+}
+
+pub struct MetricsLogger {
+    options: MetricsLogOptions,
+    file: File,
+    size: u64,
+}
+
+impl MetricsLogger {
+    /// Opens the log file for appending, creating it if needed.
+    pub fn new(options: MetricsLogOptions) -> io::Result<Self> {
+        let (file, size) = open_log_file(&options.path)?;
+        Ok(MetricsLogger {
+            options,
+            file,
+            size,
+        })
+    }
+
+    /// Appends the metrics that were asked for to the log file, first rotating it if it is too
+    /// large.
+    pub fn log(&mut self, metrics: &[Metric], timestamp: u64) -> io::Result<()> {
+        if let Some(max_size) = self.options.max_size {
+            if self.size >= max_size {
+                self.rotate()?;
+            }
+        }
+
+        let metrics = match &self.options.metrics {
+            Some(names) => metrics
+                .iter()
+                .filter(|metric| names.iter().any(|name| name == metric.name))
+                .cloned()
+                .collect::<Vec<_>>(),
+            None => metrics.to_vec(),
+        };
+
+        let mut text = String::new();
+        match self.options.format {
+            MetricsLogFormat::Csv => {
+                if self.size == 0 {
+                    text.push_str("timestamp,metric,labels,value\n");
+                }
+                for metric in &metrics {
+                    for sample in &metric.samples {
+                        let labels = sample
+                            .labels
+                            .iter()
+                            .map(|(name, value)| format!("{}={}", name, value))
+                            .collect::<Vec<_>>()
+                            .join(";");
+                        let _ = writeln!(
+                            text,
+                            "{},{},{},{}",
+                            timestamp,
+                            metric.name,
+                            escape_csv_field(&labels),
+                            sample.value
+                        );
+                    }
+                }
+            }
+            MetricsLogFormat::JsonLines => {
+                text.push_str(&json::format_metrics(&metrics, timestamp));
+                text.push('\n');
+            }
+        }
+
+        self.file.write_all(text.as_bytes())?;
+        self.size += text.len() as u64;
+        Ok(())
+    }
+
+    /// Shifts `<path>.1` to `<path>.2` and so on, dropping the oldest, then moves the current file
+    /// to `<path>.1` and starts a new one.
+    fn rotate(&mut self) -> io::Result<()> {
+        let path = &self.options.path;
+        if self.options.max_files == 0 {
+            fs::remove_file(path)?;
+        } else {
+            let oldest_path = get_rotated_path(path, self.options.max_files);
+            if oldest_path.exists() {
+                fs::remove_file(oldest_path)?;
+            }
+            for index in (1..self.options.max_files).rev() {
+                let from = get_rotated_path(path, index);
+                if from.exists() {
+                    fs::rename(from, get_rotated_path(path, index + 1))?;
+                }
+            }
+            fs::rename(path, get_rotated_path(path, 1))?;
+        }
+
+        let (file, size) = open_log_file(path)?;
+        self.file = file;
+        self.size = size;
+        Ok(())
+    }
+}
+
+fn open_log_file(path: &Path) -> io::Result<(File, u64)> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let size = file.metadata()?.len();
+    Ok((file, size))
+}
+
+fn get_rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated_path = path.as_os_str().to_owned();
+    rotated_path.push(format!(".{}", index));
+    PathBuf::from(rotated_path)
+}
+
+pub(crate) fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::export::{MetricKind, Sample};
+
+    fn get_test_metrics() -> Vec<Metric> {
+        vec![
+            Metric {
+                name: "bottom_load_average",
+                help: "The load average.",
+                kind: MetricKind::Gauge,
+                samples: vec![Sample {
+                    labels: vec![("period", "1m".to_string())],
+                    value: 0.5,
+                }],
+            },
+            Metric {
+                name: "bottom_disk_used_bytes",
+                help: "The used space of each mounted disk.",
+                kind: MetricKind::Gauge,
+                samples: vec![Sample {
+                    labels: vec![
+                        ("disk", "/dev/sda1".to_string()),
+                        ("mount", "/mnt/a,b".to_string()),
+                    ],
+                    value: 1024.0,
+                }],
+            },
+        ]
+    }
+
+    fn get_test_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "bottom_metrics_log_test_{}_{}",
+            std::process::id(),
+            name
+        ));
+        for index in 1..3 {
+            let _ = fs::remove_file(get_rotated_path(&path, index));
+        }
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_csv_log() {
+        let path = get_test_path("csv");
+        let mut logger = MetricsLogger::new(MetricsLogOptions {
+            path: path.clone(),
+            format: MetricsLogFormat::Csv,
+            metrics: None,
+            max_size: None,
+            max_files: 0,
+        })
+        .unwrap();
+        logger.log(&get_test_metrics(), 10).unwrap();
+        logger.log(&get_test_metrics(), 20).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "timestamp,metric,labels,value\n\
+             10,bottom_load_average,period=1m,0.5\n\
+             10,bottom_disk_used_bytes,\"disk=/dev/sda1;mount=/mnt/a,b\",1024\n\
+             20,bottom_load_average,period=1m,0.5\n\
+             20,bottom_disk_used_bytes,\"disk=/dev/sda1;mount=/mnt/a,b\",1024\n"
+        );
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_json_lines_log_rotation() {
+        let path = get_test_path("jsonl");
+        let mut logger = MetricsLogger::new(MetricsLogOptions {
+            path: path.clone(),
+            format: MetricsLogFormat::JsonLines,
+            metrics: Some(vec!["bottom_load_average".to_string()]),
+            max_size: Some(1),
+            max_files: 1,
+        })
+        .unwrap();
+        logger.log(&get_test_metrics(), 10).unwrap();
+        logger.log(&get_test_metrics(), 20).unwrap();
+        logger.log(&get_test_metrics(), 30).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{\"timestamp\":30,\"metrics\":{\"bottom_load_average\":[{\"period\":\"1m\",\"value\":0.5}]}}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(get_rotated_path(&path, 1)).unwrap(),
+            "{\"timestamp\":20,\"metrics\":{\"bottom_load_average\":[{\"period\":\"1m\",\"value\":0.5}]}}\n"
+        );
+        assert!(!get_rotated_path(&path, 2).exists());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(get_rotated_path(&path, 1));
+    }
+}
@@ -0,0 +1,256 @@
+//! A small HTTP server that exposes the harvested metrics in the Prometheus text format, so that
+//! bottom can be scraped while it runs.
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{Metric, MetricKind};
+
+/// How long a scraper has to send its whole request, and how long to wait on it to read.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The most that is read of a request, which is far more than scrapers send.
+const MAX_REQUEST_LEN: u64 = 8 * 1024;
+
+/// How many scrapers are served at once, past which they are turned away.
+const MAX_CONNECTIONS: usize = 8;
+
+/// Serves the metrics last passed to [`PrometheusExporter::update`] at `/metrics`.
+pub struct PrometheusExporter {
+    metrics_text: Arc<Mutex<String>>,
+}
+
+impl PrometheusExporter {
+    /// Starts listening at `address` on a thread of its own, which lives for as long as bottom.
+    pub fn start(address: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let metrics_text = Arc::new(Mutex::new(String::new()));
+
+        let thread_metrics_text = metrics_text.clone();
+        thread::spawn(move || {
+            let connection_count = Arc::new(AtomicUsize::new(0));
+            for stream in listener.incoming().flatten() {
+                if connection_count.load(Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    reject_connection(stream);
+                    continue;
+                }
+
+                // Each scraper is served on a thread of its own, so that a slow one does not hold
+                // up the others.
+                connection_count.fetch_add(1, Ordering::SeqCst);
+                let connection_count = connection_count.clone();
+                let metrics_text = thread_metrics_text.clone();
+                thread::spawn(move || {
+                    // A scraper that goes away mid-request is not worth reporting.
+                    let _ = handle_connection(stream, &metrics_text);
+                    connection_count.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        Ok(PrometheusExporter { metrics_text })
+    }
+
+    /// Replaces the metrics that are served with `metrics`.
+    pub fn update(&self, metrics: &[Metric]) {
+        let text = format_metrics(metrics);
+        if let Ok(mut metrics_text) = self.metrics_text.lock() {
+            *metrics_text = text;
+        }
+    }
+}
+
+/// Reads from a stream until a deadline, past which reads fail as timed out.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        let mut stream = self.stream;
+        stream.set_read_timeout(Some(remaining))?;
+        stream.read(buf)
+    }
+}
+
+/// Tells a scraper that there are too many others, without waiting on it.
+fn reject_connection(stream: TcpStream) {
+    if stream.set_nonblocking(true).is_ok() {
+        let _ = (&stream).write_all(
+            b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+    }
+}
+
+fn handle_connection(stream: TcpStream, metrics_text: &Mutex<String>) -> io::Result<()> {
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    // The request is read up to a limit and a deadline, so that a scraper that trickles it in
+    // can neither use up memory nor hold its thread forever.
+    let mut reader = BufReader::new(
+        DeadlineReader {
+            stream: &stream,
+            deadline: Instant::now() + CONNECTION_TIMEOUT,
+        }
+        .take(MAX_REQUEST_LEN),
+    );
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // The headers are not needed, but are read so that the scraper is not cut off mid-request.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            metrics_text
+                .lock()
+                .map(|metrics_text| metrics_text.clone())
+                .unwrap_or_default(),
+        ),
+        (Some("GET"), Some("/")) => (
+            "200 OK",
+            "text/html; charset=utf-8",
+            "<html><body><a href=\"/metrics\">Metrics</a></body></html>".to_string(),
+        ),
+        (Some("GET"), _) => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "Not found\n".to_string(),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "Method not allowed\n".to_string(),
+        ),
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Formats metrics in the Prometheus text format.  Metrics without any samples are left out.
+pub fn format_metrics(metrics: &[Metric]) -> String {
+    let mut text = String::new();
+
+    for metric in metrics.iter().filter(|metric| !metric.samples.is_empty()) {
+        let _ = writeln!(text, "# HELP {} {}", metric.name, metric.help);
+        let _ = writeln!(
+            text,
+            "# TYPE {} {}",
+            metric.name,
+            match metric.kind {
+                MetricKind::Gauge => "gauge",
+                MetricKind::Counter => "counter",
+            }
+        );
+
+        for sample in &metric.samples {
+            text.push_str(metric.name);
+            if !sample.labels.is_empty() {
+                let labels = sample
+                    .labels
+                    .iter()
+                    .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+                    .collect::<Vec<_>>();
+                let _ = write!(text, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(text, " {}", format_value(sample.value));
+        }
+    }
+
+    text
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::export::Sample;
+
+    #[test]
+    fn test_format_metrics() {
+        let metrics = vec![
+            Metric {
+                name: "bottom_temperature",
+                help: "The temperature of each sensor.",
+                kind: MetricKind::Gauge,
+                samples: vec![Sample {
+                    labels: vec![
+                        ("sensor", "nvme0: \"Composite\"".to_string()),
+                        ("unit", "celsius".to_string()),
+                    ],
+                    value: 41.5,
+                }],
+            },
+            Metric {
+                name: "bottom_power_watts",
+                help: "The power drawn by each power domain.",
+                kind: MetricKind::Gauge,
+                samples: vec![],
+            },
+            Metric {
+                name: "bottom_network_bits_total",
+                help: "The bits received or transmitted.",
+                kind: MetricKind::Counter,
+                samples: vec![Sample {
+                    labels: vec![],
+                    value: 1024.0,
+                }],
+            },
+        ];
+
+        assert_eq!(
+            format_metrics(&metrics),
+            "# HELP bottom_temperature The temperature of each sensor.\n\
+             # TYPE bottom_temperature gauge\n\
+             bottom_temperature{sensor=\"nvme0: \\\"Composite\\\"\",unit=\"celsius\"} 41.5\n\
+             # HELP bottom_network_bits_total The bits received or transmitted.\n\
+             # TYPE bottom_network_bits_total counter\n\
+             bottom_network_bits_total 1024\n"
+        );
+    }
+}
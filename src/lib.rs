@@ -30,9 +30,11 @@ use crossterm::{
 };
 
 use app::{
+    alert_commands,
     data_harvester::{self, processes::ProcessSorting},
     layout_manager::{UsedWidgets, WidgetDirection},
-    App,
+    metric_alerts::{AlertSeverity, AlertTransition},
+    notifications, App, GlobalFilterUsage,
 };
 use constants::*;
 use data_conversion::*;
@@ -49,7 +51,10 @@ pub mod canvas;
 pub mod clap;
 pub mod constants;
 pub mod data_conversion;
+pub mod export;
 pub mod options;
+pub mod recording;
+pub mod remote;
 pub mod units;
 
 #[cfg(target_family = "windows")]
@@ -63,7 +68,15 @@ pub enum BottomEvent<I, J> {
     KeyInput(I),
     MouseInput(J),
     Update(Box<data_harvester::Data>),
+    /// The data of the remote host at the index.
+    HostUpdate(usize, Box<data_harvester::Data>),
     Clean,
+    RemoteError(String),
+    /// The frames of a recording that come before the one being moved to, which replace all of
+    /// the collected data.
+    ReplayReset(Vec<Box<data_harvester::Data>>),
+    /// A message about the replay to show, and whether it is an error.
+    ReplayStatus(String, bool),
 }
 
 #[derive(Debug)]
@@ -72,12 +85,15 @@ pub enum ThreadControlEvent {
     UpdateConfig(Box<app::AppConfigFields>),
     UpdateUsedWidgets(Box<UsedWidgets>),
     UpdateUpdateTime(u64),
+    UpdateThreadPid(Option<Pid>),
+    UpdateFilters(Box<app::DataFilters>),
+    Replay(recording::ReplayControl),
 }
 
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
     match event.kind {
-        MouseEventKind::ScrollUp => app.handle_scroll_up(),
-        MouseEventKind::ScrollDown => app.handle_scroll_down(),
+        MouseEventKind::ScrollUp => app.handle_scroll_up(event.column, event.row),
+        MouseEventKind::ScrollDown => app.handle_scroll_down(event.column, event.row),
         MouseEventKind::Down(button) => {
             if !app.app_config_fields.disable_click {
                 match button {
@@ -90,6 +106,12 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                 }
             }
         }
+        MouseEventKind::Drag(crossterm::event::MouseButton::Left)
+            if !app.app_config_fields.disable_click =>
+        {
+            app.on_left_mouse_drag(event.column)
+        }
+        MouseEventKind::Up(crossterm::event::MouseButton::Left) => app.on_left_mouse_release(),
         _ => {}
     };
 }
@@ -106,7 +128,13 @@ pub fn handle_key_event_or_break(
 
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
+        if event.code == KeyCode::Char('q')
+            && !app.is_in_search_widget()
+            && !app.is_in_conn_search()
+            && !app.is_in_disk_search()
+            && !app.is_in_signal_search()
+            && !app.global_filter.is_editing
+        {
             return true;
         }
         match event.code {
@@ -125,6 +153,7 @@ pub fn handle_key_event_or_break(
             KeyCode::F(1) => app.toggle_ignore_case(),
             KeyCode::F(2) => app.toggle_search_whole_word(),
             KeyCode::F(3) => app.toggle_search_regex(),
+            KeyCode::F(4) => app.toggle_search_fuzzy(),
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort(),
             KeyCode::F(9) => app.start_killing_process(),
@@ -139,6 +168,7 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_ignore_case(),
                 KeyCode::Char('w') | KeyCode::Char('W') => app.toggle_search_whole_word(),
                 KeyCode::Char('r') | KeyCode::Char('R') => app.toggle_search_regex(),
+                KeyCode::Char('f') | KeyCode::Char('F') => app.toggle_search_fuzzy(),
                 KeyCode::Char('h') => app.on_left_key(),
                 KeyCode::Char('l') => app.on_right_key(),
                 _ => {}
@@ -159,6 +189,9 @@ pub fn handle_key_event_or_break(
                         app.reset();
                     }
                 }
+                KeyCode::Char('s') => app.save_settings(),
+                KeyCode::Char('t') => app.cycle_colour_scheme(),
+                KeyCode::Char('p') => app.request_snapshot(),
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
                 KeyCode::Char('e') => app.skip_cursor_end(),
                 KeyCode::Char('u') => app.clear_search(),
@@ -185,6 +218,48 @@ pub fn handle_key_event_or_break(
         }
     }
 
+    // Let the collection thread know if the process we want the threads of has changed.
+    if app.thread_dialog_state.is_pid_outdated {
+        let thread_pid = app
+            .thread_dialog_state
+            .process
+            .as_ref()
+            .map(|(pid, _name)| *pid);
+        if reset_sender
+            .send(ThreadControlEvent::UpdateThreadPid(thread_pid))
+            .is_ok()
+        {
+            app.thread_dialog_state.is_pid_outdated = false;
+        }
+    }
+
+    // Likewise if editing the layout has changed which widgets are used.
+    if app.layout_edit_state.is_used_widgets_outdated
+        && reset_sender
+            .send(ThreadControlEvent::UpdateUsedWidgets(Box::new(
+                app.used_widgets.clone(),
+            )))
+            .is_ok()
+    {
+        app.layout_edit_state.is_used_widgets_outdated = false;
+    }
+
+    // Likewise if the filters dialog has hidden or shown a mount, sensor, or interface.
+    if app.is_filters_outdated
+        && reset_sender
+            .send(ThreadControlEvent::UpdateFilters(Box::new(
+                app.filters.clone(),
+            )))
+            .is_ok()
+    {
+        app.is_filters_outdated = false;
+    }
+
+    // Likewise if a key has moved through the recording being replayed.
+    if let Some(replay_control) = app.replay_control.take() {
+        let _ = reset_sender.send(ThreadControlEvent::Replay(replay_control));
+    }
+
     false
 }
 
@@ -305,6 +380,60 @@ pub fn panic_hook(panic_info: &PanicInfo<'_>) {
     .unwrap();
 }
 
+/// Acts on the alert rules that started or stopped alerting in the latest update.
+pub fn handle_alert_transitions(app: &mut App, transitions: &[AlertTransition]) {
+    for transition in transitions {
+        // Replayed alerts are only shown, as they are not happening now.
+        let command_error = if app.app_config_fields.replay_path.is_none() {
+            alert_commands::run_alert_command(&app.metric_alerts, transition).err()
+        } else {
+            None
+        };
+
+        let rule = &app.metric_alerts.get_rules()[transition.rule_index];
+        if transition.is_firing {
+            let mut text = match transition.value {
+                Some(value) => format!(
+                    "Alert: {} ({})",
+                    rule.name,
+                    app.metric_alerts
+                        .describe_value(transition.rule_index, value)
+                ),
+                None => format!("Alert: {}", rule.name),
+            };
+            let mut is_error = rule.severity >= AlertSeverity::Warning;
+            if let Some(err) = &command_error {
+                text = format!("{}; its command failed: {}", text, err);
+                is_error = true;
+            }
+
+            if rule.notify {
+                let title = format!("bottom: {}", rule.name);
+                let body = match transition.value {
+                    Some(value) => format!(
+                        "{} (rule: {})",
+                        app.metric_alerts
+                            .describe_value(transition.rule_index, value),
+                        app.metric_alerts.describe_rule(transition.rule_index)
+                    ),
+                    None => app.metric_alerts.describe_rule(transition.rule_index),
+                };
+                // The alert is still shown, along with why it could not be sent.
+                if let Err(err) = notifications::send_notification(&title, &body, rule.severity) {
+                    text = format!("{}; unable to send a desktop notification: {}", text, err);
+                    is_error = true;
+                }
+            }
+            app.show_toast(text, is_error);
+        } else if let Some(err) = command_error {
+            app.show_toast(
+                format!("The command of alert {} failed: {}", rule.name, err),
+                true,
+            );
+        }
+    }
+}
+
 pub fn handle_force_redraws(app: &mut App) {
     // Currently we use an Option... because we might want to future-proof this
     // if we eventually get widget-specific redrawing!
@@ -322,29 +451,207 @@ pub fn handle_force_redraws(app: &mut App) {
             &mut app.canvas_data.cpu_data,
             app.is_frozen,
         );
+        let (cpu_group_data, cpu_legend_entries) = convert_cpu_groups(
+            &app.data_collection,
+            &app.canvas_data.cpu_data,
+            app.cpu_state.grouping,
+        );
+        app.canvas_data.cpu_group_data = cpu_group_data;
+        app.canvas_data.cpu_legend_entries = cpu_legend_entries;
+        if app.app_config_fields.cpu_frequency_graph {
+            app.canvas_data.cpu_frequency_data =
+                convert_cpu_frequency_points(&app.data_collection, app.is_frozen);
+        }
         app.canvas_data.load_avg_data = app.data_collection.load_avg_harvest;
         app.cpu_state.force_update = None;
     }
 
+    if app.cpu_state.force_update.is_some() || app.mem_state.force_update.is_some() {
+        for host_index in 0..app.remote_hosts.len() {
+            update_remote_host_data(app, host_index);
+        }
+    }
+
+    if app.power_state.force_update.is_some() {
+        app.canvas_data.power_data = convert_power_data_points(&app.data_collection, app.is_frozen);
+        app.power_state.force_update = None;
+    }
+
     // FIXME: [OPT] Prefer reassignment over new vectors?
     if app.mem_state.force_update.is_some() {
         app.canvas_data.mem_data = convert_mem_data_points(&app.data_collection, app.is_frozen);
         app.canvas_data.swap_data = convert_swap_data_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.stacked_mem_data =
+            convert_stacked_mem_data_points(&app.data_collection, app.is_frozen);
         app.mem_state.force_update = None;
     }
 
     if app.net_state.force_update.is_some() {
-        let (rx, tx) = get_rx_tx_data_points(
+        update_network_data(app);
+        app.net_state.force_update = None;
+    }
+
+    if app.disk_state.force_update.is_some() {
+        update_disk_data(app);
+        app.is_force_redraw = true;
+        app.disk_state.force_update = None;
+    }
+
+    if let Some(widget_id) = app.conn_state.force_update {
+        update_connection_data(app, widget_id);
+        app.conn_state.force_update = None;
+    }
+}
+
+/// Converts the CPU and memory data of the remote host at `host_index` for its widgets.
+pub fn update_remote_host_data(app: &mut App, host_index: usize) {
+    let is_frozen = app.is_frozen;
+    let grouping = app.cpu_state.grouping;
+    let cpu_frequency_graph = app.app_config_fields.cpu_frequency_graph;
+    let host = match app.remote_hosts.get_mut(host_index) {
+        Some(host) => host,
+        None => return,
+    };
+    let data_collection = &host.data_collection;
+    let canvas_data = &mut host.canvas_data;
+
+    convert_cpu_data_points(data_collection, &mut canvas_data.cpu_data, is_frozen);
+    let (cpu_group_data, cpu_legend_entries) =
+        convert_cpu_groups(data_collection, &canvas_data.cpu_data, grouping);
+    canvas_data.cpu_group_data = cpu_group_data;
+    canvas_data.cpu_legend_entries = cpu_legend_entries;
+    if cpu_frequency_graph {
+        canvas_data.cpu_frequency_data = convert_cpu_frequency_points(data_collection, is_frozen);
+    }
+    canvas_data.load_avg_data = data_collection.load_avg_harvest;
+
+    canvas_data.mem_data = convert_mem_data_points(data_collection, is_frozen);
+    canvas_data.swap_data = convert_swap_data_points(data_collection, is_frozen);
+    canvas_data.stacked_mem_data = convert_stacked_mem_data_points(data_collection, is_frozen);
+    let (memory_labels, swap_labels) = convert_mem_labels(data_collection);
+    canvas_data.mem_labels = memory_labels;
+    canvas_data.swap_labels = swap_labels;
+    canvas_data.huge_pages_labels = convert_huge_pages_labels(data_collection);
+    canvas_data.shared_memory_labels = convert_shared_memory_labels(data_collection);
+    canvas_data.cache_labels = convert_cache_labels(data_collection);
+}
+
+pub fn update_connection_data(app: &mut App, widget_id: u64) {
+    let connection_data = convert_connection_rows(app, widget_id);
+
+    // Keep the selection in bounds if the list shrunk, i.e. due to searching.
+    if let Some(conn_widget_state) = app.conn_state.get_mut_widget_state(widget_id) {
+        let scroll_state = &mut conn_widget_state.scroll_state;
+        if scroll_state.current_scroll_position >= connection_data.len() {
+            scroll_state.current_scroll_position = connection_data.len().saturating_sub(1);
+            scroll_state.previous_scroll_position = 0;
+            scroll_state.scroll_direction = app::ScrollDirection::Up;
+        }
+    }
+
+    app.canvas_data
+        .connection_data
+        .insert(widget_id, connection_data);
+}
+
+pub fn update_disk_data(app: &mut App) {
+    let widget_ids = app
+        .disk_state
+        .widget_states
+        .keys()
+        .copied()
+        .collect::<Vec<_>>();
+
+    for widget_id in widget_ids {
+        let disk_data = convert_disk_row(app, widget_id);
+
+        // Keep the selection in bounds if a disk was unmounted, removed, or filtered out.
+        if let Some(disk_widget_state) = app.disk_state.get_mut_widget_state(widget_id) {
+            let scroll_state = &mut disk_widget_state.scroll_state;
+            if scroll_state.current_scroll_position >= disk_data.len() {
+                scroll_state.current_scroll_position = disk_data.len().saturating_sub(1);
+                scroll_state.previous_scroll_position = 0;
+                scroll_state.scroll_direction = app::ScrollDirection::Up;
+            }
+        }
+
+        app.canvas_data.disk_data.insert(widget_id, disk_data);
+    }
+
+    // The column widths depend on the disks shown, so recalculate them if the list changed.
+    if app.data_collection.are_disks_changed {
+        app.is_force_redraw = true;
+    }
+}
+
+pub fn update_all_connection_data(app: &mut App) {
+    let widget_ids = app
+        .conn_state
+        .widget_states
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for widget_id in widget_ids {
+        update_connection_data(app, widget_id);
+    }
+}
+
+/// Updates the kernel log rows.  Widgets that had the last message selected keep following the
+/// end of the log as new messages come in.
+pub fn update_kernel_log_data(app: &mut App) {
+    let previous_len = app.canvas_data.kernel_log_data.len();
+    let kernel_log_data = convert_kernel_log_rows(app);
+
+    for kernel_log_widget_state in app.kernel_log_state.widget_states.values_mut() {
+        let scroll_state = &mut kernel_log_widget_state.scroll_state;
+        if scroll_state.current_scroll_position + 1 >= previous_len {
+            scroll_state.current_scroll_position = kernel_log_data.len().saturating_sub(1);
+            scroll_state.scroll_direction = app::ScrollDirection::Down;
+        }
+    }
+
+    app.canvas_data.kernel_log_data = kernel_log_data;
+}
+
+pub fn update_network_data(app: &mut App) {
+    let network_data = convert_network_data_points(
+        &app.data_collection,
+        app.is_frozen,
+        app.app_config_fields.use_basic_mode || app.app_config_fields.use_old_network_legend,
+        &app.app_config_fields.network_unit_type,
+        app.app_config_fields.network_use_binary_prefix,
+        app.net_state.selected_interface.as_deref(),
+    );
+    app.canvas_data.network_data_rx = network_data.rx;
+    app.canvas_data.network_data_tx = network_data.tx;
+    app.canvas_data.rx_display = network_data.rx_display;
+    app.canvas_data.tx_display = network_data.tx_display;
+    app.canvas_data.net_errors_display = network_data.errors_display;
+    app.canvas_data.are_net_errors_increased = network_data.are_errors_increased;
+    if let Some(total_rx_display) = network_data.total_rx_display {
+        app.canvas_data.total_rx_display = total_rx_display;
+    }
+    if let Some(total_tx_display) = network_data.total_tx_display {
+        app.canvas_data.total_tx_display = total_tx_display;
+    }
+
+    app.canvas_data.wireless_display = convert_wireless_data(
+        &app.data_collection,
+        app.net_state.selected_interface.as_deref(),
+    );
+    app.canvas_data.tcp_counts_display = convert_tcp_counts_data(&app.data_collection);
+
+    app.canvas_data.network_interface_data = if app.net_state.is_per_interface {
+        convert_interface_data_points(
             &app.data_collection,
             app.is_frozen,
-            &app.app_config_fields.network_scale_type,
             &app.app_config_fields.network_unit_type,
             app.app_config_fields.network_use_binary_prefix,
-        );
-        app.canvas_data.network_data_rx = rx;
-        app.canvas_data.network_data_tx = tx;
-        app.net_state.force_update = None;
-    }
+        )
+    } else {
+        Vec::new()
+    };
 }
 
 #[allow(clippy::needless_collect)]
@@ -362,7 +669,41 @@ pub fn update_all_process_lists(app: &mut App) {
         widget_ids.into_iter().for_each(|widget_id| {
             update_final_process_list(app, widget_id);
         });
+
+        update_global_filter_usage(app);
+    }
+}
+
+/// Adds up how much the processes matching the global filter use, if there is one.
+fn update_global_filter_usage(app: &mut App) {
+    let query = match &app.global_filter.query {
+        Some(query) => query,
+        None => return,
+    };
+
+    // The process widgets would have converted the processes already.
+    if app.proc_state.widget_states.is_empty() {
+        convert_process_data(
+            &app.data_collection,
+            &mut app.canvas_data.single_process_data,
+            #[cfg(target_family = "unix")]
+            &mut app.user_table,
+        );
     }
+
+    let usage = app
+        .canvas_data
+        .single_process_data
+        .values()
+        .filter(|process| query.check(process, false))
+        .fold(GlobalFilterUsage::default(), |usage, process| {
+            GlobalFilterUsage {
+                cpu_percent: usage.cpu_percent + process.cpu_percent_usage,
+                mem_percent: usage.mem_percent + process.mem_percent_usage,
+                mem_bytes: usage.mem_bytes + process.mem_usage_bytes,
+            }
+        });
+    app.global_filter.usage = Some(usage);
 }
 
 fn update_final_process_list(app: &mut App, widget_id: u64) {
@@ -378,11 +719,21 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                     .is_invalid_or_blank_search(),
                 process_state.is_using_command,
                 process_state.is_grouped,
+                process_state.is_grouped_by_user,
                 process_state.is_tree_mode,
+                process_state.is_hiding_kernel_threads,
             )
         });
 
-    if let Some((is_invalid_or_blank, is_using_command, is_grouped, is_tree)) = process_states {
+    if let Some((
+        is_invalid_or_blank,
+        is_using_command,
+        is_grouped,
+        is_grouped_by_user,
+        is_tree,
+        is_hiding_kernel_threads,
+    )) = process_states
+    {
         if !app.is_frozen {
             convert_process_data(
                 &app.data_collection,
@@ -392,18 +743,23 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
             );
         }
         let process_filter = app.get_process_filter(widget_id);
+        let global_filter = &app.global_filter.query;
+        let is_matching = |process: &ConvertedProcessData| {
+            global_filter.as_ref().map_or(true, |global_filter| {
+                global_filter.check(process, is_using_command)
+            }) && (is_invalid_or_blank
+                || process_filter.as_ref().map_or(true, |process_filter| {
+                    process_filter.check(process, is_using_command)
+                }))
+        };
         let filtered_process_data: Vec<ConvertedProcessData> = if is_tree {
             app.canvas_data
                 .single_process_data
                 .iter()
+                .filter(|(_pid, process)| !(is_hiding_kernel_threads && process.is_kernel_thread))
                 .map(|(_pid, process)| {
                     let mut process_clone = process.clone();
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            process_clone.is_disabled_entry =
-                                !process_filter.check(&process_clone, is_using_command);
-                        }
-                    }
+                    process_clone.is_disabled_entry = !is_matching(&process_clone);
                     process_clone
                 })
                 .collect::<Vec<_>>()
@@ -412,16 +768,10 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                 .single_process_data
                 .iter()
                 .filter_map(|(_pid, process)| {
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            if process_filter.check(process, is_using_command) {
-                                Some(process)
-                            } else {
-                                None
-                            }
-                        } else {
-                            Some(process)
-                        }
+                    if (is_hiding_kernel_threads && process.is_kernel_thread)
+                        || !is_matching(process)
+                    {
+                        None
                     } else {
                         Some(process)
                     }
@@ -431,6 +781,21 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
         };
 
         if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+            // Remember which process is selected before the rows change, by PID and start time so
+            // that a reused PID is not mistaken for it.  Groups have no single PID, so they are
+            // remembered by name instead.
+            let followed_process = if proc_widget_state.follow_mode == app::FollowMode::Off {
+                None
+            } else {
+                app.canvas_data
+                    .finalized_process_data_map
+                    .get(&widget_id)
+                    .and_then(|process_data| {
+                        process_data.get(proc_widget_state.scroll_state.current_scroll_position)
+                    })
+                    .map(|process| (process.pid, process.start_time, process.name.clone()))
+            };
+
             let mut finalized_process_data = if is_tree {
                 tree_process_data(
                     &filtered_process_data,
@@ -439,7 +804,7 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                     proc_widget_state.is_process_sort_descending,
                 )
             } else if is_grouped {
-                group_process_data(&filtered_process_data, is_using_command)
+                group_process_data(&filtered_process_data, is_using_command, is_grouped_by_user)
             } else {
                 filtered_process_data
             };
@@ -447,6 +812,39 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
             // Note tree mode is sorted well before this, as it's special.
             if !is_tree {
                 sort_process_data(&mut finalized_process_data, proc_widget_state);
+
+                // Fuzzy matches are ranked by how well they match, and otherwise keep their order.
+                if !is_invalid_or_blank
+                    && proc_widget_state
+                        .process_search_state
+                        .is_searching_with_fuzzy
+                {
+                    if let Some(query) = &proc_widget_state.process_search_state.search_state.query
+                    {
+                        finalized_process_data.sort_by_cached_key(|process| {
+                            std::cmp::Reverse(query.fuzzy_score(process, is_using_command))
+                        });
+                    }
+                }
+            }
+
+            if let Some((pid, start_time, name)) = followed_process {
+                if let Some(position) = finalized_process_data.iter().position(|process| {
+                    if is_grouped {
+                        process.name == name
+                    } else {
+                        process.pid == pid && process.start_time == start_time
+                    }
+                }) {
+                    // Pinning would break up the tree, so it only follows in tree mode.
+                    if proc_widget_state.follow_mode == app::FollowMode::Pinned && !is_tree {
+                        let process = finalized_process_data.remove(position);
+                        finalized_process_data.insert(0, process);
+                        proc_widget_state.scroll_state.current_scroll_position = 0;
+                    } else {
+                        proc_widget_state.scroll_state.current_scroll_position = position;
+                    }
+                }
             }
 
             if proc_widget_state.scroll_state.current_scroll_position
@@ -484,6 +882,15 @@ fn sort_process_data(
                 )
             });
         }
+        ProcessSorting::CpuTrend => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.get_average_cpu_history(),
+                    b.get_average_cpu_history(),
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
         ProcessSorting::Mem => {
             to_sort_vec.sort_by(|a, b| {
                 utils::gen_util::get_ordering(
@@ -580,6 +987,145 @@ fn sort_process_data(
             (None, Some(_)) => std::cmp::Ordering::Greater,
             (None, None) => std::cmp::Ordering::Less,
         }),
+        ProcessSorting::Group => to_sort_vec.sort_by(|a, b| match (&a.group, &b.group) {
+            (Some(group_a), Some(group_b)) => utils::gen_util::get_ordering(
+                group_a.to_lowercase(),
+                group_b.to_lowercase(),
+                proc_widget_state.is_process_sort_descending,
+            ),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Less,
+        }),
+        ProcessSorting::Pss => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.pss_bytes,
+                    b.pss_bytes,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::Uss => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.uss_bytes,
+                    b.uss_bytes,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::Swap => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.swap_bytes,
+                    b.swap_bytes,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::VoluntarySwitches => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.voluntary_switches_per_sec,
+                    b.voluntary_switches_per_sec,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::InvoluntarySwitches => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.involuntary_switches_per_sec,
+                    b.involuntary_switches_per_sec,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::MajorFaults => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.major_faults_per_sec,
+                    b.major_faults_per_sec,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::MinorFaults => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.minor_faults_per_sec,
+                    b.minor_faults_per_sec,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::SecurityLabel => {
+            to_sort_vec.sort_by(|a, b| match (&a.security_label, &b.security_label) {
+                (Some(label_a), Some(label_b)) => utils::gen_util::get_ordering(
+                    label_a,
+                    label_b,
+                    proc_widget_state.is_process_sort_descending,
+                ),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Less,
+            })
+        }
+        ProcessSorting::Capabilities => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.effective_caps,
+                    b.effective_caps,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::IoPriority => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.io_priority,
+                    b.io_priority,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::StartTime => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.start_time,
+                    b.start_time,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::CpuTime => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.cpu_time,
+                    b.cpu_time,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::GpuPercent => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.gpu_percent_usage,
+                    b.gpu_percent_usage,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::OpenFds => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.open_fds,
+                    b.open_fds,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
         ProcessSorting::Count => {
             if proc_widget_state.is_grouped {
                 to_sort_vec.sort_by(|a, b| {
@@ -648,16 +1194,44 @@ pub fn create_collection_thread(
 ) -> std::thread::JoinHandle<()> {
     let temp_type = app_config_fields.temperature_type.clone();
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
+    let show_process_fds = app_config_fields.show_process_fds;
+    let show_process_memory_breakdown = app_config_fields.show_process_memory_breakdown;
+    let show_process_latency = app_config_fields.show_process_latency;
+    let show_process_security = app_config_fields.show_process_security;
+    let show_process_io_priority = app_config_fields.show_process_io_priority;
     let show_average_cpu = app_config_fields.show_average_cpu;
+    let use_disk_smart = app_config_fields.use_disk_smart;
+    let use_ipmi = app_config_fields.use_ipmi;
+    let kubelet_url = app_config_fields.kubelet_url.clone();
+    let kubelet_insecure = app_config_fields.kubelet_insecure;
+    let use_network_wireless = app_config_fields.use_network_wireless;
+    let use_network_connections = app_config_fields.use_network_connections;
+    #[cfg(feature = "battery")]
+    let nut_server = app_config_fields.nut_server.clone();
     let update_rate_in_milliseconds = app_config_fields.update_rate_in_milliseconds;
+    let refresh_rates = app_config_fields.refresh_rates.clone();
 
     thread::spawn(move || {
         let mut data_state = data_harvester::DataCollector::new(filters);
 
         data_state.set_collected_data(used_widget_set);
+        data_state.set_refresh_rates(update_rate_in_milliseconds, &refresh_rates);
         data_state.set_temperature_type(temp_type);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
+        data_state.set_collect_process_fds(show_process_fds);
+        data_state.set_collect_process_memory_breakdown(show_process_memory_breakdown);
+        data_state.set_collect_process_latency(show_process_latency);
+        data_state.set_collect_process_security(show_process_security);
+        data_state.set_collect_process_io_priority(show_process_io_priority);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_use_disk_smart(use_disk_smart);
+        data_state.set_use_ipmi(use_ipmi);
+        data_state.set_kubelet_url(kubelet_url);
+        data_state.set_kubelet_insecure(kubelet_insecure);
+        data_state.set_use_network_wireless(use_network_wireless);
+        data_state.set_use_network_connections(use_network_connections);
+        #[cfg(feature = "battery")]
+        data_state.set_nut_server(nut_server);
 
         data_state.init();
 
@@ -671,7 +1245,6 @@ pub fn create_collection_thread(
                 }
             }
 
-            let mut update_time = update_rate_in_milliseconds;
             if let Ok(message) = control_receiver.try_recv() {
                 // trace!("Received message in collection thread: {:?}", message);
                 match message {
@@ -679,39 +1252,47 @@ pub fn create_collection_thread(
                         data_state.data.cleanup();
                     }
                     ThreadControlEvent::UpdateConfig(app_config_fields) => {
-                        data_state.set_temperature_type(app_config_fields.temperature_type.clone());
-                        data_state
-                            .set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
-                        data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+                        data_state.set_config_fields(&app_config_fields);
                     }
                     ThreadControlEvent::UpdateUsedWidgets(used_widget_set) => {
                         data_state.set_collected_data(*used_widget_set);
                     }
                     ThreadControlEvent::UpdateUpdateTime(new_time) => {
-                        update_time = new_time;
+                        data_state.set_refresh_rates(new_time, &refresh_rates);
+                    }
+                    ThreadControlEvent::UpdateThreadPid(pid) => {
+                        data_state.set_thread_pid(pid);
+                    }
+                    ThreadControlEvent::UpdateFilters(filters) => {
+                        data_state.set_filters(*filters);
                     }
+                    // Only the replay thread can be moved through.
+                    ThreadControlEvent::Replay(_) => {}
                 }
             }
-            futures::executor::block_on(data_state.update_data());
+            // Harvesters can have their own refresh rates, so only update once one of them is due.
+            if data_state.is_update_due() {
+                futures::executor::block_on(data_state.update_data());
 
-            // Yet another check to bail if needed...
-            if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
-                // We don't block here.
-                if *is_terminated {
-                    drop(is_terminated);
-                    break;
+                // Yet another check to bail if needed...
+                if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
+                    // We don't block here.
+                    if *is_terminated {
+                        drop(is_terminated);
+                        break;
+                    }
                 }
-            }
 
-            let event = BottomEvent::Update(Box::from(data_state.data));
-            data_state.data = data_harvester::Data::default();
-            if sender.send(event).is_err() {
-                break;
+                let event = BottomEvent::Update(Box::from(data_state.data));
+                data_state.data = data_harvester::Data::default();
+                if sender.send(event).is_err() {
+                    break;
+                }
             }
 
             if let Ok((is_terminated, _wait_timeout_result)) = termination_ctrl_cvar.wait_timeout(
                 termination_ctrl_lock.lock().unwrap(),
-                Duration::from_millis(update_time),
+                data_state.get_time_until_next_update(),
             ) {
                 if *is_terminated {
                     drop(is_terminated);
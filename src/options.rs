@@ -2,7 +2,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryInto,
     path::PathBuf,
     str::FromStr,
@@ -11,10 +11,23 @@ use std::{
 
 use crate::{
     app::{layout_manager::*, *},
-    canvas::ColourScheme,
+    canvas::{snapshot::SnapshotFormat, ColourScheme},
     constants::*,
+    data_harvester::processes::ProcessSorting,
+    export::{
+        history::HistoryFormat,
+        influxdb::InfluxDbOptions,
+        metrics_log::{MetricsLogFormat, MetricsLogOptions},
+    },
+    remote::server::ServerTlsOptions,
     units::data_units::DataUnit,
-    utils::error::{self, BottomError},
+    utils::{
+        error::{self, BottomError},
+        gen_util::{
+            GIBI_LIMIT_F64, GIGA_LIMIT_F64, KIBI_LIMIT_F64, KILO_LIMIT_F64, MEBI_LIMIT_F64,
+            MEGA_LIMIT_F64, TEBI_LIMIT_F64, TERA_LIMIT_F64,
+        },
+    },
 };
 
 use typed_builder::*;
@@ -29,11 +42,37 @@ use anyhow::{Context, Result};
 pub struct Config {
     pub flags: Option<ConfigFlags>,
     pub colors: Option<ConfigColours>,
+    /// Colour themes of the user's own, keyed by the name to pick them with.
+    pub themes: Option<HashMap<String, ConfigColours>>,
     pub row: Option<Vec<Row>>,
+    /// More layouts, shown as pages after the `row` one, listed as `[[layout]]` tables.
+    pub layout: Option<Vec<LayoutPageConfig>>,
     pub disk_filter: Option<IgnoreList>,
     pub mount_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
+    /// Display names for temperature sensors, keyed by the sensor name.
+    pub temp_names: Option<HashMap<String, String>>,
     pub net_filter: Option<IgnoreList>,
+    pub interface_filter: Option<IgnoreList>,
+    /// Watchdog rules for processes, listed as `[[process_alert]]` tables.
+    pub process_alert: Option<Vec<ProcessAlertConfig>>,
+    /// Alert rules for metrics of the whole system, listed as `[[alert]]` tables.
+    pub alert: Option<Vec<AlertConfig>>,
+    /// Named process searches that can be switched between, listed as `[[process_filter]]` tables.
+    pub process_filter: Option<Vec<ProcessFilterConfig>>,
+    /// The columns of the process widget, listed in order as `[[process_column]]` tables.
+    pub process_column: Option<Vec<ProcessColumnConfig>>,
+    /// The default sort of each kind of table widget.
+    pub default_sort: Option<DefaultSortConfig>,
+    /// The refresh rates in milliseconds of widgets refreshed at their own rates, keyed by the
+    /// widget type.
+    pub refresh_rates: Option<HashMap<String, u64>>,
+    /// Where and how to log the harvested metrics on every refresh.
+    pub metrics_log: Option<MetricsLogConfig>,
+    /// Where and how often to push the harvested metrics in the InfluxDB line protocol.
+    pub influxdb: Option<InfluxDbConfig>,
+    /// The remote hosts that CPU and memory widgets can show, listed as `[[host]]` tables.
+    pub host: Option<Vec<HostConfig>>,
 }
 
 impl Config {
@@ -56,6 +95,15 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub dot_marker: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub graph_style: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub filled_graphs: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub cpu_grouping: Option<String>,
+
     #[builder(default, setter(strip_option))]
     pub temperature_type: Option<String>,
 
@@ -80,9 +128,15 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub regex: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub fuzzy: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub basic: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub basic_cpu_rows: Option<u64>,
+
     #[builder(default, setter(strip_option))]
     pub default_time_value: Option<u64>,
 
@@ -95,6 +149,9 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub hide_time: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub time_gridlines: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub default_widget_type: Option<String>,
 
@@ -120,53 +177,166 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub color: Option<String>,
 
-    // This is a huge hack to enable hashmap functionality WITHOUT being able to serializing the field.
-    // Basically, keep a hashmap in the struct, and convert to a vector every time.
     #[builder(default, setter(strip_option))]
-    #[serde(skip)]
-    pub search_case_enabled_widgets_map: Option<HashMap<u64, bool>>,
+    pub mem_as_value: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub search_case_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+    pub tree: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    #[serde(skip)]
-    pub search_whole_word_enabled_widgets_map: Option<HashMap<u64, bool>>,
+    show_table_scroll_position: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub search_whole_word_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+    pub process_command: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    #[serde(skip)]
-    pub search_regex_enabled_widgets_map: Option<HashMap<u64, bool>>,
+    pub disable_advanced_kill: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub search_regex_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+    pub network_use_bytes: Option<bool>,
 
-    // End hack
     #[builder(default, setter(strip_option))]
-    pub mem_as_value: Option<bool>,
+    pub network_use_log: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub tree: Option<bool>,
+    pub network_use_binary_prefix: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    show_table_scroll_position: Option<bool>,
+    pub network_y_max: Option<String>,
 
     #[builder(default, setter(strip_option))]
-    pub process_command: Option<bool>,
+    pub memory_y_max: Option<String>,
 
     #[builder(default, setter(strip_option))]
-    pub disable_advanced_kill: Option<bool>,
+    pub stacked_memory_graph: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub network_use_bytes: Option<bool>,
+    pub disk_smart: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub network_use_log: Option<bool>,
+    pub disk_inodes: Option<bool>,
 
     #[builder(default, setter(strip_option))]
-    pub network_use_binary_prefix: Option<bool>,
+    pub disk_latency: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub ipmi: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub kubelet_url: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub kubelet_insecure: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub privilege_helper: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_cpu_trend: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_fds: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_memory_breakdown: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_latency: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_security: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_io_priority: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_times: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub hide_kernel_threads: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub persist_search_history: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub global_filter: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub cpu_frequency_graph: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub nut_server: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub prometheus_address: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub remote_token: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub serve_tls_cert: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub serve_tls_key: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub ssh_command: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub history_export_format: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub history_export_dir: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub snapshot_format: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub snapshot_dir: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_highlight_errors: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_wireless: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_connections: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_split_graph: Option<bool>,
+
+    // This is a huge hack to enable hashmap functionality WITHOUT being able to serializing the field.
+    // Basically, keep a hashmap in the struct, and convert to a vector every time.
+    // The vectors are serialized as arrays of tables, so they have to come after every other field.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip)]
+    pub search_case_enabled_widgets_map: Option<HashMap<u64, bool>>,
+
+    #[builder(default, setter(strip_option))]
+    pub search_case_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+
+    #[builder(default, setter(strip_option))]
+    #[serde(skip)]
+    pub search_whole_word_enabled_widgets_map: Option<HashMap<u64, bool>>,
+
+    #[builder(default, setter(strip_option))]
+    pub search_whole_word_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+
+    #[builder(default, setter(strip_option))]
+    #[serde(skip)]
+    pub search_regex_enabled_widgets_map: Option<HashMap<u64, bool>>,
+
+    #[builder(default, setter(strip_option))]
+    pub search_regex_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+
+    #[builder(default, setter(strip_option))]
+    #[serde(skip)]
+    pub search_fuzzy_enabled_widgets_map: Option<HashMap<u64, bool>>,
+
+    #[builder(default, setter(strip_option))]
+    pub search_fuzzy_enabled_widgets: Option<Vec<WidgetIdEnabled>>,
+    // End hack
 }
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -195,6 +365,7 @@ pub struct ConfigColours {
     pub cpu_core_colors: Option<Vec<String>>,
     pub ram_color: Option<String>,
     pub swap_color: Option<String>,
+    pub cache_color: Option<String>,
     pub rx_color: Option<String>,
     pub tx_color: Option<String>,
     pub rx_total_color: Option<String>, // These only affect basic mode.
@@ -207,9 +378,20 @@ pub struct ConfigColours {
     pub selected_bg_color: Option<String>,
     pub widget_title_color: Option<String>,
     pub graph_color: Option<String>,
+    pub graph_gradient_colors: Option<Vec<String>>,
     pub high_battery_color: Option<String>,
     pub medium_battery_color: Option<String>,
     pub low_battery_color: Option<String>,
+    pub zombie_process_color: Option<String>,
+    pub uninterruptible_process_color: Option<String>,
+    pub stopped_process_color: Option<String>,
+    pub tagged_process_color: Option<String>,
+    pub alerted_process_color: Option<String>,
+    pub critical_alert_color: Option<String>,
+    pub search_match_color: Option<String>,
+    pub failed_unit_color: Option<String>,
+    pub kernel_error_color: Option<String>,
+    pub kernel_warning_color: Option<String>,
 }
 
 impl ConfigColours {
@@ -235,15 +417,130 @@ pub struct IgnoreList {
     // TODO: Deprecate and/or rename, current name sounds awful.
     // Maybe to something like "deny_entries"?  Currently it defaults to a denylist anyways, so maybe "allow_entries"?
     pub is_list_ignored: bool,
+    #[serde(default)]
     pub list: Vec<String>,
+    /// Patterns whose matches are always kept, whatever `is_list_ignored` is.
+    pub allow: Option<Vec<String>>,
+    /// Patterns whose matches are filtered out unless they are allowed, whatever `is_list_ignored` is.
+    pub deny: Option<Vec<String>>,
     #[serde(default = "bool::default")]
     pub regex: bool,
     #[serde(default = "bool::default")]
+    pub glob: bool,
+    #[serde(default = "bool::default")]
     pub case_sensitive: bool,
     #[serde(default = "bool::default")]
     pub whole_word: bool,
 }
 
+/// A watchdog rule for processes, which alerts when a process whose name matches `name` stays
+/// above all of the given thresholds for `duration` seconds.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProcessAlertConfig {
+    pub name: String,
+    #[serde(default = "bool::default")]
+    pub regex: bool,
+    pub cpu_above: Option<f64>,
+    pub mem_above: Option<f64>,
+    pub duration: Option<u64>,
+    /// Either "info", "warning", or "critical".  Defaults to "warning".
+    pub severity: Option<String>,
+}
+
+/// An alert rule called `name`, which alerts when `metric` stays past `threshold` for `duration`
+/// seconds.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AlertConfig {
+    pub name: String,
+    pub metric: String,
+    /// Either ">", ">=", "<", or "<=".  Defaults to ">".
+    pub comparison: Option<String>,
+    pub threshold: f64,
+    pub duration: Option<u64>,
+    /// Either "info", "warning", or "critical".  Defaults to "warning".
+    pub severity: Option<String>,
+    /// Whether to show a desktop notification when the rule starts alerting.  Defaults to false.
+    pub notify: Option<bool>,
+    /// A shell command to run when the rule starts and stops alerting.
+    pub command: Option<String>,
+}
+
+/// A process search saved as `name`, which can be switched to in the process widget.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProcessFilterConfig {
+    pub name: String,
+    pub query: String,
+}
+
+/// A file that the harvested metrics are appended to on every refresh, as CSV or JSON lines.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MetricsLogConfig {
+    pub path: Option<String>,
+    /// Either "csv" or "jsonl".  Defaults to "jsonl" if the path ends in ".jsonl" or ".json", and
+    /// "csv" otherwise.
+    pub format: Option<String>,
+    /// The names of the metrics to log.  Defaults to all of them.
+    pub metrics: Option<Vec<String>>,
+    /// A size like "10MiB", past which the file is rotated.
+    pub max_size: Option<String>,
+    /// How many rotated files to keep.
+    pub max_files: Option<usize>,
+}
+
+/// An endpoint that the harvested metrics are pushed to in the InfluxDB line protocol.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct InfluxDbConfig {
+    pub url: Option<String>,
+    /// The token to authenticate with, if the endpoint needs one.
+    pub token: Option<String>,
+    /// How often to push the metrics, in seconds.  Defaults to 10.
+    pub interval: Option<u64>,
+    /// The names of the metrics to push.  Defaults to all of them.
+    pub metrics: Option<Vec<String>>,
+    /// The tags to add to every point.
+    pub tags: Option<BTreeMap<String, String>>,
+}
+
+/// A remote host called `name`, served by `btm --serve` at `address`, which CPU and memory widgets
+/// can be assigned to in the layout.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HostConfig {
+    pub name: String,
+    pub address: String,
+    /// The token that the server needs.  Defaults to `remote_token`.
+    pub token: Option<String>,
+}
+
+/// A column shown in the process widget, with a fixed `width` if given.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProcessColumnConfig {
+    pub name: String,
+    pub width: Option<u16>,
+}
+
+/// A named layout, shown as a page that can be switched to.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LayoutPageConfig {
+    pub name: String,
+    pub row: Vec<Row>,
+}
+
+/// The default sort of the process, disk, and temperature widgets.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DefaultSortConfig {
+    pub proc: Option<SortConfig>,
+    pub disk: Option<SortConfig>,
+    pub temp: Option<SortConfig>,
+}
+
+/// A column to sort a table by, by its header.  If `descending` is not given, the column is sorted
+/// in the same direction as when it is first picked.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SortConfig {
+    pub column: String,
+    pub descending: Option<bool>,
+}
+
 pub fn build_app(
     matches: &clap::ArgMatches, config: &mut Config, widget_layout: &BottomLayout,
     default_widget_id: u64, default_widget_type_option: &Option<BottomWidgetType>,
@@ -254,12 +551,19 @@ pub fn build_app(
     let default_time_value = get_default_time_value(matches, config)
         .context("Update 'default_time_value' in your config file.")?;
     let use_basic_mode = get_use_basic_mode(matches, config);
+    let layout_pages = if use_basic_mode {
+        Vec::new()
+    } else {
+        get_layout_pages(matches, config, widget_layout, default_widget_id)
+            .context("Update 'layout' in your config file.")?
+    };
 
     // For processes
     let is_grouped = get_app_grouping(matches, config);
     let is_case_sensitive = get_app_case_sensitive(matches, config);
     let is_match_whole_word = get_app_match_whole_word(matches, config);
     let is_use_regex = get_app_use_regex(matches, config);
+    let is_use_fuzzy = get_app_use_fuzzy(matches, config);
 
     let mut widget_map = HashMap::new();
     let mut cpu_state_map: HashMap<u64, CpuWidgetState> = HashMap::new();
@@ -269,6 +573,16 @@ pub fn build_app(
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskWidgetState> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut conn_state_map: HashMap<u64, ConnWidgetState> = HashMap::new();
+    let mut users_state_map: HashMap<u64, UsersWidgetState> = HashMap::new();
+    let mut activity_state_map: HashMap<u64, ActivityWidgetState> = HashMap::new();
+    let mut pods_state_map: HashMap<u64, PodsWidgetState> = HashMap::new();
+    let mut vm_state_map: HashMap<u64, VmWidgetState> = HashMap::new();
+    let mut numa_state_map: HashMap<u64, NumaWidgetState> = HashMap::new();
+    let mut units_state_map: HashMap<u64, UnitsWidgetState> = HashMap::new();
+    let mut kernel_log_state_map: HashMap<u64, KernelLogWidgetState> = HashMap::new();
+    let mut alerts_state_map: HashMap<u64, AlertsWidgetState> = HashMap::new();
+    let mut power_state_map: HashMap<u64, PowerWidgetState> = HashMap::new();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -284,103 +598,237 @@ pub fn build_app(
     let show_memory_as_values = get_mem_as_value(matches, config);
     let is_default_tree = get_is_default_tree(matches, config);
     let is_default_command = get_is_default_process_command(matches, config);
+    let process_columns = get_process_columns(&config.process_column)
+        .context("Update 'process_column' in your config file.")?;
+    let default_sort = config.default_sort.clone().unwrap_or_default();
+    let default_process_sort =
+        get_default_sort(&default_sort.proc, ProcessSorting::is_descending_by_default)
+            .context("Update 'default_sort' in your config file.")?;
+    let disk_sorting = get_default_sort(
+        &default_sort.disk,
+        data_harvester::disks::DiskSorting::is_descending_by_default,
+    )
+    .context("Update 'default_sort' in your config file.")?;
+    // Listing or sorting by an optional column also collects the data for it.
+    let is_column_listed = |listed_columns: &[ProcessSorting]| {
+        process_columns
+            .iter()
+            .map(|(column, _width)| column)
+            .chain(
+                default_process_sort
+                    .iter()
+                    .map(|(column, _is_descending)| column),
+            )
+            .any(|column| listed_columns.contains(column))
+    };
+    let show_process_cpu_trend = get_show_process_cpu_trend(matches, config)
+        || is_column_listed(&[ProcessSorting::CpuTrend]);
+    let show_process_fds =
+        get_show_process_fds(matches, config) || is_column_listed(&[ProcessSorting::OpenFds]);
+    let show_process_memory_breakdown = get_show_process_memory_breakdown(matches, config)
+        || is_column_listed(&[
+            ProcessSorting::Pss,
+            ProcessSorting::Uss,
+            ProcessSorting::Swap,
+        ]);
+    let show_process_latency = get_show_process_latency(matches, config)
+        || is_column_listed(&[
+            ProcessSorting::VoluntarySwitches,
+            ProcessSorting::InvoluntarySwitches,
+            ProcessSorting::MajorFaults,
+            ProcessSorting::MinorFaults,
+        ]);
+    let show_process_security = get_show_process_security(matches, config)
+        || is_column_listed(&[ProcessSorting::SecurityLabel, ProcessSorting::Capabilities]);
+    let show_process_io_priority = get_show_process_io_priority(matches, config)
+        || is_column_listed(&[ProcessSorting::IoPriority]);
+    let show_process_times = get_show_process_times(matches, config)
+        || is_column_listed(&[ProcessSorting::StartTime, ProcessSorting::CpuTime]);
+    let is_hiding_kernel_threads = get_hide_kernel_threads(matches, config);
+    let proc_widget_defaults = ProcWidgetDefaults {
+        is_case_sensitive,
+        is_match_whole_word,
+        is_use_regex,
+        is_use_fuzzy,
+        is_grouped,
+        show_memory_as_values,
+        is_tree_mode: is_default_tree,
+        is_using_command: is_default_command,
+        show_cpu_trend: show_process_cpu_trend,
+        show_open_fds: show_process_fds,
+        show_memory_breakdown: show_process_memory_breakdown,
+        show_latency: show_process_latency,
+        show_security: show_process_security,
+        show_io_priority: show_process_io_priority,
+        show_times: show_process_times,
+        is_hiding_kernel_threads,
+        process_columns,
+        default_sort: default_process_sort,
+    };
     let is_advanced_kill = !get_is_advanced_kill_disabled(matches, config);
+    let graph_style =
+        get_graph_style(matches, config).context("Update 'graph_style' in your config file.")?;
+    let is_filled_graph = get_is_filled_graph(config);
+    let cpu_grouping =
+        get_cpu_grouping(config).context("Update 'cpu_grouping' in your config file.")?;
 
     let network_unit_type = get_network_unit_type(matches, config);
     let network_scale_type = get_network_scale_type(matches, config);
     let network_use_binary_prefix = get_network_use_binary_prefix(matches, config);
 
-    for row in &widget_layout.rows {
-        for col in &row.children {
-            for col_row in &col.children {
-                for widget in &col_row.children {
-                    widget_map.insert(widget.widget_id, widget.clone());
-                    if let Some(default_widget_type) = &default_widget_type_option {
-                        if !is_custom_layout || use_basic_mode {
-                            match widget.widget_type {
-                                BasicCpu => {
-                                    if let Cpu = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                BasicMem => {
-                                    if let Mem = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                BasicNet => {
-                                    if let Net = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                _ => {
-                                    if *default_widget_type == widget.widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = widget.widget_type.clone();
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    used_widget_set.insert(widget.widget_type.clone());
+    let remote_hosts = get_remote_hosts(&config.host, get_remote_token(matches, config))
+        .context("Update 'host' in your config file.")?;
+    let get_widget_host = |widget: &BottomWidget| -> anyhow::Result<Option<usize>> {
+        widget
+            .host
+            .as_deref()
+            .map(|name| {
+                remote_hosts::get_host_index(&remote_hosts, name).with_context(|| {
+                    format!(
+                        "The layout has a widget for the host \"{}\", but there is no [[host]] called that in your config file.",
+                        name
+                    )
+                })
+            })
+            .transpose()
+    };
 
+    let other_page_widgets = layout_pages
+        .iter()
+        .skip(1)
+        .flat_map(|layout_page| layout_page.layout.widgets());
+    for (is_shown, widget) in widget_layout
+        .widgets()
+        .map(|widget| (true, widget))
+        .chain(other_page_widgets.map(|widget| (false, widget)))
+    {
+        // Only the widgets of the first page are shown at first, but every page has its states.
+        if is_shown {
+            widget_map.insert(widget.widget_id, widget.clone());
+            if let Some(default_widget_type) = &default_widget_type_option {
+                if !is_custom_layout || use_basic_mode {
                     match widget.widget_type {
-                        Cpu => {
-                            cpu_state_map.insert(
-                                widget.widget_id,
-                                CpuWidgetState::init(default_time_value, autohide_timer),
-                            );
-                        }
-                        Mem => {
-                            mem_state_map.insert(
-                                widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
-                            );
-                        }
-                        Net => {
-                            net_state_map.insert(
-                                widget.widget_id,
-                                NetWidgetState::init(
-                                    default_time_value,
-                                    autohide_timer,
-                                    // network_unit_type.clone(),
-                                    // network_scale_type.clone(),
-                                ),
-                            );
-                        }
-                        Proc => {
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::init(
-                                    is_case_sensitive,
-                                    is_match_whole_word,
-                                    is_use_regex,
-                                    is_grouped,
-                                    show_memory_as_values,
-                                    is_default_tree,
-                                    is_default_command,
-                                ),
-                            );
+                        BasicCpu => {
+                            if let Cpu = *default_widget_type {
+                                initial_widget_id = widget.widget_id;
+                                initial_widget_type = Cpu;
+                            }
                         }
-                        Disk => {
-                            disk_state_map.insert(widget.widget_id, DiskWidgetState::init());
+                        BasicMem => {
+                            if let Mem = *default_widget_type {
+                                initial_widget_id = widget.widget_id;
+                                initial_widget_type = Cpu;
+                            }
                         }
-                        Temp => {
-                            temp_state_map.insert(widget.widget_id, TempWidgetState::init());
+                        BasicNet => {
+                            if let Net = *default_widget_type {
+                                initial_widget_id = widget.widget_id;
+                                initial_widget_type = Cpu;
+                            }
                         }
-                        Battery => {
-                            battery_state_map
-                                .insert(widget.widget_id, BatteryWidgetState::default());
+                        _ => {
+                            if *default_widget_type == widget.widget_type {
+                                initial_widget_id = widget.widget_id;
+                                initial_widget_type = widget.widget_type.clone();
+                            }
                         }
-                        _ => {}
                     }
                 }
             }
         }
+
+        used_widget_set.insert(widget.widget_type.clone());
+
+        match widget.widget_type {
+            Cpu => {
+                cpu_state_map.insert(
+                    widget.widget_id,
+                    CpuWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        widget.graph_style.unwrap_or(graph_style),
+                        widget.is_filled.unwrap_or(is_filled_graph),
+                        get_widget_host(widget)?,
+                    ),
+                );
+            }
+            Mem => {
+                mem_state_map.insert(
+                    widget.widget_id,
+                    MemWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        widget.graph_style.unwrap_or(graph_style),
+                        widget.is_filled.unwrap_or(is_filled_graph),
+                        get_widget_host(widget)?,
+                    ),
+                );
+            }
+            Net => {
+                net_state_map.insert(
+                    widget.widget_id,
+                    NetWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        widget.graph_style.unwrap_or(graph_style),
+                        widget.is_filled.unwrap_or(is_filled_graph),
+                        network_scale_type.clone(),
+                        // network_unit_type.clone(),
+                    ),
+                );
+            }
+            Proc => {
+                proc_state_map.insert(widget.widget_id, proc_widget_defaults.init_state());
+            }
+            Disk => {
+                disk_state_map.insert(widget.widget_id, DiskWidgetState::init(disk_sorting));
+            }
+            Temp => {
+                temp_state_map.insert(widget.widget_id, TempWidgetState::init());
+            }
+            Battery => {
+                battery_state_map.insert(widget.widget_id, BatteryWidgetState::default());
+            }
+            Conn => {
+                conn_state_map.insert(widget.widget_id, ConnWidgetState::init());
+            }
+            Users => {
+                users_state_map.insert(widget.widget_id, UsersWidgetState::init());
+            }
+            Activity => {
+                activity_state_map.insert(widget.widget_id, ActivityWidgetState::init());
+            }
+            Pods => {
+                pods_state_map.insert(widget.widget_id, PodsWidgetState::init());
+            }
+            Vm => {
+                vm_state_map.insert(widget.widget_id, VmWidgetState::init());
+            }
+            Numa => {
+                numa_state_map.insert(widget.widget_id, NumaWidgetState::init());
+            }
+            Units => {
+                units_state_map.insert(widget.widget_id, UnitsWidgetState::init());
+            }
+            KernelLog => {
+                kernel_log_state_map.insert(widget.widget_id, KernelLogWidgetState::init());
+            }
+            Alerts => {
+                alerts_state_map.insert(widget.widget_id, AlertsWidgetState::init());
+            }
+            Power => {
+                power_state_map.insert(
+                    widget.widget_id,
+                    PowerWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        widget.graph_style.unwrap_or(graph_style),
+                        widget.is_filled.unwrap_or(is_filled_graph),
+                    ),
+                );
+            }
+            _ => {}
+        }
     }
 
     let basic_table_widget_state = if use_basic_mode {
@@ -414,14 +862,24 @@ pub fn build_app(
         temperature_type: get_temperature(matches, config)
             .context("Update 'temperature_type' in your config file.")?,
         show_average_cpu: get_show_average_cpu(matches, config),
-        use_dot: get_use_dot(matches, config),
+        graph_style,
+        is_filled_graph,
         left_legend: get_use_left_legend(matches, config),
         use_current_cpu_total: get_use_current_cpu_total(matches, config),
+        cpu_frequency_graph: get_cpu_frequency_graph(matches, config),
+        show_process_fds,
+        show_process_memory_breakdown,
+        show_process_latency,
+        show_process_security,
+        show_process_io_priority,
         use_basic_mode,
+        basic_cpu_rows: get_basic_cpu_rows(config)
+            .context("Update 'basic_cpu_rows' in your config file.")?,
         default_time_value,
         time_interval: get_time_interval(matches, config)
             .context("Update 'time_delta' in your config file.")?,
         hide_time: get_hide_time(matches, config),
+        show_time_gridlines: get_show_time_gridlines(config),
         autohide_time,
         use_old_network_legend: get_use_old_network_legend(matches, config),
         table_gap: if get_hide_table_gap(matches, config) {
@@ -430,24 +888,80 @@ pub fn build_app(
             1
         },
         disable_click: get_disable_click(matches, config),
-        // no_write: get_no_write(matches, config),
-        no_write: false,
+        no_write: get_no_write(matches, config),
         show_table_scroll_position: get_show_table_scroll_position(matches, config),
         is_advanced_kill,
+        use_disk_smart: get_use_disk_smart(matches, config),
+        use_disk_inodes: get_use_disk_inodes(matches, config),
+        use_disk_latency: get_use_disk_latency(matches, config),
+        use_ipmi: get_use_ipmi(matches, config),
+        kubelet_url: get_kubelet_url(matches, config),
+        kubelet_insecure: get_kubelet_insecure(matches, config),
+        privilege_helper: get_privilege_helper(matches, config),
+        network_highlight_errors: get_network_highlight_errors(matches, config),
+        use_network_wireless: get_use_network_wireless(matches, config),
+        use_network_connections: get_use_network_connections(matches, config),
+        nut_server: get_nut_server(matches, config),
+        prometheus_address: get_prometheus_address(matches, config),
+        influxdb: get_influxdb(matches, config)
+            .context("Update 'influxdb' in your config file.")?,
+        metrics_log: get_metrics_log(matches, config)
+            .context("Update 'metrics_log' in your config file.")?,
+        serve_address: matches.value_of("serve").map(|address| address.to_string()),
+        serve_tls: get_serve_tls(matches, config)?,
+        connect_address: get_connect_address(matches),
+        remote_token: get_remote_token(matches, config),
+        ssh_command: get_ssh_command(matches, config),
+        record_path: matches.value_of("record").map(PathBuf::from),
+        replay_path: matches.value_of("replay").map(PathBuf::from),
+        history_export_format: get_history_export_format(config)
+            .context("Update 'history_export_format' in your config file.")?,
+        history_export_dir: get_history_export_dir(config),
+        snapshot_format: get_snapshot_format(config)
+            .context("Update 'snapshot_format' in your config file.")?,
+        snapshot_dir: get_snapshot_dir(config),
+        temp_names: config.temp_names.clone().unwrap_or_default(),
+        disk_sorting,
+        temp_sorting: get_default_sort(
+            &default_sort.temp,
+            data_harvester::temperature::TempSorting::is_descending_by_default,
+        )
+        .context("Update 'default_sort' in your config file.")?,
+        refresh_rates: get_refresh_rates(&config.refresh_rates)
+            .context("Update 'refresh_rates' in your config file.")?,
         network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
+        network_y_range: get_network_y_range(config)
+            .context("Update 'network_y_max' in your config file.")?,
+        memory_y_range: get_memory_y_range(config)
+            .context("Update 'memory_y_max' in your config file.")?,
     };
 
-    let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
-        use_mem: used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some(),
-        use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
-        use_proc: used_widget_set.get(&Proc).is_some(),
-        use_disk: used_widget_set.get(&Disk).is_some(),
-        use_temp: used_widget_set.get(&Temp).is_some(),
-        use_battery: used_widget_set.get(&Battery).is_some(),
-    };
+    let process_alerts = process_alerts::ProcessAlerts::new(
+        get_process_alert_rules(&config.process_alert)
+            .context("Update 'process_alert' in your config file.")?,
+    );
+    let metric_alerts = metric_alerts::MetricAlerts::new(
+        get_alert_rules(&config.alert).context("Update 'alert' in your config file.")?,
+        app_config_fields.temperature_type.clone(),
+    );
+
+    let mut global_filter = GlobalFilterState::default();
+    if let Some(query) = get_global_filter(matches, config) {
+        global_filter
+            .set_query(&query)
+            .context("Update 'global_filter' in your config file.")?;
+    }
+
+    let used_widgets = UsedWidgets::from_widget_types(
+        &used_widget_set,
+        process_alerts.has_rules() || global_filter.query.is_some(),
+    )
+    .union(&metric_alerts.get_used_widgets());
+
+    let process_filters = get_process_filters(&config.process_filter)
+        .context("Update 'process_filter' in your config file.")?;
 
     let disk_filter =
         get_ignore_list(&config.disk_filter).context("Update 'disk_filter' in your config file")?;
@@ -457,6 +971,8 @@ pub fn build_app(
         get_ignore_list(&config.temp_filter).context("Update 'temp_filter' in your config file")?;
     let net_filter =
         get_ignore_list(&config.net_filter).context("Update 'net_filter' in your config file")?;
+    let interface_filter = get_ignore_list(&config.interface_filter)
+        .context("Update 'interface_filter' in your config file")?;
 
     // One more thing - we have to update the search settings of our proc_state_map, and create the hashmaps if needed!
     // Note that if you change your layout, this might not actually match properly... not sure if/where we should deal with that...
@@ -498,29 +1014,78 @@ pub fn build_app(
                 flags.search_regex_enabled_widgets_map = Some(mapping);
             }
         }
+
+        if flags.fuzzy.is_none() && !matches.is_present("fuzzy") {
+            if let Some(search_fuzzy_enabled_widgets) = &flags.search_fuzzy_enabled_widgets {
+                let mapping = HashMap::new();
+                for widget in search_fuzzy_enabled_widgets {
+                    if let Some(proc_widget) = proc_state_map.get_mut(&widget.id) {
+                        proc_widget.process_search_state.is_searching_with_fuzzy = widget.enabled;
+                    }
+                }
+                flags.search_fuzzy_enabled_widgets_map = Some(mapping);
+            }
+        }
     }
 
+    // The search history is kept next to the config file, if it is kept at all.
+    let search_history = match &config_path {
+        Some(config_path) if get_persist_search_history(matches, config) => {
+            SearchHistory::load(config_path.with_file_name(SEARCH_HISTORY_FILE_NAME))
+        }
+        _ => SearchHistory::default(),
+    };
+
     Ok(App::builder()
         .app_config_fields(app_config_fields)
-        .cpu_state(CpuState::init(cpu_state_map))
-        .mem_state(MemState::init(mem_state_map))
-        .net_state(NetState::init(net_state_map))
+        .cpu_state(CpuState::init(cpu_state_map, cpu_grouping))
+        .mem_state(MemState::init(
+            mem_state_map,
+            get_stacked_memory_graph(config),
+        ))
+        .net_state(NetState::init(
+            net_state_map,
+            get_network_split_graph(config),
+        ))
         .proc_state(ProcState::init(proc_state_map))
         .disk_state(DiskState::init(disk_state_map))
         .temp_state(TempState::init(temp_state_map))
         .battery_state(BatteryState::init(battery_state_map))
+        .conn_state(ConnState::init(conn_state_map))
+        .users_state(UsersState::init(users_state_map))
+        .activity_state(ActivityState::init(activity_state_map))
+        .pods_state(PodsState::init(pods_state_map))
+        .vm_state(VmState::init(vm_state_map))
+        .numa_state(NumaState::init(numa_state_map))
+        .units_state(UnitsState::init(units_state_map))
+        .kernel_log_state(KernelLogState::init(kernel_log_state_map))
+        .alerts_state(AlertsState::init(alerts_state_map))
+        .power_state(PowerState::init(power_state_map))
         .basic_table_widget_state(basic_table_widget_state)
         .current_widget(widget_map.get(&initial_widget_id).unwrap().clone()) // TODO: [UNWRAP] - many of the unwraps are fine (like this one) but do a once-over and/or switch to expect?
         .widget_map(widget_map)
         .used_widgets(used_widgets)
+        .process_alerts(process_alerts)
+        .metric_alerts(metric_alerts)
+        .remote_hosts(remote_hosts)
         .filters(DataFilters {
             disk_filter,
             mount_filter,
             temp_filter,
             net_filter,
+            interface_filter,
         })
         .config(config.clone())
+        .search_history(search_history)
+        .process_filters(process_filters)
+        .global_filter(global_filter)
         .config_path(config_path)
+        .layout_pages(layout_pages)
+        .colour_scheme_state(AppColourSchemeState::init(
+            get_color_schemes(config),
+            &get_color_scheme(matches, config)?,
+        ))
+        .proc_widget_defaults(proc_widget_defaults)
         .build())
 }
 
@@ -537,56 +1102,115 @@ pub fn get_widget_layout(
 
         BottomLayout::init_basic_default(get_use_battery(matches, config))
     } else {
-        let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
-                // This cannot (like it really shouldn't) fail!
-                ref_row = toml::from_str::<Config>(if get_use_battery(matches, config) {
+        convert_rows_to_layout(
+            &get_layout_rows(matches, config)?,
+            left_legend,
+            &default_widget_type,
+            &mut default_widget_count,
+            &mut default_widget_id,
+            0,
+        )?
+    };
+
+    Ok((bottom_layout, default_widget_id, default_widget_type))
+}
+
+/// Returns the rows of the layout in the config file, or of the default layout if there is none.
+pub fn get_layout_rows(matches: &clap::ArgMatches, config: &Config) -> error::Result<Vec<Row>> {
+    match &config.row {
+        Some(rows) => Ok(rows.clone()),
+        None => {
+            // This cannot (like it really shouldn't) fail!
+            Ok(
+                toml::from_str::<Config>(if get_use_battery(matches, config) {
                     DEFAULT_BATTERY_LAYOUT
                 } else {
                     DEFAULT_LAYOUT
                 })?
                 .row
-                .unwrap();
-                &ref_row
-            }
-        };
-
-        let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
-        let mut total_height_ratio = 0;
+                .unwrap(),
+            )
+        }
+    }
+}
 
-        let mut ret_bottom_layout = BottomLayout {
-            rows: rows
-                .iter()
-                .map(|row| {
-                    row.convert_row_to_bottom_row(
-                        &mut iter_id,
-                        &mut total_height_ratio,
-                        &mut default_widget_id,
-                        &default_widget_type,
-                        &mut default_widget_count,
-                        left_legend,
-                    )
-                })
-                .collect::<error::Result<Vec<_>>>()?,
-            total_row_height_ratio: total_height_ratio,
-        };
+/// Returns the pages of the layout, starting with the `[[row]]` one (or the default one), which
+/// has already been converted into `first_layout`, and followed by those in `[[layout]]`.
+pub fn get_layout_pages(
+    matches: &clap::ArgMatches, config: &Config, first_layout: &BottomLayout, first_widget_id: u64,
+) -> error::Result<Vec<LayoutPage>> {
+    let left_legend = get_use_left_legend(matches, config);
+    let mut layout_pages = vec![LayoutPage {
+        name: DEFAULT_LAYOUT_PAGE_NAME.to_string(),
+        rows: get_layout_rows(matches, config)?,
+        layout: first_layout.clone(),
+        current_widget_id: first_widget_id,
+    }];
+
+    for (page_config, page_index) in config.layout.iter().flatten().zip(1..) {
+        let widget_id_offset = page_index * LAYOUT_PAGE_WIDGET_IDS;
+        let mut current_widget_id = widget_id_offset + 1;
+        let layout = convert_rows_to_layout(
+            &page_config.row,
+            left_legend,
+            &None,
+            &mut 0,
+            &mut current_widget_id,
+            widget_id_offset,
+        )?;
+
+        layout_pages.push(LayoutPage {
+            name: page_config.name.clone(),
+            rows: page_config.row.clone(),
+            layout,
+            current_widget_id,
+        });
+    }
 
-        // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
-            ret_bottom_layout.get_movement_mappings();
-            // debug!("Bottom layout: {:#?}", ret_bottom_layout);
+    Ok(layout_pages)
+}
 
-            ret_bottom_layout
-        } else {
-            return Err(error::BottomError::ConfigError(
-                "please have at least one widget under the '[[row]]' section.".to_string(),
-            ));
-        }
+/// Converts the rows of a layout into a [`BottomLayout`], with the widget IDs assigned in order
+/// after `widget_id_offset`.
+pub fn convert_rows_to_layout(
+    rows: &[Row], left_legend: bool, default_widget_type: &Option<BottomWidgetType>,
+    default_widget_count: &mut u64, default_widget_id: &mut u64, widget_id_offset: u64,
+) -> error::Result<BottomLayout> {
+    let mut iter_id = widget_id_offset; // A lazy way of forcing unique IDs *shrugs*
+    let mut total_height_ratio = 0;
+
+    let mut ret_bottom_layout = BottomLayout {
+        rows: rows
+            .iter()
+            .map(|row| {
+                row.convert_row_to_bottom_row(
+                    &mut iter_id,
+                    &mut total_height_ratio,
+                    default_widget_id,
+                    default_widget_type,
+                    default_widget_count,
+                    left_legend,
+                )
+            })
+            .collect::<error::Result<Vec<_>>>()?,
+        total_row_height_ratio: total_height_ratio,
     };
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+    // Confirm that we have at least ONE widget left - if not, error out!
+    if iter_id - widget_id_offset >= LAYOUT_PAGE_WIDGET_IDS {
+        Err(error::BottomError::ConfigError(
+            "please have fewer widgets in a layout.".to_string(),
+        ))
+    } else if iter_id > widget_id_offset {
+        ret_bottom_layout.get_movement_mappings();
+        // debug!("Bottom layout: {:#?}", ret_bottom_layout);
+
+        Ok(ret_bottom_layout)
+    } else {
+        Err(error::BottomError::ConfigError(
+            "please have at least one widget under the '[[row]]' section.".to_string(),
+        ))
+    }
 }
 
 fn get_update_rate_in_milliseconds(
@@ -617,6 +1241,35 @@ fn get_update_rate_in_milliseconds(
     Ok(update_rate_in_milliseconds)
 }
 
+fn get_refresh_rates(
+    refresh_rates: &Option<HashMap<String, u64>>,
+) -> error::Result<HashMap<BottomWidgetType, u64>> {
+    refresh_rates
+        .iter()
+        .flatten()
+        .map(|(widget_type, rate)| {
+            let parsed_widget = widget_type.parse::<BottomWidgetType>()?;
+            if let BottomWidgetType::Empty
+            | BottomWidgetType::Alerts
+            | BottomWidgetType::CpuHeatmap = parsed_widget
+            {
+                return Err(BottomError::ConfigError(format!(
+                    "\"{}\" has no data of its own to refresh.",
+                    widget_type
+                )));
+            }
+            if *rate < 250 {
+                return Err(BottomError::ConfigError(format!(
+                    "set the refresh rate of \"{}\" to be at least 250 milliseconds.",
+                    widget_type
+                )));
+            }
+
+            Ok((parsed_widget, *rate))
+        })
+        .collect()
+}
+
 fn get_temperature(
     matches: &clap::ArgMatches, config: &Config,
 ) -> error::Result<data_harvester::temperature::TemperatureType> {
@@ -656,12 +1309,32 @@ fn get_show_average_cpu(matches: &clap::ArgMatches, config: &Config) -> bool {
     true
 }
 
-fn get_use_dot(matches: &clap::ArgMatches, config: &Config) -> bool {
+fn get_graph_style(matches: &clap::ArgMatches, config: &Config) -> error::Result<GraphStyle> {
     if matches.is_present("dot_marker") {
-        return true;
+        return Ok(GraphStyle::Dot);
     } else if let Some(flags) = &config.flags {
-        if let Some(dot_marker) = flags.dot_marker {
-            return dot_marker;
+        if let Some(graph_style) = &flags.graph_style {
+            return graph_style.parse::<GraphStyle>();
+        } else if let Some(true) = flags.dot_marker {
+            return Ok(GraphStyle::Dot);
+        }
+    }
+    Ok(GraphStyle::Braille)
+}
+
+fn get_cpu_grouping(config: &Config) -> error::Result<CpuGrouping> {
+    if let Some(flags) = &config.flags {
+        if let Some(cpu_grouping) = &flags.cpu_grouping {
+            return cpu_grouping.parse::<CpuGrouping>();
+        }
+    }
+    Ok(CpuGrouping::None)
+}
+
+fn get_is_filled_graph(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(filled_graphs) = flags.filled_graphs {
+            return filled_graphs;
         }
     }
     false
@@ -734,6 +1407,16 @@ fn get_default_time_value(matches: &clap::ArgMatches, config: &Config) -> error:
     Ok(default_time)
 }
 
+fn get_basic_cpu_rows(config: &Config) -> error::Result<Option<u16>> {
+    match config.flags.as_ref().and_then(|flags| flags.basic_cpu_rows) {
+        Some(0) => Err(BottomError::ConfigError(
+            "set your basic CPU row count to be at least 1.".to_string(),
+        )),
+        Some(basic_cpu_rows) => Ok(Some(basic_cpu_rows.try_into().unwrap_or(u16::MAX))),
+        None => Ok(None),
+    }
+}
+
 fn get_time_interval(matches: &clap::ArgMatches, config: &Config) -> error::Result<u64> {
     let time_interval = if let Some(time_interval) = matches.value_of("time_delta") {
         time_interval.parse::<u64>().map_err(|_| {
@@ -809,6 +1492,17 @@ pub fn get_app_use_regex(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
+pub fn get_app_use_fuzzy(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("fuzzy") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(fuzzy) = flags.fuzzy {
+            return fuzzy;
+        }
+    }
+    false
+}
+
 fn get_hide_time(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("hide_time") {
         return true;
@@ -820,6 +1514,15 @@ fn get_hide_time(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
+fn get_show_time_gridlines(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(time_gridlines) = flags.time_gridlines {
+            return time_gridlines;
+        }
+    }
+    false
+}
+
 fn get_autohide_time(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("autohide_time") {
         return true;
@@ -926,7 +1629,6 @@ fn get_use_battery(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
-#[allow(dead_code)]
 fn get_no_write(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("no_write") {
         return true;
@@ -938,41 +1640,165 @@ fn get_no_write(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
-fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option<Filter>> {
-    if let Some(ignore_list) = ignore_list {
-        let list: Result<Vec<_>, _> = ignore_list
-            .list
-            .iter()
-            .map(|name| {
-                let escaped_string: String;
-                let res = format!(
-                    "{}{}{}{}",
-                    if ignore_list.whole_word { "^" } else { "" },
-                    if ignore_list.case_sensitive {
-                        ""
-                    } else {
-                        "(?i)"
-                    },
-                    if ignore_list.regex {
-                        name
-                    } else {
-                        escaped_string = regex::escape(name);
-                        &escaped_string
-                    },
-                    if ignore_list.whole_word { "$" } else { "" },
-                );
+pub fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option<Filter>> {
+    ignore_list
+        .as_ref()
+        .map(|ignore_list| Ok(Filter::from_ignore_list(ignore_list)?))
+        .transpose()
+}
+
+fn get_process_columns(
+    process_columns: &Option<Vec<ProcessColumnConfig>>,
+) -> error::Result<Vec<(ProcessSorting, Option<u16>)>> {
+    process_columns
+        .iter()
+        .flatten()
+        .map(|process_column| {
+            let column =
+                ProcessSorting::from_str(&process_column.name).map_err(BottomError::ConfigError)?;
+            Ok((column, process_column.width))
+        })
+        .collect()
+}
+
+fn get_default_sort<T: FromStr<Err = String>>(
+    sort: &Option<SortConfig>, is_descending_by_default: fn(&T) -> bool,
+) -> error::Result<Option<(T, bool)>> {
+    sort.as_ref()
+        .map(|sort| {
+            let column = T::from_str(&sort.column).map_err(BottomError::ConfigError)?;
+            let is_descending = sort
+                .descending
+                .unwrap_or_else(|| is_descending_by_default(&column));
+            Ok((column, is_descending))
+        })
+        .transpose()
+}
+
+fn get_process_alert_rules(
+    process_alerts: &Option<Vec<ProcessAlertConfig>>,
+) -> error::Result<Vec<process_alerts::ProcessAlertRule>> {
+    process_alerts
+        .iter()
+        .flatten()
+        .map(|process_alert| {
+            if process_alert.cpu_above.is_none() && process_alert.mem_above.is_none() {
+                return Err(BottomError::ConfigError(format!(
+                    "the process alert for \"{}\" needs a cpu_above or mem_above threshold.",
+                    process_alert.name
+                )));
+            }
+
+            let name = if process_alert.regex {
+                Regex::new(&process_alert.name)?
+            } else {
+                Regex::new(&format!("^{}$", regex::escape(&process_alert.name)))?
+            };
 
-                Regex::new(&res)
+            Ok(process_alerts::ProcessAlertRule {
+                name,
+                cpu_above: process_alert.cpu_above,
+                mem_above: process_alert.mem_above,
+                duration: std::time::Duration::from_secs(process_alert.duration.unwrap_or(0)),
+                severity: process_alert
+                    .severity
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
             })
-            .collect();
+        })
+        .collect()
+}
 
-        Ok(Some(Filter {
-            list: list?,
-            is_list_ignored: ignore_list.is_list_ignored,
-        }))
-    } else {
-        Ok(None)
+fn get_alert_rules(
+    alerts: &Option<Vec<AlertConfig>>,
+) -> error::Result<Vec<metric_alerts::MetricAlertRule>> {
+    alerts
+        .iter()
+        .flatten()
+        .map(|alert| {
+            Ok(metric_alerts::MetricAlertRule {
+                name: alert.name.clone(),
+                metric: alert.metric.parse()?,
+                comparison: alert
+                    .comparison
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+                threshold: alert.threshold,
+                duration: std::time::Duration::from_secs(alert.duration.unwrap_or(0)),
+                severity: alert
+                    .severity
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+                notify: alert.notify.unwrap_or(false),
+                command: alert.command.clone(),
+            })
+        })
+        .collect()
+}
+
+fn get_remote_hosts(
+    hosts: &Option<Vec<HostConfig>>, remote_token: Option<String>,
+) -> error::Result<Vec<remote_hosts::RemoteHost>> {
+    let mut remote_hosts: Vec<remote_hosts::RemoteHost> = Vec::new();
+    for host in hosts.iter().flatten() {
+        if host.name.trim().is_empty() {
+            return Err(BottomError::ConfigError(format!(
+                "the host at \"{}\" needs a name.",
+                host.address
+            )));
+        } else if remote_hosts::get_host_index(&remote_hosts, &host.name).is_some() {
+            return Err(BottomError::ConfigError(format!(
+                "there is more than one host called \"{}\".",
+                host.name
+            )));
+        } else if host.address.trim().is_empty() {
+            return Err(BottomError::ConfigError(format!(
+                "the host \"{}\" needs an address.",
+                host.name
+            )));
+        }
+
+        remote_hosts.push(remote_hosts::RemoteHost::new(
+            host.name.clone(),
+            host.address.clone(),
+            host.token.clone().or_else(|| remote_token.clone()),
+        ));
     }
+
+    Ok(remote_hosts)
+}
+
+fn get_process_filters(
+    process_filters: &Option<Vec<ProcessFilterConfig>>,
+) -> error::Result<Vec<SavedProcessFilter>> {
+    process_filters
+        .iter()
+        .flatten()
+        .map(|process_filter| {
+            if process_filter.name.trim().is_empty() {
+                return Err(BottomError::ConfigError(format!(
+                    "the process filter with the query \"{}\" needs a name.",
+                    process_filter.query
+                )));
+            } else if process_filter.query.trim().is_empty() {
+                return Err(BottomError::ConfigError(format!(
+                    "the process filter \"{}\" needs a query.",
+                    process_filter.name
+                )));
+            }
+
+            Ok(SavedProcessFilter {
+                name: process_filter.name.clone(),
+                query: process_filter.query.clone(),
+            })
+        })
+        .collect()
 }
 
 pub fn get_color_scheme(
@@ -980,7 +1806,7 @@ pub fn get_color_scheme(
 ) -> error::Result<ColourScheme> {
     if let Some(color) = matches.value_of("color") {
         // Highest priority is always command line flags...
-        return ColourScheme::from_str(color);
+        return get_named_color_scheme(color, config);
     } else if let Some(colors) = &config.colors {
         if !colors.is_empty() {
             // Then, give priority to custom colours...
@@ -988,13 +1814,13 @@ pub fn get_color_scheme(
         } else if let Some(flags) = &config.flags {
             // Last priority is config file flags...
             if let Some(color) = &flags.color {
-                return ColourScheme::from_str(color);
+                return get_named_color_scheme(color, config);
             }
         }
     } else if let Some(flags) = &config.flags {
         // Last priority is config file flags...
         if let Some(color) = &flags.color {
-            return ColourScheme::from_str(color);
+            return get_named_color_scheme(color, config);
         }
     }
 
@@ -1002,6 +1828,48 @@ pub fn get_color_scheme(
     Ok(ColourScheme::Default)
 }
 
+/// Returns every colour scheme that can be switched to: the `[colors]` ones if set, then the
+/// built-in ones, and then the themes in the config file.
+fn get_color_schemes(config: &Config) -> Vec<ColourScheme> {
+    let mut theme_names: Vec<&String> = config
+        .themes
+        .iter()
+        .flat_map(|themes| themes.keys())
+        .collect();
+    theme_names.sort();
+
+    config
+        .colors
+        .iter()
+        .filter(|colors| !colors.is_empty())
+        .map(|_colors| ColourScheme::Custom)
+        .chain(
+            ColourScheme::BUILT_IN
+                .iter()
+                // A theme of the same name is used instead of a built-in colour scheme.
+                .filter(|colour_scheme| {
+                    !theme_names
+                        .iter()
+                        .any(|theme_name| theme_name.as_str() == colour_scheme.get_name())
+                })
+                .cloned(),
+        )
+        .chain(
+            theme_names
+                .iter()
+                .map(|theme_name| ColourScheme::Theme(theme_name.to_string())),
+        )
+        .collect()
+}
+
+/// Returns the theme in the config file called `name`, or otherwise the built-in colour scheme.
+fn get_named_color_scheme(name: &str, config: &Config) -> error::Result<ColourScheme> {
+    match &config.themes {
+        Some(themes) if themes.contains_key(name) => Ok(ColourScheme::Theme(name.to_string())),
+        _ => ColourScheme::from_str(name),
+    }
+}
+
 fn get_mem_as_value(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("mem_as_value") {
         return true;
@@ -1085,6 +1953,91 @@ fn get_network_scale_type(matches: &clap::ArgMatches, config: &Config) -> AxisSc
     AxisScaling::Linear
 }
 
+fn get_network_y_range(config: &Config) -> error::Result<AxisRange> {
+    if let Some(flags) = &config.flags {
+        if let Some(network_y_max) = &flags.network_y_max {
+            if network_y_max.eq_ignore_ascii_case("auto") {
+                return Ok(AxisRange::Auto);
+            }
+
+            return match parse_network_rate(network_y_max) {
+                Some(bits_per_second) if bits_per_second > 0.0 => {
+                    Ok(AxisRange::Fixed(bits_per_second))
+                }
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{}\" is an invalid network y-axis maximum, use \"auto\" or a rate like \"1Gb\" or \"125MiB\".",
+                    network_y_max
+                ))),
+            };
+        }
+    }
+
+    Ok(AxisRange::Auto)
+}
+
+/// Parses a rate like "1Gb" or "125MiB", with an optional "/s", into bits per second.
+fn parse_network_rate(rate: &str) -> Option<f64> {
+    let rate = rate.trim();
+    let rate = rate.strip_suffix("/s").unwrap_or(rate);
+    let (rate, bits_per_unit) = if let Some(rate) = rate.strip_suffix('B') {
+        (rate, 8.0)
+    } else if let Some(rate) = rate.strip_suffix('b') {
+        (rate, 1.0)
+    } else {
+        return None;
+    };
+
+    let (rate, multiplier) = if let Some(rate) = rate.strip_suffix('i') {
+        let multiplier = match rate.chars().last()?.to_ascii_uppercase() {
+            'K' => KIBI_LIMIT_F64,
+            'M' => MEBI_LIMIT_F64,
+            'G' => GIBI_LIMIT_F64,
+            'T' => TEBI_LIMIT_F64,
+            _ => return None,
+        };
+        (&rate[..rate.len() - 1], multiplier)
+    } else {
+        match rate.chars().last()?.to_ascii_uppercase() {
+            'K' => (&rate[..rate.len() - 1], KILO_LIMIT_F64),
+            'M' => (&rate[..rate.len() - 1], MEGA_LIMIT_F64),
+            'G' => (&rate[..rate.len() - 1], GIGA_LIMIT_F64),
+            'T' => (&rate[..rate.len() - 1], TERA_LIMIT_F64),
+            _ => (rate, 1.0),
+        }
+    };
+
+    let value = rate.trim().parse::<f64>().ok()?;
+    if value.is_finite() {
+        Some(value * multiplier * bits_per_unit)
+    } else {
+        None
+    }
+}
+
+fn get_memory_y_range(config: &Config) -> error::Result<AxisRange> {
+    if let Some(flags) = &config.flags {
+        if let Some(memory_y_max) = &flags.memory_y_max {
+            if memory_y_max.eq_ignore_ascii_case("auto") {
+                return Ok(AxisRange::Auto);
+            }
+
+            let percentage = memory_y_max.trim();
+            let percentage = percentage.strip_suffix('%').unwrap_or(percentage);
+            return match percentage.trim().parse::<f64>() {
+                Ok(percentage) if percentage > 0.0 && percentage <= 100.0 => {
+                    Ok(AxisRange::Fixed(percentage))
+                }
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{}\" is an invalid memory y-axis maximum, use \"auto\" or a percentage like \"50%\".",
+                    memory_y_max
+                ))),
+            };
+        }
+    }
+
+    Ok(AxisRange::Fixed(100.0))
+}
+
 fn get_network_use_binary_prefix(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("network_use_binary_prefix") {
         return true;
@@ -1095,3 +2048,457 @@ fn get_network_use_binary_prefix(matches: &clap::ArgMatches, config: &Config) ->
     }
     false
 }
+
+fn get_use_disk_smart(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_smart") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_smart) = flags.disk_smart {
+            return disk_smart;
+        }
+    }
+    false
+}
+
+fn get_use_disk_inodes(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_inodes") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_inodes) = flags.disk_inodes {
+            return disk_inodes;
+        }
+    }
+    false
+}
+
+fn get_use_disk_latency(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_latency") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_latency) = flags.disk_latency {
+            return disk_latency;
+        }
+    }
+    false
+}
+
+fn get_use_ipmi(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("ipmi") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(ipmi) = flags.ipmi {
+            return ipmi;
+        }
+    }
+    false
+}
+
+fn get_network_highlight_errors(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_highlight_errors") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_highlight_errors) = flags.network_highlight_errors {
+            return network_highlight_errors;
+        }
+    }
+    false
+}
+
+fn get_use_network_wireless(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_wireless") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_wireless) = flags.network_wireless {
+            return network_wireless;
+        }
+    }
+    false
+}
+
+fn get_use_network_connections(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_connections") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_connections) = flags.network_connections {
+            return network_connections;
+        }
+    }
+    false
+}
+
+fn get_show_process_cpu_trend(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_cpu_trend") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_cpu_trend) = flags.process_cpu_trend {
+            return process_cpu_trend;
+        }
+    }
+    false
+}
+
+fn get_show_process_fds(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_fds") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_fds) = flags.process_fds {
+            return process_fds;
+        }
+    }
+    false
+}
+
+fn get_show_process_memory_breakdown(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_memory_breakdown") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_memory_breakdown) = flags.process_memory_breakdown {
+            return process_memory_breakdown;
+        }
+    }
+    false
+}
+
+fn get_show_process_latency(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_latency") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_latency) = flags.process_latency {
+            return process_latency;
+        }
+    }
+    false
+}
+
+fn get_show_process_security(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_security") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_security) = flags.process_security {
+            return process_security;
+        }
+    }
+    false
+}
+
+fn get_show_process_io_priority(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_io_priority") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_io_priority) = flags.process_io_priority {
+            return process_io_priority;
+        }
+    }
+    false
+}
+
+fn get_show_process_times(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_times") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_times) = flags.process_times {
+            return process_times;
+        }
+    }
+    false
+}
+
+fn get_persist_search_history(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("persist_search_history") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(persist_search_history) = flags.persist_search_history {
+            return persist_search_history;
+        }
+    }
+    false
+}
+
+fn get_global_filter(matches: &clap::ArgMatches, config: &Config) -> Option<String> {
+    if let Some(global_filter) = matches.value_of("global_filter") {
+        return Some(global_filter.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(global_filter) = &flags.global_filter {
+            return Some(global_filter.clone());
+        }
+    }
+    None
+}
+
+fn get_hide_kernel_threads(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("hide_kernel_threads") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(hide_kernel_threads) = flags.hide_kernel_threads {
+            return hide_kernel_threads;
+        }
+    }
+    false
+}
+
+fn get_network_split_graph(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(network_split_graph) = flags.network_split_graph {
+            return network_split_graph;
+        }
+    }
+    false
+}
+
+fn get_stacked_memory_graph(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(stacked_memory_graph) = flags.stacked_memory_graph {
+            return stacked_memory_graph;
+        }
+    }
+    false
+}
+
+fn get_cpu_frequency_graph(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("cpu_frequency_graph") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_frequency_graph) = flags.cpu_frequency_graph {
+            return cpu_frequency_graph;
+        }
+    }
+    false
+}
+
+fn get_kubelet_url(matches: &clap::ArgMatches, config: &Config) -> Option<String> {
+    if let Some(kubelet_url) = matches.value_of("kubelet_url") {
+        return Some(kubelet_url.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(kubelet_url) = &flags.kubelet_url {
+            return Some(kubelet_url.clone());
+        }
+    }
+    None
+}
+
+fn get_kubelet_insecure(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("kubelet_insecure") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(kubelet_insecure) = flags.kubelet_insecure {
+            return kubelet_insecure;
+        }
+    }
+    false
+}
+
+fn get_privilege_helper(matches: &clap::ArgMatches, config: &Config) -> Option<String> {
+    let privilege_helper = if let Some(privilege_helper) = matches.value_of("privilege_helper") {
+        privilege_helper
+    } else if let Some(privilege_helper) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.privilege_helper.as_deref())
+    {
+        privilege_helper
+    } else {
+        DEFAULT_PRIVILEGE_HELPER
+    };
+
+    // An empty helper disables retrying as root.
+    if privilege_helper.trim().is_empty() {
+        None
+    } else {
+        Some(privilege_helper.to_string())
+    }
+}
+
+fn get_prometheus_address(matches: &clap::ArgMatches, config: &Config) -> Option<String> {
+    if let Some(prometheus_address) = matches.value_of("prometheus_address") {
+        return Some(prometheus_address.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(prometheus_address) = &flags.prometheus_address {
+            return Some(prometheus_address.clone());
+        }
+    }
+    None
+}
+
+/// Returns the address of the remote server to connect to, with `--ssh` destinations as
+/// `ssh://<destination>`.
+fn get_connect_address(matches: &clap::ArgMatches) -> Option<String> {
+    if let Some(destination) = matches.value_of("ssh") {
+        Some(format!("ssh://{}", destination))
+    } else {
+        matches
+            .value_of("connect")
+            .map(|address| address.to_string())
+    }
+}
+
+fn get_ssh_command(matches: &clap::ArgMatches, config: &Config) -> String {
+    if let Some(ssh_command) = matches.value_of("ssh_command") {
+        return ssh_command.to_string();
+    } else if let Some(flags) = &config.flags {
+        if let Some(ssh_command) = &flags.ssh_command {
+            return ssh_command.clone();
+        }
+    }
+    DEFAULT_SSH_COMMAND.to_string()
+}
+
+fn get_remote_token(matches: &clap::ArgMatches, config: &Config) -> Option<String> {
+    if let Some(remote_token) = matches.value_of("remote_token") {
+        return Some(remote_token.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(remote_token) = &flags.remote_token {
+            return Some(remote_token.clone());
+        }
+    }
+    None
+}
+
+fn get_serve_tls(
+    matches: &clap::ArgMatches, config: &Config,
+) -> error::Result<Option<ServerTlsOptions>> {
+    let flags = config.flags.as_ref();
+    let cert_path = matches
+        .value_of("serve_tls_cert")
+        .or_else(|| flags.and_then(|flags| flags.serve_tls_cert.as_deref()));
+    let key_path = matches
+        .value_of("serve_tls_key")
+        .or_else(|| flags.and_then(|flags| flags.serve_tls_key.as_deref()));
+
+    match (cert_path, key_path) {
+        (Some(cert_path), Some(key_path)) => Ok(Some(ServerTlsOptions {
+            cert_path: PathBuf::from(cert_path),
+            key_path: PathBuf::from(key_path),
+        })),
+        (None, None) => Ok(None),
+        _ => Err(BottomError::ConfigError(
+            "serve_tls_cert and serve_tls_key have to be set together.".to_string(),
+        )),
+    }
+}
+
+fn get_history_export_format(config: &Config) -> error::Result<HistoryFormat> {
+    if let Some(flags) = &config.flags {
+        if let Some(history_export_format) = &flags.history_export_format {
+            return history_export_format.parse::<HistoryFormat>();
+        }
+    }
+    Ok(HistoryFormat::default())
+}
+
+/// Returns the directory to export histories to, which defaults to the current one.
+fn get_history_export_dir(config: &Config) -> PathBuf {
+    if let Some(flags) = &config.flags {
+        if let Some(history_export_dir) = &flags.history_export_dir {
+            return PathBuf::from(history_export_dir);
+        }
+    }
+    std::env::current_dir().unwrap_or_default()
+}
+
+fn get_snapshot_format(config: &Config) -> error::Result<SnapshotFormat> {
+    if let Some(flags) = &config.flags {
+        if let Some(snapshot_format) = &flags.snapshot_format {
+            return snapshot_format.parse::<SnapshotFormat>();
+        }
+    }
+    Ok(SnapshotFormat::default())
+}
+
+/// Returns the directory to save snapshots of the screen to, which defaults to the current one.
+fn get_snapshot_dir(config: &Config) -> PathBuf {
+    if let Some(flags) = &config.flags {
+        if let Some(snapshot_dir) = &flags.snapshot_dir {
+            return PathBuf::from(snapshot_dir);
+        }
+    }
+    std::env::current_dir().unwrap_or_default()
+}
+
+fn get_metrics_log(
+    matches: &clap::ArgMatches, config: &Config,
+) -> error::Result<Option<MetricsLogOptions>> {
+    let metrics_log = config.metrics_log.clone().unwrap_or_default();
+    let path = match matches
+        .value_of("metrics_log")
+        .or(metrics_log.path.as_deref())
+    {
+        Some(path) => PathBuf::from(path),
+        None => return Ok(None),
+    };
+
+    let format = match metrics_log.format.as_deref() {
+        Some(format) if format.eq_ignore_ascii_case("csv") => MetricsLogFormat::Csv,
+        Some(format) if format.eq_ignore_ascii_case("jsonl") => MetricsLogFormat::JsonLines,
+        Some(format) => {
+            return Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid metrics log format, use \"csv\" or \"jsonl\".",
+                format
+            )));
+        }
+        None => match path.extension().and_then(|extension| extension.to_str()) {
+            Some("jsonl" | "json") => MetricsLogFormat::JsonLines,
+            _ => MetricsLogFormat::Csv,
+        },
+    };
+
+    let max_size = metrics_log
+        .max_size
+        .as_ref()
+        .map(|max_size| match parse_network_rate(max_size) {
+            Some(bits) if bits >= 8.0 => Ok((bits / 8.0) as u64),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid metrics log size, use a size like \"10MiB\".",
+                max_size
+            ))),
+        })
+        .transpose()?;
+
+    Ok(Some(MetricsLogOptions {
+        path,
+        format,
+        metrics: metrics_log.metrics,
+        max_size,
+        max_files: metrics_log
+            .max_files
+            .unwrap_or(DEFAULT_METRICS_LOG_MAX_FILES),
+    }))
+}
+
+fn get_influxdb(
+    matches: &clap::ArgMatches, config: &Config,
+) -> error::Result<Option<InfluxDbOptions>> {
+    let influxdb = config.influxdb.clone().unwrap_or_default();
+    let url = match matches.value_of("influxdb_url").or(influxdb.url.as_deref()) {
+        Some(url) => url.to_string(),
+        None => return Ok(None),
+    };
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(BottomError::ConfigError(format!(
+            "\"{}\" is an invalid InfluxDB URL, use an http:// or https:// URL.",
+            url
+        )));
+    }
+
+    Ok(Some(InfluxDbOptions {
+        url,
+        token: influxdb.token,
+        interval: std::time::Duration::from_secs(
+            influxdb
+                .interval
+                .unwrap_or(DEFAULT_INFLUXDB_INTERVAL_IN_SECONDS),
+        ),
+        metrics: influxdb.metrics,
+        tags: influxdb.tags.unwrap_or_default().into_iter().collect(),
+    }))
+}
+
+fn get_nut_server(matches: &clap::ArgMatches, config: &Config) -> Option<String> {
+    if let Some(nut_server) = matches.value_of("nut_server") {
+        return Some(nut_server.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(nut_server) = &flags.nut_server {
+            return Some(nut_server.clone());
+        }
+    }
+    None
+}
@@ -1,5 +1,5 @@
-use crate::app::layout_manager::*;
-use crate::error::Result;
+use crate::app::{layout_manager::*, GraphStyle};
+use crate::error::{BottomError, Result};
 use serde::{Deserialize, Serialize};
 
 /// Represents a row.  This has a length of some sort (optional) and a vector
@@ -33,6 +33,12 @@ impl Row {
                         let width_ratio = widget.ratio.unwrap_or(1);
                         total_col_ratio += width_ratio;
                         let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                        let graph_style = widget
+                            .graph_style
+                            .as_deref()
+                            .map(|graph_style| graph_style.parse::<GraphStyle>())
+                            .transpose()?;
+                        check_host(widget, &widget_type)?;
 
                         if let Some(default_widget_type_val) = default_widget_type {
                             if *default_widget_type_val == widget_type && *default_widget_count > 0
@@ -75,6 +81,9 @@ impl Row {
                                                     .width_ratio(17)
                                                     .widget_type(BottomWidgetType::Cpu)
                                                     .widget_id(cpu_id)
+                                                    .graph_style(graph_style)
+                                                    .is_filled(widget.filled)
+                                                    .host(widget.host.clone())
                                                     .flex_grow(true)
                                                     .build(),
                                             ])
@@ -87,6 +96,9 @@ impl Row {
                                                     .width_ratio(17)
                                                     .widget_type(BottomWidgetType::Cpu)
                                                     .widget_id(cpu_id)
+                                                    .graph_style(graph_style)
+                                                    .is_filled(widget.filled)
+                                                    .host(widget.host.clone())
                                                     .flex_grow(true)
                                                     .build(),
                                                 BottomWidget::builder()
@@ -150,6 +162,9 @@ impl Row {
                                     .children(vec![BottomWidget::builder()
                                         .widget_type(widget_type)
                                         .widget_id(*iter_id)
+                                        .graph_style(graph_style)
+                                        .is_filled(widget.filled)
+                                        .host(widget.host.clone())
                                         .build()])
                                     .build()])
                                 .build(),
@@ -165,6 +180,12 @@ impl Row {
 
                         for widget in child {
                             let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                            let graph_style = widget
+                                .graph_style
+                                .as_deref()
+                                .map(|graph_style| graph_style.parse::<GraphStyle>())
+                                .transpose()?;
+                            check_host(widget, &widget_type)?;
                             *iter_id += 1;
                             let col_row_height_ratio = widget.ratio.unwrap_or(1);
                             total_col_row_ratio += col_row_height_ratio;
@@ -211,6 +232,9 @@ impl Row {
                                                         .width_ratio(17)
                                                         .widget_type(BottomWidgetType::Cpu)
                                                         .widget_id(cpu_id)
+                                                        .graph_style(graph_style)
+                                                        .is_filled(widget.filled)
+                                                        .host(widget.host.clone())
                                                         .flex_grow(true)
                                                         .build(),
                                                 ])
@@ -226,6 +250,9 @@ impl Row {
                                                         .width_ratio(17)
                                                         .widget_type(BottomWidgetType::Cpu)
                                                         .widget_id(cpu_id)
+                                                        .graph_style(graph_style)
+                                                        .is_filled(widget.filled)
+                                                        .host(widget.host.clone())
                                                         .flex_grow(true)
                                                         .build(),
                                                     BottomWidget::builder()
@@ -289,6 +316,9 @@ impl Row {
                                         .children(vec![BottomWidget::builder()
                                             .widget_type(widget_type)
                                             .widget_id(*iter_id)
+                                            .graph_style(graph_style)
+                                            .is_filled(widget.filled)
+                                            .host(widget.host.clone())
                                             .build()])
                                         .build(),
                                 ),
@@ -351,4 +381,95 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// How the graph of the widget is drawn, if it has one.
+    pub graph_style: Option<String>,
+    /// Whether the areas below the lines of the graph are filled, if it has one.
+    pub filled: Option<bool>,
+    /// The name of the `[[host]]` whose data the widget shows, for CPU and memory widgets.
+    pub host: Option<String>,
+}
+
+/// Checks that only the widgets that can show the data of a remote host are assigned one.
+fn check_host(widget: &FinalWidget, widget_type: &BottomWidgetType) -> Result<()> {
+    match &widget.host {
+        Some(host) if !widget_type.can_have_host() => Err(BottomError::ConfigError(format!(
+            "the {} widget cannot show the host \"{}\", as only cpu and mem widgets can show a remote host.",
+            widget.widget_type, host
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Where a widget is in the rows of a layout: the index of its row, the index of the row's child
+/// that it is or is in, and its index in that column if it is in one.
+pub type FinalWidgetPath = (usize, usize, Option<usize>);
+
+/// Returns the paths of the widgets in `rows`, in the order that the widgets are given IDs.
+pub fn get_final_widget_paths(rows: &[Row]) -> Vec<FinalWidgetPath> {
+    let mut paths = Vec::new();
+    for (row_index, row) in rows.iter().enumerate() {
+        for (child_index, row_child) in row.child.iter().flatten().enumerate() {
+            match row_child {
+                RowChildren::Widget(_) => paths.push((row_index, child_index, None)),
+                RowChildren::Col { child, .. } => paths.extend(
+                    (0..child.len()).map(|col_index| (row_index, child_index, Some(col_index))),
+                ),
+            }
+        }
+    }
+
+    paths
+}
+
+pub fn get_final_widget_mut(rows: &mut [Row], path: FinalWidgetPath) -> Option<&mut FinalWidget> {
+    let (row_index, child_index, col_index) = path;
+    let row_child = rows
+        .get_mut(row_index)?
+        .child
+        .as_mut()?
+        .get_mut(child_index)?;
+    match (row_child, col_index) {
+        (RowChildren::Widget(widget), None) => Some(widget),
+        (RowChildren::Col { child, .. }, Some(col_index)) => child.get_mut(col_index),
+        _ => None,
+    }
+}
+
+/// Inserts `widget` right after the widget at `path`, in the same row or column.
+pub fn insert_final_widget_after(rows: &mut [Row], path: FinalWidgetPath, widget: FinalWidget) {
+    let (row_index, child_index, col_index) = path;
+    if let Some(row_children) = rows.get_mut(row_index).and_then(|row| row.child.as_mut()) {
+        match (row_children.get_mut(child_index), col_index) {
+            (Some(RowChildren::Col { child, .. }), Some(col_index)) => {
+                child.insert(col_index + 1, widget);
+            }
+            (Some(_), _) => row_children.insert(child_index + 1, RowChildren::Widget(widget)),
+            (None, _) => {}
+        }
+    }
+}
+
+/// Removes the widget at `path`, along with its column and row if they are left empty.
+pub fn remove_final_widget(rows: &mut Vec<Row>, path: FinalWidgetPath) {
+    let (row_index, child_index, col_index) = path;
+    if let Some(row_children) = rows.get_mut(row_index).and_then(|row| row.child.as_mut()) {
+        match (row_children.get_mut(child_index), col_index) {
+            (Some(RowChildren::Col { child, .. }), Some(col_index)) => {
+                if col_index < child.len() {
+                    child.remove(col_index);
+                }
+                if child.is_empty() {
+                    row_children.remove(child_index);
+                }
+            }
+            (Some(_), _) => {
+                row_children.remove(child_index);
+            }
+            (None, _) => {}
+        }
+
+        if row_children.is_empty() {
+            rows.remove(row_index);
+        }
+    }
 }
@@ -0,0 +1,585 @@
+//! Recording every harvest to a file, for `--record`, and playing a recording back through the TUI
+//! in place of the collection thread, for `--replay`.
+//!
+//! A recording starts with [`MAGIC`], followed by a frame for every harvest.  A frame is the Unix
+//! time of the harvest in milliseconds as a big-endian `u64`, the length of the rest of the frame
+//! in bytes as a big-endian `u32`, and the harvested data as a deflate-compressed TOML document.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::event::{KeyEvent, MouseEvent};
+
+use crate::{
+    app::data_harvester::Data, constants::STALE_MAX_MILLISECONDS, utils::gen_util, BottomEvent,
+    ThreadControlEvent,
+};
+
+/// What every recording starts with, which also gives the version of the format.
+const MAGIC: &[u8] = b"bottom recording v1\n";
+
+/// The largest frame that is read, so that a bad length does not exhaust the memory.
+const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// How long to wait at most between two frames, so that a gap in a recording, such as from the
+/// host being suspended, does not stall the replay.
+const MAX_FRAME_GAP: Duration = Duration::from_secs(60);
+
+/// How often the replay thread checks whether it should stop while it has nothing to play.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How a recording is moved through while it is replayed, as set by the keys of the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayControl {
+    TogglePause,
+    /// Pauses, and shows the frame after the current one.
+    Step,
+    /// Moves the given number of seconds forward, or back if it is negative.
+    Seek(i64),
+    /// Moves to the given tenth of the recording, where 0 is the start.
+    SeekToTenth(u8),
+}
+
+/// Appends every harvest passed to [`Recorder::record`] to a recording.
+pub struct Recorder {
+    writer: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Opens the recording at `path` to add to it, or creates it if there is none.  A frame that
+    /// was cut short at the end, as bottom was stopped while writing it, is removed first, as it
+    /// would otherwise swallow the frames that are added after it.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let len = file.metadata()?.len();
+        if len == 0 {
+            file.write_all(MAGIC)?;
+        } else {
+            let complete_len = Recording::from_reader(&mut file)?.get_complete_len();
+            if complete_len < len {
+                file.set_len(complete_len)?;
+            }
+        }
+
+        Ok(Recorder {
+            writer: BufWriter::new(file),
+        })
+    }
+
+    pub fn record(&mut self, data: &Data) -> io::Result<()> {
+        write_frame(&mut self.writer, get_unix_millis(), data)?;
+        self.writer.flush()
+    }
+}
+
+/// Where a frame is in a recording.
+#[derive(Debug, Clone, Copy)]
+struct FrameIndex {
+    timestamp: u64,
+    offset: u64,
+    len: usize,
+}
+
+/// A recording that is open for replaying, whose frames are only read and decoded once they are
+/// needed.
+pub struct Recording<R> {
+    reader: R,
+    frames: Vec<FrameIndex>,
+}
+
+impl Recording<BufReader<File>> {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let recording = Recording::from_reader(BufReader::new(File::open(path)?))?;
+        if recording.frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the recording has no frames",
+            ));
+        }
+
+        Ok(recording)
+    }
+}
+
+impl<R: Read + Seek> Recording<R> {
+    /// Finds every frame in the recording.  A frame that was cut short, as bottom was stopped
+    /// while writing it, ends the recording.
+    fn from_reader(mut reader: R) -> io::Result<Self> {
+        read_magic(&mut reader)?;
+
+        let mut frames: Vec<FrameIndex> = Vec::new();
+        let end = reader.seek(SeekFrom::End(0))?;
+        let mut offset = reader.seek(SeekFrom::Start(MAGIC.len() as u64))?;
+        let mut header = [0; 12];
+        while offset + header.len() as u64 <= end {
+            reader.read_exact(&mut header)?;
+            let mut timestamp = [0; 8];
+            let mut len = [0; 4];
+            timestamp.copy_from_slice(&header[..8]);
+            len.copy_from_slice(&header[8..]);
+            let len = u32::from_be_bytes(len) as usize;
+            offset += header.len() as u64;
+            if len > MAX_FRAME_LEN || offset + len as u64 > end {
+                break;
+            }
+
+            // The timeline must only move forward, even if the clock of the host did not.
+            let timestamp = u64::from_be_bytes(timestamp).max(
+                frames
+                    .last()
+                    .map(|frame| frame.timestamp)
+                    .unwrap_or_default(),
+            );
+            frames.push(FrameIndex {
+                timestamp,
+                offset,
+                len,
+            });
+            offset = reader.seek(SeekFrom::Current(len as i64))?;
+        }
+
+        Ok(Recording { reader, frames })
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns where the last frame that is whole ends.
+    fn get_complete_len(&self) -> u64 {
+        self.frames
+            .last()
+            .map(|frame| frame.offset + frame.len as u64)
+            .unwrap_or(MAGIC.len() as u64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the Unix time in milliseconds of the frame at `index`.
+    pub fn get_timestamp(&self, index: usize) -> u64 {
+        self.frames[index].timestamp
+    }
+
+    /// Returns the index of the last frame at or before the Unix time `timestamp`, or the first
+    /// frame if they are all after it.
+    pub fn get_index_at(&self, timestamp: u64) -> usize {
+        self.frames
+            .partition_point(|frame| frame.timestamp <= timestamp)
+            .saturating_sub(1)
+    }
+
+    /// Reads and decodes the frame at `index`.
+    pub fn read_frame(&mut self, index: usize) -> io::Result<Data> {
+        let frame = self.frames[index];
+        let mut compressed = vec![0; frame.len];
+        self.reader.seek(SeekFrom::Start(frame.offset))?;
+        self.reader.read_exact(&mut compressed)?;
+
+        let text = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_FRAME_LEN)
+            .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unable to decompress a frame, {:?}", err),
+            )
+        })?;
+        let text = String::from_utf8(text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        toml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+fn read_magic<R: Read>(reader: &mut R) -> io::Result<()> {
+    let mut magic = [0; MAGIC.len()];
+    match reader.read_exact(&mut magic) {
+        Ok(()) if magic == MAGIC => Ok(()),
+        Ok(()) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the file is not a bottom recording",
+        )),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the file is not a bottom recording",
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_frame<W: Write>(writer: &mut W, timestamp: u64, data: &Data) -> io::Result<()> {
+    let text = toml::Value::try_from(data)
+        .and_then(|value| toml::to_string(&value))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(text.as_bytes(), 6);
+
+    writer.write_all(&timestamp.to_be_bytes())?;
+    writer.write_all(&(compressed.len() as u32).to_be_bytes())?;
+    writer.write_all(&compressed)
+}
+
+fn get_unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Formats the Unix time `timestamp` in milliseconds as the local date and time.
+fn format_timestamp(timestamp: u64) -> String {
+    let seconds = (timestamp / 1000) as i64;
+    time::OffsetDateTime::from_unix_timestamp(seconds)
+        .map(|utc_time| utc_time.to_offset(gen_util::get_local_offset(seconds)))
+        .ok()
+        .and_then(|local_time| {
+            local_time
+                .format(&time::macros::format_description!(
+                    "[year]-[month]-[day] [hour]:[minute]:[second]"
+                ))
+                .ok()
+        })
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Formats a duration in milliseconds as minutes and seconds.
+fn format_position(millis: u64) -> String {
+    let seconds = millis / 1000;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Starts a thread that sends the frames of `recording` to the TUI at the pace that they were
+/// recorded at, and moves through the recording as the [`ThreadControlEvent::Replay`] events it
+/// receives ask.  It takes the place of the collection thread, whose other control events are
+/// ignored, apart from resets.
+pub fn create_replay_thread(
+    sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>,
+    control_receiver: mpsc::Receiver<ThreadControlEvent>, termination_ctrl_lock: Arc<Mutex<bool>>,
+    recording: Recording<BufReader<File>>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut player = Player::new(recording, sender);
+        if player.send_status("Replaying").is_err() {
+            return;
+        }
+
+        loop {
+            if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
+                if *is_terminated {
+                    break;
+                }
+            }
+
+            let wait_time = match player.get_time_until_next_frame() {
+                Some(wait_time) if wait_time == Duration::ZERO => {
+                    if player.play_next_frame().is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                Some(wait_time) => wait_time.min(IDLE_POLL_INTERVAL),
+                None => IDLE_POLL_INTERVAL,
+            };
+
+            let result = match control_receiver.recv_timeout(wait_time) {
+                Ok(ThreadControlEvent::Replay(control)) => player.control(control),
+                // Data collection has been reset, so the frames up to the current one are sent
+                // again.
+                Ok(ThreadControlEvent::Reset) => player.seek(player.get_current_index()),
+                Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => Ok(()),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+            if result.is_err() {
+                // The TUI has gone away.
+                break;
+            }
+        }
+    })
+}
+
+type SendResult = Result<(), mpsc::SendError<BottomEvent<KeyEvent, MouseEvent>>>;
+
+/// The state of a replay.
+struct Player {
+    recording: Recording<BufReader<File>>,
+    sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>,
+    /// The index of the frame to send next, which is the length of the recording at its end.
+    next_index: usize,
+    /// When the next frame is due, unless paused.
+    next_due: Instant,
+    is_paused: bool,
+    /// The time that the first frame is placed at, relative to which every other frame is placed,
+    /// so that graphs line up no matter how the recording is moved through.
+    start_instant: Instant,
+}
+
+impl Player {
+    fn new(
+        recording: Recording<BufReader<File>>,
+        sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>,
+    ) -> Self {
+        Player {
+            recording,
+            sender,
+            next_index: 0,
+            next_due: Instant::now(),
+            is_paused: false,
+            start_instant: Instant::now(),
+        }
+    }
+
+    /// Returns the index of the frame that is shown, or the first frame if none is yet.
+    fn get_current_index(&self) -> usize {
+        self.next_index.saturating_sub(1)
+    }
+
+    fn get_time_until_next_frame(&self) -> Option<Duration> {
+        if self.is_paused || self.next_index >= self.recording.len() {
+            None
+        } else {
+            Some(self.next_due.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    /// Reads the frame at `index`, and places it on the timeline of the replay.
+    fn read_frame(&mut self, index: usize) -> io::Result<Box<Data>> {
+        let mut data = self.recording.read_frame(index)?;
+        data.last_collection_time = self.start_instant
+            + Duration::from_millis(
+                self.recording.get_timestamp(index) - self.recording.get_timestamp(0),
+            );
+        Ok(Box::new(data))
+    }
+
+    /// Sends the next frame, and works out when the one after it is due.
+    fn play_next_frame(&mut self) -> SendResult {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.set_next_due(index);
+
+        match self.read_frame(index) {
+            Ok(data) => self.sender.send(BottomEvent::Update(data))?,
+            Err(err) => self.send_error(index, &err)?,
+        }
+
+        if self.next_index == self.recording.len() && !self.is_paused {
+            self.send_status("Reached the end of the recording")?;
+        }
+        Ok(())
+    }
+
+    fn set_next_due(&mut self, index: usize) {
+        let gap = if index + 1 < self.recording.len() {
+            Duration::from_millis(
+                self.recording.get_timestamp(index + 1) - self.recording.get_timestamp(index),
+            )
+        } else {
+            Duration::ZERO
+        };
+        self.next_due = Instant::now() + gap.min(MAX_FRAME_GAP);
+    }
+
+    fn control(&mut self, control: ReplayControl) -> SendResult {
+        match control {
+            ReplayControl::TogglePause => {
+                if self.is_paused {
+                    self.is_paused = false;
+                    if self.next_index >= self.recording.len() {
+                        // Playing again from the end starts over.
+                        self.seek(0)?;
+                    } else {
+                        self.next_due = Instant::now();
+                    }
+                    self.send_status("Playing")
+                } else {
+                    self.is_paused = true;
+                    self.send_status("Paused")
+                }
+            }
+            ReplayControl::Step => {
+                self.is_paused = true;
+                if self.next_index < self.recording.len() {
+                    self.play_next_frame()?;
+                }
+                self.send_status("Paused")
+            }
+            ReplayControl::Seek(seconds) => {
+                let timestamp = self.recording.get_timestamp(self.get_current_index());
+                let offset = seconds.unsigned_abs() * 1000;
+                let target = if seconds < 0 {
+                    timestamp.saturating_sub(offset)
+                } else {
+                    timestamp.saturating_add(offset)
+                };
+                self.seek(self.recording.get_index_at(target))?;
+                self.send_status(if self.is_paused { "Paused" } else { "Playing" })
+            }
+            ReplayControl::SeekToTenth(tenth) => {
+                let start = self.recording.get_timestamp(0);
+                let end = self.recording.get_timestamp(self.recording.len() - 1);
+                let target = start + (end - start) * u64::from(tenth.min(10)) / 10;
+                self.seek(self.recording.get_index_at(target))?;
+                self.send_status(if self.is_paused { "Paused" } else { "Playing" })
+            }
+        }
+    }
+
+    /// Shows the frame at `index`, along with the frames before it that graphs can show.
+    fn seek(&mut self, index: usize) -> SendResult {
+        let timestamp = self.recording.get_timestamp(index);
+        let first_index = self
+            .recording
+            .frames
+            .partition_point(|frame| frame.timestamp + STALE_MAX_MILLISECONDS < timestamp);
+
+        let mut history = Vec::with_capacity(index - first_index);
+        for history_index in first_index..index {
+            // A frame that cannot be read leaves a gap in the graphs, which is not worth a toast.
+            if let Ok(data) = self.read_frame(history_index) {
+                history.push(data);
+            }
+        }
+        self.sender.send(BottomEvent::ReplayReset(history))?;
+
+        self.next_index = index;
+        self.play_next_frame()
+    }
+
+    /// Sends where the replay is at, along with what it is doing.
+    fn send_status(&self, status: &str) -> SendResult {
+        let index = self.get_current_index();
+        let start = self.recording.get_timestamp(0);
+        let timestamp = self.recording.get_timestamp(index);
+        let end = self.recording.get_timestamp(self.recording.len() - 1);
+        self.sender.send(BottomEvent::ReplayStatus(
+            format!(
+                "{} at {} ({} of {})",
+                status,
+                format_timestamp(timestamp),
+                format_position(timestamp - start),
+                format_position(end - start)
+            ),
+            false,
+        ))
+    }
+
+    fn send_error(&self, index: usize, err: &io::Error) -> SendResult {
+        self.sender.send(BottomEvent::ReplayStatus(
+            format!(
+                "Unable to read frame {} of the recording: {}",
+                index + 1,
+                err
+            ),
+            true,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::app::data_harvester::{cpu::CpuData, memory::MemHarvest};
+
+    fn get_data(cpu_usage: f64) -> Data {
+        Data {
+            cpu: Some(vec![CpuData {
+                cpu_prefix: "AVG".to_string(),
+                cpu_usage,
+                ..CpuData::default()
+            }]),
+            memory: Some(MemHarvest {
+                mem_total_in_kib: 1024,
+                mem_used_in_kib: 512,
+                ..MemHarvest::default()
+            }),
+            ..Data::default()
+        }
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let mut bytes = MAGIC.to_vec();
+        write_frame(&mut bytes, 2000, &get_data(10.0)).unwrap();
+        write_frame(&mut bytes, 1000, &get_data(20.0)).unwrap();
+        write_frame(&mut bytes, 5000, &get_data(30.0)).unwrap();
+
+        let mut recording = Recording::from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(recording.len(), 3);
+        // A timestamp that goes back is moved up to the one before it.
+        assert_eq!(recording.get_timestamp(1), 2000);
+        assert_eq!(recording.get_timestamp(2), 5000);
+
+        let data = recording.read_frame(2).unwrap();
+        assert_eq!(data.cpu.unwrap()[0].cpu_usage, 30.0);
+        assert_eq!(data.memory.unwrap().mem_used_in_kib, 512);
+        let data = recording.read_frame(0).unwrap();
+        assert_eq!(data.cpu.unwrap()[0].cpu_usage, 10.0);
+    }
+
+    #[test]
+    fn test_recording_ignores_cut_short_frame() {
+        let mut bytes = MAGIC.to_vec();
+        write_frame(&mut bytes, 1000, &get_data(10.0)).unwrap();
+        write_frame(&mut bytes, 2000, &get_data(20.0)).unwrap();
+        bytes.truncate(bytes.len() - 3);
+
+        let recording = Recording::from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(recording.len(), 1);
+    }
+
+    #[test]
+    fn test_recorder_removes_cut_short_frame() {
+        let path = std::env::temp_dir().join(format!(
+            "bottom_recording_test_{}_cut_short",
+            std::process::id()
+        ));
+        let mut bytes = MAGIC.to_vec();
+        write_frame(&mut bytes, 1000, &get_data(10.0)).unwrap();
+        write_frame(&mut bytes, 2000, &get_data(20.0)).unwrap();
+        bytes.truncate(bytes.len() - 3);
+        std::fs::write(&path, bytes).unwrap();
+
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&get_data(30.0)).unwrap();
+        drop(recorder);
+
+        let mut recording = Recording::open(&path).unwrap();
+        assert_eq!(recording.len(), 2);
+        assert_eq!(
+            recording.read_frame(1).unwrap().cpu.unwrap()[0].cpu_usage,
+            30.0
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_recording_rejects_other_files() {
+        assert!(Recording::from_reader(Cursor::new(b"[[row]]\n".to_vec())).is_err());
+        assert!(Recording::from_reader(Cursor::new(Vec::new())).is_err());
+    }
+
+    #[test]
+    fn test_get_index_at() {
+        let mut bytes = MAGIC.to_vec();
+        for timestamp in [1000, 2000, 3000] {
+            write_frame(&mut bytes, timestamp, &Data::default()).unwrap();
+        }
+        let recording = Recording::from_reader(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(recording.get_index_at(0), 0);
+        assert_eq!(recording.get_index_at(1000), 0);
+        assert_eq!(recording.get_index_at(2500), 1);
+        assert_eq!(recording.get_index_at(9000), 2);
+    }
+}
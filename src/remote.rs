@@ -0,0 +1,11 @@
+//! Streaming harvested data from one bottom to another, so that a remote host can be watched in the
+//! local TUI.
+//!
+//! `btm --serve <ADDRESS>` harvests data without a TUI and sends every harvest to the clients that
+//! connect to it, and `btm --connect <ADDRESS>` shows the data that it receives from a server in
+//! place of the data of the local host.  `btm --ssh <DESTINATION>` does the same over SSH, by
+//! running `btm --serve_stdio` on the remote host.
+
+pub mod client;
+pub mod protocol;
+pub mod server;
@@ -0,0 +1,514 @@
+//! Receiving the harvests of a remote server in place of the collection thread, for `--connect`,
+//! or next to it, for the remote hosts in the config file.
+
+use std::{
+    io::{self, BufReader, Read, Write},
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    process::{Child, Command, Stdio},
+    sync::{mpsc, Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyEvent, MouseEvent};
+
+use super::protocol::{self, Message, PROTOCOL_VERSION};
+use crate::{
+    app::{data_harvester::Data, remote_hosts::RemoteHost, AppConfigFields},
+    BottomEvent, ThreadControlEvent,
+};
+
+/// How long to wait before connecting again after the connection to the server is lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// How long to wait on the server to accept the connection, and then to answer the hello.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait on an update from the server, which is far longer than any sensible refresh
+/// rate, before taking the connection to be lost.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// How long to wait on a server that stops reading before hanging up on it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often to check whether the process that carries a connection has gone quiet for too long.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The longest line that is kept of what the process that carries the connection prints.
+const MAX_STDERR_LINE_LEN: usize = 1024;
+
+/// Starts a thread that sends the updates of the server at the connect address to the TUI,
+/// reconnecting whenever the connection is lost.  It takes the place of the collection thread,
+/// whose control events it ignores, as the server decides what is harvested and how often.
+pub fn create_remote_thread(
+    sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>,
+    control_receiver: mpsc::Receiver<ThreadControlEvent>, termination_ctrl_lock: Arc<Mutex<bool>>,
+    termination_ctrl_cvar: Arc<Condvar>, app_config_fields: &AppConfigFields,
+) -> thread::JoinHandle<()> {
+    let address = app_config_fields
+        .connect_address
+        .clone()
+        .unwrap_or_default();
+
+    spawn_receiving_thread(
+        Receiver {
+            description: address.clone(),
+            address,
+            token: app_config_fields.remote_token.clone(),
+            ssh_command: app_config_fields.ssh_command.clone(),
+            control_receiver: Some(control_receiver),
+        },
+        sender,
+        BottomEvent::Update,
+        termination_ctrl_lock,
+        termination_ctrl_cvar,
+    )
+}
+
+/// Starts a thread that sends the updates of the remote host at `host_index` to the TUI as
+/// [`BottomEvent::HostUpdate`]s, reconnecting whenever the connection is lost.
+pub fn create_host_thread(
+    sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, host_index: usize, host: &RemoteHost,
+    termination_ctrl_lock: Arc<Mutex<bool>>, termination_ctrl_cvar: Arc<Condvar>,
+    app_config_fields: &AppConfigFields,
+) -> thread::JoinHandle<()> {
+    spawn_receiving_thread(
+        Receiver {
+            description: format!("{} at {}", host.name, host.address),
+            address: host.address.clone(),
+            token: host.token.clone(),
+            ssh_command: app_config_fields.ssh_command.clone(),
+            control_receiver: None,
+        },
+        sender,
+        move |data| BottomEvent::HostUpdate(host_index, data),
+        termination_ctrl_lock,
+        termination_ctrl_cvar,
+    )
+}
+
+/// What a receiving thread connects to, and how.
+struct Receiver {
+    address: String,
+    /// How the server is referred to in errors.
+    description: String,
+    token: Option<String>,
+    ssh_command: String,
+    /// The control events of the TUI meant for the collection thread, which are ignored.
+    control_receiver: Option<mpsc::Receiver<ThreadControlEvent>>,
+}
+
+fn spawn_receiving_thread<F>(
+    receiver: Receiver, sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, to_event: F,
+    termination_ctrl_lock: Arc<Mutex<bool>>, termination_ctrl_cvar: Arc<Condvar>,
+) -> thread::JoinHandle<()>
+where
+    F: Fn(Box<Data>) -> BottomEvent<KeyEvent, MouseEvent> + Send + 'static,
+{
+    thread::spawn(move || loop {
+        let err = match receive_updates(&receiver, &sender, &to_event) {
+            // The TUI has gone away.
+            Ok(()) => break,
+            Err(err) => err,
+        };
+        if sender
+            .send(BottomEvent::RemoteError(format!(
+                "Unable to receive data from {}: {}. Retrying in {} seconds.",
+                receiver.description,
+                err,
+                RECONNECT_DELAY.as_secs()
+            )))
+            .is_err()
+        {
+            break;
+        }
+
+        let result = termination_ctrl_cvar
+            .wait_timeout(termination_ctrl_lock.lock().unwrap(), RECONNECT_DELAY);
+        if let Ok(result) = result {
+            if *(result.0) {
+                break;
+            }
+        }
+    })
+}
+
+/// Connects to the server and passes its updates on until either side goes away.  Returns `Ok`
+/// only if the TUI has gone away.
+fn receive_updates<F>(
+    receiver: &Receiver, sender: &mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, to_event: &F,
+) -> io::Result<()>
+where
+    F: Fn(Box<Data>) -> BottomEvent<KeyEvent, MouseEvent>,
+{
+    let mut connection = Connection::open(&receiver.address, &receiver.ssh_command)?;
+    pass_on_updates(&mut connection, receiver, sender, to_event).map_err(|err| {
+        if connection.has_timed_out() {
+            return get_timeout_error();
+        }
+
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => connection.get_process_error().unwrap_or(err),
+            // Timeouts of sockets show up as either, depending on the OS.
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => get_timeout_error(),
+            _ => err,
+        }
+    })
+}
+
+fn get_timeout_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        "the server stopped answering in time",
+    )
+}
+
+fn pass_on_updates<F>(
+    connection: &mut Connection, receiver: &Receiver,
+    sender: &mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, to_event: &F,
+) -> io::Result<()>
+where
+    F: Fn(Box<Data>) -> BottomEvent<KeyEvent, MouseEvent>,
+{
+    protocol::write_message(
+        &mut connection.writer,
+        &Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: receiver.token.clone(),
+        },
+    )?;
+    match protocol::read_handshake_message(&mut connection.reader)? {
+        Message::Accepted => {}
+        Message::Rejected { reason } => {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("the server refused the connection, as {}", reason),
+            ));
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the server did not answer the hello",
+            ));
+        }
+    }
+    connection.set_read_timeout(UPDATE_TIMEOUT)?;
+
+    loop {
+        if let Some(control_receiver) = &receiver.control_receiver {
+            while control_receiver.try_recv().is_ok() {}
+        }
+
+        if let Message::Update { data } = protocol::read_message(&mut connection.reader)? {
+            if sender.send(to_event(data)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+struct Connection {
+    reader: Box<dyn Read + Send>,
+    writer: Box<dyn Write + Send>,
+    /// The socket of the connection, if it is over TCP.
+    stream: Option<TcpStream>,
+    /// The `openssl` or `ssh` process that carries the connection, if any.
+    process: Option<Arc<Mutex<Child>>>,
+    /// When the process last passed on anything from the server, if there is a process.
+    activity: Option<Arc<Mutex<ReadActivity>>>,
+    /// Reads what the process prints, so that it is not held up by a full pipe, and returns the
+    /// line that best explains why it failed, if any.
+    stderr_reader: Option<thread::JoinHandle<Option<String>>>,
+}
+
+/// When anything was last read from the process that carries a connection, and how long it may go
+/// quiet for before it is killed, as its pipes have no timeouts like sockets do.
+struct ReadActivity {
+    last_read: Instant,
+    timeout: Duration,
+    has_timed_out: bool,
+}
+
+/// Notes down when anything is read from `inner`.
+struct WatchedReader<R> {
+    inner: R,
+    activity: Arc<Mutex<ReadActivity>>,
+}
+
+impl<R: Read> Read for WatchedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        if let Ok(mut activity) = self.activity.lock() {
+            activity.last_read = Instant::now();
+        }
+        Ok(len)
+    }
+}
+
+impl Connection {
+    /// Connects over TCP, over TLS if the address starts with `tls://`, or over SSH if it starts
+    /// with `ssh://`.
+    ///
+    /// As bottom has no TLS library of its own, TLS goes through `openssl s_client`, which checks
+    /// the certificate of the server against the system's certificate authorities.  SSH runs
+    /// `ssh_command` on the remote host through `ssh`, and talks to it over its stdin and stdout.
+    fn open(address: &str, ssh_command: &str) -> io::Result<Self> {
+        if let Some(address) = address.strip_prefix("tls://") {
+            let host = address
+                .rsplit_once(':')
+                .map_or(address, |(host, _port)| host)
+                .trim_start_matches('[')
+                .trim_end_matches(']');
+            let verify_flag = if host.parse::<IpAddr>().is_ok() {
+                "-verify_ip"
+            } else {
+                "-verify_hostname"
+            };
+
+            Connection::spawn(
+                "openssl",
+                &[
+                    "s_client",
+                    "-quiet",
+                    "-verify_return_error",
+                    verify_flag,
+                    host,
+                    "-servername",
+                    host,
+                    "-connect",
+                    address,
+                ],
+            )
+        } else if let Some(destination) = address.strip_prefix("ssh://") {
+            // The TUI owns the terminal, so ssh must not ask for a password, and it should notice a
+            // host that stops answering on its own, as the server may be quiet for minutes.
+            Connection::spawn(
+                "ssh",
+                &[
+                    "-T",
+                    "-o",
+                    "BatchMode=yes",
+                    "-o",
+                    "ConnectTimeout=10",
+                    "-o",
+                    "ServerAliveInterval=15",
+                    "-o",
+                    "ServerAliveCountMax=3",
+                    "--",
+                    destination,
+                    ssh_command,
+                ],
+            )
+        } else {
+            let stream = connect_tcp(address)?;
+            stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+            stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+            Ok(Connection {
+                reader: Box::new(BufReader::new(stream.try_clone()?)),
+                writer: Box::new(stream.try_clone()?),
+                stream: Some(stream),
+                process: None,
+                activity: None,
+                stderr_reader: None,
+            })
+        }
+    }
+
+    /// Runs `program`, which carries the connection over its stdin and stdout.
+    fn spawn(program: &str, args: &[&str]) -> io::Result<Self> {
+        let mut process = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                io::Error::new(err.kind(), format!("unable to run {}, {}", program, err))
+            })?;
+
+        match (process.stdin.take(), process.stdout.take()) {
+            (Some(stdin), Some(stdout)) => {
+                let stderr_reader = process
+                    .stderr
+                    .take()
+                    .map(|stderr| thread::spawn(move || get_stderr_reason(stderr)));
+                let process = Arc::new(Mutex::new(process));
+                let activity = Arc::new(Mutex::new(ReadActivity {
+                    last_read: Instant::now(),
+                    timeout: HANDSHAKE_TIMEOUT,
+                    has_timed_out: false,
+                }));
+                spawn_watchdog(process.clone(), activity.clone());
+
+                Ok(Connection {
+                    reader: Box::new(BufReader::new(WatchedReader {
+                        inner: stdout,
+                        activity: activity.clone(),
+                    })),
+                    writer: Box::new(stdin),
+                    stream: None,
+                    process: Some(process),
+                    activity: Some(activity),
+                    stderr_reader,
+                })
+            }
+            _ => {
+                let _ = process.kill();
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    format!("unable to talk to {}", program),
+                ))
+            }
+        }
+    }
+
+    /// Sets how long to wait on the server to send anything before taking the connection to be
+    /// lost.
+    fn set_read_timeout(&self, timeout: Duration) -> io::Result<()> {
+        if let Some(stream) = &self.stream {
+            stream.set_read_timeout(Some(timeout))?;
+        }
+        if let Some(Ok(mut activity)) = self.activity.as_ref().map(|activity| activity.lock()) {
+            activity.timeout = timeout;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the process that carries the connection was killed for going quiet.
+    fn has_timed_out(&self) -> bool {
+        self.activity
+            .as_ref()
+            .and_then(|activity| activity.lock().ok())
+            .is_some_and(|activity| activity.has_timed_out)
+    }
+
+    /// Returns why the process that carries the connection failed, such as the certificate of the
+    /// server not being trusted, once it has closed the connection.
+    fn get_process_error(&mut self) -> Option<io::Error> {
+        let status = self.process.as_ref()?.lock().ok()?.wait().ok()?;
+        if status.success() {
+            return None;
+        }
+
+        let reason = self.stderr_reader.take()?.join().ok()??;
+        Some(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            reason.trim_end_matches('.').to_string(),
+        ))
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        if let Some(Ok(mut process)) = self.process.as_ref().map(|process| process.lock()) {
+            let _ = process.kill();
+            let _ = process.wait();
+        }
+    }
+}
+
+/// Starts a thread that kills `process` once nothing has been read from it for longer than the
+/// timeout in `activity`, which stops once the connection is dropped.
+fn spawn_watchdog(process: Arc<Mutex<Child>>, activity: Arc<Mutex<ReadActivity>>) {
+    thread::spawn(move || loop {
+        thread::sleep(WATCHDOG_INTERVAL);
+        // Only the watchdog is left once the connection is dropped.
+        if Arc::strong_count(&activity) == 1 {
+            break;
+        }
+
+        let has_timed_out = match activity.lock() {
+            Ok(mut activity) if activity.last_read.elapsed() > activity.timeout => {
+                activity.has_timed_out = true;
+                true
+            }
+            Ok(_) => false,
+            Err(_) => break,
+        };
+        if has_timed_out {
+            if let Ok(mut process) = process.lock() {
+                let _ = process.kill();
+            }
+            break;
+        }
+    });
+}
+
+/// Connects to the first address that `address` resolves to that accepts the connection in time.
+fn connect_tcp(address: &str) -> io::Result<TcpStream> {
+    let mut last_err = None;
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, HANDSHAKE_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} does not resolve to any address", address),
+        )
+    }))
+}
+
+/// Reads all of `stderr`, and returns the line that best explains why the process failed: the
+/// first certificate error of `openssl`, or else the last line that is not empty.
+fn get_stderr_reason<R: Read>(stderr: R) -> Option<String> {
+    let mut reason: Option<String> = None;
+    let mut take_line = |line: &[u8]| {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        let has_verify_error = reason
+            .as_deref()
+            .is_some_and(|reason| reason.starts_with("verify error:"));
+        if !line.is_empty() && !has_verify_error {
+            reason = Some(line.to_string());
+        }
+    };
+
+    // Lines are cut short, as the remote host can print anything over SSH.
+    let mut line = Vec::new();
+    for byte in BufReader::new(stderr).bytes() {
+        match byte {
+            Ok(b'\n') => {
+                take_line(&line);
+                line.clear();
+            }
+            Ok(byte) if line.len() < MAX_STDERR_LINE_LEN => line.push(byte),
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    take_line(&line);
+
+    reason
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_stderr_reason() {
+        let stderr = "depth=0 CN = myserver\n\
+                      verify error:num=18:self-signed certificate\n\
+                      40F7:error:0A000086:SSL routines::certificate verify failed\n";
+        assert_eq!(
+            get_stderr_reason(stderr.as_bytes()).as_deref(),
+            Some("verify error:num=18:self-signed certificate")
+        );
+
+        let stderr = "Permission denied (publickey).\n\n";
+        assert_eq!(
+            get_stderr_reason(stderr.as_bytes()).as_deref(),
+            Some("Permission denied (publickey).")
+        );
+
+        let stderr = "x".repeat(MAX_STDERR_LINE_LEN * 2);
+        assert_eq!(
+            get_stderr_reason(stderr.as_bytes()).map(|reason| reason.len()),
+            Some(MAX_STDERR_LINE_LEN)
+        );
+        assert_eq!(get_stderr_reason("".as_bytes()), None);
+    }
+}
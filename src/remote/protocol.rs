@@ -0,0 +1,189 @@
+//! The messages that a remote server and client exchange.
+//!
+//! Every message is a TOML document, preceded by its length in bytes as a big-endian `u32`.  The
+//! client starts by sending a [`Message::Hello`], to which the server answers with either a
+//! [`Message::Accepted`] or a [`Message::Rejected`].  Once accepted, the server sends a
+//! [`Message::Update`] for every harvest until either side hangs up.
+
+use std::io::{self, Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::data_harvester::Data;
+
+/// The version of the protocol, which the client and server have to agree on.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The largest message that is read, so that a bad length does not exhaust the memory.
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// The largest hello or answer to one that is read, which is far smaller, as anyone that can
+/// connect can send them.
+const MAX_HANDSHAKE_LEN: usize = 4 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Hello { version: u32, token: Option<String> },
+    Accepted,
+    Rejected { reason: String },
+    Update { data: Box<Data> },
+}
+
+/// Encodes a message, along with its length, so that it can be sent as is to any number of peers.
+pub fn encode_message(message: &Message) -> io::Result<Vec<u8>> {
+    let text = toml::Value::try_from(message)
+        .and_then(|value| toml::to_string(&value))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let mut bytes = Vec::with_capacity(text.len() + 4);
+    bytes.extend_from_slice(&(text.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(text.as_bytes());
+    Ok(bytes)
+}
+
+pub fn write_message<W: Write>(writer: &mut W, message: &Message) -> io::Result<()> {
+    writer.write_all(&encode_message(message)?)?;
+    writer.flush()
+}
+
+/// Reads the next message, blocking until all of it has arrived.
+pub fn read_message<R: Read>(reader: &mut R) -> io::Result<Message> {
+    read_message_up_to(reader, MAX_MESSAGE_LEN)
+}
+
+/// Reads the next message of the handshake, a [`Message::Hello`] or the answer to one, blocking
+/// until all of it has arrived.
+pub fn read_handshake_message<R: Read>(reader: &mut R) -> io::Result<Message> {
+    read_message_up_to(reader, MAX_HANDSHAKE_LEN)
+}
+
+fn read_message_up_to<R: Read>(reader: &mut R, max_len: usize) -> io::Result<Message> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes is too large", len),
+        ));
+    }
+
+    let mut text = vec![0; len];
+    reader.read_exact(&mut text)?;
+    let text =
+        String::from_utf8(text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    toml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::data_harvester::{
+        cpu::CpuData,
+        disks::IoData,
+        network::NetworkHarvest,
+        processes::{IoPriority, IoPriorityClass, ProcessHarvest},
+    };
+
+    #[test]
+    fn test_message_round_trip() {
+        let data = Data {
+            cpu: Some(vec![CpuData {
+                cpu_prefix: "AVG".to_string(),
+                cpu_usage: 12.5,
+                ..CpuData::default()
+            }]),
+            load_avg: Some([0.5, 0.25, f32::NAN]),
+            network: Some(NetworkHarvest {
+                rx: 1024,
+                total_rx: 4096,
+                ..NetworkHarvest::default()
+            }),
+            list_of_processes: Some(vec![ProcessHarvest {
+                pid: 42,
+                name: "btm".to_string(),
+                process_state_char: 'S',
+                io_priority: Some(IoPriority {
+                    class: IoPriorityClass::BestEffort,
+                    level: 4,
+                }),
+                cpu_time: Some(std::time::Duration::from_millis(1500)),
+                ..ProcessHarvest::default()
+            }]),
+            io: Some(
+                vec![
+                    (
+                        "sda".to_string(),
+                        Some(IoData {
+                            read_bytes: 1,
+                            write_bytes: 2,
+                            times: None,
+                        }),
+                    ),
+                    ("sdb".to_string(), None),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            ..Data::default()
+        };
+
+        let bytes = encode_message(&Message::Update {
+            data: Box::new(data),
+        })
+        .unwrap();
+        let data = match read_message(&mut bytes.as_slice()).unwrap() {
+            Message::Update { data } => data,
+            message => panic!("unexpected message {:?}", message),
+        };
+
+        assert_eq!(data.cpu.unwrap()[0].cpu_usage, 12.5);
+        let load_avg = data.load_avg.unwrap();
+        assert_eq!(load_avg[..2], [0.5, 0.25]);
+        assert!(load_avg[2].is_nan());
+        assert_eq!(data.network.unwrap().total_rx, 4096);
+        let process = &data.list_of_processes.unwrap()[0];
+        assert_eq!(process.name, "btm");
+        assert_eq!(process.process_state_char, 'S');
+        assert_eq!(process.io_priority.unwrap().level, 4);
+        assert_eq!(
+            process.cpu_time,
+            Some(std::time::Duration::from_millis(1500))
+        );
+        let io = data.io.unwrap();
+        assert_eq!(io["sda"].as_ref().unwrap().write_bytes, 2);
+        assert!(data.memory.is_none());
+    }
+
+    #[test]
+    fn test_read_message_rejects_garbage() {
+        let mut bytes = (u32::MAX).to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"garbage");
+        assert!(read_message(&mut bytes.as_slice()).is_err());
+
+        let bytes = [0, 0, 0, 3, b'a', b'=', b'1'];
+        assert!(read_message(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_read_handshake_message() {
+        let bytes = encode_message(&Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: Some("secret".to_string()),
+        })
+        .unwrap();
+        assert!(matches!(
+            read_handshake_message(&mut bytes.as_slice()).unwrap(),
+            Message::Hello { version: PROTOCOL_VERSION, token: Some(token) } if token == "secret"
+        ));
+
+        let bytes = encode_message(&Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: Some("a".repeat(MAX_HANDSHAKE_LEN)),
+        })
+        .unwrap();
+        assert!(read_handshake_message(&mut bytes.as_slice()).is_err());
+        assert!(read_message(&mut bytes.as_slice()).is_ok());
+    }
+}
@@ -0,0 +1,397 @@
+//! Serving every harvest to the remote clients that connect, for `--serve`, or to the single
+//! client on the other end of stdin and stdout, for `--serve_stdio`.
+
+use std::{
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::protocol::{self, Message, PROTOCOL_VERSION};
+use crate::app::data_harvester::Data;
+
+/// How long a client has to say hello once it connects.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait on a client that stops reading before hanging up on it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many clients can be connected at once, counting those that have yet to say hello, past
+/// which clients are turned away.
+const MAX_CLIENTS: usize = 16;
+
+/// How many updates to hold for a client that is slow to read them, past which updates are
+/// dropped for it.
+const MAX_PENDING_UPDATES: usize = 4;
+
+type ClientList = Arc<Mutex<Vec<mpsc::SyncSender<Arc<Vec<u8>>>>>>;
+
+/// The certificate and key that the server presents to clients over TLS.
+#[derive(Clone, Debug)]
+pub struct ServerTlsOptions {
+    /// The certificate in PEM, which may be followed by the rest of its chain.
+    pub cert_path: PathBuf,
+    /// The private key of the certificate in PEM, which must not be encrypted.
+    pub key_path: PathBuf,
+}
+
+/// Sends the data passed to [`RemoteServer::publish`] to every client that is connected.
+pub struct RemoteServer {
+    clients: ClientList,
+    is_closed: Arc<AtomicBool>,
+}
+
+impl RemoteServer {
+    /// Starts listening at `address` on a thread of its own, which lives for as long as bottom.
+    /// Clients have to send `token` to be accepted, if it is set, and have to speak TLS if `tls` is
+    /// set.
+    ///
+    /// As bottom has no TLS library of its own, TLS goes through an `openssl s_server` for each
+    /// client, so the certificate and key are checked with `openssl` up front.
+    pub fn start(
+        address: &str, token: Option<String>, tls: Option<ServerTlsOptions>,
+    ) -> io::Result<Self> {
+        if let Some(tls) = &tls {
+            check_tls_options(tls)?;
+        }
+        let listener = TcpListener::bind(address)?;
+        let clients: ClientList = Arc::new(Mutex::new(Vec::new()));
+
+        let thread_clients = clients.clone();
+        thread::spawn(move || {
+            // Only this thread adds to the count, so it cannot go past the limit.
+            let client_count = Arc::new(AtomicUsize::new(0));
+            for stream in listener.incoming().flatten() {
+                if client_count.load(Ordering::SeqCst) >= MAX_CLIENTS {
+                    // A client over TLS could not read the reason, so it is just hung up on.
+                    if tls.is_none() {
+                        reject_tcp_client(stream, "the server has too many clients");
+                    }
+                    continue;
+                }
+
+                client_count.fetch_add(1, Ordering::SeqCst);
+                let client_count = client_count.clone();
+                let clients = thread_clients.clone();
+                let token = token.clone();
+                let tls = tls.clone();
+                thread::spawn(move || {
+                    // A client that goes away is not worth reporting.
+                    let _ = match &tls {
+                        Some(tls) => handle_tls_client(stream, tls, token.as_deref(), &clients),
+                        None => handle_tcp_client(stream, token.as_deref(), &clients),
+                    };
+                    client_count.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        Ok(RemoteServer {
+            clients,
+            is_closed: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Serves the client on the other end of stdin and stdout, such as `btm --ssh` through `ssh`,
+    /// which has already authenticated it.  The server is closed once the client goes away.
+    pub fn start_stdio() -> Self {
+        let clients: ClientList = Arc::new(Mutex::new(Vec::new()));
+        let is_closed = Arc::new(AtomicBool::new(false));
+
+        let thread_clients = clients.clone();
+        let thread_is_closed = is_closed.clone();
+        thread::spawn(move || {
+            if let Ok(true) = accept_client(&mut io::stdin(), &mut io::stdout(), None) {
+                let _ = serve_client(&mut io::stdout(), &thread_clients);
+            }
+            thread_is_closed.store(true, Ordering::SeqCst);
+        });
+
+        RemoteServer { clients, is_closed }
+    }
+
+    /// Returns whether there will never be a client to send data to again.
+    pub fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst)
+    }
+
+    /// Sends `data` to every connected client, and forgets the ones that have hung up.
+    pub fn publish(&self, data: &Data) {
+        let mut clients = match self.clients.lock() {
+            Ok(clients) => clients,
+            Err(_) => return,
+        };
+        if clients.is_empty() {
+            return;
+        }
+
+        let message = match protocol::encode_message(&Message::Update {
+            data: Box::new(data.clone()),
+        }) {
+            Ok(message) => Arc::new(message),
+            Err(_) => return,
+        };
+        clients.retain(|client| match client.try_send(message.clone()) {
+            Ok(()) | Err(mpsc::TrySendError::Full(_)) => true,
+            Err(mpsc::TrySendError::Disconnected(_)) => false,
+        });
+    }
+}
+
+/// Tells the client why it is turned away, without waiting on it, so that accepting other clients
+/// is not held up.
+fn reject_tcp_client(stream: TcpStream, reason: &str) {
+    if stream.set_nonblocking(true).is_ok() {
+        let _ = protocol::write_message(
+            &mut &stream,
+            &Message::Rejected {
+                reason: reason.to_string(),
+            },
+        );
+    }
+}
+
+fn handle_tcp_client(
+    stream: TcpStream, token: Option<&str>, clients: &ClientList,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    if accept_client(&mut &stream, &mut &stream, token)? {
+        serve_client(&mut &stream, clients)?;
+    }
+    Ok(())
+}
+
+/// Runs `openssl s_server` on a unix socket in a directory of its own, and passes the bytes of the
+/// client between it and the socket, so that the client is served in plain text over the stdin
+/// and stdout of `openssl`.
+#[cfg(unix)]
+fn handle_tls_client(
+    stream: TcpStream, tls: &ServerTlsOptions, token: Option<&str>, clients: &ClientList,
+) -> io::Result<()> {
+    use std::{os::unix::net::UnixStream, time::Instant};
+
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let socket_dir = SocketDir::create()?;
+    let socket_path = socket_dir.0.join("tls.sock");
+    let mut process = Command::new("openssl")
+        .args(["s_server", "-quiet", "-naccept", "1", "-cert"])
+        .arg(&tls.cert_path)
+        .arg("-key")
+        .arg(&tls.key_path)
+        .arg("-unix")
+        .arg(&socket_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let pipes = process.stdin.take().zip(process.stdout.take());
+    let process = Arc::new(Mutex::new(process));
+
+    // openssl creates the socket once it has loaded the certificate, so wait on it for a while.
+    let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+    let socket = loop {
+        match UnixStream::connect(&socket_path) {
+            Ok(socket) => break Ok(socket),
+            Err(err) if Instant::now() >= deadline => break Err(err),
+            Err(_) => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+
+    let result = match (socket, pipes) {
+        (Ok(socket), Some((stdin, stdout))) => {
+            serve_tls_client(stream, socket, stdin, stdout, &process, token, clients)
+        }
+        (Err(err), _) => Err(err),
+        (_, None) => Err(io::Error::other("unable to talk to openssl")),
+    };
+
+    // Once its stdin is closed, openssl sends what is left, like a rejection, and then stops.
+    if let Ok(mut process) = process.lock() {
+        if result.is_err() {
+            let _ = process.kill();
+        }
+        let _ = process.wait();
+    }
+    result
+}
+
+/// Relays the bytes of the client to and from the socket of `openssl`, and serves the client over
+/// its stdin and stdout.
+#[cfg(unix)]
+fn serve_tls_client(
+    stream: TcpStream, socket: std::os::unix::net::UnixStream, mut stdin: std::process::ChildStdin,
+    mut stdout: std::process::ChildStdout, process: &Arc<Mutex<std::process::Child>>,
+    token: Option<&str>, clients: &ClientList,
+) -> io::Result<()> {
+    use std::net::Shutdown;
+
+    let mut from_client = stream.try_clone()?;
+    let mut to_openssl = socket.try_clone()?;
+    thread::spawn(move || {
+        let _ = io::copy(&mut from_client, &mut to_openssl);
+        let _ = to_openssl.shutdown(Shutdown::Write);
+    });
+    let mut from_openssl = socket;
+    let mut to_client = stream;
+    thread::spawn(move || {
+        let _ = io::copy(&mut from_openssl, &mut to_client);
+        let _ = to_client.shutdown(Shutdown::Both);
+    });
+
+    // Hang up on a client that has not said hello in time, by stopping openssl.
+    let (accepted_sender, accepted_receiver) = mpsc::channel::<()>();
+    let watched_process = process.clone();
+    thread::spawn(move || {
+        if let Err(mpsc::RecvTimeoutError::Timeout) =
+            accepted_receiver.recv_timeout(HANDSHAKE_TIMEOUT)
+        {
+            if let Ok(mut process) = watched_process.lock() {
+                let _ = process.kill();
+            }
+        }
+    });
+
+    if accept_client(&mut stdout, &mut stdin, token)? {
+        let _ = accepted_sender.send(());
+        serve_client(&mut stdin, clients)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn handle_tls_client(
+    _stream: TcpStream, _tls: &ServerTlsOptions, _token: Option<&str>, _clients: &ClientList,
+) -> io::Result<()> {
+    Err(get_tls_unsupported_error())
+}
+
+fn get_tls_unsupported_error() -> io::Error {
+    io::Error::other("serving over TLS is not supported on this platform")
+}
+
+/// A directory that only this user can enter, which is removed along with what is in it once it
+/// is dropped, so that no one else can connect to the socket of `openssl` before bottom does.
+#[cfg(unix)]
+struct SocketDir(PathBuf);
+
+#[cfg(unix)]
+impl SocketDir {
+    fn create() -> io::Result<Self> {
+        use std::os::unix::fs::DirBuilderExt;
+
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "btm-tls-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::DirBuilder::new().mode(0o700).create(&path)?;
+        Ok(SocketDir(path))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SocketDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Checks that `openssl` is installed and can load the certificate and key, so that a mistake
+/// shows up when the server starts rather than when a client connects.
+fn check_tls_options(tls: &ServerTlsOptions) -> io::Result<()> {
+    if cfg!(not(unix)) {
+        return Err(get_tls_unsupported_error());
+    }
+
+    let checks: [(&str, &[&str], &PathBuf); 2] = [
+        ("certificate", &["x509", "-noout", "-in"], &tls.cert_path),
+        (
+            "key",
+            &["pkey", "-noout", "-passin", "pass:", "-in"],
+            &tls.key_path,
+        ),
+    ];
+    for (name, args, path) in checks {
+        let status = Command::new("openssl")
+            .args(args)
+            .arg(path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|err| io::Error::new(err.kind(), format!("unable to run openssl, {}", err)))?;
+        if !status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "openssl is unable to load the {} at '{}'",
+                    name,
+                    path.display()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the hello of the client and answers it, returning whether the client is accepted.
+fn accept_client<R: Read, W: Write>(
+    reader: &mut R, writer: &mut W, token: Option<&str>,
+) -> io::Result<bool> {
+    let rejection = match protocol::read_handshake_message(reader)? {
+        Message::Hello { version, .. } if version != PROTOCOL_VERSION => Some(format!(
+            "the server speaks version {} of the protocol, but the client speaks version {}",
+            PROTOCOL_VERSION, version
+        )),
+        Message::Hello {
+            token: client_token,
+            ..
+        } => match token {
+            Some(token) if !is_token_valid(token, client_token.as_deref().unwrap_or_default()) => {
+                Some("the token is wrong".to_string())
+            }
+            _ => None,
+        },
+        _ => Some("the client did not say hello".to_string()),
+    };
+    if let Some(reason) = rejection {
+        protocol::write_message(writer, &Message::Rejected { reason })?;
+        return Ok(false);
+    }
+    protocol::write_message(writer, &Message::Accepted)?;
+    Ok(true)
+}
+
+/// Sends every update to an accepted client until it goes away.
+fn serve_client<W: Write>(writer: &mut W, clients: &ClientList) -> io::Result<()> {
+    let (sender, receiver) = mpsc::sync_channel(MAX_PENDING_UPDATES);
+    if let Ok(mut clients) = clients.lock() {
+        clients.push(sender);
+    }
+    for message in receiver {
+        writer.write_all(&message)?;
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Compares the tokens in constant time, so that how long it takes does not give away how much of
+/// a guess was right.
+fn is_token_valid(token: &str, client_token: &str) -> bool {
+    token.len() == client_token.len()
+        && token
+            .bytes()
+            .zip(client_token.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
@@ -22,6 +22,9 @@ pub enum BottomError {
     /// An error to represent generic errors.
     #[error("Generic error, {0}")]
     GenericError(String),
+    /// An error when the current user does not have the permissions to do something.
+    #[error("Permission error, {0}")]
+    PermissionError(String),
     /// An error to represent errors with fern.
     #[error("Fern error, {0}")]
     FernError(String),
@@ -49,6 +52,7 @@ impl From<std::io::Error> for BottomError {
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 impl From<heim::Error> for BottomError {
     fn from(err: heim::Error) -> Self {
         BottomError::InvalidHeim(err.to_string())
@@ -119,3 +119,73 @@ pub fn get_ordering<T: std::cmp::PartialOrd>(
         None => Ordering::Equal,
     }
 }
+
+/// Returns the local UTC offset at the given Unix timestamp, falling back to UTC if it can't be
+/// determined.
+pub fn get_local_offset(timestamp: i64) -> time::UtcOffset {
+    #[cfg(target_family = "unix")]
+    {
+        let timestamp = timestamp as libc::time_t;
+        // SAFETY: localtime_r only writes into the tm struct we give it, and returns null on failure.
+        let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+        if unsafe { libc::localtime_r(&timestamp, &mut tm) }.is_null() {
+            return time::UtcOffset::UTC;
+        }
+
+        time::UtcOffset::from_whole_seconds(tm.tm_gmtoff as i32).unwrap_or(time::UtcOffset::UTC)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use winapi::um::timezoneapi::{GetTimeZoneInformation, TIME_ZONE_INFORMATION};
+
+        // This uses the current offset, as Windows doesn't make it easy to get it at other times.
+        let _ = timestamp;
+        let mut info: TIME_ZONE_INFORMATION = unsafe { std::mem::zeroed() };
+        let bias = match unsafe { GetTimeZoneInformation(&mut info) } {
+            // TIME_ZONE_ID_DAYLIGHT
+            2 => info.Bias + info.DaylightBias,
+            // TIME_ZONE_ID_INVALID
+            u32::MAX => return time::UtcOffset::UTC,
+            _ => info.Bias + info.StandardBias,
+        };
+
+        // The bias is in minutes, and is what is added to the local time to get UTC.
+        time::UtcOffset::from_whole_seconds(-bias * 60).unwrap_or(time::UtcOffset::UTC)
+    }
+    #[cfg(not(any(target_family = "unix", target_os = "windows")))]
+    {
+        let _ = timestamp;
+        time::UtcOffset::UTC
+    }
+}
+
+/// Returns the current local time in a form that can be used in file names, such as
+/// `20240131-235959`.
+pub fn get_file_name_time() -> String {
+    let utc_time = time::OffsetDateTime::now_utc();
+    utc_time
+        .to_offset(get_local_offset(utc_time.unix_timestamp()))
+        .format(&time::macros::format_description!(
+            "[year][month][day]-[hour][minute][second]"
+        ))
+        .unwrap_or_default()
+}
+
+/// Quotes `text` as a value in a curl config, such as one passed on stdin with `--config -`, which
+/// takes the same escapes as C strings.
+pub fn quote_curl_config(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
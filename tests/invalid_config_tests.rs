@@ -38,6 +38,28 @@ fn test_invalid_layout_widget_type() {
         .stderr(predicate::str::contains("invalid widget name"));
 }
 
+#[test]
+fn test_invalid_layout_widget_host() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_layout_widget_host.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "only cpu and mem widgets can show a remote host",
+        ));
+}
+
+#[test]
+fn test_unknown_layout_widget_host() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/unknown_layout_widget_host.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("there is no [[host]] called that"));
+}
+
 /// This test isn't really needed as this is technically covered by TOML spec.
 /// However, I feel like it's worth checking anyways - not like it takes long.
 #[test]
@@ -143,3 +165,137 @@ fn test_invalid_default_widget_count() {
         .failure()
         .stderr(predicate::str::contains("invalid number"));
 }
+
+#[test]
+fn test_invalid_colour_scheme() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_colour_scheme.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("neither a built-in color scheme"));
+}
+
+#[test]
+fn test_invalid_colour_theme() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_colour_theme.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid hex colour"));
+}
+
+#[test]
+fn test_invalid_graph_gradient() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_graph_gradient.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least two colours"));
+}
+
+#[test]
+fn test_invalid_graph_style() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_graph_style.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid graph style"));
+}
+
+#[test]
+fn test_invalid_history_export_format() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_history_export_format.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "is an invalid history export format",
+        ));
+}
+
+#[test]
+fn test_invalid_snapshot_format() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_snapshot_format.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid snapshot format"));
+}
+
+#[test]
+fn test_invalid_alert_metric() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_alert_metric.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid alert metric"));
+}
+
+#[test]
+fn test_invalid_alert_severity() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_alert_severity.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid alert severity"));
+}
+
+#[test]
+fn test_invalid_network_y_max() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_network_y_max.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid network y-axis maximum"));
+}
+
+#[test]
+fn test_invalid_memory_y_max() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_memory_y_max.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid memory y-axis maximum"));
+}
+
+#[test]
+fn test_invalid_basic_cpu_rows() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_basic_cpu_rows.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your basic CPU row count to be at least 1",
+        ));
+}
+
+#[test]
+fn test_invalid_cpu_grouping() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_cpu_grouping.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid CPU grouping"));
+}
+
+#[test]
+fn test_invalid_influxdb_url() {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_influxdb_url.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid InfluxDB URL"));
+}